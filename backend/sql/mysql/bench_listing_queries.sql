@@ -0,0 +1,177 @@
+-- Thought Manifold MySQL benchmark: review center and admin user listings
+--
+-- Seeds 10,000 users, 32,000 posts, 30,000 comments, 36,000 AI reviews and
+-- 12,000 editorial decisions, then runs EXPLAIN ANALYZE on the previous and
+-- current form of each listing query. Compare the top-level "actual time"
+-- of each pair. Everything runs in one transaction that is rolled back, so
+-- the database is left as it was; still, use a development database.
+--
+--   mysql -u root -p < backend/sql/mysql/bench_listing_queries.sql
+
+USE thought_manifold;
+
+START TRANSACTION;
+
+SET SESSION cte_max_recursion_depth = 20000;
+SET @paper_category := (SELECT id FROM post_categories WHERE code = 'paper');
+
+INSERT INTO users (username, email, created_at)
+WITH RECURSIVE seq (n) AS (
+  SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 10000
+)
+SELECT
+  CONCAT('bench-user-', n),
+  CONCAT('bench-user-', n, '@bench.invalid'),
+  NOW(6) - INTERVAL n MINUTE
+FROM seq;
+
+SET @bench_author := (SELECT id FROM users WHERE username = 'bench-user-1');
+
+-- One prolific author with 12,000 papers under revision.
+INSERT INTO posts (title, content, category_id, author_id, paper_status, created_at, updated_at)
+WITH RECURSIVE seq (n) AS (
+  SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 12000
+)
+SELECT
+  CONCAT('Bench paper ', n),
+  'Bench content',
+  @paper_category,
+  @bench_author,
+  'revision',
+  NOW(6) - INTERVAL n MINUTE,
+  NOW(6) - INTERVAL n SECOND
+FROM seq;
+
+-- Two posts and three comments for every bench user.
+INSERT INTO posts (title, content, category_id, author_id, created_at)
+SELECT CONCAT('Bench post ', u.id, '-', k.n), 'Bench content', @paper_category, u.id, NOW(6)
+FROM users u
+JOIN (SELECT 1 AS n UNION ALL SELECT 2) k
+WHERE u.username LIKE 'bench-user-%';
+
+SET @bench_post := (SELECT MIN(id) FROM posts WHERE author_id = @bench_author);
+
+INSERT INTO comments (post_id, author_id, content, created_at)
+SELECT @bench_post, u.id, CONCAT('Bench comment ', k.n), NOW(6)
+FROM users u
+JOIN (SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3) k
+WHERE u.username LIKE 'bench-user-%';
+
+-- Three AI reviews and one revision request per paper.
+INSERT INTO post_ai_reviews (
+  post_id, status_id, trigger_id, decision_id, model, prompt_version, overall_score,
+  created_at, completed_at
+)
+SELECT
+  p.id, 2, k.n, 2, 'bench-model', 'bench', 3,
+  p.created_at + INTERVAL k.n HOUR, p.created_at + INTERVAL k.n HOUR
+FROM posts p
+JOIN (SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3) k
+WHERE p.author_id = @bench_author AND p.paper_status = 'revision';
+
+INSERT INTO editorial_decisions (post_id, decision_id, previous_status, new_status, revision_due_at)
+SELECT p.id, 2, 'submitted', 'revision', NOW(6) + INTERVAL 14 DAY
+FROM posts p
+WHERE p.author_id = @bench_author AND p.paper_status = 'revision';
+
+-- Review center, previous: two correlated subqueries per post.
+EXPLAIN ANALYZE
+SELECT
+  p.id, p.title, c.code, lr.id, s.code, led.revision_due_at
+FROM posts p
+JOIN post_categories c ON c.id = p.category_id
+LEFT JOIN post_ai_reviews lr ON lr.id = (
+  SELECT r2.id
+  FROM post_ai_reviews r2
+  WHERE r2.post_id = p.id
+  ORDER BY r2.created_at DESC, r2.id DESC
+  LIMIT 1
+)
+LEFT JOIN editorial_decisions led ON led.id = (
+  SELECT MAX(ed2.id) FROM editorial_decisions ed2 WHERE ed2.post_id = p.id
+)
+LEFT JOIN ai_review_statuses s ON s.id = lr.status_id
+WHERE p.author_id = @bench_author AND c.code = 'paper'
+ORDER BY p.updated_at DESC, p.created_at DESC
+LIMIT 20 OFFSET 0;
+
+-- Review center, current: page first, then one windowed and one grouped pass.
+EXPLAIN ANALYZE
+WITH page AS (
+  SELECT p.id, p.title, p.category_id, p.created_at, p.updated_at
+  FROM posts p
+  JOIN post_categories c ON c.id = p.category_id
+  WHERE p.author_id = @bench_author AND c.code = 'paper'
+  ORDER BY p.updated_at DESC, p.created_at DESC
+  LIMIT 20 OFFSET 0
+),
+ranked_reviews AS (
+  SELECT
+    r.id, r.post_id, r.status_id,
+    ROW_NUMBER() OVER (PARTITION BY r.post_id ORDER BY r.created_at DESC, r.id DESC) AS review_rank
+  FROM post_ai_reviews r
+  JOIN page ON page.id = r.post_id
+),
+latest_decisions AS (
+  SELECT ed.post_id, MAX(ed.id) AS id
+  FROM editorial_decisions ed
+  JOIN page ON page.id = ed.post_id
+  GROUP BY ed.post_id
+)
+SELECT
+  p.id, p.title, c.code, lr.id, s.code, led.revision_due_at
+FROM page p
+JOIN post_categories c ON c.id = p.category_id
+LEFT JOIN ranked_reviews lr ON lr.post_id = p.id AND lr.review_rank = 1
+LEFT JOIN latest_decisions ld ON ld.post_id = p.id
+LEFT JOIN editorial_decisions led ON led.id = ld.id
+LEFT JOIN ai_review_statuses s ON s.id = lr.status_id
+ORDER BY p.updated_at DESC, p.created_at DESC;
+
+-- Admin users, previous: counts grouped over the whole posts and comments tables.
+EXPLAIN ANALYZE
+SELECT
+  u.*,
+  CAST(COALESCE(pc.post_count, 0) AS SIGNED) AS post_count,
+  CAST(COALESCE(cc.comment_count, 0) AS SIGNED) AS comment_count
+FROM users u
+LEFT JOIN (
+  SELECT author_id, COUNT(*) AS post_count FROM posts GROUP BY author_id
+) pc ON pc.author_id = u.id
+LEFT JOIN (
+  SELECT author_id, COUNT(*) AS comment_count FROM comments GROUP BY author_id
+) cc ON cc.author_id = u.id
+WHERE u.deleted_at IS NULL
+ORDER BY u.created_at DESC, u.id DESC
+LIMIT 20 OFFSET 0;
+
+-- Admin users, current: page first, then counts for the page's users only.
+EXPLAIN ANALYZE
+WITH page AS (
+  SELECT u.id
+  FROM users u
+  WHERE u.deleted_at IS NULL
+  ORDER BY u.created_at DESC, u.id DESC
+  LIMIT 20 OFFSET 0
+)
+SELECT
+  u.*,
+  CAST(COALESCE(pc.post_count, 0) AS SIGNED) AS post_count,
+  CAST(COALESCE(cc.comment_count, 0) AS SIGNED) AS comment_count
+FROM page
+JOIN users u ON u.id = page.id
+LEFT JOIN (
+  SELECT p.author_id, COUNT(*) AS post_count
+  FROM posts p
+  JOIN page ON page.id = p.author_id
+  GROUP BY p.author_id
+) pc ON pc.author_id = u.id
+LEFT JOIN (
+  SELECT cm.author_id, COUNT(*) AS comment_count
+  FROM comments cm
+  JOIN page ON page.id = cm.author_id
+  GROUP BY cm.author_id
+) cc ON cc.author_id = u.id
+ORDER BY u.created_at DESC, u.id DESC;
+
+ROLLBACK;
@@ -0,0 +1,14 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS account_events (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  event_type VARCHAR(32) NOT NULL,
+  auth_method VARCHAR(16) NULL,
+  ip_address VARCHAR(64) NULL,
+  user_agent VARCHAR(512) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_account_events_user_created (user_id, created_at),
+  CONSTRAINT chk_account_events_event_type CHECK (event_type IN ('login', 'login_failed', 'username_change')),
+  CONSTRAINT fk_account_events_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
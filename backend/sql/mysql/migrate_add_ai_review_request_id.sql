@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+SET @has_post_ai_reviews_request_id := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_ai_reviews'
+    AND column_name = 'request_id'
+);
+SET @sql_post_ai_reviews_request_id := IF(
+  @has_post_ai_reviews_request_id = 0,
+  "ALTER TABLE post_ai_reviews ADD COLUMN request_id VARCHAR(64) NULL AFTER error_message",
+  "SELECT 1"
+);
+PREPARE stmt_post_ai_reviews_request_id FROM @sql_post_ai_reviews_request_id;
+EXECUTE stmt_post_ai_reviews_request_id;
+DEALLOCATE PREPARE stmt_post_ai_reviews_request_id;
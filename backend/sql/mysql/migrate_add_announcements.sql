@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS announcements (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  message TEXT NOT NULL,
+  severity VARCHAR(16) NOT NULL DEFAULT 'info',
+  audience VARCHAR(16) NOT NULL DEFAULT 'all',
+  starts_at DATETIME(6) NOT NULL,
+  ends_at DATETIME(6) NULL,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_announcements_window (starts_at, ends_at),
+  CONSTRAINT chk_announcements_severity CHECK (severity IN ('info', 'warning', 'critical')),
+  CONSTRAINT chk_announcements_audience CHECK (audience IN ('all', 'users', 'admins')),
+  CONSTRAINT fk_announcements_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,28 @@
+USE thought_manifold;
+
+SET @has_review_comments_is_anonymous := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'is_anonymous'
+);
+SET @sql_review_comments_is_anonymous := IF(
+  @has_review_comments_is_anonymous = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN is_anonymous BOOLEAN NOT NULL DEFAULT FALSE AFTER deleted_at",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_is_anonymous FROM @sql_review_comments_is_anonymous;
+EXECUTE stmt_review_comments_is_anonymous;
+DEALLOCATE PREPARE stmt_review_comments_is_anonymous;
+
+CREATE TABLE IF NOT EXISTS review_comment_pseudonyms (
+  post_id BIGINT NOT NULL,
+  user_id BIGINT NOT NULL,
+  pseudonym_number INT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (post_id, user_id),
+  UNIQUE KEY uq_review_comment_pseudonyms_post_number (post_id, pseudonym_number),
+  CONSTRAINT fk_review_comment_pseudonyms_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_review_comment_pseudonyms_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,20 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS audit_log (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  actor_id BIGINT NULL,
+  actor_username VARCHAR(191) NULL,
+  action VARCHAR(64) NOT NULL,
+  target_type VARCHAR(32) NOT NULL,
+  target_id BIGINT NULL,
+  ip_address VARCHAR(64) NULL,
+  user_agent VARCHAR(512) NULL,
+  before_snapshot JSON NULL,
+  after_snapshot JSON NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_audit_log_created_at (created_at),
+  INDEX idx_audit_log_actor (actor_id, created_at),
+  INDEX idx_audit_log_action (action, created_at),
+  INDEX idx_audit_log_target (target_type, target_id, created_at),
+  CONSTRAINT fk_audit_log_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,15 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS author_metrics_cache (
+  user_id BIGINT PRIMARY KEY,
+  g_index BIGINT NOT NULL DEFAULT 0,
+  h_index BIGINT NOT NULL DEFAULT 0,
+  i10_index BIGINT NOT NULL DEFAULT 0,
+  total_citations BIGINT NOT NULL DEFAULT 0,
+  paper_count BIGINT NOT NULL DEFAULT 0,
+  metric_version VARCHAR(16) NOT NULL,
+  computed_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_author_metrics_cache_g_index (g_index),
+  INDEX idx_author_metrics_cache_h_index (h_index),
+  CONSTRAINT fk_author_metrics_cache_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
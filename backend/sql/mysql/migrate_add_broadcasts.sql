@@ -0,0 +1,33 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS broadcasts (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  subject VARCHAR(255) NOT NULL,
+  body TEXT NOT NULL,
+  audience VARCHAR(16) NOT NULL DEFAULT 'all',
+  active_within_days INT UNSIGNED NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'scheduled',
+  scheduled_at DATETIME(6) NOT NULL,
+  recipient_count INT UNSIGNED NOT NULL DEFAULT 0,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  started_at DATETIME(6) NULL,
+  finished_at DATETIME(6) NULL,
+  INDEX idx_broadcasts_status_scheduled (status, scheduled_at),
+  CONSTRAINT chk_broadcasts_status CHECK (status IN ('scheduled', 'sending', 'sent', 'cancelled')),
+  CONSTRAINT chk_broadcasts_audience CHECK (audience IN ('all', 'users', 'admins')),
+  CONSTRAINT fk_broadcasts_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS broadcast_recipients (
+  broadcast_id BIGINT NOT NULL,
+  user_id BIGINT NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  error_message VARCHAR(512) NULL,
+  delivered_at DATETIME(6) NULL,
+  PRIMARY KEY (broadcast_id, user_id),
+  INDEX idx_broadcast_recipients_status (broadcast_id, status),
+  CONSTRAINT chk_broadcast_recipients_status CHECK (status IN ('pending', 'sent', 'failed')),
+  CONSTRAINT fk_broadcast_recipients_broadcast_id FOREIGN KEY (broadcast_id) REFERENCES broadcasts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_broadcast_recipients_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,10 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS calendar_feed_tokens (
+  user_id BIGINT PRIMARY KEY,
+  token_hash CHAR(64) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  last_used_at DATETIME(6) NULL,
+  UNIQUE KEY uq_calendar_feed_tokens_hash (token_hash),
+  CONSTRAINT fk_calendar_feed_tokens_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+SET @has_post_categories_is_active := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_categories'
+    AND column_name = 'is_active'
+);
+SET @sql_post_categories_is_active := IF(
+  @has_post_categories_is_active = 0,
+  "ALTER TABLE post_categories ADD COLUMN is_active BOOLEAN NOT NULL DEFAULT TRUE AFTER display_name",
+  "SELECT 1"
+);
+PREPARE stmt_post_categories_is_active FROM @sql_post_categories_is_active;
+EXECUTE stmt_post_categories_is_active;
+DEALLOCATE PREPARE stmt_post_categories_is_active;
@@ -0,0 +1,24 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_external_references (
+  citing_post_id BIGINT NOT NULL,
+  doi VARCHAR(255) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (citing_post_id, doi),
+  INDEX idx_post_external_references_doi (doi),
+  CONSTRAINT fk_post_external_references_citing_post_id FOREIGN KEY (citing_post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_citation_audit_log (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  actor_id BIGINT NULL,
+  action VARCHAR(16) NOT NULL,
+  cited_post_id BIGINT NULL,
+  cited_doi VARCHAR(255) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_citation_audit_log_post_created (post_id, created_at),
+  CONSTRAINT chk_post_citation_audit_log_action CHECK (action IN ('added', 'removed')),
+  CONSTRAINT fk_post_citation_audit_log_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_citation_audit_log_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,28 @@
+USE thought_manifold;
+
+SET @has_comments_edited_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'comments'
+    AND column_name = 'edited_at'
+);
+SET @sql_comments_edited_at := IF(
+  @has_comments_edited_at = 0,
+  "ALTER TABLE comments ADD COLUMN edited_at DATETIME(6) NULL AFTER deleted_at",
+  "SELECT 1"
+);
+PREPARE stmt_comments_edited_at FROM @sql_comments_edited_at;
+EXECUTE stmt_comments_edited_at;
+DEALLOCATE PREPARE stmt_comments_edited_at;
+
+CREATE TABLE IF NOT EXISTS comment_edit_history (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  comment_id BIGINT NOT NULL,
+  editor_id BIGINT NULL,
+  previous_content TEXT NOT NULL,
+  edited_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_comment_edit_history_comment_edited (comment_id, edited_at),
+  CONSTRAINT fk_comment_edit_history_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_edit_history_editor_id FOREIGN KEY (editor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
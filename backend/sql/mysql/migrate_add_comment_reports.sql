@@ -0,0 +1,35 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS comment_reports (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  comment_id BIGINT NULL,
+  post_id BIGINT NOT NULL,
+  comment_author_id BIGINT NULL,
+  reporter_id BIGINT NULL,
+  comment_excerpt TEXT NOT NULL,
+  reason VARCHAR(1000) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'open',
+  comment_deleted BOOLEAN NOT NULL DEFAULT FALSE,
+  author_banned BOOLEAN NOT NULL DEFAULT FALSE,
+  resolved_by BIGINT NULL,
+  resolution_note TEXT NULL,
+  resolved_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_comment_reports_comment_reporter (comment_id, reporter_id),
+  INDEX idx_comment_reports_status_created (status, created_at),
+  CONSTRAINT chk_comment_reports_status CHECK (status IN ('open', 'dismissed', 'actioned')),
+  CONSTRAINT fk_comment_reports_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE SET NULL,
+  CONSTRAINT fk_comment_reports_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_reports_comment_author_id FOREIGN KEY (comment_author_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_comment_reports_reporter_id FOREIGN KEY (reporter_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_comment_reports_resolved_by FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS comment_bans (
+  user_id BIGINT PRIMARY KEY,
+  reason TEXT NULL,
+  banned_by BIGINT NULL,
+  banned_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  CONSTRAINT fk_comment_bans_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_bans_banned_by FOREIGN KEY (banned_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,19 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS crossref_cache (
+  doi VARCHAR(255) PRIMARY KEY,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  title TEXT NULL,
+  journal VARCHAR(512) NULL,
+  publisher VARCHAR(512) NULL,
+  published_at VARCHAR(32) NULL,
+  source_url VARCHAR(2048) NULL,
+  raw_json JSON NULL,
+  fetched_at DATETIME(6) NULL,
+  expires_at DATETIME(6) NOT NULL,
+  last_error TEXT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_crossref_cache_expires (expires_at),
+  CONSTRAINT chk_crossref_cache_status CHECK (status IN ('pending', 'found', 'not_found'))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
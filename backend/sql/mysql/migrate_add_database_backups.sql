@@ -0,0 +1,18 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS database_backups (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  file_name VARCHAR(255) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'running',
+  trigger_source VARCHAR(16) NOT NULL,
+  size_bytes BIGINT NULL,
+  error_message TEXT NULL,
+  started_by BIGINT NULL,
+  started_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  finished_at DATETIME(6) NULL,
+  UNIQUE KEY uq_database_backups_file_name (file_name),
+  INDEX idx_database_backups_status_started (status, started_at),
+  CONSTRAINT chk_database_backups_status CHECK (status IN ('running', 'completed', 'failed', 'expired')),
+  CONSTRAINT chk_database_backups_trigger_source CHECK (trigger_source IN ('scheduled', 'manual')),
+  CONSTRAINT fk_database_backups_started_by FOREIGN KEY (started_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
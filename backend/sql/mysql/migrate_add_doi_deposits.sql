@@ -0,0 +1,18 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_doi_deposits (
+  post_id BIGINT PRIMARY KEY,
+  doi VARCHAR(255) NOT NULL,
+  registration_agency VARCHAR(32) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  attempts INT NOT NULL DEFAULT 0,
+  last_error TEXT NULL,
+  next_attempt_at DATETIME(6) NULL,
+  registered_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_post_doi_deposits_doi (doi),
+  INDEX idx_post_doi_deposits_due (status, next_attempt_at),
+  CONSTRAINT chk_post_doi_deposits_status CHECK (status IN ('pending', 'registered', 'failed')),
+  CONSTRAINT fk_post_doi_deposits_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+SET @has_posts_is_double_blind := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND column_name = 'is_double_blind'
+);
+SET @sql_posts_is_double_blind := IF(
+  @has_posts_is_double_blind = 0,
+  "ALTER TABLE posts ADD COLUMN is_double_blind BOOLEAN NOT NULL DEFAULT FALSE AFTER issue_id",
+  "SELECT 1"
+);
+PREPARE stmt_posts_is_double_blind FROM @sql_posts_is_double_blind;
+EXECUTE stmt_posts_is_double_blind;
+DEALLOCATE PREPARE stmt_posts_is_double_blind;
@@ -0,0 +1,30 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS editorial_decisions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  paper_version_id BIGINT NULL,
+  editor_id BIGINT NULL,
+  decision_id TINYINT UNSIGNED NOT NULL,
+  ai_review_id BIGINT NULL,
+  previous_status VARCHAR(32) NOT NULL,
+  new_status VARCHAR(32) NOT NULL,
+  note TEXT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_editorial_decisions_post_created (post_id, created_at),
+  INDEX idx_editorial_decisions_post_version (post_id, paper_version_id),
+  CONSTRAINT fk_editorial_decisions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_editorial_decisions_version_id FOREIGN KEY (paper_version_id) REFERENCES paper_versions(id) ON DELETE SET NULL,
+  CONSTRAINT fk_editorial_decisions_editor_id FOREIGN KEY (editor_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_editorial_decisions_decision_id FOREIGN KEY (decision_id) REFERENCES ai_review_decisions(id),
+  CONSTRAINT fk_editorial_decisions_ai_review_id FOREIGN KEY (ai_review_id) REFERENCES post_ai_reviews(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS editorial_decision_reviews (
+  decision_id BIGINT NOT NULL,
+  assignment_id BIGINT NOT NULL,
+  PRIMARY KEY (decision_id, assignment_id),
+  INDEX idx_editorial_decision_reviews_assignment (assignment_id),
+  CONSTRAINT fk_editorial_decision_reviews_decision_id FOREIGN KEY (decision_id) REFERENCES editorial_decisions(id) ON DELETE CASCADE,
+  CONSTRAINT fk_editorial_decision_reviews_assignment_id FOREIGN KEY (assignment_id) REFERENCES reviewer_assignments(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,37 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_doi_registrations (
+  post_id BIGINT PRIMARY KEY,
+  internal_doi VARCHAR(255) NOT NULL,
+  registered_doi VARCHAR(255) NOT NULL,
+  registration_agency VARCHAR(32) NOT NULL,
+  openalex_work_id VARCHAR(64) NULL,
+  external_cited_by_count BIGINT NULL,
+  citations_synced_at DATETIME(6) NULL,
+  last_sync_error TEXT NULL,
+  registered_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_post_doi_registrations_registered_doi (registered_doi),
+  INDEX idx_post_doi_registrations_synced_at (citations_synced_at),
+  CONSTRAINT fk_post_doi_registrations_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_external_citations (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  cited_post_id BIGINT NOT NULL,
+  citation_source_id TINYINT UNSIGNED NOT NULL,
+  external_work_id VARCHAR(255) NOT NULL,
+  citing_doi VARCHAR(255) NULL,
+  citing_title TEXT NULL,
+  citing_venue VARCHAR(512) NULL,
+  citing_year SMALLINT NULL,
+  first_seen_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  last_seen_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_post_external_citations_work (cited_post_id, citation_source_id, external_work_id),
+  INDEX idx_post_external_citations_source_id (citation_source_id),
+  CONSTRAINT fk_post_external_citations_cited_post_id FOREIGN KEY (cited_post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_external_citations_source_id FOREIGN KEY (citation_source_id) REFERENCES citation_sources(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+INSERT IGNORE INTO citation_sources (id, code, display_name) VALUES
+  (3, 'openalex', 'OpenAlex inbound citation');
@@ -0,0 +1,22 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  flag_key VARCHAR(64) NOT NULL,
+  enabled BOOLEAN NOT NULL,
+  rollout_percent TINYINT UNSIGNED NOT NULL DEFAULT 100,
+  updated_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_feature_flags_key (flag_key),
+  CONSTRAINT chk_feature_flags_rollout_percent CHECK (rollout_percent <= 100),
+  CONSTRAINT fk_feature_flags_updated_by FOREIGN KEY (updated_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS feature_flag_roles (
+  flag_id BIGINT NOT NULL,
+  role VARCHAR(32) NOT NULL,
+  PRIMARY KEY (flag_id, role),
+  CONSTRAINT chk_feature_flag_roles_role CHECK (role IN ('user', 'admin')),
+  CONSTRAINT fk_feature_flag_roles_flag_id FOREIGN KEY (flag_id) REFERENCES feature_flags(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
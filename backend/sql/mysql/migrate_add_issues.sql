@@ -0,0 +1,47 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS issues (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  volume INT UNSIGNED NOT NULL,
+  number INT UNSIGNED NOT NULL,
+  title VARCHAR(255) NULL,
+  publication_date DATE NULL,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_issues_volume_number (volume, number),
+  INDEX idx_issues_publication_date (publication_date),
+  CONSTRAINT fk_issues_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+SET @has_posts_issue_id := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND column_name = 'issue_id'
+);
+SET @sql_posts_issue_id := IF(
+  @has_posts_issue_id = 0,
+  "ALTER TABLE posts ADD COLUMN issue_id BIGINT NULL AFTER latest_paper_version_id, ADD INDEX idx_posts_issue_id (issue_id)",
+  "SELECT 1"
+);
+PREPARE stmt_posts_issue_id FROM @sql_posts_issue_id;
+EXECUTE stmt_posts_issue_id;
+DEALLOCATE PREPARE stmt_posts_issue_id;
+
+SET @has_fk_posts_issue_id := (
+  SELECT COUNT(*)
+  FROM information_schema.table_constraints
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND constraint_name = 'fk_posts_issue_id'
+);
+SET @sql_fk_posts_issue_id := IF(
+  @has_fk_posts_issue_id = 0,
+  "ALTER TABLE posts ADD CONSTRAINT fk_posts_issue_id FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE SET NULL",
+  "SELECT 1"
+);
+PREPARE stmt_fk_posts_issue_id FROM @sql_fk_posts_issue_id;
+EXECUTE stmt_fk_posts_issue_id;
+DEALLOCATE PREPARE stmt_fk_posts_issue_id;
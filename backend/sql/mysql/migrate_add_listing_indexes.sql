@@ -0,0 +1,21 @@
+-- Thought Manifold MySQL migration: index an author's posts by last update,
+-- the order of the review center listing
+-- Safe to run multiple times.
+
+USE thought_manifold;
+
+SET @has_posts_author_updated_index := (
+  SELECT COUNT(*)
+  FROM information_schema.statistics
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND index_name = 'idx_posts_author_updated'
+);
+SET @add_posts_author_updated_index_sql := IF(
+  @has_posts_author_updated_index = 0,
+  "CREATE INDEX idx_posts_author_updated ON posts (author_id, updated_at)",
+  "SELECT 1"
+);
+PREPARE stmt_add_posts_author_updated_index FROM @add_posts_author_updated_index_sql;
+EXECUTE stmt_add_posts_author_updated_index;
+DEALLOCATE PREPARE stmt_add_posts_author_updated_index;
@@ -0,0 +1,23 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS metric_snapshots (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  scope VARCHAR(16) NOT NULL,
+  user_id BIGINT NULL,
+  snapshot_date DATE NOT NULL,
+  g_index BIGINT NULL,
+  h_index BIGINT NULL,
+  i10_index BIGINT NULL,
+  total_citations BIGINT NULL,
+  paper_count BIGINT NULL,
+  journal_year INT NULL,
+  impact_factor DOUBLE NULL,
+  numerator_citations BIGINT NULL,
+  denominator_papers BIGINT NULL,
+  metric_version VARCHAR(16) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_metric_snapshots_scope_user_date (scope, user_id, snapshot_date),
+  INDEX idx_metric_snapshots_scope_date (scope, snapshot_date),
+  CONSTRAINT chk_metric_snapshots_scope CHECK (scope IN ('author', 'journal')),
+  CONSTRAINT fk_metric_snapshots_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,65 @@
+USE thought_manifold;
+
+SET @has_notifications_dedup_key := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'notifications'
+    AND column_name = 'dedup_key'
+);
+SET @sql_notifications_dedup_key := IF(
+  @has_notifications_dedup_key = 0,
+  "ALTER TABLE notifications ADD COLUMN dedup_key VARCHAR(191) NULL AFTER read_at",
+  "SELECT 1"
+);
+PREPARE stmt_notifications_dedup_key FROM @sql_notifications_dedup_key;
+EXECUTE stmt_notifications_dedup_key;
+DEALLOCATE PREPARE stmt_notifications_dedup_key;
+
+SET @has_notifications_batch_count := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'notifications'
+    AND column_name = 'batch_count'
+);
+SET @sql_notifications_batch_count := IF(
+  @has_notifications_batch_count = 0,
+  "ALTER TABLE notifications ADD COLUMN batch_count INT NOT NULL DEFAULT 1 AFTER dedup_key",
+  "SELECT 1"
+);
+PREPARE stmt_notifications_batch_count FROM @sql_notifications_batch_count;
+EXECUTE stmt_notifications_batch_count;
+DEALLOCATE PREPARE stmt_notifications_batch_count;
+
+SET @has_notifications_updated_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'notifications'
+    AND column_name = 'updated_at'
+);
+SET @sql_notifications_updated_at := IF(
+  @has_notifications_updated_at = 0,
+  "ALTER TABLE notifications ADD COLUMN updated_at DATETIME(6) NULL AFTER created_at",
+  "SELECT 1"
+);
+PREPARE stmt_notifications_updated_at FROM @sql_notifications_updated_at;
+EXECUTE stmt_notifications_updated_at;
+DEALLOCATE PREPARE stmt_notifications_updated_at;
+
+SET @has_idx_notifications_user_dedup := (
+  SELECT COUNT(*)
+  FROM information_schema.statistics
+  WHERE table_schema = DATABASE()
+    AND table_name = 'notifications'
+    AND index_name = 'idx_notifications_user_dedup'
+);
+SET @sql_idx_notifications_user_dedup := IF(
+  @has_idx_notifications_user_dedup = 0,
+  "CREATE INDEX idx_notifications_user_dedup ON notifications (user_id, dedup_key, created_at)",
+  "SELECT 1"
+);
+PREPARE stmt_idx_notifications_user_dedup FROM @sql_idx_notifications_user_dedup;
+EXECUTE stmt_idx_notifications_user_dedup;
+DEALLOCATE PREPARE stmt_idx_notifications_user_dedup;
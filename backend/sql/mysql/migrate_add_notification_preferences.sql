@@ -0,0 +1,11 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+  user_id BIGINT NOT NULL,
+  event_type VARCHAR(64) NOT NULL,
+  in_app BOOLEAN NOT NULL,
+  email BOOLEAN NOT NULL,
+  updated_at DATETIME(6) NOT NULL,
+  PRIMARY KEY (user_id, event_type),
+  CONSTRAINT fk_notification_preferences_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
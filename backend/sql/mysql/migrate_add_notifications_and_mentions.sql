@@ -0,0 +1,32 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS notifications (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  actor_id BIGINT NULL,
+  event_type VARCHAR(64) NOT NULL,
+  post_id BIGINT NULL,
+  comment_id BIGINT NULL,
+  message VARCHAR(512) NOT NULL,
+  is_read BOOLEAN NOT NULL DEFAULT FALSE,
+  read_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_notifications_user_read_created (user_id, is_read, created_at),
+  CONSTRAINT fk_notifications_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_notifications_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_notifications_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS comment_mentions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  comment_id BIGINT NULL,
+  review_comment_id BIGINT NULL,
+  mentioned_user_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_comment_mentions_comment_user (comment_id, mentioned_user_id),
+  UNIQUE KEY uq_comment_mentions_review_comment_user (review_comment_id, mentioned_user_id),
+  INDEX idx_comment_mentions_user_created (mentioned_user_id, created_at),
+  CONSTRAINT fk_comment_mentions_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_mentions_review_comment_id FOREIGN KEY (review_comment_id) REFERENCES paper_review_comments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_mentions_user_id FOREIGN KEY (mentioned_user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,34 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS user_orcid_links (
+  user_id BIGINT PRIMARY KEY,
+  orcid_id VARCHAR(19) NOT NULL,
+  access_token VARCHAR(255) NOT NULL,
+  refresh_token VARCHAR(255) NULL,
+  scope VARCHAR(255) NULL,
+  token_expires_at DATETIME(6) NULL,
+  sync_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+  last_synced_at DATETIME(6) NULL,
+  last_sync_error TEXT NULL,
+  linked_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_user_orcid_links_orcid_id (orcid_id),
+  INDEX idx_user_orcid_links_sync (sync_enabled, last_synced_at),
+  CONSTRAINT fk_user_orcid_links_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS orcid_work_syncs (
+  user_id BIGINT NOT NULL,
+  post_id BIGINT NOT NULL,
+  put_code VARCHAR(32) NULL,
+  payload_hash CHAR(64) NULL,
+  status VARCHAR(16) NOT NULL,
+  last_error TEXT NULL,
+  synced_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  PRIMARY KEY (user_id, post_id),
+  INDEX idx_orcid_work_syncs_post (post_id),
+  CONSTRAINT chk_orcid_work_syncs_status CHECK (status IN ('synced', 'failed')),
+  CONSTRAINT fk_orcid_work_syncs_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
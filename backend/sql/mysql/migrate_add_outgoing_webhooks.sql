@@ -0,0 +1,20 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS outgoing_webhooks (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  name VARCHAR(100) NOT NULL,
+  platform VARCHAR(16) NOT NULL,
+  url VARCHAR(512) NOT NULL,
+  is_active BOOLEAN NOT NULL DEFAULT TRUE,
+  on_submission BOOLEAN NOT NULL DEFAULT TRUE,
+  on_review_failure BOOLEAN NOT NULL DEFAULT TRUE,
+  on_content_report BOOLEAN NOT NULL DEFAULT TRUE,
+  last_delivered_at DATETIME(6) NULL,
+  last_error VARCHAR(500) NULL,
+  last_error_at DATETIME(6) NULL,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL,
+  updated_at DATETIME(6) NULL,
+  CONSTRAINT chk_outgoing_webhooks_platform CHECK (platform IN ('slack', 'discord')),
+  CONSTRAINT fk_outgoing_webhooks_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
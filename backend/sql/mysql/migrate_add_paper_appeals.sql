@@ -0,0 +1,28 @@
+USE thought_manifold;
+
+INSERT IGNORE INTO ai_review_triggers (id, code, display_name) VALUES
+  (4, 'appeal', 'Appeal Review');
+
+CREATE TABLE IF NOT EXISTS paper_appeals (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  paper_version_id BIGINT NULL,
+  author_id BIGINT NOT NULL,
+  justification TEXT NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  ai_review_id BIGINT NULL,
+  resolved_by BIGINT NULL,
+  resolution_note TEXT NULL,
+  resulting_status VARCHAR(32) NULL,
+  resolved_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_paper_appeals_post_version (post_id, paper_version_id),
+  INDEX idx_paper_appeals_status_created (status, created_at),
+  CONSTRAINT fk_paper_appeals_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_paper_appeals_version_id FOREIGN KEY (paper_version_id) REFERENCES paper_versions(id) ON DELETE SET NULL,
+  CONSTRAINT fk_paper_appeals_author_id FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_paper_appeals_ai_review_id FOREIGN KEY (ai_review_id) REFERENCES post_ai_reviews(id) ON DELETE SET NULL,
+  CONSTRAINT fk_paper_appeals_resolved_by FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT chk_paper_appeals_status CHECK (status IN ('pending', 'upheld', 'overturned'))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,15 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS paper_status_transitions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  actor_id BIGINT NULL,
+  trigger_source VARCHAR(32) NOT NULL,
+  from_status VARCHAR(32) NULL,
+  to_status VARCHAR(32) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_paper_status_transitions_post_created (post_id, created_at),
+  CONSTRAINT chk_paper_status_transitions_trigger CHECK (trigger_source IN ('create', 'update', 'publish', 'restore', 'ai_review', 'ai_review_rerun', 'editorial_decision', 'appeal', 'backfill')),
+  CONSTRAINT fk_paper_status_transitions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_paper_status_transitions_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
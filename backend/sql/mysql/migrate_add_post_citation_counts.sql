@@ -0,0 +1,49 @@
+USE thought_manifold;
+
+SET @has_post_stats_citation_count := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_stats'
+    AND column_name = 'citation_count'
+);
+SET @sql_post_stats_citation_count := IF(
+  @has_post_stats_citation_count = 0,
+  "ALTER TABLE post_stats ADD COLUMN citation_count BIGINT NOT NULL DEFAULT 0",
+  "SELECT 1"
+);
+PREPARE stmt_post_stats_citation_count FROM @sql_post_stats_citation_count;
+EXECUTE stmt_post_stats_citation_count;
+DEALLOCATE PREPARE stmt_post_stats_citation_count;
+
+SET @has_post_stats_external_citation_count := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_stats'
+    AND column_name = 'external_citation_count'
+);
+SET @sql_post_stats_external_citation_count := IF(
+  @has_post_stats_external_citation_count = 0,
+  "ALTER TABLE post_stats ADD COLUMN external_citation_count BIGINT NOT NULL DEFAULT 0",
+  "SELECT 1"
+);
+PREPARE stmt_post_stats_external_citation_count FROM @sql_post_stats_external_citation_count;
+EXECUTE stmt_post_stats_external_citation_count;
+DEALLOCATE PREPARE stmt_post_stats_external_citation_count;
+
+-- Backfill the denormalized counters from the existing citation edges.
+INSERT INTO post_stats (post_id, view_count, like_count, citation_count, external_citation_count, updated_at)
+SELECT
+  p.id,
+  0,
+  0,
+  (SELECT COUNT(DISTINCT pc.citing_post_id) FROM post_citations pc WHERE pc.cited_post_id = p.id)
+    + (SELECT COUNT(*) FROM post_external_citations pec WHERE pec.cited_post_id = p.id),
+  (SELECT COUNT(*) FROM post_external_citations pec WHERE pec.cited_post_id = p.id),
+  CURRENT_TIMESTAMP(6)
+FROM posts p
+ON DUPLICATE KEY UPDATE
+  citation_count = VALUES(citation_count),
+  external_citation_count = VALUES(external_citation_count),
+  updated_at = VALUES(updated_at);
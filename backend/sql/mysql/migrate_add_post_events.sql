@@ -0,0 +1,14 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_events (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  event_type VARCHAR(16) NOT NULL,
+  user_id BIGINT NULL,
+  referrer_host VARCHAR(255) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_events_post_type_created (post_id, event_type, created_at),
+  CONSTRAINT chk_post_events_event_type CHECK (event_type IN ('view', 'like', 'unlike')),
+  CONSTRAINT fk_post_events_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_events_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
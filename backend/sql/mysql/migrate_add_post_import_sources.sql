@@ -0,0 +1,19 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_import_sources (
+  post_id BIGINT PRIMARY KEY,
+  source_type VARCHAR(16) NOT NULL,
+  source_identifier VARCHAR(255) NOT NULL,
+  source_url VARCHAR(2048) NOT NULL,
+  source_doi VARCHAR(255) NULL,
+  authors_json JSON NULL,
+  license_url VARCHAR(2048) NULL,
+  pdf_imported BOOLEAN NOT NULL DEFAULT FALSE,
+  imported_by BIGINT NULL,
+  imported_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_import_sources_source (source_type, source_identifier),
+  INDEX idx_post_import_sources_imported_by (imported_by),
+  CONSTRAINT chk_post_import_sources_type CHECK (source_type IN ('arxiv', 'doi')),
+  CONSTRAINT fk_post_import_sources_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_import_sources_imported_by FOREIGN KEY (imported_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,19 @@
+USE thought_manifold;
+
+SET @has_post_stats_influence_score := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_stats'
+    AND column_name = 'influence_score'
+);
+SET @sql_post_stats_influence_score := IF(
+  @has_post_stats_influence_score = 0,
+  "ALTER TABLE post_stats ADD COLUMN influence_score DOUBLE NOT NULL DEFAULT 0",
+  "SELECT 1"
+);
+PREPARE stmt_post_stats_influence_score FROM @sql_post_stats_influence_score;
+EXECUTE stmt_post_stats_influence_score;
+DEALLOCATE PREPARE stmt_post_stats_influence_score;
+
+-- Scores are filled by the influence score job on its first run.
@@ -0,0 +1,54 @@
+-- Thought Manifold MySQL migration: hourly and daily post activity rollups,
+-- the trending score derived from them, and an index for reading post_events
+-- by time
+-- Safe to run multiple times.
+
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_stat_rollups (
+  post_id BIGINT NOT NULL,
+  granularity VARCHAR(8) NOT NULL,
+  bucket_start DATETIME NOT NULL,
+  views BIGINT NOT NULL DEFAULT 0,
+  likes BIGINT NOT NULL DEFAULT 0,
+  unlikes BIGINT NOT NULL DEFAULT 0,
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (post_id, granularity, bucket_start),
+  INDEX idx_post_stat_rollups_granularity_bucket (granularity, bucket_start),
+  CONSTRAINT chk_post_stat_rollups_granularity CHECK (granularity IN ('hour', 'day')),
+  CONSTRAINT fk_post_stat_rollups_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+SET @has_post_stats_trending_score := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_stats'
+    AND column_name = 'trending_score'
+);
+SET @sql_post_stats_trending_score := IF(
+  @has_post_stats_trending_score = 0,
+  "ALTER TABLE post_stats ADD COLUMN trending_score DOUBLE NOT NULL DEFAULT 0",
+  "SELECT 1"
+);
+PREPARE stmt_post_stats_trending_score FROM @sql_post_stats_trending_score;
+EXECUTE stmt_post_stats_trending_score;
+DEALLOCATE PREPARE stmt_post_stats_trending_score;
+
+SET @has_post_events_created_index := (
+  SELECT COUNT(*)
+  FROM information_schema.statistics
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_events'
+    AND index_name = 'idx_post_events_created'
+);
+SET @add_post_events_created_index_sql := IF(
+  @has_post_events_created_index = 0,
+  "CREATE INDEX idx_post_events_created ON post_events (created_at)",
+  "SELECT 1"
+);
+PREPARE stmt_add_post_events_created_index FROM @add_post_events_created_index_sql;
+EXECUTE stmt_add_post_events_created_index;
+DEALLOCATE PREPARE stmt_add_post_events_created_index;
+
+-- Existing events are rolled up by the rollup job on its first run.
@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+SET @has_posts_is_preprint := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND column_name = 'is_preprint'
+);
+SET @sql_posts_is_preprint := IF(
+  @has_posts_is_preprint = 0,
+  "ALTER TABLE posts ADD COLUMN is_preprint BOOLEAN NOT NULL DEFAULT FALSE AFTER is_double_blind",
+  "SELECT 1"
+);
+PREPARE stmt_posts_is_preprint FROM @sql_posts_is_preprint;
+EXECUTE stmt_posts_is_preprint;
+DEALLOCATE PREPARE stmt_posts_is_preprint;
@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS push_subscriptions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  endpoint VARCHAR(768) NOT NULL,
+  p256dh VARCHAR(128) NOT NULL,
+  auth_secret VARCHAR(64) NOT NULL,
+  user_agent VARCHAR(512) NULL,
+  created_at DATETIME(6) NOT NULL,
+  last_success_at DATETIME(6) NULL,
+  last_failure_at DATETIME(6) NULL,
+  failure_count INT NOT NULL DEFAULT 0,
+  UNIQUE KEY uq_push_subscriptions_endpoint (endpoint),
+  INDEX idx_push_subscriptions_user_id (user_id),
+  CONSTRAINT fk_push_subscriptions_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,21 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_repository_deposits (
+  post_id BIGINT PRIMARY KEY,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  attempts INT NOT NULL DEFAULT 0,
+  last_error TEXT NULL,
+  next_attempt_at DATETIME(6) NULL,
+  edit_iri VARCHAR(2048) NULL,
+  edit_media_iri VARCHAR(2048) NULL,
+  statement_iri VARCHAR(2048) NULL,
+  splash_url VARCHAR(2048) NULL,
+  receipt_xml LONGTEXT NULL,
+  media_deposited_at DATETIME(6) NULL,
+  deposited_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_post_repository_deposits_due (status, next_attempt_at),
+  CONSTRAINT chk_post_repository_deposits_status CHECK (status IN ('pending', 'deposited', 'failed')),
+  CONSTRAINT fk_post_repository_deposits_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
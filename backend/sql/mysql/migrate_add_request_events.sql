@@ -0,0 +1,29 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS request_events (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  event_type VARCHAR(16) NOT NULL,
+  method VARCHAR(8) NOT NULL,
+  path VARCHAR(255) NOT NULL,
+  status_code SMALLINT UNSIGNED NOT NULL,
+  user_id BIGINT NULL,
+  ip_address VARCHAR(64) NULL,
+  user_agent VARCHAR(512) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_request_events_ip_created (ip_address, created_at),
+  INDEX idx_request_events_user_created (user_id, created_at),
+  INDEX idx_request_events_created (created_at),
+  CONSTRAINT chk_request_events_event_type CHECK (event_type IN ('auth', 'write')),
+  CONSTRAINT fk_request_events_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS ip_blocks (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  ip_address VARCHAR(64) NOT NULL,
+  reason TEXT NULL,
+  blocked_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  expires_at DATETIME(6) NULL,
+  UNIQUE KEY uq_ip_blocks_ip_address (ip_address),
+  CONSTRAINT fk_ip_blocks_blocked_by FOREIGN KEY (blocked_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,49 @@
+USE thought_manifold;
+
+SET @has_users_affiliation := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'users'
+    AND column_name = 'affiliation'
+);
+SET @sql_users_affiliation := IF(
+  @has_users_affiliation = 0,
+  "ALTER TABLE users ADD COLUMN affiliation VARCHAR(255) NULL AFTER research_areas",
+  "SELECT 1"
+);
+PREPARE stmt_users_affiliation FROM @sql_users_affiliation;
+EXECUTE stmt_users_affiliation;
+DEALLOCATE PREPARE stmt_users_affiliation;
+
+SET @has_users_website := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'users'
+    AND column_name = 'website'
+);
+SET @sql_users_website := IF(
+  @has_users_website = 0,
+  "ALTER TABLE users ADD COLUMN website VARCHAR(512) NULL AFTER affiliation",
+  "SELECT 1"
+);
+PREPARE stmt_users_website FROM @sql_users_website;
+EXECUTE stmt_users_website;
+DEALLOCATE PREPARE stmt_users_website;
+
+SET @has_users_orcid_id := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'users'
+    AND column_name = 'orcid_id'
+);
+SET @sql_users_orcid_id := IF(
+  @has_users_orcid_id = 0,
+  "ALTER TABLE users ADD COLUMN orcid_id VARCHAR(19) NULL AFTER website",
+  "SELECT 1"
+);
+PREPARE stmt_users_orcid_id FROM @sql_users_orcid_id;
+EXECUTE stmt_users_orcid_id;
+DEALLOCATE PREPARE stmt_users_orcid_id;
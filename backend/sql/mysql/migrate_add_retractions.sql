@@ -0,0 +1,27 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS post_retractions (
+  post_id BIGINT PRIMARY KEY,
+  reason TEXT NOT NULL,
+  retracted_by BIGINT NULL,
+  retracted_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_retractions_retracted_at (retracted_at),
+  CONSTRAINT fk_post_retractions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_retractions_retracted_by FOREIGN KEY (retracted_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+SET @has_post_citations_retraction_flagged_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'post_citations'
+    AND column_name = 'retraction_flagged_at'
+);
+SET @sql_post_citations_retraction_flagged_at := IF(
+  @has_post_citations_retraction_flagged_at = 0,
+  "ALTER TABLE post_citations ADD COLUMN retraction_flagged_at DATETIME(6) NULL",
+  "SELECT 1"
+);
+PREPARE stmt_post_citations_retraction_flagged_at FROM @sql_post_citations_retraction_flagged_at;
+EXECUTE stmt_post_citations_retraction_flagged_at;
+DEALLOCATE PREPARE stmt_post_citations_retraction_flagged_at;
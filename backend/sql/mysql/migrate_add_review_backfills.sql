@@ -0,0 +1,36 @@
+USE thought_manifold;
+
+INSERT IGNORE INTO ai_review_triggers (id, code, display_name) VALUES
+  (5, 'backfill', 'Backfill Review');
+
+CREATE TABLE IF NOT EXISTS ai_review_backfills (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  status VARCHAR(16) NOT NULL DEFAULT 'running',
+  prompt_version VARCHAR(32) NULL,
+  paper_status VARCHAR(32) NULL,
+  batch_size INT UNSIGNED NOT NULL,
+  total_items INT UNSIGNED NOT NULL DEFAULT 0,
+  created_by BIGINT NULL,
+  cancelled_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  finished_at DATETIME(6) NULL,
+  INDEX idx_ai_review_backfills_status_created (status, created_at),
+  CONSTRAINT chk_ai_review_backfills_status CHECK (status IN ('running', 'completed', 'cancelled')),
+  CONSTRAINT fk_ai_review_backfills_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_ai_review_backfills_cancelled_by FOREIGN KEY (cancelled_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS ai_review_backfill_items (
+  backfill_id BIGINT NOT NULL,
+  post_id BIGINT NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'queued',
+  review_id BIGINT NULL,
+  error_message TEXT NULL,
+  processed_at DATETIME(6) NULL,
+  PRIMARY KEY (backfill_id, post_id),
+  INDEX idx_ai_review_backfill_items_status (backfill_id, status),
+  CONSTRAINT chk_ai_review_backfill_items_status CHECK (status IN ('queued', 'scheduled', 'failed', 'cancelled')),
+  CONSTRAINT fk_ai_review_backfill_items_backfill_id FOREIGN KEY (backfill_id) REFERENCES ai_review_backfills(id) ON DELETE CASCADE,
+  CONSTRAINT fk_ai_review_backfill_items_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_ai_review_backfill_items_review_id FOREIGN KEY (review_id) REFERENCES post_ai_reviews(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,49 @@
+USE thought_manifold;
+
+SET @has_review_comments_anchor_start := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'anchor_start'
+);
+SET @sql_review_comments_anchor_start := IF(
+  @has_review_comments_anchor_start = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN anchor_start INT NULL AFTER resolution_note",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_anchor_start FROM @sql_review_comments_anchor_start;
+EXECUTE stmt_review_comments_anchor_start;
+DEALLOCATE PREPARE stmt_review_comments_anchor_start;
+
+SET @has_review_comments_anchor_end := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'anchor_end'
+);
+SET @sql_review_comments_anchor_end := IF(
+  @has_review_comments_anchor_end = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN anchor_end INT NULL AFTER anchor_start",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_anchor_end FROM @sql_review_comments_anchor_end;
+EXECUTE stmt_review_comments_anchor_end;
+DEALLOCATE PREPARE stmt_review_comments_anchor_end;
+
+SET @has_review_comments_anchor_quote := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'anchor_quote'
+);
+SET @sql_review_comments_anchor_quote := IF(
+  @has_review_comments_anchor_quote = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN anchor_quote TEXT NULL AFTER anchor_end",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_anchor_quote FROM @sql_review_comments_anchor_quote;
+EXECUTE stmt_review_comments_anchor_quote;
+DEALLOCATE PREPARE stmt_review_comments_anchor_quote;
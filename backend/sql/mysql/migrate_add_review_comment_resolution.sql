@@ -0,0 +1,97 @@
+USE thought_manifold;
+
+SET @has_review_comments_resolution_status := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'resolution_status'
+);
+SET @sql_review_comments_resolution_status := IF(
+  @has_review_comments_resolution_status = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN resolution_status VARCHAR(16) NOT NULL DEFAULT 'open' AFTER is_anonymous",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_resolution_status FROM @sql_review_comments_resolution_status;
+EXECUTE stmt_review_comments_resolution_status;
+DEALLOCATE PREPARE stmt_review_comments_resolution_status;
+
+SET @has_review_comments_resolved_by := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'resolved_by'
+);
+SET @sql_review_comments_resolved_by := IF(
+  @has_review_comments_resolved_by = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN resolved_by BIGINT NULL AFTER resolution_status",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_resolved_by FROM @sql_review_comments_resolved_by;
+EXECUTE stmt_review_comments_resolved_by;
+DEALLOCATE PREPARE stmt_review_comments_resolved_by;
+
+SET @has_review_comments_resolved_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'resolved_at'
+);
+SET @sql_review_comments_resolved_at := IF(
+  @has_review_comments_resolved_at = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN resolved_at DATETIME(6) NULL AFTER resolved_by",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_resolved_at FROM @sql_review_comments_resolved_at;
+EXECUTE stmt_review_comments_resolved_at;
+DEALLOCATE PREPARE stmt_review_comments_resolved_at;
+
+SET @has_review_comments_resolution_note := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND column_name = 'resolution_note'
+);
+SET @sql_review_comments_resolution_note := IF(
+  @has_review_comments_resolution_note = 0,
+  "ALTER TABLE paper_review_comments ADD COLUMN resolution_note TEXT NULL AFTER resolved_at",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_resolution_note FROM @sql_review_comments_resolution_note;
+EXECUTE stmt_review_comments_resolution_note;
+DEALLOCATE PREPARE stmt_review_comments_resolution_note;
+
+SET @has_review_comments_resolved_by_fk := (
+  SELECT COUNT(*)
+  FROM information_schema.table_constraints
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND constraint_name = 'fk_paper_review_comments_resolved_by'
+);
+SET @sql_review_comments_resolved_by_fk := IF(
+  @has_review_comments_resolved_by_fk = 0,
+  "ALTER TABLE paper_review_comments ADD CONSTRAINT fk_paper_review_comments_resolved_by FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_resolved_by_fk FROM @sql_review_comments_resolved_by_fk;
+EXECUTE stmt_review_comments_resolved_by_fk;
+DEALLOCATE PREPARE stmt_review_comments_resolved_by_fk;
+
+SET @has_review_comments_resolution_check := (
+  SELECT COUNT(*)
+  FROM information_schema.table_constraints
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_review_comments'
+    AND constraint_name = 'chk_paper_review_comments_resolution_status'
+);
+SET @sql_review_comments_resolution_check := IF(
+  @has_review_comments_resolution_check = 0,
+  "ALTER TABLE paper_review_comments ADD CONSTRAINT chk_paper_review_comments_resolution_status CHECK (resolution_status IN ('open', 'addressed', 'wont_fix'))",
+  "SELECT 1"
+);
+PREPARE stmt_review_comments_resolution_check FROM @sql_review_comments_resolution_check;
+EXECUTE stmt_review_comments_resolution_check;
+DEALLOCATE PREPARE stmt_review_comments_resolution_check;
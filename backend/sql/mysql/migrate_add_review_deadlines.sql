@@ -0,0 +1,81 @@
+USE thought_manifold;
+
+SET @has_assignments_reminder_sent_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'reviewer_assignments'
+    AND column_name = 'reminder_sent_at'
+);
+SET @sql_assignments_reminder_sent_at := IF(
+  @has_assignments_reminder_sent_at = 0,
+  "ALTER TABLE reviewer_assignments ADD COLUMN reminder_sent_at DATETIME(6) NULL AFTER submitted_at",
+  "SELECT 1"
+);
+PREPARE stmt_assignments_reminder_sent_at FROM @sql_assignments_reminder_sent_at;
+EXECUTE stmt_assignments_reminder_sent_at;
+DEALLOCATE PREPARE stmt_assignments_reminder_sent_at;
+
+SET @has_assignments_overdue_notified_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'reviewer_assignments'
+    AND column_name = 'overdue_notified_at'
+);
+SET @sql_assignments_overdue_notified_at := IF(
+  @has_assignments_overdue_notified_at = 0,
+  "ALTER TABLE reviewer_assignments ADD COLUMN overdue_notified_at DATETIME(6) NULL AFTER reminder_sent_at",
+  "SELECT 1"
+);
+PREPARE stmt_assignments_overdue_notified_at FROM @sql_assignments_overdue_notified_at;
+EXECUTE stmt_assignments_overdue_notified_at;
+DEALLOCATE PREPARE stmt_assignments_overdue_notified_at;
+
+SET @has_decisions_revision_due_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'editorial_decisions'
+    AND column_name = 'revision_due_at'
+);
+SET @sql_decisions_revision_due_at := IF(
+  @has_decisions_revision_due_at = 0,
+  "ALTER TABLE editorial_decisions ADD COLUMN revision_due_at DATETIME(6) NULL AFTER note",
+  "SELECT 1"
+);
+PREPARE stmt_decisions_revision_due_at FROM @sql_decisions_revision_due_at;
+EXECUTE stmt_decisions_revision_due_at;
+DEALLOCATE PREPARE stmt_decisions_revision_due_at;
+
+SET @has_decisions_reminder_sent_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'editorial_decisions'
+    AND column_name = 'reminder_sent_at'
+);
+SET @sql_decisions_reminder_sent_at := IF(
+  @has_decisions_reminder_sent_at = 0,
+  "ALTER TABLE editorial_decisions ADD COLUMN reminder_sent_at DATETIME(6) NULL AFTER revision_due_at",
+  "SELECT 1"
+);
+PREPARE stmt_decisions_reminder_sent_at FROM @sql_decisions_reminder_sent_at;
+EXECUTE stmt_decisions_reminder_sent_at;
+DEALLOCATE PREPARE stmt_decisions_reminder_sent_at;
+
+SET @has_decisions_overdue_notified_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'editorial_decisions'
+    AND column_name = 'overdue_notified_at'
+);
+SET @sql_decisions_overdue_notified_at := IF(
+  @has_decisions_overdue_notified_at = 0,
+  "ALTER TABLE editorial_decisions ADD COLUMN overdue_notified_at DATETIME(6) NULL AFTER reminder_sent_at",
+  "SELECT 1"
+);
+PREPARE stmt_decisions_overdue_notified_at FROM @sql_decisions_overdue_notified_at;
+EXECUTE stmt_decisions_overdue_notified_at;
+DEALLOCATE PREPARE stmt_decisions_overdue_notified_at;
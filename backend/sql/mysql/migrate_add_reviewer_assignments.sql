@@ -0,0 +1,20 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS reviewer_assignments (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  reviewer_id BIGINT NOT NULL,
+  assigned_by BIGINT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'invited',
+  due_at DATETIME(6) NULL,
+  responded_at DATETIME(6) NULL,
+  submitted_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_reviewer_assignments_post_reviewer (post_id, reviewer_id),
+  INDEX idx_reviewer_assignments_reviewer_status (reviewer_id, status),
+  CONSTRAINT chk_reviewer_assignments_status CHECK (status IN ('invited', 'accepted', 'declined', 'submitted')),
+  CONSTRAINT fk_reviewer_assignments_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer_assignments_reviewer_id FOREIGN KEY (reviewer_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer_assignments_assigned_by FOREIGN KEY (assigned_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
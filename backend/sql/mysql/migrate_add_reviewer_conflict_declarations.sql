@@ -0,0 +1,14 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS reviewer_conflict_declarations (
+  assignment_id BIGINT PRIMARY KEY,
+  has_coauthorship BOOLEAN NOT NULL DEFAULT FALSE,
+  has_same_institution BOOLEAN NOT NULL DEFAULT FALSE,
+  other_conflict TEXT NULL,
+  declared_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  overridden_by BIGINT NULL,
+  override_reason TEXT NULL,
+  overridden_at DATETIME(6) NULL,
+  CONSTRAINT fk_reviewer_conflict_declarations_assignment_id FOREIGN KEY (assignment_id) REFERENCES reviewer_assignments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer_conflict_declarations_overridden_by FOREIGN KEY (overridden_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
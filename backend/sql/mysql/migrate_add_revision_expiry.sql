@@ -0,0 +1,33 @@
+USE thought_manifold;
+
+SET @has_posts_revision_expiry_warned_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND column_name = 'revision_expiry_warned_at'
+);
+SET @sql_posts_revision_expiry_warned_at := IF(
+  @has_posts_revision_expiry_warned_at = 0,
+  "ALTER TABLE posts ADD COLUMN revision_expiry_warned_at DATETIME(6) NULL AFTER is_preprint",
+  "SELECT 1"
+);
+PREPARE stmt_posts_revision_expiry_warned_at FROM @sql_posts_revision_expiry_warned_at;
+EXECUTE stmt_posts_revision_expiry_warned_at;
+DEALLOCATE PREPARE stmt_posts_revision_expiry_warned_at;
+
+SET @has_revision_expiry_trigger := (
+  SELECT COUNT(*)
+  FROM information_schema.check_constraints
+  WHERE constraint_schema = DATABASE()
+    AND constraint_name = 'chk_paper_status_transitions_trigger'
+    AND check_clause LIKE '%revision_expiry%'
+);
+SET @sql_revision_expiry_trigger := IF(
+  @has_revision_expiry_trigger = 0,
+  "ALTER TABLE paper_status_transitions DROP CHECK chk_paper_status_transitions_trigger, ADD CONSTRAINT chk_paper_status_transitions_trigger CHECK (trigger_source IN ('create', 'update', 'publish', 'restore', 'ai_review', 'ai_review_rerun', 'editorial_decision', 'appeal', 'revision_expiry', 'backfill'))",
+  "SELECT 1"
+);
+PREPARE stmt_revision_expiry_trigger FROM @sql_revision_expiry_trigger;
+EXECUTE stmt_revision_expiry_trigger;
+DEALLOCATE PREPARE stmt_revision_expiry_trigger;
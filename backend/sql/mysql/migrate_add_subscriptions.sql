@@ -0,0 +1,23 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS subscriptions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  post_id BIGINT NOT NULL,
+  comment_id BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_subscriptions_user_post_comment (user_id, post_id, comment_id),
+  INDEX idx_subscriptions_post_comment (post_id, comment_id),
+  CONSTRAINT fk_subscriptions_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_subscriptions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_subscriptions_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Existing authors follow their own posts, matching what new posts get on creation.
+INSERT INTO subscriptions (user_id, post_id, comment_id, created_at)
+SELECT p.author_id, p.id, NULL, CURRENT_TIMESTAMP(6)
+FROM posts p
+WHERE NOT EXISTS (
+  SELECT 1 FROM subscriptions s
+  WHERE s.user_id = p.author_id AND s.post_id = p.id AND s.comment_id IS NULL
+);
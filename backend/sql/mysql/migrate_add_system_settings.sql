@@ -0,0 +1,12 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS system_settings (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  setting_key VARCHAR(128) NOT NULL,
+  setting_value TEXT NOT NULL,
+  updated_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_system_settings_key (setting_key),
+  CONSTRAINT fk_system_settings_updated_by FOREIGN KEY (updated_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
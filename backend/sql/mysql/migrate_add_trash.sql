@@ -0,0 +1,101 @@
+USE thought_manifold;
+
+SET @has_users_deleted_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'users'
+    AND column_name = 'deleted_at'
+);
+SET @sql_users_deleted_at := IF(
+  @has_users_deleted_at = 0,
+  "ALTER TABLE users ADD COLUMN deleted_at DATETIME(6) NULL AFTER is_admin",
+  "SELECT 1"
+);
+PREPARE stmt_users_deleted_at FROM @sql_users_deleted_at;
+EXECUTE stmt_users_deleted_at;
+DEALLOCATE PREPARE stmt_users_deleted_at;
+
+SET @has_users_trash_item_id := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'users'
+    AND column_name = 'trash_item_id'
+);
+SET @sql_users_trash_item_id := IF(
+  @has_users_trash_item_id = 0,
+  "ALTER TABLE users ADD COLUMN trash_item_id BIGINT NULL AFTER deleted_at",
+  "SELECT 1"
+);
+PREPARE stmt_users_trash_item_id FROM @sql_users_trash_item_id;
+EXECUTE stmt_users_trash_item_id;
+DEALLOCATE PREPARE stmt_users_trash_item_id;
+
+SET @has_posts_deleted_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND column_name = 'deleted_at'
+);
+SET @sql_posts_deleted_at := IF(
+  @has_posts_deleted_at = 0,
+  "ALTER TABLE posts ADD COLUMN deleted_at DATETIME(6) NULL AFTER revision_expiry_warned_at",
+  "SELECT 1"
+);
+PREPARE stmt_posts_deleted_at FROM @sql_posts_deleted_at;
+EXECUTE stmt_posts_deleted_at;
+DEALLOCATE PREPARE stmt_posts_deleted_at;
+
+SET @has_posts_trash_item_id := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND column_name = 'trash_item_id'
+);
+SET @sql_posts_trash_item_id := IF(
+  @has_posts_trash_item_id = 0,
+  "ALTER TABLE posts ADD COLUMN trash_item_id BIGINT NULL AFTER deleted_at",
+  "SELECT 1"
+);
+PREPARE stmt_posts_trash_item_id FROM @sql_posts_trash_item_id;
+EXECUTE stmt_posts_trash_item_id;
+DEALLOCATE PREPARE stmt_posts_trash_item_id;
+
+SET @has_idx_posts_trash_item_id := (
+  SELECT COUNT(*)
+  FROM information_schema.statistics
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND index_name = 'idx_posts_trash_item_id'
+);
+SET @sql_idx_posts_trash_item_id := IF(
+  @has_idx_posts_trash_item_id = 0,
+  "CREATE INDEX idx_posts_trash_item_id ON posts (trash_item_id)",
+  "SELECT 1"
+);
+PREPARE stmt_idx_posts_trash_item_id FROM @sql_idx_posts_trash_item_id;
+EXECUTE stmt_idx_posts_trash_item_id;
+DEALLOCATE PREPARE stmt_idx_posts_trash_item_id;
+
+CREATE TABLE IF NOT EXISTS trash_items (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  item_type VARCHAR(16) NOT NULL,
+  item_id BIGINT NOT NULL,
+  label VARCHAR(255) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'trashed',
+  deleted_by BIGINT NULL,
+  deleted_at DATETIME(6) NOT NULL,
+  purge_after DATETIME(6) NOT NULL,
+  restored_by BIGINT NULL,
+  restored_at DATETIME(6) NULL,
+  purged_at DATETIME(6) NULL,
+  INDEX idx_trash_items_status_purge_after (status, purge_after),
+  INDEX idx_trash_items_item (item_type, item_id),
+  CONSTRAINT chk_trash_items_item_type CHECK (item_type IN ('post', 'user')),
+  CONSTRAINT chk_trash_items_status CHECK (status IN ('trashed', 'restored', 'purged')),
+  CONSTRAINT fk_trash_items_deleted_by FOREIGN KEY (deleted_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_trash_items_restored_by FOREIGN KEY (restored_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
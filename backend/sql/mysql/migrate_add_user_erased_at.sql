@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+SET @has_users_erased_at := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'users'
+    AND column_name = 'erased_at'
+);
+SET @sql_users_erased_at := IF(
+  @has_users_erased_at = 0,
+  "ALTER TABLE users ADD COLUMN erased_at DATETIME(6) NULL AFTER trash_item_id",
+  "SELECT 1"
+);
+PREPARE stmt_users_erased_at FROM @sql_users_erased_at;
+EXECUTE stmt_users_erased_at;
+DEALLOCATE PREPARE stmt_users_erased_at;
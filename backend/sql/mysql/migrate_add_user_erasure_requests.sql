@@ -0,0 +1,17 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS user_erasure_requests (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  reason TEXT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  reviewed_by BIGINT NULL,
+  review_note TEXT NULL,
+  reviewed_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_user_erasure_requests_status_created (status, created_at),
+  INDEX idx_user_erasure_requests_user (user_id, status),
+  CONSTRAINT chk_user_erasure_requests_status CHECK (status IN ('pending', 'approved', 'rejected')),
+  CONSTRAINT fk_user_erasure_requests_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_erasure_requests_reviewed_by FOREIGN KEY (reviewed_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,11 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS user_expertise_tags (
+  user_id BIGINT NOT NULL,
+  tag_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (user_id, tag_id),
+  INDEX idx_user_expertise_tags_tag_id (tag_id),
+  CONSTRAINT fk_user_expertise_tags_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_expertise_tags_tag_id FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
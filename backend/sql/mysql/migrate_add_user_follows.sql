@@ -0,0 +1,12 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS user_follows (
+  follower_id BIGINT NOT NULL,
+  followed_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (follower_id, followed_id),
+  INDEX idx_user_follows_followed_created (followed_id, created_at),
+  INDEX idx_user_follows_follower_created (follower_id, created_at),
+  CONSTRAINT fk_user_follows_follower_id FOREIGN KEY (follower_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_follows_followed_id FOREIGN KEY (followed_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,18 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS user_suspensions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  reason TEXT NOT NULL,
+  suspended_by BIGINT NULL,
+  starts_at DATETIME(6) NOT NULL,
+  expires_at DATETIME(6) NULL,
+  lifted_at DATETIME(6) NULL,
+  lifted_by BIGINT NULL,
+  lift_reason TEXT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_user_suspensions_user (user_id, lifted_at, expires_at),
+  CONSTRAINT fk_user_suspensions_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_suspensions_suspended_by FOREIGN KEY (suspended_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_user_suspensions_lifted_by FOREIGN KEY (lifted_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,11 @@
+USE thought_manifold;
+
+CREATE TABLE IF NOT EXISTS username_history (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  old_username VARCHAR(191) NOT NULL,
+  changed_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_username_history_old_username (old_username),
+  INDEX idx_username_history_user_changed (user_id, changed_at),
+  CONSTRAINT fk_username_history_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
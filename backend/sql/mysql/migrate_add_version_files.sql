@@ -0,0 +1,19 @@
+USE thought_manifold;
+
+-- Attachments of existing versions are copied into the version store by the
+-- backend on its next start; this only adds the column that marks them.
+SET @has_paper_versions_file_sha256 := (
+  SELECT COUNT(*)
+  FROM information_schema.columns
+  WHERE table_schema = DATABASE()
+    AND table_name = 'paper_versions'
+    AND column_name = 'file_sha256'
+);
+SET @sql_paper_versions_file_sha256 := IF(
+  @has_paper_versions_file_sha256 = 0,
+  "ALTER TABLE paper_versions ADD COLUMN file_sha256 CHAR(64) NULL AFTER file_name",
+  "SELECT 1"
+);
+PREPARE stmt_paper_versions_file_sha256 FROM @sql_paper_versions_file_sha256;
+EXECUTE stmt_paper_versions_file_sha256;
+DEALLOCATE PREPARE stmt_paper_versions_file_sha256;
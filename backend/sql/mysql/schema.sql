@@ -1,11 +1,54 @@
 -- Thought Manifold MySQL Schema
 -- Recommended MySQL version: 8.0+
 -- 3NF notes:
--- 1) post_categories: category domain master (posts.category_id FK)
+-- 1) post_categories: category domain master (posts.category_id FK); admins curate it, and disabled categories take no new posts
 -- 2) post_files: optional attachment attributes split from posts
 -- 3) post_stats: mutable counters split from posts
 -- 4) citation_sources + post_citations: citation relation + source type master
 -- 5) ai_review_* + post_ai_reviews: AI paper review workflow + history
+-- 6) post_doi_registrations + post_external_citations: externally registered DOIs + inbound citations
+-- 7) metric_snapshots: daily author/journal metric history (one row per scope, subject, date)
+-- 8) author_metrics_cache: derived author indices, rebuildable from post_stats
+-- 9) post_events: append-only view/like log backing per-post analytics
+-- 10) comment_edit_history: previous comment bodies, one row per edit
+-- 11) notifications: per-user in-app inbox written by the notification dispatcher; unread rows sharing a dedup_key within the batch window are collapsed into one, counted by batch_count
+-- 12) comment_mentions: @username references from comments or review comments
+-- 13) comment_reports: user reports on comments with a moderation outcome; the excerpt survives deletion
+-- 14) comment_bans: users barred from commenting by a moderator
+-- 15) subscriptions: users following a whole post (comment_id NULL) or the replies under one comment
+-- 16) review_comment_pseudonyms: stable per-paper "Reviewer N" numbers for anonymous review comments
+-- 17) reviewer_assignments: human reviewers invited to a paper by an editor, with their response, due date and reminder state
+-- 18) editorial_decisions + editorial_decision_reviews: editor decisions on a paper version and the AI/human reviews behind them; revision requests carry a due date
+-- 19) issues: journal volumes and issues; posts.issue_id places an accepted paper in one issue
+-- 20) paper_appeals: one author appeal per rejected paper version, queued for an editor to uphold or overturn
+-- 21) paper_status_transitions: append-only log of every paper_status change with its trigger and actor; from_status is NULL when a post is created
+-- 22) reviewer_conflict_declarations: one conflict-of-interest declaration per reviewer assignment, required before accepting; a declared conflict blocks acceptance until an editor overrides it
+-- 23) post_doi_deposits: one DataCite deposit per published paper with its retry state; a registered deposit is mirrored into post_doi_registrations
+-- 24) post_import_sources: the arXiv ID or DOI a draft was imported from, with the external authors and license at import time
+-- 25) crossref_cache: Crossref metadata per DOI with an expiry; post_doi_metadata rows for external DOIs are filled from it by the refresh job
+-- 26) user_orcid_links / orcid_work_syncs: a user's linked ORCID iD with its sync opt-in, and the ORCID put-code of every paper pushed to it; work rows outlive deleted posts so the ORCID work can still be removed
+-- 27) post_repository_deposits: one SWORD v2 deposit per accepted paper with its retry state and the deposit receipt returned by the institutional repository; edit_iri is set once the metadata entry exists, media_deposited_at once the PDF is attached
+-- 28) audit_log: append-only record of admin and destructive actions with the actor, request origin and JSON snapshots of the target before and after; actor_username and target ids are kept as plain values so entries outlive the rows they describe
+-- 29) user_suspensions: suspension history per user; a row without expires_at is a permanent ban, and a row is active until it expires or lifted_at is set
+-- 30) system_settings: admin overrides of runtime settings keyed by the setting's environment variable name; a missing row falls back to the environment, then to the built-in default
+-- 31) feature_flags / feature_flag_roles: admin overrides of built-in feature flags with a percentage rollout, and the roles a flag is limited to; a flag without role rows applies to every role
+-- 32) announcements: site-wide notices shown between starts_at and ends_at (open-ended when NULL) to everyone, signed-in users or admins
+-- 33) user_erasure_requests: a user's request to erase their account, approved or rejected by an admin; approval anonymizes the users row in place so authored papers, review comments and citations keep their ids, and sets users.erased_at so the account's tokens stop working
+-- 34) ai_review_backfills / ai_review_backfill_items: admin-started bulk AI re-reviews and the papers each one covers; a background job schedules queued items in throttled batches, and backfill reviews are advisory like appeal reviews
+-- 35) database_backups: mysqldump files written to BACKUP_DIR on a schedule or by an admin; expired rows are kept after retention removes their file
+-- 36) request_events / ip_blocks: source IP and user agent of auth and write requests, purged after REQUEST_EVENT_RETENTION_DAYS, and the client addresses an admin barred from them; a block without expires_at is permanent
+-- 37) broadcasts / broadcast_recipients: admin announcements delivered as in-app notifications to every user or a role/activity segment, optionally scheduled; recipients are snapshotted when sending starts and each delivery keeps its own status
+-- 38) post_stat_rollups: hourly and daily view/like/unlike counts per post, aggregated from post_events by the rollup job; hourly rows are kept for POST_STAT_HOURLY_RETENTION_DAYS and also feed post_stats.trending_score
+-- 38) trash_items: posts and users an admin deleted, kept for TRASH_RETENTION_DAYS so they can be restored before the purge job removes them; while trashed, posts.trash_item_id / users.trash_item_id point at the item and deleted_at hides the row
+-- 39) notification_preferences: per-user overrides of the in-app / email defaults for each notification event type; event types without a row use the defaults in notifications::preferences
+-- 40) push_subscriptions: browser Web Push endpoints (one row per endpoint, re-registering moves it to the current user); removed when the push service reports the endpoint gone (404/410) or after PUSH_MAX_FAILURES failures in a row
+-- 41) outgoing_webhooks: admin-configured Slack / Discord incoming-webhook URLs that receive editorial alerts (new submissions, failed AI reviews, content reports), each event toggled per webhook
+-- 42) calendar_feed_tokens: one secret iCalendar feed token per user, stored as a SHA-256 hash; calendar apps pass it as ?token= since they cannot send a bearer header, and rotating it replaces the row
+-- 43) user_follows: one row per user following another author (self-follows are rejected by the API); drives the following feed and new-post notifications
+-- 44) user_blocks: users hidden from the blocker's comment threads and barred from commenting on the blocker's posts
+-- 45) username_history: usernames a user gave up, kept reserved so old profile links and legacy username-subject tokens still resolve to them; also drives the USERNAME_CHANGE_COOLDOWN_DAYS cooldown
+-- 46) user_expertise_tags: expertise a user declares, drawn from the same tags vocabulary as post_tags so reviewer suggestions can rank by overlap with a submission's tags
+-- 47) account_events: per-user sign-in and account-change history shown on the account activity page
 
 CREATE DATABASE IF NOT EXISTS thought_manifold
   CHARACTER SET utf8mb4
@@ -25,8 +68,14 @@ CREATE TABLE IF NOT EXISTS users (
   hobbies TEXT NULL,
   interests TEXT NULL,
   research_areas TEXT NULL,
+  affiliation VARCHAR(255) NULL,
+  website VARCHAR(512) NULL,
+  orcid_id VARCHAR(19) NULL,
   avatar_url TEXT NULL,
   is_admin BOOLEAN NOT NULL DEFAULT FALSE,
+  deleted_at DATETIME(6) NULL,
+  trash_item_id BIGINT NULL,
+  erased_at DATETIME(6) NULL,
   created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
   updated_at DATETIME(6) NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -34,7 +83,8 @@ CREATE TABLE IF NOT EXISTS users (
 CREATE TABLE IF NOT EXISTS post_categories (
   id SMALLINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
   code VARCHAR(64) NOT NULL UNIQUE,
-  display_name VARCHAR(128) NOT NULL
+  display_name VARCHAR(128) NOT NULL,
+  is_active BOOLEAN NOT NULL DEFAULT TRUE
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
 CREATE TABLE IF NOT EXISTS posts (
@@ -50,13 +100,22 @@ CREATE TABLE IF NOT EXISTS posts (
   paper_status VARCHAR(32) NOT NULL DEFAULT 'published',
   current_revision INT UNSIGNED NOT NULL DEFAULT 0,
   latest_paper_version_id BIGINT NULL,
+  issue_id BIGINT NULL,
+  is_double_blind BOOLEAN NOT NULL DEFAULT FALSE,
+  is_preprint BOOLEAN NOT NULL DEFAULT FALSE,
+  revision_expiry_warned_at DATETIME(6) NULL,
+  deleted_at DATETIME(6) NULL,
+  trash_item_id BIGINT NULL,
   created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
   updated_at DATETIME(6) NULL,
   INDEX idx_posts_author_id (author_id),
+  INDEX idx_posts_author_updated (author_id, updated_at),
   INDEX idx_posts_published_created_at (is_published, created_at),
   INDEX idx_posts_category_created_at (category_id, created_at),
   INDEX idx_posts_paper_status_created_at (paper_status, created_at),
   INDEX idx_posts_latest_paper_version_id (latest_paper_version_id),
+  INDEX idx_posts_issue_id (issue_id),
+  INDEX idx_posts_trash_item_id (trash_item_id),
   CONSTRAINT chk_posts_paper_status CHECK (paper_status IN ('draft', 'submitted', 'revision', 'accepted', 'published', 'rejected')),
   CONSTRAINT fk_posts_category_id FOREIGN KEY (category_id) REFERENCES post_categories(id),
   CONSTRAINT fk_posts_author_id FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE
@@ -75,6 +134,10 @@ CREATE TABLE IF NOT EXISTS post_stats (
   post_id BIGINT PRIMARY KEY,
   view_count BIGINT NOT NULL DEFAULT 0,
   like_count BIGINT NOT NULL DEFAULT 0,
+  citation_count BIGINT NOT NULL DEFAULT 0,
+  external_citation_count BIGINT NOT NULL DEFAULT 0,
+  influence_score DOUBLE NOT NULL DEFAULT 0,
+  trending_score DOUBLE NOT NULL DEFAULT 0,
   updated_at DATETIME(6) NULL,
   CONSTRAINT fk_post_stats_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -98,6 +161,7 @@ CREATE TABLE IF NOT EXISTS comments (
   content TEXT NOT NULL,
   is_deleted BOOLEAN NOT NULL DEFAULT FALSE,
   deleted_at DATETIME(6) NULL,
+  edited_at DATETIME(6) NULL,
   created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
   updated_at DATETIME(6) NULL,
   INDEX idx_comments_post_id_created_at (post_id, created_at),
@@ -108,6 +172,64 @@ CREATE TABLE IF NOT EXISTS comments (
   CONSTRAINT fk_comments_parent_comment_id FOREIGN KEY (parent_comment_id) REFERENCES comments(id) ON DELETE SET NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
+CREATE TABLE IF NOT EXISTS comment_edit_history (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  comment_id BIGINT NOT NULL,
+  editor_id BIGINT NULL,
+  previous_content TEXT NOT NULL,
+  edited_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_comment_edit_history_comment_edited (comment_id, edited_at),
+  CONSTRAINT fk_comment_edit_history_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_edit_history_editor_id FOREIGN KEY (editor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS comment_reports (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  comment_id BIGINT NULL,
+  post_id BIGINT NOT NULL,
+  comment_author_id BIGINT NULL,
+  reporter_id BIGINT NULL,
+  comment_excerpt TEXT NOT NULL,
+  reason VARCHAR(1000) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'open',
+  comment_deleted BOOLEAN NOT NULL DEFAULT FALSE,
+  author_banned BOOLEAN NOT NULL DEFAULT FALSE,
+  resolved_by BIGINT NULL,
+  resolution_note TEXT NULL,
+  resolved_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_comment_reports_comment_reporter (comment_id, reporter_id),
+  INDEX idx_comment_reports_status_created (status, created_at),
+  CONSTRAINT chk_comment_reports_status CHECK (status IN ('open', 'dismissed', 'actioned')),
+  CONSTRAINT fk_comment_reports_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE SET NULL,
+  CONSTRAINT fk_comment_reports_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_reports_comment_author_id FOREIGN KEY (comment_author_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_comment_reports_reporter_id FOREIGN KEY (reporter_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_comment_reports_resolved_by FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS comment_bans (
+  user_id BIGINT PRIMARY KEY,
+  reason TEXT NULL,
+  banned_by BIGINT NULL,
+  banned_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  CONSTRAINT fk_comment_bans_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_bans_banned_by FOREIGN KEY (banned_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS subscriptions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  post_id BIGINT NOT NULL,
+  comment_id BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_subscriptions_user_post_comment (user_id, post_id, comment_id),
+  INDEX idx_subscriptions_post_comment (post_id, comment_id),
+  CONSTRAINT fk_subscriptions_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_subscriptions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_subscriptions_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
 CREATE TABLE IF NOT EXISTS tags (
   id BIGINT AUTO_INCREMENT PRIMARY KEY,
   name VARCHAR(191) NOT NULL UNIQUE
@@ -133,6 +255,7 @@ CREATE TABLE IF NOT EXISTS post_citations (
   cited_post_id BIGINT NOT NULL,
   citation_source_id TINYINT UNSIGNED NOT NULL,
   created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  retraction_flagged_at DATETIME(6) NULL,
   PRIMARY KEY (citing_post_id, cited_post_id, citation_source_id),
   CONSTRAINT chk_post_citations_no_self CHECK (citing_post_id <> cited_post_id),
   INDEX idx_post_citations_citation_source_id (citation_source_id),
@@ -142,6 +265,103 @@ CREATE TABLE IF NOT EXISTS post_citations (
   CONSTRAINT fk_post_citations_source_id FOREIGN KEY (citation_source_id) REFERENCES citation_sources(id)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
+CREATE TABLE IF NOT EXISTS post_retractions (
+  post_id BIGINT PRIMARY KEY,
+  reason TEXT NOT NULL,
+  retracted_by BIGINT NULL,
+  retracted_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_retractions_retracted_at (retracted_at),
+  CONSTRAINT fk_post_retractions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_retractions_retracted_by FOREIGN KEY (retracted_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_external_references (
+  citing_post_id BIGINT NOT NULL,
+  doi VARCHAR(255) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (citing_post_id, doi),
+  INDEX idx_post_external_references_doi (doi),
+  CONSTRAINT fk_post_external_references_citing_post_id FOREIGN KEY (citing_post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_citation_audit_log (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  actor_id BIGINT NULL,
+  action VARCHAR(16) NOT NULL,
+  cited_post_id BIGINT NULL,
+  cited_doi VARCHAR(255) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_citation_audit_log_post_created (post_id, created_at),
+  CONSTRAINT chk_post_citation_audit_log_action CHECK (action IN ('added', 'removed')),
+  CONSTRAINT fk_post_citation_audit_log_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_citation_audit_log_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS metric_snapshots (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  scope VARCHAR(16) NOT NULL,
+  user_id BIGINT NULL,
+  snapshot_date DATE NOT NULL,
+  g_index BIGINT NULL,
+  h_index BIGINT NULL,
+  i10_index BIGINT NULL,
+  total_citations BIGINT NULL,
+  paper_count BIGINT NULL,
+  journal_year INT NULL,
+  impact_factor DOUBLE NULL,
+  numerator_citations BIGINT NULL,
+  denominator_papers BIGINT NULL,
+  metric_version VARCHAR(16) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_metric_snapshots_scope_user_date (scope, user_id, snapshot_date),
+  INDEX idx_metric_snapshots_scope_date (scope, snapshot_date),
+  CONSTRAINT chk_metric_snapshots_scope CHECK (scope IN ('author', 'journal')),
+  CONSTRAINT fk_metric_snapshots_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS author_metrics_cache (
+  user_id BIGINT PRIMARY KEY,
+  g_index BIGINT NOT NULL DEFAULT 0,
+  h_index BIGINT NOT NULL DEFAULT 0,
+  i10_index BIGINT NOT NULL DEFAULT 0,
+  total_citations BIGINT NOT NULL DEFAULT 0,
+  paper_count BIGINT NOT NULL DEFAULT 0,
+  metric_version VARCHAR(16) NOT NULL,
+  computed_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_author_metrics_cache_g_index (g_index),
+  INDEX idx_author_metrics_cache_h_index (h_index),
+  CONSTRAINT fk_author_metrics_cache_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_events (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  event_type VARCHAR(16) NOT NULL,
+  user_id BIGINT NULL,
+  referrer_host VARCHAR(255) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_events_post_type_created (post_id, event_type, created_at),
+  INDEX idx_post_events_created (created_at),
+  CONSTRAINT chk_post_events_event_type CHECK (event_type IN ('view', 'like', 'unlike')),
+  CONSTRAINT fk_post_events_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_events_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_stat_rollups (
+  post_id BIGINT NOT NULL,
+  granularity VARCHAR(8) NOT NULL,
+  bucket_start DATETIME NOT NULL,
+  views BIGINT NOT NULL DEFAULT 0,
+  likes BIGINT NOT NULL DEFAULT 0,
+  unlikes BIGINT NOT NULL DEFAULT 0,
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (post_id, granularity, bucket_start),
+  INDEX idx_post_stat_rollups_granularity_bucket (granularity, bucket_start),
+  CONSTRAINT chk_post_stat_rollups_granularity CHECK (granularity IN ('hour', 'day')),
+  CONSTRAINT fk_post_stat_rollups_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
 CREATE TABLE IF NOT EXISTS post_doi_metadata (
   id BIGINT AUTO_INCREMENT PRIMARY KEY,
   post_id BIGINT NOT NULL,
@@ -159,6 +379,92 @@ CREATE TABLE IF NOT EXISTS post_doi_metadata (
   CONSTRAINT fk_post_doi_metadata_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
+CREATE TABLE IF NOT EXISTS crossref_cache (
+  doi VARCHAR(255) PRIMARY KEY,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  title TEXT NULL,
+  journal VARCHAR(512) NULL,
+  publisher VARCHAR(512) NULL,
+  published_at VARCHAR(32) NULL,
+  source_url VARCHAR(2048) NULL,
+  raw_json JSON NULL,
+  fetched_at DATETIME(6) NULL,
+  expires_at DATETIME(6) NOT NULL,
+  last_error TEXT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_crossref_cache_expires (expires_at),
+  CONSTRAINT chk_crossref_cache_status CHECK (status IN ('pending', 'found', 'not_found'))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_doi_registrations (
+  post_id BIGINT PRIMARY KEY,
+  internal_doi VARCHAR(255) NOT NULL,
+  registered_doi VARCHAR(255) NOT NULL,
+  registration_agency VARCHAR(32) NOT NULL,
+  openalex_work_id VARCHAR(64) NULL,
+  external_cited_by_count BIGINT NULL,
+  citations_synced_at DATETIME(6) NULL,
+  last_sync_error TEXT NULL,
+  registered_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_post_doi_registrations_registered_doi (registered_doi),
+  INDEX idx_post_doi_registrations_synced_at (citations_synced_at),
+  CONSTRAINT fk_post_doi_registrations_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_doi_deposits (
+  post_id BIGINT PRIMARY KEY,
+  doi VARCHAR(255) NOT NULL,
+  registration_agency VARCHAR(32) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  attempts INT NOT NULL DEFAULT 0,
+  last_error TEXT NULL,
+  next_attempt_at DATETIME(6) NULL,
+  registered_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_post_doi_deposits_doi (doi),
+  INDEX idx_post_doi_deposits_due (status, next_attempt_at),
+  CONSTRAINT chk_post_doi_deposits_status CHECK (status IN ('pending', 'registered', 'failed')),
+  CONSTRAINT fk_post_doi_deposits_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_import_sources (
+  post_id BIGINT PRIMARY KEY,
+  source_type VARCHAR(16) NOT NULL,
+  source_identifier VARCHAR(255) NOT NULL,
+  source_url VARCHAR(2048) NOT NULL,
+  source_doi VARCHAR(255) NULL,
+  authors_json JSON NULL,
+  license_url VARCHAR(2048) NULL,
+  pdf_imported BOOLEAN NOT NULL DEFAULT FALSE,
+  imported_by BIGINT NULL,
+  imported_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_post_import_sources_source (source_type, source_identifier),
+  INDEX idx_post_import_sources_imported_by (imported_by),
+  CONSTRAINT chk_post_import_sources_type CHECK (source_type IN ('arxiv', 'doi')),
+  CONSTRAINT fk_post_import_sources_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_import_sources_imported_by FOREIGN KEY (imported_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_external_citations (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  cited_post_id BIGINT NOT NULL,
+  citation_source_id TINYINT UNSIGNED NOT NULL,
+  external_work_id VARCHAR(255) NOT NULL,
+  citing_doi VARCHAR(255) NULL,
+  citing_title TEXT NULL,
+  citing_venue VARCHAR(512) NULL,
+  citing_year SMALLINT NULL,
+  first_seen_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  last_seen_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_post_external_citations_work (cited_post_id, citation_source_id, external_work_id),
+  INDEX idx_post_external_citations_source_id (citation_source_id),
+  CONSTRAINT fk_post_external_citations_cited_post_id FOREIGN KEY (cited_post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_post_external_citations_source_id FOREIGN KEY (citation_source_id) REFERENCES citation_sources(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
 CREATE TABLE IF NOT EXISTS paper_versions (
   id BIGINT AUTO_INCREMENT PRIMARY KEY,
   post_id BIGINT NOT NULL,
@@ -169,6 +475,7 @@ CREATE TABLE IF NOT EXISTS paper_versions (
   github_url VARCHAR(2048) NULL,
   file_path TEXT NULL,
   file_name VARCHAR(255) NULL,
+  file_sha256 CHAR(64) NULL,
   tags_json JSON NULL,
   citations_json JSON NULL,
   submitted_by BIGINT NULL,
@@ -223,6 +530,7 @@ CREATE TABLE IF NOT EXISTS post_ai_reviews (
   input_snapshot_json JSON NULL,
   raw_response_json JSON NULL,
   error_message TEXT NULL,
+  request_id VARCHAR(64) NULL,
   created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
   completed_at DATETIME(6) NULL,
   INDEX idx_post_ai_reviews_version_created (paper_version_id, created_at),
@@ -249,6 +557,14 @@ CREATE TABLE IF NOT EXISTS paper_review_comments (
   content TEXT NOT NULL,
   is_deleted BOOLEAN NOT NULL DEFAULT FALSE,
   deleted_at DATETIME(6) NULL,
+  is_anonymous BOOLEAN NOT NULL DEFAULT FALSE,
+  resolution_status VARCHAR(16) NOT NULL DEFAULT 'open',
+  resolved_by BIGINT NULL,
+  resolved_at DATETIME(6) NULL,
+  resolution_note TEXT NULL,
+  anchor_start INT NULL,
+  anchor_end INT NULL,
+  anchor_quote TEXT NULL,
   created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
   updated_at DATETIME(6) NULL,
   INDEX idx_paper_review_comments_post_version_created (post_id, paper_version_id, created_at),
@@ -257,7 +573,579 @@ CREATE TABLE IF NOT EXISTS paper_review_comments (
   CONSTRAINT fk_paper_review_comments_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
   CONSTRAINT fk_paper_review_comments_version_id FOREIGN KEY (paper_version_id) REFERENCES paper_versions(id) ON DELETE SET NULL,
   CONSTRAINT fk_paper_review_comments_author_id FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE,
-  CONSTRAINT fk_paper_review_comments_parent_id FOREIGN KEY (parent_comment_id) REFERENCES paper_review_comments(id) ON DELETE SET NULL
+  CONSTRAINT fk_paper_review_comments_parent_id FOREIGN KEY (parent_comment_id) REFERENCES paper_review_comments(id) ON DELETE SET NULL,
+  CONSTRAINT fk_paper_review_comments_resolved_by FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT chk_paper_review_comments_resolution_status CHECK (resolution_status IN ('open', 'addressed', 'wont_fix'))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS review_comment_pseudonyms (
+  post_id BIGINT NOT NULL,
+  user_id BIGINT NOT NULL,
+  pseudonym_number INT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (post_id, user_id),
+  UNIQUE KEY uq_review_comment_pseudonyms_post_number (post_id, pseudonym_number),
+  CONSTRAINT fk_review_comment_pseudonyms_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_review_comment_pseudonyms_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS reviewer_assignments (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  reviewer_id BIGINT NOT NULL,
+  assigned_by BIGINT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'invited',
+  due_at DATETIME(6) NULL,
+  responded_at DATETIME(6) NULL,
+  submitted_at DATETIME(6) NULL,
+  reminder_sent_at DATETIME(6) NULL,
+  overdue_notified_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_reviewer_assignments_post_reviewer (post_id, reviewer_id),
+  INDEX idx_reviewer_assignments_reviewer_status (reviewer_id, status),
+  CONSTRAINT chk_reviewer_assignments_status CHECK (status IN ('invited', 'accepted', 'declined', 'submitted')),
+  CONSTRAINT fk_reviewer_assignments_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer_assignments_reviewer_id FOREIGN KEY (reviewer_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer_assignments_assigned_by FOREIGN KEY (assigned_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS reviewer_conflict_declarations (
+  assignment_id BIGINT PRIMARY KEY,
+  has_coauthorship BOOLEAN NOT NULL DEFAULT FALSE,
+  has_same_institution BOOLEAN NOT NULL DEFAULT FALSE,
+  other_conflict TEXT NULL,
+  declared_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  overridden_by BIGINT NULL,
+  override_reason TEXT NULL,
+  overridden_at DATETIME(6) NULL,
+  CONSTRAINT fk_reviewer_conflict_declarations_assignment_id FOREIGN KEY (assignment_id) REFERENCES reviewer_assignments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_reviewer_conflict_declarations_overridden_by FOREIGN KEY (overridden_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS editorial_decisions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  paper_version_id BIGINT NULL,
+  editor_id BIGINT NULL,
+  decision_id TINYINT UNSIGNED NOT NULL,
+  ai_review_id BIGINT NULL,
+  previous_status VARCHAR(32) NOT NULL,
+  new_status VARCHAR(32) NOT NULL,
+  note TEXT NULL,
+  revision_due_at DATETIME(6) NULL,
+  reminder_sent_at DATETIME(6) NULL,
+  overdue_notified_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_editorial_decisions_post_created (post_id, created_at),
+  INDEX idx_editorial_decisions_post_version (post_id, paper_version_id),
+  CONSTRAINT fk_editorial_decisions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_editorial_decisions_version_id FOREIGN KEY (paper_version_id) REFERENCES paper_versions(id) ON DELETE SET NULL,
+  CONSTRAINT fk_editorial_decisions_editor_id FOREIGN KEY (editor_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_editorial_decisions_decision_id FOREIGN KEY (decision_id) REFERENCES ai_review_decisions(id),
+  CONSTRAINT fk_editorial_decisions_ai_review_id FOREIGN KEY (ai_review_id) REFERENCES post_ai_reviews(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS editorial_decision_reviews (
+  decision_id BIGINT NOT NULL,
+  assignment_id BIGINT NOT NULL,
+  PRIMARY KEY (decision_id, assignment_id),
+  INDEX idx_editorial_decision_reviews_assignment (assignment_id),
+  CONSTRAINT fk_editorial_decision_reviews_decision_id FOREIGN KEY (decision_id) REFERENCES editorial_decisions(id) ON DELETE CASCADE,
+  CONSTRAINT fk_editorial_decision_reviews_assignment_id FOREIGN KEY (assignment_id) REFERENCES reviewer_assignments(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS issues (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  volume INT UNSIGNED NOT NULL,
+  number INT UNSIGNED NOT NULL,
+  title VARCHAR(255) NULL,
+  publication_date DATE NULL,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_issues_volume_number (volume, number),
+  INDEX idx_issues_publication_date (publication_date),
+  CONSTRAINT fk_issues_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+SET @has_fk_posts_issue_id := (
+  SELECT COUNT(*)
+  FROM information_schema.table_constraints
+  WHERE table_schema = DATABASE()
+    AND table_name = 'posts'
+    AND constraint_name = 'fk_posts_issue_id'
+);
+SET @sql_fk_posts_issue_id := IF(
+  @has_fk_posts_issue_id = 0,
+  "ALTER TABLE posts ADD CONSTRAINT fk_posts_issue_id FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE SET NULL",
+  "SELECT 1"
+);
+PREPARE stmt_fk_posts_issue_id FROM @sql_fk_posts_issue_id;
+EXECUTE stmt_fk_posts_issue_id;
+DEALLOCATE PREPARE stmt_fk_posts_issue_id;
+
+CREATE TABLE IF NOT EXISTS paper_appeals (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  paper_version_id BIGINT NULL,
+  author_id BIGINT NOT NULL,
+  justification TEXT NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  ai_review_id BIGINT NULL,
+  resolved_by BIGINT NULL,
+  resolution_note TEXT NULL,
+  resulting_status VARCHAR(32) NULL,
+  resolved_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_paper_appeals_post_version (post_id, paper_version_id),
+  INDEX idx_paper_appeals_status_created (status, created_at),
+  CONSTRAINT fk_paper_appeals_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_paper_appeals_version_id FOREIGN KEY (paper_version_id) REFERENCES paper_versions(id) ON DELETE SET NULL,
+  CONSTRAINT fk_paper_appeals_author_id FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_paper_appeals_ai_review_id FOREIGN KEY (ai_review_id) REFERENCES post_ai_reviews(id) ON DELETE SET NULL,
+  CONSTRAINT fk_paper_appeals_resolved_by FOREIGN KEY (resolved_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT chk_paper_appeals_status CHECK (status IN ('pending', 'upheld', 'overturned'))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS paper_status_transitions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  post_id BIGINT NOT NULL,
+  actor_id BIGINT NULL,
+  trigger_source VARCHAR(32) NOT NULL,
+  from_status VARCHAR(32) NULL,
+  to_status VARCHAR(32) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_paper_status_transitions_post_created (post_id, created_at),
+  CONSTRAINT chk_paper_status_transitions_trigger CHECK (trigger_source IN ('create', 'update', 'publish', 'restore', 'ai_review', 'ai_review_rerun', 'editorial_decision', 'appeal', 'revision_expiry', 'backfill')),
+  CONSTRAINT fk_paper_status_transitions_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_paper_status_transitions_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS user_orcid_links (
+  user_id BIGINT PRIMARY KEY,
+  orcid_id VARCHAR(19) NOT NULL,
+  access_token VARCHAR(255) NOT NULL,
+  refresh_token VARCHAR(255) NULL,
+  scope VARCHAR(255) NULL,
+  token_expires_at DATETIME(6) NULL,
+  sync_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+  last_synced_at DATETIME(6) NULL,
+  last_sync_error TEXT NULL,
+  linked_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  UNIQUE KEY uq_user_orcid_links_orcid_id (orcid_id),
+  INDEX idx_user_orcid_links_sync (sync_enabled, last_synced_at),
+  CONSTRAINT fk_user_orcid_links_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS orcid_work_syncs (
+  user_id BIGINT NOT NULL,
+  post_id BIGINT NOT NULL,
+  put_code VARCHAR(32) NULL,
+  payload_hash CHAR(64) NULL,
+  status VARCHAR(16) NOT NULL,
+  last_error TEXT NULL,
+  synced_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  PRIMARY KEY (user_id, post_id),
+  INDEX idx_orcid_work_syncs_post (post_id),
+  CONSTRAINT chk_orcid_work_syncs_status CHECK (status IN ('synced', 'failed')),
+  CONSTRAINT fk_orcid_work_syncs_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS post_repository_deposits (
+  post_id BIGINT PRIMARY KEY,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  attempts INT NOT NULL DEFAULT 0,
+  last_error TEXT NULL,
+  next_attempt_at DATETIME(6) NULL,
+  edit_iri VARCHAR(2048) NULL,
+  edit_media_iri VARCHAR(2048) NULL,
+  statement_iri VARCHAR(2048) NULL,
+  splash_url VARCHAR(2048) NULL,
+  receipt_xml LONGTEXT NULL,
+  media_deposited_at DATETIME(6) NULL,
+  deposited_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_post_repository_deposits_due (status, next_attempt_at),
+  CONSTRAINT chk_post_repository_deposits_status CHECK (status IN ('pending', 'deposited', 'failed')),
+  CONSTRAINT fk_post_repository_deposits_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS audit_log (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  actor_id BIGINT NULL,
+  actor_username VARCHAR(191) NULL,
+  action VARCHAR(64) NOT NULL,
+  target_type VARCHAR(32) NOT NULL,
+  target_id BIGINT NULL,
+  ip_address VARCHAR(64) NULL,
+  user_agent VARCHAR(512) NULL,
+  before_snapshot JSON NULL,
+  after_snapshot JSON NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_audit_log_created_at (created_at),
+  INDEX idx_audit_log_actor (actor_id, created_at),
+  INDEX idx_audit_log_action (action, created_at),
+  INDEX idx_audit_log_target (target_type, target_id, created_at),
+  CONSTRAINT fk_audit_log_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS user_suspensions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  reason TEXT NOT NULL,
+  suspended_by BIGINT NULL,
+  starts_at DATETIME(6) NOT NULL,
+  expires_at DATETIME(6) NULL,
+  lifted_at DATETIME(6) NULL,
+  lifted_by BIGINT NULL,
+  lift_reason TEXT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_user_suspensions_user (user_id, lifted_at, expires_at),
+  CONSTRAINT fk_user_suspensions_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_suspensions_suspended_by FOREIGN KEY (suspended_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_user_suspensions_lifted_by FOREIGN KEY (lifted_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS system_settings (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  setting_key VARCHAR(128) NOT NULL,
+  setting_value TEXT NOT NULL,
+  updated_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_system_settings_key (setting_key),
+  CONSTRAINT fk_system_settings_updated_by FOREIGN KEY (updated_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  flag_key VARCHAR(64) NOT NULL,
+  enabled BOOLEAN NOT NULL,
+  rollout_percent TINYINT UNSIGNED NOT NULL DEFAULT 100,
+  updated_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_feature_flags_key (flag_key),
+  CONSTRAINT chk_feature_flags_rollout_percent CHECK (rollout_percent <= 100),
+  CONSTRAINT fk_feature_flags_updated_by FOREIGN KEY (updated_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS feature_flag_roles (
+  flag_id BIGINT NOT NULL,
+  role VARCHAR(32) NOT NULL,
+  PRIMARY KEY (flag_id, role),
+  CONSTRAINT chk_feature_flag_roles_role CHECK (role IN ('user', 'admin')),
+  CONSTRAINT fk_feature_flag_roles_flag_id FOREIGN KEY (flag_id) REFERENCES feature_flags(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS announcements (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  message TEXT NOT NULL,
+  severity VARCHAR(16) NOT NULL DEFAULT 'info',
+  audience VARCHAR(16) NOT NULL DEFAULT 'all',
+  starts_at DATETIME(6) NOT NULL,
+  ends_at DATETIME(6) NULL,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_announcements_window (starts_at, ends_at),
+  CONSTRAINT chk_announcements_severity CHECK (severity IN ('info', 'warning', 'critical')),
+  CONSTRAINT chk_announcements_audience CHECK (audience IN ('all', 'users', 'admins')),
+  CONSTRAINT fk_announcements_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS user_erasure_requests (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  reason TEXT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  reviewed_by BIGINT NULL,
+  review_note TEXT NULL,
+  reviewed_at DATETIME(6) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_user_erasure_requests_status_created (status, created_at),
+  INDEX idx_user_erasure_requests_user (user_id, status),
+  CONSTRAINT chk_user_erasure_requests_status CHECK (status IN ('pending', 'approved', 'rejected')),
+  CONSTRAINT fk_user_erasure_requests_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_erasure_requests_reviewed_by FOREIGN KEY (reviewed_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS ai_review_backfills (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  status VARCHAR(16) NOT NULL DEFAULT 'running',
+  prompt_version VARCHAR(32) NULL,
+  paper_status VARCHAR(32) NULL,
+  batch_size INT UNSIGNED NOT NULL,
+  total_items INT UNSIGNED NOT NULL DEFAULT 0,
+  created_by BIGINT NULL,
+  cancelled_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  finished_at DATETIME(6) NULL,
+  INDEX idx_ai_review_backfills_status_created (status, created_at),
+  CONSTRAINT chk_ai_review_backfills_status CHECK (status IN ('running', 'completed', 'cancelled')),
+  CONSTRAINT fk_ai_review_backfills_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_ai_review_backfills_cancelled_by FOREIGN KEY (cancelled_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS ai_review_backfill_items (
+  backfill_id BIGINT NOT NULL,
+  post_id BIGINT NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'queued',
+  review_id BIGINT NULL,
+  error_message TEXT NULL,
+  processed_at DATETIME(6) NULL,
+  PRIMARY KEY (backfill_id, post_id),
+  INDEX idx_ai_review_backfill_items_status (backfill_id, status),
+  CONSTRAINT chk_ai_review_backfill_items_status CHECK (status IN ('queued', 'scheduled', 'failed', 'cancelled')),
+  CONSTRAINT fk_ai_review_backfill_items_backfill_id FOREIGN KEY (backfill_id) REFERENCES ai_review_backfills(id) ON DELETE CASCADE,
+  CONSTRAINT fk_ai_review_backfill_items_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_ai_review_backfill_items_review_id FOREIGN KEY (review_id) REFERENCES post_ai_reviews(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS database_backups (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  file_name VARCHAR(255) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'running',
+  trigger_source VARCHAR(16) NOT NULL,
+  size_bytes BIGINT NULL,
+  error_message TEXT NULL,
+  started_by BIGINT NULL,
+  started_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  finished_at DATETIME(6) NULL,
+  UNIQUE KEY uq_database_backups_file_name (file_name),
+  INDEX idx_database_backups_status_started (status, started_at),
+  CONSTRAINT chk_database_backups_status CHECK (status IN ('running', 'completed', 'failed', 'expired')),
+  CONSTRAINT chk_database_backups_trigger_source CHECK (trigger_source IN ('scheduled', 'manual')),
+  CONSTRAINT fk_database_backups_started_by FOREIGN KEY (started_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS request_events (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  event_type VARCHAR(16) NOT NULL,
+  method VARCHAR(8) NOT NULL,
+  path VARCHAR(255) NOT NULL,
+  status_code SMALLINT UNSIGNED NOT NULL,
+  user_id BIGINT NULL,
+  ip_address VARCHAR(64) NULL,
+  user_agent VARCHAR(512) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_request_events_ip_created (ip_address, created_at),
+  INDEX idx_request_events_user_created (user_id, created_at),
+  INDEX idx_request_events_created (created_at),
+  CONSTRAINT chk_request_events_event_type CHECK (event_type IN ('auth', 'write')),
+  CONSTRAINT fk_request_events_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS ip_blocks (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  ip_address VARCHAR(64) NOT NULL,
+  reason TEXT NULL,
+  blocked_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  expires_at DATETIME(6) NULL,
+  UNIQUE KEY uq_ip_blocks_ip_address (ip_address),
+  CONSTRAINT fk_ip_blocks_blocked_by FOREIGN KEY (blocked_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS broadcasts (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  subject VARCHAR(255) NOT NULL,
+  body TEXT NOT NULL,
+  audience VARCHAR(16) NOT NULL DEFAULT 'all',
+  active_within_days INT UNSIGNED NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'scheduled',
+  scheduled_at DATETIME(6) NOT NULL,
+  recipient_count INT UNSIGNED NOT NULL DEFAULT 0,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  started_at DATETIME(6) NULL,
+  finished_at DATETIME(6) NULL,
+  INDEX idx_broadcasts_status_scheduled (status, scheduled_at),
+  CONSTRAINT chk_broadcasts_status CHECK (status IN ('scheduled', 'sending', 'sent', 'cancelled')),
+  CONSTRAINT chk_broadcasts_audience CHECK (audience IN ('all', 'users', 'admins')),
+  CONSTRAINT fk_broadcasts_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS broadcast_recipients (
+  broadcast_id BIGINT NOT NULL,
+  user_id BIGINT NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending',
+  error_message VARCHAR(512) NULL,
+  delivered_at DATETIME(6) NULL,
+  PRIMARY KEY (broadcast_id, user_id),
+  INDEX idx_broadcast_recipients_status (broadcast_id, status),
+  CONSTRAINT chk_broadcast_recipients_status CHECK (status IN ('pending', 'sent', 'failed')),
+  CONSTRAINT fk_broadcast_recipients_broadcast_id FOREIGN KEY (broadcast_id) REFERENCES broadcasts(id) ON DELETE CASCADE,
+  CONSTRAINT fk_broadcast_recipients_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS trash_items (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  item_type VARCHAR(16) NOT NULL,
+  item_id BIGINT NOT NULL,
+  label VARCHAR(255) NOT NULL,
+  status VARCHAR(16) NOT NULL DEFAULT 'trashed',
+  deleted_by BIGINT NULL,
+  deleted_at DATETIME(6) NOT NULL,
+  purge_after DATETIME(6) NOT NULL,
+  restored_by BIGINT NULL,
+  restored_at DATETIME(6) NULL,
+  purged_at DATETIME(6) NULL,
+  INDEX idx_trash_items_status_purge_after (status, purge_after),
+  INDEX idx_trash_items_item (item_type, item_id),
+  CONSTRAINT chk_trash_items_item_type CHECK (item_type IN ('post', 'user')),
+  CONSTRAINT chk_trash_items_status CHECK (status IN ('trashed', 'restored', 'purged')),
+  CONSTRAINT fk_trash_items_deleted_by FOREIGN KEY (deleted_by) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_trash_items_restored_by FOREIGN KEY (restored_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+  user_id BIGINT NOT NULL,
+  event_type VARCHAR(64) NOT NULL,
+  in_app BOOLEAN NOT NULL,
+  email BOOLEAN NOT NULL,
+  updated_at DATETIME(6) NOT NULL,
+  PRIMARY KEY (user_id, event_type),
+  CONSTRAINT fk_notification_preferences_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS push_subscriptions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  endpoint VARCHAR(768) NOT NULL,
+  p256dh VARCHAR(128) NOT NULL,
+  auth_secret VARCHAR(64) NOT NULL,
+  user_agent VARCHAR(512) NULL,
+  created_at DATETIME(6) NOT NULL,
+  last_success_at DATETIME(6) NULL,
+  last_failure_at DATETIME(6) NULL,
+  failure_count INT NOT NULL DEFAULT 0,
+  UNIQUE KEY uq_push_subscriptions_endpoint (endpoint),
+  INDEX idx_push_subscriptions_user_id (user_id),
+  CONSTRAINT fk_push_subscriptions_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS outgoing_webhooks (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  name VARCHAR(100) NOT NULL,
+  platform VARCHAR(16) NOT NULL,
+  url VARCHAR(512) NOT NULL,
+  is_active BOOLEAN NOT NULL DEFAULT TRUE,
+  on_submission BOOLEAN NOT NULL DEFAULT TRUE,
+  on_review_failure BOOLEAN NOT NULL DEFAULT TRUE,
+  on_content_report BOOLEAN NOT NULL DEFAULT TRUE,
+  last_delivered_at DATETIME(6) NULL,
+  last_error VARCHAR(500) NULL,
+  last_error_at DATETIME(6) NULL,
+  created_by BIGINT NULL,
+  created_at DATETIME(6) NOT NULL,
+  updated_at DATETIME(6) NULL,
+  CONSTRAINT chk_outgoing_webhooks_platform CHECK (platform IN ('slack', 'discord')),
+  CONSTRAINT fk_outgoing_webhooks_created_by FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS calendar_feed_tokens (
+  user_id BIGINT PRIMARY KEY,
+  token_hash CHAR(64) NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  last_used_at DATETIME(6) NULL,
+  UNIQUE KEY uq_calendar_feed_tokens_hash (token_hash),
+  CONSTRAINT fk_calendar_feed_tokens_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS user_follows (
+  follower_id BIGINT NOT NULL,
+  followed_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (follower_id, followed_id),
+  INDEX idx_user_follows_followed_created (followed_id, created_at),
+  INDEX idx_user_follows_follower_created (follower_id, created_at),
+  CONSTRAINT fk_user_follows_follower_id FOREIGN KEY (follower_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_follows_followed_id FOREIGN KEY (followed_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS user_blocks (
+  blocker_id BIGINT NOT NULL,
+  blocked_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (blocker_id, blocked_id),
+  INDEX idx_user_blocks_blocked_id (blocked_id),
+  CONSTRAINT fk_user_blocks_blocker_id FOREIGN KEY (blocker_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_blocks_blocked_id FOREIGN KEY (blocked_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS username_history (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  old_username VARCHAR(191) NOT NULL,
+  changed_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_username_history_old_username (old_username),
+  INDEX idx_username_history_user_changed (user_id, changed_at),
+  CONSTRAINT fk_username_history_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS user_expertise_tags (
+  user_id BIGINT NOT NULL,
+  tag_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  PRIMARY KEY (user_id, tag_id),
+  INDEX idx_user_expertise_tags_tag_id (tag_id),
+  CONSTRAINT fk_user_expertise_tags_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_user_expertise_tags_tag_id FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS account_events (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  event_type VARCHAR(32) NOT NULL,
+  auth_method VARCHAR(16) NULL,
+  ip_address VARCHAR(64) NULL,
+  user_agent VARCHAR(512) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  INDEX idx_account_events_user_created (user_id, created_at),
+  CONSTRAINT chk_account_events_event_type CHECK (event_type IN ('login', 'login_failed', 'username_change')),
+  CONSTRAINT fk_account_events_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS notifications (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  user_id BIGINT NOT NULL,
+  actor_id BIGINT NULL,
+  event_type VARCHAR(64) NOT NULL,
+  post_id BIGINT NULL,
+  comment_id BIGINT NULL,
+  message VARCHAR(512) NOT NULL,
+  is_read BOOLEAN NOT NULL DEFAULT FALSE,
+  read_at DATETIME(6) NULL,
+  dedup_key VARCHAR(191) NULL,
+  batch_count INT NOT NULL DEFAULT 1,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NULL,
+  INDEX idx_notifications_user_read_created (user_id, is_read, created_at),
+  INDEX idx_notifications_user_dedup (user_id, dedup_key, created_at),
+  CONSTRAINT fk_notifications_user_id FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  CONSTRAINT fk_notifications_actor_id FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL,
+  CONSTRAINT fk_notifications_post_id FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS comment_mentions (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  comment_id BIGINT NULL,
+  review_comment_id BIGINT NULL,
+  mentioned_user_id BIGINT NOT NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  UNIQUE KEY uq_comment_mentions_comment_user (comment_id, mentioned_user_id),
+  UNIQUE KEY uq_comment_mentions_review_comment_user (review_comment_id, mentioned_user_id),
+  INDEX idx_comment_mentions_user_created (mentioned_user_id, created_at),
+  CONSTRAINT fk_comment_mentions_comment_id FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_mentions_review_comment_id FOREIGN KEY (review_comment_id) REFERENCES paper_review_comments(id) ON DELETE CASCADE,
+  CONSTRAINT fk_comment_mentions_user_id FOREIGN KEY (mentioned_user_id) REFERENCES users(id) ON DELETE CASCADE
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
 
 INSERT IGNORE INTO post_categories (code, display_name) VALUES
@@ -269,7 +1157,8 @@ INSERT IGNORE INTO post_categories (code, display_name) VALUES
 
 INSERT IGNORE INTO citation_sources (id, code, display_name) VALUES
   (1, 'manual', 'Manual citation'),
-  (2, 'auto', 'Automatic citation');
+  (2, 'auto', 'Automatic citation'),
+  (3, 'openalex', 'OpenAlex inbound citation');
 
 INSERT IGNORE INTO ai_review_statuses (id, code, display_name) VALUES
   (1, 'pending', 'Pending'),
@@ -279,7 +1168,9 @@ INSERT IGNORE INTO ai_review_statuses (id, code, display_name) VALUES
 INSERT IGNORE INTO ai_review_triggers (id, code, display_name) VALUES
   (1, 'auto_create', 'Automatic on Create'),
   (2, 'auto_update', 'Automatic on Update'),
-  (3, 'manual', 'Manual Rerun');
+  (3, 'manual', 'Manual Rerun'),
+  (4, 'appeal', 'Appeal Review'),
+  (5, 'backfill', 'Backfill Review');
 
 INSERT IGNORE INTO ai_review_decisions (id, code, display_name) VALUES
   (1, 'accept', 'Accept'),
@@ -0,0 +1,74 @@
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::audit_log::RequestMetadata;
+use crate::tasks;
+
+/// Records a sign-in or account change for the user's activity log. The
+/// insert runs in the background and a failure is only logged, so it
+/// never holds up or fails the request that triggered it.
+pub fn record_account_event(
+    pool: &MySqlPool,
+    user_id: i64,
+    event_type: &'static str,
+    auth_method: Option<&'static str>,
+    metadata: RequestMetadata,
+) {
+    let pool = pool.clone();
+    tasks::spawn_task("account_event", async move {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO account_events
+                (user_id, event_type, auth_method, ip_address, user_agent, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id)
+        .bind(event_type)
+        .bind(auth_method)
+        .bind(metadata.ip_address)
+        .bind(metadata.user_agent)
+        .bind(Utc::now())
+        .execute(&pool)
+        .await;
+        if let Err(error) = result {
+            tracing::warn!("Failed to record account event: {}", error);
+        }
+    });
+}
+
+/// Turns a user agent into a label like "Firefox on Windows". Order
+/// matters: Edge and Opera also claim to be Chrome, and Chrome claims to
+/// be Safari.
+pub fn describe_device(user_agent: &str) -> Option<String> {
+    const BROWSERS: [(&str, &str); 6] = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("CriOS/", "Chrome"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+    ];
+    const SYSTEMS: [(&str, &str); 6] = [
+        ("Android", "Android"),
+        ("iPhone", "iOS"),
+        ("iPad", "iPadOS"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ];
+
+    let browser = BROWSERS
+        .iter()
+        .find(|(marker, _)| user_agent.contains(marker))
+        .map(|(_, name)| *name);
+    let system = SYSTEMS
+        .iter()
+        .find(|(marker, _)| user_agent.contains(marker))
+        .map(|(_, name)| *name);
+    match (browser, system) {
+        (Some(browser), Some(system)) => Some(format!("{browser} on {system}")),
+        (Some(name), None) | (None, Some(name)) => Some(name.to_string()),
+        (None, None) => None,
+    }
+}
@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use super::{
+    AI_REVIEW_PROMPT_VERSION, AI_REVIEW_STATUS_PENDING_ID, ReviewTrigger, schedule_review,
+};
+use crate::models::{
+    BACKFILL_ITEM_STATUS_FAILED, BACKFILL_ITEM_STATUS_QUEUED, BACKFILL_ITEM_STATUS_SCHEDULED,
+    BACKFILL_STATUS_COMPLETED, BACKFILL_STATUS_RUNNING,
+};
+use crate::tasks;
+
+pub const DEFAULT_REVIEW_BACKFILL_INTERVAL_SECS: u64 = 60;
+/// The backfill job leaves room in the queue once this many reviews are
+/// pending, so submissions are not stuck behind a large backfill.
+pub const DEFAULT_REVIEW_BACKFILL_MAX_PENDING: i64 = 5;
+
+pub const REVIEW_BACKFILL_SELECT: &str = r#"
+    SELECT
+        b.id AS id,
+        b.status AS status,
+        b.prompt_version AS prompt_version,
+        b.paper_status AS paper_status,
+        CAST(b.batch_size AS SIGNED) AS batch_size,
+        CAST(b.total_items AS SIGNED) AS total_items,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            WHERE i.backfill_id = b.id AND i.status = 'queued') AS queued_items,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            WHERE i.backfill_id = b.id AND i.status = 'scheduled') AS scheduled_items,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            WHERE i.backfill_id = b.id AND i.status = 'failed') AS failed_items,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            WHERE i.backfill_id = b.id AND i.status = 'cancelled') AS cancelled_items,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            JOIN post_ai_reviews r ON r.id = i.review_id
+            JOIN ai_review_statuses s ON s.id = r.status_id
+            WHERE i.backfill_id = b.id AND s.code = 'pending') AS reviews_pending,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            JOIN post_ai_reviews r ON r.id = i.review_id
+            JOIN ai_review_statuses s ON s.id = r.status_id
+            WHERE i.backfill_id = b.id AND s.code = 'completed') AS reviews_completed,
+        (SELECT COUNT(*) FROM ai_review_backfill_items i
+            JOIN post_ai_reviews r ON r.id = i.review_id
+            JOIN ai_review_statuses s ON s.id = r.status_id
+            WHERE i.backfill_id = b.id AND s.code = 'failed') AS reviews_failed,
+        b.created_by AS created_by,
+        b.cancelled_by AS cancelled_by,
+        b.created_at AS created_at,
+        b.finished_at AS finished_at
+    FROM ai_review_backfills b
+"#;
+
+/// Papers a backfill with these filters would re-review: those whose latest
+/// review is finished and used `prompt_version`, or any older prompt than
+/// the current one when no version is given.
+pub async fn find_backfill_candidates(
+    pool: &MySqlPool,
+    prompt_version: Option<&str>,
+    paper_status: Option<&str>,
+) -> Result<Vec<i64>, sqlx::Error> {
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT p.id
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        JOIN post_ai_reviews r
+            ON r.id = (SELECT MAX(r2.id) FROM post_ai_reviews r2 WHERE r2.post_id = p.id)
+        WHERE c.code = 'paper' AND p.deleted_at IS NULL AND r.status_id <> "#,
+    );
+    query_builder.push_bind(AI_REVIEW_STATUS_PENDING_ID);
+    match prompt_version {
+        Some(prompt_version) => {
+            query_builder.push(" AND r.prompt_version = ");
+            query_builder.push_bind(prompt_version);
+        }
+        None => {
+            query_builder.push(" AND r.prompt_version <> ");
+            query_builder.push_bind(AI_REVIEW_PROMPT_VERSION);
+        }
+    }
+    if let Some(paper_status) = paper_status {
+        query_builder.push(" AND p.paper_status = ");
+        query_builder.push_bind(paper_status);
+    }
+    query_builder.push(" ORDER BY p.id ASC");
+
+    query_builder.build_query_scalar().fetch_all(pool).await
+}
+
+pub fn spawn_review_backfills(pool: MySqlPool) {
+    let interval_secs = backfill_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("AI review backfill job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "ai_review_backfill",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match run_backfill_batch(&pool).await {
+                    Ok(0) => {}
+                    Ok(scheduled) => {
+                        tracing::info!("Scheduled {} AI review(s) from backfills", scheduled)
+                    }
+                    Err(error) => tracing::error!("AI review backfill run failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Schedules the next queued papers of running backfills, oldest backfill
+/// first, without letting the pending queue grow past the configured cap.
+/// Backfills with nothing left to schedule are marked completed.
+async fn run_backfill_batch(pool: &MySqlPool) -> Result<usize, anyhow::Error> {
+    let pending: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM post_ai_reviews WHERE status_id = ?")
+            .bind(AI_REVIEW_STATUS_PENDING_ID)
+            .fetch_one(pool)
+            .await?;
+    let mut capacity = backfill_max_pending().saturating_sub(pending);
+
+    let backfills: Vec<(i64, i64)> = sqlx::query_as(
+        "SELECT id, CAST(batch_size AS SIGNED) FROM ai_review_backfills WHERE status = ? ORDER BY id ASC",
+    )
+    .bind(BACKFILL_STATUS_RUNNING)
+    .fetch_all(pool)
+    .await?;
+
+    let mut scheduled = 0;
+    for (backfill_id, batch_size) in backfills {
+        if capacity <= 0 {
+            break;
+        }
+
+        let post_ids: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT post_id
+            FROM ai_review_backfill_items
+            WHERE backfill_id = ? AND status = ?
+            ORDER BY post_id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(backfill_id)
+        .bind(BACKFILL_ITEM_STATUS_QUEUED)
+        .bind(batch_size.min(capacity))
+        .fetch_all(pool)
+        .await?;
+        if post_ids.is_empty() {
+            sqlx::query(
+                "UPDATE ai_review_backfills SET status = ?, finished_at = ? WHERE id = ? AND status = ?",
+            )
+            .bind(BACKFILL_STATUS_COMPLETED)
+            .bind(Utc::now())
+            .bind(backfill_id)
+            .bind(BACKFILL_STATUS_RUNNING)
+            .execute(pool)
+            .await?;
+            continue;
+        }
+
+        for post_id in post_ids {
+            // Claiming the item first keeps a cancelled backfill or another
+            // server instance from scheduling it twice.
+            let claimed = sqlx::query(
+                r#"
+                UPDATE ai_review_backfill_items
+                SET status = ?, processed_at = ?
+                WHERE backfill_id = ? AND post_id = ? AND status = ?
+                "#,
+            )
+            .bind(BACKFILL_ITEM_STATUS_SCHEDULED)
+            .bind(Utc::now())
+            .bind(backfill_id)
+            .bind(post_id)
+            .bind(BACKFILL_ITEM_STATUS_QUEUED)
+            .execute(pool)
+            .await?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            // A paper trashed since the backfill started is not reviewed.
+            let latest_paper_version_id: Option<Option<i64>> = sqlx::query_scalar(
+                "SELECT latest_paper_version_id FROM posts WHERE id = ? AND deleted_at IS NULL",
+            )
+            .bind(post_id)
+            .fetch_optional(pool)
+            .await?;
+            let Some(latest_paper_version_id) = latest_paper_version_id else {
+                sqlx::query(
+                    "UPDATE ai_review_backfill_items SET status = ?, error_message = ? WHERE backfill_id = ? AND post_id = ?",
+                )
+                .bind(BACKFILL_ITEM_STATUS_FAILED)
+                .bind("Paper was deleted")
+                .bind(backfill_id)
+                .bind(post_id)
+                .execute(pool)
+                .await?;
+                continue;
+            };
+            match schedule_review(
+                pool,
+                post_id,
+                latest_paper_version_id,
+                ReviewTrigger::Backfill,
+            )
+            .await
+            {
+                Ok(review_id) => {
+                    sqlx::query(
+                        "UPDATE ai_review_backfill_items SET review_id = ? WHERE backfill_id = ? AND post_id = ?",
+                    )
+                    .bind(review_id)
+                    .bind(backfill_id)
+                    .bind(post_id)
+                    .execute(pool)
+                    .await?;
+                    scheduled += 1;
+                    capacity -= 1;
+                }
+                Err(error) => {
+                    sqlx::query(
+                        "UPDATE ai_review_backfill_items SET status = ?, error_message = ? WHERE backfill_id = ? AND post_id = ?",
+                    )
+                    .bind(BACKFILL_ITEM_STATUS_FAILED)
+                    .bind(error.to_string())
+                    .bind(backfill_id)
+                    .bind(post_id)
+                    .execute(pool)
+                    .await?;
+                }
+            }
+        }
+    }
+
+    Ok(scheduled)
+}
+
+fn backfill_interval_secs() -> u64 {
+    std::env::var("AI_REVIEW_BACKFILL_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_REVIEW_BACKFILL_INTERVAL_SECS)
+}
+
+fn backfill_max_pending() -> i64 {
+    std::env::var("AI_REVIEW_BACKFILL_MAX_PENDING")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_REVIEW_BACKFILL_MAX_PENDING)
+}
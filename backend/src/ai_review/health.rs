@@ -0,0 +1,198 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+
+use super::{
+    AI_REVIEW_STATUS_COMPLETED_ID, AI_REVIEW_STATUS_FAILED_ID, AI_REVIEW_STATUS_PENDING_ID,
+};
+use crate::models::{AiReviewFailureClassCount, AiReviewHealth, GeminiRateLimitStatus};
+
+/// How long a 429 without a `Retry-After` header counts as rate limiting.
+const DEFAULT_RATE_LIMIT_BACKOFF_SECS: i64 = 60;
+const RATE_LIMIT_HISTORY_SECS: i64 = 3_600;
+
+#[derive(Debug, Default)]
+struct RateLimitTracker {
+    responses: VecDeque<DateTime<Utc>>,
+    limited_until: Option<DateTime<Utc>>,
+}
+
+impl RateLimitTracker {
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - chrono::Duration::seconds(RATE_LIMIT_HISTORY_SECS);
+        while self.responses.front().is_some_and(|at| *at < cutoff) {
+            self.responses.pop_front();
+        }
+    }
+}
+
+fn rate_limit_tracker() -> &'static Mutex<RateLimitTracker> {
+    static TRACKER: OnceLock<Mutex<RateLimitTracker>> = OnceLock::new();
+    TRACKER.get_or_init(|| Mutex::new(RateLimitTracker::default()))
+}
+
+/// Notes a 429 from Gemini, honouring its `Retry-After` when present.
+pub(super) fn record_rate_limited(retry_after: Option<Duration>) {
+    let now = Utc::now();
+    let backoff = retry_after
+        .and_then(|delay| chrono::Duration::from_std(delay).ok())
+        .unwrap_or_else(|| chrono::Duration::seconds(DEFAULT_RATE_LIMIT_BACKOFF_SECS));
+    let Ok(mut tracker) = rate_limit_tracker().lock() else {
+        return;
+    };
+    tracker.prune(now);
+    tracker.responses.push_back(now);
+    tracker.limited_until = Some(now + backoff);
+}
+
+/// A successful Gemini call ends any rate limiting in progress.
+pub(super) fn record_gemini_success() {
+    if let Ok(mut tracker) = rate_limit_tracker().lock() {
+        tracker.limited_until = None;
+    }
+}
+
+fn gemini_rate_limit_status() -> GeminiRateLimitStatus {
+    let now = Utc::now();
+    let Ok(mut tracker) = rate_limit_tracker().lock() else {
+        return GeminiRateLimitStatus {
+            is_rate_limited: false,
+            limited_until: None,
+            last_rate_limited_at: None,
+            rate_limited_responses_last_hour: 0,
+        };
+    };
+    tracker.prune(now);
+    let limited_until = tracker.limited_until.filter(|until| *until > now);
+    GeminiRateLimitStatus {
+        is_rate_limited: limited_until.is_some(),
+        limited_until,
+        last_rate_limited_at: tracker.responses.back().copied(),
+        rate_limited_responses_last_hour: tracker.responses.len() as i64,
+    }
+}
+
+/// Buckets a stored `error_message` by what went wrong, following the
+/// messages produced by `run_review`.
+fn classify_review_error(message: &str) -> &'static str {
+    if message.starts_with("GEMINI_API_KEY is not configured")
+        || message.starts_with("Failed to build Gemini HTTP client")
+    {
+        "configuration"
+    } else if message.starts_with("Gemini API error 429") {
+        "rate_limited"
+    } else if message.starts_with("Gemini API error 5") {
+        "upstream_error"
+    } else if message.starts_with("Gemini API error") {
+        "api_error"
+    } else if message.starts_with("Failed to call Gemini API")
+        || message.starts_with("Failed to read Gemini response body")
+        || message.starts_with("Gemini API request did not succeed")
+    {
+        "network"
+    } else if message.starts_with("Gemini response does not contain")
+        || message.starts_with("Failed to parse Gemini structured JSON")
+        || message.starts_with("Invalid decision")
+        || message.ends_with("must be between 1 and 5")
+        || message.ends_with("must not be empty")
+    {
+        "invalid_response"
+    } else if message.starts_with("Post not found")
+        || message.starts_with("Paper version not found")
+        || message.starts_with("AI review is only available")
+        || message.contains("DOCX")
+    {
+        "input"
+    } else {
+        "other"
+    }
+}
+
+pub async fn fetch_review_health(
+    pool: &MySqlPool,
+    window_hours: i64,
+) -> Result<AiReviewHealth, sqlx::Error> {
+    let now = Utc::now();
+    let since = now - chrono::Duration::hours(window_hours);
+
+    let (queue_depth, oldest_pending_created_at): (i64, Option<DateTime<Utc>>) =
+        sqlx::query_as("SELECT COUNT(*), MIN(created_at) FROM post_ai_reviews WHERE status_id = ?")
+            .bind(AI_REVIEW_STATUS_PENDING_ID)
+            .fetch_one(pool)
+            .await?;
+
+    let mut latencies_ms: Vec<i64> = sqlx::query_scalar(
+        r#"
+        SELECT CAST(TIMESTAMPDIFF(MICROSECOND, created_at, completed_at) DIV 1000 AS SIGNED)
+        FROM post_ai_reviews
+        WHERE status_id = ? AND completed_at >= ?
+        "#,
+    )
+    .bind(AI_REVIEW_STATUS_COMPLETED_ID)
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    latencies_ms.sort_unstable();
+
+    let failure_messages: Vec<Option<String>> = sqlx::query_scalar(
+        "SELECT error_message FROM post_ai_reviews WHERE status_id = ? AND completed_at >= ?",
+    )
+    .bind(AI_REVIEW_STATUS_FAILED_ID)
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    let mut class_counts: HashMap<&'static str, i64> = HashMap::new();
+    for message in &failure_messages {
+        let class = classify_review_error(message.as_deref().unwrap_or_default());
+        *class_counts.entry(class).or_default() += 1;
+    }
+    let mut failures_by_class: Vec<AiReviewFailureClassCount> = class_counts
+        .into_iter()
+        .map(|(error_class, count)| AiReviewFailureClassCount {
+            error_class: error_class.to_string(),
+            count,
+        })
+        .collect();
+    failures_by_class.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.error_class.cmp(&b.error_class))
+    });
+
+    let completed_in_window = latencies_ms.len() as i64;
+    let failed_in_window = failure_messages.len() as i64;
+    let finished = completed_in_window + failed_in_window;
+    let failure_rate = if finished > 0 {
+        failed_in_window as f64 / finished as f64
+    } else {
+        0.0
+    };
+
+    Ok(AiReviewHealth {
+        window_hours,
+        queue_depth,
+        oldest_pending_created_at,
+        oldest_pending_age_secs: oldest_pending_created_at
+            .map(|created_at| (now - created_at).num_seconds().max(0)),
+        completed_in_window,
+        failed_in_window,
+        failure_rate,
+        latency_p50_ms: percentile(&latencies_ms, 50),
+        latency_p95_ms: percentile(&latencies_ms, 95),
+        failures_by_class,
+        rate_limit: gemini_rate_limit_status(),
+    })
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[i64], percent: usize) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
@@ -1,4 +1,11 @@
+mod backfill;
+mod health;
+
+pub use backfill::*;
+pub use health::*;
+
 use std::{
+    collections::HashMap,
     fs::File,
     io::{Cursor, Read},
     path::Path,
@@ -15,12 +22,19 @@ use sqlx::{FromRow, MySql, MySqlPool, QueryBuilder};
 use tokio::task;
 use zip::ZipArchive;
 
+use crate::cache::invalidate_cached_post;
 use crate::models::{
     AiReviewDecision, AiReviewEditorial, AiReviewListResponse, AiReviewMetricsSummary,
     AiReviewPeer, AiReviewResponse, AiReviewScores, AiReviewStatus, AiReviewSummary,
     MyPaperReviewItem, MyPaperReviewListResponse, PAPER_STATUS_ACCEPTED, PAPER_STATUS_REJECTED,
-    PAPER_STATUS_REVISION,
+    PAPER_STATUS_REVISION, ReviewCommentVersionSummary, WEBHOOK_EVENT_REVIEW_FAILURE,
 };
+use crate::outgoing_webhooks::{WebhookMessage, queue_webhook_message};
+use crate::paper_status::{STATUS_TRIGGER_AI_REVIEW, record_status_transition};
+use crate::request_id;
+use crate::settings::{setting, setting_value};
+use crate::tasks;
+use crate::telemetry;
 
 pub const AI_REVIEW_PROMPT_VERSION: &str = "v1";
 pub const AI_REVIEW_LANGUAGE: &str = "ko";
@@ -38,6 +52,8 @@ const AI_REVIEW_STATUS_FAILED_ID: u8 = 3;
 const AI_REVIEW_TRIGGER_AUTO_CREATE_ID: u8 = 1;
 const AI_REVIEW_TRIGGER_AUTO_UPDATE_ID: u8 = 2;
 const AI_REVIEW_TRIGGER_MANUAL_ID: u8 = 3;
+const AI_REVIEW_TRIGGER_APPEAL_ID: u8 = 4;
+const AI_REVIEW_TRIGGER_BACKFILL_ID: u8 = 5;
 
 const AI_REVIEW_DECISION_ACCEPT_ID: u8 = 1;
 const AI_REVIEW_DECISION_MINOR_REVISION_ID: u8 = 2;
@@ -70,6 +86,7 @@ const REVIEW_SELECT_COLUMNS: &str = r#"
         CAST(r.input_snapshot_json AS CHAR) AS input_snapshot_json,
         CAST(r.raw_response_json AS CHAR) AS raw_response_json,
         r.error_message,
+        r.request_id,
         r.created_at,
         r.completed_at
 "#;
@@ -87,6 +104,10 @@ pub enum ReviewTrigger {
     AutoCreate,
     AutoUpdate,
     Manual,
+    /// Re-review of a rejected version with the author's appeal attached.
+    Appeal,
+    /// Re-review queued by an admin backfill, e.g. after a prompt change.
+    Backfill,
 }
 
 impl ReviewTrigger {
@@ -95,6 +116,8 @@ impl ReviewTrigger {
             Self::AutoCreate => AI_REVIEW_TRIGGER_AUTO_CREATE_ID,
             Self::AutoUpdate => AI_REVIEW_TRIGGER_AUTO_UPDATE_ID,
             Self::Manual => AI_REVIEW_TRIGGER_MANUAL_ID,
+            Self::Appeal => AI_REVIEW_TRIGGER_APPEAL_ID,
+            Self::Backfill => AI_REVIEW_TRIGGER_BACKFILL_ID,
         }
     }
 }
@@ -125,6 +148,7 @@ struct ReviewRow {
     input_snapshot_json: Option<String>,
     raw_response_json: Option<String>,
     error_message: Option<String>,
+    request_id: Option<String>,
     created_at: chrono::DateTime<chrono::Utc>,
     completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -159,6 +183,14 @@ struct ReviewCenterRow {
     error_message: Option<String>,
     review_created_at: Option<chrono::DateTime<chrono::Utc>>,
     review_completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    revision_due_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+struct ReviewCommentSummaryRow {
+    post_id: i64,
+    #[sqlx(flatten)]
+    summary: ReviewCommentVersionSummary,
 }
 
 #[derive(Debug, Serialize)]
@@ -170,6 +202,7 @@ struct ReviewInputSnapshot {
     truncated: bool,
     max_input_chars: usize,
     attachments: Vec<AttachmentSnapshot>,
+    appeal_id: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -215,7 +248,7 @@ pub async fn schedule_review(
     trigger: ReviewTrigger,
 ) -> Result<i64, anyhow::Error> {
     let now = Utc::now();
-    let model = std::env::var("GEMINI_MODEL").unwrap_or_else(|_| DEFAULT_GEMINI_MODEL.to_string());
+    let model = setting_value("GEMINI_MODEL").unwrap_or_else(|| DEFAULT_GEMINI_MODEL.to_string());
 
     let result = sqlx::query(
         r#"
@@ -227,8 +260,9 @@ pub async fn schedule_review(
             model,
             prompt_version,
             language_code,
+            request_id,
             created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(post_id)
@@ -238,13 +272,14 @@ pub async fn schedule_review(
     .bind(model)
     .bind(AI_REVIEW_PROMPT_VERSION)
     .bind(AI_REVIEW_LANGUAGE)
+    .bind(request_id::current())
     .bind(now)
     .execute(pool)
     .await?;
 
     let review_id = result.last_insert_id() as i64;
     let pool_clone = pool.clone();
-    tokio::spawn(async move {
+    tasks::spawn_task("ai_review_run", async move {
         if let Err(error) = run_review(&pool_clone, review_id).await {
             tracing::error!(
                 "AI review run failed for review_id={}: {}",
@@ -258,16 +293,35 @@ pub async fn schedule_review(
 }
 
 pub async fn run_review(pool: &MySqlPool, review_id: i64) -> Result<(), anyhow::Error> {
-    let row: Option<(i64, Option<i64>)> =
-        sqlx::query_as("SELECT post_id, paper_version_id FROM post_ai_reviews WHERE id = ?")
-        .bind(review_id)
-        .fetch_optional(pool)
-        .await?;
-    let Some((post_id, paper_version_id)) = row else {
+    let row: Option<(i64, Option<i64>, u8)> = sqlx::query_as(
+        "SELECT post_id, paper_version_id, trigger_id FROM post_ai_reviews WHERE id = ?",
+    )
+    .bind(review_id)
+    .fetch_optional(pool)
+    .await?;
+    let Some((post_id, paper_version_id, trigger_id)) = row else {
         return Err(anyhow!("Review not found: {}", review_id));
     };
 
-    let built_input = match build_review_input(pool, post_id, paper_version_id).await {
+    let appeal = if trigger_id == AI_REVIEW_TRIGGER_APPEAL_ID {
+        sqlx::query_as::<_, (i64, String)>(
+            r#"
+            SELECT id, justification
+            FROM paper_appeals
+            WHERE post_id = ? AND paper_version_id <=> ?
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(post_id)
+        .bind(paper_version_id)
+        .fetch_optional(pool)
+        .await?
+    } else {
+        None
+    };
+
+    let built_input = match build_review_input(pool, post_id, paper_version_id, appeal).await {
         Ok(input) => input,
         Err(error) => {
             mark_failed(pool, review_id, &error.to_string(), None, None).await?;
@@ -433,8 +487,38 @@ pub async fn fetch_user_review_center(
     let per_page = per_page.clamp(1, 100);
     let offset = i64::from(page - 1) * i64::from(per_page);
 
+    // The page of posts is picked first, then the latest review and editorial
+    // decision of just those posts come from one windowed or grouped pass
+    // each, rather than a pair of subqueries per post.
     let rows = sqlx::query_as::<_, ReviewCenterRow>(
         r#"
+        WITH page AS (
+            SELECT p.id, p.title, p.category_id, p.paper_status, p.current_revision,
+                p.is_published, p.published_at, p.latest_paper_version_id,
+                p.created_at, p.updated_at
+            FROM posts p
+            JOIN post_categories c ON c.id = p.category_id
+            WHERE p.author_id = ? AND c.code = 'paper' AND p.deleted_at IS NULL
+            ORDER BY p.updated_at DESC, p.created_at DESC
+            LIMIT ? OFFSET ?
+        ),
+        ranked_reviews AS (
+            SELECT
+                r.id, r.post_id, r.paper_version_id, r.status_id, r.decision_id,
+                r.trigger_id, r.overall_score, r.error_message, r.created_at,
+                r.completed_at,
+                ROW_NUMBER() OVER (
+                    PARTITION BY r.post_id ORDER BY r.created_at DESC, r.id DESC
+                ) AS review_rank
+            FROM post_ai_reviews r
+            JOIN page ON page.id = r.post_id
+        ),
+        latest_decisions AS (
+            SELECT ed.post_id, MAX(ed.id) AS id
+            FROM editorial_decisions ed
+            JOIN page ON page.id = ed.post_id
+            GROUP BY ed.post_id
+        )
         SELECT
             p.id AS post_id,
             p.title AS title,
@@ -452,23 +536,22 @@ pub async fn fetch_user_review_center(
             CAST(lr.overall_score AS SIGNED) AS overall_score,
             lr.error_message AS error_message,
             lr.created_at AS review_created_at,
-            lr.completed_at AS review_completed_at
-        FROM posts p
+            lr.completed_at AS review_completed_at,
+            CASE
+                WHEN p.paper_status = 'revision'
+                    AND p.latest_paper_version_id <=> led.paper_version_id
+                THEN led.revision_due_at
+            END AS revision_due_at
+        FROM page p
         JOIN post_categories c ON c.id = p.category_id
-        LEFT JOIN post_ai_reviews lr ON lr.id = (
-            SELECT r2.id
-            FROM post_ai_reviews r2
-            WHERE r2.post_id = p.id
-            ORDER BY r2.created_at DESC, r2.id DESC
-            LIMIT 1
-        )
+        LEFT JOIN ranked_reviews lr ON lr.post_id = p.id AND lr.review_rank = 1
+        LEFT JOIN latest_decisions ld ON ld.post_id = p.id
+        LEFT JOIN editorial_decisions led ON led.id = ld.id
         LEFT JOIN paper_versions pv ON pv.id = lr.paper_version_id
         LEFT JOIN ai_review_statuses s ON s.id = lr.status_id
         LEFT JOIN ai_review_decisions d ON d.id = lr.decision_id
         LEFT JOIN ai_review_triggers t ON t.id = lr.trigger_id
-        WHERE p.author_id = ? AND c.code = 'paper'
         ORDER BY p.updated_at DESC, p.created_at DESC
-        LIMIT ? OFFSET ?
         "#,
     )
     .bind(user_id)
@@ -478,12 +561,16 @@ pub async fn fetch_user_review_center(
     .await?;
 
     let (total,): (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM posts p JOIN post_categories c ON c.id = p.category_id WHERE p.author_id = ? AND c.code = 'paper'",
+        "SELECT COUNT(*) FROM posts p JOIN post_categories c ON c.id = p.category_id WHERE p.author_id = ? AND c.code = 'paper' AND p.deleted_at IS NULL",
     )
     .bind(user_id)
     .fetch_one(pool)
     .await?;
 
+    let mut comment_summaries =
+        fetch_review_comment_summaries(pool, rows.iter().map(|row| row.post_id).collect()).await?;
+
+    let now = Utc::now();
     let items = rows
         .into_iter()
         .map(|row| {
@@ -512,6 +599,11 @@ pub async fn fetch_user_review_center(
                 is_published: row.is_published,
                 published_at: row.published_at,
                 latest_review,
+                is_overdue: row.revision_due_at.is_some_and(|due_at| due_at <= now),
+                revision_due_at: row.revision_due_at,
+                review_comment_summaries: comment_summaries
+                    .remove(&row.post_id)
+                    .unwrap_or_default(),
             }
         })
         .collect();
@@ -524,6 +616,53 @@ pub async fn fetch_user_review_center(
     })
 }
 
+/// Counts open and resolved top-level review comments per version, so the
+/// author can check a revision against what is still outstanding.
+async fn fetch_review_comment_summaries(
+    pool: &MySqlPool,
+    post_ids: Vec<i64>,
+) -> Result<HashMap<i64, Vec<ReviewCommentVersionSummary>>, sqlx::Error> {
+    let mut summaries: HashMap<i64, Vec<ReviewCommentVersionSummary>> = HashMap::new();
+    if post_ids.is_empty() {
+        return Ok(summaries);
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT
+            rc.post_id AS post_id,
+            pv.id AS paper_version_id,
+            CAST(pv.version_number AS SIGNED) AS version_number,
+            CAST(SUM(rc.resolution_status = 'open') AS SIGNED) AS open_count,
+            CAST(SUM(rc.resolution_status = 'addressed') AS SIGNED) AS addressed_count,
+            CAST(SUM(rc.resolution_status = 'wont_fix') AS SIGNED) AS wont_fix_count,
+            CAST(SUM(rc.resolution_status <> 'open') AS SIGNED) AS resolved_count
+        FROM paper_review_comments rc
+        JOIN paper_versions pv ON pv.id = rc.paper_version_id
+        WHERE rc.parent_comment_id IS NULL AND rc.is_deleted = FALSE AND rc.post_id IN (
+        "#,
+    );
+    {
+        let mut separated = query_builder.separated(", ");
+        for post_id in &post_ids {
+            separated.push_bind(post_id);
+        }
+    }
+    query_builder.push(
+        ") GROUP BY rc.post_id, pv.id, pv.version_number ORDER BY rc.post_id ASC, pv.version_number DESC",
+    );
+
+    let rows = query_builder
+        .build_query_as::<ReviewCommentSummaryRow>()
+        .fetch_all(pool)
+        .await?;
+    for row in rows {
+        summaries.entry(row.post_id).or_default().push(row.summary);
+    }
+
+    Ok(summaries)
+}
+
 pub fn parse_status_filter(raw: &str) -> Option<&'static str> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "pending" => Some("pending"),
@@ -574,6 +713,7 @@ fn map_review_row(row: ReviewRow) -> AiReviewResponse {
         input_snapshot: parse_json_value(row.input_snapshot_json),
         raw_response: parse_json_value(row.raw_response_json),
         error_message: row.error_message,
+        request_id: row.request_id,
         created_at: row.created_at,
         completed_at: row.completed_at,
     }
@@ -587,7 +727,25 @@ fn map_status_code(code: &str) -> AiReviewStatus {
     }
 }
 
-fn map_decision_code(code: &str) -> Option<AiReviewDecision> {
+pub fn decision_code(decision: AiReviewDecision) -> &'static str {
+    match decision {
+        AiReviewDecision::Accept => "accept",
+        AiReviewDecision::MinorRevision => "minor_revision",
+        AiReviewDecision::MajorRevision => "major_revision",
+        AiReviewDecision::Reject => "reject",
+    }
+}
+
+/// Paper status a review decision moves the submission to.
+pub fn paper_status_for_decision(decision: AiReviewDecision) -> &'static str {
+    match decision {
+        AiReviewDecision::Accept => PAPER_STATUS_ACCEPTED,
+        AiReviewDecision::MinorRevision | AiReviewDecision::MajorRevision => PAPER_STATUS_REVISION,
+        AiReviewDecision::Reject => PAPER_STATUS_REJECTED,
+    }
+}
+
+pub fn map_decision_code(code: &str) -> Option<AiReviewDecision> {
     match code {
         "accept" => Some(AiReviewDecision::Accept),
         "minor_revision" => Some(AiReviewDecision::MinorRevision),
@@ -610,6 +768,7 @@ async fn build_review_input(
     pool: &MySqlPool,
     post_id: i64,
     paper_version_id: Option<i64>,
+    appeal: Option<(i64, String)>,
 ) -> Result<BuiltReviewInput, anyhow::Error> {
     let source = if let Some(version_id) = paper_version_id {
         sqlx::query_as::<_, ReviewPostSource>(
@@ -625,7 +784,7 @@ async fn build_review_input(
             FROM posts p
             JOIN post_categories c ON c.id = p.category_id
             JOIN paper_versions v ON v.post_id = p.id
-            WHERE p.id = ? AND v.id = ?
+            WHERE p.id = ? AND v.id = ? AND p.deleted_at IS NULL
             "#,
         )
         .bind(post_id)
@@ -647,7 +806,7 @@ async fn build_review_input(
             FROM posts p
             JOIN post_categories c ON c.id = p.category_id
             LEFT JOIN post_files pf ON pf.post_id = p.id
-            WHERE p.id = ?
+            WHERE p.id = ? AND p.deleted_at IS NULL
             "#,
         )
         .bind(post_id)
@@ -719,6 +878,14 @@ async fn build_review_input(
         input_text.push('\n');
     }
 
+    // The appeal goes before the manuscript so truncation never drops it.
+    if let Some((_, justification)) = appeal.as_ref() {
+        input_text = format!(
+            "저자 이의신청 (이전 게재 거절 결정에 대한 재심 요청):\n{}\n\n{}",
+            justification, input_text
+        );
+    }
+
     let max_chars = max_input_chars();
     let (truncated_input, truncated) = truncate_chars(&input_text, max_chars);
 
@@ -730,6 +897,7 @@ async fn build_review_input(
         truncated,
         max_input_chars: max_chars,
         attachments: attachment_snapshots,
+        appeal_id: appeal.map(|(appeal_id, _)| appeal_id),
     })?;
 
     Ok(BuiltReviewInput {
@@ -851,11 +1019,8 @@ async fn invoke_gemini_review(
 ) -> Result<(GeminiReviewOutput, Value), (anyhow::Error, Option<Value>)> {
     let api_key = std::env::var("GEMINI_API_KEY")
         .map_err(|_| (anyhow!("GEMINI_API_KEY is not configured"), None))?;
-    let model = std::env::var("GEMINI_MODEL").unwrap_or_else(|_| DEFAULT_GEMINI_MODEL.to_string());
-    let timeout_secs = std::env::var("GEMINI_TIMEOUT_SECS")
-        .ok()
-        .and_then(|raw| raw.parse::<u64>().ok())
-        .unwrap_or(DEFAULT_GEMINI_TIMEOUT_SECS);
+    let model = setting_value("GEMINI_MODEL").unwrap_or_else(|| DEFAULT_GEMINI_MODEL.to_string());
+    let timeout_secs = setting::<u64>("GEMINI_TIMEOUT_SECS").unwrap_or(DEFAULT_GEMINI_TIMEOUT_SECS);
     let max_retries = gemini_max_retries();
     let total_attempts = max_retries + 1;
     let retry_base_ms = gemini_retry_base_ms();
@@ -891,7 +1056,7 @@ async fn invoke_gemini_review(
 
     for attempt in 1..=total_attempts {
         let can_retry = attempt < total_attempts;
-        let response = client.post(&url).json(&request_body).send().await;
+        let response = telemetry::send("gemini", client.post(&url).json(&request_body)).await;
         let response = match response {
             Ok(resp) => resp,
             Err(error) => {
@@ -919,6 +1084,15 @@ async fn invoke_gemini_review(
         };
 
         let status = response.status();
+        if status == HttpStatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            record_rate_limited(retry_after);
+        }
         let body = response.text().await.map_err(|error| {
             (
                 anyhow!("Failed to read Gemini response body: {}", error),
@@ -952,6 +1126,7 @@ async fn invoke_gemini_review(
                 Some(raw_response),
             ));
         }
+        record_gemini_success();
 
         let candidate_text = raw_response
             .get("candidates")
@@ -1150,15 +1325,40 @@ async fn mark_completed(
         _ => PAPER_STATUS_REVISION,
     };
 
-    sqlx::query(
+    // The row lock keeps the logged previous status in step with the update.
+    let mut tx = pool.begin().await?;
+    let (post_id, previous_status): (i64, String) = sqlx::query_as(
+        r#"
+        SELECT p.id, p.paper_status
+        FROM posts p
+        JOIN post_ai_reviews r ON r.post_id = p.id
+        WHERE r.id = ?
+        FOR UPDATE
+        "#,
+    )
+    .bind(review_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // An editorial decision on the reviewed version outranks the AI verdict,
+    // appeal reviews only advise the editor resolving the appeal, and
+    // backfill reviews never move a paper that was already decided.
+    let updated = sqlx::query(
         r#"
         UPDATE posts
         SET
             paper_status = ?,
-            is_published = FALSE,
-            published_at = NULL,
+            is_published = is_preprint AND published_at IS NOT NULL,
+            published_at = CASE WHEN is_preprint THEN published_at END,
             updated_at = ?
         WHERE id = (SELECT post_id FROM post_ai_reviews WHERE id = ?)
+          AND (SELECT trigger_id FROM post_ai_reviews WHERE id = ?) NOT IN (?, ?)
+          AND NOT EXISTS (
+              SELECT 1
+              FROM editorial_decisions ed
+              WHERE ed.post_id = posts.id
+                AND ed.paper_version_id <=> posts.latest_paper_version_id
+          )
           AND (
               (
                   (SELECT paper_version_id FROM post_ai_reviews WHERE id = ?) IS NOT NULL
@@ -1175,10 +1375,26 @@ async fn mark_completed(
     .bind(now)
     .bind(review_id)
     .bind(review_id)
+    .bind(AI_REVIEW_TRIGGER_APPEAL_ID)
+    .bind(AI_REVIEW_TRIGGER_BACKFILL_ID)
     .bind(review_id)
     .bind(review_id)
-    .execute(pool)
+    .bind(review_id)
+    .execute(&mut *tx)
     .await?;
+    if updated.rows_affected() > 0 {
+        record_status_transition(
+            &mut *tx,
+            post_id,
+            None,
+            STATUS_TRIGGER_AI_REVIEW,
+            Some(&previous_status),
+            next_paper_status,
+        )
+        .await?;
+    }
+    tx.commit().await?;
+    invalidate_cached_post(post_id).await;
 
     Ok(())
 }
@@ -1193,11 +1409,11 @@ async fn mark_failed(
     let now = Utc::now();
     let raw_json = raw_response
         .as_ref()
-        .map(|value| serde_json::to_string(value))
+        .map(serde_json::to_string)
         .transpose()?;
     let input_json = input_snapshot
         .as_ref()
-        .map(|value| serde_json::to_string(value))
+        .map(serde_json::to_string)
         .transpose()?;
 
     sqlx::query(
@@ -1221,13 +1437,35 @@ async fn mark_failed(
     .execute(pool)
     .await?;
 
+    let post: Option<(i64, String)> = sqlx::query_as(
+        "SELECT p.id, p.title FROM post_ai_reviews r JOIN posts p ON p.id = r.post_id WHERE r.id = ?",
+    )
+    .bind(review_id)
+    .fetch_optional(pool)
+    .await?;
+    if let Some((post_id, title)) = post {
+        queue_webhook_message(
+            pool,
+            WebhookMessage {
+                event: WEBHOOK_EVENT_REVIEW_FAILURE,
+                title: format!("AI review failed: {}", title),
+                body: match request_id::current() {
+                    Some(request_id) => format!(
+                        "Review {} failed: {} (request {})",
+                        review_id, error_message, request_id
+                    ),
+                    None => format!("Review {} failed: {}", review_id, error_message),
+                },
+                post_id: Some(post_id),
+            },
+        );
+    }
+
     Ok(())
 }
 
 fn max_input_chars() -> usize {
-    std::env::var("AI_REVIEW_MAX_INPUT_CHARS")
-        .ok()
-        .and_then(|raw| raw.parse::<usize>().ok())
+    setting::<usize>("AI_REVIEW_MAX_INPUT_CHARS")
         .filter(|value| *value > 2000)
         .unwrap_or(DEFAULT_MAX_INPUT_CHARS)
 }
@@ -0,0 +1,70 @@
+//! API versioning. Every route is served under `/api/v1`, and the original
+//! unversioned `/api/...` paths stay mounted as aliases of v1 so existing
+//! clients keep working; alias responses carry deprecation headers that
+//! point at the versioned path.
+//!
+//! Breaking response changes (such as the co-author and pagination
+//! envelopes) belong in a new version: build a v2 router that merges the
+//! changed routes over the v1 ones and mount it at `/api/v2`, leaving v1
+//! and the alias unchanged.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+pub const API_PREFIX: &str = "/api";
+pub const API_V1_PREFIX: &str = "/api/v1";
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// HTTP date after which the unversioned aliases may be removed, announced
+/// in the `Sunset` header when set.
+static UNVERSIONED_SUNSET: LazyLock<Option<HeaderValue>> = LazyLock::new(|| {
+    std::env::var("API_UNVERSIONED_SUNSET")
+        .ok()
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty())
+        .and_then(|raw| HeaderValue::from_str(&raw).ok())
+});
+
+/// Marks responses from the unversioned aliases as deprecated and links to
+/// the same path under the current version.
+pub async fn mark_unversioned_deprecated(request: Request, next: Next) -> Response {
+    let successor = versioned_path(request.uri().path());
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+    headers.insert(DEPRECATION, HeaderValue::from_static("true"));
+    if let Ok(link) = HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor))
+    {
+        headers.append(axum::http::header::LINK, link);
+    }
+    if let Some(sunset) = UNVERSIONED_SUNSET.as_ref() {
+        headers.insert(SUNSET, sunset.clone());
+    }
+    response
+}
+
+/// Maps an unversioned `/api/...` path to its `/api/v1/...` equivalent.
+fn versioned_path(path: &str) -> String {
+    let rest = path.strip_prefix(API_PREFIX).unwrap_or(path);
+    format!("{}{}", API_V1_PREFIX, rest)
+}
+
+/// Strips the version segment so `/api/v1/...` and its alias compare
+/// equal, e.g. when classifying or logging requests.
+pub fn unversioned_path(path: &str) -> Cow<'_, str> {
+    match path.strip_prefix(API_V1_PREFIX) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            Cow::Owned(format!("{}{}", API_PREFIX, rest))
+        }
+        _ => Cow::Borrowed(path),
+    }
+}
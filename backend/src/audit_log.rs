@@ -0,0 +1,178 @@
+use axum::http::{HeaderMap, header};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Executor, FromRow, MySql};
+
+use crate::client_ip;
+use crate::models::User;
+
+pub const AUDIT_ACTION_USER_ROLE_UPDATE: &str = "user.role_update";
+pub const AUDIT_ACTION_USER_DELETE: &str = "user.delete";
+pub const AUDIT_ACTION_USER_SUSPEND: &str = "user.suspend";
+pub const AUDIT_ACTION_USER_UNSUSPEND: &str = "user.unsuspend";
+pub const AUDIT_ACTION_USER_ERASE: &str = "user.erase";
+pub const AUDIT_ACTION_USER_ERASURE_REJECT: &str = "user.erasure_reject";
+pub const AUDIT_ACTION_USER_RESTORE: &str = "user.restore";
+pub const AUDIT_ACTION_USER_PURGE: &str = "user.purge";
+pub const AUDIT_ACTION_POST_DELETE: &str = "post.delete";
+pub const AUDIT_ACTION_POST_RESTORE: &str = "post.restore";
+pub const AUDIT_ACTION_POST_PURGE: &str = "post.purge";
+pub const AUDIT_ACTION_POST_PUBLISH: &str = "post.publish";
+pub const AUDIT_ACTION_POST_RETRACT: &str = "post.retract";
+pub const AUDIT_ACTION_POST_RETRACTION_WITHDRAW: &str = "post.retraction_withdraw";
+pub const AUDIT_ACTION_POST_UNPUBLISH: &str = "post.unpublish";
+pub const AUDIT_ACTION_POST_CATEGORY_UPDATE: &str = "post.category_update";
+pub const AUDIT_ACTION_COMMENT_DELETE: &str = "comment.delete";
+pub const AUDIT_ACTION_COMMENT_REPORT_ACTION: &str = "comment_report.action";
+pub const AUDIT_ACTION_COMMENT_BAN_LIFT: &str = "comment_ban.lift";
+pub const AUDIT_ACTION_EDITORIAL_DECISION: &str = "paper.editorial_decision";
+pub const AUDIT_ACTION_APPEAL_RESOLVE: &str = "paper.appeal_resolve";
+pub const AUDIT_ACTION_CONFLICT_OVERRIDE: &str = "reviewer_assignment.conflict_override";
+pub const AUDIT_ACTION_SETTING_UPDATE: &str = "setting.update";
+pub const AUDIT_ACTION_SETTING_RESET: &str = "setting.reset";
+pub const AUDIT_ACTION_FEATURE_FLAG_UPDATE: &str = "feature_flag.update";
+pub const AUDIT_ACTION_FEATURE_FLAG_RESET: &str = "feature_flag.reset";
+pub const AUDIT_ACTION_ANNOUNCEMENT_CREATE: &str = "announcement.create";
+pub const AUDIT_ACTION_ANNOUNCEMENT_UPDATE: &str = "announcement.update";
+pub const AUDIT_ACTION_ANNOUNCEMENT_DELETE: &str = "announcement.delete";
+pub const AUDIT_ACTION_REVIEW_BACKFILL_START: &str = "review_backfill.start";
+pub const AUDIT_ACTION_REVIEW_BACKFILL_CANCEL: &str = "review_backfill.cancel";
+pub const AUDIT_ACTION_CATEGORY_CREATE: &str = "category.create";
+pub const AUDIT_ACTION_CATEGORY_UPDATE: &str = "category.update";
+pub const AUDIT_ACTION_CATEGORY_MERGE: &str = "category.merge";
+pub const AUDIT_ACTION_BACKUP_CREATE: &str = "backup.create";
+pub const AUDIT_ACTION_IP_BLOCK_CREATE: &str = "ip_block.create";
+pub const AUDIT_ACTION_IP_BLOCK_DELETE: &str = "ip_block.delete";
+pub const AUDIT_ACTION_BROADCAST_CREATE: &str = "broadcast.create";
+pub const AUDIT_ACTION_BROADCAST_CANCEL: &str = "broadcast.cancel";
+pub const AUDIT_ACTION_WEBHOOK_CREATE: &str = "webhook.create";
+pub const AUDIT_ACTION_WEBHOOK_UPDATE: &str = "webhook.update";
+pub const AUDIT_ACTION_WEBHOOK_DELETE: &str = "webhook.delete";
+
+pub const AUDIT_TARGET_USER: &str = "user";
+pub const AUDIT_TARGET_POST: &str = "post";
+pub const AUDIT_TARGET_COMMENT: &str = "comment";
+pub const AUDIT_TARGET_COMMENT_REPORT: &str = "comment_report";
+pub const AUDIT_TARGET_REVIEWER_ASSIGNMENT: &str = "reviewer_assignment";
+pub const AUDIT_TARGET_SYSTEM_SETTING: &str = "system_setting";
+pub const AUDIT_TARGET_FEATURE_FLAG: &str = "feature_flag";
+pub const AUDIT_TARGET_ANNOUNCEMENT: &str = "announcement";
+pub const AUDIT_TARGET_REVIEW_BACKFILL: &str = "review_backfill";
+pub const AUDIT_TARGET_CATEGORY: &str = "category";
+pub const AUDIT_TARGET_DATABASE_BACKUP: &str = "database_backup";
+pub const AUDIT_TARGET_IP_BLOCK: &str = "ip_block";
+pub const AUDIT_TARGET_BROADCAST: &str = "broadcast";
+pub const AUDIT_TARGET_OUTGOING_WEBHOOK: &str = "outgoing_webhook";
+
+const USER_AGENT_MAX_CHARS: usize = 512;
+
+/// Where an audited request came from. The client address is the one
+/// `client_ip::assign_client_ip` resolved for the request being served, so
+/// forwarded headers only count when they come from a trusted proxy.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMetadata {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl RequestMetadata {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let ip_address = client_ip::current().map(|address| address.to_string());
+        let user_agent = headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| value.chars().take(USER_AGENT_MAX_CHARS).collect());
+
+        Self {
+            ip_address,
+            user_agent,
+        }
+    }
+}
+
+/// One audited action. `before` and `after` are JSON snapshots of the
+/// fields the action changed; deletes only have `before`.
+pub struct AuditEvent<'a> {
+    pub actor: &'a User,
+    pub action: &'a str,
+    pub target_type: &'a str,
+    pub target_id: i64,
+    pub request: &'a RequestMetadata,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Appends one row to `audit_log`. Callers with a transaction pass it so
+/// the entry commits or rolls back with the action itself.
+pub async fn record_audit_event<'e, E>(
+    executor: E,
+    event: AuditEvent<'_>,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log
+            (actor_id, actor_username, action, target_type, target_id, ip_address, user_agent, before_snapshot, after_snapshot, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(event.actor.id)
+    .bind(&event.actor.username)
+    .bind(event.action)
+    .bind(event.target_type)
+    .bind(event.target_id)
+    .bind(&event.request.ip_address)
+    .bind(&event.request.user_agent)
+    .bind(event.before.map(|value| value.to_string()))
+    .bind(event.after.map(|value| value.to_string()))
+    .bind(Utc::now())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// The fields of a post worth keeping in a `before` snapshot.
+#[derive(Debug, Serialize, FromRow)]
+struct PostSnapshot {
+    title: String,
+    author_id: i64,
+    category: String,
+    paper_status: String,
+    is_published: bool,
+    published_at: Option<DateTime<Utc>>,
+}
+
+/// Snapshot of a post for its audit entry. `None` when the post does not
+/// exist.
+pub async fn snapshot_post<'e, E>(
+    executor: E,
+    post_id: i64,
+) -> Result<Option<serde_json::Value>, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    let snapshot = sqlx::query_as::<_, PostSnapshot>(
+        r#"
+        SELECT
+            p.title,
+            p.author_id,
+            c.code AS category,
+            p.paper_status,
+            p.is_published,
+            p.published_at
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.id = ?
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(snapshot.map(|snapshot| serde_json::json!(snapshot)))
+}
@@ -0,0 +1,360 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::MySqlPool;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::db::database_url;
+use crate::models::{
+    BACKUP_STATUS_COMPLETED, BACKUP_STATUS_EXPIRED, BACKUP_STATUS_FAILED, BACKUP_STATUS_RUNNING,
+    BACKUP_TRIGGER_SCHEDULED,
+};
+use crate::tasks;
+
+pub const DEFAULT_BACKUP_INTERVAL_SECS: u64 = 86_400;
+pub const DEFAULT_BACKUP_RETENTION_COUNT: i64 = 14;
+pub const DEFAULT_BACKUP_TIMEOUT_SECS: u64 = 1_800;
+
+const DEFAULT_BACKUP_DIR: &str = "backups";
+const DEFAULT_MYSQLDUMP_BIN: &str = "mysqldump";
+
+#[derive(Debug)]
+pub enum BackupStartError {
+    /// Another backup is still running.
+    AlreadyRunning(i64),
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for BackupStartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyRunning(backup_id) => {
+                write!(f, "backup {} is still running", backup_id)
+            }
+            Self::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<sqlx::Error> for BackupStartError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+/// Records a running backup and dumps the database in the background.
+/// Only one backup runs at a time across server instances; a backup still
+/// marked running after `BACKUP_TIMEOUT_SECS` is assumed to have died with
+/// its server and is marked failed.
+pub async fn start_backup(
+    pool: &MySqlPool,
+    trigger_source: &str,
+    started_by: Option<i64>,
+) -> Result<i64, BackupStartError> {
+    let now = Utc::now();
+    let stale_before = now - chrono::Duration::seconds(backup_timeout_secs() as i64);
+    let file_name = format!(
+        "thought_manifold-{}-{}.sql",
+        now.format("%Y%m%dT%H%M%SZ"),
+        &Uuid::new_v4().simple().to_string()[..8]
+    );
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        UPDATE database_backups
+        SET status = ?, error_message = 'Backup was interrupted', finished_at = ?
+        WHERE status = ? AND started_at < ?
+        "#,
+    )
+    .bind(BACKUP_STATUS_FAILED)
+    .bind(now)
+    .bind(BACKUP_STATUS_RUNNING)
+    .bind(stale_before)
+    .execute(&mut *tx)
+    .await?;
+    let running: Option<i64> =
+        sqlx::query_scalar("SELECT id FROM database_backups WHERE status = ? LIMIT 1 FOR UPDATE")
+            .bind(BACKUP_STATUS_RUNNING)
+            .fetch_optional(&mut *tx)
+            .await?;
+    if let Some(running_id) = running {
+        return Err(BackupStartError::AlreadyRunning(running_id));
+    }
+    let result = sqlx::query(
+        r#"
+        INSERT INTO database_backups (file_name, status, trigger_source, started_by, started_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&file_name)
+    .bind(BACKUP_STATUS_RUNNING)
+    .bind(trigger_source)
+    .bind(started_by)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let backup_id = result.last_insert_id() as i64;
+    let pool = pool.clone();
+    tasks::spawn_task("database_backup", async move {
+        if let Err(error) = run_backup(&pool, backup_id, &file_name).await {
+            tracing::error!("Failed to record result of backup {}: {}", backup_id, error);
+        }
+    });
+    Ok(backup_id)
+}
+
+pub fn spawn_database_backups(pool: MySqlPool) {
+    let interval_secs = backup_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Scheduled database backups are disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "database_backups",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match backup_is_due(&pool, interval_secs).await {
+                    Ok(false) => return,
+                    Ok(true) => {}
+                    Err(error) => {
+                        tracing::error!("Database backup check failed: {}", error);
+                        return;
+                    }
+                }
+                match start_backup(&pool, BACKUP_TRIGGER_SCHEDULED, None).await {
+                    Ok(backup_id) => {
+                        tracing::info!("Started scheduled database backup {}", backup_id)
+                    }
+                    Err(BackupStartError::AlreadyRunning(_)) => {}
+                    Err(error) => tracing::error!("Scheduled database backup failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Skips the scheduled run when a backup started within the last half
+/// interval, so restarts and other server instances do not pile up dumps.
+async fn backup_is_due(pool: &MySqlPool, interval_secs: u64) -> Result<bool, sqlx::Error> {
+    let since = Utc::now() - chrono::Duration::seconds((interval_secs / 2) as i64);
+    let recent: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM database_backups WHERE status <> ? AND started_at > ?",
+    )
+    .bind(BACKUP_STATUS_FAILED)
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+    Ok(recent == 0)
+}
+
+async fn run_backup(pool: &MySqlPool, backup_id: i64, file_name: &str) -> Result<(), sqlx::Error> {
+    match dump_database(file_name).await {
+        Ok(size_bytes) => {
+            sqlx::query(
+                "UPDATE database_backups SET status = ?, size_bytes = ?, finished_at = ? WHERE id = ?",
+            )
+            .bind(BACKUP_STATUS_COMPLETED)
+            .bind(size_bytes)
+            .bind(Utc::now())
+            .bind(backup_id)
+            .execute(pool)
+            .await?;
+            tracing::info!("Database backup {} written to {}", backup_id, file_name);
+            prune_backups(pool).await?;
+        }
+        Err(message) => {
+            tracing::error!("Database backup {} failed: {}", backup_id, message);
+            sqlx::query(
+                "UPDATE database_backups SET status = ?, error_message = ?, finished_at = ? WHERE id = ?",
+            )
+            .bind(BACKUP_STATUS_FAILED)
+            .bind(message)
+            .bind(Utc::now())
+            .bind(backup_id)
+            .execute(pool)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs `mysqldump` into a partial file and renames it once complete, so a
+/// listed file is never a truncated dump. Returns the size of the dump.
+async fn dump_database(file_name: &str) -> Result<i64, String> {
+    let target = DumpTarget::from_url(&database_url())?;
+    let dir = backup_dir();
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|error| format!("cannot create {}: {}", dir.display(), error))?;
+    let path = dir.join(file_name);
+    let partial_path = dir.join(format!("{}.partial", file_name));
+
+    let result = run_mysqldump(&target, &partial_path).await;
+    if let Err(message) = result {
+        let _ = tokio::fs::remove_file(&partial_path).await;
+        return Err(message);
+    }
+    tokio::fs::rename(&partial_path, &path)
+        .await
+        .map_err(|error| error.to_string())?;
+    let metadata = tokio::fs::metadata(&path)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(metadata.len() as i64)
+}
+
+async fn run_mysqldump(target: &DumpTarget, output_path: &Path) -> Result<(), String> {
+    let mut command = Command::new(mysqldump_bin());
+    command
+        .arg("--single-transaction")
+        .arg("--quick")
+        .arg("--routines")
+        .arg("--triggers")
+        .arg("--no-tablespaces")
+        .arg("--protocol=TCP")
+        .arg(format!("--host={}", target.host))
+        .arg(format!("--port={}", target.port))
+        .arg(format!("--user={}", target.user))
+        .arg(format!("--result-file={}", output_path.display()))
+        .arg(&target.database)
+        .kill_on_drop(true);
+    // Passed through the environment so the password stays out of `ps`.
+    if let Some(password) = &target.password {
+        command.env("MYSQL_PWD", password);
+    }
+
+    let output =
+        match tokio::time::timeout(Duration::from_secs(backup_timeout_secs()), command.output())
+            .await
+        {
+            Ok(Ok(output)) => output,
+            Ok(Err(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(format!("{} is not installed", mysqldump_bin()));
+            }
+            Ok(Err(error)) => return Err(error.to_string()),
+            Err(_) => return Err("mysqldump timed out".to_string()),
+        };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "mysqldump exited with {}: {}",
+            output.status,
+            stderr.trim().chars().take(500).collect::<String>()
+        ));
+    }
+    Ok(())
+}
+
+/// Deletes the files of completed backups beyond `BACKUP_RETENTION_COUNT`,
+/// newest kept first, and marks their rows expired.
+async fn prune_backups(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    let retention = backup_retention_count();
+    if retention == 0 {
+        return Ok(());
+    }
+
+    let completed: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT id, file_name FROM database_backups WHERE status = ? ORDER BY started_at DESC, id DESC",
+    )
+    .bind(BACKUP_STATUS_COMPLETED)
+    .fetch_all(pool)
+    .await?;
+    let dir = backup_dir();
+    for (backup_id, file_name) in completed.into_iter().skip(retention as usize) {
+        match tokio::fs::remove_file(dir.join(&file_name)).await {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                tracing::warn!("Failed to remove expired backup {}: {}", file_name, error);
+                continue;
+            }
+        }
+        sqlx::query("UPDATE database_backups SET status = ? WHERE id = ?")
+            .bind(BACKUP_STATUS_EXPIRED)
+            .bind(backup_id)
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Connection details for `mysqldump`, taken from the server's own
+/// `DATABASE_URL`.
+struct DumpTarget {
+    host: String,
+    port: u16,
+    user: String,
+    password: Option<String>,
+    database: String,
+}
+
+impl DumpTarget {
+    fn from_url(raw: &str) -> Result<Self, String> {
+        let url =
+            reqwest::Url::parse(raw).map_err(|error| format!("invalid DATABASE_URL: {}", error))?;
+        let decode = |value: &str| {
+            urlencoding::decode(value)
+                .map(|decoded| decoded.into_owned())
+                .map_err(|error| format!("invalid DATABASE_URL: {}", error))
+        };
+        let database = url.path().trim_start_matches('/');
+        if database.is_empty() {
+            return Err("DATABASE_URL does not name a database".to_string());
+        }
+
+        Ok(Self {
+            host: url.host_str().unwrap_or("localhost").to_string(),
+            port: url.port().unwrap_or(3306),
+            user: decode(url.username())?,
+            password: url.password().map(decode).transpose()?,
+            database: decode(database)?,
+        })
+    }
+}
+
+fn backup_dir() -> PathBuf {
+    std::env::var("BACKUP_DIR")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_BACKUP_DIR))
+}
+
+fn mysqldump_bin() -> String {
+    std::env::var("BACKUP_MYSQLDUMP_BIN")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_MYSQLDUMP_BIN.to_string())
+}
+
+fn backup_interval_secs() -> u64 {
+    std::env::var("BACKUP_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_BACKUP_INTERVAL_SECS)
+}
+
+fn backup_timeout_secs() -> u64 {
+    std::env::var("BACKUP_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_BACKUP_TIMEOUT_SECS)
+}
+
+fn backup_retention_count() -> i64 {
+    std::env::var("BACKUP_RETENTION_COUNT")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value >= 0)
+        .unwrap_or(DEFAULT_BACKUP_RETENTION_COUNT)
+}
@@ -0,0 +1,278 @@
+//! Read-through cache for hot public reads: post detail, post listings and
+//! metrics. The Redis backend is compiled in with the `redis-cache` feature
+//! and used when `REDIS_URL` is set; otherwise every lookup misses and
+//! handlers read MySQL as before.
+//!
+//! Post details are removed when the post is written. Listings and metrics
+//! depend on many rows, so their keys carry a generation counter that writes
+//! bump instead. Entries also expire after `CACHE_TTL_SECS`, which bounds
+//! staleness from writers that do not invalidate, such as background jobs
+//! and view counts.
+
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use crate::models::CacheStats;
+
+const DEFAULT_CACHE_TTL_SECS: u64 = 60;
+const KEY_PREFIX: &str = "thought-manifold:";
+const POST_LIST_GENERATION: &str = "posts:generation";
+const METRICS_GENERATION: &str = "metrics:generation";
+
+static CACHE_TTL_SECS: LazyLock<u64> = LazyLock::new(|| {
+    std::env::var("CACHE_TTL_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_CACHE_TTL_SECS)
+});
+
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+static ERRORS: AtomicU64 = AtomicU64::new(0);
+
+/// What a cached value is, which decides how it is invalidated.
+pub enum CacheKey {
+    /// Public view of one post, removed when the post changes.
+    Post(i64),
+    /// One page of a post listing, identified by its query.
+    PostList(String),
+    /// A metrics response, identified by endpoint and parameters.
+    Metrics(String),
+}
+
+impl CacheKey {
+    async fn resolve(&self) -> anyhow::Result<String> {
+        Ok(match self {
+            Self::Post(post_id) => format!("{}post:{}", KEY_PREFIX, post_id),
+            Self::PostList(query) => format!(
+                "{}posts:{}:{}",
+                KEY_PREFIX,
+                generation(POST_LIST_GENERATION).await?,
+                digest(query)
+            ),
+            Self::Metrics(name) => format!(
+                "{}metrics:{}:{}",
+                KEY_PREFIX,
+                generation(METRICS_GENERATION).await?,
+                digest(name)
+            ),
+        })
+    }
+}
+
+/// Connects to Redis when the backend is compiled in and configured.
+pub async fn init_cache() {
+    backend::init().await;
+}
+
+/// The cached value for `key`, or `None` on a miss, an error or when the
+/// cache is off.
+pub async fn fetch_cached<T: DeserializeOwned>(key: &CacheKey) -> Option<T> {
+    if !backend::enabled() {
+        return None;
+    }
+    let cached = match key.resolve().await {
+        Ok(key) => backend::get(&key).await,
+        Err(error) => Err(error),
+    };
+    match cached {
+        Ok(Some(raw)) => match serde_json::from_str(&raw) {
+            Ok(value) => {
+                HITS.fetch_add(1, Ordering::Relaxed);
+                Some(value)
+            }
+            // Written by another version of the type; treat as a miss.
+            Err(_) => {
+                MISSES.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        },
+        Ok(None) => {
+            MISSES.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+        Err(error) => {
+            record_error("read", &error);
+            None
+        }
+    }
+}
+
+pub async fn store_cached<T: Serialize>(key: &CacheKey, value: &T) {
+    if !backend::enabled() {
+        return;
+    }
+    let result = async {
+        let raw = serde_json::to_string(value)?;
+        backend::set(&key.resolve().await?, &raw, *CACHE_TTL_SECS).await
+    }
+    .await;
+    if let Err(error) = result {
+        record_error("write", &error);
+    }
+}
+
+/// Drops the post's cached detail and every cached listing, after the post
+/// was created, edited, published, deleted or otherwise changed.
+pub async fn invalidate_cached_post(post_id: i64) {
+    if !backend::enabled() {
+        return;
+    }
+    if let Err(error) = backend::delete(&format!("{}post:{}", KEY_PREFIX, post_id)).await {
+        record_error("invalidate", &error);
+    }
+    invalidate_cached_post_lists().await;
+}
+
+/// Drops every cached listing, for changes that bring posts back or touch
+/// many of them at once.
+pub async fn invalidate_cached_post_lists() {
+    if !backend::enabled() {
+        return;
+    }
+    if let Err(error) = backend::increment(&format!("{}{}", KEY_PREFIX, POST_LIST_GENERATION)).await
+    {
+        record_error("invalidate", &error);
+    }
+}
+
+/// Drops every cached metrics response, after citation counts change.
+pub async fn invalidate_cached_metrics() {
+    if !backend::enabled() {
+        return;
+    }
+    if let Err(error) = backend::increment(&format!("{}{}", KEY_PREFIX, METRICS_GENERATION)).await {
+        record_error("invalidate", &error);
+    }
+}
+
+/// Counters since startup, for `GET /admin/system/cache`.
+pub fn cache_stats() -> CacheStats {
+    CacheStats {
+        enabled: backend::enabled(),
+        ttl_secs: *CACHE_TTL_SECS,
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+        errors: ERRORS.load(Ordering::Relaxed),
+    }
+}
+
+async fn generation(name: &str) -> anyhow::Result<i64> {
+    Ok(backend::get(&format!("{}{}", KEY_PREFIX, name))
+        .await?
+        .and_then(|raw| raw.parse().ok())
+        .unwrap_or(0))
+}
+
+/// Listing queries can be long, so keys hold their hash.
+fn digest(value: &str) -> String {
+    Sha256::digest(value.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn record_error(operation: &str, error: &anyhow::Error) {
+    ERRORS.fetch_add(1, Ordering::Relaxed);
+    tracing::warn!("Cache {} failed: {}", operation, error);
+}
+
+#[cfg(feature = "redis-cache")]
+mod backend {
+    use std::sync::OnceLock;
+
+    use redis::AsyncCommands;
+    use redis::aio::ConnectionManager;
+
+    static CONNECTION: OnceLock<ConnectionManager> = OnceLock::new();
+
+    pub async fn init() {
+        let Some(url) = std::env::var("REDIS_URL")
+            .ok()
+            .map(|raw| raw.trim().to_string())
+            .filter(|raw| !raw.is_empty())
+        else {
+            tracing::info!("REDIS_URL is not set; response cache disabled");
+            return;
+        };
+        let connection = match redis::Client::open(url) {
+            Ok(client) => ConnectionManager::new(client).await,
+            Err(error) => Err(error),
+        };
+        match connection {
+            Ok(connection) => {
+                let _ = CONNECTION.set(connection);
+                tracing::info!("Response cache connected to Redis");
+            }
+            Err(error) => tracing::warn!("Failed to connect to Redis; cache disabled: {}", error),
+        }
+    }
+
+    pub fn enabled() -> bool {
+        CONNECTION.get().is_some()
+    }
+
+    fn connection() -> anyhow::Result<ConnectionManager> {
+        CONNECTION
+            .get()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Redis is not connected"))
+    }
+
+    pub async fn get(key: &str) -> anyhow::Result<Option<String>> {
+        Ok(connection()?.get(key).await?)
+    }
+
+    pub async fn set(key: &str, value: &str, ttl_secs: u64) -> anyhow::Result<()> {
+        connection()?
+            .set_ex::<_, _, ()>(key, value, ttl_secs)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(key: &str) -> anyhow::Result<()> {
+        connection()?.del::<_, ()>(key).await?;
+        Ok(())
+    }
+
+    pub async fn increment(key: &str) -> anyhow::Result<()> {
+        connection()?.incr::<_, _, ()>(key, 1).await?;
+        Ok(())
+    }
+}
+
+/// Stand-in when the Redis backend is not compiled in; `enabled` is false,
+/// so the other functions are never reached.
+#[cfg(not(feature = "redis-cache"))]
+mod backend {
+    pub async fn init() {
+        if std::env::var("REDIS_URL").is_ok_and(|url| !url.trim().is_empty()) {
+            tracing::warn!("REDIS_URL is set but the redis-cache feature is not compiled in");
+        }
+    }
+
+    pub fn enabled() -> bool {
+        false
+    }
+
+    pub async fn get(_key: &str) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    pub async fn set(_key: &str, _value: &str, _ttl_secs: u64) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    pub async fn delete(_key: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    pub async fn increment(_key: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::models::User;
+
+/// Issues published longer ago than this are left out of the feed.
+const PAST_ISSUE_DAYS: i64 = 365;
+/// RFC 5545 lines are folded after 75 octets.
+const MAX_LINE_OCTETS: usize = 75;
+
+// Open assignments the user reviews, or assigned as editor.
+const REVIEW_DEADLINE_SELECT: &str = r#"
+    SELECT
+        ra.id AS id,
+        ra.post_id AS post_id,
+        ra.due_at AS due_at,
+        p.title AS title,
+        ra.reviewer_id AS owner_id
+    FROM reviewer_assignments ra
+    JOIN posts p ON p.id = ra.post_id
+    WHERE ra.status IN ('invited', 'accepted')
+      AND ra.due_at IS NOT NULL
+      AND p.deleted_at IS NULL
+      AND (ra.reviewer_id = ? OR ra.assigned_by = ?)
+"#;
+
+// The latest decision of each paper still awaiting the author's revision,
+// for its author or the editor who requested it.
+const REVISION_DEADLINE_SELECT: &str = r#"
+    SELECT
+        ed.id AS id,
+        ed.post_id AS post_id,
+        ed.revision_due_at AS due_at,
+        p.title AS title,
+        p.author_id AS owner_id
+    FROM editorial_decisions ed
+    JOIN posts p ON p.id = ed.post_id
+    WHERE ed.revision_due_at IS NOT NULL
+      AND p.paper_status = 'revision'
+      AND p.deleted_at IS NULL
+      AND p.latest_paper_version_id <=> ed.paper_version_id
+      AND ed.id = (SELECT MAX(ed2.id) FROM editorial_decisions ed2 WHERE ed2.post_id = ed.post_id)
+      AND (p.author_id = ? OR ed.editor_id = ?)
+"#;
+
+#[derive(Debug, FromRow)]
+struct DeadlineRow {
+    id: i64,
+    post_id: i64,
+    due_at: DateTime<Utc>,
+    title: String,
+    /// The reviewer or author; anyone else is the editor.
+    owner_id: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct IssueRow {
+    id: i64,
+    volume: i64,
+    number: i64,
+    title: Option<String>,
+    publication_date: NaiveDate,
+}
+
+#[derive(Debug)]
+enum EventStart {
+    At(DateTime<Utc>),
+    AllDay(NaiveDate),
+}
+
+#[derive(Debug)]
+struct CalendarEvent {
+    uid: String,
+    start: EventStart,
+    summary: String,
+    url: Option<String>,
+}
+
+/// A new random feed token. Only its hash is stored.
+pub fn generate_feed_token() -> String {
+    let bytes: [u8; 32] = rand::rng().random();
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn hash_feed_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// The owner of a feed token, recording that the feed was fetched.
+pub async fn find_feed_token_user(
+    pool: &MySqlPool,
+    token: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    let token_hash = hash_feed_token(token);
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        SELECT u.*
+        FROM calendar_feed_tokens t
+        JOIN users u ON u.id = t.user_id
+        WHERE t.token_hash = ? AND u.deleted_at IS NULL
+        "#,
+    )
+    .bind(&token_hash)
+    .fetch_optional(pool)
+    .await?;
+    if user.is_some() {
+        sqlx::query("UPDATE calendar_feed_tokens SET last_used_at = ? WHERE token_hash = ?")
+            .bind(Utc::now())
+            .bind(&token_hash)
+            .execute(pool)
+            .await?;
+    }
+    Ok(user)
+}
+
+/// The user's editorial calendar as an iCalendar document: open review
+/// deadlines, revision due dates and issue publication dates.
+pub async fn build_user_calendar(pool: &MySqlPool, user: &User) -> Result<String, sqlx::Error> {
+    let mut events = Vec::new();
+
+    let reviews = sqlx::query_as::<_, DeadlineRow>(REVIEW_DEADLINE_SELECT)
+        .bind(user.id)
+        .bind(user.id)
+        .fetch_all(pool)
+        .await?;
+    events.extend(reviews.into_iter().map(|row| CalendarEvent {
+        uid: format!("review-assignment-{}", row.id),
+        start: EventStart::At(row.due_at),
+        summary: if row.owner_id == user.id {
+            format!("Review due: {}", row.title)
+        } else {
+            format!("Assigned review due: {}", row.title)
+        },
+        url: Some(post_url(row.post_id)),
+    }));
+
+    let revisions = sqlx::query_as::<_, DeadlineRow>(REVISION_DEADLINE_SELECT)
+        .bind(user.id)
+        .bind(user.id)
+        .fetch_all(pool)
+        .await?;
+    events.extend(revisions.into_iter().map(|row| CalendarEvent {
+        uid: format!("revision-request-{}", row.id),
+        start: EventStart::At(row.due_at),
+        summary: if row.owner_id == user.id {
+            format!("Revision due: {}", row.title)
+        } else {
+            format!("Requested revision due: {}", row.title)
+        },
+        url: Some(post_url(row.post_id)),
+    }));
+
+    let issues = sqlx::query_as::<_, IssueRow>(
+        r#"
+        SELECT
+            id,
+            CAST(volume AS SIGNED) AS volume,
+            CAST(number AS SIGNED) AS number,
+            title,
+            publication_date
+        FROM issues
+        WHERE publication_date IS NOT NULL AND publication_date >= ?
+        ORDER BY publication_date ASC, id ASC
+        "#,
+    )
+    .bind((Utc::now() - Duration::days(PAST_ISSUE_DAYS)).date_naive())
+    .fetch_all(pool)
+    .await?;
+    events.extend(issues.into_iter().map(|row| {
+        let label = format!("Vol. {} No. {}", row.volume, row.number);
+        CalendarEvent {
+            uid: format!("issue-{}", row.id),
+            start: EventStart::AllDay(row.publication_date),
+            summary: match row.title {
+                Some(title) => format!("Issue published: {} — {}", label, title),
+                None => format!("Issue published: {}", label),
+            },
+            url: None,
+        }
+    }));
+
+    Ok(render_calendar(&events))
+}
+
+fn render_calendar(events: &[CalendarEvent]) -> String {
+    let stamp = format_timestamp(Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Thought Manifold//Editorial Calendar//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Thought Manifold".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@thought-manifold", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        match event.start {
+            EventStart::At(at) => {
+                lines.push(format!("DTSTART:{}", format_timestamp(at)));
+                lines.push(format!("DTEND:{}", format_timestamp(at)));
+            }
+            EventStart::AllDay(date) => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+                lines.push(format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (date + Duration::days(1)).format("%Y%m%d")
+                ));
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(url) = &event.url {
+            lines.push(format!("URL:{}", url));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+/// Ends the line with CRLF, continuing it on lines that start with a space
+/// whenever it runs past 75 octets. Never splits a UTF-8 character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+    for ch in line.chars() {
+        if octets + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(ch);
+        octets += ch.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn post_url(post_id: i64) -> String {
+    format!("{}/posts/{}", frontend_base_url(), post_id)
+}
+
+fn frontend_base_url() -> String {
+    std::env::var("FRONTEND_URL")
+        .ok()
+        .map(|value| value.trim().trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "http://localhost:5173".to_string())
+}
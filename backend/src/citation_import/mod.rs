@@ -0,0 +1,391 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode as HttpStatusCode, Url};
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::metrics::refresh_post_citation_counts;
+use crate::tasks;
+
+pub const DEFAULT_OPENALEX_IMPORT_INTERVAL_SECS: u64 = 86_400;
+pub const DEFAULT_OPENALEX_TIMEOUT_SECS: u64 = 15;
+pub const DEFAULT_OPENALEX_MAX_PAGES: u32 = 5;
+
+const OPENALEX_API_BASE: &str = "https://api.openalex.org";
+const OPENALEX_WORK_ID_PREFIX: &str = "https://openalex.org/";
+const DOI_URL_PREFIX: &str = "https://doi.org/";
+const OPENALEX_PAGE_SIZE: u32 = 200;
+const CITATION_SOURCE_OPENALEX: u8 = 3;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportSummary {
+    pub posts_checked: usize,
+    pub posts_failed: usize,
+    pub citations_seen: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAlexWork {
+    id: String,
+    #[serde(default)]
+    cited_by_count: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAlexCitingWork {
+    id: String,
+    doi: Option<String>,
+    display_name: Option<String>,
+    publication_year: Option<i32>,
+    primary_location: Option<OpenAlexLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAlexLocation {
+    source: Option<OpenAlexSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAlexSource {
+    display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAlexListMeta {
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAlexListResponse {
+    meta: OpenAlexListMeta,
+    #[serde(default)]
+    results: Vec<OpenAlexCitingWork>,
+}
+
+/// Starts the periodic OpenAlex reverse-citation import.
+/// Setting `OPENALEX_IMPORT_INTERVAL_SECS=0` disables the job.
+pub fn spawn_reverse_citation_import(pool: MySqlPool) {
+    let interval_secs = import_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("OpenAlex reverse citation import is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "reverse_citation_import",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match run_reverse_citation_import(&pool).await {
+                    Ok(summary) => tracing::info!(
+                        "OpenAlex reverse citation import finished: posts_checked={}, posts_failed={}, citations_seen={}",
+                        summary.posts_checked,
+                        summary.posts_failed,
+                        summary.citations_seen
+                    ),
+                    Err(error) => {
+                        tracing::error!("OpenAlex reverse citation import failed: {}", error)
+                    }
+                }
+            }
+        },
+    );
+}
+
+pub async fn run_reverse_citation_import(pool: &MySqlPool) -> anyhow::Result<ImportSummary> {
+    let registrations: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT post_id, registered_doi
+        FROM post_doi_registrations
+        ORDER BY citations_synced_at IS NOT NULL, citations_synced_at ASC, post_id ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut summary = ImportSummary::default();
+    if registrations.is_empty() {
+        return Ok(summary);
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(openalex_timeout_secs()))
+        .user_agent("ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)")
+        .build()?;
+
+    for (post_id, registered_doi) in registrations {
+        summary.posts_checked += 1;
+        match import_post_citations(pool, &client, post_id, &registered_doi).await {
+            Ok(seen) => summary.citations_seen += seen,
+            Err(error) => {
+                summary.posts_failed += 1;
+                tracing::warn!(
+                    "OpenAlex citation import failed for post {} (doi={}): {}",
+                    post_id,
+                    registered_doi,
+                    error
+                );
+                if let Err(record_error) =
+                    record_sync_error(pool, post_id, &error.to_string()).await
+                {
+                    tracing::warn!(
+                        "Failed to record citation sync error for post {}: {}",
+                        post_id,
+                        record_error
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+async fn import_post_citations(
+    pool: &MySqlPool,
+    client: &Client,
+    post_id: i64,
+    registered_doi: &str,
+) -> anyhow::Result<usize> {
+    let started_at = Utc::now();
+    let Some(work) = fetch_openalex_work(client, registered_doi).await? else {
+        record_sync_error(pool, post_id, "DOI is not indexed by OpenAlex yet").await?;
+        return Ok(0);
+    };
+
+    let work_id = normalize_openalex_work_id(&work.id);
+    let (citing_works, complete) = fetch_openalex_citing_works(client, &work_id).await?;
+
+    let mut tx = pool.begin().await?;
+    for citing_work in &citing_works {
+        let venue = citing_work
+            .primary_location
+            .as_ref()
+            .and_then(|location| location.source.as_ref())
+            .and_then(|source| source.display_name.clone());
+
+        sqlx::query(
+            r#"
+            INSERT INTO post_external_citations (
+                cited_post_id,
+                citation_source_id,
+                external_work_id,
+                citing_doi,
+                citing_title,
+                citing_venue,
+                citing_year,
+                first_seen_at,
+                last_seen_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                citing_doi = VALUES(citing_doi),
+                citing_title = VALUES(citing_title),
+                citing_venue = VALUES(citing_venue),
+                citing_year = VALUES(citing_year),
+                last_seen_at = VALUES(last_seen_at)
+            "#,
+        )
+        .bind(post_id)
+        .bind(CITATION_SOURCE_OPENALEX)
+        .bind(normalize_openalex_work_id(&citing_work.id))
+        .bind(citing_work.doi.as_deref().map(normalize_doi_url))
+        .bind(&citing_work.display_name)
+        .bind(venue)
+        .bind(citing_work.publication_year)
+        .bind(started_at)
+        .bind(started_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // Only prune works that disappeared when the full citing list was walked;
+    // a truncated walk cannot tell a removed citation from an unvisited page.
+    if complete {
+        prune_stale_citations(&mut tx, post_id, started_at).await?;
+    }
+    refresh_post_citation_counts(&mut tx, &[post_id]).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE post_doi_registrations
+        SET
+            openalex_work_id = ?,
+            external_cited_by_count = ?,
+            citations_synced_at = ?,
+            last_sync_error = NULL,
+            updated_at = ?
+        WHERE post_id = ?
+        "#,
+    )
+    .bind(&work_id)
+    .bind(work.cited_by_count)
+    .bind(Utc::now())
+    .bind(Utc::now())
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(citing_works.len())
+}
+
+async fn prune_stale_citations(
+    tx: &mut sqlx::Transaction<'_, sqlx::MySql>,
+    post_id: i64,
+    started_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        DELETE FROM post_external_citations
+        WHERE cited_post_id = ?
+          AND citation_source_id = ?
+          AND last_seen_at < ?
+        "#,
+    )
+    .bind(post_id)
+    .bind(CITATION_SOURCE_OPENALEX)
+    .bind(started_at)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn record_sync_error(pool: &MySqlPool, post_id: i64, message: &str) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        UPDATE post_doi_registrations
+        SET citations_synced_at = ?, last_sync_error = ?, updated_at = ?
+        WHERE post_id = ?
+        "#,
+    )
+    .bind(now)
+    .bind(message)
+    .bind(now)
+    .bind(post_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn fetch_openalex_work(client: &Client, doi: &str) -> anyhow::Result<Option<OpenAlexWork>> {
+    let url = openalex_url(&format!("/works/doi:{}", doi), &[])?;
+    let response = client.get(url).send().await?;
+
+    if response.status() == HttpStatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        anyhow::bail!("OpenAlex work lookup returned {}", response.status());
+    }
+
+    let work = response
+        .json::<OpenAlexWork>()
+        .await
+        .context("Failed to decode OpenAlex work")?;
+    Ok(Some(work))
+}
+
+async fn fetch_openalex_citing_works(
+    client: &Client,
+    work_id: &str,
+) -> anyhow::Result<(Vec<OpenAlexCitingWork>, bool)> {
+    let filter = format!("cites:{}", work_id);
+    let per_page = OPENALEX_PAGE_SIZE.to_string();
+    let mut cursor = "*".to_string();
+    let mut works = Vec::new();
+
+    for _ in 0..openalex_max_pages() {
+        let url = openalex_url(
+            "/works",
+            &[
+                ("filter", filter.as_str()),
+                ("per-page", per_page.as_str()),
+                ("cursor", cursor.as_str()),
+                (
+                    "select",
+                    "id,doi,display_name,publication_year,primary_location",
+                ),
+            ],
+        )?;
+        let response = client.get(url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("OpenAlex citing works lookup returned {}", response.status());
+        }
+
+        let page = response
+            .json::<OpenAlexListResponse>()
+            .await
+            .context("Failed to decode OpenAlex citing works")?;
+        let page_is_empty = page.results.is_empty();
+        works.extend(page.results);
+
+        match page.meta.next_cursor {
+            Some(next_cursor) if !page_is_empty => cursor = next_cursor,
+            _ => return Ok((works, true)),
+        }
+    }
+
+    Ok((works, false))
+}
+
+fn openalex_url(path: &str, params: &[(&str, &str)]) -> anyhow::Result<Url> {
+    let mut url = Url::parse(&format!("{}{}", OPENALEX_API_BASE, path))?;
+    {
+        let mut query = url.query_pairs_mut();
+        for (key, value) in params {
+            query.append_pair(key, value);
+        }
+        if let Some(mailto) = openalex_mailto() {
+            query.append_pair("mailto", &mailto);
+        }
+    }
+    Ok(url)
+}
+
+fn normalize_openalex_work_id(raw: &str) -> String {
+    raw.trim()
+        .trim_start_matches(OPENALEX_WORK_ID_PREFIX)
+        .to_string()
+}
+
+fn normalize_doi_url(raw: &str) -> String {
+    raw.trim()
+        .trim_start_matches(DOI_URL_PREFIX)
+        .to_ascii_lowercase()
+}
+
+fn import_interval_secs() -> u64 {
+    std::env::var("OPENALEX_IMPORT_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_OPENALEX_IMPORT_INTERVAL_SECS)
+}
+
+fn openalex_timeout_secs() -> u64 {
+    std::env::var("OPENALEX_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_OPENALEX_TIMEOUT_SECS)
+}
+
+fn openalex_max_pages() -> u32 {
+    std::env::var("OPENALEX_MAX_PAGES")
+        .ok()
+        .and_then(|raw| raw.parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_OPENALEX_MAX_PAGES)
+}
+
+fn openalex_mailto() -> Option<String> {
+    std::env::var("OPENALEX_MAILTO")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
@@ -0,0 +1,114 @@
+//! Client address resolution. The peer address of the connection is the
+//! client, unless the peer is one of the reverse proxies listed in
+//! `TRUSTED_PROXIES` (comma-separated addresses or CIDR ranges). Only then
+//! are `X-Forwarded-For` and `X-Real-IP` read: the forwarded chain is walked
+//! from the right, skipping hops that are themselves trusted proxies, and
+//! the first untrusted hop is the client. Entries left of it were written
+//! by the client and are never used.
+//!
+//! `assign_client_ip` resolves the address once per request, for IP blocks,
+//! request events, audit entries and rate limits alike.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+
+tokio::task_local! {
+    static CURRENT_CLIENT_IP: Option<IpAddr>;
+}
+
+static TRUSTED_PROXIES: LazyLock<Vec<IpNet>> = LazyLock::new(|| {
+    std::env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from));
+            if parsed.is_err() {
+                tracing::warn!("Ignoring invalid TRUSTED_PROXIES entry: {}", entry);
+            }
+            parsed.ok()
+        })
+        .collect()
+});
+
+/// Resolves the client address and serves the rest of the request with it.
+pub async fn assign_client_ip(request: Request, next: Next) -> Response {
+    let client_ip = from_request(&request);
+    CURRENT_CLIENT_IP.scope(client_ip, next.run(request)).await
+}
+
+/// The client address of the request being served; `None` outside a
+/// request.
+pub fn current() -> Option<IpAddr> {
+    CURRENT_CLIENT_IP
+        .try_with(|client_ip| *client_ip)
+        .ok()
+        .flatten()
+}
+
+/// The client address of `request`; `None` when the server was not started
+/// with connect info.
+fn from_request(request: &Request) -> Option<IpAddr> {
+    let ConnectInfo(peer) = request.extensions().get::<ConnectInfo<SocketAddr>>()?;
+    Some(resolve(peer.ip(), request.headers()))
+}
+
+/// The client behind `peer`, following forwarded headers only through
+/// trusted proxies.
+pub fn resolve(peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+    let peer = peer.to_canonical();
+    if !is_trusted_proxy(peer) {
+        return peer;
+    }
+
+    let forwarded_for: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+    if !forwarded_for.is_empty() {
+        let mut client = peer;
+        for hop in forwarded_for.iter().rev() {
+            // A malformed hop ends the chain; nothing left of it can be trusted.
+            let Some(hop) = parse_hop(hop) else {
+                break;
+            };
+            client = hop;
+            if !is_trusted_proxy(hop) {
+                break;
+            }
+        }
+        return client;
+    }
+
+    headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_hop)
+        .unwrap_or(peer)
+}
+
+fn is_trusted_proxy(address: IpAddr) -> bool {
+    TRUSTED_PROXIES.iter().any(|net| net.contains(&address))
+}
+
+/// Accepts a bare address or `address:port`, as some proxies append ports.
+fn parse_hop(raw: &str) -> Option<IpAddr> {
+    let raw = raw.trim();
+    raw.parse::<IpAddr>()
+        .or_else(|_| raw.parse::<SocketAddr>().map(|address| address.ip()))
+        .ok()
+        .map(|address| address.to_canonical())
+}
@@ -0,0 +1,117 @@
+//! Conditional GET for responses that clients poll: post detail, comment
+//! lists and AI reviews. They carry a weak `ETag` hashed from the JSON body
+//! and a `Cache-Control` policy, and a request whose `If-None-Match` already
+//! holds the current tag gets an empty 304 instead of the body.
+//!
+//! Tags are weak because only the JSON value is compared; the bytes on the
+//! wire may differ, e.g. once compressed.
+
+use axum::{
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY},
+    },
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+
+/// How browsers and proxies may keep a response. Either way they revalidate
+/// before reuse, so a change is seen on the next poll.
+#[derive(Debug, Clone, Copy)]
+pub enum CachePolicy {
+    /// The same for every anonymous reader, so shared caches may store it.
+    Public,
+    /// Depends on who is signed in; only the viewer's own cache may keep it.
+    Private,
+}
+
+impl CachePolicy {
+    /// Public for anonymous readers, private once a user is signed in.
+    pub fn for_viewer(signed_in: bool) -> Self {
+        if signed_in {
+            Self::Private
+        } else {
+            Self::Public
+        }
+    }
+
+    fn header_value(self) -> HeaderValue {
+        HeaderValue::from_static(match self {
+            Self::Public => "public, no-cache",
+            Self::Private => "private, no-cache",
+        })
+    }
+}
+
+/// Responds with `body` as JSON, or with 304 when the client's copy is
+/// current.
+pub fn conditional_json<T: Serialize>(
+    request_headers: &HeaderMap,
+    policy: CachePolicy,
+    body: &T,
+) -> Result<Response, AppError> {
+    conditional_json_ignoring(request_headers, policy, body, &[])
+}
+
+/// Like `conditional_json`, but the tag leaves out the top-level `volatile`
+/// fields, such as counters that change on every read. A 304 then keeps the
+/// client's older value of those fields.
+pub fn conditional_json_ignoring<T: Serialize>(
+    request_headers: &HeaderMap,
+    policy: CachePolicy,
+    body: &T,
+    volatile: &[&str],
+) -> Result<Response, AppError> {
+    let body = serde_json::to_value(body).map_err(AppError::internal)?;
+    let bytes = serde_json::to_vec(&body).map_err(AppError::internal)?;
+    let etag = match &body {
+        Value::Object(fields) if !volatile.is_empty() => {
+            let mut tagged = fields.clone();
+            tagged.retain(|field, _| !volatile.contains(&field.as_str()));
+            entity_tag(&serde_json::to_vec(&tagged).map_err(AppError::internal)?)
+        }
+        _ => entity_tag(&bytes),
+    };
+
+    let mut response = if if_none_match(request_headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (
+            [(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+            bytes,
+        )
+            .into_response()
+    };
+    let headers = response.headers_mut();
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(ETAG, etag);
+    }
+    headers.insert(CACHE_CONTROL, policy.header_value());
+    headers.insert(VARY, HeaderValue::from_static("Authorization"));
+    Ok(response)
+}
+
+fn entity_tag(bytes: &[u8]) -> String {
+    let digest: String = Sha256::digest(bytes)[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("W/\"{}\"", digest)
+}
+
+/// Whether `If-None-Match` lists `etag`, using the weak comparison that
+/// header calls for.
+fn if_none_match(request_headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = etag.trim_start_matches("W/");
+    request_headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == opaque)
+}
@@ -0,0 +1,426 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use sqlx::{FromRow, MySql, MySqlPool, QueryBuilder};
+
+use crate::tasks;
+use crate::telemetry;
+
+pub const DEFAULT_CROSSREF_CACHE_REFRESH_INTERVAL_SECS: u64 = 60;
+pub const DEFAULT_CROSSREF_CACHE_TTL_SECS: i64 = 7 * 86_400;
+pub const DEFAULT_CROSSREF_TIMEOUT_SECS: u64 = 8;
+pub const DEFAULT_CROSSREF_CACHE_BATCH_SIZE: i64 = 50;
+
+pub const CACHE_STATUS_PENDING: &str = "pending";
+pub const CACHE_STATUS_FOUND: &str = "found";
+pub const CACHE_STATUS_NOT_FOUND: &str = "not_found";
+
+const CROSSREF_API_BASE: &str = "https://api.crossref.org/works/";
+// A failed lookup is retried sooner than a successful one is refreshed.
+const CROSSREF_RETRY_AFTER_SECS: i64 = 3_600;
+
+/// Crossref metadata of one DOI as last fetched by the refresh job. Fields
+/// are empty while the DOI is pending or unknown to Crossref.
+#[derive(Debug, Clone, FromRow)]
+pub struct CrossrefCacheEntry {
+    pub doi: String,
+    pub title: Option<String>,
+    pub journal: Option<String>,
+    pub publisher: Option<String>,
+    pub published_at: Option<String>,
+    pub source_url: Option<String>,
+    pub raw_json: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct RefreshSummary {
+    refreshed: usize,
+    not_found: usize,
+    failed: usize,
+    pruned: u64,
+}
+
+#[derive(Debug)]
+struct CrossrefWork {
+    title: Option<String>,
+    journal: Option<String>,
+    publisher: Option<String>,
+    published_at: Option<String>,
+    source_url: Option<String>,
+    raw_json: String,
+}
+
+/// Returns the cached entries of `dois` without touching the network. DOIs
+/// seen for the first time are queued for the refresh job and have no entry
+/// until it has run.
+pub async fn lookup_cached_metadata(
+    pool: &MySqlPool,
+    dois: &[String],
+) -> Result<HashMap<String, CrossrefCacheEntry>, sqlx::Error> {
+    if dois.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT doi, title, journal, publisher, published_at, source_url,
+               CAST(raw_json AS CHAR) AS raw_json
+        FROM crossref_cache
+        WHERE doi IN (
+        "#,
+    );
+    {
+        let mut separated = query_builder.separated(", ");
+        for doi in dois {
+            separated.push_bind(doi);
+        }
+    }
+    query_builder.push(")");
+    let entries: HashMap<String, CrossrefCacheEntry> = query_builder
+        .build_query_as::<CrossrefCacheEntry>()
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|entry| (entry.doi.clone(), entry))
+        .collect();
+
+    let missing: Vec<&String> = dois
+        .iter()
+        .filter(|doi| !entries.contains_key(*doi))
+        .collect();
+    if !missing.is_empty() {
+        let now = Utc::now();
+        let mut query_builder = QueryBuilder::<MySql>::new(
+            "INSERT IGNORE INTO crossref_cache (doi, status, expires_at, created_at) ",
+        );
+        query_builder.push_values(missing, |mut row, doi| {
+            row.push_bind(doi)
+                .push_bind(CACHE_STATUS_PENDING)
+                .push_bind(now)
+                .push_bind(now);
+        });
+        query_builder.build().execute(pool).await?;
+    }
+
+    Ok(entries)
+}
+
+/// Starts the periodic job that fetches queued and expired DOIs from
+/// Crossref and copies the results into `post_doi_metadata`. Setting
+/// `CROSSREF_CACHE_REFRESH_INTERVAL_SECS=0` disables the job.
+pub fn spawn_crossref_cache_refresh(pool: MySqlPool) {
+    let interval_secs = refresh_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Crossref cache refresh is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "crossref_cache_refresh",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match run_crossref_cache_refresh(&pool).await {
+                    Ok(summary)
+                        if summary.refreshed + summary.not_found + summary.failed == 0
+                            && summary.pruned == 0 => {}
+                    Ok(summary) => tracing::info!(
+                        "Crossref cache refresh finished: refreshed={}, not_found={}, failed={}, pruned={}",
+                        summary.refreshed,
+                        summary.not_found,
+                        summary.failed,
+                        summary.pruned
+                    ),
+                    Err(error) => tracing::error!("Crossref cache refresh failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+async fn run_crossref_cache_refresh(pool: &MySqlPool) -> anyhow::Result<RefreshSummary> {
+    let now = Utc::now();
+    let mut summary = RefreshSummary {
+        pruned: prune_unreferenced_entries(pool, now).await?,
+        ..RefreshSummary::default()
+    };
+
+    let dois: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT doi
+        FROM crossref_cache
+        WHERE expires_at <= ?
+        ORDER BY expires_at ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(now)
+    .bind(batch_size())
+    .fetch_all(pool)
+    .await?;
+    if dois.is_empty() {
+        return Ok(summary);
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(crossref_timeout_secs()))
+        .user_agent("ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)")
+        .build()?;
+    let ttl = chrono::Duration::seconds(cache_ttl_secs());
+
+    for doi in dois {
+        let fetched_at = Utc::now();
+        match fetch_crossref_work(&client, &doi).await {
+            Ok(Some(work)) => {
+                store_found(pool, &doi, &work, fetched_at, fetched_at + ttl).await?;
+                summary.refreshed += 1;
+            }
+            Ok(None) => {
+                store_not_found(pool, &doi, fetched_at, fetched_at + ttl).await?;
+                summary.not_found += 1;
+            }
+            Err(error) => {
+                tracing::warn!("Crossref lookup failed for DOI {}: {}", doi, error);
+                sqlx::query(
+                    "UPDATE crossref_cache SET last_error = ?, expires_at = ?, updated_at = ? WHERE doi = ?",
+                )
+                .bind(error.to_string())
+                .bind(fetched_at + chrono::Duration::seconds(CROSSREF_RETRY_AFTER_SECS))
+                .bind(fetched_at)
+                .bind(&doi)
+                .execute(pool)
+                .await?;
+                summary.failed += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+async fn store_found(
+    pool: &MySqlPool,
+    doi: &str,
+    work: &CrossrefWork,
+    fetched_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        UPDATE crossref_cache
+        SET status = ?, title = ?, journal = ?, publisher = ?, published_at = ?, source_url = ?,
+            raw_json = ?, fetched_at = ?, expires_at = ?, last_error = NULL, updated_at = ?
+        WHERE doi = ?
+        "#,
+    )
+    .bind(CACHE_STATUS_FOUND)
+    .bind(&work.title)
+    .bind(&work.journal)
+    .bind(&work.publisher)
+    .bind(&work.published_at)
+    .bind(&work.source_url)
+    .bind(&work.raw_json)
+    .bind(fetched_at)
+    .bind(expires_at)
+    .bind(fetched_at)
+    .bind(doi)
+    .execute(&mut *tx)
+    .await?;
+
+    // Rows of papers that own this DOI through a registration carry this
+    // site's metadata and are left alone.
+    sqlx::query(
+        r#"
+        UPDATE post_doi_metadata m
+        SET m.title = ?, m.journal = ?, m.publisher = ?, m.published_at = ?, m.source_url = ?,
+            m.raw_json = ?, m.updated_at = ?
+        WHERE m.doi = ?
+          AND NOT EXISTS (
+              SELECT 1 FROM post_doi_registrations r
+              WHERE r.post_id = m.post_id AND r.registered_doi = m.doi
+          )
+        "#,
+    )
+    .bind(&work.title)
+    .bind(&work.journal)
+    .bind(&work.publisher)
+    .bind(&work.published_at)
+    .bind(&work.source_url)
+    .bind(&work.raw_json)
+    .bind(fetched_at)
+    .bind(doi)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Keeps the metadata of a DOI that Crossref used to know, so a withdrawn
+/// record does not blank out posts that already cite it.
+async fn store_not_found(
+    pool: &MySqlPool,
+    doi: &str,
+    fetched_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE crossref_cache
+        SET status = IF(status = ?, status, ?), fetched_at = ?, expires_at = ?,
+            last_error = NULL, updated_at = ?
+        WHERE doi = ?
+        "#,
+    )
+    .bind(CACHE_STATUS_FOUND)
+    .bind(CACHE_STATUS_NOT_FOUND)
+    .bind(fetched_at)
+    .bind(expires_at)
+    .bind(fetched_at)
+    .bind(doi)
+    .execute(pool)
+    .await
+    .map(|_| ())
+}
+
+/// Drops expired entries no post refers to anymore instead of refreshing
+/// them forever.
+async fn prune_unreferenced_entries(
+    pool: &MySqlPool,
+    now: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM crossref_cache
+        WHERE expires_at <= ?
+          AND status <> ?
+          AND NOT EXISTS (SELECT 1 FROM post_doi_metadata m WHERE m.doi = crossref_cache.doi)
+        "#,
+    )
+    .bind(now)
+    .bind(CACHE_STATUS_PENDING)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+async fn fetch_crossref_work(client: &Client, doi: &str) -> anyhow::Result<Option<CrossrefWork>> {
+    let url = format!("{}{}", CROSSREF_API_BASE, urlencoding::encode(doi));
+    let response = telemetry::send("crossref", client.get(url)).await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        anyhow::bail!("Crossref returned {}", response.status());
+    }
+
+    let payload = response.json::<serde_json::Value>().await?;
+    let message = payload
+        .get("message")
+        .and_then(|value| value.as_object())
+        .cloned()
+        .unwrap_or_default();
+    let message_value = serde_json::Value::Object(message);
+
+    Ok(Some(CrossrefWork {
+        title: extract_crossref_title(&message_value),
+        journal: extract_crossref_first_array_text(&message_value, "container-title"),
+        publisher: extract_crossref_text(&message_value, "publisher"),
+        published_at: extract_crossref_published_at(&message_value),
+        source_url: extract_crossref_text(&message_value, "URL")
+            .or_else(|| Some(format!("https://doi.org/{}", doi))),
+        raw_json: payload.to_string(),
+    }))
+}
+
+fn extract_crossref_text(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|item| item.as_str())
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+fn extract_crossref_first_array_text(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|item| item.as_array())
+        .and_then(|items| items.iter().find_map(|entry| entry.as_str()))
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+fn extract_crossref_title(value: &serde_json::Value) -> Option<String> {
+    extract_crossref_first_array_text(value, "title")
+        .or_else(|| extract_crossref_text(value, "title"))
+}
+
+fn extract_crossref_published_at(value: &serde_json::Value) -> Option<String> {
+    for key in ["published-print", "published-online", "issued"] {
+        let Some(date_parts) = value
+            .get(key)
+            .and_then(|entry| entry.get("date-parts"))
+            .and_then(|entry| entry.as_array())
+            .and_then(|outer| outer.first())
+            .and_then(|entry| entry.as_array())
+        else {
+            continue;
+        };
+
+        let year = date_parts.first().and_then(|value| value.as_i64());
+        let month = date_parts.get(1).and_then(|value| value.as_i64());
+        let day = date_parts.get(2).and_then(|value| value.as_i64());
+
+        if let Some(year_value) = year {
+            if let (Some(month_value), Some(day_value)) = (month, day) {
+                return Some(format!(
+                    "{:04}-{:02}-{:02}",
+                    year_value, month_value, day_value
+                ));
+            }
+            if let Some(month_value) = month {
+                return Some(format!("{:04}-{:02}", year_value, month_value));
+            }
+            return Some(format!("{:04}", year_value));
+        }
+    }
+
+    None
+}
+
+fn refresh_interval_secs() -> u64 {
+    std::env::var("CROSSREF_CACHE_REFRESH_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CROSSREF_CACHE_REFRESH_INTERVAL_SECS)
+}
+
+fn cache_ttl_secs() -> i64 {
+    std::env::var("CROSSREF_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_CROSSREF_CACHE_TTL_SECS)
+}
+
+fn crossref_timeout_secs() -> u64 {
+    std::env::var("CROSSREF_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_CROSSREF_TIMEOUT_SECS)
+}
+
+fn batch_size() -> i64 {
+    std::env::var("CROSSREF_CACHE_BATCH_SIZE")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_CROSSREF_CACHE_BATCH_SIZE)
+}
@@ -0,0 +1,120 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, bail};
+use log::LevelFilter;
+use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions};
+use sqlx::{ConnectOptions, Executor, MySqlPool};
+
+use super::database_url;
+
+const DEFAULT_MAX_CONNECTIONS: u32 = 10;
+const DEFAULT_MIN_CONNECTIONS: u32 = 0;
+const DEFAULT_ACQUIRE_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_STATEMENT_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_SLOW_QUERY_MS: u64 = 1_000;
+
+/// Connection pool settings, read from `DB_*` variables at startup. A value
+/// that does not parse stops the server rather than falling back silently.
+#[derive(Clone)]
+pub struct DbConfig {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// How long a request waits for a free connection before failing with
+    /// 503; also bounds the initial connection at startup.
+    pub acquire_timeout: Duration,
+    /// `max_execution_time` for every session, which MySQL applies to
+    /// read-only `SELECT`s. `None` leaves them unbounded.
+    pub statement_timeout: Option<Duration>,
+    /// Statements slower than this are logged as warnings.
+    pub slow_query_threshold: Option<Duration>,
+}
+
+impl DbConfig {
+    pub fn from_env() -> anyhow::Result<Self> {
+        let config = Self {
+            url: database_url(),
+            max_connections: env_number("DB_MAX_CONNECTIONS", DEFAULT_MAX_CONNECTIONS)?,
+            min_connections: env_number("DB_MIN_CONNECTIONS", DEFAULT_MIN_CONNECTIONS)?,
+            acquire_timeout: Duration::from_secs(env_number(
+                "DB_ACQUIRE_TIMEOUT_SECS",
+                DEFAULT_ACQUIRE_TIMEOUT_SECS,
+            )?),
+            statement_timeout: optional_millis(env_number(
+                "DB_STATEMENT_TIMEOUT_MS",
+                DEFAULT_STATEMENT_TIMEOUT_MS,
+            )?),
+            slow_query_threshold: optional_millis(env_number(
+                "DB_SLOW_QUERY_MS",
+                DEFAULT_SLOW_QUERY_MS,
+            )?),
+        };
+        if config.max_connections == 0 {
+            bail!("DB_MAX_CONNECTIONS must be at least 1");
+        }
+        if config.min_connections > config.max_connections {
+            bail!(
+                "DB_MIN_CONNECTIONS ({}) must not exceed DB_MAX_CONNECTIONS ({})",
+                config.min_connections,
+                config.max_connections
+            );
+        }
+        if config.acquire_timeout.is_zero() {
+            bail!("DB_ACQUIRE_TIMEOUT_SECS must be at least 1");
+        }
+        Ok(config)
+    }
+
+    /// Opens the pool, failing once `acquire_timeout` passes instead of
+    /// waiting for an unreachable server.
+    pub async fn connect(&self) -> anyhow::Result<MySqlPool> {
+        let mut options = MySqlConnectOptions::from_str(&self.url)
+            .context("DATABASE_URL is not a valid MySQL connection URL")?;
+        options = match self.slow_query_threshold {
+            Some(threshold) => options.log_slow_statements(LevelFilter::Warn, threshold),
+            None => options.log_slow_statements(LevelFilter::Off, Duration::default()),
+        };
+
+        let statement_timeout_ms = self.statement_timeout.map(|timeout| timeout.as_millis());
+        MySqlPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .after_connect(move |connection, _| {
+                Box::pin(async move {
+                    if let Some(timeout_ms) = statement_timeout_ms {
+                        connection
+                            .execute(
+                                format!("SET SESSION max_execution_time = {}", timeout_ms).as_str(),
+                            )
+                            .await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect_with(options)
+            .await
+            .with_context(|| {
+                format!(
+                    "Could not connect to the database within {}s; check DATABASE_URL and that MySQL is running",
+                    self.acquire_timeout.as_secs()
+                )
+            })
+    }
+}
+
+fn env_number<T: FromStr>(name: &str, default: T) -> anyhow::Result<T> {
+    match std::env::var(name) {
+        Ok(raw) if !raw.trim().is_empty() => raw
+            .trim()
+            .parse()
+            .map_err(|_| anyhow::anyhow!("{} must be a non-negative integer, got {:?}", name, raw)),
+        _ => Ok(default),
+    }
+}
+
+/// Zero turns a millisecond limit off.
+fn optional_millis(millis: u64) -> Option<Duration> {
+    (millis > 0).then(|| Duration::from_millis(millis))
+}
@@ -0,0 +1,520 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, Datelike, Utc};
+use reqwest::{Client, header};
+use sqlx::{FromRow, MySqlConnection, MySqlPool};
+
+use crate::feature_flags::{FEATURE_DOI_REGISTRATION, is_feature_enabled};
+use crate::tasks;
+
+pub const DEFAULT_DOI_REGISTRATION_INTERVAL_SECS: u64 = 600;
+pub const DEFAULT_DOI_REGISTRATION_MAX_ATTEMPTS: i32 = 8;
+pub const DEFAULT_DATACITE_TIMEOUT_SECS: u64 = 15;
+
+pub const DEPOSIT_STATUS_PENDING: &str = "pending";
+pub const DEPOSIT_STATUS_REGISTERED: &str = "registered";
+pub const DEPOSIT_STATUS_FAILED: &str = "failed";
+
+const DEFAULT_DATACITE_API_URL: &str = "https://api.datacite.org";
+const REGISTRATION_AGENCY_DATACITE: &str = "datacite";
+const JOURNAL_NAME: &str = "Thought Manifold";
+const DEPOSIT_BATCH_SIZE: i64 = 50;
+const RETRY_BASE_SECS: i64 = 300;
+const RETRY_MAX_SECS: i64 = 86_400;
+const ERROR_BODY_MAX_CHARS: usize = 500;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DepositSummary {
+    pub queued: usize,
+    pub registered: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone)]
+struct DataCiteConfig {
+    api_url: String,
+    repository_id: String,
+    password: String,
+    doi_prefix: String,
+}
+
+impl DataCiteConfig {
+    /// `None` unless the repository credentials and DOI prefix are all set.
+    fn from_env() -> Option<Self> {
+        let doi_prefix = env_value("DATACITE_DOI_PREFIX")?
+            .trim_end_matches('/')
+            .to_ascii_lowercase();
+        if !doi_prefix.starts_with("10.") {
+            tracing::warn!(
+                "DATACITE_DOI_PREFIX must look like 10.1234, ignoring DataCite settings"
+            );
+            return None;
+        }
+
+        Some(Self {
+            api_url: env_value("DATACITE_API_URL")
+                .map(|value| value.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| DEFAULT_DATACITE_API_URL.to_string()),
+            repository_id: env_value("DATACITE_REPOSITORY_ID")?,
+            password: env_value("DATACITE_PASSWORD")?,
+            doi_prefix,
+        })
+    }
+
+    /// Registered DOIs reuse the internal DOI as suffix, so
+    /// `TM.2025.paper/1A2B3C` becomes `10.1234/tm.2025.paper.1a2b3c`.
+    fn registered_doi_for(&self, internal_doi: &str) -> String {
+        format!(
+            "{}/{}",
+            self.doi_prefix,
+            internal_doi.to_ascii_lowercase().replace('/', ".")
+        )
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DueDeposit {
+    post_id: i64,
+    doi: String,
+    attempts: i32,
+    internal_doi: Option<String>,
+    title: String,
+    author_name: String,
+    published_on: DateTime<Utc>,
+}
+
+/// Starts the periodic job that queues newly published papers for DataCite
+/// registration and retries failed deposits. Setting
+/// `DOI_REGISTRATION_INTERVAL_SECS=0`, or leaving the DataCite credentials
+/// unset, disables the job.
+pub fn spawn_doi_registration(pool: MySqlPool) {
+    let interval_secs = registration_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("DataCite DOI registration is disabled");
+        return;
+    }
+    let Some(config) = DataCiteConfig::from_env() else {
+        tracing::info!("DataCite DOI registration is not configured");
+        return;
+    };
+
+    tasks::spawn_periodic(
+        "doi_registration",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            let config = config.clone();
+            async move {
+                if !is_feature_enabled(FEATURE_DOI_REGISTRATION, None) {
+                    return;
+                }
+                match run_doi_registration(&pool, &config).await {
+                    Ok(summary) if summary.queued + summary.registered + summary.failed == 0 => {}
+                    Ok(summary) => tracing::info!(
+                        "DataCite DOI registration finished: queued={}, registered={}, failed={}",
+                        summary.queued,
+                        summary.registered,
+                        summary.failed
+                    ),
+                    Err(error) => tracing::error!("DataCite DOI registration failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+async fn run_doi_registration(
+    pool: &MySqlPool,
+    config: &DataCiteConfig,
+) -> anyhow::Result<DepositSummary> {
+    let mut summary = DepositSummary {
+        queued: queue_published_papers(pool, config).await?,
+        ..DepositSummary::default()
+    };
+
+    let now = Utc::now();
+    let deposits = sqlx::query_as::<_, DueDeposit>(
+        r#"
+        SELECT
+            d.post_id,
+            d.doi,
+            d.attempts,
+            (
+                SELECT m.doi
+                FROM post_doi_metadata m
+                WHERE m.post_id = d.post_id AND m.doi LIKE 'TM.%'
+                ORDER BY m.id ASC
+                LIMIT 1
+            ) AS internal_doi,
+            p.title,
+            COALESCE(NULLIF(TRIM(u.display_name), ''), u.username) AS author_name,
+            COALESCE(p.published_at, p.created_at) AS published_on
+        FROM post_doi_deposits d
+        JOIN posts p ON p.id = d.post_id
+        JOIN users u ON u.id = p.author_id
+        WHERE d.status = ?
+          AND (d.next_attempt_at IS NULL OR d.next_attempt_at <= ?)
+          AND p.paper_status = 'published'
+        ORDER BY d.next_attempt_at IS NOT NULL, d.next_attempt_at ASC, d.post_id ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(DEPOSIT_STATUS_PENDING)
+    .bind(now)
+    .bind(DEPOSIT_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+    if deposits.is_empty() {
+        return Ok(summary);
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(datacite_timeout_secs()))
+        .user_agent("ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)")
+        .build()?;
+    let max_attempts = max_attempts();
+
+    for deposit in deposits {
+        let result = match deposit_doi(&client, config, &deposit).await {
+            Ok(()) => record_registration(pool, &deposit).await,
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(()) => summary.registered += 1,
+            Err(error) => {
+                summary.failed += 1;
+                tracing::warn!(
+                    "DataCite deposit failed for post {} (doi={}): {}",
+                    deposit.post_id,
+                    deposit.doi,
+                    error
+                );
+                record_deposit_failure(pool, &deposit, &error.to_string(), max_attempts).await?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Adds a pending deposit for every published paper that has neither a
+/// deposit nor a DOI registered by hand. Returns the number queued.
+async fn queue_published_papers(
+    pool: &MySqlPool,
+    config: &DataCiteConfig,
+) -> Result<usize, sqlx::Error> {
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT p.id, MIN(m.doi)
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        JOIN post_doi_metadata m ON m.post_id = p.id AND m.doi LIKE 'TM.%'
+        WHERE c.code = 'paper'
+          AND p.paper_status = 'published'
+          AND NOT EXISTS (SELECT 1 FROM post_doi_deposits d WHERE d.post_id = p.id)
+          AND NOT EXISTS (SELECT 1 FROM post_doi_registrations r WHERE r.post_id = p.id)
+        GROUP BY p.id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let now = Utc::now();
+    let mut queued = 0;
+    for (post_id, internal_doi) in rows {
+        let inserted = sqlx::query(
+            r#"
+            INSERT IGNORE INTO post_doi_deposits (
+                post_id,
+                doi,
+                registration_agency,
+                status,
+                next_attempt_at,
+                created_at,
+                updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(post_id)
+        .bind(config.registered_doi_for(&internal_doi))
+        .bind(REGISTRATION_AGENCY_DATACITE)
+        .bind(DEPOSIT_STATUS_PENDING)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        queued += inserted.rows_affected() as usize;
+    }
+
+    Ok(queued)
+}
+
+/// Creates or updates the DOI with `PUT /dois/{doi}`, which makes a retry
+/// after a lost response harmless.
+async fn deposit_doi(
+    client: &Client,
+    config: &DataCiteConfig,
+    deposit: &DueDeposit,
+) -> anyhow::Result<()> {
+    let payload = serde_json::json!({
+        "data": {
+            "id": deposit.doi,
+            "type": "dois",
+            "attributes": {
+                "doi": deposit.doi,
+                "event": "publish",
+                "url": landing_page_url(deposit.post_id),
+                "titles": [{ "title": deposit.title }],
+                "creators": [{ "name": deposit.author_name }],
+                "publisher": JOURNAL_NAME,
+                "publicationYear": deposit.published_on.year(),
+                "types": {
+                    "resourceTypeGeneral": "JournalArticle",
+                    "resourceType": "Paper",
+                },
+            },
+        },
+    });
+
+    let response = client
+        .put(format!("{}/dois/{}", config.api_url, deposit.doi))
+        .basic_auth(&config.repository_id, Some(&config.password))
+        .header(header::CONTENT_TYPE, "application/vnd.api+json")
+        .body(payload.to_string())
+        .send()
+        .await
+        .context("DataCite request failed")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let body: String = body.chars().take(ERROR_BODY_MAX_CHARS).collect();
+        anyhow::bail!("DataCite returned {}: {}", status, body.trim());
+    }
+
+    Ok(())
+}
+
+async fn record_registration(pool: &MySqlPool, deposit: &DueDeposit) -> anyhow::Result<()> {
+    let Some(internal_doi) = deposit.internal_doi.as_deref() else {
+        anyhow::bail!("post {} has no internal DOI", deposit.post_id);
+    };
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        UPDATE post_doi_deposits
+        SET
+            status = ?,
+            attempts = attempts + 1,
+            last_error = NULL,
+            next_attempt_at = NULL,
+            registered_at = ?,
+            updated_at = ?
+        WHERE post_id = ?
+        "#,
+    )
+    .bind(DEPOSIT_STATUS_REGISTERED)
+    .bind(now)
+    .bind(now)
+    .bind(deposit.post_id)
+    .execute(&mut *tx)
+    .await?;
+
+    upsert_doi_registration(
+        &mut tx,
+        deposit.post_id,
+        internal_doi,
+        &deposit.doi,
+        REGISTRATION_AGENCY_DATACITE,
+    )
+    .await?;
+
+    upsert_registered_doi_metadata(&mut tx, deposit.post_id).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Schedules the next attempt with exponential backoff, or gives up once
+/// `max_attempts` deposits have failed.
+async fn record_deposit_failure(
+    pool: &MySqlPool,
+    deposit: &DueDeposit,
+    message: &str,
+    max_attempts: i32,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let attempts = deposit.attempts + 1;
+    let (status, next_attempt_at) = if attempts >= max_attempts {
+        (DEPOSIT_STATUS_FAILED, None)
+    } else {
+        (DEPOSIT_STATUS_PENDING, Some(now + retry_delay(attempts)))
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE post_doi_deposits
+        SET status = ?, attempts = ?, last_error = ?, next_attempt_at = ?, updated_at = ?
+        WHERE post_id = ? AND status = ?
+        "#,
+    )
+    .bind(status)
+    .bind(attempts)
+    .bind(message)
+    .bind(next_attempt_at)
+    .bind(now)
+    .bind(deposit.post_id)
+    .bind(DEPOSIT_STATUS_PENDING)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Records `registered_doi` as the post's registered DOI. A changed DOI
+/// also clears the OpenAlex work id matched to the old one, so inbound
+/// citations are looked up again.
+pub async fn upsert_doi_registration(
+    conn: &mut MySqlConnection,
+    post_id: i64,
+    internal_doi: &str,
+    registered_doi: &str,
+    registration_agency: &str,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        INSERT INTO post_doi_registrations (
+            post_id,
+            internal_doi,
+            registered_doi,
+            registration_agency,
+            registered_at,
+            updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            -- Assignments apply left to right, so this has to compare
+            -- against the old DOI before it is overwritten.
+            openalex_work_id = IF(registered_doi = VALUES(registered_doi), openalex_work_id, NULL),
+            internal_doi = VALUES(internal_doi),
+            registered_doi = VALUES(registered_doi),
+            registration_agency = VALUES(registration_agency),
+            updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(post_id)
+    .bind(internal_doi)
+    .bind(registered_doi)
+    .bind(registration_agency)
+    .bind(now)
+    .bind(now)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Mirrors the post's registered DOI, if any, into `post_doi_metadata` so the
+/// DOI list and BibTeX of the post cite the resolvable DOI.
+pub async fn upsert_registered_doi_metadata(
+    conn: &mut MySqlConnection,
+    post_id: i64,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        INSERT INTO post_doi_metadata (
+            post_id,
+            doi,
+            title,
+            journal,
+            publisher,
+            published_at,
+            source_url,
+            raw_json,
+            created_at,
+            updated_at
+        )
+        SELECT
+            p.id,
+            r.registered_doi,
+            p.title,
+            ?,
+            ?,
+            DATE_FORMAT(COALESCE(p.published_at, p.created_at), '%Y-%m-%d'),
+            CONCAT('https://doi.org/', r.registered_doi),
+            JSON_OBJECT('source', 'post_doi_registrations', 'registration_agency', r.registration_agency),
+            ?,
+            ?
+        FROM posts p
+        JOIN post_doi_registrations r ON r.post_id = p.id
+        WHERE p.id = ?
+        ON DUPLICATE KEY UPDATE
+            title = VALUES(title),
+            journal = VALUES(journal),
+            publisher = VALUES(publisher),
+            published_at = VALUES(published_at),
+            source_url = VALUES(source_url),
+            raw_json = VALUES(raw_json),
+            updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(JOURNAL_NAME)
+    .bind(JOURNAL_NAME)
+    .bind(now)
+    .bind(now)
+    .bind(post_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+fn retry_delay(attempts: i32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let secs = RETRY_BASE_SECS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(RETRY_MAX_SECS);
+    chrono::Duration::seconds(secs)
+}
+
+fn landing_page_url(post_id: i64) -> String {
+    let base = env_value("FRONTEND_URL")
+        .map(|value| value.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| "http://localhost:5173".to_string());
+    format!("{}/posts/{}", base, post_id)
+}
+
+fn registration_interval_secs() -> u64 {
+    std::env::var("DOI_REGISTRATION_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_DOI_REGISTRATION_INTERVAL_SECS)
+}
+
+fn max_attempts() -> i32 {
+    std::env::var("DOI_REGISTRATION_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|raw| raw.parse::<i32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_DOI_REGISTRATION_MAX_ATTEMPTS)
+}
+
+fn datacite_timeout_secs() -> u64 {
+    std::env::var("DATACITE_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_DATACITE_TIMEOUT_SECS)
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
@@ -0,0 +1,226 @@
+use std::any::Any;
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::mysql::MySqlDatabaseError;
+use utoipa::ToSchema;
+
+/// The error every handler returns. Responses always have the shape
+/// `{"detail": "...", "code": "..."}`: `detail` is meant for people and
+/// `code` for clients to branch on. Internal errors are logged and reported
+/// without their message, so SQL and upstream errors never reach clients.
+#[derive(Debug)]
+pub enum AppError {
+    BadRequest(String),
+    /// Input that failed per-field checks; listed under `errors`.
+    Validation(Vec<FieldError>),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    PayloadTooLarge(String),
+    TooManyRequests(String),
+    BadGateway(String),
+    ServiceUnavailable(String),
+    Internal(String),
+    /// Another error with extra fields next to `detail`, such as the current
+    /// `paper_status` on a conflict.
+    WithFields(Box<AppError>, Map<String, Value>),
+}
+
+/// The JSON body of every error response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub detail: String,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+    /// Extra context added with `AppError::with_field`.
+    #[serde(flatten)]
+    #[schema(ignore)]
+    pub fields: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl AppError {
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::BadRequest(detail.into())
+    }
+
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation(vec![FieldError::new(field, message)])
+    }
+
+    pub fn unauthorized(detail: impl Into<String>) -> Self {
+        Self::Unauthorized(detail.into())
+    }
+
+    pub fn forbidden(detail: impl Into<String>) -> Self {
+        Self::Forbidden(detail.into())
+    }
+
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::NotFound(detail.into())
+    }
+
+    pub fn conflict(detail: impl Into<String>) -> Self {
+        Self::Conflict(detail.into())
+    }
+
+    pub fn payload_too_large(detail: impl Into<String>) -> Self {
+        Self::PayloadTooLarge(detail.into())
+    }
+
+    pub fn too_many_requests(detail: impl Into<String>) -> Self {
+        Self::TooManyRequests(detail.into())
+    }
+
+    pub fn bad_gateway(detail: impl Into<String>) -> Self {
+        Self::BadGateway(detail.into())
+    }
+
+    pub fn service_unavailable(detail: impl Into<String>) -> Self {
+        Self::ServiceUnavailable(detail.into())
+    }
+
+    /// Takes any error so it can be passed straight to `map_err`. Running out
+    /// of database connections or time is reported as 503 rather than 500,
+    /// since the same request can succeed later.
+    pub fn internal<E: ToString + 'static>(error: E) -> Self {
+        if let Some(error) = (&error as &dyn Any).downcast_ref::<sqlx::Error>()
+            && let Some(detail) = database_timeout(error)
+        {
+            tracing::warn!("{}: {}", detail, error);
+            return Self::ServiceUnavailable(detail.to_string());
+        }
+        Self::Internal(error.to_string())
+    }
+
+    /// Adds a field to the response body next to `detail`.
+    pub fn with_field(self, key: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value).unwrap_or(Value::Null);
+        match self {
+            Self::WithFields(error, mut fields) => {
+                fields.insert(key.to_string(), value);
+                Self::WithFields(error, fields)
+            }
+            error => {
+                let mut fields = Map::new();
+                fields.insert(key.to_string(), value);
+                Self::WithFields(Box::new(error), fields)
+            }
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::BadGateway(_) => StatusCode::BAD_GATEWAY,
+            Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::WithFields(error, _) => error.status(),
+        }
+    }
+
+    /// The machine-readable `code` in the response body.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest(_) => "bad_request",
+            Self::Validation(_) => "validation_failed",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::Forbidden(_) => "forbidden",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::PayloadTooLarge(_) => "payload_too_large",
+            Self::TooManyRequests(_) => "rate_limited",
+            Self::BadGateway(_) => "upstream_error",
+            Self::ServiceUnavailable(_) => "service_unavailable",
+            Self::Internal(_) => "internal_error",
+            Self::WithFields(error, _) => error.code(),
+        }
+    }
+
+    fn into_body(self) -> ErrorBody {
+        let code = self.code().to_string();
+        let (detail, errors) = match self {
+            Self::BadRequest(detail)
+            | Self::Unauthorized(detail)
+            | Self::Forbidden(detail)
+            | Self::NotFound(detail)
+            | Self::Conflict(detail)
+            | Self::PayloadTooLarge(detail)
+            | Self::TooManyRequests(detail)
+            | Self::BadGateway(detail)
+            | Self::ServiceUnavailable(detail) => (detail, None),
+            Self::Validation(errors) => {
+                let messages: Vec<&str> =
+                    errors.iter().map(|error| error.message.as_str()).collect();
+                (messages.join("; "), Some(errors))
+            }
+            Self::Internal(message) => {
+                tracing::error!("Internal error: {}", message);
+                ("Internal server error".to_string(), None)
+            }
+            Self::WithFields(error, fields) => {
+                let mut body = error.into_body();
+                body.fields.extend(fields);
+                return body;
+            }
+        };
+        ErrorBody {
+            detail,
+            code,
+            errors,
+            fields: Map::new(),
+        }
+    }
+}
+
+/// MySQL's error for a `SELECT` stopped by `max_execution_time`.
+const MYSQL_STATEMENT_TIMEOUT: u16 = 3024;
+
+fn database_timeout(error: &sqlx::Error) -> Option<&'static str> {
+    match error {
+        sqlx::Error::PoolTimedOut => Some("Database is busy; try again shortly"),
+        sqlx::Error::Database(error)
+            if error
+                .try_downcast_ref::<MySqlDatabaseError>()
+                .is_some_and(|error| error.number() == MYSQL_STATEMENT_TIMEOUT) =>
+        {
+            Some("Database query timed out; try again shortly")
+        }
+        _ => None,
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.into_body())).into_response()
+    }
+}
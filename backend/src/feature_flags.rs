@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::models::{FeatureFlag, User};
+
+pub const FEATURE_AI_REVIEW_AUTO_SCHEDULE: &str = "ai_review_auto_schedule";
+pub const FEATURE_DOI_REGISTRATION: &str = "doi_registration";
+pub const FEATURE_OAI_PMH: &str = "oai_pmh";
+
+pub const FEATURE_ROLE_USER: &str = "user";
+pub const FEATURE_ROLE_ADMIN: &str = "admin";
+pub const FEATURE_ROLES: &[&str] = &[FEATURE_ROLE_USER, FEATURE_ROLE_ADMIN];
+
+/// A flag the code consults. Flags without a `feature_flags` row use
+/// `default_enabled` for everyone.
+#[derive(Debug)]
+pub struct FeatureFlagDefinition {
+    pub key: &'static str,
+    pub description: &'static str,
+    pub default_enabled: bool,
+}
+
+pub const FEATURE_FLAG_DEFINITIONS: &[FeatureFlagDefinition] = &[
+    FeatureFlagDefinition {
+        key: FEATURE_AI_REVIEW_AUTO_SCHEDULE,
+        description: "Schedule an AI review when a paper is submitted or resubmitted",
+        default_enabled: true,
+    },
+    FeatureFlagDefinition {
+        key: FEATURE_DOI_REGISTRATION,
+        description: "Register DOIs of published papers with DataCite",
+        default_enabled: true,
+    },
+    FeatureFlagDefinition {
+        key: FEATURE_OAI_PMH,
+        description: "Serve the OAI-PMH metadata feed",
+        default_enabled: true,
+    },
+];
+
+pub fn find_feature_flag(key: &str) -> Option<&'static FeatureFlagDefinition> {
+    FEATURE_FLAG_DEFINITIONS
+        .iter()
+        .find(|definition| definition.key == key)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FlagState {
+    enabled: bool,
+    rollout_percent: u8,
+    roles: Vec<String>,
+}
+
+fn flag_states() -> &'static RwLock<HashMap<String, FlagState>> {
+    static STATES: OnceLock<RwLock<HashMap<String, FlagState>>> = OnceLock::new();
+    STATES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Whether `key` is on for `user`. Without a user (background jobs,
+/// anonymous requests) only the `enabled` switch counts, since there is no
+/// role to match or one to place in a rollout bucket.
+pub fn is_feature_enabled(key: &str, user: Option<&User>) -> bool {
+    let state = flag_states()
+        .read()
+        .ok()
+        .and_then(|states| states.get(key).cloned());
+    let Some(state) = state else {
+        return find_feature_flag(key).is_some_and(|definition| definition.default_enabled);
+    };
+    if !state.enabled {
+        return false;
+    }
+    let Some(user) = user else {
+        return true;
+    };
+
+    let role = if user.is_admin {
+        FEATURE_ROLE_ADMIN
+    } else {
+        FEATURE_ROLE_USER
+    };
+    if !state.roles.is_empty() && !state.roles.iter().any(|allowed| allowed == role) {
+        return false;
+    }
+    state.rollout_percent >= 100 || rollout_bucket(key, user.id) < state.rollout_percent
+}
+
+/// Stable bucket in `0..100` for a user, salted with the flag key so each
+/// flag rolls out to a different slice of users.
+fn rollout_bucket(key: &str, user_id: i64) -> u8 {
+    let digest = Sha256::digest(format!("{}:{}", key, user_id).as_bytes());
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    (value % 100) as u8
+}
+
+#[derive(Debug, FromRow)]
+struct FeatureFlagRow {
+    id: i64,
+    flag_key: String,
+    enabled: bool,
+    rollout_percent: i32,
+    updated_by: Option<i64>,
+    updated_at: DateTime<Utc>,
+}
+
+async fn fetch_flag_rows(
+    pool: &MySqlPool,
+) -> Result<(Vec<FeatureFlagRow>, HashMap<i64, Vec<String>>), sqlx::Error> {
+    let rows = sqlx::query_as::<_, FeatureFlagRow>(
+        "SELECT id, flag_key, enabled, CAST(rollout_percent AS SIGNED) AS rollout_percent, updated_by, updated_at FROM feature_flags",
+    )
+    .fetch_all(pool)
+    .await?;
+    let role_rows: Vec<(i64, String)> =
+        sqlx::query_as("SELECT flag_id, role FROM feature_flag_roles ORDER BY flag_id, role")
+            .fetch_all(pool)
+            .await?;
+
+    let mut roles: HashMap<i64, Vec<String>> = HashMap::new();
+    for (flag_id, role) in role_rows {
+        roles.entry(flag_id).or_default().push(role);
+    }
+    Ok((rows, roles))
+}
+
+/// Replaces the in-memory flag overrides with the contents of
+/// `feature_flags`. Returns whether anything changed.
+pub async fn reload_feature_flags(pool: &MySqlPool) -> Result<bool, sqlx::Error> {
+    let (rows, mut roles) = fetch_flag_rows(pool).await?;
+    let loaded: HashMap<String, FlagState> = rows
+        .into_iter()
+        .map(|row| {
+            let state = FlagState {
+                enabled: row.enabled,
+                rollout_percent: row.rollout_percent.clamp(0, 100) as u8,
+                roles: roles.remove(&row.id).unwrap_or_default(),
+            };
+            (row.flag_key, state)
+        })
+        .collect();
+
+    let Ok(mut states) = flag_states().write() else {
+        return Ok(false);
+    };
+    if *states == loaded {
+        return Ok(false);
+    }
+    *states = loaded;
+    Ok(true)
+}
+
+/// Every registered flag with its stored override, in registration order.
+pub async fn list_feature_flags(pool: &MySqlPool) -> Result<Vec<FeatureFlag>, sqlx::Error> {
+    let (rows, mut roles) = fetch_flag_rows(pool).await?;
+    let mut rows: HashMap<String, FeatureFlagRow> = rows
+        .into_iter()
+        .map(|row| (row.flag_key.clone(), row))
+        .collect();
+
+    Ok(FEATURE_FLAG_DEFINITIONS
+        .iter()
+        .map(|definition| match rows.remove(definition.key) {
+            Some(row) => FeatureFlag {
+                id: Some(row.id),
+                key: definition.key.to_string(),
+                description: definition.description.to_string(),
+                enabled: row.enabled,
+                rollout_percent: row.rollout_percent,
+                roles: roles.remove(&row.id).unwrap_or_default(),
+                default_enabled: definition.default_enabled,
+                updated_by: row.updated_by,
+                updated_at: Some(row.updated_at),
+            },
+            None => FeatureFlag {
+                id: None,
+                key: definition.key.to_string(),
+                description: definition.description.to_string(),
+                enabled: definition.default_enabled,
+                rollout_percent: 100,
+                roles: Vec::new(),
+                default_enabled: definition.default_enabled,
+                updated_by: None,
+                updated_at: None,
+            },
+        })
+        .collect())
+}
@@ -0,0 +1,159 @@
+//! Readiness checks behind `GET /health/ready`, for Kubernetes-style
+//! probes. `/health` only says the process is up; readiness also checks
+//! that the database answers and that the storage directories accept
+//! writes, and answers 503 when either fails. Setting
+//! `READINESS_CHECK_EXTERNAL=true` adds reachability checks for Gemini and
+//! Crossref; those are reported but only mark the instance degraded, since
+//! an outage upstream is no reason to stop routing traffic to it.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use reqwest::Client;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::models::{DependencyCheck, ReadinessReport};
+use crate::telemetry;
+use crate::uploads::UPLOADS_DIR;
+use crate::version_files::VERSION_FILES_DIR;
+
+const DEFAULT_CHECK_TIMEOUT_MS: u64 = 2_000;
+const GEMINI_PROBE_URL: &str = "https://generativelanguage.googleapis.com/";
+const CROSSREF_PROBE_URL: &str = "https://api.crossref.org/";
+
+// GET /health/ready
+pub async fn readiness_check(State(pool): State<MySqlPool>) -> impl IntoResponse {
+    let report = check_readiness(&pool).await;
+    let status = if report.status == "not_ready" {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(report))
+}
+
+/// Runs every check concurrently, each bounded by the check timeout.
+async fn check_readiness(pool: &MySqlPool) -> ReadinessReport {
+    let (database, uploads, version_files) = tokio::join!(
+        run_check("database", true, check_database(pool)),
+        run_check("storage:uploads", true, check_writable(UPLOADS_DIR)),
+        run_check(
+            "storage:version_files",
+            true,
+            check_writable(VERSION_FILES_DIR)
+        ),
+    );
+    let mut checks = vec![database, uploads, version_files];
+
+    if external_checks_enabled() {
+        let client = Client::builder().timeout(check_timeout()).build();
+        match client {
+            Ok(client) => {
+                let (gemini, crossref) = tokio::join!(
+                    run_check(
+                        "gemini",
+                        false,
+                        check_reachable(&client, "gemini", GEMINI_PROBE_URL)
+                    ),
+                    run_check(
+                        "crossref",
+                        false,
+                        check_reachable(&client, "crossref", CROSSREF_PROBE_URL)
+                    ),
+                );
+                checks.extend([gemini, crossref]);
+            }
+            Err(error) => tracing::warn!("Failed to build readiness HTTP client: {}", error),
+        }
+    }
+
+    let failed = |required: bool| {
+        checks
+            .iter()
+            .any(|check| check.required == required && check.status != "ok")
+    };
+    let status = if failed(true) {
+        "not_ready"
+    } else if failed(false) {
+        "degraded"
+    } else {
+        "ready"
+    };
+    if status != "ready" {
+        let failures: Vec<&str> = checks
+            .iter()
+            .filter(|check| check.status != "ok")
+            .map(|check| check.name.as_str())
+            .collect();
+        tracing::warn!("Readiness check {}: {}", status, failures.join(", "));
+    }
+
+    ReadinessReport {
+        status: status.to_string(),
+        checked_at: Utc::now(),
+        checks,
+    }
+}
+
+async fn run_check<F>(name: &str, required: bool, check: F) -> DependencyCheck
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    let timeout = check_timeout();
+    let started = Instant::now();
+    let result = match tokio::time::timeout(timeout, check).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!("timed out after {}ms", timeout.as_millis())),
+    };
+    DependencyCheck {
+        name: name.to_string(),
+        status: if result.is_ok() { "ok" } else { "error" }.to_string(),
+        required,
+        latency_ms: started.elapsed().as_millis() as u64,
+        error: result.err().map(|error| error.to_string()),
+    }
+}
+
+async fn check_database(pool: &MySqlPool) -> anyhow::Result<()> {
+    sqlx::query_scalar::<_, i32>("SELECT 1")
+        .fetch_one(pool)
+        .await?;
+    Ok(())
+}
+
+/// Writes and removes a small probe file, which fails on a full, read-only
+/// or missing volume.
+async fn check_writable(dir: &str) -> anyhow::Result<()> {
+    let path = PathBuf::from(dir).join(format!(".ready-{}.probe", Uuid::new_v4()));
+    tokio::fs::write(&path, b"ok").await?;
+    tokio::fs::remove_file(&path).await?;
+    Ok(())
+}
+
+/// Any HTTP response counts; only connection and TLS failures mean the
+/// service cannot be reached.
+async fn check_reachable(client: &Client, service: &'static str, url: &str) -> anyhow::Result<()> {
+    telemetry::send(service, client.head(url)).await?;
+    Ok(())
+}
+
+fn external_checks_enabled() -> bool {
+    std::env::var("READINESS_CHECK_EXTERNAL").is_ok_and(|raw| {
+        matches!(
+            raw.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes"
+        )
+    })
+}
+
+fn check_timeout() -> Duration {
+    let millis = std::env::var("READINESS_CHECK_TIMEOUT_MS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|millis| *millis > 0)
+        .unwrap_or(DEFAULT_CHECK_TIMEOUT_MS);
+    Duration::from_millis(millis)
+}
@@ -0,0 +1,279 @@
+//! Log line formatting. `LOG_FORMAT=json` switches stdout to one JSON object
+//! per line for log collectors, with the request id and signed-in user id
+//! of the request being served as top-level fields. In either format,
+//! credentials are masked before a line is written: bearer tokens, OAuth
+//! codes and tokens and API keys in query strings or JSON bodies, and the
+//! values of secret environment variables wherever they appear.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::LazyLock;
+
+use chrono::{SecondsFormat, Utc};
+use regex::Regex;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Record};
+use tracing::{Event, Id, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, MakeWriter};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Secrets shorter than this are not masked by value; they would match
+/// ordinary words.
+const MIN_SECRET_VALUE_CHARS: usize = 8;
+
+static SENSITIVE_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    let query_names = "access_token|refresh_token|id_token|client_secret|api_key|apikey|key\
+                       |token|code|code_verifier|password|secret|signature";
+    let json_names = "access_token|refresh_token|id_token|client_secret|api_key|apikey\
+                      |token|password|secret|authorization";
+    vec![
+        (
+            Regex::new(r"(?i)\b(bearer\s+)[A-Za-z0-9._~+/=-]+").unwrap(),
+            "${1}[REDACTED]",
+        ),
+        (
+            Regex::new(r"(://[^/\s:@]+:)[^/\s@]+@").unwrap(),
+            "${1}[REDACTED]@",
+        ),
+        (
+            Regex::new(&format!(r#"(?i)([?&](?:{})=)[^&\s#)"'\\]+"#, query_names)).unwrap(),
+            "${1}[REDACTED]",
+        ),
+        (
+            Regex::new(&format!(
+                r#"(?i)(\\?"(?:{})\\?"\s*:\s*\\?")(?:[^"\\]|\\[^"])+"#,
+                json_names
+            ))
+            .unwrap(),
+            "${1}[REDACTED]",
+        ),
+    ]
+});
+
+/// Values of secret-looking environment variables, read once at startup.
+static SECRET_VALUES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let mut values: Vec<String> = std::env::vars()
+        .filter(|(name, _)| is_secret_name(name))
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| value.chars().count() >= MIN_SECRET_VALUE_CHARS)
+        .collect();
+    // Longest first, so a secret containing another is masked whole.
+    values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    values.dedup();
+    values
+});
+
+fn is_secret_name(name: &str) -> bool {
+    ["SECRET", "PASSWORD", "TOKEN", "API_KEY", "PRIVATE_KEY"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+/// Masks credentials in one formatted log line.
+pub fn redact(line: &str) -> String {
+    let mut redacted = line.to_string();
+    for secret in SECRET_VALUES.iter() {
+        if redacted.contains(secret.as_str()) {
+            redacted = redacted.replace(secret.as_str(), REDACTED);
+        }
+    }
+    for (pattern, replacement) in SENSITIVE_PATTERNS.iter() {
+        if let std::borrow::Cow::Owned(replaced) = pattern.replace_all(&redacted, *replacement) {
+            redacted = replaced;
+        }
+    }
+    redacted
+}
+
+/// Stdout writer that masks each line before writing it. The formatter
+/// writes a whole event in one call, so a line is never split.
+pub struct RedactingStdout;
+
+impl<'a> MakeWriter<'a> for RedactingStdout {
+    type Writer = RedactingWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter
+    }
+}
+
+pub struct RedactingWriter;
+
+impl Write for RedactingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        io::stdout().lock().write_all(redact(&line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+/// Request and user ids declared on a span, kept in its extensions so every
+/// event under it can carry them.
+#[derive(Default)]
+struct CorrelationIds {
+    request_id: Option<String>,
+    user_id: Option<i64>,
+}
+
+impl Visit for CorrelationIds {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if field.name() == "user_id" {
+            self.user_id = Some(value);
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "user_id" {
+            self.user_id = i64::try_from(value).ok();
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "request_id" && !value.is_empty() {
+            self.request_id = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "request_id" {
+            let value = format!("{:?}", value);
+            if !value.is_empty() {
+                self.request_id = Some(value);
+            }
+        }
+    }
+}
+
+/// Tracks `request_id` and `user_id` span fields for `JsonFormat`.
+pub struct CorrelationLayer;
+
+impl<S> Layer<S> for CorrelationLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let fields = attrs.metadata().fields();
+        if fields.field("request_id").is_none() && fields.field("user_id").is_none() {
+            return;
+        }
+        let mut ids = CorrelationIds::default();
+        attrs.record(&mut ids);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(ids);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(ids) = span.extensions_mut().get_mut::<CorrelationIds>()
+        {
+            values.record(ids);
+        }
+    }
+}
+
+/// One JSON object per event: timestamp, level, target, message, the
+/// event's fields, the innermost span's name, and the request and user
+/// ids of the enclosing spans.
+pub struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = JsonFields(Map::new());
+        event.record(&mut fields);
+
+        let mut line = Map::new();
+        line.insert(
+            "timestamp".to_string(),
+            Value::from(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)),
+        );
+        line.insert("level".to_string(), Value::from(metadata.level().as_str()));
+        line.insert("target".to_string(), Value::from(metadata.target()));
+        if let Some(message) = fields.0.remove("message") {
+            line.insert("message".to_string(), message);
+        }
+        if !fields.0.is_empty() {
+            line.insert("fields".to_string(), Value::Object(fields.0));
+        }
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut request_id = None;
+            let mut user_id = None;
+            let mut innermost = None;
+            for span in scope {
+                innermost.get_or_insert(span.name());
+                if let Some(ids) = span.extensions().get::<CorrelationIds>() {
+                    request_id = request_id.or_else(|| ids.request_id.clone());
+                    user_id = user_id.or(ids.user_id);
+                }
+            }
+            if let Some(name) = innermost {
+                line.insert("span".to_string(), Value::from(name));
+            }
+            if let Some(request_id) = request_id {
+                line.insert("request_id".to_string(), Value::from(request_id));
+            }
+            if let Some(user_id) = user_id {
+                line.insert("user_id".to_string(), Value::from(user_id));
+            }
+        }
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::from(format!("{:?}", value)),
+        );
+    }
+}
+
+/// Whether `LOG_FORMAT` asks for JSON lines; anything else keeps the
+/// human-readable format.
+pub fn json_enabled() -> bool {
+    std::env::var("LOG_FORMAT").is_ok_and(|raw| raw.trim().eq_ignore_ascii_case("json"))
+}
@@ -0,0 +1,124 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use ammonia::Builder;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd, html};
+
+const LINK_REL: &str = "nofollow noopener noreferrer";
+
+/// Which `<img>` sources survive sanitization. Configured with
+/// `COMMENT_IMAGE_POLICY` (`none`, `https` or `any`); defaults to `https`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImagePolicy {
+    None,
+    HttpsOnly,
+    Any,
+}
+
+impl ImagePolicy {
+    fn current() -> Self {
+        static POLICY: OnceLock<ImagePolicy> = OnceLock::new();
+        *POLICY.get_or_init(Self::from_env)
+    }
+
+    fn from_env() -> Self {
+        match std::env::var("COMMENT_IMAGE_POLICY")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "none" => Self::None,
+            "any" => Self::Any,
+            _ => Self::HttpsOnly,
+        }
+    }
+
+    fn allows(self, src: &str) -> bool {
+        match self {
+            Self::None => false,
+            Self::HttpsOnly => src.starts_with("https://") || src.starts_with("/uploads/"),
+            Self::Any => {
+                src.starts_with("https://")
+                    || src.starts_with("http://")
+                    || src.starts_with("/uploads/")
+            }
+        }
+    }
+}
+
+/// Renders user Markdown to sanitized HTML. Mirrors the frontend renderer:
+/// GitHub-flavored extensions, single newlines become line breaks and raw
+/// HTML in the source is dropped. Math is left as text for the client. Links
+/// get `rel="nofollow noopener noreferrer"`; images the policy rejects are
+/// replaced by their alt text.
+pub fn render_markdown(source: &str) -> String {
+    render(source, false)
+}
+
+/// Renders a paper body like [`render_markdown`], but keeps `$...$` and
+/// `$$...$$` math intact as `\(...\)` and `\[...\]` text so KaTeX can
+/// typeset it instead of Markdown emphasis mangling it.
+pub fn render_paper_markdown(source: &str) -> String {
+    render(source, true)
+}
+
+fn render(source: &str, keep_math: bool) -> String {
+    let mut options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    if keep_math {
+        options |= Options::ENABLE_MATH;
+    }
+    let image_policy = ImagePolicy::current();
+    // One entry per open image: whether its tags are being dropped.
+    let mut open_images: Vec<bool> = Vec::new();
+    let events = Parser::new_ext(source, options).filter_map(|event| match event {
+        Event::Html(_) | Event::InlineHtml(_) => None,
+        Event::SoftBreak => Some(Event::HardBreak),
+        Event::InlineMath(math) => Some(Event::Text(format!("\\({}\\)", math).into())),
+        Event::DisplayMath(math) => Some(Event::Text(format!("\\[{}\\]", math).into())),
+        Event::Start(Tag::Image { ref dest_url, .. }) => {
+            let allowed = image_policy.allows(dest_url);
+            open_images.push(!allowed);
+            allowed.then_some(event)
+        }
+        Event::End(TagEnd::Image) => match open_images.pop() {
+            Some(true) => None,
+            _ => Some(event),
+        },
+        other => Some(other),
+    });
+
+    let mut rendered = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut rendered, events);
+    sanitizer().clean(&rendered).to_string()
+}
+
+fn sanitizer() -> &'static Builder<'static> {
+    static SANITIZER: OnceLock<Builder<'static>> = OnceLock::new();
+    SANITIZER.get_or_init(|| {
+        let image_policy = ImagePolicy::current();
+        let mut builder = Builder::default();
+        builder
+            .url_schemes(HashSet::from(["http", "https", "mailto"]))
+            .link_rel(Some(LINK_REL))
+            .add_tag_attributes("input", ["type", "checked", "disabled"])
+            .add_tags(["input"])
+            .attribute_filter(move |element, attribute, value| {
+                if element == "img" && attribute == "src" && !image_policy.allows(value) {
+                    return None;
+                }
+                if element == "input" && attribute == "type" && value != "checkbox" {
+                    return None;
+                }
+                Some(Cow::Borrowed(value))
+            });
+        if image_policy == ImagePolicy::None {
+            builder.rm_tags(["img"]);
+        }
+        builder
+    })
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+
+use super::{AUTHOR_G_INDEX_FORMULA, build_author_metrics};
+use crate::models::AuthorMetrics;
+
+const REBUILD_BATCH_SIZE: usize = 500;
+
+type AuthorMetricsCacheRow = (i64, i64, i64, i64, i64, i64, String);
+
+/// Recomputes the cached metrics of the given authors from the paper
+/// citation counters in `post_stats`. Authors without papers get a zero row.
+pub async fn refresh_author_metrics_cache(
+    conn: &mut MySqlConnection,
+    user_ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT p.author_id, COALESCE(ps.citation_count, 0) AS citation_count
+        FROM posts p
+        JOIN post_categories pc ON pc.id = p.category_id
+        LEFT JOIN post_stats ps ON ps.post_id = p.id
+        WHERE pc.code = 'paper' AND p.deleted_at IS NULL AND p.author_id IN (
+        "#,
+    );
+    {
+        let mut separated = query_builder.separated(", ");
+        for user_id in user_ids {
+            separated.push_bind(user_id);
+        }
+    }
+    query_builder.push(") ORDER BY p.author_id ASC, citation_count DESC, p.id ASC");
+
+    let rows: Vec<(i64, i64)> = query_builder.build_query_as().fetch_all(&mut *conn).await?;
+    let mut counts_by_author: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (author_id, citation_count) in rows {
+        counts_by_author
+            .entry(author_id)
+            .or_default()
+            .push(citation_count);
+    }
+
+    let now = Utc::now();
+    for user_id in user_ids {
+        let citation_counts = counts_by_author.remove(user_id).unwrap_or_default();
+        let metrics = build_author_metrics(*user_id, &citation_counts);
+
+        // INSERT ... SELECT skips users deleted since the ids were collected.
+        sqlx::query(
+            r#"
+            INSERT INTO author_metrics_cache (
+                user_id,
+                g_index,
+                h_index,
+                i10_index,
+                total_citations,
+                paper_count,
+                metric_version,
+                computed_at
+            )
+            SELECT id, ?, ?, ?, ?, ?, ?, ? FROM users WHERE id = ?
+            ON DUPLICATE KEY UPDATE
+                g_index = VALUES(g_index),
+                h_index = VALUES(h_index),
+                i10_index = VALUES(i10_index),
+                total_citations = VALUES(total_citations),
+                paper_count = VALUES(paper_count),
+                metric_version = VALUES(metric_version),
+                computed_at = VALUES(computed_at)
+            "#,
+        )
+        .bind(metrics.g_index)
+        .bind(metrics.h_index)
+        .bind(metrics.i10_index)
+        .bind(metrics.total_citations)
+        .bind(metrics.paper_count)
+        .bind(&metrics.metric_version)
+        .bind(now)
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Refreshes the cache entry of every user. Runs on the citation count
+/// repair schedule so paper deletions and category changes are picked up.
+pub async fn rebuild_author_metrics_cache(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let user_ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM users ORDER BY id ASC")
+        .fetch_all(pool)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    for batch in user_ids.chunks(REBUILD_BATCH_SIZE) {
+        refresh_author_metrics_cache(&mut conn, batch).await?;
+    }
+
+    Ok(user_ids.len())
+}
+
+/// Reads author metrics from `author_metrics_cache`, filling the entry on a
+/// cache miss.
+pub async fn fetch_author_metrics(
+    pool: &MySqlPool,
+    user_id: i64,
+) -> Result<AuthorMetrics, sqlx::Error> {
+    if let Some(metrics) = fetch_cached_author_metrics(pool, user_id).await? {
+        return Ok(metrics);
+    }
+
+    let mut conn = pool.acquire().await?;
+    refresh_author_metrics_cache(&mut conn, &[user_id]).await?;
+    drop(conn);
+
+    Ok(fetch_cached_author_metrics(pool, user_id)
+        .await?
+        .unwrap_or_else(|| build_author_metrics(user_id, &[])))
+}
+
+/// Cached metrics of every author with at least one paper.
+pub async fn fetch_cached_author_metrics_list(
+    pool: &MySqlPool,
+) -> Result<Vec<AuthorMetrics>, sqlx::Error> {
+    let rows: Vec<AuthorMetricsCacheRow> = sqlx::query_as(
+        r#"
+        SELECT
+            user_id,
+            g_index,
+            h_index,
+            i10_index,
+            total_citations,
+            paper_count,
+            metric_version
+        FROM author_metrics_cache
+        WHERE paper_count > 0
+        ORDER BY user_id ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(author_metrics_from_row).collect())
+}
+
+async fn fetch_cached_author_metrics(
+    pool: &MySqlPool,
+    user_id: i64,
+) -> Result<Option<AuthorMetrics>, sqlx::Error> {
+    let row: Option<AuthorMetricsCacheRow> = sqlx::query_as(
+        r#"
+        SELECT
+            user_id,
+            g_index,
+            h_index,
+            i10_index,
+            total_citations,
+            paper_count,
+            metric_version
+        FROM author_metrics_cache
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(author_metrics_from_row))
+}
+
+fn author_metrics_from_row(row: AuthorMetricsCacheRow) -> AuthorMetrics {
+    let (user_id, g_index, h_index, i10_index, total_citations, paper_count, metric_version) = row;
+    AuthorMetrics {
+        user_id,
+        g_index,
+        h_index,
+        i10_index,
+        total_citations,
+        paper_count,
+        formula: AUTHOR_G_INDEX_FORMULA.to_string(),
+        metric_version,
+    }
+}
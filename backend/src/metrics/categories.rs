@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use sqlx::MySqlPool;
+
+use super::{METRIC_VERSION, ratio};
+use crate::models::{AiScoreAverages, CategoryMetrics, CategoryMetricsResponse};
+
+type CategoryVolumeRow = (i64, String, String, i64, i64, i64, i64);
+type CategoryReviewRow = (
+    i64,
+    i64,
+    i64,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+);
+
+/// Volume, review and citation metrics per post category. When `year` is
+/// set only posts submitted during that year are counted.
+pub async fn compute_category_metrics(
+    pool: &MySqlPool,
+    year: Option<i32>,
+) -> Result<CategoryMetricsResponse, sqlx::Error> {
+    let volume_rows: Vec<CategoryVolumeRow> = sqlx::query_as(
+        r#"
+        SELECT
+            CAST(c.id AS SIGNED),
+            c.code,
+            c.display_name,
+            COUNT(p.id),
+            CAST(COALESCE(SUM(CASE WHEN p.is_published = TRUE THEN 1 ELSE 0 END), 0) AS SIGNED),
+            CAST(COALESCE(SUM(CASE WHEN pr.post_id IS NOT NULL THEN 1 ELSE 0 END), 0) AS SIGNED),
+            CAST(COALESCE(SUM(ps.citation_count), 0) AS SIGNED)
+        FROM post_categories c
+        LEFT JOIN posts p
+            ON p.category_id = c.id
+            AND p.deleted_at IS NULL
+            AND (? IS NULL OR YEAR(p.created_at) = ?)
+        LEFT JOIN post_stats ps ON ps.post_id = p.id
+        LEFT JOIN post_retractions pr ON pr.post_id = p.id
+        GROUP BY c.id, c.code, c.display_name
+        ORDER BY c.id ASC
+        "#,
+    )
+    .bind(year)
+    .bind(year)
+    .fetch_all(pool)
+    .await?;
+
+    // Only the latest completed, decided review of each post counts.
+    let review_rows: Vec<CategoryReviewRow> = sqlx::query_as(
+        r#"
+        SELECT
+            CAST(p.category_id AS SIGNED),
+            COUNT(*),
+            CAST(COALESCE(SUM(CASE WHEN d.code = 'accept' THEN 1 ELSE 0 END), 0) AS SIGNED),
+            CAST(AVG(r.overall_score) AS DOUBLE),
+            CAST(AVG(r.novelty_score) AS DOUBLE),
+            CAST(AVG(r.methodology_score) AS DOUBLE),
+            CAST(AVG(r.clarity_score) AS DOUBLE),
+            CAST(AVG(r.citation_integrity_score) AS DOUBLE)
+        FROM post_ai_reviews r
+        JOIN ai_review_decisions d ON d.id = r.decision_id
+        JOIN posts p ON p.id = r.post_id
+        WHERE r.status_id = 2
+          AND p.deleted_at IS NULL
+          AND (? IS NULL OR YEAR(p.created_at) = ?)
+          AND r.id = (
+              SELECT MAX(r2.id)
+              FROM post_ai_reviews r2
+              WHERE r2.post_id = r.post_id AND r2.status_id = 2 AND r2.decision_id IS NOT NULL
+          )
+        GROUP BY p.category_id
+        "#,
+    )
+    .bind(year)
+    .bind(year)
+    .fetch_all(pool)
+    .await?;
+
+    let mut reviews_by_category: HashMap<i64, CategoryReviewRow> =
+        review_rows.into_iter().map(|row| (row.0, row)).collect();
+
+    let categories = volume_rows
+        .into_iter()
+        .map(
+            |(
+                category_id,
+                category,
+                display_name,
+                submissions,
+                published_posts,
+                retracted_posts,
+                total_citations,
+            )| {
+                let (reviewed_posts, accepted_posts, average_ai_scores) =
+                    match reviews_by_category.remove(&category_id) {
+                        Some((
+                            _,
+                            reviewed,
+                            accepted,
+                            overall,
+                            novelty,
+                            methodology,
+                            clarity,
+                            citation_integrity,
+                        )) => (
+                            reviewed,
+                            accepted,
+                            AiScoreAverages {
+                                overall,
+                                novelty,
+                                methodology,
+                                clarity,
+                                citation_integrity,
+                            },
+                        ),
+                        None => (0, 0, AiScoreAverages::default()),
+                    };
+
+                CategoryMetrics {
+                    category,
+                    display_name,
+                    submissions,
+                    published_posts,
+                    retracted_posts,
+                    reviewed_posts,
+                    accepted_posts,
+                    acceptance_rate: ratio(accepted_posts, reviewed_posts),
+                    average_ai_scores,
+                    total_citations,
+                    citations_per_post: ratio(total_citations, published_posts),
+                }
+            },
+        )
+        .collect();
+
+    Ok(CategoryMetricsResponse {
+        year,
+        categories,
+        metric_version: METRIC_VERSION.to_string(),
+    })
+}
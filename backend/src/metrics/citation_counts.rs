@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::{Executor, MySql, MySqlConnection, MySqlPool, QueryBuilder};
+
+use crate::tasks;
+
+use super::{rebuild_author_metrics_cache, refresh_author_metrics_cache};
+
+pub const DEFAULT_CITATION_COUNT_REPAIR_INTERVAL_SECS: u64 = 3_600;
+
+const REPAIR_BATCH_SIZE: usize = 500;
+
+/// Recomputes the denormalized `post_stats.citation_count` and
+/// `post_stats.external_citation_count` of the given posts from the citation
+/// edges, then refreshes the cached metrics of their authors. Call it inside
+/// the transaction that changed the edges so the counters commit (or roll
+/// back) together with them.
+pub async fn refresh_post_citation_counts(
+    conn: &mut MySqlConnection,
+    post_ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    if post_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        INSERT INTO post_stats (post_id, view_count, like_count, citation_count, external_citation_count, updated_at)
+        SELECT
+            p.id,
+            0,
+            0,
+            (SELECT COUNT(DISTINCT pc.citing_post_id) FROM post_citations pc JOIN posts citing ON citing.id = pc.citing_post_id WHERE pc.cited_post_id = p.id AND citing.deleted_at IS NULL)
+                + (SELECT COUNT(*) FROM post_external_citations pec WHERE pec.cited_post_id = p.id),
+            (SELECT COUNT(*) FROM post_external_citations pec WHERE pec.cited_post_id = p.id),
+        "#,
+    );
+    query_builder.push_bind(Utc::now());
+    query_builder.push(" FROM posts p WHERE p.id IN (");
+    {
+        let mut separated = query_builder.separated(", ");
+        for post_id in post_ids {
+            separated.push_bind(post_id);
+        }
+    }
+    query_builder.push(
+        r#")
+        ON DUPLICATE KEY UPDATE
+            citation_count = VALUES(citation_count),
+            external_citation_count = VALUES(external_citation_count),
+            updated_at = VALUES(updated_at)
+        "#,
+    );
+
+    query_builder.build().execute(&mut *conn).await?;
+
+    let mut author_query = QueryBuilder::<MySql>::new("SELECT DISTINCT author_id FROM posts WHERE id IN (");
+    {
+        let mut separated = author_query.separated(", ");
+        for post_id in post_ids {
+            separated.push_bind(post_id);
+        }
+    }
+    author_query.push(")");
+    let author_ids: Vec<i64> = author_query
+        .build_query_scalar()
+        .fetch_all(&mut *conn)
+        .await?;
+
+    refresh_author_metrics_cache(conn, &author_ids).await
+}
+
+/// Returns the posts cited by any of `citing_post_ids`; their counters must be
+/// refreshed after the citing posts lose their outgoing edges.
+pub async fn fetch_cited_post_ids<'e, E>(
+    executor: E,
+    citing_post_ids: &[i64],
+) -> Result<Vec<i64>, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    if citing_post_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        "SELECT DISTINCT cited_post_id FROM post_citations WHERE citing_post_id IN (",
+    );
+    {
+        let mut separated = query_builder.separated(", ");
+        for post_id in citing_post_ids {
+            separated.push_bind(post_id);
+        }
+    }
+    query_builder.push(")");
+
+    query_builder.build_query_scalar().fetch_all(executor).await
+}
+
+/// Starts the periodic job that repairs drifted citation counters and
+/// rebuilds the author metrics cache.
+/// Setting `CITATION_COUNT_REPAIR_INTERVAL_SECS=0` disables the job.
+pub fn spawn_citation_count_repair(pool: MySqlPool) {
+    let interval_secs = repair_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Citation count repair job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "citation_count_repair",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match repair_citation_counts(&pool).await {
+                    Ok(0) => {}
+                    Ok(repaired) => {
+                        tracing::warn!("Repaired citation counters of {} posts", repaired)
+                    }
+                    Err(error) => tracing::error!("Citation count repair failed: {}", error),
+                }
+                if let Err(error) = rebuild_author_metrics_cache(&pool).await {
+                    tracing::error!("Author metrics cache rebuild failed: {}", error);
+                }
+            }
+        },
+    );
+}
+
+/// Finds posts whose stored counters disagree with the citation edges and
+/// recomputes them. Returns the number of posts that were repaired.
+pub async fn repair_citation_counts(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let drifted_post_ids: Vec<i64> = sqlx::query_scalar(
+        r#"
+        SELECT p.id
+        FROM posts p
+        LEFT JOIN post_stats ps ON ps.post_id = p.id
+        LEFT JOIN (
+            SELECT pc.cited_post_id, COUNT(DISTINCT pc.citing_post_id) AS citation_count
+            FROM post_citations pc
+            JOIN posts citing ON citing.id = pc.citing_post_id
+            WHERE citing.deleted_at IS NULL
+            GROUP BY pc.cited_post_id
+        ) ic ON ic.cited_post_id = p.id
+        LEFT JOIN (
+            SELECT cited_post_id, COUNT(*) AS citation_count
+            FROM post_external_citations
+            GROUP BY cited_post_id
+        ) ec ON ec.cited_post_id = p.id
+        WHERE COALESCE(ps.citation_count, 0)
+                <> COALESCE(ic.citation_count, 0) + COALESCE(ec.citation_count, 0)
+           OR COALESCE(ps.external_citation_count, 0) <> COALESCE(ec.citation_count, 0)
+        ORDER BY p.id ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut conn = pool.acquire().await?;
+    for batch in drifted_post_ids.chunks(REPAIR_BATCH_SIZE) {
+        refresh_post_citation_counts(&mut conn, batch).await?;
+    }
+
+    Ok(drifted_post_ids.len())
+}
+
+fn repair_interval_secs() -> u64 {
+    std::env::var("CITATION_COUNT_REPAIR_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CITATION_COUNT_REPAIR_INTERVAL_SECS)
+}
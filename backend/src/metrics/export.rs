@@ -0,0 +1,459 @@
+use std::io;
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::compute_impact_factor;
+
+const EXPORT_CHANNEL_CAPACITY: usize = 32;
+
+const AUTHOR_EXPORT_COLUMNS: &[&str] = &[
+    "user_id",
+    "username",
+    "display_name",
+    "g_index",
+    "h_index",
+    "i10_index",
+    "total_citations",
+    "paper_count",
+    "metric_version",
+    "computed_at",
+];
+
+const JOURNAL_EXPORT_COLUMNS: &[&str] = &[
+    "year",
+    "impact_factor",
+    "numerator_citations",
+    "denominator_papers",
+    "impact_factor_5y",
+    "numerator_citations_5y",
+    "denominator_papers_5y",
+    "immediacy_index",
+    "immediacy_citations",
+    "immediacy_papers",
+    "cited_half_life",
+    "acceptance_rate",
+    "accepted_papers",
+    "reviewed_papers",
+    "formula",
+    "metric_version",
+];
+
+const REVIEW_EXPORT_COLUMNS: &[&str] = &[
+    "review_id",
+    "post_id",
+    "category",
+    "status",
+    "trigger",
+    "decision",
+    "model",
+    "prompt_version",
+    "overall_score",
+    "novelty_score",
+    "methodology_score",
+    "clarity_score",
+    "citation_integrity_score",
+    "created_at",
+    "completed_at",
+];
+
+type AuthorExportRow = (
+    i64,
+    String,
+    Option<String>,
+    i64,
+    i64,
+    i64,
+    i64,
+    i64,
+    String,
+    DateTime<Utc>,
+);
+
+type ReviewExportRow = (
+    i64,
+    i64,
+    String,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportDataset {
+    Authors,
+    Journal,
+    Reviews,
+}
+
+impl ExportDataset {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "authors" => Some(Self::Authors),
+            "journal" => Some(Self::Journal),
+            "reviews" => Some(Self::Reviews),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Authors => "authors",
+            Self::Journal => "journal",
+            Self::Reviews => "reviews",
+        }
+    }
+
+    pub fn columns(self) -> &'static [&'static str] {
+        match self {
+            Self::Authors => AUTHOR_EXPORT_COLUMNS,
+            Self::Journal => JOURNAL_EXPORT_COLUMNS,
+            Self::Reviews => REVIEW_EXPORT_COLUMNS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Resolves a comma-separated column list against the dataset, keeping the
+/// requested order. Returns the first unknown column name on failure.
+pub fn resolve_export_columns(
+    dataset: ExportDataset,
+    requested: Option<&str>,
+) -> Result<Vec<&'static str>, String> {
+    let available = dataset.columns();
+    let Some(requested) = requested.filter(|raw| !raw.trim().is_empty()) else {
+        return Ok(available.to_vec());
+    };
+
+    let mut columns = Vec::new();
+    for name in requested
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let column = available
+            .iter()
+            .find(|column| **column == name)
+            .ok_or_else(|| name.to_string())?;
+        if !columns.contains(column) {
+            columns.push(*column);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Streams the dataset as CSV or a JSON array. Rows are fetched in a
+/// background task and written as they arrive; a database error ends the
+/// stream with an error so the client sees a truncated download.
+pub fn stream_metrics_export(
+    pool: MySqlPool,
+    dataset: ExportDataset,
+    format: ExportFormat,
+    columns: Vec<&'static str>,
+    journal_years: RangeInclusive<i32>,
+) -> ReceiverStream<Result<String, io::Error>> {
+    let (sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+
+    tokio::spawn(async move {
+        let mut writer = ExportWriter {
+            sender,
+            format,
+            columns,
+            rows_written: 0,
+        };
+        if !writer.start().await {
+            return;
+        }
+
+        let result = match dataset {
+            ExportDataset::Authors => export_authors(&pool, &mut writer).await,
+            ExportDataset::Journal => export_journal(&pool, &mut writer, journal_years).await,
+            ExportDataset::Reviews => export_reviews(&pool, &mut writer).await,
+        };
+
+        match result {
+            Ok(()) => writer.finish().await,
+            Err(error) => {
+                tracing::error!("Metrics export of {} failed: {}", dataset.as_str(), error);
+                let _ = writer.sender.send(Err(io::Error::other(error))).await;
+            }
+        }
+    });
+
+    ReceiverStream::new(receiver)
+}
+
+struct ExportWriter {
+    sender: mpsc::Sender<Result<String, io::Error>>,
+    format: ExportFormat,
+    columns: Vec<&'static str>,
+    rows_written: usize,
+}
+
+impl ExportWriter {
+    /// Each send returns `false` once the client has gone away.
+    async fn send(&self, chunk: String) -> bool {
+        self.sender.send(Ok(chunk)).await.is_ok()
+    }
+
+    async fn start(&self) -> bool {
+        match self.format {
+            ExportFormat::Csv => {
+                let header = self
+                    .columns
+                    .iter()
+                    .map(|column| csv_escape(column))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                self.send(format!("{}\r\n", header)).await
+            }
+            ExportFormat::Json => self.send("[".to_string()).await,
+        }
+    }
+
+    async fn write_row(&mut self, row: &serde_json::Value) -> bool {
+        let chunk = match self.format {
+            ExportFormat::Csv => {
+                let line = self
+                    .columns
+                    .iter()
+                    .map(|column| csv_cell(row.get(*column)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("{}\r\n", line)
+            }
+            ExportFormat::Json => {
+                let selected: serde_json::Map<String, serde_json::Value> = self
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        (
+                            column.to_string(),
+                            row.get(*column).cloned().unwrap_or(serde_json::Value::Null),
+                        )
+                    })
+                    .collect();
+                let separator = if self.rows_written == 0 { "\n" } else { ",\n" };
+                format!("{}{}", separator, serde_json::Value::Object(selected))
+            }
+        };
+        self.rows_written += 1;
+        self.send(chunk).await
+    }
+
+    async fn finish(&self) {
+        if self.format == ExportFormat::Json {
+            self.send("\n]\n".to_string()).await;
+        }
+    }
+}
+
+fn csv_cell(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(text)) => {
+            // Leading formula characters would be evaluated by spreadsheets.
+            if text.starts_with(['=', '+', '-', '@']) {
+                csv_escape(&format!("'{}", text))
+            } else {
+                csv_escape(text)
+            }
+        }
+        Some(other) => other.to_string(),
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+async fn export_authors(pool: &MySqlPool, writer: &mut ExportWriter) -> Result<(), sqlx::Error> {
+    let mut rows = sqlx::query_as::<_, AuthorExportRow>(
+        r#"
+        SELECT
+            amc.user_id,
+            u.username,
+            u.display_name,
+            amc.g_index,
+            amc.h_index,
+            amc.i10_index,
+            amc.total_citations,
+            amc.paper_count,
+            amc.metric_version,
+            amc.computed_at
+        FROM author_metrics_cache amc
+        JOIN users u ON u.id = amc.user_id
+        WHERE amc.paper_count > 0
+        ORDER BY amc.user_id ASC
+        "#,
+    )
+    .fetch(pool);
+
+    while let Some(row) = rows.next().await {
+        let (
+            user_id,
+            username,
+            display_name,
+            g_index,
+            h_index,
+            i10_index,
+            total_citations,
+            paper_count,
+            metric_version,
+            computed_at,
+        ) = row?;
+        let row = serde_json::json!({
+            "user_id": user_id,
+            "username": username,
+            "display_name": display_name,
+            "g_index": g_index,
+            "h_index": h_index,
+            "i10_index": i10_index,
+            "total_citations": total_citations,
+            "paper_count": paper_count,
+            "metric_version": metric_version,
+            "computed_at": computed_at,
+        });
+        if !writer.write_row(&row).await {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+async fn export_journal(
+    pool: &MySqlPool,
+    writer: &mut ExportWriter,
+    years: RangeInclusive<i32>,
+) -> Result<(), sqlx::Error> {
+    for year in years {
+        let metrics = compute_impact_factor(pool, year).await?;
+        let row = serde_json::to_value(&metrics).unwrap_or(serde_json::Value::Null);
+        if !writer.write_row(&row).await {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+async fn export_reviews(pool: &MySqlPool, writer: &mut ExportWriter) -> Result<(), sqlx::Error> {
+    let mut rows = sqlx::query_as::<_, ReviewExportRow>(
+        r#"
+        SELECT
+            r.id,
+            r.post_id,
+            c.code,
+            s.code,
+            t.code,
+            d.code,
+            r.model,
+            r.prompt_version,
+            CAST(r.overall_score AS SIGNED),
+            CAST(r.novelty_score AS SIGNED),
+            CAST(r.methodology_score AS SIGNED),
+            CAST(r.clarity_score AS SIGNED),
+            CAST(r.citation_integrity_score AS SIGNED),
+            r.created_at,
+            r.completed_at
+        FROM post_ai_reviews r
+        JOIN posts p ON p.id = r.post_id
+        JOIN post_categories c ON c.id = p.category_id
+        JOIN ai_review_statuses s ON s.id = r.status_id
+        JOIN ai_review_triggers t ON t.id = r.trigger_id
+        LEFT JOIN ai_review_decisions d ON d.id = r.decision_id
+        ORDER BY r.id ASC
+        "#,
+    )
+    .fetch(pool);
+
+    while let Some(row) = rows.next().await {
+        let (
+            review_id,
+            post_id,
+            category,
+            status,
+            trigger,
+            decision,
+            model,
+            prompt_version,
+            overall_score,
+            novelty_score,
+            methodology_score,
+            clarity_score,
+            citation_integrity_score,
+            created_at,
+            completed_at,
+        ) = row?;
+        let row = serde_json::json!({
+            "review_id": review_id,
+            "post_id": post_id,
+            "category": category,
+            "status": status,
+            "trigger": trigger,
+            "decision": decision,
+            "model": model,
+            "prompt_version": prompt_version,
+            "overall_score": overall_score,
+            "novelty_score": novelty_score,
+            "methodology_score": methodology_score,
+            "clarity_score": clarity_score,
+            "citation_integrity_score": citation_integrity_score,
+            "created_at": created_at,
+            "completed_at": completed_at,
+        });
+        if !writer.write_row(&row).await {
+            break;
+        }
+    }
+
+    Ok(())
+}
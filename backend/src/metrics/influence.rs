@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use crate::tasks;
+
+pub const DEFAULT_INFLUENCE_SCORE_INTERVAL_SECS: u64 = 3_600;
+
+const DAMPING_FACTOR: f64 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const CONVERGENCE_TOLERANCE: f64 = 1e-9;
+const WRITE_BATCH_SIZE: usize = 500;
+
+/// Starts the periodic job that recomputes `post_stats.influence_score`.
+/// Setting `INFLUENCE_SCORE_INTERVAL_SECS=0` disables the job.
+pub fn spawn_influence_scores(pool: MySqlPool) {
+    let interval_secs = influence_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Influence score job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "influence_scores",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match refresh_influence_scores(&pool).await {
+                    Ok(post_count) => {
+                        tracing::info!("Influence scores refreshed for {} posts", post_count)
+                    }
+                    Err(error) => tracing::error!("Influence score refresh failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Runs PageRank over the internal citation graph and stores the result in
+/// `post_stats.influence_score`. Scores are scaled so the average post scores
+/// 1.0, which keeps them comparable as the graph grows. Edges flagged by a
+/// retraction and self-citations are ignored. Returns the number of posts.
+pub async fn refresh_influence_scores(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let post_ids: Vec<i64> =
+        sqlx::query_scalar("SELECT id FROM posts WHERE deleted_at IS NULL ORDER BY id ASC")
+            .fetch_all(pool)
+            .await?;
+    let edges: Vec<(i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT citing_post_id, cited_post_id
+        FROM post_citations
+        WHERE retraction_flagged_at IS NULL AND citing_post_id <> cited_post_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let scores = calculate_page_rank(&post_ids, &edges);
+    let node_count = post_ids.len() as f64;
+    let now = Utc::now();
+
+    let mut conn = pool.acquire().await?;
+    for batch in post_ids.chunks(WRITE_BATCH_SIZE) {
+        let mut query_builder = QueryBuilder::<MySql>::new(
+            "INSERT INTO post_stats (post_id, view_count, like_count, influence_score, updated_at) ",
+        );
+        query_builder.push_values(batch, |mut row, post_id| {
+            row.push_bind(*post_id)
+                .push_bind(0_i64)
+                .push_bind(0_i64)
+                .push_bind(scores[post_id] * node_count)
+                .push_bind(now);
+        });
+        query_builder.push(
+            " ON DUPLICATE KEY UPDATE influence_score = VALUES(influence_score), updated_at = VALUES(updated_at)",
+        );
+        query_builder.build().execute(&mut *conn).await?;
+    }
+
+    Ok(post_ids.len())
+}
+
+/// Power iteration over `edges` (citing, cited). Rank held by posts that cite
+/// nothing is spread evenly over all posts. The returned scores sum to 1.
+fn calculate_page_rank(post_ids: &[i64], edges: &[(i64, i64)]) -> HashMap<i64, f64> {
+    let node_count = post_ids.len();
+    if node_count == 0 {
+        return HashMap::new();
+    }
+
+    let index_of: HashMap<i64, usize> = post_ids
+        .iter()
+        .enumerate()
+        .map(|(idx, post_id)| (*post_id, idx))
+        .collect();
+    let mut out_degree = vec![0_usize; node_count];
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    for (citing, cited) in edges {
+        if let (Some(&from), Some(&to)) = (index_of.get(citing), index_of.get(cited)) {
+            out_degree[from] += 1;
+            incoming[to].push(from);
+        }
+    }
+
+    let n = node_count as f64;
+    let mut ranks = vec![1.0 / n; node_count];
+    for _ in 0..MAX_ITERATIONS {
+        let dangling_rank: f64 = ranks
+            .iter()
+            .zip(&out_degree)
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(rank, _)| *rank)
+            .sum();
+        let base = (1.0 - DAMPING_FACTOR) / n + DAMPING_FACTOR * dangling_rank / n;
+
+        let next: Vec<f64> = incoming
+            .iter()
+            .map(|sources| {
+                base + DAMPING_FACTOR
+                    * sources
+                        .iter()
+                        .map(|from| ranks[*from] / out_degree[*from] as f64)
+                        .sum::<f64>()
+            })
+            .collect();
+
+        let delta: f64 = next
+            .iter()
+            .zip(&ranks)
+            .map(|(next_rank, rank)| (next_rank - rank).abs())
+            .sum();
+        ranks = next;
+        if delta < CONVERGENCE_TOLERANCE {
+            break;
+        }
+    }
+
+    post_ids.iter().copied().zip(ranks).collect()
+}
+
+fn influence_interval_secs() -> u64 {
+    std::env::var("INFLUENCE_SCORE_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_INFLUENCE_SCORE_INTERVAL_SECS)
+}
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::{Months, NaiveDate, Utc};
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use crate::models::{AuthorLeaderboardEntry, LeaderboardResponse, PaperLeaderboardEntry};
+
+pub const DEFAULT_LEADERBOARD_CACHE_TTL_SECS: u64 = 300;
+
+/// Citations received per post since the bound date: internal citations are
+/// dated by the first edge of each citing post, external ones by first sight.
+/// Binds the start date twice.
+const PERIOD_CITATIONS_SUBQUERY: &str = r#"(
+    SELECT post_id, CAST(SUM(citation_count) AS SIGNED) AS citations
+    FROM (
+        SELECT edges.cited_post_id AS post_id, COUNT(*) AS citation_count
+        FROM (
+            SELECT citing_post_id, cited_post_id, MIN(created_at) AS first_cited_at
+            FROM post_citations
+            GROUP BY citing_post_id, cited_post_id
+        ) edges
+        WHERE edges.first_cited_at >= "#;
+const PERIOD_CITATIONS_SUBQUERY_MIDDLE: &str = r#"
+        GROUP BY edges.cited_post_id
+        UNION ALL
+        SELECT cited_post_id AS post_id, COUNT(*) AS citation_count
+        FROM post_external_citations
+        WHERE first_seen_at >= "#;
+const PERIOD_CITATIONS_SUBQUERY_END: &str = r#"
+        GROUP BY cited_post_id
+    ) period_sources
+    GROUP BY post_id
+)"#;
+
+type AuthorLeaderboardRow = (i64, String, Option<String>, i64, i64, i64, i64, i64, i64);
+type PaperLeaderboardRow = (i64, String, i64, String, i64, i64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeaderboardBoard {
+    Authors,
+    Papers,
+}
+
+impl LeaderboardBoard {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "authors" => Some(Self::Authors),
+            "papers" => Some(Self::Papers),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Authors => "authors",
+            Self::Papers => "papers",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeaderboardMetric {
+    GIndex,
+    HIndex,
+    Citations,
+    Views,
+}
+
+impl LeaderboardMetric {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "g_index" => Some(Self::GIndex),
+            "h_index" => Some(Self::HIndex),
+            "citations" => Some(Self::Citations),
+            "views" => Some(Self::Views),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::GIndex => "g_index",
+            Self::HIndex => "h_index",
+            Self::Citations => "citations",
+            Self::Views => "views",
+        }
+    }
+
+    pub fn supports(self, board: LeaderboardBoard) -> bool {
+        match board {
+            LeaderboardBoard::Authors => {
+                matches!(self, Self::GIndex | Self::HIndex | Self::Citations)
+            }
+            LeaderboardBoard::Papers => matches!(self, Self::Citations | Self::Views),
+        }
+    }
+
+    /// Author indices are only tracked over the whole history.
+    pub fn supports_period(self) -> bool {
+        matches!(self, Self::Citations | Self::Views)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeaderboardPeriod {
+    All,
+    Year,
+    Month,
+    Week,
+}
+
+impl LeaderboardPeriod {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "all" => Some(Self::All),
+            "year" => Some(Self::Year),
+            "month" => Some(Self::Month),
+            "week" => Some(Self::Week),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Year => "year",
+            Self::Month => "month",
+            Self::Week => "week",
+        }
+    }
+
+    fn since(self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::All => None,
+            Self::Year => today.checked_sub_months(Months::new(12)),
+            Self::Month => today.checked_sub_months(Months::new(1)),
+            Self::Week => Some(today - chrono::Duration::days(7)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LeaderboardRequest {
+    pub board: LeaderboardBoard,
+    pub metric: LeaderboardMetric,
+    pub period: LeaderboardPeriod,
+    pub page: i64,
+    pub per_page: i64,
+}
+
+type LeaderboardCache = Mutex<HashMap<LeaderboardRequest, (Instant, LeaderboardResponse)>>;
+
+fn leaderboard_cache() -> &'static LeaderboardCache {
+    static CACHE: OnceLock<LeaderboardCache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Serves leaderboards from a process-local cache that expires after
+/// `LEADERBOARD_CACHE_TTL_SECS` (0 disables caching).
+pub async fn fetch_leaderboard(
+    pool: &MySqlPool,
+    request: LeaderboardRequest,
+) -> Result<LeaderboardResponse, sqlx::Error> {
+    let ttl = Duration::from_secs(cache_ttl_secs());
+    if !ttl.is_zero()
+        && let Ok(cache) = leaderboard_cache().lock()
+        && let Some((cached_at, response)) = cache.get(&request)
+        && cached_at.elapsed() < ttl
+    {
+        return Ok(response.clone());
+    }
+
+    let response = compute_leaderboard(pool, request).await?;
+
+    if !ttl.is_zero()
+        && let Ok(mut cache) = leaderboard_cache().lock()
+    {
+        cache.retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+        cache.insert(request, (Instant::now(), response.clone()));
+    }
+
+    Ok(response)
+}
+
+pub async fn compute_leaderboard(
+    pool: &MySqlPool,
+    request: LeaderboardRequest,
+) -> Result<LeaderboardResponse, sqlx::Error> {
+    let since = request.period.since(Utc::now().date_naive());
+    let offset = (request.page - 1) * request.per_page;
+
+    let (total, authors, papers) = match request.board {
+        LeaderboardBoard::Authors => {
+            let (total, entries) = fetch_author_leaderboard(pool, request, since, offset).await?;
+            (total, Some(entries), None)
+        }
+        LeaderboardBoard::Papers => {
+            let (total, entries) = fetch_paper_leaderboard(pool, request, since, offset).await?;
+            (total, None, Some(entries))
+        }
+    };
+
+    Ok(LeaderboardResponse {
+        board: request.board.as_str().to_string(),
+        metric: request.metric.as_str().to_string(),
+        period: request.period.as_str().to_string(),
+        since,
+        page: request.page,
+        per_page: request.per_page,
+        total,
+        authors,
+        papers,
+        generated_at: Utc::now(),
+    })
+}
+
+fn push_period_citations(query_builder: &mut QueryBuilder<MySql>, since: NaiveDate) {
+    query_builder.push(PERIOD_CITATIONS_SUBQUERY);
+    query_builder.push_bind(since);
+    query_builder.push(PERIOD_CITATIONS_SUBQUERY_MIDDLE);
+    query_builder.push_bind(since);
+    query_builder.push(PERIOD_CITATIONS_SUBQUERY_END);
+}
+
+async fn fetch_author_leaderboard(
+    pool: &MySqlPool,
+    request: LeaderboardRequest,
+    since: Option<NaiveDate>,
+    offset: i64,
+) -> Result<(i64, Vec<AuthorLeaderboardEntry>), sqlx::Error> {
+    let push_from = |query_builder: &mut QueryBuilder<MySql>| {
+        query_builder.push(" FROM author_metrics_cache amc JOIN users u ON u.id = amc.user_id");
+        if let Some(since) = since {
+            query_builder.push(
+                " JOIN (SELECT p.author_id, CAST(SUM(pcs.citations) AS SIGNED) AS citations FROM ",
+            );
+            push_period_citations(query_builder, since);
+            query_builder.push(
+                " pcs JOIN posts p ON p.id = pcs.post_id JOIN post_categories c ON c.id = p.category_id WHERE c.code = 'paper' AND p.deleted_at IS NULL GROUP BY p.author_id) period ON period.author_id = u.id",
+            );
+        }
+        query_builder.push(" WHERE amc.paper_count > 0");
+        if request.metric == LeaderboardMetric::Citations {
+            query_builder.push(if since.is_some() {
+                " AND period.citations > 0"
+            } else {
+                " AND amc.total_citations > 0"
+            });
+        }
+    };
+
+    let mut count_qb = QueryBuilder::<MySql>::new("SELECT COUNT(*)");
+    push_from(&mut count_qb);
+    let (total,): (i64,) = count_qb.build_query_as().fetch_one(pool).await?;
+
+    let period_column = if since.is_some() {
+        "period.citations"
+    } else {
+        "amc.total_citations"
+    };
+    let order_column = match request.metric {
+        LeaderboardMetric::GIndex => "amc.g_index",
+        LeaderboardMetric::HIndex => "amc.h_index",
+        _ => period_column,
+    };
+
+    let mut query_builder = QueryBuilder::<MySql>::new(format!(
+        "SELECT u.id, u.username, u.display_name, amc.g_index, amc.h_index, amc.i10_index, amc.total_citations, {} AS period_citations, amc.paper_count",
+        period_column
+    ));
+    push_from(&mut query_builder);
+    query_builder.push(format!(
+        " ORDER BY {} DESC, amc.total_citations DESC, u.id ASC LIMIT ",
+        order_column
+    ));
+    query_builder.push_bind(request.per_page);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(offset);
+
+    let rows: Vec<AuthorLeaderboardRow> = query_builder.build_query_as().fetch_all(pool).await?;
+    let entries = rows
+        .into_iter()
+        .enumerate()
+        .map(
+            |(
+                idx,
+                (
+                    user_id,
+                    username,
+                    display_name,
+                    g_index,
+                    h_index,
+                    i10_index,
+                    total_citations,
+                    period_citations,
+                    paper_count,
+                ),
+            )| AuthorLeaderboardEntry {
+                rank: offset + idx as i64 + 1,
+                user_id,
+                username,
+                display_name,
+                g_index,
+                h_index,
+                i10_index,
+                total_citations,
+                period_citations,
+                paper_count,
+            },
+        )
+        .collect();
+
+    Ok((total, entries))
+}
+
+async fn fetch_paper_leaderboard(
+    pool: &MySqlPool,
+    request: LeaderboardRequest,
+    since: Option<NaiveDate>,
+    offset: i64,
+) -> Result<(i64, Vec<PaperLeaderboardEntry>), sqlx::Error> {
+    let (citations_column, views_column) = if since.is_some() {
+        ("COALESCE(pcs.citations, 0)", "COALESCE(pv.views, 0)")
+    } else {
+        (
+            "COALESCE(ps.citation_count, 0)",
+            "COALESCE(ps.view_count, 0)",
+        )
+    };
+    let (order_column, tie_column) = match request.metric {
+        LeaderboardMetric::Views => (views_column, citations_column),
+        _ => (citations_column, views_column),
+    };
+
+    let push_from = |query_builder: &mut QueryBuilder<MySql>| {
+        query_builder.push(
+            r#"
+            FROM posts p
+            JOIN post_categories c ON c.id = p.category_id
+            JOIN users u ON u.id = p.author_id
+            LEFT JOIN post_stats ps ON ps.post_id = p.id
+            LEFT JOIN post_retractions pr ON pr.post_id = p.id
+            "#,
+        );
+        if let Some(since) = since {
+            query_builder.push(" LEFT JOIN ");
+            push_period_citations(query_builder, since);
+            query_builder.push(
+                " pcs ON pcs.post_id = p.id LEFT JOIN (SELECT post_id, SUM(views) AS views FROM post_stat_rollups WHERE granularity = 'day' AND bucket_start >= ",
+            );
+            query_builder.push_bind(since);
+            query_builder.push(" GROUP BY post_id) pv ON pv.post_id = p.id");
+        }
+        query_builder.push(format!(
+            " WHERE c.code = 'paper' AND p.is_published = TRUE AND p.deleted_at IS NULL AND pr.post_id IS NULL AND {} > 0",
+            order_column
+        ));
+    };
+
+    let mut count_qb = QueryBuilder::<MySql>::new("SELECT COUNT(*)");
+    push_from(&mut count_qb);
+    let (total,): (i64,) = count_qb.build_query_as().fetch_one(pool).await?;
+
+    let mut query_builder = QueryBuilder::<MySql>::new(format!(
+        "SELECT p.id, p.title, p.author_id, u.username, CAST({} AS SIGNED) AS citations, CAST({} AS SIGNED) AS views",
+        citations_column, views_column
+    ));
+    push_from(&mut query_builder);
+    query_builder.push(format!(
+        " ORDER BY {} DESC, {} DESC, p.id ASC LIMIT ",
+        order_column, tie_column
+    ));
+    query_builder.push_bind(request.per_page);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(offset);
+
+    let rows: Vec<PaperLeaderboardRow> = query_builder.build_query_as().fetch_all(pool).await?;
+    let entries = rows
+        .into_iter()
+        .enumerate()
+        .map(
+            |(idx, (post_id, title, author_id, author_username, citations, views))| {
+                PaperLeaderboardEntry {
+                    rank: offset + idx as i64 + 1,
+                    post_id,
+                    title,
+                    author_id,
+                    author_username,
+                    citations,
+                    views,
+                }
+            },
+        )
+        .collect();
+
+    Ok((total, entries))
+}
+
+fn cache_ttl_secs() -> u64 {
+    std::env::var("LEADERBOARD_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_LEADERBOARD_CACHE_TTL_SECS)
+}
@@ -1,58 +1,44 @@
-use std::collections::HashMap;
+mod author_cache;
+mod categories;
+mod citation_counts;
+mod export;
+mod influence;
+mod leaderboards;
+mod rollups;
+mod snapshots;
 
-use crate::models::{AuthorMetrics, JournalMetrics};
-use sqlx::{MySql, MySqlPool, QueryBuilder};
+pub use author_cache::*;
+pub use categories::*;
+pub use citation_counts::*;
+pub use export::*;
+pub use influence::*;
+pub use leaderboards::*;
+pub use rollups::*;
+pub use snapshots::*;
+
+use crate::models::{AuthorMetrics, JournalMetrics, PostMetrics};
+use sqlx::MySqlPool;
 
 pub const METRIC_VERSION: &str = "v1";
 pub const JOURNAL_IMPACT_FORMULA: &str = "jif_2y";
 pub const AUTHOR_G_INDEX_FORMULA: &str = "g_index";
+/// Minimum citations for a paper to count towards the i10-index.
+pub const I10_CITATION_THRESHOLD: i64 = 10;
 
-pub async fn compute_citation_count(pool: &MySqlPool, post_id: i64) -> Result<i64, sqlx::Error> {
-    let (count,): (i64,) = sqlx::query_as(
-        r#"
-        SELECT COUNT(*)
-        FROM (
-            SELECT DISTINCT citing_post_id, cited_post_id
-            FROM post_citations
-        ) c
-        WHERE c.cited_post_id = ?
-        "#,
-    )
-    .bind(post_id)
-    .fetch_one(pool)
-    .await?;
-
-    Ok(count)
-}
-
-pub async fn compute_citation_counts_for_posts(
-    pool: &MySqlPool,
-    post_ids: &[i64],
-) -> Result<HashMap<i64, i64>, sqlx::Error> {
-    if post_ids.is_empty() {
-        return Ok(HashMap::new());
-    }
-
-    let mut query_builder = QueryBuilder::<MySql>::new(
-        r#"
-        SELECT c.cited_post_id, COUNT(*) as citation_count
-        FROM (
-            SELECT DISTINCT citing_post_id, cited_post_id
-            FROM post_citations
-        ) c
-        WHERE c.cited_post_id IN (
-        "#,
-    );
-    {
-        let mut separated = query_builder.separated(", ");
-        for post_id in post_ids {
-            separated.push_bind(post_id);
-        }
+/// Builds the public metrics block from the denormalized counters stored in
+/// `post_stats` (see [`refresh_post_citation_counts`] and
+/// [`refresh_influence_scores`]).
+pub fn build_post_metrics(
+    citation_count: i64,
+    external_citation_count: i64,
+    influence_score: f64,
+) -> PostMetrics {
+    PostMetrics {
+        citation_count,
+        external_citation_count,
+        influence_score,
+        metric_version: METRIC_VERSION.to_string(),
     }
-    query_builder.push(") GROUP BY c.cited_post_id");
-
-    let rows: Vec<(i64, i64)> = query_builder.build_query_as().fetch_all(pool).await?;
-    Ok(rows.into_iter().collect())
 }
 
 #[allow(dead_code)]
@@ -61,38 +47,77 @@ pub async fn compute_g_index(pool: &MySqlPool, user_id: i64) -> Result<i64, sqlx
     Ok(calculate_g_index(&citation_counts))
 }
 
-pub async fn compute_author_metrics(
-    pool: &MySqlPool,
-    user_id: i64,
-) -> Result<AuthorMetrics, sqlx::Error> {
-    let citation_counts = fetch_author_paper_citation_counts(pool, user_id).await?;
-    let total_citations = citation_counts.iter().sum::<i64>();
-    let g_index = calculate_g_index(&citation_counts);
-
-    Ok(AuthorMetrics {
+/// Expects `citation_counts` sorted in descending order.
+pub fn build_author_metrics(user_id: i64, citation_counts: &[i64]) -> AuthorMetrics {
+    AuthorMetrics {
         user_id,
-        g_index,
-        total_citations,
+        g_index: calculate_g_index(citation_counts),
+        h_index: calculate_h_index(citation_counts),
+        i10_index: calculate_i10_index(citation_counts),
+        total_citations: citation_counts.iter().sum::<i64>(),
         paper_count: citation_counts.len() as i64,
         formula: AUTHOR_G_INDEX_FORMULA.to_string(),
         metric_version: METRIC_VERSION.to_string(),
-    })
+    }
 }
 
 pub async fn compute_impact_factor(
     pool: &MySqlPool,
     year: i32,
 ) -> Result<JournalMetrics, sqlx::Error> {
-    let target_year = year;
-    let prev_year = year - 1;
-    let prev_prev_year = year - 2;
+    let numerator_citations = count_paper_citations(pool, year, year - 2, year - 1).await?;
+    let denominator_papers = count_citable_papers(pool, year - 2, year - 1).await?;
+    let numerator_citations_5y = count_paper_citations(pool, year, year - 5, year - 1).await?;
+    let denominator_papers_5y = count_citable_papers(pool, year - 5, year - 1).await?;
+    let immediacy_citations = count_paper_citations(pool, year, year, year).await?;
+    let immediacy_papers = count_citable_papers(pool, year, year).await?;
+    let cited_half_life = compute_cited_half_life(pool, year).await?;
+    let (reviewed_papers, accepted_papers) = count_review_decisions(pool, year).await?;
 
-    let (numerator_citations,): (i64,) = sqlx::query_as(
+    Ok(JournalMetrics {
+        year,
+        impact_factor: ratio(numerator_citations, denominator_papers),
+        numerator_citations,
+        denominator_papers,
+        impact_factor_5y: ratio(numerator_citations_5y, denominator_papers_5y),
+        numerator_citations_5y,
+        denominator_papers_5y,
+        immediacy_index: ratio(immediacy_citations, immediacy_papers),
+        immediacy_citations,
+        immediacy_papers,
+        cited_half_life,
+        acceptance_rate: ratio(accepted_papers, reviewed_papers),
+        accepted_papers,
+        reviewed_papers,
+        formula: JOURNAL_IMPACT_FORMULA.to_string(),
+        metric_version: METRIC_VERSION.to_string(),
+    })
+}
+
+fn ratio(numerator: i64, denominator: i64) -> Option<f64> {
+    if denominator > 0 {
+        Some(numerator as f64 / denominator as f64)
+    } else {
+        None
+    }
+}
+
+/// Paper-to-paper citations made in `citing_year` to papers published
+/// between `cited_from_year` and `cited_to_year` (inclusive). Edges flagged
+/// by a retraction do not count.
+async fn count_paper_citations(
+    pool: &MySqlPool,
+    citing_year: i32,
+    cited_from_year: i32,
+    cited_to_year: i32,
+) -> Result<i64, sqlx::Error> {
+    let (count,): (i64,) = sqlx::query_as(
         r#"
         SELECT COUNT(*)
         FROM (
             SELECT DISTINCT citing_post_id, cited_post_id
             FROM post_citations
+            WHERE retraction_flagged_at IS NULL
         ) pc
         JOIN posts citing ON citing.id = pc.citing_post_id
         JOIN post_categories citing_category ON citing_category.id = citing.category_id
@@ -100,44 +125,125 @@ pub async fn compute_impact_factor(
         JOIN post_categories cited_category ON cited_category.id = cited.category_id
         WHERE citing_category.code = 'paper'
           AND cited_category.code = 'paper'
+          AND citing.deleted_at IS NULL
+          AND cited.deleted_at IS NULL
           AND YEAR(citing.created_at) = ?
-          AND YEAR(cited.created_at) IN (?, ?)
+          AND YEAR(cited.created_at) BETWEEN ? AND ?
         "#,
     )
-    .bind(target_year)
-    .bind(prev_year.clone())
-    .bind(prev_prev_year.clone())
+    .bind(citing_year)
+    .bind(cited_from_year)
+    .bind(cited_to_year)
     .fetch_one(pool)
     .await?;
 
-    let (denominator_papers,): (i64,) = sqlx::query_as(
+    Ok(count)
+}
+
+/// Non-retracted papers published between `from_year` and `to_year`.
+async fn count_citable_papers(
+    pool: &MySqlPool,
+    from_year: i32,
+    to_year: i32,
+) -> Result<i64, sqlx::Error> {
+    let (count,): (i64,) = sqlx::query_as(
         r#"
         SELECT COUNT(*)
         FROM posts p
         JOIN post_categories c ON c.id = p.category_id
+        LEFT JOIN post_retractions pr ON pr.post_id = p.id
         WHERE c.code = 'paper'
-          AND YEAR(p.created_at) IN (?, ?)
+          AND p.deleted_at IS NULL
+          AND pr.post_id IS NULL
+          AND YEAR(p.created_at) BETWEEN ? AND ?
         "#,
     )
-    .bind(prev_year)
-    .bind(prev_prev_year)
+    .bind(from_year)
+    .bind(to_year)
     .fetch_one(pool)
     .await?;
 
-    let impact_factor = if denominator_papers > 0 {
-        Some(numerator_citations as f64 / denominator_papers as f64)
-    } else {
-        None
-    };
+    Ok(count)
+}
 
-    Ok(JournalMetrics {
-        year,
-        impact_factor,
-        numerator_citations,
-        denominator_papers,
-        formula: JOURNAL_IMPACT_FORMULA.to_string(),
-        metric_version: METRIC_VERSION.to_string(),
-    })
+/// Median age (in years) of the papers cited during `year`, interpolated
+/// within the age bucket that crosses the 50% mark.
+async fn compute_cited_half_life(pool: &MySqlPool, year: i32) -> Result<Option<f64>, sqlx::Error> {
+    let rows: Vec<(i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT CAST(YEAR(citing.created_at) - YEAR(cited.created_at) AS SIGNED) AS age, COUNT(*)
+        FROM (
+            SELECT DISTINCT citing_post_id, cited_post_id
+            FROM post_citations
+            WHERE retraction_flagged_at IS NULL
+        ) pc
+        JOIN posts citing ON citing.id = pc.citing_post_id
+        JOIN post_categories citing_category ON citing_category.id = citing.category_id
+        JOIN posts cited ON cited.id = pc.cited_post_id
+        JOIN post_categories cited_category ON cited_category.id = cited.category_id
+        WHERE citing_category.code = 'paper'
+          AND cited_category.code = 'paper'
+          AND citing.deleted_at IS NULL
+          AND cited.deleted_at IS NULL
+          AND YEAR(citing.created_at) = ?
+          AND YEAR(cited.created_at) <= ?
+        GROUP BY age
+        ORDER BY age ASC
+        "#,
+    )
+    .bind(year)
+    .bind(year)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(calculate_half_life(&rows))
+}
+
+fn calculate_half_life(citations_by_age: &[(i64, i64)]) -> Option<f64> {
+    let total = citations_by_age.iter().map(|(_, count)| *count).sum::<i64>();
+    if total == 0 {
+        return None;
+    }
+
+    let half = total as f64 / 2.0;
+    let mut cumulative = 0_i64;
+    for (age, count) in citations_by_age {
+        if (cumulative + count) as f64 >= half {
+            let within_bucket = (half - cumulative as f64) / *count as f64;
+            return Some(*age as f64 + within_bucket);
+        }
+        cumulative += count;
+    }
+
+    None
+}
+
+/// Papers whose latest completed AI review finished during `year`, and how
+/// many of those were accepted.
+async fn count_review_decisions(pool: &MySqlPool, year: i32) -> Result<(i64, i64), sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            COUNT(*),
+            CAST(COALESCE(SUM(CASE WHEN d.code = 'accept' THEN 1 ELSE 0 END), 0) AS SIGNED)
+        FROM post_ai_reviews r
+        JOIN ai_review_decisions d ON d.id = r.decision_id
+        JOIN posts p ON p.id = r.post_id
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE c.code = 'paper'
+          AND p.deleted_at IS NULL
+          AND r.status_id = 2
+          AND YEAR(r.completed_at) = ?
+          AND r.id = (
+              SELECT MAX(r2.id)
+              FROM post_ai_reviews r2
+              WHERE r2.post_id = r.post_id AND r2.status_id = 2 AND r2.decision_id IS NOT NULL
+          )
+        "#,
+    )
+    .bind(year)
+    .fetch_one(pool)
+    .await
 }
 
 async fn fetch_author_paper_citation_counts(
@@ -146,18 +252,11 @@ async fn fetch_author_paper_citation_counts(
 ) -> Result<Vec<i64>, sqlx::Error> {
     let rows: Vec<(i64,)> = sqlx::query_as(
         r#"
-        SELECT COALESCE(c.citation_count, 0) as citation_count
+        SELECT COALESCE(ps.citation_count, 0) as citation_count
         FROM posts p
         JOIN post_categories pc ON pc.id = p.category_id
-        LEFT JOIN (
-            SELECT cited_post_id, COUNT(*) as citation_count
-            FROM (
-                SELECT DISTINCT citing_post_id, cited_post_id
-                FROM post_citations
-            ) distinct_citations
-            GROUP BY cited_post_id
-        ) c ON c.cited_post_id = p.id
-        WHERE p.author_id = ? AND pc.code = 'paper'
+        LEFT JOIN post_stats ps ON ps.post_id = p.id
+        WHERE p.author_id = ? AND pc.code = 'paper' AND p.deleted_at IS NULL
         ORDER BY citation_count DESC, p.id ASC
         "#,
     )
@@ -182,3 +281,18 @@ fn calculate_g_index(citation_counts: &[i64]) -> i64 {
 
     g_index
 }
+
+fn calculate_h_index(citation_counts: &[i64]) -> i64 {
+    citation_counts
+        .iter()
+        .enumerate()
+        .take_while(|(idx, count)| **count > *idx as i64)
+        .count() as i64
+}
+
+fn calculate_i10_index(citation_counts: &[i64]) -> i64 {
+    citation_counts
+        .iter()
+        .filter(|count| **count >= I10_CITATION_THRESHOLD)
+        .count() as i64
+}
@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use chrono::{DateTime, DurationRound, NaiveDateTime, TimeDelta, Utc};
+use sqlx::MySqlPool;
+
+use crate::tasks;
+
+pub const DEFAULT_POST_STAT_ROLLUP_INTERVAL_SECS: u64 = 300;
+pub const DEFAULT_POST_STAT_HOURLY_RETENTION_DAYS: i64 = 30;
+
+pub const ROLLUP_GRANULARITY_HOUR: &str = "hour";
+pub const ROLLUP_GRANULARITY_DAY: &str = "day";
+
+/// Hourly rows back the trending score, so they are kept at least this long
+/// whatever `POST_STAT_HOURLY_RETENTION_DAYS` says.
+const MIN_HOURLY_RETENTION_DAYS: i64 = 3;
+const TRENDING_WINDOW_HOURS: i64 = 72;
+const TRENDING_HALF_LIFE_HOURS: f64 = 12.0;
+/// A like counts as this many views towards the trending score.
+const TRENDING_LIKE_WEIGHT: i64 = 3;
+
+/// What one rollup run wrote, for the job log.
+pub struct PostStatRollupRun {
+    pub since: Option<DateTime<Utc>>,
+    pub hourly_rows: u64,
+    pub daily_rows: u64,
+    pub pruned_rows: u64,
+}
+
+/// Starts the job that folds `post_events` into `post_stat_rollups` and
+/// refreshes `post_stats.trending_score` from them. Analytics, period
+/// leaderboards and the trending sort read the rollups, so their counts lag
+/// by up to one interval. Setting `POST_STAT_ROLLUP_INTERVAL_SECS=0`
+/// disables the job.
+pub fn spawn_post_stat_rollups(pool: MySqlPool) {
+    let interval_secs = rollup_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Post stat rollup job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "post_stat_rollups",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match refresh_post_stat_rollups(&pool).await {
+                    Ok(run) => tracing::debug!(
+                        "Post stat rollups refreshed since {:?}: hourly={}, daily={}, pruned={}",
+                        run.since,
+                        run.hourly_rows,
+                        run.daily_rows,
+                        run.pruned_rows
+                    ),
+                    Err(error) => tracing::error!("Post stat rollup failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Re-aggregates every hour from the latest hourly bucket on (or from the
+/// first event, on the first run), then the days those hours fall in. The
+/// latest bucket is redone because it was probably still filling last time,
+/// and the one before it to catch events committed just after that run.
+/// Rows are upserted, so a run can be repeated safely.
+///
+/// Like counters of posts liked or unliked in that span are recounted from
+/// `post_likes`, since the like toggle only adjusts them by one.
+pub async fn refresh_post_stat_rollups(pool: &MySqlPool) -> Result<PostStatRollupRun, sqlx::Error> {
+    let now = Utc::now();
+    let since = rollup_start(pool).await?;
+    let mut run = PostStatRollupRun {
+        since,
+        hourly_rows: 0,
+        daily_rows: 0,
+        pruned_rows: 0,
+    };
+
+    let mut tx = pool.begin().await?;
+    if let Some(since) = since {
+        run.hourly_rows = sqlx::query(
+            r#"
+            INSERT INTO post_stat_rollups (post_id, granularity, bucket_start, views, likes, unlikes, updated_at)
+            SELECT
+                post_id,
+                ?,
+                DATE_FORMAT(created_at, '%Y-%m-%d %H:00:00') AS bucket,
+                SUM(event_type = 'view'),
+                SUM(event_type = 'like'),
+                SUM(event_type = 'unlike'),
+                ?
+            FROM post_events
+            WHERE created_at >= ?
+            GROUP BY post_id, bucket
+            ON DUPLICATE KEY UPDATE
+                views = VALUES(views),
+                likes = VALUES(likes),
+                unlikes = VALUES(unlikes),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(ROLLUP_GRANULARITY_HOUR)
+        .bind(now)
+        .bind(since)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        run.daily_rows = sqlx::query(
+            r#"
+            INSERT INTO post_stat_rollups (post_id, granularity, bucket_start, views, likes, unlikes, updated_at)
+            SELECT post_id, ?, DATE(bucket_start) AS bucket, SUM(views), SUM(likes), SUM(unlikes), ?
+            FROM post_stat_rollups
+            WHERE granularity = ? AND bucket_start >= ?
+            GROUP BY post_id, bucket
+            ON DUPLICATE KEY UPDATE
+                views = VALUES(views),
+                likes = VALUES(likes),
+                unlikes = VALUES(unlikes),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(ROLLUP_GRANULARITY_DAY)
+        .bind(now)
+        .bind(ROLLUP_GRANULARITY_HOUR)
+        .bind(since.date_naive())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query(
+            r#"
+            UPDATE post_stats ps
+            SET like_count = (SELECT COUNT(*) FROM post_likes pl WHERE pl.post_id = ps.post_id)
+            WHERE ps.post_id IN (
+                SELECT post_id FROM post_events WHERE event_type IN ('like', 'unlike') AND created_at >= ?
+            )
+            "#,
+        )
+        .bind(since)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    refresh_trending_scores(pool, now).await?;
+
+    run.pruned_rows =
+        sqlx::query("DELETE FROM post_stat_rollups WHERE granularity = ? AND bucket_start < ?")
+            .bind(ROLLUP_GRANULARITY_HOUR)
+            .bind(now - TimeDelta::days(hourly_retention_days()))
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+    Ok(run)
+}
+
+/// One hour before the latest hourly bucket, or the hour of the first event
+/// when nothing has been rolled up yet. `None` when there are no events.
+async fn rollup_start(pool: &MySqlPool) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let latest_bucket: Option<NaiveDateTime> =
+        sqlx::query_scalar("SELECT MAX(bucket_start) FROM post_stat_rollups WHERE granularity = ?")
+            .bind(ROLLUP_GRANULARITY_HOUR)
+            .fetch_one(pool)
+            .await?;
+    if let Some(latest_bucket) = latest_bucket {
+        return Ok(Some(latest_bucket.and_utc() - TimeDelta::hours(1)));
+    }
+
+    let first_event: Option<DateTime<Utc>> =
+        sqlx::query_scalar("SELECT MIN(created_at) FROM post_events")
+            .fetch_one(pool)
+            .await?;
+    Ok(first_event.map(|created_at| {
+        created_at
+            .duration_trunc(TimeDelta::hours(1))
+            .unwrap_or(created_at)
+    }))
+}
+
+/// Scores each post by its views and net likes over the last
+/// `TRENDING_WINDOW_HOURS`, halving the weight of an hour every
+/// `TRENDING_HALF_LIFE_HOURS`. Posts with no recent activity drop to 0.
+async fn refresh_trending_scores(pool: &MySqlPool, now: DateTime<Utc>) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE post_stats SET trending_score = 0 WHERE trending_score <> 0")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO post_stats (post_id, view_count, like_count, trending_score, updated_at)
+        SELECT
+            post_id,
+            0,
+            0,
+            SUM(GREATEST(views + ? * (likes - unlikes), 0) * POW(0.5, TIMESTAMPDIFF(HOUR, bucket_start, ?) / ?)),
+            ?
+        FROM post_stat_rollups
+        WHERE granularity = ? AND bucket_start >= ?
+        GROUP BY post_id
+        ON DUPLICATE KEY UPDATE trending_score = VALUES(trending_score), updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(TRENDING_LIKE_WEIGHT)
+    .bind(now)
+    .bind(TRENDING_HALF_LIFE_HOURS)
+    .bind(now)
+    .bind(ROLLUP_GRANULARITY_HOUR)
+    .bind(now - TimeDelta::hours(TRENDING_WINDOW_HOURS))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+fn rollup_interval_secs() -> u64 {
+    std::env::var("POST_STAT_ROLLUP_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_POST_STAT_ROLLUP_INTERVAL_SECS)
+}
+
+fn hourly_retention_days() -> i64 {
+    std::env::var("POST_STAT_HOURLY_RETENTION_DAYS")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_POST_STAT_HOURLY_RETENTION_DAYS)
+        .max(MIN_HOURLY_RETENTION_DAYS)
+}
@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use sqlx::MySqlPool;
+
+use crate::tasks;
+
+use super::{
+    compute_impact_factor, fetch_cached_author_metrics_list, rebuild_author_metrics_cache,
+};
+
+pub const DEFAULT_METRIC_SNAPSHOT_CHECK_INTERVAL_SECS: u64 = 3_600;
+
+pub const SNAPSHOT_SCOPE_AUTHOR: &str = "author";
+pub const SNAPSHOT_SCOPE_JOURNAL: &str = "journal";
+
+/// Starts the job that stores one metrics snapshot per UTC day. The job
+/// wakes up every `METRIC_SNAPSHOT_CHECK_INTERVAL_SECS` and only snapshots
+/// when today's rows are missing, so restarts do not create duplicates.
+/// Setting the interval to `0` disables the job.
+pub fn spawn_metric_snapshots(pool: MySqlPool) {
+    let interval_secs = check_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Metric snapshot job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "metric_snapshots",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                let today = Utc::now().date_naive();
+                match snapshot_exists(&pool, today).await {
+                    Ok(true) => return,
+                    Ok(false) => {}
+                    Err(error) => {
+                        tracing::error!("Failed to check metric snapshots: {}", error);
+                        return;
+                    }
+                }
+
+                match take_metric_snapshots(&pool, today).await {
+                    Ok(author_count) => tracing::info!(
+                        "Metric snapshot for {} stored: authors={}",
+                        today,
+                        author_count
+                    ),
+                    Err(error) => {
+                        tracing::error!("Metric snapshot for {} failed: {}", today, error)
+                    }
+                }
+            }
+        },
+    );
+}
+
+async fn snapshot_exists(pool: &MySqlPool, snapshot_date: NaiveDate) -> Result<bool, sqlx::Error> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM metric_snapshots WHERE scope = ? AND snapshot_date = ?",
+    )
+    .bind(SNAPSHOT_SCOPE_JOURNAL)
+    .bind(snapshot_date)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count > 0)
+}
+
+/// Rebuilds the author metrics cache, then stores the metrics of every user
+/// with at least one paper and the journal impact factor of the snapshot
+/// year, replacing any rows already stored for `snapshot_date`. Returns the
+/// number of author snapshots.
+pub async fn take_metric_snapshots(
+    pool: &MySqlPool,
+    snapshot_date: NaiveDate,
+) -> Result<usize, sqlx::Error> {
+    rebuild_author_metrics_cache(pool).await?;
+    let author_metrics = fetch_cached_author_metrics_list(pool).await?;
+    let journal_metrics = compute_impact_factor(pool, snapshot_date.year()).await?;
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM metric_snapshots WHERE snapshot_date = ?")
+        .bind(snapshot_date)
+        .execute(&mut *tx)
+        .await?;
+
+    for metrics in &author_metrics {
+        sqlx::query(
+            r#"
+            INSERT INTO metric_snapshots (
+                scope,
+                user_id,
+                snapshot_date,
+                g_index,
+                h_index,
+                i10_index,
+                total_citations,
+                paper_count,
+                metric_version,
+                created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(SNAPSHOT_SCOPE_AUTHOR)
+        .bind(metrics.user_id)
+        .bind(snapshot_date)
+        .bind(metrics.g_index)
+        .bind(metrics.h_index)
+        .bind(metrics.i10_index)
+        .bind(metrics.total_citations)
+        .bind(metrics.paper_count)
+        .bind(&metrics.metric_version)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO metric_snapshots (
+            scope,
+            snapshot_date,
+            journal_year,
+            impact_factor,
+            numerator_citations,
+            denominator_papers,
+            metric_version,
+            created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(SNAPSHOT_SCOPE_JOURNAL)
+    .bind(snapshot_date)
+    .bind(journal_metrics.year)
+    .bind(journal_metrics.impact_factor)
+    .bind(journal_metrics.numerator_citations)
+    .bind(journal_metrics.denominator_papers)
+    .bind(&journal_metrics.metric_version)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(author_metrics.len())
+}
+
+fn check_interval_secs() -> u64 {
+    std::env::var("METRIC_SNAPSHOT_CHECK_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_METRIC_SNAPSHOT_CHECK_INTERVAL_SECS)
+}
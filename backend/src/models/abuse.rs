@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const REQUEST_EVENT_AUTH: &str = "auth";
+pub const REQUEST_EVENT_WRITE: &str = "write";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct RequestEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub method: String,
+    /// The route pattern, e.g. `/api/posts/{post_id}/comments`.
+    pub path: String,
+    pub status_code: i32,
+    pub user_id: Option<i64>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RequestEventListResponse {
+    pub events: Vec<RequestEvent>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+/// An address used by several accounts or with repeated failed sign-ins.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SuspiciousIp {
+    pub ip_address: String,
+    pub event_count: i64,
+    pub account_count: i64,
+    pub registration_count: i64,
+    pub failed_auth_count: i64,
+    pub user_agent_count: i64,
+    /// Signed-in accounts seen from the address.
+    pub user_ids: Vec<i64>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub is_blocked: bool,
+}
+
+/// A user whose busiest minute had at least the report's write threshold.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct RapidSubmitter {
+    pub user_id: i64,
+    pub username: String,
+    pub write_count: i64,
+    pub peak_per_minute: i64,
+    pub ip_count: i64,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AbuseReport {
+    pub generated_at: DateTime<Utc>,
+    pub window_hours: i64,
+    pub shared_ips: Vec<SuspiciousIp>,
+    pub rapid_submitters: Vec<RapidSubmitter>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct IpBlock {
+    pub id: i64,
+    pub ip_address: String,
+    pub reason: Option<String>,
+    pub blocked_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    /// Permanent when empty.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IpBlockListResponse {
+    pub blocks: Vec<IpBlock>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateIpBlock {
+    pub ip_address: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const ACCOUNT_EVENT_LOGIN: &str = "login";
+pub const ACCOUNT_EVENT_LOGIN_FAILED: &str = "login_failed";
+pub const ACCOUNT_EVENT_USERNAME_CHANGE: &str = "username_change";
+
+pub const AUTH_METHOD_PASSWORD: &str = "password";
+pub const AUTH_METHOD_GOOGLE: &str = "google";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AccountEvent {
+    pub id: i64,
+    pub event_type: String,
+    /// `password` or `google` for sign-ins; absent for other events.
+    pub auth_method: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// A short browser and OS label derived from `user_agent`.
+    #[sqlx(skip)]
+    pub device: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountActivityResponse {
+    pub events: Vec<AccountEvent>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
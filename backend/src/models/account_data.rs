@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use super::UserResponse;
+
+pub const ERASURE_STATUS_PENDING: &str = "pending";
+pub const ERASURE_STATUS_APPROVED: &str = "approved";
+pub const ERASURE_STATUS_REJECTED: &str = "rejected";
+
+/// Everything `GET /api/users/me/export` hands back about the caller.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountDataExport {
+    pub exported_at: DateTime<Utc>,
+    pub profile: UserResponse,
+    pub posts: Vec<ExportedPost>,
+    pub comments: Vec<ExportedComment>,
+    pub review_comments: Vec<ExportedReviewComment>,
+    pub reviewer_assignments: Vec<ExportedReviewerAssignment>,
+    pub likes: Vec<ExportedLike>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ExportedPost {
+    pub id: i64,
+    pub title: String,
+    pub content: String,
+    pub summary: Option<String>,
+    pub github_url: Option<String>,
+    pub category: String,
+    pub paper_status: String,
+    pub is_published: bool,
+    pub is_preprint: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub file_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ExportedComment {
+    pub id: i64,
+    pub post_id: i64,
+    pub post_title: String,
+    pub parent_comment_id: Option<i64>,
+    pub content: String,
+    pub is_deleted: bool,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ExportedReviewComment {
+    pub id: i64,
+    pub post_id: i64,
+    pub post_title: String,
+    pub paper_version_id: Option<i64>,
+    pub parent_comment_id: Option<i64>,
+    pub content: String,
+    pub is_anonymous: bool,
+    pub is_deleted: bool,
+    pub resolution_status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ExportedReviewerAssignment {
+    pub id: i64,
+    pub post_id: i64,
+    pub post_title: String,
+    pub status: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ExportedLike {
+    pub post_id: i64,
+    pub post_title: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ErasureRequestResponse {
+    pub id: i64,
+    pub user_id: i64,
+    pub username: String,
+    pub reason: Option<String>,
+    pub status: String,
+    pub reviewed_by: Option<i64>,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ErasureRequestListResponse {
+    pub requests: Vec<ErasureRequestResponse>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateErasureRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ReviewErasureRequest {
+    pub note: Option<String>,
+}
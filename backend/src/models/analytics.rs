@@ -0,0 +1,39 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct DailyPostActivity {
+    pub date: NaiveDate,
+    pub views: i64,
+    pub likes: i64,
+    pub unlikes: i64,
+    pub comments: i64,
+    pub citations: i64,
+    pub cumulative_citations: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReferrerCount {
+    pub referrer: String,
+    pub views: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct PostAnalyticsTotals {
+    pub views: i64,
+    pub likes: i64,
+    pub unlikes: i64,
+    pub comments: i64,
+    pub citations: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostAnalyticsResponse {
+    pub post_id: i64,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub totals: PostAnalyticsTotals,
+    pub daily: Vec<DailyPostActivity>,
+    pub referrers: Vec<ReferrerCount>,
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const ANNOUNCEMENT_SEVERITY_INFO: &str = "info";
+pub const ANNOUNCEMENT_SEVERITY_WARNING: &str = "warning";
+pub const ANNOUNCEMENT_SEVERITY_CRITICAL: &str = "critical";
+
+pub const ANNOUNCEMENT_AUDIENCE_ALL: &str = "all";
+pub const ANNOUNCEMENT_AUDIENCE_USERS: &str = "users";
+pub const ANNOUNCEMENT_AUDIENCE_ADMINS: &str = "admins";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Announcement {
+    pub id: i64,
+    pub message: String,
+    pub severity: String,
+    pub audience: String,
+    pub starts_at: DateTime<Utc>,
+    /// Shown until removed when empty.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub created_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AnnouncementListResponse {
+    pub announcements: Vec<Announcement>,
+    pub total: i64,
+}
+
+/// `starts_at` defaults to now, `severity` to `info` and `audience` to
+/// `all`.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateAnnouncement {
+    pub message: String,
+    pub severity: Option<String>,
+    pub audience: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateAnnouncement {
+    pub message: Option<String>,
+    pub severity: Option<String>,
+    pub audience: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const APPEAL_STATUS_PENDING: &str = "pending";
+pub const APPEAL_STATUS_UPHELD: &str = "upheld";
+pub const APPEAL_STATUS_OVERTURNED: &str = "overturned";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PaperAppealResponse {
+    pub id: i64,
+    pub post_id: i64,
+    pub post_title: String,
+    pub paper_version_id: Option<i64>,
+    pub version_number: Option<i32>,
+    pub author_id: i64,
+    pub author_username: String,
+    pub justification: String,
+    pub status: String,
+    /// Fresh AI review an editor requested with the appeal attached.
+    pub ai_review_id: Option<i64>,
+    pub resolved_by: Option<i64>,
+    pub resolution_note: Option<String>,
+    /// Paper status after an overturned appeal.
+    pub resulting_status: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaperAppealListResponse {
+    pub appeals: Vec<PaperAppealResponse>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreatePaperAppeal {
+    pub justification: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AppealOutcome {
+    Uphold,
+    Overturn,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ResolvePaperAppeal {
+    pub outcome: AppealOutcome,
+    pub note: Option<String>,
+    /// Status the paper returns to when overturned: `submitted` (default),
+    /// `revision` or `accepted`.
+    pub paper_status: Option<String>,
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// One named background task, aggregated over all of its runs since the
+/// server started.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackgroundTaskStatus {
+    pub name: String,
+    /// Scheduled at a fixed interval, rather than started by requests.
+    pub periodic: bool,
+    pub interval_secs: Option<u64>,
+    /// `running`, `idle`, or `stopped` once shutdown has ended its schedule.
+    pub state: String,
+    /// Runs in progress right now.
+    pub running: u32,
+    pub runs: u64,
+    pub panics: u64,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_panic: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackgroundTaskListResponse {
+    pub shutting_down: bool,
+    pub tasks: Vec<BackgroundTaskStatus>,
+}
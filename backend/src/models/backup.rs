@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const BACKUP_STATUS_RUNNING: &str = "running";
+pub const BACKUP_STATUS_COMPLETED: &str = "completed";
+pub const BACKUP_STATUS_FAILED: &str = "failed";
+/// The file was removed by retention; the row is kept as history.
+pub const BACKUP_STATUS_EXPIRED: &str = "expired";
+
+pub const BACKUP_TRIGGER_SCHEDULED: &str = "scheduled";
+pub const BACKUP_TRIGGER_MANUAL: &str = "manual";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DatabaseBackup {
+    pub id: i64,
+    /// Name of the dump inside `BACKUP_DIR`.
+    pub file_name: String,
+    pub status: String,
+    pub trigger_source: String,
+    pub size_bytes: Option<i64>,
+    pub error_message: Option<String>,
+    pub started_by: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DatabaseBackupListResponse {
+    pub backups: Vec<DatabaseBackup>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const BROADCAST_STATUS_SCHEDULED: &str = "scheduled";
+pub const BROADCAST_STATUS_SENDING: &str = "sending";
+pub const BROADCAST_STATUS_SENT: &str = "sent";
+pub const BROADCAST_STATUS_CANCELLED: &str = "cancelled";
+
+pub const BROADCAST_RECIPIENT_STATUS_PENDING: &str = "pending";
+pub const BROADCAST_RECIPIENT_STATUS_SENT: &str = "sent";
+pub const BROADCAST_RECIPIENT_STATUS_FAILED: &str = "failed";
+
+/// A broadcast with its delivery progress. Recipients are fixed when
+/// sending starts, so `recipient_count` is 0 while it is still scheduled.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Broadcast {
+    pub id: i64,
+    pub subject: String,
+    pub body: String,
+    pub audience: String,
+    pub active_within_days: Option<i64>,
+    pub status: String,
+    pub scheduled_at: DateTime<Utc>,
+    pub recipient_count: i64,
+    pub pending_count: i64,
+    pub sent_count: i64,
+    pub failed_count: i64,
+    pub created_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BroadcastListResponse {
+    pub broadcasts: Vec<Broadcast>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct BroadcastRecipient {
+    pub user_id: i64,
+    pub username: String,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BroadcastRecipientListResponse {
+    pub recipients: Vec<BroadcastRecipient>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+/// `subject` and `body` may use `{{username}}` and `{{display_name}}`,
+/// filled in per recipient. `audience` defaults to `all`; with
+/// `active_within_days`, only users who posted, commented or made any
+/// other change within that many days are included. Without `scheduled_at`
+/// the broadcast goes out on the next run of the delivery job.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateBroadcast {
+    pub subject: String,
+    pub body: String,
+    pub audience: Option<String>,
+    pub active_within_days: Option<i64>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// Only report who would receive it and how it renders.
+    #[serde(default)]
+    pub preview: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BroadcastPreview {
+    pub recipient_count: i64,
+    /// The first few recipients, by user id.
+    pub sample_recipients: Vec<String>,
+    /// The notification as the first recipient (or the caller, when nobody
+    /// matches) would see it.
+    pub rendered_message: String,
+}
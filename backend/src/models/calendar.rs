@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Returned once when a calendar feed token is created; only its hash is
+/// stored, so a lost token has to be rotated.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CalendarFeedTokenResponse {
+    pub token: String,
+    /// The feed path with the token, for calendar apps to subscribe to.
+    pub feed_path: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CalendarFeedStatus {
+    pub enabled: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ExternalCitation {
+    pub id: i64,
+    pub cited_post_id: i64,
+    pub source: String,
+    pub external_work_id: String,
+    pub citing_doi: Option<String>,
+    pub citing_title: Option<String>,
+    pub citing_venue: Option<String>,
+    pub citing_year: Option<i32>,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ExternalCitationListResponse {
+    pub post_id: i64,
+    pub registered_doi: Option<String>,
+    pub synced_at: Option<DateTime<Utc>>,
+    pub citations: Vec<ExternalCitation>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PostDoiRegistration {
+    pub post_id: i64,
+    pub internal_doi: String,
+    pub registered_doi: String,
+    pub registration_agency: String,
+    pub openalex_work_id: Option<String>,
+    pub external_cited_by_count: Option<i64>,
+    pub citations_synced_at: Option<DateTime<Utc>>,
+    pub last_sync_error: Option<String>,
+    pub registered_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Automatic DataCite registration of a published paper's DOI. Deposits are
+/// retried with backoff until they succeed or run out of attempts.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PostDoiDeposit {
+    pub post_id: i64,
+    pub doi: String,
+    pub registration_agency: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub registered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostDoiDepositListResponse {
+    pub deposits: Vec<PostDoiDeposit>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdatePostCitations {
+    #[serde(default)]
+    pub post_ids: Vec<i64>,
+    #[serde(default)]
+    pub dois: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CitationEdge {
+    pub cited_post_id: i64,
+    pub title: String,
+    pub source: String,
+    pub retraction_flagged: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostCitationsResponse {
+    pub post_id: i64,
+    pub citations: Vec<CitationEdge>,
+    pub external_references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct CitationChangeSet {
+    pub added_post_ids: Vec<i64>,
+    pub removed_post_ids: Vec<i64>,
+    pub added_dois: Vec<String>,
+    pub removed_dois: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UpdatePostCitationsResponse {
+    pub changes: CitationChangeSet,
+    pub current: PostCitationsResponse,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CitationAuditEntry {
+    pub id: i64,
+    pub post_id: i64,
+    pub actor_id: Option<i64>,
+    pub action: String,
+    pub cited_post_id: Option<i64>,
+    pub cited_doi: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CitationAuditListResponse {
+    pub entries: Vec<CitationAuditEntry>,
+    pub total: i64,
+    pub limit: i32,
+    pub offset: i32,
+}
@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
 
 use crate::models::UserResponse;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Comment {
     pub id: i64,
     pub post_id: i64,
@@ -13,26 +15,125 @@ pub struct Comment {
     pub content: String,
     pub is_deleted: bool,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub edited_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CommentMention {
+    pub user_id: i64,
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CommentResponse {
     pub id: i64,
     pub post_id: i64,
     pub author_id: i64,
     pub parent_comment_id: Option<i64>,
+    /// Nesting level below the top-level comment, which is 0.
+    pub depth: i32,
     pub author: UserResponse,
     pub content: String,
+    /// Sanitized HTML rendering of `content`, only present for `render=html`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_html: Option<String>,
     pub is_deleted: bool,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub edited_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub mentions: Vec<CommentMention>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    /// Number of replies at any depth below this comment.
+    pub reply_count: i64,
+    #[schema(no_recursion)]
+    pub replies: Vec<CommentThread>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentListResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<Vec<CommentResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<Vec<CommentThread>>,
+    pub total: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_threads: Option<i64>,
+    pub limit: i32,
+    pub offset: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CreateComment {
+    #[validate(
+        custom(
+            function = "crate::validation::not_blank",
+            message = "content is required"
+        ),
+        length(max = 10_000, message = "content must be at most 10000 characters")
+    )]
     pub content: String,
     pub parent_comment_id: Option<i64>,
 }
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct UpdateComment {
+    #[validate(
+        custom(
+            function = "crate::validation::not_blank",
+            message = "content is required"
+        ),
+        length(max = 10_000, message = "content must be at most 10000 characters")
+    )]
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct CreateCommentReport {
+    #[validate(
+        custom(
+            function = "crate::validation::not_blank",
+            message = "reason is required"
+        ),
+        length(max = 1000, message = "reason must be at most 1000 characters")
+    )]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CommentReportResponse {
+    pub id: i64,
+    /// `None` once the reported comment has been removed.
+    pub comment_id: Option<i64>,
+    pub post_id: i64,
+    pub comment_author_id: Option<i64>,
+    pub comment_author_username: Option<String>,
+    pub reporter_id: Option<i64>,
+    pub reporter_username: Option<String>,
+    pub comment_excerpt: String,
+    pub reason: String,
+    pub status: String,
+    /// Open reports currently filed against the same comment.
+    pub open_report_count: i64,
+    pub comment_deleted: bool,
+    pub author_banned: bool,
+    pub resolved_by: Option<i64>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentReportListResponse {
+    pub reports: Vec<CommentReportResponse>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
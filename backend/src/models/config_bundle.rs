@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Bumped when the bundle layout changes incompatibly.
+pub const CONFIG_BUNDLE_FORMAT_VERSION: i32 = 1;
+
+/// Journal configuration that can be moved between environments. Settings
+/// and feature flags list only admin overrides, so importing a bundle also
+/// drops overrides it does not contain. AI review prompts are built into
+/// the server; `ai_review_prompt_version` only records which one was in use.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigBundle {
+    pub format_version: i32,
+    pub exported_at: DateTime<Utc>,
+    pub ai_review_prompt_version: String,
+    pub settings: Vec<ConfigBundleSetting>,
+    pub feature_flags: Vec<ConfigBundleFeatureFlag>,
+    pub categories: Vec<ConfigBundleCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigBundleSetting {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigBundleFeatureFlag {
+    pub key: String,
+    pub enabled: bool,
+    pub rollout_percent: i32,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigBundleCategory {
+    pub code: String,
+    pub display_name: String,
+    pub is_active: bool,
+}
+
+/// Keys and codes an import changed, or would change on a dry run.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ConfigImportResult {
+    pub dry_run: bool,
+    pub settings_updated: Vec<String>,
+    pub settings_reset: Vec<String>,
+    pub feature_flags_updated: Vec<String>,
+    pub feature_flags_reset: Vec<String>,
+    pub categories_created: Vec<String>,
+    pub categories_updated: Vec<String>,
+    pub warnings: Vec<String>,
+}
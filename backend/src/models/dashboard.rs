@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// A post of the user's that is not public yet.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DashboardDraft {
+    pub id: i64,
+    pub title: String,
+    pub category: String,
+    pub paper_status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DashboardStatusCount {
+    pub paper_status: String,
+    pub count: i64,
+}
+
+/// A comment someone else left on one of the user's posts.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DashboardComment {
+    pub comment_id: i64,
+    pub post_id: i64,
+    pub post_title: String,
+    pub author_username: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Totals across every post the user has not deleted.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DashboardTotals {
+    pub post_count: i64,
+    pub view_count: i64,
+    pub like_count: i64,
+    pub citation_count: i64,
+    pub external_citation_count: i64,
+}
+
+/// A paper sent back for revision, with the due date of the latest decision.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DashboardRevision {
+    pub post_id: i64,
+    pub title: String,
+    pub revision_due_at: Option<DateTime<Utc>>,
+}
+
+/// An open review assignment of the user's.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DashboardReviewAssignment {
+    pub assignment_id: i64,
+    pub post_id: i64,
+    pub title: String,
+    pub status: String,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DashboardPendingActions {
+    pub revisions_due: Vec<DashboardRevision>,
+    pub reviews_assigned: Vec<DashboardReviewAssignment>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserDashboardResponse {
+    pub drafts: Vec<DashboardDraft>,
+    pub submissions_by_status: Vec<DashboardStatusCount>,
+    pub recent_comments: Vec<DashboardComment>,
+    pub totals: DashboardTotals,
+    pub pending_actions: DashboardPendingActions,
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::AiReviewDecision;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateEditorialDecision {
+    pub decision: AiReviewDecision,
+    /// Defaults to the latest completed AI review of the current version.
+    pub ai_review_id: Option<i64>,
+    /// Defaults to every submitted reviewer assignment on the paper.
+    pub reviewer_assignment_ids: Option<Vec<i64>>,
+    pub note: Option<String>,
+    /// Deadline for the author's revision. Only valid for revision decisions.
+    pub revision_due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EditorialDecisionResponse {
+    pub id: i64,
+    pub post_id: i64,
+    pub paper_version_id: Option<i64>,
+    pub version_number: Option<i32>,
+    pub editor_id: Option<i64>,
+    pub editor_username: Option<String>,
+    pub decision: AiReviewDecision,
+    pub ai_review_id: Option<i64>,
+    pub reviewer_assignment_ids: Vec<i64>,
+    pub previous_status: String,
+    pub paper_status: String,
+    pub note: Option<String>,
+    pub revision_due_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EditorialDecisionListResponse {
+    pub decisions: Vec<EditorialDecisionResponse>,
+    pub total: i64,
+}
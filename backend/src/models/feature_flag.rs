@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A feature flag as shown to admins. `id` is empty while the flag runs on
+/// its built-in default.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FeatureFlag {
+    pub id: Option<i64>,
+    pub key: String,
+    pub description: String,
+    pub enabled: bool,
+    pub rollout_percent: i32,
+    /// Roles the flag is limited to; empty means every role.
+    pub roles: Vec<String>,
+    pub default_enabled: bool,
+    pub updated_by: Option<i64>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeatureFlagListResponse {
+    pub flags: Vec<FeatureFlag>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateFeatureFlag {
+    pub enabled: bool,
+    pub rollout_percent: Option<i32>,
+    pub roles: Option<Vec<String>>,
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Result of one dependency check behind `GET /health/ready`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyCheck {
+    pub name: String,
+    /// `ok` or `error`.
+    pub status: String,
+    /// A failing required check makes the instance not ready; optional
+    /// ones only degrade it.
+    pub required: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessReport {
+    /// `ready`, `degraded` when only optional checks fail, or `not_ready`.
+    pub status: String,
+    pub checked_at: DateTime<Utc>,
+    pub checks: Vec<DependencyCheck>,
+}
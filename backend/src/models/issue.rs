@@ -0,0 +1,45 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct IssueResponse {
+    pub id: i64,
+    pub volume: i32,
+    pub number: i32,
+    pub title: Option<String>,
+    pub publication_date: Option<NaiveDate>,
+    /// Published papers in the issue.
+    pub paper_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IssueListResponse {
+    pub issues: Vec<IssueResponse>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateIssue {
+    pub volume: i32,
+    pub number: i32,
+    pub title: Option<String>,
+    pub publication_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateIssue {
+    pub title: Option<String>,
+    pub publication_date: Option<NaiveDate>,
+}
+
+/// Volume and number shown next to a post's own DOI.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct IssueCitation {
+    pub issue_id: i64,
+    pub volume: i32,
+    pub number: i32,
+}
@@ -1,27 +1,148 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostMetrics {
     pub citation_count: i64,
+    pub external_citation_count: i64,
+    pub influence_score: f64,
     pub metric_version: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AuthorMetrics {
     pub user_id: i64,
     pub g_index: i64,
+    pub h_index: i64,
+    pub i10_index: i64,
     pub total_citations: i64,
     pub paper_count: i64,
     pub formula: String,
     pub metric_version: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct JournalMetrics {
     pub year: i32,
     pub impact_factor: Option<f64>,
     pub numerator_citations: i64,
     pub denominator_papers: i64,
+    pub impact_factor_5y: Option<f64>,
+    pub numerator_citations_5y: i64,
+    pub denominator_papers_5y: i64,
+    pub immediacy_index: Option<f64>,
+    pub immediacy_citations: i64,
+    pub immediacy_papers: i64,
+    pub cited_half_life: Option<f64>,
+    pub acceptance_rate: Option<f64>,
+    pub accepted_papers: i64,
+    pub reviewed_papers: i64,
     pub formula: String,
     pub metric_version: String,
 }
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AuthorMetricsSnapshot {
+    pub snapshot_date: NaiveDate,
+    pub g_index: i64,
+    pub h_index: i64,
+    pub i10_index: i64,
+    pub total_citations: i64,
+    pub paper_count: i64,
+    pub metric_version: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuthorMetricsHistoryResponse {
+    pub user_id: i64,
+    pub snapshots: Vec<AuthorMetricsSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct JournalMetricsSnapshot {
+    pub snapshot_date: NaiveDate,
+    pub year: i32,
+    pub impact_factor: Option<f64>,
+    pub numerator_citations: i64,
+    pub denominator_papers: i64,
+    pub metric_version: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JournalMetricsHistoryResponse {
+    pub snapshots: Vec<JournalMetricsSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuthorLeaderboardEntry {
+    pub rank: i64,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub g_index: i64,
+    pub h_index: i64,
+    pub i10_index: i64,
+    pub total_citations: i64,
+    pub period_citations: i64,
+    pub paper_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaperLeaderboardEntry {
+    pub rank: i64,
+    pub post_id: i64,
+    pub title: String,
+    pub author_id: i64,
+    pub author_username: String,
+    pub citations: i64,
+    pub views: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LeaderboardResponse {
+    pub board: String,
+    pub metric: String,
+    pub period: String,
+    pub since: Option<NaiveDate>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authors: Option<Vec<AuthorLeaderboardEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub papers: Option<Vec<PaperLeaderboardEntry>>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct AiScoreAverages {
+    pub overall: Option<f64>,
+    pub novelty: Option<f64>,
+    pub methodology: Option<f64>,
+    pub clarity: Option<f64>,
+    pub citation_integrity: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CategoryMetrics {
+    pub category: String,
+    pub display_name: String,
+    pub submissions: i64,
+    pub published_posts: i64,
+    pub retracted_posts: i64,
+    pub reviewed_posts: i64,
+    pub accepted_posts: i64,
+    pub acceptance_rate: Option<f64>,
+    pub average_ai_scores: AiScoreAverages,
+    pub total_citations: i64,
+    pub citations_per_post: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CategoryMetricsResponse {
+    pub year: Option<i32>,
+    pub categories: Vec<CategoryMetrics>,
+    pub metric_version: String,
+}
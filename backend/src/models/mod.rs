@@ -1,15 +1,81 @@
+pub mod abuse;
+pub mod account_activity;
+pub mod account_data;
+pub mod analytics;
+pub mod announcement;
+pub mod appeal;
+pub mod audit_log;
+pub mod background_task;
+pub mod backup;
+pub mod broadcast;
+pub mod calendar;
+pub mod citation;
 pub mod comment;
+pub mod config_bundle;
+pub mod dashboard;
+pub mod editorial_decision;
+pub mod feature_flag;
+pub mod health;
+pub mod issue;
 pub mod metrics;
+pub mod notification;
+pub mod orcid;
+pub mod outgoing_webhook;
+pub mod paper_timeline;
 pub mod paper_version;
 pub mod post;
+pub mod post_category;
+pub mod profile;
+pub mod repository_deposit;
 pub mod review_comment;
 pub mod review;
+pub mod review_backfill;
+pub mod reviewer_assignment;
+pub mod revision_expiry;
+pub mod scholar_meta;
+pub mod status_transition;
+pub mod system_setting;
+pub mod system_usage;
+pub mod trash;
 pub mod user;
 
+pub use abuse::*;
+pub use account_activity::*;
+pub use account_data::*;
+pub use analytics::*;
+pub use announcement::*;
+pub use appeal::*;
+pub use audit_log::*;
+pub use background_task::*;
+pub use backup::*;
+pub use broadcast::*;
+pub use calendar::*;
+pub use citation::*;
 pub use comment::*;
+pub use config_bundle::*;
+pub use dashboard::*;
+pub use editorial_decision::*;
+pub use feature_flag::*;
+pub use health::*;
+pub use issue::*;
 pub use metrics::*;
+pub use notification::*;
+pub use orcid::*;
+pub use outgoing_webhook::*;
+pub use paper_timeline::*;
 pub use paper_version::*;
 pub use post::*;
+pub use post_category::*;
+pub use profile::*;
+pub use repository_deposit::*;
 pub use review_comment::*;
 pub use review::*;
+pub use review_backfill::*;
+pub use reviewer_assignment::*;
+pub use revision_expiry::*;
+pub use scholar_meta::*;
+pub use status_transition::*;
+pub use system_setting::*;
+pub use system_usage::*;
+pub use trash::*;
 pub use user::*;
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct NotificationResponse {
+    pub id: i64,
+    pub event_type: String,
+    pub actor_id: Option<i64>,
+    pub actor_username: Option<String>,
+    pub post_id: Option<i64>,
+    pub comment_id: Option<i64>,
+    pub message: String,
+    pub is_read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    /// How many events this notification stands for; above 1 when rapid
+    /// activity on a post was collapsed into it.
+    pub batch_count: i32,
+    pub created_at: DateTime<Utc>,
+    /// When the latest event was folded in, if any.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<NotificationResponse>,
+    pub total: i64,
+    pub unread: i64,
+    pub limit: i32,
+    pub offset: i32,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SubscriptionStatusResponse {
+    pub post_id: i64,
+    pub following_post: bool,
+    pub followed_comment_ids: Vec<i64>,
+}
+
+/// Whether one event type reaches the user in-app and by email. Nothing
+/// sends email yet, so the email choice is only recorded for now.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotificationPreference {
+    pub event_type: String,
+    pub in_app: bool,
+    pub email: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotificationPreferenceListResponse {
+    pub preferences: Vec<NotificationPreference>,
+}
+
+/// A channel left out keeps its current setting.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct NotificationPreferenceUpdate {
+    pub event_type: String,
+    pub in_app: Option<bool>,
+    pub email: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateNotificationPreferences {
+    pub preferences: Vec<NotificationPreferenceUpdate>,
+}
+
+/// The keys from the browser's `PushSubscription`, base64url encoded.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PushSubscriptionKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+/// The browser's `PushSubscription.toJSON()`.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct RegisterPushSubscription {
+    pub endpoint: String,
+    pub keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UnregisterPushSubscription {
+    pub endpoint: String,
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const ORCID_WORK_SYNCED: &str = "synced";
+pub const ORCID_WORK_FAILED: &str = "failed";
+
+/// Sync state of one paper on the author's ORCID record.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct OrcidWorkSync {
+    pub post_id: i64,
+    /// `None` once the post has been deleted.
+    pub title: Option<String>,
+    pub put_code: Option<String>,
+    pub status: String,
+    pub last_error: Option<String>,
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OrcidSyncStatus {
+    pub linked: bool,
+    pub orcid_id: Option<String>,
+    pub sync_enabled: bool,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub last_sync_error: Option<String>,
+    pub works: Vec<OrcidWorkSync>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrcidAuthorizeResponse {
+    pub authorize_url: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOrcidSync {
+    pub sync_enabled: bool,
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const WEBHOOK_PLATFORM_SLACK: &str = "slack";
+pub const WEBHOOK_PLATFORM_DISCORD: &str = "discord";
+
+pub const WEBHOOK_EVENT_SUBMISSION: &str = "submission";
+pub const WEBHOOK_EVENT_REVIEW_FAILURE: &str = "review_failure";
+pub const WEBHOOK_EVENT_CONTENT_REPORT: &str = "content_report";
+
+/// A Slack or Discord webhook. The URL carries the webhook's secret, so
+/// responses only show `url_hint`, the URL without its final segment.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct OutgoingWebhook {
+    pub id: i64,
+    pub name: String,
+    pub platform: String,
+    #[serde(skip_serializing)]
+    pub url: String,
+    pub url_hint: String,
+    pub is_active: bool,
+    pub on_submission: bool,
+    pub on_review_failure: bool,
+    pub on_content_report: bool,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub created_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OutgoingWebhookListResponse {
+    pub webhooks: Vec<OutgoingWebhook>,
+}
+
+/// Every event is enabled unless turned off here.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateOutgoingWebhook {
+    pub name: String,
+    pub platform: String,
+    pub url: String,
+    pub is_active: Option<bool>,
+    pub on_submission: Option<bool>,
+    pub on_review_failure: Option<bool>,
+    pub on_content_report: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateOutgoingWebhook {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub is_active: Option<bool>,
+    pub on_submission: Option<bool>,
+    pub on_review_failure: Option<bool>,
+    pub on_content_report: Option<bool>,
+}
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub const TIMELINE_VERSION_SUBMITTED: &str = "version_submitted";
+pub const TIMELINE_AI_REVIEW_REQUESTED: &str = "ai_review_requested";
+pub const TIMELINE_AI_REVIEW_COMPLETED: &str = "ai_review_completed";
+pub const TIMELINE_AI_REVIEW_FAILED: &str = "ai_review_failed";
+pub const TIMELINE_STATUS_CHANGED: &str = "status_changed";
+pub const TIMELINE_REVIEW_COMMENT: &str = "review_comment";
+pub const TIMELINE_PUBLISHED: &str = "published";
+
+/// One entry in a paper's activity timeline. `reference_id` is the id of the
+/// row behind the event (version, AI review, transition or review comment).
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaperTimelineEvent {
+    pub event_type: &'static str,
+    pub occurred_at: DateTime<Utc>,
+    pub reference_id: Option<i64>,
+    pub paper_version_id: Option<i64>,
+    /// `None` for system events and for hidden reviewers.
+    pub actor_id: Option<i64>,
+    /// The actor's username, or the reviewer pseudonym when hidden.
+    pub actor_label: Option<String>,
+    pub summary: String,
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaperTimelineResponse {
+    pub post_id: i64,
+    pub events: Vec<PaperTimelineEvent>,
+}
@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct PaperVersion {
     pub id: i64,
     pub post_id: i64,
@@ -13,6 +14,9 @@ pub struct PaperVersion {
     pub github_url: Option<String>,
     pub file_path: Option<String>,
     pub file_name: Option<String>,
+    /// Set once the attachment is in the version store; older versions may
+    /// still point at a working-copy file that has since been replaced.
+    pub file_sha256: Option<String>,
     pub tags_json: Option<String>,
     pub citations_json: Option<String>,
     pub submitted_by: Option<i64>,
@@ -20,7 +24,7 @@ pub struct PaperVersion {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PaperVersionResponse {
     pub id: i64,
     pub post_id: i64,
@@ -29,8 +33,10 @@ pub struct PaperVersionResponse {
     pub content: String,
     pub summary: Option<String>,
     pub github_url: Option<String>,
-    pub file_path: Option<String>,
+    /// Download URL of the attachment stored with this version.
+    pub file_url: Option<String>,
     pub file_name: Option<String>,
+    pub file_sha256: Option<String>,
     pub tags: Vec<String>,
     pub citations: Vec<i64>,
     pub submitted_by: Option<i64>,
@@ -38,7 +44,7 @@ pub struct PaperVersionResponse {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PaperVersionListResponse {
     pub versions: Vec<PaperVersionResponse>,
     pub total: i64,
@@ -1,8 +1,13 @@
+use super::citation::{CitationChangeSet, PostCitationsResponse, UpdatePostCitations};
+use super::issue::IssueCitation;
 use super::metrics::PostMetrics;
 use super::user::UserResponse;
+use crate::validation::{not_null, tag_names};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationErrors};
 
 pub const PAPER_STATUS_DRAFT: &str = "draft";
 pub const PAPER_STATUS_SUBMITTED: &str = "submitted";
@@ -11,7 +16,10 @@ pub const PAPER_STATUS_ACCEPTED: &str = "accepted";
 pub const PAPER_STATUS_PUBLISHED: &str = "published";
 pub const PAPER_STATUS_REJECTED: &str = "rejected";
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+/// A post row with its counters. Load it with `POST_SELECT_COLUMNS` in
+/// `routes::posts`; a hand-written select that misses a column fails at
+/// runtime.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Post {
     pub id: i64,
     pub title: String,
@@ -28,11 +36,77 @@ pub struct Post {
     pub current_revision: i32,
     pub view_count: i64,
     pub like_count: i64,
+    pub citation_count: i64,
+    pub external_citation_count: i64,
+    pub influence_score: f64,
+    pub retracted_at: Option<DateTime<Utc>>,
+    pub retraction_reason: Option<String>,
+    pub is_double_blind: bool,
+    pub is_preprint: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+impl Post {
+    pub fn retraction_notice(&self) -> Option<RetractionNotice> {
+        self.retracted_at.map(|retracted_at| RetractionNotice {
+            retracted_at,
+            reason: self.retraction_reason.clone().unwrap_or_default(),
+        })
+    }
+
+    pub fn is_blind_review_active(&self) -> bool {
+        is_blind_review_active(self.is_double_blind, &self.paper_status)
+    }
+
+    pub fn preprint_badge(&self) -> Option<String> {
+        preprint_badge(&self.paper_status)
+            .filter(|_| self.is_preprint)
+            .map(ToOwned::to_owned)
+    }
+}
+
+/// The metadata of a post that ownership and state checks read, without
+/// the LONGTEXT content and the counters `Post` carries.
+#[derive(Debug, Clone, FromRow)]
+pub struct PostSummary {
+    pub title: String,
+    pub category: String,
+    pub file_path: Option<String>,
+    pub author_id: i64,
+    pub paper_status: String,
+}
+
+/// Review badge shown on a public preprint in place of hiding it. Drafts
+/// have not been posted and carry no badge.
+pub fn preprint_badge(paper_status: &str) -> Option<&'static str> {
+    match paper_status {
+        PAPER_STATUS_SUBMITTED => Some("under_review"),
+        PAPER_STATUS_REVISION => Some("revision_requested"),
+        PAPER_STATUS_ACCEPTED => Some("accepted"),
+        PAPER_STATUS_REJECTED => Some("not_accepted"),
+        PAPER_STATUS_PUBLISHED => Some("published"),
+        _ => None,
+    }
+}
+
+/// A double-blind paper keeps its author hidden from reviewers until an
+/// editorial decision moves it past review.
+pub fn is_blind_review_active(is_double_blind: bool, paper_status: &str) -> bool {
+    is_double_blind
+        && matches!(
+            paper_status,
+            PAPER_STATUS_DRAFT | PAPER_STATUS_SUBMITTED | PAPER_STATUS_REVISION
+        )
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RetractionNotice {
+    pub retracted_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PostResponse {
     pub id: i64,
     pub title: String,
@@ -52,13 +126,22 @@ pub struct PostResponse {
     pub like_count: i64,
     pub user_liked: Option<bool>,
     pub metrics: PostMetrics,
+    pub retraction: Option<RetractionNotice>,
     pub doi_metadata: Vec<PostDoiMetadata>,
+    pub is_double_blind: bool,
+    pub is_preprint: bool,
+    /// Review state of a public preprint; `None` for other posts.
+    pub preprint_badge: Option<String>,
+    /// Places where the manuscript names its author, returned to the author
+    /// when a double-blind paper is saved.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blind_review_warnings: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PostDoiMetadata {
     pub doi: String,
     pub title: Option<String>,
@@ -66,10 +149,13 @@ pub struct PostDoiMetadata {
     pub publisher: Option<String>,
     pub published_at: Option<String>,
     pub source_url: Option<String>,
+    /// Set only on the post's own Thought Manifold DOI once the paper is
+    /// assigned to an issue.
+    pub issue: Option<IssueCitation>,
     pub bibtex: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PostListResponse {
     pub posts: Vec<PostResponse>,
     pub total: i64,
@@ -77,7 +163,189 @@ pub struct PostListResponse {
     pub per_page: i32,
 }
 
-#[derive(Debug, Deserialize, Default)]
+/// Body of `PATCH /api/posts/{post_id}`. Absent fields are left unchanged;
+/// `null` clears `summary` and `github_url` and is rejected elsewhere.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PatchPost {
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub content: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub summary: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub github_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub category: Option<Option<String>>,
+    /// Replaces all tags; `[]` removes them.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<Vec<String>>)]
+    pub tags: Option<Option<Vec<String>>>,
+    /// Replaces the manual citations with these post ids.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<Vec<i64>>)]
+    pub citations: Option<Option<Vec<i64>>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub paper_status: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<bool>)]
+    pub double_blind: Option<Option<bool>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<bool>)]
+    pub preprint: Option<Option<bool>>,
+}
+
+/// Written by hand because the derive skips `Some(None)`, which is how an
+/// explicit `null` arrives.
+impl Validate for PatchPost {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let non_nullable = [
+            ("title", not_null(&self.title)),
+            ("content", not_null(&self.content)),
+            ("category", not_null(&self.category)),
+            ("tags", not_null(&self.tags)),
+            ("citations", not_null(&self.citations)),
+            ("paper_status", not_null(&self.paper_status)),
+            ("double_blind", not_null(&self.double_blind)),
+            ("preprint", not_null(&self.preprint)),
+        ];
+        for (field, result) in non_nullable {
+            if let Err(error) = result {
+                errors.add(field, error);
+            }
+        }
+        if let Some(Some(tags)) = &self.tags
+            && let Err(error) = tag_names(tags)
+        {
+            errors.add("tags", error);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Wraps a present field in `Some`, so with `#[serde(default)]` an absent
+/// field is `None` and an explicit `null` is `Some(None)`.
+fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct BatchPostRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "ids must list between 1 and 100 post ids"
+    ))]
+    pub ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchPostResponse {
+    /// In the order requested, without duplicates.
+    pub posts: Vec<PostResponse>,
+    /// Requested ids that do not exist or are not published.
+    pub missing_ids: Vec<i64>,
+}
+
+/// Body of `POST /api/posts/{post_id}/tags:batch`. Removals are applied
+/// before additions; tags already present or absent are ignored.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct TagBatchRequest {
+    #[serde(default)]
+    #[validate(custom(function = "crate::validation::tag_names"))]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostTagsResponse {
+    pub post_id: i64,
+    pub tags: Vec<String>,
+}
+
+/// Body of `POST /api/posts/{post_id}/bulk`. Each present field replaces
+/// the current value, and both are written in one transaction.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct PostBulkUpdate {
+    /// Replaces all tags; `[]` removes them.
+    #[validate(custom(function = "crate::validation::tag_names"))]
+    pub tags: Option<Vec<String>>,
+    /// Replaces the manual citations, as `PUT /api/posts/{post_id}/citations`
+    /// does.
+    pub citations: Option<UpdatePostCitations>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostBulkUpdateResponse {
+    pub post_id: i64,
+    pub tags: Vec<String>,
+    /// Present when citations were replaced.
+    pub citation_changes: Option<CitationChangeSet>,
+    pub citations: PostCitationsResponse,
+}
+
+/// Row of the admin post listing; drafts and unpublished posts included.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AdminPostSummary {
+    pub id: i64,
+    pub title: String,
+    pub category: String,
+    pub author_id: i64,
+    pub author_username: String,
+    pub paper_status: String,
+    pub is_published: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub is_preprint: bool,
+    pub retracted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminPostListResponse {
+    pub posts: Vec<AdminPostSummary>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminBulkUnpublish {
+    pub post_ids: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdminBulkCategoryUpdate {
+    pub post_ids: Vec<i64>,
+    pub category: String,
+}
+
+/// Outcome of a bulk post action. `skipped` holds ids that do not exist or
+/// were already in the requested state.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminBulkPostResult {
+    pub updated: Vec<i64>,
+    pub skipped: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PostQuery {
     pub page: Option<i32>,
     pub per_page: Option<i32>,
@@ -91,4 +359,79 @@ pub struct PostQuery {
     pub min_citation_count: Option<i64>,
     pub max_citation_count: Option<i64>,
     pub min_author_g_index: Option<i64>,
+    pub min_author_h_index: Option<i64>,
+    pub min_author_i10_index: Option<i64>,
+    pub issue_id: Option<i64>,
+    pub preprint: Option<bool>,
+    pub sort: Option<String>,
+    /// Comma-separated top-level fields to return for each post, e.g.
+    /// `id,title,summary,tags`.
+    pub fields: Option<String>,
+    /// Leaves out each post's `content`; ignored when `fields` is given.
+    pub summary_only: Option<bool>,
+    /// Limits the list to authors this user follows; set by the following
+    /// feed, not accepted from the query string.
+    #[serde(skip)]
+    pub followed_by: Option<i64>,
+}
+
+/// The text fields of the multipart post forms as they will be stored,
+/// checked before anything is written.
+#[derive(Debug, Validate)]
+pub struct PostFields<'a> {
+    #[validate(
+        custom(
+            function = "crate::validation::not_blank",
+            message = "title is required"
+        ),
+        length(max = 255, message = "title must be at most 255 characters")
+    )]
+    pub title: &'a str,
+    #[validate(
+        custom(
+            function = "crate::validation::not_blank",
+            message = "content is required"
+        ),
+        length(
+            max = 1_000_000,
+            message = "content must be at most 1000000 characters"
+        )
+    )]
+    pub content: &'a str,
+    #[validate(length(max = 5000, message = "summary must be at most 5000 characters"))]
+    pub summary: Option<&'a str>,
+    #[validate(length(max = 64, message = "category must be at most 64 characters"))]
+    pub category: &'a str,
+    #[validate(custom(function = "crate::validation::tag_list"))]
+    pub tags: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportPost {
+    /// An arXiv ID (`2401.01234`, `arXiv:hep-th/9901001`) or a DOI.
+    pub identifier: String,
+}
+
+/// The external record a draft was imported from.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostImportSource {
+    pub post_id: i64,
+    pub source_type: String,
+    pub source_identifier: String,
+    pub source_url: String,
+    pub source_doi: Option<String>,
+    pub authors: Vec<String>,
+    pub license_url: Option<String>,
+    pub pdf_imported: bool,
+    pub imported_by: Option<i64>,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportPostResponse {
+    pub post_id: i64,
+    pub title: String,
+    pub paper_status: String,
+    pub source: PostImportSource,
+    pub reference_count: usize,
 }
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PostCategory {
+    pub id: i64,
+    pub code: String,
+    pub display_name: String,
+    /// Disabled categories keep their posts but take no new ones.
+    pub is_active: bool,
+    pub post_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostCategoryListResponse {
+    pub categories: Vec<PostCategory>,
+    /// Whether posts are limited to these categories
+    /// (`POST_CATEGORIES_RESTRICTED`).
+    pub restricted: bool,
+}
+
+/// `display_name` defaults to the title-cased code.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreatePostCategory {
+    pub code: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdatePostCategory {
+    pub code: Option<String>,
+    pub display_name: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct MergePostCategory {
+    pub target_category_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostCategoryMergeResult {
+    pub category: PostCategory,
+    pub moved_posts: u64,
+}
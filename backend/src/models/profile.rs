@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use super::AuthorMetrics;
+
+pub const PROFILE_ACTIVITY_POST: &str = "post";
+pub const PROFILE_ACTIVITY_COMMENT: &str = "comment";
+
+/// A published paper as listed on its author's public profile.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ProfilePublication {
+    pub id: i64,
+    pub title: String,
+    pub summary: Option<String>,
+    pub paper_status: String,
+    pub is_preprint: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub citation_count: i64,
+    pub external_citation_count: i64,
+}
+
+/// A recent public post or comment by the profile's owner.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ProfileActivity {
+    pub activity_type: String,
+    pub post_id: i64,
+    pub post_title: String,
+    pub comment_id: Option<i64>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// What anyone can see about a user. Email, admin status and anything not
+/// yet published are left out.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicProfileResponse {
+    pub id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub introduction: Option<String>,
+    pub hobbies: Option<String>,
+    pub interests: Option<String>,
+    pub research_areas: Option<String>,
+    pub affiliation: Option<String>,
+    pub website: Option<String>,
+    pub orcid_id: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub follower_count: i64,
+    pub following_count: i64,
+    pub metrics: AuthorMetrics,
+    pub publications: Vec<ProfilePublication>,
+    pub recent_activity: Vec<ProfileActivity>,
+}
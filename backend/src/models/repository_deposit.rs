@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const REPOSITORY_DEPOSIT_PENDING: &str = "pending";
+pub const REPOSITORY_DEPOSIT_DEPOSITED: &str = "deposited";
+pub const REPOSITORY_DEPOSIT_FAILED: &str = "failed";
+
+/// SWORD v2 deposit of one accepted paper and the receipt the
+/// institutional repository returned for it.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PostRepositoryDeposit {
+    pub post_id: i64,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub edit_iri: Option<String>,
+    pub edit_media_iri: Option<String>,
+    pub statement_iri: Option<String>,
+    pub splash_url: Option<String>,
+    pub receipt_xml: Option<String>,
+    pub media_deposited_at: Option<DateTime<Utc>>,
+    pub deposited_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostRepositoryDepositListResponse {
+    pub deposits: Vec<PostRepositoryDeposit>,
+    pub total: i64,
+}
@@ -1,8 +1,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+use crate::models::ReviewCommentVersionSummary;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AiReviewStatus {
     Pending,
@@ -10,7 +13,7 @@ pub enum AiReviewStatus {
     Failed,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AiReviewDecision {
     Accept,
@@ -19,7 +22,7 @@ pub enum AiReviewDecision {
     Reject,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct AiReviewScores {
     pub overall_score: Option<i32>,
     pub novelty_score: Option<i32>,
@@ -28,12 +31,12 @@ pub struct AiReviewScores {
     pub citation_integrity_score: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct AiReviewEditorial {
     pub summary: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct AiReviewPeer {
     pub summary: Option<String>,
     pub major_issues: Vec<String>,
@@ -42,7 +45,7 @@ pub struct AiReviewPeer {
     pub strengths: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AiReviewResponse {
     pub id: i64,
     pub post_id: i64,
@@ -57,14 +60,29 @@ pub struct AiReviewResponse {
     pub scores: AiReviewScores,
     pub editorial: AiReviewEditorial,
     pub peer: AiReviewPeer,
+    /// The manuscript as sent to the model; admins only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub input_snapshot: Option<Value>,
+    /// The model's unparsed reply; admins only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub raw_response: Option<Value>,
     pub error_message: Option<String>,
+    /// `X-Request-Id` of the request that scheduled the review, if any.
+    pub request_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl AiReviewResponse {
+    /// Drops the input snapshot and raw model reply, which can run to
+    /// hundreds of KB and are only needed to debug the reviewer.
+    pub fn strip_raw_payloads(&mut self) {
+        self.input_snapshot = None;
+        self.raw_response = None;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AiReviewListResponse {
     pub reviews: Vec<AiReviewResponse>,
     pub total: i64,
@@ -72,7 +90,7 @@ pub struct AiReviewListResponse {
     pub per_page: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AiReviewSummary {
     pub id: i64,
     pub paper_version_id: Option<i64>,
@@ -86,7 +104,7 @@ pub struct AiReviewSummary {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MyPaperReviewItem {
     pub post_id: i64,
     pub title: String,
@@ -96,9 +114,14 @@ pub struct MyPaperReviewItem {
     pub is_published: bool,
     pub published_at: Option<DateTime<Utc>>,
     pub latest_review: Option<AiReviewSummary>,
+    /// Deadline of an outstanding revision request, if the editor set one.
+    pub revision_due_at: Option<DateTime<Utc>>,
+    pub is_overdue: bool,
+    /// Review comment resolution per submitted version, newest first.
+    pub review_comment_summaries: Vec<ReviewCommentVersionSummary>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MyPaperReviewListResponse {
     pub items: Vec<MyPaperReviewItem>,
     pub total: i64,
@@ -106,10 +129,44 @@ pub struct MyPaperReviewListResponse {
     pub per_page: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AiReviewMetricsSummary {
     pub total_reviews: i64,
     pub pending_reviews: i64,
     pub completed_reviews: i64,
     pub failed_reviews: i64,
 }
+
+/// Operational view of the AI review pipeline over a recent window.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AiReviewHealth {
+    pub window_hours: i64,
+    /// Reviews still pending, regardless of the window.
+    pub queue_depth: i64,
+    pub oldest_pending_created_at: Option<DateTime<Utc>>,
+    pub oldest_pending_age_secs: Option<i64>,
+    pub completed_in_window: i64,
+    pub failed_in_window: i64,
+    /// Share of finished reviews in the window that failed, from 0 to 1.
+    pub failure_rate: f64,
+    /// Time from scheduling to completion of successful reviews.
+    pub latency_p50_ms: Option<i64>,
+    pub latency_p95_ms: Option<i64>,
+    pub failures_by_class: Vec<AiReviewFailureClassCount>,
+    pub rate_limit: GeminiRateLimitStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AiReviewFailureClassCount {
+    pub error_class: String,
+    pub count: i64,
+}
+
+/// Gemini rate limiting as seen by this server instance since it started.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GeminiRateLimitStatus {
+    pub is_rate_limited: bool,
+    pub limited_until: Option<DateTime<Utc>>,
+    pub last_rate_limited_at: Option<DateTime<Utc>>,
+    pub rate_limited_responses_last_hour: i64,
+}
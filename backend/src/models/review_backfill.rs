@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const BACKFILL_STATUS_RUNNING: &str = "running";
+pub const BACKFILL_STATUS_COMPLETED: &str = "completed";
+pub const BACKFILL_STATUS_CANCELLED: &str = "cancelled";
+
+pub const BACKFILL_ITEM_STATUS_QUEUED: &str = "queued";
+pub const BACKFILL_ITEM_STATUS_SCHEDULED: &str = "scheduled";
+pub const BACKFILL_ITEM_STATUS_FAILED: &str = "failed";
+pub const BACKFILL_ITEM_STATUS_CANCELLED: &str = "cancelled";
+
+/// A bulk re-review with its progress. Item counts track scheduling; the
+/// `reviews_*` counts track the outcome of the reviews scheduled so far.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReviewBackfill {
+    pub id: i64,
+    pub status: String,
+    pub prompt_version: Option<String>,
+    pub paper_status: Option<String>,
+    pub batch_size: i32,
+    pub total_items: i64,
+    pub queued_items: i64,
+    pub scheduled_items: i64,
+    pub failed_items: i64,
+    pub cancelled_items: i64,
+    pub reviews_pending: i64,
+    pub reviews_completed: i64,
+    pub reviews_failed: i64,
+    pub created_by: Option<i64>,
+    pub cancelled_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReviewBackfillListResponse {
+    pub backfills: Vec<ReviewBackfill>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+/// Selects the papers to re-review. Without `prompt_version`, papers whose
+/// latest review used an older prompt than the current one are picked.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateReviewBackfill {
+    pub prompt_version: Option<String>,
+    pub paper_status: Option<String>,
+    /// Reviews scheduled per run of the backfill job.
+    pub batch_size: Option<i32>,
+    /// Only report how many papers match.
+    #[serde(default)]
+    pub dry_run: bool,
+}
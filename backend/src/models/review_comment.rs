@@ -1,10 +1,15 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 
-use crate::models::UserResponse;
+use crate::models::{CommentMention, UserResponse};
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub const REVIEW_COMMENT_RESOLUTION_OPEN: &str = "open";
+pub const REVIEW_COMMENT_RESOLUTION_ADDRESSED: &str = "addressed";
+pub const REVIEW_COMMENT_RESOLUTION_WONT_FIX: &str = "wont_fix";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ReviewComment {
     pub id: i64,
     pub post_id: i64,
@@ -14,36 +19,96 @@ pub struct ReviewComment {
     pub content: String,
     pub is_deleted: bool,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub is_anonymous: bool,
+    pub resolution_status: String,
+    pub resolved_by: Option<i64>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolution_note: Option<String>,
+    pub anchor_start: Option<i32>,
+    pub anchor_end: Option<i32>,
+    pub anchor_quote: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Passage of the paper version a review comment refers to. Offsets count
+/// Unicode characters of the version content; `end` is exclusive.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReviewCommentAnchor {
+    pub start: i32,
+    pub end: i32,
+    pub quote: String,
+}
+
+/// Either a character range, a quoted snippet, or both. A snippet alone must
+/// occur exactly once in the version content.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ReviewCommentAnchorInput {
+    pub start: Option<i32>,
+    pub end: Option<i32>,
+    pub quote: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ReviewCommentResponse {
     pub id: i64,
     pub post_id: i64,
     pub paper_version_id: Option<i64>,
     pub author_id: i64,
     pub parent_comment_id: Option<i64>,
+    /// Nesting level below the top-level comment, which is 0.
+    pub depth: i32,
     pub author: UserResponse,
     pub content: String,
     pub is_deleted: bool,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub is_anonymous: bool,
+    /// "Reviewer N" label for anonymous comments. While the paper is
+    /// unpublished, non-admins other than the commenter see only this label
+    /// and `author_id`/`author` are replaced with a placeholder.
+    pub pseudonym: Option<String>,
+    /// `open`, `addressed` or `wont_fix`. Only top-level comments are
+    /// tracked; replies stay `open`.
+    pub resolution_status: String,
+    pub resolved_by: Option<i64>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolution_note: Option<String>,
+    pub anchor: Option<ReviewCommentAnchor>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub mentions: Vec<CommentMention>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateReviewComment {
     pub content: String,
     pub parent_comment_id: Option<i64>,
     pub paper_version_id: Option<i64>,
+    pub anonymous: Option<bool>,
+    pub anchor: Option<ReviewCommentAnchorInput>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateReviewCommentResolution {
+    pub resolution_status: String,
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ReviewCommentListResponse {
     pub comments: Vec<ReviewCommentResponse>,
     pub total: i64,
     pub limit: i32,
     pub offset: i32,
 }
+
+/// Top-level review comment counts for one submitted version.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ReviewCommentVersionSummary {
+    pub paper_version_id: i64,
+    pub version_number: i32,
+    pub open_count: i64,
+    pub addressed_count: i64,
+    pub wont_fix_count: i64,
+    pub resolved_count: i64,
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const REVIEWER_ASSIGNMENT_INVITED: &str = "invited";
+pub const REVIEWER_ASSIGNMENT_ACCEPTED: &str = "accepted";
+pub const REVIEWER_ASSIGNMENT_DECLINED: &str = "declined";
+pub const REVIEWER_ASSIGNMENT_SUBMITTED: &str = "submitted";
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReviewerAssignmentResponse {
+    pub id: i64,
+    pub post_id: i64,
+    pub reviewer_id: i64,
+    pub reviewer_username: String,
+    pub reviewer_display_name: Option<String>,
+    pub assigned_by: Option<i64>,
+    pub status: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    pub is_overdue: bool,
+    #[sqlx(skip)]
+    pub conflict_declaration: Option<ReviewerConflictDeclaration>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReviewerAssignmentListResponse {
+    pub assignments: Vec<ReviewerAssignmentResponse>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateReviewerAssignment {
+    pub reviewer_id: i64,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateReviewerAssignment {
+    /// `null` clears the due date.
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// A reviewer's conflict-of-interest declaration for one assignment.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReviewerConflictDeclaration {
+    pub assignment_id: i64,
+    pub has_coauthorship: bool,
+    pub has_same_institution: bool,
+    pub other_conflict: Option<String>,
+    pub declared_at: DateTime<Utc>,
+    pub overridden_by: Option<i64>,
+    pub override_reason: Option<String>,
+    pub overridden_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    pub has_conflict: bool,
+}
+
+impl ReviewerConflictDeclaration {
+    pub fn declares_conflict(&self) -> bool {
+        self.has_coauthorship || self.has_same_institution || self.other_conflict.is_some()
+    }
+
+    /// A declared conflict blocks acceptance until an editor overrides it.
+    pub fn blocks_acceptance(&self) -> bool {
+        self.declares_conflict() && self.overridden_at.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DeclareReviewerConflicts {
+    #[serde(default)]
+    pub has_coauthorship: bool,
+    #[serde(default)]
+    pub has_same_institution: bool,
+    /// Any other conflict, described in free text.
+    pub other_conflict: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct OverrideReviewerConflict {
+    pub reason: String,
+}
+
+/// Invitations and accepted assignments still owe a response or a review, so
+/// only those can be overdue.
+pub fn is_assignment_overdue(
+    status: &str,
+    due_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> bool {
+    (status == REVIEWER_ASSIGNMENT_INVITED || status == REVIEWER_ASSIGNMENT_ACCEPTED)
+        && due_at.is_some_and(|due_at| due_at <= now)
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AssignedPaperItem {
+    pub assignment_id: i64,
+    pub post_id: i64,
+    pub title: String,
+    pub paper_status: String,
+    pub current_revision: i32,
+    pub is_published: bool,
+    pub is_double_blind: bool,
+    pub status: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub assigned_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub conflicts_declared_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    pub is_overdue: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AssignedPaperListResponse {
+    pub items: Vec<AssignedPaperItem>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+/// A possible reviewer for a submission, ranked by how many of its tags
+/// they declared as expertise and then by how many reviews they have open.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReviewerSuggestion {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub affiliation: Option<String>,
+    pub tag_overlap: i64,
+    #[sqlx(skip)]
+    pub matched_tags: Vec<String>,
+    pub open_assignments: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReviewerSuggestionResponse {
+    pub post_id: i64,
+    pub post_tags: Vec<String>,
+    pub suggestions: Vec<ReviewerSuggestion>,
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// A paper in `revision` and the moment it will be rejected unless the author
+/// submits a new version.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ExpiringSubmission {
+    pub post_id: i64,
+    pub title: String,
+    pub author_id: i64,
+    pub author_username: String,
+    pub revision_started_at: DateTime<Utc>,
+    /// Due date set by the editor's revision decision, if any.
+    pub revision_due_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub expiry_warned_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ExpiringSubmissionListResponse {
+    pub submissions: Vec<ExpiringSubmission>,
+    pub total: i64,
+    pub within_days: i64,
+}
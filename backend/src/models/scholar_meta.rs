@@ -0,0 +1,17 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// One Highwire Press `<meta name="citation_*">` tag.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ScholarMetaTag {
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ScholarMetaResponse {
+    pub post_id: i64,
+    pub meta_tags: Vec<ScholarMetaTag>,
+    /// schema.org `ScholarlyArticle` description of the paper.
+    pub json_ld: serde_json::Value,
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PaperStatusTransition {
+    pub id: i64,
+    pub post_id: i64,
+    /// `None` for system changes such as AI review completion and backfills.
+    pub actor_id: Option<i64>,
+    pub actor_username: Option<String>,
+    pub trigger_source: String,
+    /// `None` on the entry recorded when the post was created.
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaperStatusHistoryResponse {
+    pub transitions: Vec<PaperStatusTransition>,
+    pub total: i64,
+    pub limit: i32,
+    pub offset: i32,
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A runtime setting as shown to admins. `source` is `database` for an
+/// admin override, `environment` or `default` otherwise; `value` is empty
+/// when the built-in default applies.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SystemSetting {
+    pub id: Option<i64>,
+    pub key: String,
+    pub kind: String,
+    pub description: String,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    pub value: Option<String>,
+    pub source: String,
+    pub updated_by: Option<i64>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SystemSettingListResponse {
+    pub settings: Vec<SystemSetting>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateSystemSetting {
+    pub value: String,
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SystemUsageReport {
+    pub generated_at: DateTime<Utc>,
+    pub storage: Vec<DirectoryUsage>,
+    pub database: DatabaseUsage,
+    pub largest_posts: Vec<LargePost>,
+    pub largest_attachments: Vec<LargeAttachment>,
+}
+
+/// Disk usage of one local storage directory, including subdirectories.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DirectoryUsage {
+    pub name: String,
+    pub exists: bool,
+    pub file_count: i64,
+    pub total_bytes: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DatabaseUsage {
+    pub total_bytes: i64,
+    pub tables: Vec<TableUsage>,
+}
+
+/// `approximate_rows` is InnoDB's estimate, not an exact count.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TableUsage {
+    pub table_name: String,
+    pub approximate_rows: i64,
+    pub data_bytes: i64,
+    pub index_bytes: i64,
+    pub total_bytes: i64,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct LargePost {
+    pub post_id: i64,
+    pub title: String,
+    pub author_id: i64,
+    pub content_bytes: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LargeAttachment {
+    pub post_id: i64,
+    pub title: String,
+    pub file_name: String,
+    pub file_path: String,
+    pub size_bytes: i64,
+}
+
+/// Response cache counters since the server started.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CacheStats {
+    /// Whether Redis is compiled in and connected.
+    pub enabled: bool,
+    pub ttl_secs: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub errors: u64,
+}
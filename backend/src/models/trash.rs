@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+pub const TRASH_ITEM_POST: &str = "post";
+pub const TRASH_ITEM_USER: &str = "user";
+
+pub const TRASH_STATUS_TRASHED: &str = "trashed";
+pub const TRASH_STATUS_RESTORED: &str = "restored";
+pub const TRASH_STATUS_PURGED: &str = "purged";
+
+/// A post or user an admin deleted. Trashing a user also trashes their
+/// posts under the same item; `post_count` is how many posts it still holds.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TrashItem {
+    pub id: i64,
+    pub item_type: String,
+    pub item_id: i64,
+    pub label: String,
+    pub status: String,
+    pub post_count: i64,
+    pub deleted_by: Option<i64>,
+    pub deleted_by_username: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+    pub purge_after: DateTime<Utc>,
+    pub restored_by: Option<i64>,
+    pub restored_at: Option<DateTime<Utc>>,
+    pub purged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TrashItemListResponse {
+    pub items: Vec<TrashItem>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
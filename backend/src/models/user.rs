@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct User {
     pub id: i64,
     pub username: String,
@@ -16,13 +18,16 @@ pub struct User {
     pub hobbies: Option<String>,
     pub interests: Option<String>,
     pub research_areas: Option<String>,
+    pub affiliation: Option<String>,
+    pub website: Option<String>,
+    pub orcid_id: Option<String>,
     pub avatar_url: Option<String>,
     pub is_admin: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct UserResponse {
     pub id: i64,
     pub username: String,
@@ -33,9 +38,20 @@ pub struct UserResponse {
     pub hobbies: Option<String>,
     pub interests: Option<String>,
     pub research_areas: Option<String>,
+    pub affiliation: Option<String>,
+    pub website: Option<String>,
+    pub orcid_id: Option<String>,
     pub avatar_url: Option<String>,
     pub is_admin: bool,
     pub created_at: DateTime<Utc>,
+    /// Active suspension or ban; only filled in for admins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspension: Option<UserSuspension>,
+    /// Follow counts; only filled in where a single user is returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follower_count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub following_count: Option<i64>,
 }
 
 impl From<User> for UserResponse {
@@ -50,23 +66,217 @@ impl From<User> for UserResponse {
             hobbies: user.hobbies,
             interests: user.interests,
             research_areas: user.research_areas,
+            affiliation: user.affiliation,
+            website: user.website,
+            orcid_id: user.orcid_id,
             avatar_url: user.avatar_url,
             is_admin: user.is_admin,
             created_at: user.created_at,
+            suspension: None,
+            follower_count: None,
+            following_count: None,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// One suspension of a user. `kind` is `ban` when there is no expiry.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UserSuspension {
+    pub id: i64,
+    pub user_id: i64,
+    pub kind: String,
+    pub reason: String,
+    pub suspended_by: Option<i64>,
+    pub starts_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lifted_by: Option<i64>,
+    pub lift_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body of `POST /admin/users/{id}/suspend`. Leaving out `expires_at`
+/// bans the user until the ban is lifted.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SuspendUser {
+    pub reason: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserSuspensionListResponse {
+    pub suspensions: Vec<UserSuspension>,
+    pub total: i64,
+}
+
+/// Row of the admin user list with the user's content counts.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserSummary {
+    #[serde(flatten)]
+    pub user: UserResponse,
+    pub post_count: i64,
+    pub comment_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserListResponse {
+    pub users: Vec<AdminUserSummary>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct CreateUser {
+    #[validate(custom(function = "crate::validation::username"))]
     pub username: String,
+    #[validate(
+        email(message = "email must be a valid email address"),
+        length(max = 191, message = "email must be at most 191 characters")
+    )]
     pub email: String,
+    #[validate(length(
+        min = 6,
+        max = 128,
+        message = "password must be between 6 and 128 characters"
+    ))]
     pub password: String,
+    #[validate(length(max = 255, message = "display_name must be at most 255 characters"))]
     pub display_name: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct LoginForm {
+    #[validate(length(min = 1, max = 191, message = "username is required"))]
+    pub username: String,
+    #[validate(length(min = 1, max = 128, message = "password is required"))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TokenResponse {
     pub access_token: String,
     pub token_type: String,
 }
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CitationRelation {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub citation_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserNetworkResponse {
+    pub user_id: i64,
+    /// Authors whose posts cite this user's posts, most frequent first.
+    pub cited_by: Vec<CitationRelation>,
+    /// Authors this user's posts cite, most frequent first.
+    pub citing: Vec<CitationRelation>,
+}
+
+/// One side of a follow edge, as listed under followers or following.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct FollowUser {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub followed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowListResponse {
+    pub users: Vec<FollowUser>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowStatusResponse {
+    pub user_id: i64,
+    pub following: bool,
+    pub follower_count: i64,
+}
+
+/// A user the caller has blocked.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct BlockedUser {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub blocked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockStatusResponse {
+    pub user_id: i64,
+    pub blocked: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct UpdateProfile {
+    #[validate(length(max = 255, message = "display_name must be at most 255 characters"))]
+    pub display_name: Option<String>,
+    #[validate(length(max = 5000, message = "bio must be at most 5000 characters"))]
+    pub bio: Option<String>,
+    #[validate(length(max = 5000, message = "introduction must be at most 5000 characters"))]
+    pub introduction: Option<String>,
+    #[validate(length(max = 5000, message = "hobbies must be at most 5000 characters"))]
+    pub hobbies: Option<String>,
+    #[validate(length(max = 5000, message = "interests must be at most 5000 characters"))]
+    pub interests: Option<String>,
+    #[validate(length(max = 5000, message = "research_areas must be at most 5000 characters"))]
+    pub research_areas: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct UpdateResearchProfile {
+    #[validate(length(max = 5000, message = "introduction must be at most 5000 characters"))]
+    pub introduction: Option<String>,
+    #[validate(length(max = 5000, message = "hobbies must be at most 5000 characters"))]
+    pub hobbies: Option<String>,
+    #[validate(length(max = 5000, message = "interests must be at most 5000 characters"))]
+    pub interests: Option<String>,
+    #[validate(length(max = 5000, message = "research_areas must be at most 5000 characters"))]
+    pub research_areas: Option<String>,
+    #[validate(length(max = 255, message = "affiliation must be at most 255 characters"))]
+    pub affiliation: Option<String>,
+    #[validate(length(max = 512, message = "website must be at most 512 characters"))]
+    pub website: Option<String>,
+    pub orcid_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct ChangeUsername {
+    #[validate(custom(function = "crate::validation::username"))]
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NetworkQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FollowListQuery {
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateExpertiseTags {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExpertiseTagsResponse {
+    pub user_id: i64,
+    pub tags: Vec<String>,
+}
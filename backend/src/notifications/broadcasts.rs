@@ -0,0 +1,382 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, MySql, MySqlPool, QueryBuilder};
+
+use super::{MAX_MESSAGE_CHARS, NOTIFICATION_BROADCAST, publish_notification};
+use crate::models::{
+    ANNOUNCEMENT_AUDIENCE_ADMINS, ANNOUNCEMENT_AUDIENCE_USERS, BROADCAST_RECIPIENT_STATUS_FAILED,
+    BROADCAST_RECIPIENT_STATUS_PENDING, BROADCAST_RECIPIENT_STATUS_SENT,
+    BROADCAST_STATUS_SCHEDULED, BROADCAST_STATUS_SENDING, BROADCAST_STATUS_SENT,
+    ERASURE_STATUS_APPROVED,
+};
+use crate::tasks;
+
+pub const DEFAULT_BROADCAST_INTERVAL_SECS: u64 = 30;
+/// Notifications written per run of the delivery job, across broadcasts.
+pub const DEFAULT_BROADCAST_BATCH_SIZE: i64 = 500;
+
+const MAX_ERROR_MESSAGE_CHARS: usize = 512;
+
+pub const BROADCAST_SELECT: &str = r#"
+    SELECT
+        b.id AS id,
+        b.subject AS subject,
+        b.body AS body,
+        b.audience AS audience,
+        CAST(b.active_within_days AS SIGNED) AS active_within_days,
+        b.status AS status,
+        b.scheduled_at AS scheduled_at,
+        CAST(b.recipient_count AS SIGNED) AS recipient_count,
+        (SELECT COUNT(*) FROM broadcast_recipients r
+            WHERE r.broadcast_id = b.id AND r.status = 'pending') AS pending_count,
+        (SELECT COUNT(*) FROM broadcast_recipients r
+            WHERE r.broadcast_id = b.id AND r.status = 'sent') AS sent_count,
+        (SELECT COUNT(*) FROM broadcast_recipients r
+            WHERE r.broadcast_id = b.id AND r.status = 'failed') AS failed_count,
+        b.created_by AS created_by,
+        b.created_at AS created_at,
+        b.started_at AS started_at,
+        b.finished_at AS finished_at
+    FROM broadcasts b
+"#;
+
+/// A user a broadcast is addressed to, with the fields its template uses.
+#[derive(Debug, Clone, FromRow)]
+pub struct BroadcastUser {
+    pub id: i64,
+    pub username: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct SendingBroadcast {
+    id: i64,
+    subject: String,
+    body: String,
+    created_by: Option<i64>,
+}
+
+/// Fills in the template placeholders for one recipient and joins subject
+/// and body into a notification message, cut to the notification limit.
+pub fn render_broadcast(subject: &str, body: &str, user: &BroadcastUser) -> String {
+    let display_name = user
+        .display_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&user.username);
+    let fill = |template: &str| {
+        template
+            .replace("{{username}}", &user.username)
+            .replace("{{display_name}}", display_name)
+    };
+
+    format!("{}\n\n{}", fill(subject), fill(body))
+        .chars()
+        .take(MAX_MESSAGE_CHARS)
+        .collect()
+}
+
+/// Appends the segment filter to a query over `users u`. Erased accounts
+/// are never addressed. Activity counts posts, comments and any write
+/// request since `active_since`; write requests only go back as far as
+/// `REQUEST_EVENT_RETENTION_DAYS`.
+fn push_segment_filter(
+    query_builder: &mut QueryBuilder<'_, MySql>,
+    audience: &str,
+    active_since: Option<DateTime<Utc>>,
+) {
+    query_builder.push(
+        " WHERE NOT EXISTS (SELECT 1 FROM user_erasure_requests e WHERE e.user_id = u.id AND e.status = ",
+    );
+    query_builder.push_bind(ERASURE_STATUS_APPROVED);
+    query_builder.push(")");
+    if audience == ANNOUNCEMENT_AUDIENCE_ADMINS {
+        query_builder.push(" AND u.is_admin = TRUE");
+    } else if audience == ANNOUNCEMENT_AUDIENCE_USERS {
+        query_builder.push(" AND u.is_admin = FALSE");
+    }
+    if let Some(active_since) = active_since {
+        query_builder.push(
+            " AND (EXISTS (SELECT 1 FROM posts p WHERE p.author_id = u.id AND p.created_at >= ",
+        );
+        query_builder.push_bind(active_since);
+        query_builder.push(
+            ") OR EXISTS (SELECT 1 FROM comments c WHERE c.author_id = u.id AND c.created_at >= ",
+        );
+        query_builder.push_bind(active_since);
+        query_builder.push(
+            ") OR EXISTS (SELECT 1 FROM request_events r WHERE r.user_id = u.id AND r.created_at >= ",
+        );
+        query_builder.push_bind(active_since);
+        query_builder.push("))");
+    }
+}
+
+fn active_since(active_within_days: Option<i64>) -> Option<DateTime<Utc>> {
+    active_within_days.map(|days| Utc::now() - chrono::Duration::days(days))
+}
+
+pub async fn count_segment(
+    pool: &MySqlPool,
+    audience: &str,
+    active_within_days: Option<i64>,
+) -> Result<i64, sqlx::Error> {
+    let mut query_builder = QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM users u");
+    push_segment_filter(
+        &mut query_builder,
+        audience,
+        active_since(active_within_days),
+    );
+    query_builder.build_query_scalar().fetch_one(pool).await
+}
+
+/// The first `limit` users of a segment, by user id.
+pub async fn sample_segment(
+    pool: &MySqlPool,
+    audience: &str,
+    active_within_days: Option<i64>,
+    limit: i64,
+) -> Result<Vec<BroadcastUser>, sqlx::Error> {
+    let mut query_builder =
+        QueryBuilder::<MySql>::new("SELECT u.id, u.username, u.display_name FROM users u");
+    push_segment_filter(
+        &mut query_builder,
+        audience,
+        active_since(active_within_days),
+    );
+    query_builder.push(" ORDER BY u.id ASC LIMIT ");
+    query_builder.push_bind(limit);
+    query_builder
+        .build_query_as::<BroadcastUser>()
+        .fetch_all(pool)
+        .await
+}
+
+/// Starts the periodic job that sends due broadcasts. Setting
+/// `BROADCAST_INTERVAL_SECS=0` disables the job; scheduled broadcasts then
+/// stay scheduled.
+pub fn spawn_broadcasts(pool: MySqlPool) {
+    let interval_secs = broadcast_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Broadcast delivery job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "broadcasts",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                if let Err(error) = start_due_broadcasts(&pool).await {
+                    tracing::error!("Starting due broadcasts failed: {}", error);
+                    return;
+                }
+                match deliver_broadcast_batch(&pool).await {
+                    Ok(0) => {}
+                    Ok(delivered) => {
+                        tracing::info!("Delivered {} broadcast notification(s)", delivered)
+                    }
+                    Err(error) => tracing::error!("Broadcast delivery failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Moves broadcasts whose time has come to `sending` and snapshots their
+/// recipients, so users who join or become active later are not added
+/// halfway through.
+async fn start_due_broadcasts(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let due: Vec<(i64, String, Option<i64>)> = sqlx::query_as(
+        r#"
+        SELECT id, audience, CAST(active_within_days AS SIGNED)
+        FROM broadcasts
+        WHERE status = ? AND scheduled_at <= ?
+        ORDER BY scheduled_at ASC, id ASC
+        "#,
+    )
+    .bind(BROADCAST_STATUS_SCHEDULED)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    for (broadcast_id, audience, active_within_days) in due {
+        let mut tx = pool.begin().await?;
+        // Claiming the broadcast first keeps a cancelled broadcast or
+        // another server instance from starting it twice.
+        let claimed = sqlx::query(
+            "UPDATE broadcasts SET status = ?, started_at = ? WHERE id = ? AND status = ?",
+        )
+        .bind(BROADCAST_STATUS_SENDING)
+        .bind(now)
+        .bind(broadcast_id)
+        .bind(BROADCAST_STATUS_SCHEDULED)
+        .execute(&mut *tx)
+        .await?;
+        if claimed.rows_affected() == 0 {
+            continue;
+        }
+
+        let mut query_builder = QueryBuilder::<MySql>::new(
+            "INSERT IGNORE INTO broadcast_recipients (broadcast_id, user_id, status) SELECT ",
+        );
+        query_builder.push_bind(broadcast_id);
+        query_builder.push(", u.id, ");
+        query_builder.push_bind(BROADCAST_RECIPIENT_STATUS_PENDING);
+        query_builder.push(" FROM users u");
+        push_segment_filter(
+            &mut query_builder,
+            &audience,
+            active_since(active_within_days),
+        );
+        let inserted = query_builder.build().execute(&mut *tx).await?;
+
+        sqlx::query("UPDATE broadcasts SET recipient_count = ? WHERE id = ?")
+            .bind(inserted.rows_affected())
+            .bind(broadcast_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        tracing::info!(
+            "Started broadcast {} to {} recipient(s)",
+            broadcast_id,
+            inserted.rows_affected()
+        );
+    }
+
+    Ok(())
+}
+
+/// Writes the next pending notifications of sending broadcasts, oldest
+/// broadcast first. Broadcasts with nobody left are marked sent. Returns
+/// the number of notifications written.
+async fn deliver_broadcast_batch(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let broadcasts = sqlx::query_as::<_, SendingBroadcast>(
+        "SELECT id, subject, body, created_by FROM broadcasts WHERE status = ? ORDER BY id ASC",
+    )
+    .bind(BROADCAST_STATUS_SENDING)
+    .fetch_all(pool)
+    .await?;
+
+    let mut capacity = broadcast_batch_size();
+    let mut delivered = 0;
+    for broadcast in broadcasts {
+        if capacity <= 0 {
+            break;
+        }
+
+        let recipients = sqlx::query_as::<_, BroadcastUser>(
+            r#"
+            SELECT u.id, u.username, u.display_name
+            FROM broadcast_recipients r
+            JOIN users u ON u.id = r.user_id
+            WHERE r.broadcast_id = ? AND r.status = ?
+            ORDER BY r.user_id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(broadcast.id)
+        .bind(BROADCAST_RECIPIENT_STATUS_PENDING)
+        .bind(capacity)
+        .fetch_all(pool)
+        .await?;
+        if recipients.is_empty() {
+            sqlx::query(
+                "UPDATE broadcasts SET status = ?, finished_at = ? WHERE id = ? AND status = ?",
+            )
+            .bind(BROADCAST_STATUS_SENT)
+            .bind(Utc::now())
+            .bind(broadcast.id)
+            .bind(BROADCAST_STATUS_SENDING)
+            .execute(pool)
+            .await?;
+            continue;
+        }
+
+        for recipient in recipients {
+            capacity -= 1;
+            // Marked before notifying, so an overlapping run cannot send the
+            // same broadcast to a user twice.
+            let now = Utc::now();
+            let claimed = sqlx::query(
+                r#"
+                UPDATE broadcast_recipients
+                SET status = ?, delivered_at = ?
+                WHERE broadcast_id = ? AND user_id = ? AND status = ?
+                "#,
+            )
+            .bind(BROADCAST_RECIPIENT_STATUS_SENT)
+            .bind(now)
+            .bind(broadcast.id)
+            .bind(recipient.id)
+            .bind(BROADCAST_RECIPIENT_STATUS_PENDING)
+            .execute(pool)
+            .await?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            let result = sqlx::query(
+                r#"
+                INSERT INTO notifications
+                    (user_id, actor_id, event_type, post_id, comment_id, message, is_read, created_at)
+                VALUES (?, ?, ?, NULL, NULL, ?, FALSE, ?)
+                "#,
+            )
+            .bind(recipient.id)
+            .bind(broadcast.created_by)
+            .bind(NOTIFICATION_BROADCAST)
+            .bind(render_broadcast(&broadcast.subject, &broadcast.body, &recipient))
+            .bind(now)
+            .execute(pool)
+            .await;
+            match result {
+                Ok(result) => {
+                    delivered += 1;
+                    publish_notification(recipient.id, result.last_insert_id() as i64);
+                }
+                Err(error) => {
+                    let error_message: String = error
+                        .to_string()
+                        .chars()
+                        .take(MAX_ERROR_MESSAGE_CHARS)
+                        .collect();
+                    sqlx::query(
+                        r#"
+                        UPDATE broadcast_recipients
+                        SET status = ?, error_message = ?, delivered_at = NULL
+                        WHERE broadcast_id = ? AND user_id = ?
+                        "#,
+                    )
+                    .bind(BROADCAST_RECIPIENT_STATUS_FAILED)
+                    .bind(error_message)
+                    .bind(broadcast.id)
+                    .bind(recipient.id)
+                    .execute(pool)
+                    .await?;
+                }
+            }
+        }
+    }
+
+    Ok(delivered)
+}
+
+fn broadcast_interval_secs() -> u64 {
+    std::env::var("BROADCAST_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_BROADCAST_INTERVAL_SECS)
+}
+
+fn broadcast_batch_size() -> i64 {
+    std::env::var("BROADCAST_BATCH_SIZE")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_BROADCAST_BATCH_SIZE)
+}
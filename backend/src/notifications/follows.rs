@@ -0,0 +1,65 @@
+use sqlx::MySqlPool;
+
+use super::{
+    NOTIFICATION_FOLLOWED_AUTHOR_POST, NOTIFICATION_NEW_FOLLOWER, NewNotification,
+    dispatch_notifications,
+};
+
+/// Tells `followed_id` that `follower` started following them.
+pub async fn notify_new_follower(pool: &MySqlPool, follower: (i64, &str), followed_id: i64) {
+    let (follower_id, follower_username) = follower;
+    dispatch_notifications(
+        pool,
+        vec![NewNotification {
+            user_id: followed_id,
+            actor_id: Some(follower_id),
+            event_type: NOTIFICATION_NEW_FOLLOWER,
+            post_id: None,
+            comment_id: None,
+            message: format!("@{} started following you", follower_username),
+        }],
+    )
+    .await;
+}
+
+/// Tells everyone following the author about a newly published post. Callers
+/// must skip posts whose author is hidden by double-blind review.
+pub async fn notify_followers_of_post(pool: &MySqlPool, author_id: i64, post_id: i64, title: &str) {
+    let rows: Vec<(i64, String)> = match sqlx::query_as(
+        r#"
+        SELECT f.follower_id, a.username
+        FROM user_follows f
+        JOIN users a ON a.id = f.followed_id
+        WHERE f.followed_id = ?
+        "#,
+    )
+    .bind(author_id)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to load followers of user {} for post {}: {}",
+                author_id,
+                post_id,
+                error
+            );
+            return;
+        }
+    };
+
+    let notifications = rows
+        .into_iter()
+        .map(|(user_id, author_username)| NewNotification {
+            user_id,
+            actor_id: Some(author_id),
+            event_type: NOTIFICATION_FOLLOWED_AUTHOR_POST,
+            post_id: Some(post_id),
+            comment_id: None,
+            message: format!("@{} published \"{}\"", author_username, title),
+        })
+        .collect();
+
+    dispatch_notifications(pool, notifications).await;
+}
@@ -0,0 +1,271 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use regex::Regex;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use super::{NOTIFICATION_MENTION, NewNotification, dispatch_notifications};
+use crate::models::{CommentMention, REVIEWER_ASSIGNMENT_ACCEPTED, REVIEWER_ASSIGNMENT_SUBMITTED};
+
+const MENTION_PATTERN: &str = r"(?:^|[^A-Za-z0-9_@])@([A-Za-z0-9_][A-Za-z0-9_.\-]*)";
+const MAX_MENTIONS_PER_COMMENT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MentionSource {
+    Comment,
+    ReviewComment,
+}
+
+impl MentionSource {
+    fn column(self) -> &'static str {
+        match self {
+            Self::Comment => "comment_id",
+            Self::ReviewComment => "review_comment_id",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Comment => "a comment",
+            Self::ReviewComment => "a review comment",
+        }
+    }
+}
+
+/// Extracts `@username` mentions in order of appearance. Matches inside email
+/// addresses are skipped and duplicates are folded case-insensitively.
+pub fn parse_mentions(content: &str) -> Vec<String> {
+    let regex = match Regex::new(MENTION_PATTERN) {
+        Ok(compiled) => compiled,
+        Err(error) => {
+            tracing::error!("Failed to compile mention regex: {}", error);
+            return Vec::new();
+        }
+    };
+
+    let mut seen = HashSet::new();
+    let mut usernames = Vec::new();
+    for captures in regex.captures_iter(content) {
+        let Some(matched) = captures.get(1) else {
+            continue;
+        };
+        let username = matched.as_str().trim_end_matches(['.', '-']);
+        if username.is_empty() || !seen.insert(username.to_lowercase()) {
+            continue;
+        }
+        usernames.push(username.to_string());
+        if usernames.len() >= MAX_MENTIONS_PER_COMMENT {
+            break;
+        }
+    }
+
+    usernames
+}
+
+/// Resolves mentions in `content`, stores one record per mentioned user and
+/// notifies users who were not already mentioned by this comment. Records for
+/// users no longer mentioned are removed, so this also handles edits. When
+/// `restricted_to_author` is set (unpublished papers), only that author,
+/// admins and reviewers who accepted an assignment can be mentioned. Returns every mention resolved from `content`.
+pub async fn record_mentions(
+    pool: &MySqlPool,
+    source: MentionSource,
+    comment_id: i64,
+    post_id: i64,
+    actor: (Option<i64>, &str),
+    content: &str,
+    restricted_to_author: Option<i64>,
+) -> Vec<CommentMention> {
+    let usernames = parse_mentions(content);
+    let mentions = if usernames.is_empty() {
+        Vec::new()
+    } else {
+        store_mentions(
+            pool,
+            source,
+            comment_id,
+            post_id,
+            actor,
+            &usernames,
+            restricted_to_author,
+        )
+        .await
+    };
+
+    let mut query_builder = QueryBuilder::<MySql>::new(format!(
+        "DELETE FROM comment_mentions WHERE {} = ",
+        source.column()
+    ));
+    query_builder.push_bind(comment_id);
+    if !mentions.is_empty() {
+        query_builder.push(" AND mentioned_user_id NOT IN (");
+        let mut separated = query_builder.separated(", ");
+        for mention in &mentions {
+            separated.push_bind(mention.user_id);
+        }
+        separated.push_unseparated(")");
+    }
+    if let Err(error) = query_builder.build().execute(pool).await {
+        tracing::warn!(
+            "Failed to prune mentions for {} {}: {}",
+            source.column(),
+            comment_id,
+            error
+        );
+    }
+
+    mentions
+}
+
+async fn store_mentions(
+    pool: &MySqlPool,
+    source: MentionSource,
+    comment_id: i64,
+    post_id: i64,
+    actor: (Option<i64>, &str),
+    usernames: &[String],
+    restricted_to_author: Option<i64>,
+) -> Vec<CommentMention> {
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        "SELECT id, username, is_admin FROM users WHERE deleted_at IS NULL AND username IN (",
+    );
+    let mut separated = query_builder.separated(", ");
+    for username in usernames {
+        separated.push_bind(username);
+    }
+    separated.push_unseparated(")");
+
+    let users: Vec<(i64, String, bool)> = match query_builder.build_query_as().fetch_all(pool).await
+    {
+        Ok(users) => users,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to resolve mentions for {} {}: {}",
+                source.column(),
+                comment_id,
+                error
+            );
+            return Vec::new();
+        }
+    };
+
+    // Reviewers who accepted an assignment can read the unpublished paper,
+    // so they may be mentioned alongside the author.
+    let active_reviewer_ids: Vec<i64> = if restricted_to_author.is_some() {
+        sqlx::query_scalar(
+            "SELECT reviewer_id FROM reviewer_assignments WHERE post_id = ? AND status IN (?, ?)",
+        )
+        .bind(post_id)
+        .bind(REVIEWER_ASSIGNMENT_ACCEPTED)
+        .bind(REVIEWER_ASSIGNMENT_SUBMITTED)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_else(|error| {
+            tracing::warn!("Failed to load reviewers for post {}: {}", post_id, error);
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let (actor_id, actor_username) = actor;
+    let now = Utc::now();
+    let insert_sql = format!(
+        "INSERT IGNORE INTO comment_mentions ({}, mentioned_user_id, created_at) VALUES (?, ?, ?)",
+        source.column()
+    );
+    let mut mentions = Vec::new();
+    let mut notifications = Vec::new();
+
+    for username in usernames {
+        let Some((user_id, username, is_admin)) = users
+            .iter()
+            .find(|(_, candidate, _)| candidate.eq_ignore_ascii_case(username))
+        else {
+            continue;
+        };
+        if let Some(author_id) = restricted_to_author
+            && *user_id != author_id
+            && !is_admin
+            && !active_reviewer_ids.contains(user_id)
+        {
+            continue;
+        }
+        if mentions
+            .iter()
+            .any(|mention: &CommentMention| mention.user_id == *user_id)
+        {
+            continue;
+        }
+
+        let inserted = sqlx::query(&insert_sql)
+            .bind(comment_id)
+            .bind(*user_id)
+            .bind(now)
+            .execute(pool)
+            .await;
+        match inserted {
+            Ok(result) if result.rows_affected() == 1 => {
+                notifications.push(NewNotification {
+                    user_id: *user_id,
+                    actor_id,
+                    event_type: NOTIFICATION_MENTION,
+                    post_id: Some(post_id),
+                    comment_id: (source == MentionSource::Comment).then_some(comment_id),
+                    message: format!("@{} mentioned you in {}", actor_username, source.label()),
+                });
+            }
+            Ok(_) => {}
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to store mention of user {} in {} {}: {}",
+                    user_id,
+                    source.column(),
+                    comment_id,
+                    error
+                );
+                continue;
+            }
+        }
+
+        mentions.push(CommentMention {
+            user_id: *user_id,
+            username: username.clone(),
+        });
+    }
+
+    dispatch_notifications(pool, notifications).await;
+    mentions
+}
+
+/// Loads stored mentions for a page of comments, keyed by comment id.
+pub async fn fetch_mentions(
+    pool: &MySqlPool,
+    source: MentionSource,
+    comment_ids: &[i64],
+) -> Result<HashMap<i64, Vec<CommentMention>>, sqlx::Error> {
+    let mut by_comment: HashMap<i64, Vec<CommentMention>> = HashMap::new();
+    if comment_ids.is_empty() {
+        return Ok(by_comment);
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(format!(
+        "SELECT cm.{column}, u.id, u.username FROM comment_mentions cm JOIN users u ON u.id = cm.mentioned_user_id WHERE cm.{column} IN (",
+        column = source.column()
+    ));
+    let mut separated = query_builder.separated(", ");
+    for comment_id in comment_ids {
+        separated.push_bind(*comment_id);
+    }
+    separated.push_unseparated(") ORDER BY cm.id ASC");
+
+    let rows: Vec<(i64, i64, String)> = query_builder.build_query_as().fetch_all(pool).await?;
+    for (comment_id, user_id, username) in rows {
+        by_comment
+            .entry(comment_id)
+            .or_default()
+            .push(CommentMention { user_id, username });
+    }
+
+    Ok(by_comment)
+}
@@ -0,0 +1,249 @@
+mod broadcasts;
+mod follows;
+mod mentions;
+mod preferences;
+mod reminders;
+mod stream;
+mod subscriptions;
+
+pub use broadcasts::*;
+pub use follows::*;
+pub use mentions::*;
+pub use preferences::*;
+pub use reminders::*;
+pub use stream::*;
+pub use subscriptions::*;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::MySqlPool;
+
+use crate::web_push::{PushMessage, queue_push};
+
+pub const NOTIFICATION_MENTION: &str = "mention";
+pub const NOTIFICATION_REPLY: &str = "reply";
+pub const NOTIFICATION_NEW_COMMENT: &str = "new_comment";
+pub const NOTIFICATION_REVIEW_INVITATION: &str = "review_invitation";
+pub const NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE: &str = "review_assignment_update";
+pub const NOTIFICATION_EDITORIAL_DECISION: &str = "editorial_decision";
+pub const NOTIFICATION_DEADLINE_REMINDER: &str = "deadline_reminder";
+pub const NOTIFICATION_DEADLINE_OVERDUE: &str = "deadline_overdue";
+pub const NOTIFICATION_APPEAL_SUBMITTED: &str = "appeal_submitted";
+pub const NOTIFICATION_APPEAL_RESOLVED: &str = "appeal_resolved";
+pub const NOTIFICATION_REVISION_EXPIRY_WARNING: &str = "revision_expiry_warning";
+pub const NOTIFICATION_REVISION_EXPIRED: &str = "revision_expired";
+pub const NOTIFICATION_ERASURE_REQUESTED: &str = "erasure_requested";
+pub const NOTIFICATION_ERASURE_REJECTED: &str = "erasure_rejected";
+pub const NOTIFICATION_BROADCAST: &str = "broadcast";
+pub const NOTIFICATION_NEW_FOLLOWER: &str = "new_follower";
+pub const NOTIFICATION_FOLLOWED_AUTHOR_POST: &str = "followed_author_post";
+
+const MAX_MESSAGE_CHARS: usize = 512;
+pub const DEFAULT_NOTIFICATION_BATCH_WINDOW_SECS: i64 = 900;
+
+/// Event types collapsed into one unread notification per user and post
+/// while the batch window is open, so a busy thread does not flood inboxes.
+const BATCHED_EVENT_TYPES: &[&str] = &[NOTIFICATION_NEW_COMMENT, NOTIFICATION_REPLY];
+
+/// Notifications with the actor's username, in the shape the inbox returns.
+pub const NOTIFICATION_SELECT: &str = r#"
+    SELECT
+        n.id,
+        n.event_type,
+        n.actor_id,
+        a.username AS actor_username,
+        n.post_id,
+        n.comment_id,
+        n.message,
+        n.is_read,
+        n.read_at,
+        n.batch_count,
+        n.created_at,
+        n.updated_at
+    FROM notifications n
+    LEFT JOIN users a ON a.id = n.actor_id
+"#;
+
+/// Event types that also go out as Web Push messages to subscribed browsers.
+const PUSH_EVENT_TYPES: &[&str] = &[
+    NOTIFICATION_REVIEW_INVITATION,
+    NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE,
+    NOTIFICATION_EDITORIAL_DECISION,
+    NOTIFICATION_APPEAL_RESOLVED,
+];
+
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: i64,
+    pub actor_id: Option<i64>,
+    pub event_type: &'static str,
+    pub post_id: Option<i64>,
+    pub comment_id: Option<i64>,
+    pub message: String,
+}
+
+/// Stores in-app notifications and pushes the high-priority ones to the
+/// user's browsers. Users are never notified about their own actions or
+/// about event types they switched off in-app, and failures are logged
+/// rather than surfaced so the action that triggered the notification still
+/// succeeds. Batched event types are folded into a matching unread
+/// notification from the current window instead of adding a new row.
+pub async fn dispatch_notifications(pool: &MySqlPool, notifications: Vec<NewNotification>) {
+    let now = Utc::now();
+    let batch_window_secs = notification_batch_window_secs();
+    for notification in notifications {
+        if notification.actor_id == Some(notification.user_id) {
+            continue;
+        }
+        match wants_in_app(pool, notification.user_id, notification.event_type).await {
+            Ok(true) => {}
+            Ok(false) => continue,
+            // Deliver anyway rather than lose the notification.
+            Err(error) => tracing::warn!(
+                "Failed to load notification preferences for user {}: {}",
+                notification.user_id,
+                error
+            ),
+        }
+
+        let message: String = notification
+            .message
+            .chars()
+            .take(MAX_MESSAGE_CHARS)
+            .collect();
+        let dedup_key = notification_dedup_key(&notification);
+        if let Some(dedup_key) = dedup_key.as_deref().filter(|_| batch_window_secs > 0) {
+            let window_start = now - Duration::seconds(batch_window_secs);
+            match merge_into_batch(pool, &notification, dedup_key, &message, window_start, now)
+                .await
+            {
+                Ok(Some(notification_id)) => {
+                    publish_notification(notification.user_id, notification_id);
+                    continue;
+                }
+                Ok(None) => {}
+                // Fall back to a separate notification.
+                Err(error) => tracing::warn!(
+                    "Failed to batch {} notification for user {}: {}",
+                    notification.event_type,
+                    notification.user_id,
+                    error
+                ),
+            }
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO notifications
+                (user_id, actor_id, event_type, post_id, comment_id, message, is_read,
+                 dedup_key, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, FALSE, ?, ?)
+            "#,
+        )
+        .bind(notification.user_id)
+        .bind(notification.actor_id)
+        .bind(notification.event_type)
+        .bind(notification.post_id)
+        .bind(notification.comment_id)
+        .bind(&message)
+        .bind(dedup_key.as_deref())
+        .bind(now)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(result) => {
+                publish_notification(notification.user_id, result.last_insert_id() as i64);
+                if PUSH_EVENT_TYPES.contains(&notification.event_type) {
+                    queue_push(
+                        pool,
+                        notification.user_id,
+                        PushMessage {
+                            event_type: notification.event_type.to_string(),
+                            message,
+                            post_id: notification.post_id,
+                        },
+                    );
+                }
+            }
+            Err(error) => tracing::warn!(
+                "Failed to store {} notification for user {}: {}",
+                notification.event_type,
+                notification.user_id,
+                error
+            ),
+        }
+    }
+}
+
+/// The key shared by notifications that may collapse into one: same event
+/// type on the same post. Other event types are never batched.
+fn notification_dedup_key(notification: &NewNotification) -> Option<String> {
+    if !BATCHED_EVENT_TYPES.contains(&notification.event_type) {
+        return None;
+    }
+    notification
+        .post_id
+        .map(|post_id| format!("{}:post:{}", notification.event_type, post_id))
+}
+
+/// Folds the notification into the user's newest unread one with the same
+/// key created since `window_start`. The batch keeps the latest actor,
+/// comment and message, with a count of the events it stands for. Returns
+/// the batch's id, or `None` when there is no open batch.
+async fn merge_into_batch(
+    pool: &MySqlPool,
+    notification: &NewNotification,
+    dedup_key: &str,
+    message: &str,
+    window_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<Option<i64>, sqlx::Error> {
+    let batch: Option<(i64, i32)> = sqlx::query_as(
+        r#"
+        SELECT id, batch_count
+        FROM notifications
+        WHERE user_id = ? AND dedup_key = ? AND is_read = FALSE AND created_at >= ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(notification.user_id)
+    .bind(dedup_key)
+    .bind(window_start)
+    .fetch_optional(pool)
+    .await?;
+    let Some((notification_id, batch_count)) = batch else {
+        return Ok(None);
+    };
+
+    let suffix = format!(" (+{} more)", batch_count);
+    let mut batched_message: String = message
+        .chars()
+        .take(MAX_MESSAGE_CHARS - suffix.chars().count())
+        .collect();
+    batched_message.push_str(&suffix);
+    sqlx::query(
+        r#"
+        UPDATE notifications
+        SET actor_id = ?, comment_id = ?, message = ?, batch_count = batch_count + 1,
+            updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(notification.actor_id)
+    .bind(notification.comment_id)
+    .bind(&batched_message)
+    .bind(now)
+    .bind(notification_id)
+    .execute(pool)
+    .await?;
+    Ok(Some(notification_id))
+}
+
+fn notification_batch_window_secs() -> i64 {
+    std::env::var("NOTIFICATION_BATCH_WINDOW_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value >= 0)
+        .unwrap_or(DEFAULT_NOTIFICATION_BATCH_WINDOW_SECS)
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use super::{
+    NOTIFICATION_APPEAL_RESOLVED, NOTIFICATION_APPEAL_SUBMITTED, NOTIFICATION_DEADLINE_OVERDUE,
+    NOTIFICATION_DEADLINE_REMINDER, NOTIFICATION_EDITORIAL_DECISION, NOTIFICATION_ERASURE_REJECTED,
+    NOTIFICATION_ERASURE_REQUESTED, NOTIFICATION_FOLLOWED_AUTHOR_POST, NOTIFICATION_MENTION,
+    NOTIFICATION_NEW_COMMENT, NOTIFICATION_NEW_FOLLOWER, NOTIFICATION_REPLY,
+    NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE, NOTIFICATION_REVIEW_INVITATION,
+    NOTIFICATION_REVISION_EXPIRED, NOTIFICATION_REVISION_EXPIRY_WARNING,
+};
+use crate::models::NotificationPreference;
+
+/// Event types users can configure, with their default `(in_app, email)`
+/// channels. Email defaults on only for events that ask something of the
+/// user or settle their submission. Broadcasts are not listed: admins pick
+/// their audience, so they always arrive in-app.
+pub const NOTIFICATION_PREFERENCE_DEFAULTS: &[(&str, bool, bool)] = &[
+    (NOTIFICATION_MENTION, true, false),
+    (NOTIFICATION_REPLY, true, false),
+    (NOTIFICATION_NEW_COMMENT, true, false),
+    (NOTIFICATION_REVIEW_INVITATION, true, true),
+    (NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE, true, false),
+    (NOTIFICATION_EDITORIAL_DECISION, true, true),
+    (NOTIFICATION_DEADLINE_REMINDER, true, true),
+    (NOTIFICATION_DEADLINE_OVERDUE, true, true),
+    (NOTIFICATION_APPEAL_SUBMITTED, true, false),
+    (NOTIFICATION_APPEAL_RESOLVED, true, true),
+    (NOTIFICATION_REVISION_EXPIRY_WARNING, true, true),
+    (NOTIFICATION_REVISION_EXPIRED, true, true),
+    (NOTIFICATION_ERASURE_REQUESTED, true, false),
+    (NOTIFICATION_ERASURE_REJECTED, true, true),
+    (NOTIFICATION_NEW_FOLLOWER, true, false),
+    (NOTIFICATION_FOLLOWED_AUTHOR_POST, true, false),
+];
+
+pub fn is_configurable_event_type(event_type: &str) -> bool {
+    NOTIFICATION_PREFERENCE_DEFAULTS
+        .iter()
+        .any(|(configurable, _, _)| *configurable == event_type)
+}
+
+/// Every configurable event type with the user's saved choice, or the
+/// default where they have not made one.
+pub async fn fetch_notification_preferences(
+    pool: &MySqlPool,
+    user_id: i64,
+) -> Result<Vec<NotificationPreference>, sqlx::Error> {
+    let saved: HashMap<String, (bool, bool)> = sqlx::query_as::<_, (String, bool, bool)>(
+        "SELECT event_type, in_app, email FROM notification_preferences WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(event_type, in_app, email)| (event_type, (in_app, email)))
+    .collect();
+
+    Ok(NOTIFICATION_PREFERENCE_DEFAULTS
+        .iter()
+        .map(|(event_type, default_in_app, default_email)| {
+            let (in_app, email) = saved
+                .get(*event_type)
+                .copied()
+                .unwrap_or((*default_in_app, *default_email));
+            NotificationPreference {
+                event_type: event_type.to_string(),
+                in_app,
+                email,
+            }
+        })
+        .collect())
+}
+
+/// Stores the complete setting for each given event type, so later changes
+/// to the defaults do not flip a choice the user already made.
+pub async fn save_notification_preferences(
+    pool: &MySqlPool,
+    user_id: i64,
+    preferences: &[NotificationPreference],
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+    for preference in preferences {
+        sqlx::query(
+            r#"
+            INSERT INTO notification_preferences (user_id, event_type, in_app, email, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                in_app = VALUES(in_app),
+                email = VALUES(email),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(user_id)
+        .bind(&preference.event_type)
+        .bind(preference.in_app)
+        .bind(preference.email)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Whether `event_type` should be stored as an in-app notification for the
+/// user. Event types outside the configurable set are always delivered.
+pub(super) async fn wants_in_app(
+    pool: &MySqlPool,
+    user_id: i64,
+    event_type: &str,
+) -> Result<bool, sqlx::Error> {
+    let Some((_, default_in_app, _)) = NOTIFICATION_PREFERENCE_DEFAULTS
+        .iter()
+        .find(|(configurable, _, _)| *configurable == event_type)
+    else {
+        return Ok(true);
+    };
+
+    let saved: Option<bool> = sqlx::query_scalar(
+        "SELECT in_app FROM notification_preferences WHERE user_id = ? AND event_type = ?",
+    )
+    .bind(user_id)
+    .bind(event_type)
+    .fetch_optional(pool)
+    .await?;
+    Ok(saved.unwrap_or(*default_in_app))
+}
@@ -0,0 +1,278 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::tasks;
+
+use super::{
+    NOTIFICATION_DEADLINE_OVERDUE, NOTIFICATION_DEADLINE_REMINDER, NewNotification,
+    dispatch_notifications,
+};
+
+pub const DEFAULT_DEADLINE_REMINDER_INTERVAL_SECS: u64 = 3_600;
+pub const DEFAULT_DEADLINE_REMINDER_LEAD_HOURS: i64 = 48;
+
+const ASSIGNMENT_DEADLINE_SELECT: &str = r#"
+    SELECT
+        ra.id AS id,
+        ra.post_id AS post_id,
+        ra.reviewer_id AS recipient_id,
+        ra.assigned_by AS editor_id,
+        ra.due_at AS due_at,
+        p.title AS title
+    FROM reviewer_assignments ra
+    JOIN posts p ON p.id = ra.post_id
+    WHERE ra.status IN ('invited', 'accepted')
+      AND ra.due_at IS NOT NULL
+"#;
+
+// Only the latest decision counts, and only while the author has not
+// submitted a new version since it was made.
+const REVISION_DEADLINE_SELECT: &str = r#"
+    SELECT
+        ed.id AS id,
+        ed.post_id AS post_id,
+        p.author_id AS recipient_id,
+        ed.editor_id AS editor_id,
+        ed.revision_due_at AS due_at,
+        p.title AS title
+    FROM editorial_decisions ed
+    JOIN posts p ON p.id = ed.post_id
+    WHERE ed.revision_due_at IS NOT NULL
+      AND p.paper_status = 'revision'
+      AND p.latest_paper_version_id <=> ed.paper_version_id
+      AND ed.id = (SELECT MAX(ed2.id) FROM editorial_decisions ed2 WHERE ed2.post_id = ed.post_id)
+"#;
+
+#[derive(Debug, FromRow)]
+struct DeadlineRow {
+    id: i64,
+    post_id: i64,
+    recipient_id: i64,
+    editor_id: Option<i64>,
+    due_at: DateTime<Utc>,
+    title: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DeadlineKind {
+    ReviewAssignment,
+    RevisionRequest,
+}
+
+impl DeadlineKind {
+    fn table(self) -> &'static str {
+        match self {
+            Self::ReviewAssignment => "reviewer_assignments",
+            Self::RevisionRequest => "editorial_decisions",
+        }
+    }
+
+    fn select_sql(self) -> &'static str {
+        match self {
+            Self::ReviewAssignment => ASSIGNMENT_DEADLINE_SELECT,
+            Self::RevisionRequest => REVISION_DEADLINE_SELECT,
+        }
+    }
+
+    fn due_column(self) -> &'static str {
+        match self {
+            Self::ReviewAssignment => "ra.due_at",
+            Self::RevisionRequest => "ed.revision_due_at",
+        }
+    }
+
+    fn table_alias(self) -> &'static str {
+        match self {
+            Self::ReviewAssignment => "ra",
+            Self::RevisionRequest => "ed",
+        }
+    }
+
+    fn subject(self, title: &str) -> String {
+        match self {
+            Self::ReviewAssignment => format!("Your review of \"{}\"", title),
+            Self::RevisionRequest => format!("The revision of \"{}\"", title),
+        }
+    }
+
+    fn editor_subject(self, title: &str) -> String {
+        match self {
+            Self::ReviewAssignment => format!("A review you assigned for \"{}\"", title),
+            Self::RevisionRequest => format!("The revision you requested for \"{}\"", title),
+        }
+    }
+}
+
+/// Starts the periodic job that reminds reviewers and authors of upcoming
+/// and missed deadlines. Setting `REVIEW_DEADLINE_REMINDER_INTERVAL_SECS=0`
+/// disables the job.
+pub fn spawn_deadline_reminders(pool: MySqlPool) {
+    let interval_secs = reminder_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Review deadline reminder job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "deadline_reminders",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match send_deadline_reminders(&pool).await {
+                    Ok(0) => {}
+                    Ok(sent) => tracing::info!("Sent {} review deadline reminders", sent),
+                    Err(error) => tracing::error!("Review deadline reminders failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Sends at most one "due soon" reminder and one overdue notice per deadline.
+/// Each row is marked before notifying, so an overlapping run cannot send the
+/// same reminder twice. Returns the number of deadlines handled.
+pub async fn send_deadline_reminders(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let now = Utc::now();
+    let remind_before = now + chrono::Duration::hours(reminder_lead_hours());
+    let mut handled = 0;
+
+    for kind in [
+        DeadlineKind::ReviewAssignment,
+        DeadlineKind::RevisionRequest,
+    ] {
+        let due_soon_sql = format!(
+            "{} AND {alias}.reminder_sent_at IS NULL AND {due} > ? AND {due} <= ?",
+            kind.select_sql(),
+            alias = kind.table_alias(),
+            due = kind.due_column()
+        );
+        let due_soon = sqlx::query_as::<_, DeadlineRow>(&due_soon_sql)
+            .bind(now)
+            .bind(remind_before)
+            .fetch_all(pool)
+            .await?;
+        let mark_reminded_sql = format!(
+            "UPDATE {} SET reminder_sent_at = ? WHERE id = ? AND reminder_sent_at IS NULL",
+            kind.table()
+        );
+        for row in due_soon {
+            let marked = sqlx::query(&mark_reminded_sql)
+                .bind(now)
+                .bind(row.id)
+                .execute(pool)
+                .await?;
+            if marked.rows_affected() == 0 {
+                continue;
+            }
+
+            let message = format!(
+                "{} is due {}",
+                kind.subject(&row.title),
+                format_due(row.due_at)
+            );
+            dispatch_notifications(
+                pool,
+                vec![deadline_notification(
+                    &row,
+                    row.recipient_id,
+                    NOTIFICATION_DEADLINE_REMINDER,
+                    message,
+                )],
+            )
+            .await;
+            handled += 1;
+        }
+
+        let overdue_sql = format!(
+            "{} AND {alias}.overdue_notified_at IS NULL AND {due} <= ?",
+            kind.select_sql(),
+            alias = kind.table_alias(),
+            due = kind.due_column()
+        );
+        let overdue = sqlx::query_as::<_, DeadlineRow>(&overdue_sql)
+            .bind(now)
+            .fetch_all(pool)
+            .await?;
+        let mark_overdue_sql = format!(
+            "UPDATE {} SET overdue_notified_at = ?, reminder_sent_at = COALESCE(reminder_sent_at, ?) WHERE id = ? AND overdue_notified_at IS NULL",
+            kind.table()
+        );
+        for row in overdue {
+            let marked = sqlx::query(&mark_overdue_sql)
+                .bind(now)
+                .bind(now)
+                .bind(row.id)
+                .execute(pool)
+                .await?;
+            if marked.rows_affected() == 0 {
+                continue;
+            }
+
+            let due = format_due(row.due_at);
+            let mut notifications = vec![deadline_notification(
+                &row,
+                row.recipient_id,
+                NOTIFICATION_DEADLINE_OVERDUE,
+                format!(
+                    "{} was due {} and is overdue",
+                    kind.subject(&row.title),
+                    due
+                ),
+            )];
+            if let Some(editor_id) = row.editor_id {
+                notifications.push(deadline_notification(
+                    &row,
+                    editor_id,
+                    NOTIFICATION_DEADLINE_OVERDUE,
+                    format!(
+                        "{} was due {} and is overdue",
+                        kind.editor_subject(&row.title),
+                        due
+                    ),
+                ));
+            }
+            dispatch_notifications(pool, notifications).await;
+            handled += 1;
+        }
+    }
+
+    Ok(handled)
+}
+
+fn deadline_notification(
+    row: &DeadlineRow,
+    user_id: i64,
+    event_type: &'static str,
+    message: String,
+) -> NewNotification {
+    NewNotification {
+        user_id,
+        actor_id: None,
+        event_type,
+        post_id: Some(row.post_id),
+        comment_id: None,
+        message,
+    }
+}
+
+fn format_due(due_at: DateTime<Utc>) -> String {
+    due_at.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+fn reminder_interval_secs() -> u64 {
+    std::env::var("REVIEW_DEADLINE_REMINDER_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_DEADLINE_REMINDER_INTERVAL_SECS)
+}
+
+fn reminder_lead_hours() -> i64 {
+    std::env::var("REVIEW_DEADLINE_REMINDER_LEAD_HOURS")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|hours| *hours >= 0)
+        .unwrap_or(DEFAULT_DEADLINE_REMINDER_LEAD_HOURS)
+}
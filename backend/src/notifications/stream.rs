@@ -0,0 +1,158 @@
+use std::convert::Infallible;
+use std::sync::OnceLock;
+
+use axum::response::sse::Event;
+use sqlx::MySqlPool;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::NOTIFICATION_SELECT;
+use crate::models::NotificationResponse;
+use crate::tasks;
+
+const INBOX_CHANGE_CAPACITY: usize = 1024;
+const CLIENT_CHANNEL_CAPACITY: usize = 16;
+
+/// A change to one user's inbox: a notification was created or updated, or
+/// only the unread count moved. Changes reach the streams open on this
+/// server instance only.
+#[derive(Debug, Clone, Copy)]
+struct InboxChange {
+    user_id: i64,
+    notification_id: Option<i64>,
+}
+
+fn inbox_changes() -> &'static broadcast::Sender<InboxChange> {
+    static CHANNEL: OnceLock<broadcast::Sender<InboxChange>> = OnceLock::new();
+    CHANNEL.get_or_init(|| broadcast::channel(INBOX_CHANGE_CAPACITY).0)
+}
+
+/// Sends the notification and the new unread count to the user's open
+/// streams. Nothing happens when no stream is listening.
+pub(super) fn publish_notification(user_id: i64, notification_id: i64) {
+    let _ = inbox_changes().send(InboxChange {
+        user_id,
+        notification_id: Some(notification_id),
+    });
+}
+
+/// Sends the user's current unread count to their open streams, e.g. after
+/// notifications were marked read.
+pub fn publish_unread_count(user_id: i64) {
+    let _ = inbox_changes().send(InboxChange {
+        user_id,
+        notification_id: None,
+    });
+}
+
+/// Server-sent events for one user: an `unread` event with the current
+/// count right away, then a `notification` event for every notification
+/// created or updated and an `unread` event after every change.
+pub fn notification_stream(
+    pool: MySqlPool,
+    user_id: i64,
+) -> ReceiverStream<Result<Event, Infallible>> {
+    let mut changes = inbox_changes().subscribe();
+    let (sender, receiver) = mpsc::channel(CLIENT_CHANNEL_CAPACITY);
+
+    tokio::spawn(async move {
+        if !send_unread_count(&pool, &sender, user_id).await {
+            return;
+        }
+        loop {
+            let change = tokio::select! {
+                _ = sender.closed() => return,
+                // Ends the stream so open connections do not hold up shutdown;
+                // clients reconnect to the next instance.
+                _ = tasks::shutdown_requested() => return,
+                change = changes.recv() => change,
+            };
+            let notification_id = match change {
+                Ok(change) if change.user_id == user_id => change.notification_id,
+                Ok(_) => continue,
+                // Some changes were dropped; the unread count still catches
+                // the client up.
+                Err(broadcast::error::RecvError::Lagged(_)) => None,
+                Err(broadcast::error::RecvError::Closed) => return,
+            };
+            if let Some(notification_id) = notification_id
+                && !send_notification(&pool, &sender, user_id, notification_id).await
+            {
+                return;
+            }
+            if !send_unread_count(&pool, &sender, user_id).await {
+                return;
+            }
+        }
+    });
+
+    ReceiverStream::new(receiver)
+}
+
+/// Each send returns `false` once the client has gone away. Database errors
+/// are logged and skipped so the stream stays open.
+async fn send_notification(
+    pool: &MySqlPool,
+    sender: &mpsc::Sender<Result<Event, Infallible>>,
+    user_id: i64,
+    notification_id: i64,
+) -> bool {
+    let notification = sqlx::query_as::<_, NotificationResponse>(&format!(
+        "{} WHERE n.id = ? AND n.user_id = ?",
+        NOTIFICATION_SELECT
+    ))
+    .bind(notification_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await;
+    match notification {
+        Ok(Some(notification)) => send_event(sender, "notification", &notification).await,
+        Ok(None) => true,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to load notification {} for stream: {}",
+                notification_id,
+                error
+            );
+            true
+        }
+    }
+}
+
+async fn send_unread_count(
+    pool: &MySqlPool,
+    sender: &mpsc::Sender<Result<Event, Infallible>>,
+    user_id: i64,
+) -> bool {
+    let unread = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND is_read = FALSE",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await;
+    match unread {
+        Ok(unread) => send_event(sender, "unread", &serde_json::json!({ "unread": unread })).await,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to count unread notifications for user {}: {}",
+                user_id,
+                error
+            );
+            true
+        }
+    }
+}
+
+async fn send_event<T: serde::Serialize>(
+    sender: &mpsc::Sender<Result<Event, Infallible>>,
+    name: &str,
+    data: &T,
+) -> bool {
+    match Event::default().event(name).json_data(data) {
+        Ok(event) => sender.send(Ok(event)).await.is_ok(),
+        Err(error) => {
+            tracing::warn!("Failed to encode {} stream event: {}", name, error);
+            true
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use super::{
+    NOTIFICATION_NEW_COMMENT, NOTIFICATION_REPLY, NewNotification, dispatch_notifications,
+};
+
+/// Upper bound on how far a reply walks up its parent chain when looking for
+/// followed threads.
+const MAX_THREAD_DEPTH: i64 = 64;
+
+/// Follows a whole post (`comment_id = None`) or the replies under one
+/// comment. Returns `false` when the subscription already existed.
+pub async fn subscribe(
+    pool: &MySqlPool,
+    user_id: i64,
+    post_id: i64,
+    comment_id: Option<i64>,
+) -> Result<bool, sqlx::Error> {
+    // The unique key does not cover NULL comment ids, so post-level
+    // subscriptions are deduplicated here.
+    let result = sqlx::query(
+        r#"
+        INSERT INTO subscriptions (user_id, post_id, comment_id, created_at)
+        SELECT ?, ?, ?, ?
+        FROM DUAL
+        WHERE NOT EXISTS (
+            SELECT 1 FROM subscriptions
+            WHERE user_id = ? AND post_id = ? AND comment_id <=> ?
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(post_id)
+    .bind(comment_id)
+    .bind(Utc::now())
+    .bind(user_id)
+    .bind(post_id)
+    .bind(comment_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns `false` when there was nothing to remove.
+pub async fn unsubscribe(
+    pool: &MySqlPool,
+    user_id: i64,
+    post_id: i64,
+    comment_id: Option<i64>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM subscriptions WHERE user_id = ? AND post_id = ? AND comment_id <=> ?",
+    )
+    .bind(user_id)
+    .bind(post_id)
+    .bind(comment_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Returns whether the user follows the post and which comment threads on it
+/// they follow.
+pub async fn fetch_subscriptions(
+    pool: &MySqlPool,
+    user_id: i64,
+    post_id: i64,
+) -> Result<(bool, Vec<i64>), sqlx::Error> {
+    let rows: Vec<(Option<i64>,)> = sqlx::query_as(
+        "SELECT comment_id FROM subscriptions WHERE user_id = ? AND post_id = ? ORDER BY comment_id ASC",
+    )
+    .bind(user_id)
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    let following_post = rows.iter().any(|(comment_id,)| comment_id.is_none());
+    let comment_ids = rows
+        .into_iter()
+        .filter_map(|(comment_id,)| comment_id)
+        .collect();
+    Ok((following_post, comment_ids))
+}
+
+/// Notifies users following the post or any thread above a new comment. Each
+/// user gets at most one notification; following the thread takes precedence
+/// over following the post. Users in `skip_user_ids` (for example, those
+/// already notified about a mention) are left out.
+pub async fn notify_comment_subscribers(
+    pool: &MySqlPool,
+    post_id: i64,
+    comment_id: i64,
+    parent_comment_id: Option<i64>,
+    actor: (i64, &str),
+    skip_user_ids: &[i64],
+) {
+    let rows: Vec<(i64, Option<i64>)> = match sqlx::query_as(
+        r#"
+        WITH RECURSIVE ancestors (id, parent_comment_id, depth) AS (
+            SELECT id, parent_comment_id, 1
+            FROM comments
+            WHERE id = ?
+            UNION ALL
+            SELECT c.id, c.parent_comment_id, a.depth + 1
+            FROM comments c
+            JOIN ancestors a ON c.id = a.parent_comment_id
+            WHERE a.depth < ?
+        )
+        SELECT s.user_id, s.comment_id
+        FROM subscriptions s
+        WHERE s.post_id = ?
+          AND (s.comment_id IS NULL OR s.comment_id IN (SELECT id FROM ancestors))
+        "#,
+    )
+    .bind(parent_comment_id)
+    .bind(MAX_THREAD_DEPTH)
+    .bind(post_id)
+    .fetch_all(pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to load subscribers for comment {} on post {}: {}",
+                comment_id,
+                post_id,
+                error
+            );
+            return;
+        }
+    };
+
+    let mut follows_thread: HashMap<i64, bool> = HashMap::new();
+    for (user_id, thread_comment_id) in rows {
+        if skip_user_ids.contains(&user_id) {
+            continue;
+        }
+        *follows_thread.entry(user_id).or_default() |= thread_comment_id.is_some();
+    }
+
+    let (actor_id, actor_username) = actor;
+    let notifications = follows_thread
+        .into_iter()
+        .map(|(user_id, in_thread)| {
+            let (event_type, message) = if in_thread {
+                (
+                    NOTIFICATION_REPLY,
+                    format!("@{} replied in a thread you follow", actor_username),
+                )
+            } else {
+                (
+                    NOTIFICATION_NEW_COMMENT,
+                    format!("@{} commented on a post you follow", actor_username),
+                )
+            };
+            NewNotification {
+                user_id,
+                actor_id: Some(actor_id),
+                event_type,
+                post_id: Some(post_id),
+                comment_id: Some(comment_id),
+                message,
+            }
+        })
+        .collect();
+
+    dispatch_notifications(pool, notifications).await;
+}
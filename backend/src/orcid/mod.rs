@@ -0,0 +1,657 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, Datelike, Utc};
+use reqwest::{Client, StatusCode as HttpStatusCode, header};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::models::{ORCID_WORK_FAILED, ORCID_WORK_SYNCED};
+use crate::tasks;
+
+pub const DEFAULT_ORCID_SYNC_INTERVAL_SECS: u64 = 21_600;
+pub const DEFAULT_ORCID_TIMEOUT_SECS: u64 = 15;
+
+const DEFAULT_ORCID_BASE_URL: &str = "https://orcid.org";
+const DEFAULT_ORCID_API_URL: &str = "https://api.orcid.org/v3.0";
+const ORCID_SCOPE: &str = "/activities/update";
+const ORCID_CONTENT_TYPE: &str = "application/vnd.orcid+json";
+const JOURNAL_NAME: &str = "Thought Manifold";
+// ORCID rejects longer short descriptions.
+const MAX_SHORT_DESCRIPTION_CHARS: usize = 5_000;
+const REVOKED_ERROR: &str = "ORCID access was revoked; link the ORCID iD again";
+
+/// Member API credentials. ORCID_BASE_URL and ORCID_API_URL point at the
+/// sandbox (`https://sandbox.orcid.org`, `https://api.sandbox.orcid.org/v3.0`)
+/// during development.
+#[derive(Debug, Clone)]
+pub struct OrcidConfig {
+    client_id: String,
+    client_secret: String,
+    base_url: String,
+    api_url: String,
+    redirect_uri: String,
+}
+
+impl OrcidConfig {
+    pub fn from_env() -> Option<Self> {
+        let read = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .map(|value| value.trim().trim_end_matches('/').to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        Some(Self {
+            client_id: read("ORCID_CLIENT_ID")?,
+            client_secret: read("ORCID_CLIENT_SECRET")?,
+            base_url: read("ORCID_BASE_URL").unwrap_or_else(|| DEFAULT_ORCID_BASE_URL.to_string()),
+            api_url: read("ORCID_API_URL").unwrap_or_else(|| DEFAULT_ORCID_API_URL.to_string()),
+            redirect_uri: read("ORCID_REDIRECT_URI")
+                .unwrap_or_else(|| "http://localhost:8000/api/users/me/orcid/callback".to_string()),
+        })
+    }
+
+    pub fn authorize_url(&self, state: &str) -> String {
+        format!(
+            "{}/oauth/authorize?client_id={}&response_type=code&scope={}&redirect_uri={}&state={}",
+            self.base_url,
+            urlencoding::encode(&self.client_id),
+            urlencoding::encode(ORCID_SCOPE),
+            urlencoding::encode(&self.redirect_uri),
+            urlencoding::encode(state)
+        )
+    }
+
+    /// Trades the authorization code from the OAuth callback for a token
+    /// that may write to the user's works.
+    pub async fn exchange_code(&self, client: &Client, code: &str) -> anyhow::Result<OrcidToken> {
+        let response = client
+            .post(format!("{}/oauth/token", self.base_url))
+            .header(header::ACCEPT, "application/json")
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.redirect_uri.as_str()),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("ORCID token exchange returned {}: {}", status, body);
+        }
+
+        let token = response.json::<OrcidToken>().await?;
+        if !is_orcid_id(&token.orcid) {
+            anyhow::bail!("ORCID returned an invalid iD: {}", token.orcid);
+        }
+        Ok(token)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrcidToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_in: Option<i64>,
+    pub scope: Option<String>,
+    pub orcid: String,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WorkSyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub failed: usize,
+    pub revoked: bool,
+}
+
+#[derive(Debug, FromRow)]
+struct OrcidLinkRow {
+    orcid_id: String,
+    access_token: String,
+}
+
+#[derive(Debug, FromRow)]
+struct PublishedPaperRow {
+    id: i64,
+    title: String,
+    summary: Option<String>,
+    published_on: DateTime<Utc>,
+    registered_doi: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct ExistingWorkRow {
+    post_id: i64,
+    put_code: Option<String>,
+    payload_hash: Option<String>,
+    status: String,
+}
+
+enum WorkRequestError {
+    Revoked,
+    Failed(String),
+}
+
+pub fn orcid_client() -> reqwest::Result<Client> {
+    Client::builder()
+        .timeout(Duration::from_secs(orcid_timeout_secs()))
+        .user_agent("ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)")
+        .build()
+}
+
+/// Starts the periodic job that pushes the published papers of every user
+/// who opted in to their ORCID record. Setting `ORCID_SYNC_INTERVAL_SECS=0`,
+/// or leaving the ORCID credentials unset, disables the job.
+pub fn spawn_orcid_sync(pool: MySqlPool) {
+    let interval_secs = sync_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("ORCID sync is disabled");
+        return;
+    }
+    let Some(config) = OrcidConfig::from_env() else {
+        tracing::info!("ORCID sync is not configured");
+        return;
+    };
+
+    tasks::spawn_periodic(
+        "orcid_sync",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            let config = config.clone();
+            async move {
+                match run_orcid_sync(&pool, &config).await {
+                    Ok(total)
+                        if total.created + total.updated + total.removed + total.failed == 0 => {}
+                    Ok(total) => tracing::info!(
+                        "ORCID sync finished: created={}, updated={}, removed={}, failed={}",
+                        total.created,
+                        total.updated,
+                        total.removed,
+                        total.failed
+                    ),
+                    Err(error) => tracing::error!("ORCID sync failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+async fn run_orcid_sync(pool: &MySqlPool, config: &OrcidConfig) -> anyhow::Result<WorkSyncSummary> {
+    let user_ids: Vec<i64> = sqlx::query_scalar(
+        r#"
+        SELECT user_id
+        FROM user_orcid_links
+        WHERE sync_enabled = TRUE
+        ORDER BY last_synced_at IS NOT NULL, last_synced_at ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    if user_ids.is_empty() {
+        return Ok(WorkSyncSummary::default());
+    }
+
+    let client = orcid_client()?;
+    let mut total = WorkSyncSummary::default();
+    for user_id in user_ids {
+        match sync_user_works(pool, config, &client, user_id).await {
+            Ok(summary) => {
+                total.created += summary.created;
+                total.updated += summary.updated;
+                total.removed += summary.removed;
+                total.failed += summary.failed;
+            }
+            Err(error) => tracing::warn!("ORCID sync failed for user {}: {}", user_id, error),
+        }
+    }
+
+    Ok(total)
+}
+
+/// Brings the user's ORCID works in line with their published papers:
+/// new papers are added, changed ones updated and papers that were
+/// unpublished or deleted are removed again. Works the user added on ORCID
+/// themselves are never touched.
+pub async fn sync_user_works(
+    pool: &MySqlPool,
+    config: &OrcidConfig,
+    client: &Client,
+    user_id: i64,
+) -> anyhow::Result<WorkSyncSummary> {
+    let Some(link) = sqlx::query_as::<_, OrcidLinkRow>(
+        "SELECT orcid_id, access_token FROM user_orcid_links WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(WorkSyncSummary::default());
+    };
+
+    let papers = sqlx::query_as::<_, PublishedPaperRow>(
+        r#"
+        SELECT
+            p.id,
+            p.title,
+            p.summary,
+            COALESCE(p.published_at, p.created_at) AS published_on,
+            (SELECT r.registered_doi FROM post_doi_registrations r WHERE r.post_id = p.id) AS registered_doi
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.author_id = ?
+          AND c.code = 'paper'
+          AND p.paper_status = 'published'
+          AND p.is_published = TRUE
+          AND p.deleted_at IS NULL
+        ORDER BY p.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    let mut existing: HashMap<i64, ExistingWorkRow> = sqlx::query_as::<_, ExistingWorkRow>(
+        "SELECT post_id, put_code, payload_hash, status FROM orcid_work_syncs WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.post_id, row))
+    .collect();
+
+    let mut summary = WorkSyncSummary::default();
+    for paper in &papers {
+        let work = build_work_payload(paper);
+        let payload_hash = format!("{:x}", Sha256::digest(work.to_string().as_bytes()));
+        let previous = existing.remove(&paper.id);
+        let put_code = previous.as_ref().and_then(|row| row.put_code.clone());
+        if previous.as_ref().is_some_and(|row| {
+            row.status == ORCID_WORK_SYNCED && row.payload_hash.as_deref() == Some(&payload_hash)
+        }) {
+            continue;
+        }
+
+        let result = match put_code.as_deref() {
+            Some(put_code) => update_work(config, client, &link, put_code, work)
+                .await
+                .map(|_| put_code.to_string()),
+            None => create_work(config, client, &link, &work).await,
+        };
+        match result {
+            Ok(put_code) => {
+                if previous
+                    .as_ref()
+                    .and_then(|row| row.put_code.as_ref())
+                    .is_some()
+                {
+                    summary.updated += 1;
+                } else {
+                    summary.created += 1;
+                }
+                record_work(
+                    pool,
+                    user_id,
+                    paper.id,
+                    Some(&put_code),
+                    Some(&payload_hash),
+                    None,
+                )
+                .await?;
+            }
+            Err(WorkRequestError::Revoked) => {
+                summary.revoked = true;
+                break;
+            }
+            Err(WorkRequestError::Failed(message)) => {
+                summary.failed += 1;
+                record_work(
+                    pool,
+                    user_id,
+                    paper.id,
+                    put_code.as_deref(),
+                    None,
+                    Some(&message),
+                )
+                .await?;
+            }
+        }
+    }
+
+    if !summary.revoked {
+        for (post_id, row) in existing {
+            let result = match row.put_code.as_deref() {
+                Some(put_code) => delete_work(config, client, &link, put_code).await,
+                None => Ok(()),
+            };
+            match result {
+                Ok(()) => {
+                    sqlx::query("DELETE FROM orcid_work_syncs WHERE user_id = ? AND post_id = ?")
+                        .bind(user_id)
+                        .bind(post_id)
+                        .execute(pool)
+                        .await?;
+                    summary.removed += 1;
+                }
+                Err(WorkRequestError::Revoked) => {
+                    summary.revoked = true;
+                    break;
+                }
+                Err(WorkRequestError::Failed(message)) => {
+                    summary.failed += 1;
+                    record_work(
+                        pool,
+                        user_id,
+                        post_id,
+                        row.put_code.as_deref(),
+                        None,
+                        Some(&message),
+                    )
+                    .await?;
+                }
+            }
+        }
+    }
+
+    // A revoked token cannot be used again, so syncing stops until the user
+    // links the iD anew.
+    let (sync_error, keep_enabled) = if summary.revoked {
+        (Some(REVOKED_ERROR.to_string()), false)
+    } else if summary.failed > 0 {
+        (
+            Some(format!("{} works failed to sync", summary.failed)),
+            true,
+        )
+    } else {
+        (None, true)
+    };
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        UPDATE user_orcid_links
+        SET last_synced_at = ?, last_sync_error = ?, sync_enabled = sync_enabled AND ?, updated_at = ?
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(now)
+    .bind(sync_error)
+    .bind(keep_enabled)
+    .bind(now)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(summary)
+}
+
+/// Removes every synced work from the user's ORCID record, e.g. before the
+/// link is deleted. Failures are logged; the works stay on ORCID.
+pub async fn remove_user_works(
+    pool: &MySqlPool,
+    config: &OrcidConfig,
+    client: &Client,
+    user_id: i64,
+) -> Result<(), sqlx::Error> {
+    let Some(link) = sqlx::query_as::<_, OrcidLinkRow>(
+        "SELECT orcid_id, access_token FROM user_orcid_links WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let put_codes: Vec<String> = sqlx::query_scalar(
+        "SELECT put_code FROM orcid_work_syncs WHERE user_id = ? AND put_code IS NOT NULL",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    for put_code in put_codes {
+        match delete_work(config, client, &link, &put_code).await {
+            Ok(()) => {}
+            Err(WorkRequestError::Revoked) => break,
+            Err(WorkRequestError::Failed(message)) => tracing::warn!(
+                "Failed to remove ORCID work {} of user {}: {}",
+                put_code,
+                user_id,
+                message
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_work(
+    config: &OrcidConfig,
+    client: &Client,
+    link: &OrcidLinkRow,
+    work: &serde_json::Value,
+) -> Result<String, WorkRequestError> {
+    let response = client
+        .post(format!("{}/{}/work", config.api_url, link.orcid_id))
+        .bearer_auth(&link.access_token)
+        .header(header::ACCEPT, "application/json")
+        .header(header::CONTENT_TYPE, ORCID_CONTENT_TYPE)
+        .body(work.to_string())
+        .send()
+        .await
+        .map_err(|error| WorkRequestError::Failed(error.to_string()))?;
+    let response = check_response(response).await?;
+
+    // The put-code of the new work is the last segment of its location.
+    response
+        .headers()
+        .get(header::LOCATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|location| location.trim_end_matches('/').rsplit('/').next())
+        .filter(|put_code| !put_code.is_empty())
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| WorkRequestError::Failed("ORCID did not return a put-code".to_string()))
+}
+
+async fn update_work(
+    config: &OrcidConfig,
+    client: &Client,
+    link: &OrcidLinkRow,
+    put_code: &str,
+    mut work: serde_json::Value,
+) -> Result<(), WorkRequestError> {
+    work["put-code"] = put_code
+        .parse::<i64>()
+        .map(serde_json::Value::from)
+        .unwrap_or_else(|_| serde_json::Value::from(put_code));
+    let response = client
+        .put(format!(
+            "{}/{}/work/{}",
+            config.api_url, link.orcid_id, put_code
+        ))
+        .bearer_auth(&link.access_token)
+        .header(header::ACCEPT, "application/json")
+        .header(header::CONTENT_TYPE, ORCID_CONTENT_TYPE)
+        .body(work.to_string())
+        .send()
+        .await
+        .map_err(|error| WorkRequestError::Failed(error.to_string()))?;
+    check_response(response).await.map(|_| ())
+}
+
+async fn delete_work(
+    config: &OrcidConfig,
+    client: &Client,
+    link: &OrcidLinkRow,
+    put_code: &str,
+) -> Result<(), WorkRequestError> {
+    let response = client
+        .delete(format!(
+            "{}/{}/work/{}",
+            config.api_url, link.orcid_id, put_code
+        ))
+        .bearer_auth(&link.access_token)
+        .header(header::ACCEPT, "application/json")
+        .send()
+        .await
+        .map_err(|error| WorkRequestError::Failed(error.to_string()))?;
+    // The user may already have deleted the work on ORCID.
+    if response.status() == HttpStatusCode::NOT_FOUND {
+        return Ok(());
+    }
+    check_response(response).await.map(|_| ())
+}
+
+async fn check_response(
+    response: reqwest::Response,
+) -> Result<reqwest::Response, WorkRequestError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    if status == HttpStatusCode::UNAUTHORIZED || status == HttpStatusCode::FORBIDDEN {
+        return Err(WorkRequestError::Revoked);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|value| value["user-message"].as_str().map(ToOwned::to_owned))
+        .unwrap_or(body);
+    Err(WorkRequestError::Failed(format!(
+        "ORCID returned {}: {}",
+        status,
+        message.chars().take(500).collect::<String>()
+    )))
+}
+
+fn build_work_payload(paper: &PublishedPaperRow) -> serde_json::Value {
+    let landing_url = format!("{}/posts/{}", frontend_base_url(), paper.id);
+    // Internal `TM.` DOIs do not resolve, so unregistered papers are
+    // identified by their landing page instead.
+    let external_id = match &paper.registered_doi {
+        Some(doi) => serde_json::json!({
+            "external-id-type": "doi",
+            "external-id-value": doi,
+            "external-id-url": { "value": format!("https://doi.org/{}", doi) },
+            "external-id-relationship": "self",
+        }),
+        None => serde_json::json!({
+            "external-id-type": "uri",
+            "external-id-value": landing_url,
+            "external-id-url": { "value": landing_url },
+            "external-id-relationship": "self",
+        }),
+    };
+
+    let mut work = serde_json::json!({
+        "title": { "title": { "value": paper.title } },
+        "journal-title": { "value": JOURNAL_NAME },
+        "type": "journal-article",
+        "publication-date": {
+            "year": { "value": paper.published_on.year().to_string() },
+            "month": { "value": format!("{:02}", paper.published_on.month()) },
+            "day": { "value": format!("{:02}", paper.published_on.day()) },
+        },
+        "external-ids": { "external-id": [external_id] },
+        "url": { "value": landing_url },
+    });
+    if let Some(summary) = paper
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        work["short-description"] = serde_json::Value::from(
+            summary
+                .chars()
+                .take(MAX_SHORT_DESCRIPTION_CHARS)
+                .collect::<String>(),
+        );
+    }
+
+    work
+}
+
+async fn record_work(
+    pool: &MySqlPool,
+    user_id: i64,
+    post_id: i64,
+    put_code: Option<&str>,
+    payload_hash: Option<&str>,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let status = if error.is_some() {
+        ORCID_WORK_FAILED
+    } else {
+        ORCID_WORK_SYNCED
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO orcid_work_syncs
+            (user_id, post_id, put_code, payload_hash, status, last_error, synced_at, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            put_code = VALUES(put_code),
+            payload_hash = VALUES(payload_hash),
+            status = VALUES(status),
+            last_error = VALUES(last_error),
+            synced_at = COALESCE(VALUES(synced_at), synced_at),
+            updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(user_id)
+    .bind(post_id)
+    .bind(put_code)
+    .bind(payload_hash)
+    .bind(status)
+    .bind(error)
+    .bind(error.is_none().then_some(now))
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await
+    .map(|_| ())
+}
+
+/// `0000-0002-1825-0097`: four groups of four digits, the last character
+/// may be the `X` checksum.
+pub fn is_orcid_id(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 4
+        && groups.iter().enumerate().all(|(index, group)| {
+            group.len() == 4
+                && group.chars().enumerate().all(|(position, ch)| {
+                    ch.is_ascii_digit() || (index == 3 && position == 3 && ch == 'X')
+                })
+        })
+}
+
+fn frontend_base_url() -> String {
+    std::env::var("FRONTEND_URL")
+        .ok()
+        .map(|value| value.trim().trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "http://localhost:5173".to_string())
+}
+
+fn sync_interval_secs() -> u64 {
+    std::env::var("ORCID_SYNC_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_ORCID_SYNC_INTERVAL_SECS)
+}
+
+fn orcid_timeout_secs() -> u64 {
+    std::env::var("ORCID_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_ORCID_TIMEOUT_SECS)
+}
@@ -0,0 +1,253 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use chrono::Utc;
+use reqwest::Client;
+use sqlx::MySqlPool;
+
+use crate::models::{
+    OutgoingWebhook, WEBHOOK_EVENT_CONTENT_REPORT, WEBHOOK_EVENT_REVIEW_FAILURE,
+    WEBHOOK_EVENT_SUBMISSION, WEBHOOK_PLATFORM_DISCORD, WEBHOOK_PLATFORM_SLACK,
+};
+use crate::tasks;
+
+pub const DEFAULT_WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+const MAX_ERROR_CHARS: usize = 500;
+/// Discord rejects embed descriptions over 4096 characters; Slack section
+/// text is capped at 3000.
+const MAX_BODY_CHARS: usize = 2_500;
+const DISCORD_EMBED_COLOR: u32 = 0x4F46E5;
+
+pub const OUTGOING_WEBHOOK_SELECT: &str = r#"
+    SELECT
+        id,
+        name,
+        platform,
+        url,
+        CONCAT(SUBSTRING_INDEX(url, '/', 6), '/…') AS url_hint,
+        is_active,
+        on_submission,
+        on_review_failure,
+        on_content_report,
+        last_delivered_at,
+        last_error,
+        last_error_at,
+        created_by,
+        created_at,
+        updated_at
+    FROM outgoing_webhooks
+"#;
+
+/// One alert for the editorial channels.
+#[derive(Debug, Clone)]
+pub struct WebhookMessage {
+    pub event: &'static str,
+    pub title: String,
+    pub body: String,
+    pub post_id: Option<i64>,
+}
+
+/// Checks that `url` is an incoming-webhook URL of the given platform. Only
+/// the platforms' own hosts are accepted, so the server never posts to an
+/// arbitrary address.
+pub fn validate_webhook_url(platform: &str, url: &str) -> Result<(), String> {
+    let prefixes: &[&str] = match platform {
+        WEBHOOK_PLATFORM_SLACK => &["https://hooks.slack.com/services/"],
+        WEBHOOK_PLATFORM_DISCORD => &[
+            "https://discord.com/api/webhooks/",
+            "https://discordapp.com/api/webhooks/",
+        ],
+        _ => return Err("platform must be one of: slack, discord".to_string()),
+    };
+    if !prefixes.iter().any(|prefix| url.starts_with(prefix)) {
+        return Err(format!("url must start with {}", prefixes.join(" or ")));
+    }
+    Ok(())
+}
+
+/// Sends `message` to every active webhook that has its event enabled, in
+/// the background. Failures are recorded on the webhook, never surfaced.
+pub fn queue_webhook_message(pool: &MySqlPool, message: WebhookMessage) {
+    let pool = pool.clone();
+    tasks::spawn_task("webhook_delivery", async move {
+        if let Err(error) = deliver_to_subscribers(&pool, &message).await {
+            tracing::warn!("Failed to send {} webhook alerts: {}", message.event, error);
+        }
+    });
+}
+
+async fn deliver_to_subscribers(pool: &MySqlPool, message: &WebhookMessage) -> anyhow::Result<()> {
+    let event_column = match message.event {
+        WEBHOOK_EVENT_SUBMISSION => "on_submission",
+        WEBHOOK_EVENT_REVIEW_FAILURE => "on_review_failure",
+        WEBHOOK_EVENT_CONTENT_REPORT => "on_content_report",
+        other => return Err(anyhow!("unknown webhook event {}", other)),
+    };
+    let webhooks = sqlx::query_as::<_, OutgoingWebhook>(&format!(
+        "{} WHERE is_active = TRUE AND {} = TRUE ORDER BY id ASC",
+        OUTGOING_WEBHOOK_SELECT, event_column
+    ))
+    .fetch_all(pool)
+    .await?;
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let client = webhook_client()?;
+    for webhook in webhooks {
+        let result = deliver(&client, &webhook, message).await;
+        record_delivery(pool, webhook.id, result.as_ref().err()).await?;
+    }
+    Ok(())
+}
+
+/// Sends a test message to one webhook right away and records the outcome.
+pub async fn send_test_message(pool: &MySqlPool, webhook: &OutgoingWebhook) -> anyhow::Result<()> {
+    let message = WebhookMessage {
+        event: "test",
+        title: "Thought Manifold test message".to_string(),
+        body: format!("The webhook \"{}\" is set up correctly.", webhook.name),
+        post_id: None,
+    };
+    let result = deliver(&webhook_client()?, webhook, &message).await;
+    record_delivery(pool, webhook.id, result.as_ref().err()).await?;
+    result
+}
+
+async fn deliver(
+    client: &Client,
+    webhook: &OutgoingWebhook,
+    message: &WebhookMessage,
+) -> anyhow::Result<()> {
+    let payload = if webhook.platform == WEBHOOK_PLATFORM_DISCORD {
+        discord_payload(message)
+    } else {
+        slack_payload(message)
+    };
+    let response = client.post(&webhook.url).json(&payload).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!(
+            "{} returned {}: {}",
+            webhook.platform,
+            status,
+            body.chars().take(200).collect::<String>()
+        ));
+    }
+    Ok(())
+}
+
+fn slack_payload(message: &WebhookMessage) -> serde_json::Value {
+    let mut text = format!(
+        "*{}*\n{}",
+        escape_slack(&message.title),
+        escape_slack(&truncate(&message.body))
+    );
+    if let Some(post_id) = message.post_id {
+        text.push_str(&format!(
+            "\n<{}|View on Thought Manifold>",
+            post_url(post_id)
+        ));
+    }
+    serde_json::json!({
+        "text": message.title,
+        "blocks": [{
+            "type": "section",
+            "text": {"type": "mrkdwn", "text": text}
+        }]
+    })
+}
+
+fn discord_payload(message: &WebhookMessage) -> serde_json::Value {
+    let mut embed = serde_json::json!({
+        "title": message.title.chars().take(256).collect::<String>(),
+        "description": truncate(&message.body),
+        "color": DISCORD_EMBED_COLOR,
+        "footer": {"text": message.event},
+        "timestamp": Utc::now().to_rfc3339(),
+    });
+    if let Some(post_id) = message.post_id {
+        embed["url"] = serde_json::json!(post_url(post_id));
+    }
+    serde_json::json!({
+        "username": "Thought Manifold",
+        "embeds": [embed],
+        // Reports quote user text; never let it ping anyone.
+        "allowed_mentions": {"parse": []}
+    })
+}
+
+/// Slack treats `&`, `<` and `>` as markup in mrkdwn text.
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_BODY_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_BODY_CHARS - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+async fn record_delivery(
+    pool: &MySqlPool,
+    webhook_id: i64,
+    error: Option<&anyhow::Error>,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    match error {
+        None => {
+            sqlx::query("UPDATE outgoing_webhooks SET last_delivered_at = ? WHERE id = ?")
+                .bind(now)
+                .bind(webhook_id)
+                .execute(pool)
+                .await?;
+        }
+        Some(error) => {
+            tracing::warn!("Webhook {} delivery failed: {}", webhook_id, error);
+            let message: String = error.to_string().chars().take(MAX_ERROR_CHARS).collect();
+            sqlx::query(
+                "UPDATE outgoing_webhooks SET last_error = ?, last_error_at = ? WHERE id = ?",
+            )
+            .bind(message)
+            .bind(now)
+            .bind(webhook_id)
+            .execute(pool)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+fn webhook_client() -> reqwest::Result<Client> {
+    Client::builder()
+        .timeout(Duration::from_secs(webhook_timeout_secs()))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+}
+
+fn post_url(post_id: i64) -> String {
+    format!("{}/posts/{}", frontend_base_url(), post_id)
+}
+
+fn frontend_base_url() -> String {
+    std::env::var("FRONTEND_URL")
+        .ok()
+        .map(|value| value.trim().trim_end_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "http://localhost:5173".to_string())
+}
+
+fn webhook_timeout_secs() -> u64 {
+    std::env::var("WEBHOOK_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_WEBHOOK_TIMEOUT_SECS)
+}
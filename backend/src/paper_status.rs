@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::{Executor, MySql, MySqlPool, QueryBuilder};
+
+pub const STATUS_TRIGGER_CREATE: &str = "create";
+pub const STATUS_TRIGGER_UPDATE: &str = "update";
+pub const STATUS_TRIGGER_PUBLISH: &str = "publish";
+pub const STATUS_TRIGGER_RESTORE: &str = "restore";
+pub const STATUS_TRIGGER_AI_REVIEW: &str = "ai_review";
+pub const STATUS_TRIGGER_AI_REVIEW_RERUN: &str = "ai_review_rerun";
+pub const STATUS_TRIGGER_EDITORIAL_DECISION: &str = "editorial_decision";
+pub const STATUS_TRIGGER_APPEAL: &str = "appeal";
+pub const STATUS_TRIGGER_REVISION_EXPIRY: &str = "revision_expiry";
+pub const STATUS_TRIGGER_BACKFILL: &str = "backfill";
+
+/// Appends one row to `paper_status_transitions`. Writes that leave the
+/// status unchanged are not recorded.
+pub async fn record_status_transition<'e, E>(
+    executor: E,
+    post_id: i64,
+    actor_id: Option<i64>,
+    trigger: &str,
+    from_status: Option<&str>,
+    to_status: &str,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    if from_status == Some(to_status) {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO paper_status_transitions
+            (post_id, actor_id, trigger_source, from_status, to_status, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(post_id)
+    .bind(actor_id)
+    .bind(trigger)
+    .bind(from_status)
+    .bind(to_status)
+    .bind(Utc::now())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Current `paper_status` of every post, taken before a bulk backfill so the
+/// rows it changes can be logged with [`record_backfill_transitions`].
+pub async fn snapshot_paper_statuses(
+    pool: &MySqlPool,
+) -> Result<HashMap<i64, String>, sqlx::Error> {
+    let rows: Vec<(i64, String)> = sqlx::query_as("SELECT id, paper_status FROM posts")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+/// Logs every post whose status differs from `before` as a backfill
+/// transition. Returns the number of transitions written.
+pub async fn record_backfill_transitions(
+    pool: &MySqlPool,
+    before: &HashMap<i64, String>,
+) -> Result<usize, sqlx::Error> {
+    let after = snapshot_paper_statuses(pool).await?;
+    let changed: Vec<(i64, &str, &str)> = after
+        .iter()
+        .filter_map(|(post_id, to_status)| {
+            let from_status = before.get(post_id)?;
+            (from_status != to_status).then_some((
+                *post_id,
+                from_status.as_str(),
+                to_status.as_str(),
+            ))
+        })
+        .collect();
+    if changed.is_empty() {
+        return Ok(0);
+    }
+
+    let now = Utc::now();
+    for chunk in changed.chunks(500) {
+        let mut query_builder = QueryBuilder::<MySql>::new(
+            "INSERT INTO paper_status_transitions (post_id, actor_id, trigger_source, from_status, to_status, created_at) ",
+        );
+        query_builder.push_values(chunk, |mut row, (post_id, from_status, to_status)| {
+            row.push_bind(*post_id)
+                .push_bind(None::<i64>)
+                .push_bind(STATUS_TRIGGER_BACKFILL)
+                .push_bind(*from_status)
+                .push_bind(*to_status)
+                .push_bind(now);
+        });
+        query_builder.build().execute(pool).await?;
+    }
+
+    Ok(changed.len())
+}
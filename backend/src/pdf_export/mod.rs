@@ -0,0 +1,257 @@
+use std::{fmt, path::PathBuf, sync::OnceLock, time::Duration};
+
+use chrono::{DateTime, Datelike, Utc};
+use quick_xml::escape::escape;
+use tokio::{process::Command, sync::Semaphore};
+use uuid::Uuid;
+
+use crate::markdown::render_paper_markdown;
+
+pub const DEFAULT_PDF_RENDER_TIMEOUT_SECS: u64 = 60;
+pub const DEFAULT_PDF_RENDER_CONCURRENCY: usize = 2;
+
+const DEFAULT_PDF_RENDERER_BIN: &str = "chromium";
+const KATEX_CDN_BASE: &str = "https://cdn.jsdelivr.net/npm/katex@0.16.25/dist";
+// Headless Chromium prints once the page is idle; the budget lets KaTeX load
+// and typeset math first.
+const VIRTUAL_TIME_BUDGET_MS: u64 = 10_000;
+
+/// Everything printed on the title page and body of an exported paper.
+#[derive(Debug, Clone)]
+pub struct PdfDocument {
+    pub title: String,
+    pub authors: Vec<String>,
+    pub doi: Option<String>,
+    pub license: String,
+    pub published_on: Option<String>,
+    pub summary: Option<String>,
+    pub content: String,
+    pub landing_url: String,
+    /// Resolves relative links and `/uploads/` images in the content.
+    pub base_url: String,
+}
+
+#[derive(Debug)]
+pub enum PdfRenderError {
+    /// No renderer binary is installed.
+    Unavailable,
+    Failed(String),
+}
+
+impl fmt::Display for PdfRenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable => write!(f, "PDF renderer is not installed"),
+            Self::Failed(message) => write!(f, "PDF rendering failed: {}", message),
+        }
+    }
+}
+
+/// Typesets the document with headless Chromium. Renders are limited to
+/// `PDF_RENDER_CONCURRENCY` at a time since each one starts a browser.
+pub async fn render_pdf(document: &PdfDocument) -> Result<Vec<u8>, PdfRenderError> {
+    let _permit = render_slots()
+        .acquire()
+        .await
+        .map_err(|error| PdfRenderError::Failed(error.to_string()))?;
+
+    let work_dir = std::env::temp_dir().join(format!("tm-pdf-{}", Uuid::new_v4()));
+    tokio::fs::create_dir_all(&work_dir)
+        .await
+        .map_err(|error| PdfRenderError::Failed(error.to_string()))?;
+    let result = render_in_dir(document, &work_dir).await;
+    if let Err(error) = tokio::fs::remove_dir_all(&work_dir).await {
+        tracing::warn!(
+            "Failed to remove PDF work directory {}: {}",
+            work_dir.display(),
+            error
+        );
+    }
+    result
+}
+
+async fn render_in_dir(
+    document: &PdfDocument,
+    work_dir: &std::path::Path,
+) -> Result<Vec<u8>, PdfRenderError> {
+    let html_path = work_dir.join("paper.html");
+    let pdf_path = work_dir.join("paper.pdf");
+    tokio::fs::write(&html_path, build_print_html(document))
+        .await
+        .map_err(|error| PdfRenderError::Failed(error.to_string()))?;
+
+    let mut command = Command::new(renderer_bin());
+    command
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-sandbox")
+        .arg("--no-pdf-header-footer")
+        .arg(format!("--virtual-time-budget={}", VIRTUAL_TIME_BUDGET_MS))
+        .arg(format!("--print-to-pdf={}", pdf_path.display()))
+        .arg(format!("file://{}", html_path.display()))
+        .kill_on_drop(true);
+
+    let output =
+        match tokio::time::timeout(Duration::from_secs(render_timeout_secs()), command.output())
+            .await
+        {
+            Ok(Ok(output)) => output,
+            Ok(Err(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(PdfRenderError::Unavailable);
+            }
+            Ok(Err(error)) => return Err(PdfRenderError::Failed(error.to_string())),
+            Err(_) => return Err(PdfRenderError::Failed("renderer timed out".to_string())),
+        };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(PdfRenderError::Failed(format!(
+            "renderer exited with {}: {}",
+            output.status,
+            stderr.trim().chars().take(500).collect::<String>()
+        )));
+    }
+
+    let data = tokio::fs::read(&pdf_path)
+        .await
+        .map_err(|error| PdfRenderError::Failed(error.to_string()))?;
+    if !data.starts_with(b"%PDF") {
+        return Err(PdfRenderError::Failed(
+            "renderer did not produce a PDF".to_string(),
+        ));
+    }
+    Ok(data)
+}
+
+fn build_print_html(document: &PdfDocument) -> String {
+    let mut title_page = String::new();
+    title_page.push_str(&format!(
+        "<h1 class=\"title\">{}</h1>\n",
+        escape(document.title.as_str())
+    ));
+    if !document.authors.is_empty() {
+        title_page.push_str(&format!(
+            "<p class=\"authors\">{}</p>\n",
+            escape(document.authors.join(", ").as_str())
+        ));
+    }
+    let mut details = Vec::new();
+    if let Some(published_on) = &document.published_on {
+        details.push(format!("Published {}", escape(published_on.as_str())));
+    }
+    if let Some(doi) = &document.doi {
+        details.push(format!(
+            "DOI <a href=\"https://doi.org/{0}\">{0}</a>",
+            escape(doi.as_str())
+        ));
+    }
+    details.push(format!(
+        "<a href=\"{0}\">{0}</a>",
+        escape(document.landing_url.as_str())
+    ));
+    title_page.push_str(&format!(
+        "<p class=\"details\">{}</p>\n",
+        details.join("<br />")
+    ));
+    if let Some(summary) = document
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        title_page.push_str(&format!(
+            "<section class=\"abstract\"><h2>Abstract</h2><p>{}</p></section>\n",
+            escape(summary)
+        ));
+    }
+    title_page.push_str(&format!(
+        "<p class=\"license\">{}</p>\n",
+        escape(document.license.as_str())
+    ));
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8" />
+<base href="{base}/" />
+<title>{title}</title>
+<link rel="stylesheet" href="{katex}/katex.min.css" />
+<script defer src="{katex}/katex.min.js"></script>
+<script defer src="{katex}/contrib/auto-render.min.js" onload="renderMathInElement(document.body, {{throwOnError: false}});"></script>
+<style>
+@page {{ size: A4; margin: 25mm 20mm; }}
+body {{ font-family: "Noto Serif", "Noto Serif CJK KR", serif; font-size: 11pt; line-height: 1.5; }}
+.title-page {{ page-break-after: always; padding-top: 40mm; text-align: center; }}
+.title {{ font-size: 22pt; margin-bottom: 12mm; }}
+.authors {{ font-size: 13pt; }}
+.details {{ font-size: 10pt; color: #444; }}
+.abstract {{ text-align: justify; margin: 12mm 10mm; }}
+.license {{ font-size: 9pt; color: #444; margin-top: 20mm; }}
+pre, code {{ font-family: "Noto Sans Mono", monospace; font-size: 9pt; }}
+pre {{ white-space: pre-wrap; }}
+img {{ max-width: 100%; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #888; padding: 2pt 4pt; }}
+a {{ color: inherit; }}
+</style>
+</head>
+<body>
+<section class="title-page">
+{title_page}</section>
+<main>
+{body}
+</main>
+</body>
+</html>
+"#,
+        base = escape(document.base_url.as_str()),
+        title = escape(document.title.as_str()),
+        katex = KATEX_CDN_BASE,
+        title_page = title_page,
+        body = render_paper_markdown(&document.content),
+    )
+}
+
+/// License line for exported papers: `PAPER_LICENSE` when set, otherwise a
+/// copyright notice for the publication year.
+pub fn paper_license(published_on: DateTime<Utc>) -> String {
+    std::env::var("PAPER_LICENSE")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| {
+            format!(
+                "© {} The authors. All rights reserved.",
+                published_on.year()
+            )
+        })
+}
+
+fn render_slots() -> &'static Semaphore {
+    static SLOTS: OnceLock<Semaphore> = OnceLock::new();
+    SLOTS.get_or_init(|| {
+        let permits = std::env::var("PDF_RENDER_CONCURRENCY")
+            .ok()
+            .and_then(|raw| raw.parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_PDF_RENDER_CONCURRENCY);
+        Semaphore::new(permits)
+    })
+}
+
+fn renderer_bin() -> PathBuf {
+    std::env::var("PDF_RENDERER_BIN")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_PDF_RENDERER_BIN.to_string())
+        .into()
+}
+
+fn render_timeout_secs() -> u64 {
+    std::env::var("PDF_RENDER_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_PDF_RENDER_TIMEOUT_SECS)
+}
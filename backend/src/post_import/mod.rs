@@ -0,0 +1,443 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use quick_xml::{Reader, events::Event};
+use reqwest::{Client, StatusCode as HttpStatusCode, header};
+
+use crate::public_url::{check_public_https_url, public_client};
+use crate::telemetry;
+
+pub const DEFAULT_IMPORT_TIMEOUT_SECS: u64 = 20;
+pub const IMPORT_SOURCE_ARXIV: &str = "arxiv";
+pub const IMPORT_SOURCE_DOI: &str = "doi";
+
+const DEFAULT_ARXIV_OAI_URL: &str = "https://oaipmh.arxiv.org/oai";
+const CROSSREF_WORKS_URL: &str = "https://api.crossref.org/works/";
+const MAX_IMPORTED_REFERENCES: usize = 500;
+const IMPORT_USER_AGENT: &str = "ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)";
+
+/// A work that can be imported, with its identifier normalized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportIdentifier {
+    /// arXiv ID without version, e.g. `2401.01234` or `hep-th/9901001`.
+    Arxiv(String),
+    /// Lowercase DOI without resolver prefix.
+    Doi(String),
+}
+
+impl ImportIdentifier {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let trimmed = raw.trim();
+        let lowered = trimmed.to_ascii_lowercase();
+
+        let doi = [
+            "https://doi.org/",
+            "http://doi.org/",
+            "https://dx.doi.org/",
+            "doi:",
+        ]
+        .iter()
+        .find_map(|prefix| lowered.strip_prefix(prefix))
+        .unwrap_or(&lowered)
+        .trim();
+        if doi.starts_with("10.")
+            && doi.contains('/')
+            && !doi.ends_with('/')
+            && !doi.chars().any(char::is_whitespace)
+            && doi.len() <= 255
+        {
+            return Some(Self::Doi(doi.to_string()));
+        }
+
+        // Prefixes are matched case-insensitively, but old-style arXiv IDs
+        // carry an upper-case subject class (`math.AG/0101001`).
+        let arxiv_id = [
+            "arxiv:",
+            "https://arxiv.org/abs/",
+            "http://arxiv.org/abs/",
+            "https://arxiv.org/pdf/",
+            "http://arxiv.org/pdf/",
+        ]
+        .iter()
+        .find(|prefix| lowered.starts_with(*prefix))
+        .map(|prefix| &trimmed[prefix.len()..])
+        .unwrap_or(trimmed);
+        let arxiv_id = arxiv_id.trim_end_matches(".pdf");
+        let unversioned = strip_arxiv_version(arxiv_id);
+        is_arxiv_id(unversioned).then(|| Self::Arxiv(unversioned.to_string()))
+    }
+
+    pub fn source_type(&self) -> &'static str {
+        match self {
+            Self::Arxiv(_) => IMPORT_SOURCE_ARXIV,
+            Self::Doi(_) => IMPORT_SOURCE_DOI,
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Arxiv(id) | Self::Doi(id) => id,
+        }
+    }
+
+    pub fn source_url(&self) -> String {
+        match self {
+            Self::Arxiv(id) => format!("https://arxiv.org/abs/{}", id),
+            Self::Doi(doi) => format!("https://doi.org/{}", doi),
+        }
+    }
+}
+
+/// Metadata of an external work, ready to prefill a draft.
+#[derive(Debug, Clone, Default)]
+pub struct ImportedWork {
+    pub title: String,
+    pub abstract_text: Option<String>,
+    pub authors: Vec<String>,
+    pub published_at: Option<String>,
+    pub doi: Option<String>,
+    pub license_url: Option<String>,
+    /// Set only when the license allows redistributing the full text.
+    pub pdf_url: Option<String>,
+    pub reference_dois: Vec<String>,
+}
+
+pub fn import_client() -> reqwest::Result<Client> {
+    Client::builder()
+        .timeout(Duration::from_secs(import_timeout_secs()))
+        .user_agent(IMPORT_USER_AGENT)
+        .build()
+}
+
+/// Looks the work up at arXiv or Crossref. Returns `None` when the source
+/// does not know the identifier.
+pub async fn fetch_imported_work(
+    client: &Client,
+    identifier: &ImportIdentifier,
+) -> anyhow::Result<Option<ImportedWork>> {
+    match identifier {
+        ImportIdentifier::Arxiv(id) => fetch_arxiv_work(client, id).await,
+        ImportIdentifier::Doi(doi) => fetch_crossref_work(client, doi).await,
+    }
+}
+
+/// Downloads a PDF of at most `max_bytes`, rejecting anything that is not a
+/// PDF such as a publisher login page. The link comes from publisher
+/// metadata, so only https URLs on public hosts are fetched, and the body
+/// is streamed so an oversized response is cut off rather than buffered.
+pub async fn download_pdf(url: &str, max_bytes: usize) -> anyhow::Result<Vec<u8>> {
+    let url = check_public_https_url(url)
+        .await
+        .map_err(|error| anyhow::anyhow!("PDF link {}", error))?;
+    let client = public_client()
+        .timeout(Duration::from_secs(import_timeout_secs()))
+        .user_agent(IMPORT_USER_AGENT)
+        .build()?;
+    let mut response = client
+        .get(url)
+        .header(header::ACCEPT, "application/pdf")
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("PDF download returned {}", response.status());
+    }
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        anyhow::bail!("PDF is larger than {} bytes", max_bytes);
+    }
+
+    let mut data = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if data.len() + chunk.len() > max_bytes {
+            anyhow::bail!("PDF is larger than {} bytes", max_bytes);
+        }
+        data.extend_from_slice(&chunk);
+    }
+    if !data.starts_with(b"%PDF") {
+        anyhow::bail!("Downloaded file is not a PDF");
+    }
+
+    Ok(data)
+}
+
+async fn fetch_arxiv_work(client: &Client, id: &str) -> anyhow::Result<Option<ImportedWork>> {
+    let identifier = format!("oai:arXiv.org:{}", id);
+    let response = telemetry::send(
+        "arxiv",
+        client.get(arxiv_oai_url()).query(&[
+            ("verb", "GetRecord"),
+            ("identifier", identifier.as_str()),
+            ("metadataPrefix", "arXiv"),
+        ]),
+    )
+    .await?;
+    if !response.status().is_success() {
+        anyhow::bail!("arXiv lookup returned {}", response.status());
+    }
+    let body = response.text().await?;
+
+    let mut reader = Reader::from_str(&body);
+    reader.config_mut().trim_text(true);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut work = ImportedWork::default();
+    let mut author_parts: (String, String) = (String::new(), String::new());
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(event)) => {
+                let name = String::from_utf8_lossy(event.local_name().as_ref()).to_string();
+                if name == "error" {
+                    let code = event
+                        .try_get_attribute("code")
+                        .ok()
+                        .flatten()
+                        .map(|attribute| String::from_utf8_lossy(&attribute.value).to_string());
+                    if code.as_deref() == Some("idDoesNotExist") {
+                        return Ok(None);
+                    }
+                    anyhow::bail!("arXiv lookup failed: {}", code.unwrap_or_default());
+                }
+                if name == "author" {
+                    author_parts = (String::new(), String::new());
+                }
+                path.push(name);
+            }
+            Ok(Event::End(_)) => {
+                if path.pop().as_deref() == Some("author") {
+                    let (forenames, keyname) = &author_parts;
+                    let full_name = format!("{} {}", forenames, keyname);
+                    let full_name = full_name.trim();
+                    if !full_name.is_empty() {
+                        work.authors.push(full_name.to_string());
+                    }
+                }
+            }
+            Ok(Event::Text(event)) => {
+                let text = event
+                    .unescape()
+                    .context("Failed to decode arXiv metadata")?;
+                let text = collapse_whitespace(&text);
+                let in_record = path.iter().any(|segment| segment == "arXiv");
+                match path.last().map(String::as_str) {
+                    _ if !in_record => {}
+                    Some("title") => work.title = text,
+                    Some("abstract") => work.abstract_text = Some(text),
+                    Some("license") => work.license_url = Some(text),
+                    Some("doi") => {
+                        work.doi = text.split_whitespace().next().map(str::to_ascii_lowercase)
+                    }
+                    Some("created") if work.published_at.is_none() => {
+                        work.published_at = Some(text)
+                    }
+                    Some("forenames") => author_parts.0 = text,
+                    Some("keyname") => author_parts.1 = text,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(error) => anyhow::bail!("Failed to parse arXiv metadata: {}", error),
+        }
+    }
+
+    if work.title.is_empty() {
+        return Ok(None);
+    }
+    if work.license_url.as_deref().is_some_and(is_open_license) {
+        work.pdf_url = Some(format!("https://arxiv.org/pdf/{}", id));
+    }
+
+    Ok(Some(work))
+}
+
+async fn fetch_crossref_work(client: &Client, doi: &str) -> anyhow::Result<Option<ImportedWork>> {
+    let url = format!("{}{}", CROSSREF_WORKS_URL, urlencoding::encode(doi));
+    let response = telemetry::send("crossref", client.get(url)).await?;
+    if response.status() == HttpStatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        anyhow::bail!("Crossref lookup returned {}", response.status());
+    }
+
+    let payload = response
+        .json::<serde_json::Value>()
+        .await
+        .context("Failed to decode Crossref work")?;
+    let message = &payload["message"];
+
+    let Some(title) = message["title"]
+        .as_array()
+        .and_then(|titles| titles.iter().find_map(|title| title.as_str()))
+        .map(collapse_whitespace)
+        .filter(|title| !title.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let authors = message["author"]
+        .as_array()
+        .map(|authors| {
+            authors
+                .iter()
+                .filter_map(|author| {
+                    let name = match (author["given"].as_str(), author["family"].as_str()) {
+                        (Some(given), Some(family)) => format!("{} {}", given, family),
+                        (None, Some(family)) => family.to_string(),
+                        _ => author["name"].as_str()?.to_string(),
+                    };
+                    Some(collapse_whitespace(&name)).filter(|name| !name.is_empty())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let license_url = message["license"].as_array().and_then(|licenses| {
+        licenses
+            .iter()
+            .filter_map(|license| license["URL"].as_str())
+            .find(|url| is_open_license(url))
+            .or_else(|| licenses.iter().find_map(|license| license["URL"].as_str()))
+            .map(ToOwned::to_owned)
+    });
+    let pdf_url = message["link"]
+        .as_array()
+        .and_then(|links| {
+            links.iter().find_map(|link| {
+                (link["content-type"].as_str() == Some("application/pdf"))
+                    .then(|| link["URL"].as_str())
+                    .flatten()
+            })
+        })
+        .filter(|_| license_url.as_deref().is_some_and(is_open_license))
+        .map(ToOwned::to_owned);
+
+    let reference_dois = message["reference"]
+        .as_array()
+        .map(|references| {
+            references
+                .iter()
+                .filter_map(|reference| reference["DOI"].as_str())
+                .map(str::to_ascii_lowercase)
+                .take(MAX_IMPORTED_REFERENCES)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(Some(ImportedWork {
+        title,
+        abstract_text: message["abstract"]
+            .as_str()
+            .map(strip_jats_markup)
+            .filter(|text| !text.is_empty()),
+        authors,
+        published_at: crossref_published_at(message),
+        doi: Some(doi.to_string()),
+        license_url,
+        pdf_url,
+        reference_dois,
+    }))
+}
+
+fn crossref_published_at(message: &serde_json::Value) -> Option<String> {
+    ["published-print", "published-online", "issued"]
+        .iter()
+        .find_map(|key| {
+            let parts = message[*key]["date-parts"].get(0)?.as_array()?;
+            let values: Vec<i64> = parts.iter().map_while(|part| part.as_i64()).collect();
+            match values.as_slice() {
+                [year, month, day, ..] => Some(format!("{:04}-{:02}-{:02}", year, month, day)),
+                [year, month] => Some(format!("{:04}-{:02}", year, month)),
+                [year] => Some(format!("{:04}", year)),
+                [] => None,
+            }
+        })
+}
+
+/// Crossref abstracts are JATS XML; only their text is kept.
+fn strip_jats_markup(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut in_tag = false;
+    for ch in raw.chars() {
+        match ch {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&");
+    collapse_whitespace(text.trim_start_matches("Abstract").trim())
+}
+
+/// Creative Commons licenses, including NoDerivatives ones, allow sharing the
+/// unmodified full text.
+fn is_open_license(url: &str) -> bool {
+    let lowered = url.to_ascii_lowercase();
+    lowered.contains("creativecommons.org/licenses/")
+        || lowered.contains("creativecommons.org/publicdomain/")
+}
+
+fn strip_arxiv_version(id: &str) -> &str {
+    match id.rfind('v') {
+        Some(index)
+            if index > 0
+                && index + 1 < id.len()
+                && id[index + 1..].chars().all(|ch| ch.is_ascii_digit())
+                && id[..index].ends_with(|ch: char| ch.is_ascii_digit()) =>
+        {
+            &id[..index]
+        }
+        _ => id,
+    }
+}
+
+/// `YYMM.NNNNN` since 2007, `archive(.SC)/YYMMNNN` before.
+fn is_arxiv_id(id: &str) -> bool {
+    if let Some((archive, number)) = id.split_once('/') {
+        let archive_name = archive.split('.').next().unwrap_or_default();
+        return !archive_name.is_empty()
+            && archive_name
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch == '-')
+            && number.len() == 7
+            && number.chars().all(|ch| ch.is_ascii_digit());
+    }
+
+    let Some((year_month, number)) = id.split_once('.') else {
+        return false;
+    };
+    year_month.len() == 4
+        && year_month.chars().all(|ch| ch.is_ascii_digit())
+        && (4..=5).contains(&number.len())
+        && number.chars().all(|ch| ch.is_ascii_digit())
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn arxiv_oai_url() -> String {
+    std::env::var("ARXIV_OAI_URL")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_ARXIV_OAI_URL.to_string())
+}
+
+fn import_timeout_secs() -> u64 {
+    std::env::var("IMPORT_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_IMPORT_TIMEOUT_SECS)
+}
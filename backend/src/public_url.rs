@@ -0,0 +1,142 @@
+//! Guards for requests to URLs that users or third parties supply, such as
+//! Web Push endpoints and publisher PDF links. Only https URLs with a host
+//! name are accepted, and the name has to resolve to public addresses, so
+//! the server cannot be pointed at itself, the cloud metadata service or
+//! hosts on the internal network. `public_client` re-checks the addresses
+//! when it connects and on every redirect, so a name that resolves
+//! differently later gets no further.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use reqwest::{ClientBuilder, Url};
+
+const MAX_REDIRECTS: usize = 10;
+
+/// Parses `raw` and checks its form: https, a host name rather than an IP
+/// literal, and no credentials.
+pub fn parse_public_https_url(raw: &str) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|_| "must be a valid URL".to_string())?;
+    if url.scheme() != "https" {
+        return Err("must be an https URL".to_string());
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err("must not contain credentials".to_string());
+    }
+    if url.host_str().is_none() {
+        return Err("must have a host".to_string());
+    }
+    match url.domain() {
+        None => Err("must use a host name, not an IP address".to_string()),
+        Some(domain) if is_internal_name(domain) => {
+            Err("must not point to an internal host".to_string())
+        }
+        Some(_) => Ok(url),
+    }
+}
+
+/// Like `parse_public_https_url`, and also resolves the host and rejects it
+/// when any of its addresses is not public.
+pub async fn check_public_https_url(raw: &str) -> Result<Url, String> {
+    let url = parse_public_https_url(raw)?;
+    let host = url.host_str().unwrap_or_default();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|_| "host could not be resolved".to_string())?
+        .collect();
+    if addresses.is_empty() || !addresses.iter().all(|address| is_public_ip(address.ip())) {
+        return Err("must point to a public host".to_string());
+    }
+    Ok(url)
+}
+
+/// Client builder whose connections only reach public addresses, and whose
+/// redirects only follow URLs that pass `parse_public_https_url`.
+pub fn public_client() -> ClientBuilder {
+    reqwest::Client::builder()
+        .dns_resolver(Arc::new(PublicResolver))
+        .redirect(Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if let Err(error) = parse_public_https_url(attempt.url().as_str()) {
+                attempt.error(format!("redirect target {}", error))
+            } else {
+                attempt.follow()
+            }
+        }))
+}
+
+/// Resolves with the system resolver and drops every non-public address;
+/// a name left without addresses fails to resolve.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| is_public_ip(address.ip()))
+                .collect();
+            if addresses.is_empty() {
+                return Err(
+                    format!("{} does not resolve to a public address", name.as_str()).into(),
+                );
+            }
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+fn is_internal_name(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    domain == "localhost"
+        || domain.ends_with(".localhost")
+        || domain.ends_with(".local")
+        || domain.ends_with(".internal")
+        || !domain.contains('.')
+}
+
+fn is_public_ip(address: IpAddr) -> bool {
+    match address.to_canonical() {
+        IpAddr::V4(address) => is_public_ipv4(address),
+        IpAddr::V6(address) => is_public_ipv6(address),
+    }
+}
+
+fn is_public_ipv4(address: Ipv4Addr) -> bool {
+    let [a, b, ..] = address.octets();
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_broadcast()
+        || address.is_documentation()
+        || address.is_multicast()
+        || a == 0
+        // Shared address space (carrier-grade NAT)
+        || (a == 100 && (64..128).contains(&b))
+        // Benchmarking
+        || (a == 198 && (18..20).contains(&b))
+        // Reserved, including the IETF protocol assignments block
+        || a >= 240
+        || (a == 192 && b == 0 && address.octets()[2] == 0))
+}
+
+fn is_public_ipv6(address: Ipv6Addr) -> bool {
+    let first = address.segments()[0];
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || address.is_multicast()
+        // Unique local
+        || (first & 0xfe00) == 0xfc00
+        // Link-local
+        || (first & 0xffc0) == 0xfe80
+        // Documentation
+        || (first == 0x2001 && address.segments()[1] == 0x0db8)
+        // NAT64, which can reach IPv4 addresses
+        || (first == 0x0064 && address.segments()[1] == 0xff9b))
+}
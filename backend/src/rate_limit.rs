@@ -0,0 +1,290 @@
+//! Sliding-window rate limits. `SlidingWindowLimiter` is the building block;
+//! `enforce_rate_limit` applies it per client address and per signed-in user
+//! to the routes in `RateLimitedRoute`, reporting the remaining quota in
+//! `RateLimit-*` headers.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{Request, State},
+    handler::Handler,
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use sqlx::MySqlPool;
+
+use crate::client_ip;
+use crate::error::AppError;
+use crate::routes::auth::token_subject;
+
+/// Keys are forgotten once their newest hit is older than every window, but
+/// only when the map grows past this size so small deployments never scan.
+const PRUNE_THRESHOLD: usize = 1_024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SlidingWindow {
+    pub max_hits: usize,
+    pub window: Duration,
+}
+
+/// Where a key stands in its tightest window after a check.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitQuota {
+    pub allowed: bool,
+    pub limit: usize,
+    pub remaining: usize,
+    /// Until the oldest counted hit leaves the window and frees a slot; for
+    /// a rejected hit, how long to wait before retrying.
+    pub reset: Duration,
+}
+
+/// Process-local sliding-window log. A hit is accepted only when every
+/// configured window has room for it; rejected hits are not recorded.
+#[derive(Debug)]
+pub struct SlidingWindowLimiter<K = i64> {
+    windows: Vec<SlidingWindow>,
+    hits: Mutex<HashMap<K, VecDeque<Instant>>>,
+}
+
+impl<K: Eq + Hash> SlidingWindowLimiter<K> {
+    /// Windows with `max_hits == 0` are treated as disabled.
+    pub fn new(windows: Vec<SlidingWindow>) -> Self {
+        Self {
+            windows: windows
+                .into_iter()
+                .filter(|window| window.max_hits > 0 && !window.window.is_zero())
+                .collect(),
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a hit for `key`, or returns how long the caller has to wait
+    /// before the next hit would be accepted.
+    pub fn check(&self, key: K) -> Result<(), Duration> {
+        match self.check_quota(key) {
+            Some(quota) if !quota.allowed => Err(quota.reset),
+            _ => Ok(()),
+        }
+    }
+
+    /// Like `check`, but reports the window closest to its limit. `None`
+    /// when no window is configured.
+    pub fn check_quota(&self, key: K) -> Option<RateLimitQuota> {
+        let longest = self.windows.iter().map(|window| window.window).max()?;
+        let Ok(mut hits) = self.hits.lock() else {
+            return None;
+        };
+        let now = Instant::now();
+
+        if hits.len() > PRUNE_THRESHOLD {
+            hits.retain(|_, log| log.back().is_some_and(|last| now - *last < longest));
+        }
+
+        let log = hits.entry(key).or_default();
+        while log.front().is_some_and(|first| now - *first >= longest) {
+            log.pop_front();
+        }
+
+        let blocked = self
+            .windows
+            .iter()
+            .filter_map(|window| {
+                let in_window: Vec<&Instant> = log
+                    .iter()
+                    .filter(|hit| now - **hit < window.window)
+                    .collect();
+                (in_window.len() >= window.max_hits).then(|| {
+                    // The oldest hit that still counts has to leave the window.
+                    let oldest = in_window[in_window.len() - window.max_hits];
+                    RateLimitQuota {
+                        allowed: false,
+                        limit: window.max_hits,
+                        remaining: 0,
+                        reset: window.window - (now - *oldest),
+                    }
+                })
+            })
+            .max_by_key(|quota| quota.reset);
+        if blocked.is_some() {
+            return blocked;
+        }
+
+        log.push_back(now);
+        self.windows
+            .iter()
+            .map(|window| {
+                let in_window: Vec<&Instant> = log
+                    .iter()
+                    .filter(|hit| now - **hit < window.window)
+                    .collect();
+                RateLimitQuota {
+                    allowed: true,
+                    limit: window.max_hits,
+                    remaining: window.max_hits - in_window.len(),
+                    reset: window.window - (now - *in_window[0]),
+                }
+            })
+            .min_by_key(|quota| (quota.remaining, Reverse(quota.reset)))
+    }
+}
+
+/// Routes with their own request limits, applied with `rate_limited`. Each
+/// has a per-address and a per-user limit over a sliding window, read from
+/// `RATE_LIMIT_<NAME>_PER_IP` and `RATE_LIMIT_<NAME>_PER_USER`; 0 turns a
+/// limit off.
+#[derive(Debug, Clone, Copy)]
+pub enum RateLimitedRoute {
+    /// Sign-up, sign-in and the Google callback; per minute.
+    Auth,
+    /// Creating and importing posts; per hour.
+    PostCreate,
+    /// Writing post and review comments; per minute. The comment flood
+    /// limits still apply on top.
+    Comment,
+    /// Asking for an AI review rerun; per hour.
+    AiReview,
+}
+
+struct RoutePolicy {
+    name: &'static str,
+    window: Duration,
+    default_per_ip: usize,
+    default_per_user: usize,
+}
+
+struct RouteLimiters {
+    per_ip: SlidingWindowLimiter<String>,
+    /// Keyed by the token subject, so it only counts signed-in requests.
+    per_user: SlidingWindowLimiter<String>,
+}
+
+impl RateLimitedRoute {
+    fn policy(self) -> RoutePolicy {
+        let (name, window_secs, default_per_ip, default_per_user) = match self {
+            Self::Auth => ("AUTH", 60, 20, 0),
+            Self::PostCreate => ("POST_CREATE", 3_600, 60, 20),
+            Self::Comment => ("COMMENT", 60, 60, 30),
+            Self::AiReview => ("AI_REVIEW", 3_600, 30, 10),
+        };
+        RoutePolicy {
+            name,
+            window: Duration::from_secs(window_secs),
+            default_per_ip,
+            default_per_user,
+        }
+    }
+
+    fn limiters(self) -> &'static RouteLimiters {
+        static AUTH: OnceLock<RouteLimiters> = OnceLock::new();
+        static POST_CREATE: OnceLock<RouteLimiters> = OnceLock::new();
+        static COMMENT: OnceLock<RouteLimiters> = OnceLock::new();
+        static AI_REVIEW: OnceLock<RouteLimiters> = OnceLock::new();
+        let cell = match self {
+            Self::Auth => &AUTH,
+            Self::PostCreate => &POST_CREATE,
+            Self::Comment => &COMMENT,
+            Self::AiReview => &AI_REVIEW,
+        };
+        cell.get_or_init(|| {
+            let policy = self.policy();
+            let limiter = |scope: &str, default: usize| {
+                SlidingWindowLimiter::new(vec![SlidingWindow {
+                    max_hits: limit_from_env(
+                        &format!("RATE_LIMIT_{}_PER_{}", policy.name, scope),
+                        default,
+                    ),
+                    window: policy.window,
+                }])
+            };
+            RouteLimiters {
+                per_ip: limiter("IP", policy.default_per_ip),
+                per_user: limiter("USER", policy.default_per_user),
+            }
+        })
+    }
+}
+
+/// Wraps `handler` in `enforce_rate_limit` for `route`, e.g.
+/// `post(rate_limited(RateLimitedRoute::Auth, login))`.
+pub fn rate_limited<H, T>(route: RateLimitedRoute, handler: H) -> impl Handler<T, MySqlPool>
+where
+    H: Handler<T, MySqlPool>,
+    T: 'static,
+{
+    handler.layer(middleware::from_fn_with_state(route, enforce_rate_limit))
+}
+
+/// Counts the request against the route's per-address and per-user limits
+/// and answers 429 when either is used up. Responses carry
+/// `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` for the
+/// tighter of the two.
+async fn enforce_rate_limit(
+    State(route): State<RateLimitedRoute>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limiters = route.limiters();
+    let ip_quota =
+        client_ip::current().and_then(|address| limiters.per_ip.check_quota(address.to_string()));
+    // A request already turned away by address does not use up the user's quota.
+    let user_quota = match ip_quota {
+        Some(quota) if !quota.allowed => None,
+        _ => match request.extensions().get::<MySqlPool>() {
+            Some(pool) => token_subject(pool, request.headers())
+                .await
+                .and_then(|subject| limiters.per_user.check_quota(subject)),
+            None => None,
+        },
+    };
+    let Some(quota) = ip_quota
+        .into_iter()
+        .chain(user_quota)
+        .min_by_key(|quota| (quota.allowed, quota.remaining, Reverse(quota.reset)))
+    else {
+        return next.run(request).await;
+    };
+
+    let mut response = if quota.allowed {
+        next.run(request).await
+    } else {
+        let retry_after = whole_secs(quota.reset);
+        (
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            AppError::too_many_requests(format!(
+                "Too many requests. Try again in {} seconds",
+                retry_after
+            ))
+            .with_field("retry_after", retry_after),
+        )
+            .into_response()
+    };
+    insert_quota_headers(response.headers_mut(), &quota);
+    response
+}
+
+fn insert_quota_headers(headers: &mut HeaderMap, quota: &RateLimitQuota) {
+    for (name, value) in [
+        ("ratelimit-limit", quota.limit as u64),
+        ("ratelimit-remaining", quota.remaining as u64),
+        ("ratelimit-reset", whole_secs(quota.reset)),
+    ] {
+        headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+    }
+}
+
+/// Rounded up, so a client that waits this long is let through.
+fn whole_secs(duration: Duration) -> u64 {
+    duration.as_secs_f64().ceil().max(1.0) as u64
+}
+
+fn limit_from_env(key: &str, default: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<usize>().ok())
+        .unwrap_or(default)
+}
@@ -0,0 +1,594 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use quick_xml::{Reader, escape::escape, events::Event};
+use reqwest::{Client, RequestBuilder, header};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::models::{
+    REPOSITORY_DEPOSIT_DEPOSITED, REPOSITORY_DEPOSIT_FAILED, REPOSITORY_DEPOSIT_PENDING,
+};
+use crate::pdf_export::{PdfDocument, paper_license, render_pdf};
+use crate::tasks;
+
+pub const DEFAULT_SWORD_DEPOSIT_INTERVAL_SECS: u64 = 900;
+pub const DEFAULT_SWORD_DEPOSIT_MAX_ATTEMPTS: i32 = 8;
+pub const DEFAULT_SWORD_TIMEOUT_SECS: u64 = 60;
+
+const JOURNAL_NAME: &str = "Thought Manifold";
+const SWORD_STATEMENT_REL: &str = "http://purl.org/net/sword/terms/statement";
+const SWORD_BINARY_PACKAGING: &str = "http://purl.org/net/sword/package/Binary";
+// Papers are rendered one by one, so a small batch keeps a run short.
+const DEPOSIT_BATCH_SIZE: i64 = 10;
+const RETRY_BASE_SECS: i64 = 600;
+const RETRY_MAX_SECS: i64 = 86_400;
+const ERROR_BODY_MAX_CHARS: usize = 500;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RepositoryDepositSummary {
+    pub queued: usize,
+    pub deposited: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone)]
+struct SwordConfig {
+    collection_url: String,
+    username: String,
+    password: String,
+    on_behalf_of: Option<String>,
+}
+
+impl SwordConfig {
+    /// `None` unless the collection URL (Col-IRI) and credentials are set.
+    fn from_env() -> Option<Self> {
+        Some(Self {
+            collection_url: env_value("SWORD_COLLECTION_URL")?,
+            username: env_value("SWORD_USERNAME")?,
+            password: env_value("SWORD_PASSWORD")?,
+            on_behalf_of: env_value("SWORD_ON_BEHALF_OF"),
+        })
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        let builder = builder.basic_auth(&self.username, Some(&self.password));
+        match &self.on_behalf_of {
+            Some(user) => builder.header("On-Behalf-Of", user),
+            None => builder,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DueDeposit {
+    post_id: i64,
+    attempts: i32,
+    edit_iri: Option<String>,
+    edit_media_iri: Option<String>,
+    media_deposited_at: Option<DateTime<Utc>>,
+    title: String,
+    summary: Option<String>,
+    content: String,
+    author_name: String,
+    published_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    doi: Option<String>,
+}
+
+/// Links of a SWORD v2 deposit receipt.
+#[derive(Debug, Default)]
+struct DepositReceipt {
+    edit_iri: Option<String>,
+    edit_media_iri: Option<String>,
+    statement_iri: Option<String>,
+    splash_url: Option<String>,
+}
+
+/// Starts the periodic job that deposits accepted papers into the
+/// institutional repository configured by `SWORD_COLLECTION_URL`. Setting
+/// `SWORD_DEPOSIT_INTERVAL_SECS=0`, or leaving the SWORD settings unset,
+/// disables the job.
+pub fn spawn_repository_deposit(pool: MySqlPool) {
+    let interval_secs = deposit_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("SWORD repository deposit is disabled");
+        return;
+    }
+    let Some(config) = SwordConfig::from_env() else {
+        tracing::info!("SWORD repository deposit is not configured");
+        return;
+    };
+
+    tasks::spawn_periodic(
+        "repository_deposit",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            let config = config.clone();
+            async move {
+                match run_repository_deposit(&pool, &config).await {
+                    Ok(summary) if summary.queued + summary.deposited + summary.failed == 0 => {}
+                    Ok(summary) => tracing::info!(
+                        "SWORD repository deposit finished: queued={}, deposited={}, failed={}",
+                        summary.queued,
+                        summary.deposited,
+                        summary.failed
+                    ),
+                    Err(error) => tracing::error!("SWORD repository deposit failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+async fn run_repository_deposit(
+    pool: &MySqlPool,
+    config: &SwordConfig,
+) -> anyhow::Result<RepositoryDepositSummary> {
+    let mut summary = RepositoryDepositSummary {
+        queued: queue_accepted_papers(pool).await?,
+        ..RepositoryDepositSummary::default()
+    };
+
+    let now = Utc::now();
+    let deposits = sqlx::query_as::<_, DueDeposit>(
+        r#"
+        SELECT
+            d.post_id,
+            d.attempts,
+            d.edit_iri,
+            d.edit_media_iri,
+            d.media_deposited_at,
+            p.title,
+            p.summary,
+            p.content,
+            COALESCE(NULLIF(TRIM(u.display_name), ''), u.username) AS author_name,
+            p.published_at,
+            p.created_at,
+            COALESCE(
+                (SELECT r.registered_doi FROM post_doi_registrations r WHERE r.post_id = p.id),
+                (
+                    SELECT m.doi
+                    FROM post_doi_metadata m
+                    WHERE m.post_id = p.id AND m.doi LIKE 'TM.%'
+                    ORDER BY m.id ASC
+                    LIMIT 1
+                )
+            ) AS doi
+        FROM post_repository_deposits d
+        JOIN posts p ON p.id = d.post_id
+        JOIN users u ON u.id = p.author_id
+        WHERE d.status = ?
+          AND (d.next_attempt_at IS NULL OR d.next_attempt_at <= ?)
+          AND p.paper_status IN ('accepted', 'published')
+        ORDER BY d.next_attempt_at IS NOT NULL, d.next_attempt_at ASC, d.post_id ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(REPOSITORY_DEPOSIT_PENDING)
+    .bind(now)
+    .bind(DEPOSIT_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+    if deposits.is_empty() {
+        return Ok(summary);
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(sword_timeout_secs()))
+        .user_agent("ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)")
+        .build()?;
+    let max_attempts = max_attempts();
+
+    for deposit in deposits {
+        match deposit_paper(pool, &client, config, &deposit).await {
+            Ok(()) => summary.deposited += 1,
+            Err(error) => {
+                summary.failed += 1;
+                tracing::warn!(
+                    "SWORD deposit failed for post {}: {}",
+                    deposit.post_id,
+                    error
+                );
+                record_deposit_failure(pool, &deposit, &error.to_string(), max_attempts).await?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Adds a pending deposit for every accepted or published paper that has
+/// none yet. Returns the number queued.
+async fn queue_accepted_papers(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let now = Utc::now();
+    let result = sqlx::query(
+        r#"
+        INSERT IGNORE INTO post_repository_deposits (post_id, status, next_attempt_at, created_at, updated_at)
+        SELECT p.id, ?, ?, ?, ?
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE c.code = 'paper'
+          AND p.paper_status IN ('accepted', 'published')
+          AND NOT EXISTS (SELECT 1 FROM post_repository_deposits d WHERE d.post_id = p.id)
+        "#,
+    )
+    .bind(REPOSITORY_DEPOSIT_PENDING)
+    .bind(now)
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Runs the SWORD v2 deposit in three steps, saving progress after each so a
+/// retry resumes where the last attempt stopped:
+/// 1. POST the Atom metadata entry to the Col-IRI with `In-Progress: true`.
+/// 2. POST the rendered PDF to the EM-IRI from the receipt.
+/// 3. POST an empty body to the Edit-IRI with `In-Progress: false`, which
+///    hands the item over to the repository's review workflow.
+async fn deposit_paper(
+    pool: &MySqlPool,
+    client: &Client,
+    config: &SwordConfig,
+    deposit: &DueDeposit,
+) -> anyhow::Result<()> {
+    let (edit_iri, edit_media_iri) = match (&deposit.edit_iri, &deposit.edit_media_iri) {
+        (Some(edit_iri), Some(edit_media_iri)) => (edit_iri.clone(), edit_media_iri.clone()),
+        _ => {
+            let response = config
+                .request(client.post(&config.collection_url))
+                .header(header::CONTENT_TYPE, "application/atom+xml;type=entry")
+                .header("In-Progress", "true")
+                .body(build_atom_entry(deposit))
+                .send()
+                .await
+                .context("SWORD metadata deposit failed")?;
+            let location = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+            let body = check_response(response, "metadata deposit").await?;
+            let mut receipt = parse_receipt(&body)?;
+            if receipt.edit_iri.is_none() {
+                receipt.edit_iri = location;
+            }
+            let (Some(edit_iri), Some(edit_media_iri)) =
+                (receipt.edit_iri.clone(), receipt.edit_media_iri.clone())
+            else {
+                anyhow::bail!("deposit receipt has no Edit-IRI or EM-IRI");
+            };
+            store_receipt(pool, deposit.post_id, &receipt, &body).await?;
+            (edit_iri, edit_media_iri)
+        }
+    };
+
+    if deposit.media_deposited_at.is_none() {
+        let document = PdfDocument {
+            title: deposit.title.clone(),
+            authors: vec![deposit.author_name.clone()],
+            doi: deposit.doi.clone(),
+            license: paper_license(deposit.published_at.unwrap_or(deposit.created_at)),
+            published_on: deposit
+                .published_at
+                .map(|published_at| published_at.format("%Y-%m-%d").to_string()),
+            summary: deposit.summary.clone(),
+            content: deposit.content.clone(),
+            landing_url: landing_page_url(deposit.post_id),
+            base_url: frontend_base_url(),
+        };
+        let pdf = render_pdf(&document)
+            .await
+            .map_err(|error| anyhow::anyhow!(error.to_string()))?;
+
+        let response = config
+            .request(client.post(&edit_media_iri))
+            .header(header::CONTENT_TYPE, "application/pdf")
+            .header(
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=thought-manifold-{}.pdf",
+                    deposit.post_id
+                ),
+            )
+            .header("Packaging", SWORD_BINARY_PACKAGING)
+            .header("In-Progress", "true")
+            .body(pdf)
+            .send()
+            .await
+            .context("SWORD file deposit failed")?;
+        check_response(response, "file deposit").await?;
+
+        let now = Utc::now();
+        sqlx::query(
+            "UPDATE post_repository_deposits SET media_deposited_at = ?, updated_at = ? WHERE post_id = ?",
+        )
+        .bind(now)
+        .bind(now)
+        .bind(deposit.post_id)
+        .execute(pool)
+        .await?;
+    }
+
+    let response = config
+        .request(client.post(&edit_iri))
+        .header("In-Progress", "false")
+        .header(header::CONTENT_LENGTH, "0")
+        .send()
+        .await
+        .context("SWORD deposit completion failed")?;
+    let body = check_response(response, "deposit completion").await?;
+    // Servers may answer the completion with a fresh receipt; keep the
+    // one from the metadata step otherwise.
+    if !body.trim().is_empty()
+        && let Ok(receipt) = parse_receipt(&body)
+    {
+        store_receipt(pool, deposit.post_id, &receipt, &body).await?;
+    }
+
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        UPDATE post_repository_deposits
+        SET
+            status = ?,
+            attempts = attempts + 1,
+            last_error = NULL,
+            next_attempt_at = NULL,
+            deposited_at = ?,
+            updated_at = ?
+        WHERE post_id = ?
+        "#,
+    )
+    .bind(REPOSITORY_DEPOSIT_DEPOSITED)
+    .bind(now)
+    .bind(now)
+    .bind(deposit.post_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn check_response(response: reqwest::Response, step: &str) -> anyhow::Result<String> {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        let body: String = body.chars().take(ERROR_BODY_MAX_CHARS).collect();
+        anyhow::bail!("SWORD {} returned {}: {}", step, status, body.trim());
+    }
+    Ok(body)
+}
+
+/// Stores the receipt links, keeping earlier values for links a later
+/// receipt leaves out.
+async fn store_receipt(
+    pool: &MySqlPool,
+    post_id: i64,
+    receipt: &DepositReceipt,
+    receipt_xml: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE post_repository_deposits
+        SET
+            edit_iri = COALESCE(?, edit_iri),
+            edit_media_iri = COALESCE(?, edit_media_iri),
+            statement_iri = COALESCE(?, statement_iri),
+            splash_url = COALESCE(?, splash_url),
+            receipt_xml = ?,
+            updated_at = ?
+        WHERE post_id = ?
+        "#,
+    )
+    .bind(&receipt.edit_iri)
+    .bind(&receipt.edit_media_iri)
+    .bind(&receipt.statement_iri)
+    .bind(&receipt.splash_url)
+    .bind(receipt_xml)
+    .bind(Utc::now())
+    .bind(post_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Schedules the next attempt with exponential backoff, or gives up once
+/// `max_attempts` deposits have failed.
+async fn record_deposit_failure(
+    pool: &MySqlPool,
+    deposit: &DueDeposit,
+    message: &str,
+    max_attempts: i32,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    let attempts = deposit.attempts + 1;
+    let (status, next_attempt_at) = if attempts >= max_attempts {
+        (REPOSITORY_DEPOSIT_FAILED, None)
+    } else {
+        (
+            REPOSITORY_DEPOSIT_PENDING,
+            Some(now + retry_delay(attempts)),
+        )
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE post_repository_deposits
+        SET status = ?, attempts = ?, last_error = ?, next_attempt_at = ?, updated_at = ?
+        WHERE post_id = ? AND status = ?
+        "#,
+    )
+    .bind(status)
+    .bind(attempts)
+    .bind(message)
+    .bind(next_attempt_at)
+    .bind(now)
+    .bind(deposit.post_id)
+    .bind(REPOSITORY_DEPOSIT_PENDING)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Atom entry with Dublin Core terms, the metadata format every SWORD v2
+/// server accepts.
+fn build_atom_entry(deposit: &DueDeposit) -> String {
+    let published_on = deposit.published_at.unwrap_or(deposit.created_at);
+    let mut dcterms = vec![
+        format!(
+            "<dcterms:title>{}</dcterms:title>",
+            escape(deposit.title.as_str())
+        ),
+        format!(
+            "<dcterms:creator>{}</dcterms:creator>",
+            escape(deposit.author_name.as_str())
+        ),
+        format!(
+            "<dcterms:issued>{}</dcterms:issued>",
+            published_on.format("%Y-%m-%d")
+        ),
+        format!("<dcterms:publisher>{}</dcterms:publisher>", JOURNAL_NAME),
+        "<dcterms:type>Journal Article</dcterms:type>".to_string(),
+        format!(
+            "<dcterms:rights>{}</dcterms:rights>",
+            escape(paper_license(published_on).as_str())
+        ),
+        format!(
+            "<dcterms:relation>{}</dcterms:relation>",
+            escape(landing_page_url(deposit.post_id).as_str())
+        ),
+    ];
+    if let Some(summary) = deposit
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        dcterms.push(format!(
+            "<dcterms:abstract>{}</dcterms:abstract>",
+            escape(summary)
+        ));
+    }
+    if let Some(doi) = &deposit.doi {
+        dcterms.push(format!(
+            "<dcterms:identifier>doi:{}</dcterms:identifier>",
+            escape(doi.as_str())
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<entry xmlns="http://www.w3.org/2005/Atom" xmlns:dcterms="http://purl.org/dc/terms/">
+<title>{title}</title>
+<id>urn:thought-manifold:post:{post_id}</id>
+<updated>{updated}</updated>
+<author><name>{author}</name></author>
+<summary type="text">{summary}</summary>
+{dcterms}
+</entry>
+"#,
+        title = escape(deposit.title.as_str()),
+        post_id = deposit.post_id,
+        updated = Utc::now().to_rfc3339(),
+        author = escape(deposit.author_name.as_str()),
+        summary = escape(deposit.summary.as_deref().unwrap_or_default()),
+        dcterms = dcterms.join("\n"),
+    )
+}
+
+fn parse_receipt(body: &str) -> anyhow::Result<DepositReceipt> {
+    let mut reader = Reader::from_str(body);
+    reader.config_mut().trim_text(true);
+
+    let mut receipt = DepositReceipt::default();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(event)) | Ok(Event::Empty(event))
+                if event.local_name().as_ref() == b"link" =>
+            {
+                let attribute = |name: &str| {
+                    event
+                        .try_get_attribute(name)
+                        .ok()
+                        .flatten()
+                        .and_then(|attribute| attribute.unescape_value().ok())
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                };
+                let (Some(rel), Some(href)) = (attribute("rel"), attribute("href")) else {
+                    continue;
+                };
+                let slot = match rel.as_str() {
+                    "edit" => &mut receipt.edit_iri,
+                    "edit-media" => &mut receipt.edit_media_iri,
+                    SWORD_STATEMENT_REL => &mut receipt.statement_iri,
+                    "alternate" => &mut receipt.splash_url,
+                    _ => continue,
+                };
+                if slot.is_none() {
+                    *slot = Some(href);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(error) => anyhow::bail!("Failed to parse deposit receipt: {}", error),
+        }
+    }
+
+    Ok(receipt)
+}
+
+fn retry_delay(attempts: i32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let secs = RETRY_BASE_SECS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(RETRY_MAX_SECS);
+    chrono::Duration::seconds(secs)
+}
+
+fn frontend_base_url() -> String {
+    env_value("FRONTEND_URL")
+        .map(|value| value.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| "http://localhost:5173".to_string())
+}
+
+fn landing_page_url(post_id: i64) -> String {
+    format!("{}/posts/{}", frontend_base_url(), post_id)
+}
+
+fn deposit_interval_secs() -> u64 {
+    std::env::var("SWORD_DEPOSIT_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SWORD_DEPOSIT_INTERVAL_SECS)
+}
+
+fn max_attempts() -> i32 {
+    std::env::var("SWORD_DEPOSIT_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|raw| raw.parse::<i32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_SWORD_DEPOSIT_MAX_ATTEMPTS)
+}
+
+fn sword_timeout_secs() -> u64 {
+    std::env::var("SWORD_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_SWORD_TIMEOUT_SECS)
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
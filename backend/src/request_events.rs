@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::api_version::unversioned_path;
+use crate::audit_log::RequestMetadata;
+use crate::error::AppError;
+use crate::models::{REQUEST_EVENT_AUTH, REQUEST_EVENT_WRITE};
+use crate::routes::auth::extract_optional_user;
+use crate::tasks;
+
+pub const DEFAULT_REQUEST_EVENT_PURGE_INTERVAL_SECS: u64 = 3_600;
+pub const DEFAULT_REQUEST_EVENT_RETENTION_DAYS: i64 = 30;
+
+const MAX_EVENT_PATH_CHARS: usize = 255;
+
+/// Records where auth and write requests come from and turns away blocked
+/// addresses. Reads are neither logged nor blocked, so a blocked address
+/// can still browse published content.
+pub async fn track_request_source(
+    State(pool): State<MySqlPool>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(event_type) =
+        classify_request(request.method(), &unversioned_path(request.uri().path()))
+    else {
+        return next.run(request).await;
+    };
+
+    let headers = request.headers().clone();
+    let metadata = RequestMetadata::from_headers(&headers);
+    if let Some(ip_address) = &metadata.ip_address {
+        match is_ip_blocked(&pool, ip_address).await {
+            Ok(false) => {}
+            Ok(true) => {
+                return AppError::forbidden("Requests from this address are blocked")
+                    .into_response();
+            }
+            Err(error) => tracing::warn!("IP block lookup failed: {}", error),
+        }
+    }
+
+    let method = request.method().to_string();
+    // Logged without the version segment so reports group a route's
+    // versioned and unversioned paths together.
+    let path: String = unversioned_path(
+        request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(MatchedPath::as_str)
+            .unwrap_or_else(|| request.uri().path()),
+    )
+    .chars()
+    .take(MAX_EVENT_PATH_CHARS)
+    .collect();
+    let response = next.run(request).await;
+    let status_code = response.status().as_u16();
+
+    // Logged after the response so the request is not held up by it.
+    tasks::spawn_task("request_event", async move {
+        let user_id = extract_optional_user(&pool, &headers)
+            .await
+            .ok()
+            .flatten()
+            .map(|user| user.id);
+        let result = sqlx::query(
+            r#"
+            INSERT INTO request_events
+                (event_type, method, path, status_code, user_id, ip_address, user_agent, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(event_type)
+        .bind(method)
+        .bind(path)
+        .bind(status_code)
+        .bind(user_id)
+        .bind(metadata.ip_address)
+        .bind(metadata.user_agent)
+        .bind(Utc::now())
+        .execute(&pool)
+        .await;
+        if let Err(error) = result {
+            tracing::warn!("Failed to record request event: {}", error);
+        }
+    });
+
+    response
+}
+
+/// Sign-in and sign-up calls count as auth events (the frequent session
+/// check is skipped); any other non-read API call is a write.
+fn classify_request(method: &Method, path: &str) -> Option<&'static str> {
+    if path.starts_with("/api/auth/") && path != "/api/auth/me" {
+        return Some(REQUEST_EVENT_AUTH);
+    }
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return None;
+    }
+    path.starts_with("/api/").then_some(REQUEST_EVENT_WRITE)
+}
+
+async fn is_ip_blocked(pool: &MySqlPool, ip_address: &str) -> Result<bool, sqlx::Error> {
+    let blocked: Option<i64> = sqlx::query_scalar(
+        "SELECT id FROM ip_blocks WHERE ip_address = ? AND (expires_at IS NULL OR expires_at > ?)",
+    )
+    .bind(ip_address)
+    .bind(Utc::now())
+    .fetch_optional(pool)
+    .await?;
+    Ok(blocked.is_some())
+}
+
+pub fn spawn_request_event_purge(pool: MySqlPool) {
+    let interval_secs = purge_interval_secs();
+    let retention_days = retention_days();
+    if interval_secs == 0 || retention_days == 0 {
+        tracing::info!("Request event purge job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "request_event_purge",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                let cutoff = Utc::now() - chrono::Duration::days(retention_days);
+                match sqlx::query("DELETE FROM request_events WHERE created_at < ?")
+                    .bind(cutoff)
+                    .execute(&pool)
+                    .await
+                {
+                    Ok(result) if result.rows_affected() > 0 => {
+                        tracing::info!("Purged {} old request event(s)", result.rows_affected())
+                    }
+                    Ok(_) => {}
+                    Err(error) => tracing::error!("Request event purge failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+fn purge_interval_secs() -> u64 {
+    std::env::var("REQUEST_EVENT_PURGE_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_REQUEST_EVENT_PURGE_INTERVAL_SECS)
+}
+
+fn retention_days() -> i64 {
+    std::env::var("REQUEST_EVENT_RETENTION_DAYS")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value >= 0)
+        .unwrap_or(DEFAULT_REQUEST_EVENT_RETENTION_DAYS)
+}
@@ -0,0 +1,89 @@
+//! Request correlation. Every request gets an id, taken from an incoming
+//! `X-Request-Id` when a proxy already assigned one and generated
+//! otherwise, which is echoed in the response, recorded on the request's
+//! tracing span, and carried into background tasks the request spawns, so
+//! a log line from a failed AI review run can be matched to the call that
+//! scheduled it.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Span;
+
+use crate::telemetry;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+const MAX_REQUEST_ID_CHARS: usize = 64;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: Option<RequestId>;
+}
+
+/// The id of the request being served, kept in the request extensions.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Assigns the request id and serves the rest of the request inside it.
+/// Mounted outside the trace layer so its span can pick the id up.
+pub async fn assign_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|raw| is_valid_request_id(raw))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
+
+    let mut response = CURRENT_REQUEST_ID
+        .scope(Some(RequestId(request_id.clone())), next.run(request))
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Span for `TraceLayer`, which every log line of the request nests under.
+pub fn make_request_span(request: &Request) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.as_str())
+        .unwrap_or_default();
+    telemetry::request_span(request, request_id)
+}
+
+/// The id of the request the current task serves or was spawned from;
+/// `None` in scheduled jobs.
+pub fn current() -> Option<String> {
+    CURRENT_REQUEST_ID
+        .try_with(|request_id| request_id.as_ref().map(|id| id.0.clone()))
+        .ok()
+        .flatten()
+}
+
+/// Wraps `work` so it runs as part of the current request, for work moved
+/// onto another task. The id is captured now, not when `work` first runs.
+pub fn propagate<Fut>(work: Fut) -> impl Future<Output = Fut::Output>
+where
+    Fut: Future,
+{
+    CURRENT_REQUEST_ID.scope(current().map(RequestId), work)
+}
+
+/// Ids from clients are echoed in headers and logs, so only short tokens of
+/// URL-safe characters are accepted; anything else is replaced.
+fn is_valid_request_id(raw: &str) -> bool {
+    !raw.is_empty()
+        && raw.len() <= MAX_REQUEST_ID_CHARS
+        && raw
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
@@ -0,0 +1,328 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::cache::invalidate_cached_post;
+use crate::models::{ExpiringSubmission, PAPER_STATUS_REJECTED, PAPER_STATUS_REVISION};
+use crate::notifications::{
+    NOTIFICATION_REVISION_EXPIRED, NOTIFICATION_REVISION_EXPIRY_WARNING, NewNotification,
+    dispatch_notifications,
+};
+use crate::paper_status::{STATUS_TRIGGER_REVISION_EXPIRY, record_status_transition};
+use crate::tasks;
+
+pub const DEFAULT_REVISION_EXPIRY_INTERVAL_SECS: u64 = 3_600;
+pub const DEFAULT_REVISION_DEADLINE_DAYS: i64 = 60;
+pub const DEFAULT_REVISION_EXPIRY_GRACE_DAYS: i64 = 7;
+pub const DEFAULT_REVISION_EXPIRY_WARNING_DAYS: i64 = 7;
+
+// A revision starts with the latest move into `revision`; papers that got
+// there before transitions were logged fall back to their last update. The
+// editor's due date only counts while the latest decision still applies to
+// the current version.
+const REVISION_SUBMISSION_SELECT: &str = r#"
+    SELECT
+        p.id AS post_id,
+        p.title AS title,
+        p.author_id AS author_id,
+        u.username AS author_username,
+        COALESCE(
+            (
+                SELECT MAX(t.created_at)
+                FROM paper_status_transitions t
+                WHERE t.post_id = p.id AND t.to_status = 'revision'
+            ),
+            p.updated_at,
+            p.created_at
+        ) AS revision_started_at,
+        (
+            SELECT ed.revision_due_at
+            FROM editorial_decisions ed
+            WHERE ed.post_id = p.id
+              AND ed.paper_version_id <=> p.latest_paper_version_id
+              AND ed.id = (SELECT MAX(ed2.id) FROM editorial_decisions ed2 WHERE ed2.post_id = p.id)
+        ) AS revision_due_at,
+        p.revision_expiry_warned_at AS expiry_warned_at
+    FROM posts p
+    JOIN users u ON u.id = p.author_id
+    WHERE p.paper_status = 'revision'
+"#;
+
+#[derive(Debug, FromRow)]
+struct RevisionSubmissionRow {
+    post_id: i64,
+    title: String,
+    author_id: i64,
+    author_username: String,
+    revision_started_at: DateTime<Utc>,
+    revision_due_at: Option<DateTime<Utc>>,
+    expiry_warned_at: Option<DateTime<Utc>>,
+}
+
+impl RevisionSubmissionRow {
+    /// The warning stamp is kept across revisions, so one from an earlier
+    /// revision does not count.
+    fn warned_at(&self) -> Option<DateTime<Utc>> {
+        self.expiry_warned_at
+            .filter(|warned_at| *warned_at >= self.revision_started_at)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RevisionExpiryPolicy {
+    /// `None` leaves revisions without an editor due date open indefinitely.
+    deadline_days: Option<i64>,
+    grace_days: i64,
+    warning_days: i64,
+}
+
+impl RevisionExpiryPolicy {
+    fn from_env() -> Self {
+        Self {
+            deadline_days: Some(env_days(
+                "REVISION_DEADLINE_DAYS",
+                DEFAULT_REVISION_DEADLINE_DAYS,
+            ))
+            .filter(|days| *days > 0),
+            grace_days: env_days(
+                "REVISION_EXPIRY_GRACE_DAYS",
+                DEFAULT_REVISION_EXPIRY_GRACE_DAYS,
+            ),
+            warning_days: env_days(
+                "REVISION_EXPIRY_WARNING_DAYS",
+                DEFAULT_REVISION_EXPIRY_WARNING_DAYS,
+            ),
+        }
+    }
+
+    /// Expiry comes a grace period after the revision deadline, which is the
+    /// editor's due date or else the configured number of days.
+    fn expires_at(&self, row: &RevisionSubmissionRow) -> Option<DateTime<Utc>> {
+        let deadline = row.revision_due_at.or_else(|| {
+            self.deadline_days
+                .map(|days| row.revision_started_at + chrono::Duration::days(days))
+        })?;
+
+        Some(deadline + chrono::Duration::days(self.grace_days))
+    }
+
+    fn warn_from(&self, expires_at: DateTime<Utc>) -> DateTime<Utc> {
+        expires_at - chrono::Duration::days(self.warning_days)
+    }
+}
+
+/// Starts the periodic job that warns authors of revisions about to expire
+/// and rejects the ones that did. Setting `REVISION_EXPIRY_INTERVAL_SECS=0`
+/// disables the job.
+pub fn spawn_revision_expiry(pool: MySqlPool) {
+    let interval_secs = expiry_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Revision expiry job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "revision_expiry",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match expire_stale_revisions(&pool).await {
+                    Ok(0) => {}
+                    Ok(handled) => tracing::info!("Handled {} expiring revisions", handled),
+                    Err(error) => tracing::error!("Revision expiry failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+/// Sends one warning per revision once it is within the warning window, and
+/// rejects revisions past their expiry. Returns the number of papers warned
+/// or rejected.
+pub async fn expire_stale_revisions(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let policy = RevisionExpiryPolicy::from_env();
+    let now = Utc::now();
+    let rows = sqlx::query_as::<_, RevisionSubmissionRow>(REVISION_SUBMISSION_SELECT)
+        .fetch_all(pool)
+        .await?;
+    let mut handled = 0;
+
+    for row in rows {
+        let Some(expires_at) = policy.expires_at(&row) else {
+            continue;
+        };
+        if expires_at <= now {
+            if expire_revision(pool, &row, now).await? {
+                handled += 1;
+            }
+        } else if policy.warn_from(expires_at) <= now
+            && row.warned_at().is_none()
+            && warn_revision(pool, &row, expires_at, now).await?
+        {
+            handled += 1;
+        }
+    }
+
+    Ok(handled)
+}
+
+/// Revisions that expire within `within` from now, soonest first. Overdue
+/// ones the job has not processed yet are included.
+pub async fn list_expiring_revisions(
+    pool: &MySqlPool,
+    within: chrono::Duration,
+) -> Result<Vec<ExpiringSubmission>, sqlx::Error> {
+    let policy = RevisionExpiryPolicy::from_env();
+    let horizon = Utc::now() + within;
+    let rows = sqlx::query_as::<_, RevisionSubmissionRow>(REVISION_SUBMISSION_SELECT)
+        .fetch_all(pool)
+        .await?;
+
+    let mut submissions: Vec<ExpiringSubmission> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let expires_at = policy.expires_at(&row).filter(|at| *at <= horizon)?;
+            Some(ExpiringSubmission {
+                expiry_warned_at: row.warned_at(),
+                post_id: row.post_id,
+                title: row.title,
+                author_id: row.author_id,
+                author_username: row.author_username,
+                revision_started_at: row.revision_started_at,
+                revision_due_at: row.revision_due_at,
+                expires_at,
+            })
+        })
+        .collect();
+    submissions.sort_by_key(|submission| (submission.expires_at, submission.post_id));
+
+    Ok(submissions)
+}
+
+/// Marks the warning before notifying, so an overlapping run cannot warn the
+/// author twice about the same revision.
+async fn warn_revision(
+    pool: &MySqlPool,
+    row: &RevisionSubmissionRow,
+    expires_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    let marked = sqlx::query(
+        r#"
+        UPDATE posts
+        SET revision_expiry_warned_at = ?
+        WHERE id = ?
+          AND paper_status = ?
+          AND (revision_expiry_warned_at IS NULL OR revision_expiry_warned_at < ?)
+        "#,
+    )
+    .bind(now)
+    .bind(row.post_id)
+    .bind(PAPER_STATUS_REVISION)
+    .bind(row.revision_started_at)
+    .execute(pool)
+    .await?;
+    if marked.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    dispatch_notifications(
+        pool,
+        vec![NewNotification {
+            user_id: row.author_id,
+            actor_id: None,
+            event_type: NOTIFICATION_REVISION_EXPIRY_WARNING,
+            post_id: Some(row.post_id),
+            comment_id: None,
+            message: format!(
+                "Submit a revision of \"{}\" by {} or the submission will be rejected",
+                row.title,
+                expires_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+        }],
+    )
+    .await;
+
+    Ok(true)
+}
+
+/// Rejects the paper unless the author resubmitted, or a new revision
+/// started, after the row was read.
+async fn expire_revision(
+    pool: &MySqlPool,
+    row: &RevisionSubmissionRow,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE posts
+        SET paper_status = ?, updated_at = ?
+        WHERE id = ?
+          AND paper_status = ?
+          AND NOT EXISTS (
+              SELECT 1
+              FROM paper_status_transitions t
+              WHERE t.post_id = posts.id
+                AND t.to_status = 'revision'
+                AND t.created_at > ?
+          )
+        "#,
+    )
+    .bind(PAPER_STATUS_REJECTED)
+    .bind(now)
+    .bind(row.post_id)
+    .bind(PAPER_STATUS_REVISION)
+    .bind(row.revision_started_at)
+    .execute(&mut *tx)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(false);
+    }
+    record_status_transition(
+        &mut *tx,
+        row.post_id,
+        None,
+        STATUS_TRIGGER_REVISION_EXPIRY,
+        Some(PAPER_STATUS_REVISION),
+        PAPER_STATUS_REJECTED,
+    )
+    .await?;
+    tx.commit().await?;
+    invalidate_cached_post(row.post_id).await;
+
+    dispatch_notifications(
+        pool,
+        vec![NewNotification {
+            user_id: row.author_id,
+            actor_id: None,
+            event_type: NOTIFICATION_REVISION_EXPIRED,
+            post_id: Some(row.post_id),
+            comment_id: None,
+            message: format!(
+                "\"{}\" was rejected because no revision was submitted before the deadline",
+                row.title
+            ),
+        }],
+    )
+    .await;
+
+    Ok(true)
+}
+
+fn expiry_interval_secs() -> u64 {
+    std::env::var("REVISION_EXPIRY_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_REVISION_EXPIRY_INTERVAL_SECS)
+}
+
+fn env_days(name: &str, default: i64) -> i64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|days| *days >= 0)
+        .unwrap_or(default)
+}
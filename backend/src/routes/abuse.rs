@@ -0,0 +1,401 @@
+use std::net::IpAddr;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{delete, get},
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use crate::audit_log::{
+    AUDIT_ACTION_IP_BLOCK_CREATE, AUDIT_ACTION_IP_BLOCK_DELETE, AUDIT_TARGET_IP_BLOCK, AuditEvent,
+    RequestMetadata, record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    AbuseReport, CreateIpBlock, IpBlock, IpBlockListResponse, RapidSubmitter, RequestEvent,
+    RequestEventListResponse, SuspiciousIp,
+};
+use crate::routes::admin::extract_admin_user;
+
+const DEFAULT_REPORT_HOURS: i64 = 24;
+const MAX_REPORT_HOURS: i64 = 24 * 30;
+const DEFAULT_MIN_ACCOUNTS: i64 = 3;
+const DEFAULT_MIN_FAILED_AUTH: i64 = 10;
+const DEFAULT_MIN_WRITES_PER_MINUTE: i64 = 10;
+const DEFAULT_REPORT_LIMIT: i64 = 50;
+const MAX_BLOCK_REASON_CHARS: usize = 1_000;
+
+const IP_BLOCK_SELECT: &str =
+    "SELECT id, ip_address, reason, blocked_by, created_at, expires_at FROM ip_blocks";
+
+#[derive(Debug, Deserialize)]
+pub struct AbuseReportQuery {
+    pub hours: Option<i64>,
+    /// Accounts (signed in or registered) from one address before it is listed.
+    pub min_accounts: Option<i64>,
+    pub min_failed_auth: Option<i64>,
+    pub min_writes_per_minute: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestEventQuery {
+    pub ip_address: Option<String>,
+    pub user_id: Option<i64>,
+    pub event_type: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SuspiciousIpRow {
+    ip_address: String,
+    event_count: i64,
+    account_count: i64,
+    registration_count: i64,
+    failed_auth_count: i64,
+    user_agent_count: i64,
+    /// Comma-separated ids from `GROUP_CONCAT`.
+    user_ids: Option<String>,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    is_blocked: i64,
+}
+
+/// Admin abuse analytics routes, nested under `/api/admin`.
+pub fn abuse_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/abuse/report", get(get_abuse_report))
+        .route("/abuse/events", get(list_request_events))
+        .route(
+            "/abuse/ip-blocks",
+            get(list_ip_blocks).post(create_ip_block),
+        )
+        .route("/abuse/ip-blocks/{block_id}", delete(delete_ip_block))
+}
+
+/// Addresses shared by many accounts or hammering sign-in, and users
+/// submitting writes in rapid bursts, over the last `hours`.
+async fn get_abuse_report(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<AbuseReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let hours = query
+        .hours
+        .unwrap_or(DEFAULT_REPORT_HOURS)
+        .clamp(1, MAX_REPORT_HOURS);
+    let min_accounts = query.min_accounts.unwrap_or(DEFAULT_MIN_ACCOUNTS).max(1);
+    let min_failed_auth = query
+        .min_failed_auth
+        .unwrap_or(DEFAULT_MIN_FAILED_AUTH)
+        .max(1);
+    let min_writes_per_minute = query
+        .min_writes_per_minute
+        .unwrap_or(DEFAULT_MIN_WRITES_PER_MINUTE)
+        .max(1);
+    let limit = query.limit.unwrap_or(DEFAULT_REPORT_LIMIT).clamp(1, 500);
+    let now = Utc::now();
+    let since = now - chrono::Duration::hours(hours);
+
+    let ip_rows = sqlx::query_as::<_, SuspiciousIpRow>(
+        r#"
+        SELECT
+            e.ip_address,
+            COUNT(*) AS event_count,
+            COUNT(DISTINCT e.user_id) AS account_count,
+            CAST(SUM(e.path = '/api/auth/register' AND e.status_code < 300) AS SIGNED) AS registration_count,
+            CAST(SUM(e.event_type = 'auth' AND e.status_code IN (400, 401, 403)) AS SIGNED) AS failed_auth_count,
+            COUNT(DISTINCT e.user_agent) AS user_agent_count,
+            CAST(GROUP_CONCAT(DISTINCT e.user_id ORDER BY e.user_id) AS CHAR) AS user_ids,
+            MIN(e.created_at) AS first_seen,
+            MAX(e.created_at) AS last_seen,
+            CAST(EXISTS(
+                SELECT 1 FROM ip_blocks b
+                WHERE b.ip_address = e.ip_address AND (b.expires_at IS NULL OR b.expires_at > ?)
+            ) AS SIGNED) AS is_blocked
+        FROM request_events e
+        WHERE e.created_at >= ? AND e.ip_address IS NOT NULL
+        GROUP BY e.ip_address
+        HAVING account_count + registration_count >= ? OR failed_auth_count >= ?
+        ORDER BY account_count + registration_count DESC, failed_auth_count DESC, event_count DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(now)
+    .bind(since)
+    .bind(min_accounts)
+    .bind(min_failed_auth)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let shared_ips = ip_rows
+        .into_iter()
+        .map(|row| SuspiciousIp {
+            ip_address: row.ip_address,
+            event_count: row.event_count,
+            account_count: row.account_count,
+            registration_count: row.registration_count,
+            failed_auth_count: row.failed_auth_count,
+            user_agent_count: row.user_agent_count,
+            user_ids: row
+                .user_ids
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|id| id.trim().parse().ok())
+                .collect(),
+            first_seen: row.first_seen,
+            last_seen: row.last_seen,
+            is_blocked: row.is_blocked != 0,
+        })
+        .collect();
+
+    let rapid_submitters = sqlx::query_as::<_, RapidSubmitter>(
+        r#"
+        SELECT
+            m.user_id AS user_id,
+            u.username AS username,
+            CAST(SUM(m.writes) AS SIGNED) AS write_count,
+            MAX(m.writes) AS peak_per_minute,
+            (SELECT COUNT(DISTINCT e2.ip_address) FROM request_events e2
+                WHERE e2.user_id = m.user_id AND e2.created_at >= ?) AS ip_count,
+            MAX(m.last_seen) AS last_seen
+        FROM (
+            SELECT e.user_id, COUNT(*) AS writes, MAX(e.created_at) AS last_seen
+            FROM request_events e
+            WHERE e.created_at >= ? AND e.event_type = 'write' AND e.user_id IS NOT NULL
+            GROUP BY e.user_id, DATE_FORMAT(e.created_at, '%Y-%m-%d %H:%i')
+        ) m
+        JOIN users u ON u.id = m.user_id
+        GROUP BY m.user_id, u.username
+        HAVING peak_per_minute >= ?
+        ORDER BY peak_per_minute DESC, write_count DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(since)
+    .bind(since)
+    .bind(min_writes_per_minute)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(AbuseReport {
+        generated_at: now,
+        window_hours: hours,
+        shared_ips,
+        rapid_submitters,
+    }))
+}
+
+async fn list_request_events(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<RequestEventQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(50).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+    let ip_address = query
+        .ip_address
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let event_type = query
+        .event_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let push_filters = |query_builder: &mut QueryBuilder<'_, MySql>| {
+        query_builder.push(" WHERE 1 = 1");
+        if let Some(ip_address) = ip_address {
+            query_builder.push(" AND ip_address = ");
+            query_builder.push_bind(ip_address.to_string());
+        }
+        if let Some(user_id) = query.user_id {
+            query_builder.push(" AND user_id = ");
+            query_builder.push_bind(user_id);
+        }
+        if let Some(event_type) = event_type {
+            query_builder.push(" AND event_type = ");
+            query_builder.push_bind(event_type.to_string());
+        }
+        if let Some(since) = query.since {
+            query_builder.push(" AND created_at >= ");
+            query_builder.push_bind(since);
+        }
+    };
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        "SELECT id, event_type, method, path, CAST(status_code AS SIGNED) AS status_code, user_id, ip_address, user_agent, created_at FROM request_events",
+    );
+    push_filters(&mut query_builder);
+    query_builder.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+    query_builder.push_bind(i64::from(per_page));
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(offset);
+    let events = query_builder
+        .build_query_as::<RequestEvent>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let mut count_builder = QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM request_events");
+    push_filters(&mut count_builder);
+    let total: i64 = count_builder
+        .build_query_scalar()
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(RequestEventListResponse {
+        events,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+async fn list_ip_blocks(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let blocks = sqlx::query_as::<_, IpBlock>(&format!(
+        "{} ORDER BY created_at DESC, id DESC",
+        IP_BLOCK_SELECT
+    ))
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(IpBlockListResponse { blocks }))
+}
+
+/// Blocks auth and write requests from an address. Blocking an address
+/// that is already blocked replaces the reason and expiry.
+async fn create_ip_block(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateIpBlock>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let ip_address = input
+        .ip_address
+        .trim()
+        .parse::<IpAddr>()
+        .map_err(|_| AppError::bad_request("ip_address must be an IPv4 or IPv6 address"))?
+        .to_string();
+    let reason = input
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    if reason.is_some_and(|reason| reason.chars().count() > MAX_BLOCK_REASON_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "reason must be at most {} characters",
+            MAX_BLOCK_REASON_CHARS
+        )));
+    }
+    let now = Utc::now();
+    if input.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(AppError::bad_request("expires_at must be in the future"));
+    }
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        r#"
+        INSERT INTO ip_blocks (ip_address, reason, blocked_by, created_at, expires_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            reason = VALUES(reason),
+            blocked_by = VALUES(blocked_by),
+            created_at = VALUES(created_at),
+            expires_at = VALUES(expires_at)
+        "#,
+    )
+    .bind(&ip_address)
+    .bind(reason)
+    .bind(admin.id)
+    .bind(now)
+    .bind(input.expires_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let block = sqlx::query_as::<_, IpBlock>(&format!("{} WHERE ip_address = ?", IP_BLOCK_SELECT))
+        .bind(&ip_address)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_IP_BLOCK_CREATE,
+            target_type: AUDIT_TARGET_IP_BLOCK,
+            target_id: block.id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!(block)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok((StatusCode::CREATED, Json(block)))
+}
+
+async fn delete_ip_block(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(block_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let block =
+        sqlx::query_as::<_, IpBlock>(&format!("{} WHERE id = ? FOR UPDATE", IP_BLOCK_SELECT))
+            .bind(block_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::not_found("IP block not found"))?;
+    sqlx::query("DELETE FROM ip_blocks WHERE id = ?")
+        .bind(block_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_IP_BLOCK_DELETE,
+            target_type: AUDIT_TARGET_IP_BLOCK,
+            target_id: block_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(block)),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,729 @@
+use std::io::{Cursor, Write};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::account_activity::describe_device;
+use crate::audit_log::{
+    AUDIT_ACTION_USER_ERASE, AUDIT_ACTION_USER_ERASURE_REJECT, AUDIT_TARGET_USER, AuditEvent,
+    RequestMetadata, record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    AccountActivityResponse, AccountDataExport, AccountEvent, CreateErasureRequest,
+    ERASURE_STATUS_APPROVED, ERASURE_STATUS_PENDING, ERASURE_STATUS_REJECTED,
+    ErasureRequestListResponse, ErasureRequestResponse, ExportedComment, ExportedLike,
+    ExportedPost, ExportedReviewComment, ExportedReviewerAssignment, ReviewErasureRequest, User,
+    UserResponse,
+};
+use crate::notifications::{
+    NOTIFICATION_ERASURE_REJECTED, NOTIFICATION_ERASURE_REQUESTED, NewNotification,
+    dispatch_notifications,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::auth::extract_current_user;
+
+const MAX_REASON_CHARS: usize = 5_000;
+const MAX_REVIEW_NOTE_CHARS: usize = 5_000;
+const ZIP_CONTENT_TYPE: &str = "application/zip";
+/// Shown in place of the name of an erased account.
+const ERASED_DISPLAY_NAME: &str = "Deleted user";
+
+const ERASURE_REQUEST_SELECT: &str = r#"
+    SELECT
+        r.id AS id,
+        r.user_id AS user_id,
+        u.username AS username,
+        r.reason AS reason,
+        r.status AS status,
+        r.reviewed_by AS reviewed_by,
+        r.review_note AS review_note,
+        r.reviewed_at AS reviewed_at,
+        r.created_at AS created_at
+    FROM user_erasure_requests r
+    JOIN users u ON u.id = r.user_id
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// `zip` (default) or `json`.
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountActivityQuery {
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ErasureQueueQuery {
+    pub status: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+/// Self-service routes, nested under `/api/users`.
+pub fn account_data_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/me/export", get(export_account_data))
+        .route("/me/activity", get(list_account_activity))
+        .route(
+            "/me/erasure-request",
+            get(get_own_erasure_request).post(create_erasure_request),
+        )
+}
+
+/// Admin review of erasure requests, nested under `/api/admin`.
+pub fn erasure_queue_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/erasure-requests", get(list_erasure_requests))
+        .route(
+            "/erasure-requests/{request_id}/approve",
+            post(approve_erasure_request),
+        )
+        .route(
+            "/erasure-requests/{request_id}/reject",
+            post(reject_erasure_request),
+        )
+}
+
+/// The caller's profile, posts, comments, review activity and likes, as a
+/// ZIP with one JSON file per section or as a single JSON document.
+async fn export_account_data(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let as_zip = match query.format.as_deref().map(str::trim) {
+        None | Some("") | Some("zip") => true,
+        Some("json") => false,
+        Some(_) => {
+            return Err(AppError::bad_request("format must be one of: zip, json"));
+        }
+    };
+
+    let export = collect_account_data(&pool, current_user)
+        .await
+        .map_err(AppError::internal)?;
+    if !as_zip {
+        return Ok(Json(export).into_response());
+    }
+
+    let archive = build_export_archive(&export).map_err(AppError::internal)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, ZIP_CONTENT_TYPE.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"user-{}-data-export.zip\"",
+                    export.profile.id
+                ),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+async fn collect_account_data(
+    pool: &MySqlPool,
+    user: User,
+) -> Result<AccountDataExport, sqlx::Error> {
+    let user_id = user.id;
+    let posts = sqlx::query_as::<_, ExportedPost>(
+        r#"
+        SELECT
+            p.id AS id,
+            p.title AS title,
+            p.content AS content,
+            p.summary AS summary,
+            p.github_url AS github_url,
+            c.code AS category,
+            p.paper_status AS paper_status,
+            p.is_published AS is_published,
+            p.is_preprint AS is_preprint,
+            p.published_at AS published_at,
+            pf.file_name AS file_name,
+            p.created_at AS created_at,
+            p.updated_at AS updated_at
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        LEFT JOIN post_files pf ON pf.post_id = p.id
+        WHERE p.author_id = ?
+        ORDER BY p.created_at ASC, p.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let comments = sqlx::query_as::<_, ExportedComment>(
+        r#"
+        SELECT
+            cm.id AS id,
+            cm.post_id AS post_id,
+            p.title AS post_title,
+            cm.parent_comment_id AS parent_comment_id,
+            cm.content AS content,
+            cm.is_deleted AS is_deleted,
+            cm.edited_at AS edited_at,
+            cm.created_at AS created_at
+        FROM comments cm
+        JOIN posts p ON p.id = cm.post_id
+        WHERE cm.author_id = ?
+        ORDER BY cm.created_at ASC, cm.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let review_comments = sqlx::query_as::<_, ExportedReviewComment>(
+        r#"
+        SELECT
+            rc.id AS id,
+            rc.post_id AS post_id,
+            p.title AS post_title,
+            rc.paper_version_id AS paper_version_id,
+            rc.parent_comment_id AS parent_comment_id,
+            rc.content AS content,
+            rc.is_anonymous AS is_anonymous,
+            rc.is_deleted AS is_deleted,
+            rc.resolution_status AS resolution_status,
+            rc.created_at AS created_at
+        FROM paper_review_comments rc
+        JOIN posts p ON p.id = rc.post_id
+        WHERE rc.author_id = ?
+        ORDER BY rc.created_at ASC, rc.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let reviewer_assignments = sqlx::query_as::<_, ExportedReviewerAssignment>(
+        r#"
+        SELECT
+            ra.id AS id,
+            ra.post_id AS post_id,
+            p.title AS post_title,
+            ra.status AS status,
+            ra.due_at AS due_at,
+            ra.responded_at AS responded_at,
+            ra.submitted_at AS submitted_at,
+            ra.created_at AS created_at
+        FROM reviewer_assignments ra
+        JOIN posts p ON p.id = ra.post_id
+        WHERE ra.reviewer_id = ?
+        ORDER BY ra.created_at ASC, ra.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let likes = sqlx::query_as::<_, ExportedLike>(
+        r#"
+        SELECT pl.post_id AS post_id, p.title AS post_title, pl.created_at AS created_at
+        FROM post_likes pl
+        JOIN posts p ON p.id = pl.post_id
+        WHERE pl.user_id = ?
+        ORDER BY pl.created_at ASC, pl.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(AccountDataExport {
+        exported_at: Utc::now(),
+        profile: UserResponse::from(user),
+        posts,
+        comments,
+        review_comments,
+        reviewer_assignments,
+        likes,
+    })
+}
+
+fn build_export_archive(export: &AccountDataExport) -> anyhow::Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    write_json_entry(&mut zip, options, "profile.json", &export.profile)?;
+    write_json_entry(&mut zip, options, "posts.json", &export.posts)?;
+    write_json_entry(&mut zip, options, "comments.json", &export.comments)?;
+    write_json_entry(
+        &mut zip,
+        options,
+        "review_comments.json",
+        &export.review_comments,
+    )?;
+    write_json_entry(
+        &mut zip,
+        options,
+        "reviewer_assignments.json",
+        &export.reviewer_assignments,
+    )?;
+    write_json_entry(&mut zip, options, "likes.json", &export.likes)?;
+    write_json_entry(
+        &mut zip,
+        options,
+        "export.json",
+        &serde_json::json!({"exported_at": export.exported_at, "user_id": export.profile.id}),
+    )?;
+
+    Ok(zip.finish()?.into_inner())
+}
+
+fn write_json_entry<T: Serialize>(
+    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    options: SimpleFileOptions,
+    name: &str,
+    value: &T,
+) -> anyhow::Result<()> {
+    zip.start_file(name, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(value)?)?;
+    Ok(())
+}
+
+/// The caller's sign-ins, failed password attempts and username changes,
+/// newest first, so they can spot access they don't recognize.
+async fn list_account_activity(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<AccountActivityQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+
+    let mut events = sqlx::query_as::<_, AccountEvent>(
+        r#"
+        SELECT id, event_type, auth_method, ip_address, user_agent, created_at
+        FROM account_events
+        WHERE user_id = ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(current_user.id)
+    .bind(i64::from(per_page))
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    for event in &mut events {
+        event.device = event.user_agent.as_deref().and_then(describe_device);
+    }
+
+    let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM account_events WHERE user_id = ?")
+        .bind(current_user.id)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(AccountActivityResponse {
+        events,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+/// The caller's most recent erasure request, if any.
+async fn get_own_erasure_request(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let request = sqlx::query_as::<_, ErasureRequestResponse>(&format!(
+        "{} WHERE r.user_id = ? ORDER BY r.created_at DESC, r.id DESC LIMIT 1",
+        ERASURE_REQUEST_SELECT
+    ))
+    .bind(current_user.id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("No erasure request found"))?;
+
+    Ok(Json(request))
+}
+
+/// Asks an admin to erase the caller's account. Only one request can be
+/// pending at a time.
+async fn create_erasure_request(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateErasureRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let reason = input
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if reason.is_some_and(|reason| reason.chars().count() > MAX_REASON_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "Reason must be at most {} characters",
+            MAX_REASON_CHARS
+        )));
+    }
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    // Locks the user row so two concurrent requests cannot both pass the
+    // pending check.
+    sqlx::query("SELECT id FROM users WHERE id = ? FOR UPDATE")
+        .bind(current_user.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    let pending: Option<i64> = sqlx::query_scalar(
+        "SELECT id FROM user_erasure_requests WHERE user_id = ? AND status = ? LIMIT 1",
+    )
+    .bind(current_user.id)
+    .bind(ERASURE_STATUS_PENDING)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    if pending.is_some() {
+        return Err(AppError::conflict("An erasure request is already pending"));
+    }
+
+    let result = sqlx::query(
+        "INSERT INTO user_erasure_requests (user_id, reason, status, created_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(current_user.id)
+    .bind(reason)
+    .bind(ERASURE_STATUS_PENDING)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    let request_id = result.last_insert_id() as i64;
+
+    let admin_ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM users WHERE is_admin = TRUE")
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    let message = format!(
+        "{} asked for their account to be erased",
+        current_user.username
+    );
+    dispatch_notifications(
+        &pool,
+        admin_ids
+            .into_iter()
+            .map(|admin_id| NewNotification {
+                user_id: admin_id,
+                actor_id: Some(current_user.id),
+                event_type: NOTIFICATION_ERASURE_REQUESTED,
+                post_id: None,
+                comment_id: None,
+                message: message.clone(),
+            })
+            .collect(),
+    )
+    .await;
+
+    let request = fetch_erasure_request(&pool, request_id).await?;
+    Ok((StatusCode::CREATED, Json(request)))
+}
+
+async fn list_erasure_requests(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ErasureQueueQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let status = match query.status.as_deref().map(str::trim) {
+        None | Some("") => ERASURE_STATUS_PENDING,
+        Some(ERASURE_STATUS_PENDING) => ERASURE_STATUS_PENDING,
+        Some(ERASURE_STATUS_APPROVED) => ERASURE_STATUS_APPROVED,
+        Some(ERASURE_STATUS_REJECTED) => ERASURE_STATUS_REJECTED,
+        Some(_) => {
+            return Err(AppError::bad_request(
+                "status must be one of: pending, approved, rejected",
+            ));
+        }
+    };
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+
+    let mut query_builder = QueryBuilder::<MySql>::new(ERASURE_REQUEST_SELECT);
+    query_builder.push(" WHERE r.status = ");
+    query_builder.push_bind(status);
+    query_builder.push(" ORDER BY r.created_at ASC, r.id ASC LIMIT ");
+    query_builder.push_bind(i64::from(per_page));
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(offset);
+    let requests = query_builder
+        .build_query_as::<ErasureRequestResponse>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let (total,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM user_erasure_requests WHERE status = ?")
+            .bind(status)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(ErasureRequestListResponse {
+        requests,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+/// Erases the account by anonymizing it in place. Posts and review comments
+/// keep their ids and author link so citations, DOIs and review threads
+/// stay intact; they are simply attributed to a deleted user. Discussion
+/// comments are blanked, and the inbox, subscriptions and ORCID link are
+/// removed. Setting `erased_at` ends every session of the account.
+async fn approve_erasure_request(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(request_id): Path<i64>,
+    Json(input): Json<ReviewErasureRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let note = normalize_review_note(input.note.as_deref())?;
+    let request = fetch_erasure_request(&pool, request_id).await?;
+    ensure_pending(&request)?;
+
+    let is_admin: bool = sqlx::query_scalar("SELECT is_admin FROM users WHERE id = ?")
+        .bind(request.user_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    if is_admin {
+        return Err(AppError::conflict(
+            "Remove admin rights before erasing this account",
+        ));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    mark_reviewed(&mut tx, request_id, ERASURE_STATUS_APPROVED, admin.id, note).await?;
+
+    let user_id = request.user_id;
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET username = ?, email = ?, hashed_password = NULL, google_id = NULL,
+            display_name = ?, bio = NULL, introduction = NULL, hobbies = NULL,
+            interests = NULL, research_areas = NULL, affiliation = NULL, website = NULL,
+            orcid_id = NULL, avatar_url = NULL, erased_at = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(format!("deleted-user-{}", user_id))
+    .bind(format!("deleted-user-{}@deleted.invalid", user_id))
+    .bind(ERASED_DISPLAY_NAME)
+    .bind(now)
+    .bind(now)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    sqlx::query(
+        r#"
+        DELETE h FROM comment_edit_history h
+        JOIN comments c ON c.id = h.comment_id
+        WHERE c.author_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    sqlx::query(
+        "UPDATE comments SET is_deleted = TRUE, deleted_at = COALESCE(deleted_at, ?), content = '', updated_at = ? WHERE author_id = ?",
+    )
+    .bind(now)
+    .bind(now)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    for statement in [
+        "DELETE FROM notifications WHERE user_id = ?",
+        "DELETE FROM subscriptions WHERE user_id = ?",
+        "DELETE FROM notification_preferences WHERE user_id = ?",
+        "DELETE FROM push_subscriptions WHERE user_id = ?",
+        "DELETE FROM user_orcid_links WHERE user_id = ?",
+        "DELETE FROM request_events WHERE user_id = ?",
+        "DELETE FROM calendar_feed_tokens WHERE user_id = ?",
+        "DELETE FROM user_follows WHERE follower_id = ?",
+        "DELETE FROM user_follows WHERE followed_id = ?",
+        "DELETE FROM user_blocks WHERE blocker_id = ?",
+        "DELETE FROM user_blocks WHERE blocked_id = ?",
+        "DELETE FROM username_history WHERE user_id = ?",
+        "DELETE FROM user_expertise_tags WHERE user_id = ?",
+        "DELETE FROM account_events WHERE user_id = ?",
+    ] {
+        sqlx::query(statement)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+    }
+
+    // The previous username and profile are left out of the audit entry so
+    // the log does not keep what was just erased.
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_USER_ERASE,
+            target_type: AUDIT_TARGET_USER,
+            target_id: user_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!({"erasure_request_id": request_id})),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let request = fetch_erasure_request(&pool, request_id).await?;
+    Ok(Json(request))
+}
+
+async fn reject_erasure_request(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(request_id): Path<i64>,
+    Json(input): Json<ReviewErasureRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let note = normalize_review_note(input.note.as_deref())?;
+    let request = fetch_erasure_request(&pool, request_id).await?;
+    ensure_pending(&request)?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    mark_reviewed(&mut tx, request_id, ERASURE_STATUS_REJECTED, admin.id, note).await?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_USER_ERASURE_REJECT,
+            target_type: AUDIT_TARGET_USER,
+            target_id: request.user_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(request)),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let message = match note {
+        Some(note) => format!("Your account erasure request was declined: {}", note),
+        None => "Your account erasure request was declined".to_string(),
+    };
+    dispatch_notifications(
+        &pool,
+        vec![NewNotification {
+            user_id: request.user_id,
+            actor_id: Some(admin.id),
+            event_type: NOTIFICATION_ERASURE_REJECTED,
+            post_id: None,
+            comment_id: None,
+            message,
+        }],
+    )
+    .await;
+
+    let request = fetch_erasure_request(&pool, request_id).await?;
+    Ok(Json(request))
+}
+
+async fn mark_reviewed(
+    tx: &mut sqlx::Transaction<'_, MySql>,
+    request_id: i64,
+    status: &str,
+    reviewer_id: i64,
+    note: Option<&str>,
+) -> Result<(), AppError> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE user_erasure_requests
+        SET status = ?, reviewed_by = ?, review_note = ?, reviewed_at = ?
+        WHERE id = ? AND status = ?
+        "#,
+    )
+    .bind(status)
+    .bind(reviewer_id)
+    .bind(note)
+    .bind(Utc::now())
+    .bind(request_id)
+    .bind(ERASURE_STATUS_PENDING)
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::internal)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::conflict(
+            "Erasure request has already been reviewed",
+        ));
+    }
+    Ok(())
+}
+
+async fn fetch_erasure_request(
+    pool: &MySqlPool,
+    request_id: i64,
+) -> Result<ErasureRequestResponse, AppError> {
+    sqlx::query_as::<_, ErasureRequestResponse>(&format!(
+        "{} WHERE r.id = ?",
+        ERASURE_REQUEST_SELECT
+    ))
+    .bind(request_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Erasure request not found"))
+}
+
+fn ensure_pending(request: &ErasureRequestResponse) -> Result<(), AppError> {
+    if request.status != ERASURE_STATUS_PENDING {
+        return Err(AppError::conflict(
+            "Erasure request has already been reviewed",
+        ));
+    }
+    Ok(())
+}
+
+fn normalize_review_note(raw: Option<&str>) -> Result<Option<&str>, AppError> {
+    let note = raw.map(str::trim).filter(|note| !note.is_empty());
+    if note.is_some_and(|note| note.chars().count() > MAX_REVIEW_NOTE_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "Note must be at most {} characters",
+            MAX_REVIEW_NOTE_CHARS
+        )));
+    }
+    Ok(note)
+}
@@ -1,46 +1,155 @@
+use std::collections::HashMap;
+
 use axum::{
     Router,
+    body::Body,
     extract::{Json, Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, StatusCode, header},
     response::IntoResponse,
-    routing::{delete, get, put},
+    routing::{delete, get, post, put},
 };
-use chrono::{Datelike, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use serde::Deserialize;
-use sqlx::MySqlPool;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
 
-use crate::ai_review::{fetch_admin_reviews, fetch_ai_review_metrics, parse_status_filter};
-use crate::metrics::compute_impact_factor;
-use crate::models::{User, UserResponse};
-use crate::routes::auth::extract_current_user;
+use crate::ai_review::{
+    fetch_admin_reviews, fetch_ai_review_metrics, fetch_review_health, parse_status_filter,
+};
+use crate::audit_log::{
+    AUDIT_ACTION_COMMENT_BAN_LIFT, AUDIT_ACTION_COMMENT_DELETE, AUDIT_ACTION_COMMENT_REPORT_ACTION,
+    AUDIT_ACTION_FEATURE_FLAG_RESET, AUDIT_ACTION_FEATURE_FLAG_UPDATE,
+    AUDIT_ACTION_POST_CATEGORY_UPDATE, AUDIT_ACTION_POST_DELETE, AUDIT_ACTION_POST_RETRACT,
+    AUDIT_ACTION_POST_RETRACTION_WITHDRAW, AUDIT_ACTION_POST_UNPUBLISH, AUDIT_ACTION_SETTING_RESET,
+    AUDIT_ACTION_SETTING_UPDATE, AUDIT_ACTION_USER_DELETE, AUDIT_ACTION_USER_ROLE_UPDATE,
+    AUDIT_ACTION_USER_SUSPEND, AUDIT_ACTION_USER_UNSUSPEND, AUDIT_TARGET_COMMENT,
+    AUDIT_TARGET_COMMENT_REPORT, AUDIT_TARGET_FEATURE_FLAG, AUDIT_TARGET_POST,
+    AUDIT_TARGET_SYSTEM_SETTING, AUDIT_TARGET_USER, AuditEvent, RequestMetadata,
+    record_audit_event, snapshot_post,
+};
+use crate::cache::{cache_stats, invalidate_cached_post};
+use crate::doi_registration::{
+    DEPOSIT_STATUS_FAILED, DEPOSIT_STATUS_PENDING, DEPOSIT_STATUS_REGISTERED,
+    upsert_doi_registration, upsert_registered_doi_metadata,
+};
+use crate::error::AppError;
+use crate::feature_flags::{
+    FEATURE_ROLES, find_feature_flag, list_feature_flags, reload_feature_flags,
+};
+use crate::metrics::{
+    ExportDataset, ExportFormat, compute_impact_factor, resolve_export_columns,
+    stream_metrics_export,
+};
+use crate::models::{
+    AdminBulkCategoryUpdate, AdminBulkPostResult, AdminBulkUnpublish, AdminPostListResponse,
+    AdminPostSummary, AdminUserListResponse, AdminUserSummary, AuditLogEntry, AuditLogListResponse,
+    CommentReportListResponse, CommentReportResponse, ExpiringSubmissionListResponse, FeatureFlag,
+    FeatureFlagListResponse, PostDoiDeposit, PostDoiDepositListResponse, PostDoiRegistration,
+    PostRepositoryDeposit, PostRepositoryDepositListResponse, REPOSITORY_DEPOSIT_DEPOSITED,
+    REPOSITORY_DEPOSIT_FAILED, REPOSITORY_DEPOSIT_PENDING, RetractionNotice, ReviewerSuggestion,
+    ReviewerSuggestionResponse, SuspendUser, SystemSetting, SystemSettingListResponse,
+    UpdateFeatureFlag, UpdateSystemSetting, User, UserResponse, UserSuspension,
+    UserSuspensionListResponse,
+};
+use crate::revision_expiry::list_expiring_revisions;
+use crate::routes::auth::{USER_SUSPENSION_SELECT, extract_current_user, fetch_active_suspension};
 use crate::routes::comments::{apply_comment_delete_policy, find_comment_target};
+use crate::routes::posts::validate_paper_status_filter;
+use crate::settings::{find_setting, list_system_settings, reload_settings};
+use crate::system_usage::collect_system_usage;
+use crate::tasks::task_statuses;
+use crate::trash::{fetch_trash_item, trash_post, trash_user};
 
 // ============================
 // Helper: Extract Admin User
 // ============================
-pub async fn extract_admin_user(
-    pool: &MySqlPool,
-    headers: &HeaderMap,
-) -> Result<User, (StatusCode, Json<serde_json::Value>)> {
+pub async fn extract_admin_user(pool: &MySqlPool, headers: &HeaderMap) -> Result<User, AppError> {
     let user = extract_current_user(pool, headers).await?;
     if !user.is_admin {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({"detail": "Admin access required"})),
-        ));
+        return Err(AppError::forbidden("Admin access required"));
     }
     Ok(user)
 }
 
+
 pub fn admin_routes() -> Router<MySqlPool> {
     Router::new()
         .route("/stats", get(admin_stats))
+        .route("/audit-log", get(admin_list_audit_log))
+        .route("/settings", get(admin_list_settings))
+        .route("/feature-flags", get(admin_list_feature_flags))
+        .route(
+            "/feature-flags/{key}",
+            put(admin_update_feature_flag).delete(admin_reset_feature_flag),
+        )
+        .route(
+            "/settings/{key}",
+            put(admin_update_setting).delete(admin_reset_setting),
+        )
+        .route("/reviewers/suggest", get(admin_suggest_reviewers))
         .route("/users", get(admin_list_users))
         .route("/reviews", get(admin_list_reviews))
+        .route("/reviews/health", get(admin_review_health))
+        .route(
+            "/expiring-submissions",
+            get(admin_list_expiring_submissions),
+        )
+        .route("/metrics/export", get(admin_export_metrics))
+        .route("/system/usage", get(admin_system_usage))
+        .route("/system/cache", get(admin_cache_stats))
+        .route("/tasks", get(admin_list_tasks))
         .route("/users/{user_id}/role", put(admin_update_role))
         .route("/users/{user_id}", delete(admin_delete_user))
+        .route(
+            "/users/{user_id}/suspend",
+            post(admin_suspend_user).delete(admin_unsuspend_user),
+        )
+        .route(
+            "/users/{user_id}/suspensions",
+            get(admin_list_user_suspensions),
+        )
+        .route("/posts", get(admin_list_posts))
+        .route("/posts/bulk-unpublish", post(admin_bulk_unpublish_posts))
+        .route(
+            "/posts/bulk-category",
+            post(admin_bulk_update_post_category),
+        )
         .route("/posts/{post_id}", delete(admin_delete_post))
+        .route(
+            "/posts/{post_id}/doi-registration",
+            put(admin_register_post_doi),
+        )
+        .route("/doi-deposits", get(admin_list_doi_deposits))
+        .route(
+            "/posts/{post_id}/doi-deposit/retry",
+            post(admin_retry_doi_deposit),
+        )
+        .route("/repository-deposits", get(admin_list_repository_deposits))
+        .route(
+            "/posts/{post_id}/repository-deposit",
+            get(admin_get_repository_deposit),
+        )
+        .route(
+            "/posts/{post_id}/repository-deposit/retry",
+            post(admin_retry_repository_deposit),
+        )
+        .route(
+            "/posts/{post_id}/retraction",
+            put(admin_retract_post).delete(admin_withdraw_retraction),
+        )
         .route("/comments/{comment_id}", delete(admin_delete_comment))
+        .route("/comment-reports", get(admin_list_comment_reports))
+        .route(
+            "/comment-reports/{report_id}/dismiss",
+            post(admin_dismiss_comment_report),
+        )
+        .route(
+            "/comment-reports/{report_id}/action",
+            post(admin_action_comment_report),
+        )
+        .route(
+            "/users/{user_id}/comment-ban",
+            delete(admin_lift_comment_ban),
+        )
 }
 
 // ============================
@@ -49,76 +158,43 @@ pub fn admin_routes() -> Router<MySqlPool> {
 async fn admin_stats(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let _admin = extract_admin_user(&pool, &headers).await?;
 
     let user_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     let post_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM posts")
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     let comment_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM comments")
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     let total_views: (i64,) =
         sqlx::query_as("SELECT CAST(COALESCE(SUM(view_count), 0) AS SIGNED) FROM post_stats")
             .fetch_one(&pool)
             .await
-            .map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({"detail": e.to_string()})),
-                )
-            })?;
+            .map_err(AppError::internal)?;
 
     let total_likes: (i64,) =
         sqlx::query_as("SELECT CAST(COALESCE(SUM(like_count), 0) AS SIGNED) FROM post_stats")
             .fetch_one(&pool)
             .await
-            .map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({"detail": e.to_string()})),
-                )
-            })?;
+            .map_err(AppError::internal)?;
 
     let journal_metrics = compute_impact_factor(&pool, Utc::now().year())
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
-
-    let ai_review_metrics = fetch_ai_review_metrics(&pool).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+        .map_err(AppError::internal)?;
+
+    let ai_review_metrics = fetch_ai_review_metrics(&pool)
+        .await
+        .map_err(AppError::internal)?;
 
     Ok(Json(serde_json::json!({
         "total_users": user_count.0,
@@ -142,15 +218,12 @@ async fn admin_list_reviews(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Query(query): Query<AdminReviewQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let _admin = extract_admin_user(&pool, &headers).await?;
 
     let status_filter = if let Some(status_raw) = query.status.as_deref() {
         Some(parse_status_filter(status_raw).ok_or_else(|| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"detail": "Invalid status filter. Use pending|completed|failed"})),
-            )
+            AppError::bad_request("Invalid status filter. Use pending|completed|failed")
         })?)
     } else {
         None
@@ -161,81 +234,489 @@ async fn admin_list_reviews(
 
     let response = fetch_admin_reviews(&pool, status_filter, page, per_page)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     Ok(Json(response))
 }
 
+// ============================
+// GET /admin/reviews/health
+// ============================
+const DEFAULT_REVIEW_HEALTH_WINDOW_HOURS: i64 = 24;
+const MAX_REVIEW_HEALTH_WINDOW_HOURS: i64 = 720;
+
+#[derive(Debug, Deserialize)]
+struct ReviewHealthQuery {
+    hours: Option<i64>,
+}
+
+/// Queue depth, latency, failures and Gemini rate limiting of the AI review
+/// pipeline. Latency and failures cover the last `hours` (default 24).
+async fn admin_review_health(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ReviewHealthQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let hours = query
+        .hours
+        .unwrap_or(DEFAULT_REVIEW_HEALTH_WINDOW_HOURS)
+        .clamp(1, MAX_REVIEW_HEALTH_WINDOW_HOURS);
+    let health = fetch_review_health(&pool, hours)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(health))
+}
+
+// ============================
+// GET /admin/expiring-submissions
+// ============================
+const MAX_BULK_POST_IDS: usize = 100;
+const DEFAULT_EXPIRING_WITHIN_DAYS: i64 = 14;
+const MAX_EXPIRING_WITHIN_DAYS: i64 = 365;
+
+#[derive(Debug, Deserialize)]
+struct ExpiringSubmissionQuery {
+    within_days: Option<i64>,
+}
+
+async fn admin_list_expiring_submissions(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ExpiringSubmissionQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let within_days = query
+        .within_days
+        .unwrap_or(DEFAULT_EXPIRING_WITHIN_DAYS)
+        .clamp(0, MAX_EXPIRING_WITHIN_DAYS);
+    let submissions = list_expiring_revisions(&pool, chrono::Duration::days(within_days))
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(ExpiringSubmissionListResponse {
+        total: submissions.len() as i64,
+        submissions,
+        within_days,
+    }))
+}
+
+const DEFAULT_EXPORT_JOURNAL_YEARS: i32 = 5;
+const MAX_EXPORT_JOURNAL_YEARS: i32 = 50;
+
+#[derive(Debug, Deserialize)]
+struct MetricsExportQuery {
+    dataset: Option<String>,
+    format: Option<String>,
+    columns: Option<String>,
+    from_year: Option<i32>,
+    to_year: Option<i32>,
+}
+
+// ============================
+// GET /admin/system/usage
+// ============================
+const DEFAULT_USAGE_LIST_LIMIT: i64 = 10;
+const MAX_USAGE_LIST_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+struct SystemUsageQuery {
+    limit: Option<i64>,
+}
+
+/// Upload directory sizes, per-table database sizes and the largest posts
+/// and attachments, for capacity planning.
+async fn admin_system_usage(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<SystemUsageQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_USAGE_LIST_LIMIT)
+        .clamp(1, MAX_USAGE_LIST_LIMIT);
+    let report = collect_system_usage(&pool, limit)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(report))
+}
+
+// ============================
+// GET /admin/system/cache
+// ============================
+/// Hit, miss and error counts of the response cache.
+async fn admin_cache_stats(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    Ok(Json(cache_stats()))
+}
+
+// ============================
+// GET /admin/tasks
+// ============================
+/// Background tasks with their run and panic counts since startup.
+async fn admin_list_tasks(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    Ok(Json(task_statuses()))
+}
+
+// ============================
+// GET /admin/metrics/export
+// ============================
+async fn admin_export_metrics(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<MetricsExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let bad_request = |detail: String| AppError::bad_request(detail);
+
+    let dataset = ExportDataset::parse(query.dataset.as_deref().unwrap_or("authors"))
+        .ok_or_else(|| bad_request("Invalid dataset. Use authors|journal|reviews".to_string()))?;
+    let format = ExportFormat::parse(query.format.as_deref().unwrap_or("csv"))
+        .ok_or_else(|| bad_request("Invalid format. Use csv|json".to_string()))?;
+    let columns = resolve_export_columns(dataset, query.columns.as_deref()).map_err(|column| {
+        bad_request(format!(
+            "Unknown column '{}' for dataset {}. Available: {}",
+            column,
+            dataset.as_str(),
+            dataset.columns().join(", ")
+        ))
+    })?;
+
+    let to_year = query.to_year.unwrap_or_else(|| Utc::now().year());
+    let from_year = query
+        .from_year
+        .unwrap_or(to_year - DEFAULT_EXPORT_JOURNAL_YEARS + 1);
+    if !(1900..=3000).contains(&from_year)
+        || !(1900..=3000).contains(&to_year)
+        || from_year > to_year
+        || to_year - from_year >= MAX_EXPORT_JOURNAL_YEARS
+    {
+        return Err(bad_request(format!(
+            "Years must be between 1900 and 3000, in order, and span at most {} years",
+            MAX_EXPORT_JOURNAL_YEARS
+        )));
+    }
+
+    let filename = format!(
+        "{}-metrics-{}.{}",
+        dataset.as_str(),
+        Utc::now().format("%Y%m%d"),
+        format.extension()
+    );
+    let stream = stream_metrics_export(pool, dataset, format, columns, from_year..=to_year);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        Body::from_stream(stream),
+    ))
+}
+
+// ============================
+// GET /admin/reviewers/suggest
+// ============================
+const DEFAULT_REVIEWER_SUGGESTION_LIMIT: i64 = 10;
+const MAX_REVIEWER_SUGGESTION_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+struct ReviewerSuggestionQuery {
+    post_id: i64,
+    limit: Option<i64>,
+}
+
+/// Users whose expertise tags overlap the submission's tags, most overlap
+/// first and then fewest open reviews. The author, reviewers already on the
+/// paper (unless they declined) and suspended users are left out.
+async fn admin_suggest_reviewers(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ReviewerSuggestionQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_REVIEWER_SUGGESTION_LIMIT)
+        .clamp(1, MAX_REVIEWER_SUGGESTION_LIMIT);
+
+    let author_id: i64 =
+        sqlx::query_scalar("SELECT author_id FROM posts WHERE id = ? AND deleted_at IS NULL")
+            .bind(query.post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    let post_tags: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT t.name
+        FROM post_tags pt
+        JOIN tags t ON t.id = pt.tag_id
+        WHERE pt.post_id = ?
+        ORDER BY t.name ASC
+        "#,
+    )
+    .bind(query.post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let now = Utc::now();
+    let mut suggestions = sqlx::query_as::<_, ReviewerSuggestion>(
+        r#"
+        SELECT
+            u.id AS user_id,
+            u.username,
+            u.display_name,
+            u.affiliation,
+            COUNT(*) AS tag_overlap,
+            (
+                SELECT COUNT(*)
+                FROM reviewer_assignments open_ra
+                JOIN posts open_p ON open_p.id = open_ra.post_id
+                WHERE open_ra.reviewer_id = u.id
+                  AND open_ra.status IN ('invited', 'accepted')
+                  AND open_p.deleted_at IS NULL
+            ) AS open_assignments
+        FROM post_tags pt
+        JOIN user_expertise_tags ue ON ue.tag_id = pt.tag_id
+        JOIN users u ON u.id = ue.user_id
+        WHERE pt.post_id = ?
+          AND u.id <> ?
+          AND u.deleted_at IS NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM reviewer_assignments ra
+              WHERE ra.post_id = pt.post_id AND ra.reviewer_id = u.id AND ra.status <> 'declined'
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM user_suspensions us
+              WHERE us.user_id = u.id
+                AND us.lifted_at IS NULL
+                AND us.starts_at <= ?
+                AND (us.expires_at IS NULL OR us.expires_at > ?)
+          )
+        GROUP BY u.id, u.username, u.display_name, u.affiliation
+        ORDER BY tag_overlap DESC, open_assignments ASC, u.id ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(query.post_id)
+    .bind(author_id)
+    .bind(now)
+    .bind(now)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    if !suggestions.is_empty() {
+        let mut matched_qb = QueryBuilder::<MySql>::new(
+            r#"
+            SELECT ue.user_id, t.name
+            FROM user_expertise_tags ue
+            JOIN post_tags pt ON pt.tag_id = ue.tag_id
+            JOIN tags t ON t.id = ue.tag_id
+            WHERE pt.post_id = "#,
+        );
+        matched_qb.push_bind(query.post_id);
+        matched_qb.push(" AND ue.user_id IN (");
+        let mut separated = matched_qb.separated(", ");
+        for suggestion in &suggestions {
+            separated.push_bind(suggestion.user_id);
+        }
+        separated.push_unseparated(") ORDER BY t.name ASC");
+        let matched: Vec<(i64, String)> = matched_qb
+            .build_query_as()
+            .fetch_all(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+        let mut tags_by_user: HashMap<i64, Vec<String>> = HashMap::new();
+        for (user_id, tag) in matched {
+            tags_by_user.entry(user_id).or_default().push(tag);
+        }
+        for suggestion in &mut suggestions {
+            suggestion.matched_tags = tags_by_user.remove(&suggestion.user_id).unwrap_or_default();
+        }
+    }
+
+    Ok(Json(ReviewerSuggestionResponse {
+        post_id: query.post_id,
+        post_tags,
+        suggestions,
+    }))
+}
+
 // ============================
 // GET /admin/users
 // ============================
+#[derive(Debug, Deserialize)]
+struct AdminUserQuery {
+    q: Option<String>,
+    sort: Option<String>,
+    order: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AdminUserRow {
+    #[sqlx(flatten)]
+    user: User,
+    post_count: i64,
+    comment_count: i64,
+}
+
+/// `q` matches username, email or display name. Sorting is by `created_at`
+/// (default), `username`, `post_count` or `comment_count`.
 async fn admin_list_users(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Query(query): Query<AdminUserQuery>,
+) -> Result<impl IntoResponse, AppError> {
     let _admin = extract_admin_user(&pool, &headers).await?;
 
-    let users = sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY created_at DESC")
+    let sort_column = match query.sort.as_deref().unwrap_or("created_at") {
+        "created_at" => "u.created_at",
+        "username" => "u.username",
+        "post_count" => "post_count",
+        "comment_count" => "comment_count",
+        _ => {
+            return Err(AppError::bad_request(
+                "sort must be one of: created_at, username, post_count, comment_count",
+            ));
+        }
+    };
+    let sort_direction = match query.order.as_deref() {
+        None if sort_column == "u.username" => "ASC",
+        None | Some("desc") => "DESC",
+        Some("asc") => "ASC",
+        Some(_) => {
+            return Err(AppError::bad_request("order must be asc or desc"));
+        }
+    };
+    let pattern = query
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| format!("%{}%", value));
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+
+    let (total,): (i64,) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*)
+        FROM users u
+        WHERE u.deleted_at IS NULL
+          AND (? IS NULL OR u.username LIKE ? OR u.email LIKE ? OR u.display_name LIKE ?)
+        "#,
+    )
+    .bind(&pattern)
+    .bind(&pattern)
+    .bind(&pattern)
+    .bind(&pattern)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    // Counts come from one grouped pass over each table instead of a pair of
+    // COUNT queries per user. Unless the page is ordered by a count, it is
+    // picked first so only its users' posts and comments are counted.
+    let order_by = format!("ORDER BY {} {}, u.id DESC", sort_column, sort_direction);
+    let (page_clause, outer_clause) = if matches!(sort_column, "post_count" | "comment_count") {
+        (String::new(), format!("{} LIMIT ? OFFSET ?", order_by))
+    } else {
+        (format!("{} LIMIT ? OFFSET ?", order_by), order_by)
+    };
+    let users_query = format!(
+        r#"
+        WITH page AS (
+            SELECT u.id
+            FROM users u
+            WHERE u.deleted_at IS NULL
+              AND (? IS NULL OR u.username LIKE ? OR u.email LIKE ? OR u.display_name LIKE ?)
+            {}
+        )
+        SELECT
+            u.*,
+            CAST(COALESCE(pc.post_count, 0) AS SIGNED) AS post_count,
+            CAST(COALESCE(cc.comment_count, 0) AS SIGNED) AS comment_count
+        FROM page
+        JOIN users u ON u.id = page.id
+        LEFT JOIN (
+            SELECT p.author_id, COUNT(*) AS post_count
+            FROM posts p
+            JOIN page ON page.id = p.author_id
+            GROUP BY p.author_id
+        ) pc ON pc.author_id = u.id
+        LEFT JOIN (
+            SELECT cm.author_id, COUNT(*) AS comment_count
+            FROM comments cm
+            JOIN page ON page.id = cm.author_id
+            GROUP BY cm.author_id
+        ) cc ON cc.author_id = u.id
+        {}
+        "#,
+        page_clause, outer_clause
+    );
+    let rows = sqlx::query_as::<_, AdminUserRow>(&users_query)
+        .bind(&pattern)
+        .bind(&pattern)
+        .bind(&pattern)
+        .bind(&pattern)
+        .bind(i64::from(per_page))
+        .bind(i64::from((page - 1) * per_page))
         .fetch_all(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
-
-    // Return full user info with post counts
-    let mut user_list = Vec::new();
-    for u in users {
-        let post_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM posts WHERE author_id = ?")
-            .bind(u.id)
-            .fetch_one(&pool)
-            .await
-            .map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({"detail": e.to_string()})),
-                )
-            })?;
-
-        let comment_count: (i64,) =
-            sqlx::query_as("SELECT COUNT(*) FROM comments WHERE author_id = ?")
-                .bind(u.id)
-                .fetch_one(&pool)
-                .await
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(serde_json::json!({"detail": e.to_string()})),
-                    )
-                })?;
-
-        let resp = UserResponse::from(u);
-        user_list.push(serde_json::json!({
-            "id": resp.id,
-            "username": resp.username,
-            "email": resp.email,
-            "display_name": resp.display_name,
-            "bio": resp.bio,
-            "introduction": resp.introduction,
-            "hobbies": resp.hobbies,
-            "interests": resp.interests,
-            "research_areas": resp.research_areas,
-            "avatar_url": resp.avatar_url,
-            "is_admin": resp.is_admin,
-            "created_at": resp.created_at,
-            "post_count": post_count.0,
-            "comment_count": comment_count.0,
-        }));
-    }
-
-    Ok(Json(serde_json::json!(user_list)))
+        .map_err(AppError::internal)?;
+
+    let user_ids: Vec<i64> = rows.iter().map(|row| row.user.id).collect();
+    let mut suspensions = fetch_active_suspensions(&pool, &user_ids)
+        .await
+        .map_err(AppError::internal)?;
+
+    let users = rows
+        .into_iter()
+        .map(|row| {
+            let suspension = suspensions.remove(&row.user.id);
+            AdminUserSummary {
+                user: UserResponse {
+                    suspension,
+                    ..UserResponse::from(row.user)
+                },
+                post_count: row.post_count,
+                comment_count: row.comment_count,
+            }
+        })
+        .collect();
+
+    Ok(Json(AdminUserListResponse {
+        users,
+        total,
+        page,
+        per_page,
+    }))
 }
 
 // ============================
@@ -251,216 +732,1726 @@ async fn admin_update_role(
     headers: HeaderMap,
     Path(user_id): Path<i64>,
     Json(input): Json<UpdateRole>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let admin = extract_admin_user(&pool, &headers).await?;
 
     // Prevent self-demotion
     if admin.id == user_id && !input.is_admin {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Cannot remove your own admin role"})),
-        ));
+        return Err(AppError::bad_request("Cannot remove your own admin role"));
     }
 
     // Verify target user exists
-    let _target = sqlx::query("SELECT id FROM users WHERE id = ?")
+    let was_admin: bool = sqlx::query_scalar("SELECT is_admin FROM users WHERE id = ?")
         .bind(user_id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "User not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
 
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
     sqlx::query("UPDATE users SET is_admin = ? WHERE id = ?")
         .bind(input.is_admin)
         .bind(user_id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+    if was_admin != input.is_admin {
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_USER_ROLE_UPDATE,
+                target_type: AUDIT_TARGET_USER,
+                target_id: user_id,
+                request: &RequestMetadata::from_headers(&headers),
+                before: Some(serde_json::json!({"is_admin": was_admin})),
+                after: Some(serde_json::json!({"is_admin": input.is_admin})),
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+    tx.commit().await.map_err(AppError::internal)?;
 
     let updated_user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
         .bind(user_id)
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+    let suspension = fetch_active_suspension(&pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(UserResponse {
+        suspension,
+        ..UserResponse::from(updated_user)
+    }))
+}
+
+/// Active suspensions of the given users, keyed by user id.
+async fn fetch_active_suspensions(
+    pool: &MySqlPool,
+    user_ids: &[i64],
+) -> Result<HashMap<i64, UserSuspension>, sqlx::Error> {
+    if user_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let now = Utc::now();
+    let mut query_builder = QueryBuilder::<MySql>::new(USER_SUSPENSION_SELECT);
+    query_builder
+        .push(" WHERE lifted_at IS NULL AND starts_at <= ")
+        .push_bind(now)
+        .push(" AND (expires_at IS NULL OR expires_at > ")
+        .push_bind(now)
+        .push(") AND user_id IN (");
+    {
+        let mut separated = query_builder.separated(", ");
+        for user_id in user_ids {
+            separated.push_bind(user_id);
+        }
+    }
+    // Oldest first, so the newest suspension wins when collected.
+    query_builder.push(") ORDER BY starts_at ASC, id ASC");
+
+    let suspensions = query_builder
+        .build_query_as::<UserSuspension>()
+        .fetch_all(pool)
+        .await?;
 
-    Ok(Json(UserResponse::from(updated_user)))
+    Ok(suspensions
+        .into_iter()
+        .map(|suspension| (suspension.user_id, suspension))
+        .collect())
 }
 
 // ============================
-// DELETE /admin/users/:id
+// POST /admin/users/:id/suspend
 // ============================
-async fn admin_delete_user(
+async fn admin_suspend_user(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(user_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Json(input): Json<SuspendUser>,
+) -> Result<impl IntoResponse, AppError> {
     let admin = extract_admin_user(&pool, &headers).await?;
 
-    // Prevent self-deletion
     if admin.id == user_id {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Cannot delete your own account"})),
-        ));
+        return Err(AppError::bad_request("Cannot suspend yourself"));
+    }
+    let reason = input.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::bad_request("A reason is required"));
+    }
+    let now = Utc::now();
+    if input.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(AppError::bad_request("expires_at must be in the future"));
     }
 
-    // Delete user's comments, post_likes, posts, then user
-    sqlx::query("DELETE FROM comments WHERE author_id = ?")
+    let is_admin: bool = sqlx::query_scalar("SELECT is_admin FROM users WHERE id = ?")
         .bind(user_id)
-        .execute(&pool)
+        .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
+    if is_admin {
+        return Err(AppError::bad_request("Cannot suspend an admin"));
+    }
 
-    sqlx::query("DELETE FROM post_likes WHERE user_id = ?")
-        .bind(user_id)
-        .execute(&pool)
+    let previous = fetch_active_suspension(&pool, user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    // A new suspension replaces whatever was in effect.
     sqlx::query(
         r#"
-        DELETE FROM post_citations
-        WHERE citing_post_id IN (SELECT id FROM posts WHERE author_id = ?)
-           OR cited_post_id IN (SELECT id FROM posts WHERE author_id = ?)
+        UPDATE user_suspensions
+        SET lifted_at = ?, lifted_by = ?, lift_reason = 'Replaced by a new suspension'
+        WHERE user_id = ? AND lifted_at IS NULL AND (expires_at IS NULL OR expires_at > ?)
         "#,
     )
+    .bind(now)
+    .bind(admin.id)
     .bind(user_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let result = sqlx::query(
+        r#"
+        INSERT INTO user_suspensions (user_id, reason, suspended_by, starts_at, expires_at, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
     .bind(user_id)
-    .execute(&pool)
+    .bind(reason)
+    .bind(admin.id)
+    .bind(now)
+    .bind(input.expires_at)
+    .bind(now)
+    .execute(&mut *tx)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    .map_err(AppError::internal)?;
+    let suspension =
+        sqlx::query_as::<_, UserSuspension>(&format!("{} WHERE id = ?", USER_SUSPENSION_SELECT))
+            .bind(result.last_insert_id() as i64)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
 
-    sqlx::query("DELETE FROM posts WHERE author_id = ?")
-        .bind(user_id)
-        .execute(&pool)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_USER_SUSPEND,
+            target_type: AUDIT_TARGET_USER,
+            target_id: user_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: previous.map(|previous| serde_json::json!(previous)),
+            after: Some(serde_json::json!(suspension)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
 
-    let result = sqlx::query("DELETE FROM users WHERE id = ?")
-        .bind(user_id)
-        .execute(&pool)
+    Ok((StatusCode::CREATED, Json(suspension)))
+}
+
+// ============================
+// DELETE /admin/users/:id/suspend
+// ============================
+#[derive(Debug, Deserialize)]
+struct LiftSuspensionQuery {
+    reason: Option<String>,
+}
+
+async fn admin_unsuspend_user(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+    Query(query): Query<LiftSuspensionQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let active = fetch_active_suspension(&pool, user_id)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User is not suspended"))?;
+    let lift_reason = query
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
 
-    if result.rows_affected() == 0 {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "User not found"})),
-        ));
-    }
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        "UPDATE user_suspensions SET lifted_at = ?, lifted_by = ?, lift_reason = ? WHERE id = ?",
+    )
+    .bind(Utc::now())
+    .bind(admin.id)
+    .bind(lift_reason)
+    .bind(active.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let lifted =
+        sqlx::query_as::<_, UserSuspension>(&format!("{} WHERE id = ?", USER_SUSPENSION_SELECT))
+            .bind(active.id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_USER_UNSUSPEND,
+            target_type: AUDIT_TARGET_USER,
+            target_id: user_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(active)),
+            after: Some(serde_json::json!(lifted)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
 
-    Ok(Json(serde_json::json!({"detail": "User deleted"})))
+    Ok(Json(lifted))
 }
 
 // ============================
-// DELETE /admin/posts/:id
+// GET /admin/users/:id/suspensions
 // ============================
-async fn admin_delete_post(
+async fn admin_list_user_suspensions(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
-    Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
     let _admin = extract_admin_user(&pool, &headers).await?;
 
-    // Delete associated data
-    sqlx::query("DELETE FROM comments WHERE post_id = ?")
-        .bind(post_id)
-        .execute(&pool)
+    let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+    if exists.is_none() {
+        return Err(AppError::not_found("User not found"));
+    }
 
-    sqlx::query("DELETE FROM post_likes WHERE post_id = ?")
-        .bind(post_id)
-        .execute(&pool)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+    let suspensions = sqlx::query_as::<_, UserSuspension>(&format!(
+        "{} WHERE user_id = ? ORDER BY starts_at DESC, id DESC",
+        USER_SUSPENSION_SELECT
+    ))
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
 
-    sqlx::query("DELETE FROM post_citations WHERE citing_post_id = ? OR cited_post_id = ?")
-        .bind(post_id)
+    Ok(Json(UserSuspensionListResponse {
+        total: suspensions.len() as i64,
+        suspensions,
+    }))
+}
+
+// ============================
+// DELETE /admin/users/:id
+// ============================
+/// Moves the user and their posts to the trash; see `/admin/trash`.
+async fn admin_delete_user(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    // Prevent self-deletion
+    if admin.id == user_id {
+        return Err(AppError::bad_request("Cannot delete your own account"));
+    }
+
+    let target =
+        sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ? AND deleted_at IS NULL")
+            .bind(user_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::not_found("User not found"))?;
+    let post_ids: Vec<i64> =
+        sqlx::query_scalar("SELECT id FROM posts WHERE author_id = ? AND deleted_at IS NULL")
+            .bind(user_id)
+            .fetch_all(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let trash_item_id = trash_user(&mut tx, user_id, admin.id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
+    let trash_item = fetch_trash_item(&mut *tx, trash_item_id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::internal("Trash item disappeared"))?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_USER_DELETE,
+            target_type: AUDIT_TARGET_USER,
+            target_id: user_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "username": target.username,
+                "email": target.email,
+                "display_name": target.display_name,
+                "is_admin": target.is_admin,
+                "post_count": post_ids.len(),
+            })),
+            after: Some(serde_json::json!({
+                "trash_item_id": trash_item_id,
+                "purge_after": trash_item.purge_after,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    for post_id in post_ids {
+        invalidate_cached_post(post_id).await;
+    }
+
+    Ok(Json(serde_json::json!({
+        "detail": "User moved to trash",
+        "trash_item": trash_item,
+    })))
+}
+
+// ============================
+// GET /admin/posts
+// ============================
+#[derive(Debug, Deserialize)]
+struct AdminPostQuery {
+    q: Option<String>,
+    category: Option<String>,
+    paper_status: Option<String>,
+    author_id: Option<i64>,
+    author: Option<String>,
+    published: Option<bool>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+}
+
+/// Filters of the admin post listing after normalization.
+struct AdminPostFilters {
+    title_pattern: Option<String>,
+    category: Option<String>,
+    paper_status: Option<String>,
+    author_id: Option<i64>,
+    author_pattern: Option<String>,
+    published: Option<bool>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+/// Every post regardless of publication state, newest first. `q` matches the
+/// title, `author` the author's username or display name, and `since` /
+/// `until` bound `created_at`. Trashed posts are listed under `/admin/trash`.
+async fn admin_list_posts(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<AdminPostQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let like_pattern = |value: Option<&str>| {
+        value
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| format!("%{}%", value))
+    };
+    let paper_status = match query
+        .paper_status
+        .as_deref()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+    {
+        Some(value) => Some(validate_paper_status_filter(&value)?),
+        None => None,
+    };
+    let filters = AdminPostFilters {
+        title_pattern: like_pattern(query.q.as_deref()),
+        category: query
+            .category
+            .as_deref()
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty()),
+        paper_status,
+        author_id: query.author_id,
+        author_pattern: like_pattern(query.author.as_deref()),
+        published: query.published,
+        since: query.since,
+        until: query.until,
+    };
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+
+    let mut count_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT COUNT(*)
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        JOIN users u ON u.id = p.author_id
+        "#,
+    );
+    push_admin_post_filters(&mut count_builder, &filters);
+    let (total,): (i64,) = count_builder
+        .build_query_as()
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT
+            p.id,
+            p.title,
+            c.code AS category,
+            p.author_id,
+            u.username AS author_username,
+            p.paper_status,
+            p.is_published,
+            p.published_at,
+            p.is_preprint,
+            pr.retracted_at,
+            p.created_at,
+            p.updated_at
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        JOIN users u ON u.id = p.author_id
+        LEFT JOIN post_retractions pr ON pr.post_id = p.id
+        "#,
+    );
+    push_admin_post_filters(&mut query_builder, &filters);
+    query_builder
+        .push(" ORDER BY p.created_at DESC, p.id DESC LIMIT ")
+        .push_bind(i64::from(per_page))
+        .push(" OFFSET ")
+        .push_bind(i64::from((page - 1) * per_page));
+    let posts = query_builder
+        .build_query_as::<AdminPostSummary>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(AdminPostListResponse {
+        posts,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+fn push_admin_post_filters(builder: &mut QueryBuilder<'_, MySql>, filters: &AdminPostFilters) {
+    builder.push(" WHERE p.deleted_at IS NULL");
+    if let Some(pattern) = &filters.title_pattern {
+        builder
+            .push(" AND p.title LIKE ")
+            .push_bind(pattern.clone());
+    }
+    if let Some(category) = &filters.category {
+        builder.push(" AND c.code = ").push_bind(category.clone());
+    }
+    if let Some(paper_status) = &filters.paper_status {
+        builder
+            .push(" AND p.paper_status = ")
+            .push_bind(paper_status.clone());
+    }
+    if let Some(author_id) = filters.author_id {
+        builder.push(" AND p.author_id = ").push_bind(author_id);
+    }
+    if let Some(pattern) = &filters.author_pattern {
+        builder
+            .push(" AND (u.username LIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR u.display_name LIKE ")
+            .push_bind(pattern.clone())
+            .push(")");
+    }
+    if let Some(published) = filters.published {
+        builder.push(" AND p.is_published = ").push_bind(published);
+    }
+    if let Some(since) = filters.since {
+        builder.push(" AND p.created_at >= ").push_bind(since);
+    }
+    if let Some(until) = filters.until {
+        builder.push(" AND p.created_at < ").push_bind(until);
+    }
+}
+
+/// Deduplicates the ids of a bulk action and enforces its size limit.
+fn validate_bulk_post_ids(post_ids: &[i64]) -> Result<Vec<i64>, AppError> {
+    let mut unique = Vec::with_capacity(post_ids.len());
+    for post_id in post_ids {
+        if !unique.contains(post_id) {
+            unique.push(*post_id);
+        }
+    }
+    if unique.is_empty() || unique.len() > MAX_BULK_POST_IDS {
+        return Err(AppError::bad_request(format!(
+            "post_ids must contain between 1 and {} ids",
+            MAX_BULK_POST_IDS
+        )));
+    }
+    Ok(unique)
+}
+
+// ============================
+// POST /admin/posts/bulk-unpublish
+// ============================
+/// Hides posts from public listings without deleting them. The paper
+/// status is kept, so an accepted or published paper can be republished.
+async fn admin_bulk_unpublish_posts(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<AdminBulkUnpublish>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let post_ids = validate_bulk_post_ids(&input.post_ids)?;
+    let request = RequestMetadata::from_headers(&headers);
+
+    let mut result = AdminBulkPostResult {
+        updated: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    for post_id in post_ids {
+        let is_published: Option<bool> =
+            sqlx::query_scalar("SELECT is_published FROM posts WHERE id = ? FOR UPDATE")
+                .bind(post_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(AppError::internal)?;
+        if is_published != Some(true) {
+            result.skipped.push(post_id);
+            continue;
+        }
+
+        let before = snapshot_post(&mut *tx, post_id)
+            .await
+            .map_err(AppError::internal)?;
+        sqlx::query("UPDATE posts SET is_published = FALSE, updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        let after = snapshot_post(&mut *tx, post_id)
+            .await
+            .map_err(AppError::internal)?;
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_POST_UNPUBLISH,
+                target_type: AUDIT_TARGET_POST,
+                target_id: post_id,
+                request: &request,
+                before,
+                after,
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+        result.updated.push(post_id);
+    }
+    tx.commit().await.map_err(AppError::internal)?;
+    for post_id in &result.updated {
+        invalidate_cached_post(*post_id).await;
+    }
+
+    Ok(Json(result))
+}
+
+// ============================
+// POST /admin/posts/bulk-category
+// ============================
+/// Moves posts to an existing, enabled category. Unlike author edits this
+/// never creates a new category.
+async fn admin_bulk_update_post_category(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<AdminBulkCategoryUpdate>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let post_ids = validate_bulk_post_ids(&input.post_ids)?;
+    let request = RequestMetadata::from_headers(&headers);
+
+    let category = input.category.trim().to_ascii_lowercase();
+    let (category_id, is_active): (i64, bool) =
+        sqlx::query_as("SELECT CAST(id AS SIGNED), is_active FROM post_categories WHERE code = ?")
+            .bind(&category)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::bad_request("Unknown category"))?;
+    if !is_active {
+        return Err(AppError::bad_request(
+            "This category is no longer available",
+        ));
+    }
+
+    let mut result = AdminBulkPostResult {
+        updated: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    for post_id in post_ids {
+        let current: Option<i64> = sqlx::query_scalar(
+            "SELECT CAST(category_id AS SIGNED) FROM posts WHERE id = ? FOR UPDATE",
+        )
         .bind(post_id)
-        .execute(&pool)
+        .fetch_optional(&mut *tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+        if current.is_none_or(|current| current == category_id) {
+            result.skipped.push(post_id);
+            continue;
+        }
+
+        let before = snapshot_post(&mut *tx, post_id)
+            .await
+            .map_err(AppError::internal)?;
+        sqlx::query("UPDATE posts SET category_id = ?, updated_at = ? WHERE id = ?")
+            .bind(category_id)
+            .bind(now)
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        let after = snapshot_post(&mut *tx, post_id)
+            .await
+            .map_err(AppError::internal)?;
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_POST_CATEGORY_UPDATE,
+                target_type: AUDIT_TARGET_POST,
+                target_id: post_id,
+                request: &request,
+                before,
+                after,
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+        result.updated.push(post_id);
+    }
+    tx.commit().await.map_err(AppError::internal)?;
+    for post_id in &result.updated {
+        invalidate_cached_post(*post_id).await;
+    }
 
-    let result = sqlx::query("DELETE FROM posts WHERE id = ?")
+    Ok(Json(result))
+}
+
+// ============================
+// DELETE /admin/posts/:id
+// ============================
+/// Moves the post to the trash; see `/admin/trash`.
+async fn admin_delete_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let before = snapshot_post(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let trash_item_id = trash_post(&mut tx, post_id, admin.id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Post not found"))?;
+    let trash_item = fetch_trash_item(&mut *tx, trash_item_id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::internal("Trash item disappeared"))?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_POST_DELETE,
+            target_type: AUDIT_TARGET_POST,
+            target_id: post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before,
+            after: Some(serde_json::json!({
+                "trash_item_id": trash_item_id,
+                "purge_after": trash_item.purge_after,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(serde_json::json!({
+        "detail": "Post moved to trash",
+        "trash_item": trash_item,
+    })))
+}
+
+// ============================
+// PUT /admin/posts/:id/doi-registration
+// ============================
+#[derive(Debug, Deserialize)]
+struct RegisterPostDoi {
+    doi: String,
+    registration_agency: String,
+}
+
+async fn admin_register_post_doi(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Json(input): Json<RegisterPostDoi>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let registered_doi = input
+        .doi
+        .trim()
+        .trim_start_matches("https://doi.org/")
+        .to_ascii_lowercase();
+    if !registered_doi.starts_with("10.") || !registered_doi.contains('/') {
+        return Err(AppError::bad_request(
+            "doi must be a registered DOI such as 10.1234/abc",
+        ));
+    }
+
+    let registration_agency = input.registration_agency.trim().to_ascii_lowercase();
+    if !["crossref", "datacite"].contains(&registration_agency.as_str()) {
+        return Err(AppError::bad_request(
+            "registration_agency must be one of: crossref, datacite",
+        ));
+    }
+
+    // The internal DOI is issued lazily when a post is first read.
+    let internal_doi: String = sqlx::query_scalar(
+        "SELECT doi FROM post_doi_metadata WHERE post_id = ? AND doi LIKE 'TM.%' ORDER BY id ASC LIMIT 1",
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| {
+        AppError::not_found("Post has no internal DOI")
+    })?;
+
+    let previous_doi: Option<String> =
+        sqlx::query_scalar("SELECT registered_doi FROM post_doi_registrations WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    upsert_doi_registration(
+        &mut tx,
+        post_id,
+        &internal_doi,
+        &registered_doi,
+        &registration_agency,
+    )
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            AppError::conflict("DOI is already registered to another post")
+        }
+        _ => AppError::internal(e),
+    })?;
+
+    if let Some(previous_doi) = previous_doi.filter(|doi| *doi != registered_doi) {
+        sqlx::query("DELETE FROM post_doi_metadata WHERE post_id = ? AND doi = ?")
+            .bind(post_id)
+            .bind(&previous_doi)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+    }
+    upsert_registered_doi_metadata(&mut tx, post_id)
+        .await
+        .map_err(AppError::internal)?;
+    // A DOI registered by hand replaces any automatic deposit still in flight.
+    sqlx::query("DELETE FROM post_doi_deposits WHERE post_id = ? AND status <> ?")
         .bind(post_id)
-        .execute(&pool)
+        .bind(DEPOSIT_STATUS_REGISTERED)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let registration = sqlx::query_as::<_, PostDoiRegistration>(
+        "SELECT * FROM post_doi_registrations WHERE post_id = ?",
+    )
+    .bind(post_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(registration))
+}
+
+// ============================
+// GET /admin/doi-deposits
+// ============================
+#[derive(Debug, Deserialize)]
+struct DoiDepositQuery {
+    status: Option<String>,
+}
+
+async fn admin_list_doi_deposits(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<DoiDepositQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let status = query
+        .status
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+    if let Some(value) = status.as_deref()
+        && ![
+            DEPOSIT_STATUS_PENDING,
+            DEPOSIT_STATUS_REGISTERED,
+            DEPOSIT_STATUS_FAILED,
+        ]
+        .contains(&value)
+    {
+        return Err(AppError::bad_request(
+            "status must be one of: pending, registered, failed",
+        ));
+    }
+
+    let deposits = sqlx::query_as::<_, PostDoiDeposit>(
+        r#"
+        SELECT *
+        FROM post_doi_deposits
+        WHERE (? IS NULL OR status = ?)
+        ORDER BY updated_at DESC, post_id DESC
+        "#,
+    )
+    .bind(&status)
+    .bind(&status)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(PostDoiDepositListResponse {
+        total: deposits.len() as i64,
+        deposits,
+    }))
+}
+
+// ============================
+// POST /admin/posts/:id/doi-deposit/retry
+// ============================
+async fn admin_retry_doi_deposit(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let now = Utc::now();
+    let result = sqlx::query(
+        r#"
+        UPDATE post_doi_deposits
+        SET status = ?, attempts = 0, next_attempt_at = ?, updated_at = ?
+        WHERE post_id = ? AND status <> ?
+        "#,
+    )
+    .bind(DEPOSIT_STATUS_PENDING)
+    .bind(now)
+    .bind(now)
+    .bind(post_id)
+    .bind(DEPOSIT_STATUS_REGISTERED)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("Post has no unregistered DOI deposit"));
+    }
+
+    let deposit =
+        sqlx::query_as::<_, PostDoiDeposit>("SELECT * FROM post_doi_deposits WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(deposit))
+}
+
+// ============================
+// GET /admin/settings
+// ============================
+async fn admin_list_settings(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let settings = list_system_settings(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(SystemSettingListResponse { settings }))
+}
+
+async fn find_system_setting(pool: &MySqlPool, key: &str) -> Result<SystemSetting, AppError> {
+    list_system_settings(pool)
+        .await
+        .map_err(AppError::internal)?
+        .into_iter()
+        .find(|setting| setting.key == key)
+        .ok_or_else(|| AppError::not_found("Unknown setting"))
+}
+
+// ============================
+// PUT /admin/settings/:key
+// ============================
+/// Stores an override and applies it right away; other instances pick it up
+/// on their next reload.
+async fn admin_update_setting(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+    Json(input): Json<UpdateSystemSetting>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let definition = find_setting(&key).ok_or_else(|| AppError::not_found("Unknown setting"))?;
+    let value = definition
+        .validate(&input.value)
+        .map_err(AppError::bad_request)?;
+    let before = find_system_setting(&pool, definition.key).await?;
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        r#"
+        INSERT INTO system_settings (setting_key, setting_value, updated_by, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            setting_value = VALUES(setting_value),
+            updated_by = VALUES(updated_by),
+            updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(definition.key)
+    .bind(&value)
+    .bind(admin.id)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let setting_id: i64 =
+        sqlx::query_scalar("SELECT id FROM system_settings WHERE setting_key = ?")
+            .bind(definition.key)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_SETTING_UPDATE,
+            target_type: AUDIT_TARGET_SYSTEM_SETTING,
+            target_id: setting_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "key": before.key,
+                "value": before.value,
+                "source": before.source,
+            })),
+            after: Some(serde_json::json!({"key": definition.key, "value": value})),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    reload_settings(&pool).await.map_err(AppError::internal)?;
+    find_system_setting(&pool, definition.key).await.map(Json)
+}
+
+// ============================
+// DELETE /admin/settings/:key
+// ============================
+/// Drops the override so the environment variable or built-in default
+/// applies again.
+async fn admin_reset_setting(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let before = find_system_setting(&pool, &key).await?;
+    let Some(setting_id) = before.id else {
+        return Err(AppError::not_found("Setting has no override"));
+    };
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM system_settings WHERE id = ?")
+        .bind(setting_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_SETTING_RESET,
+            target_type: AUDIT_TARGET_SYSTEM_SETTING,
+            target_id: setting_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({"key": before.key, "value": before.value})),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    reload_settings(&pool).await.map_err(AppError::internal)?;
+    find_system_setting(&pool, &key).await.map(Json)
+}
+
+// ============================
+// GET /admin/feature-flags
+// ============================
+async fn admin_list_feature_flags(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let flags = list_feature_flags(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(FeatureFlagListResponse { flags }))
+}
+
+async fn find_feature_flag_state(pool: &MySqlPool, key: &str) -> Result<FeatureFlag, AppError> {
+    list_feature_flags(pool)
+        .await
+        .map_err(AppError::internal)?
+        .into_iter()
+        .find(|flag| flag.key == key)
+        .ok_or_else(|| AppError::not_found("Unknown feature flag"))
+}
+
+// ============================
+// PUT /admin/feature-flags/:key
+// ============================
+/// Replaces the flag's override. Leaving out `rollout_percent` means 100,
+/// and leaving out `roles` opens the flag to every role.
+async fn admin_update_feature_flag(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+    Json(input): Json<UpdateFeatureFlag>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let definition =
+        find_feature_flag(&key).ok_or_else(|| AppError::not_found("Unknown feature flag"))?;
+    let rollout_percent = input.rollout_percent.unwrap_or(100);
+    if !(0..=100).contains(&rollout_percent) {
+        return Err(AppError::bad_request(
+            "rollout_percent must be between 0 and 100",
+        ));
+    }
+    let mut roles: Vec<String> = Vec::new();
+    for role in input.roles.unwrap_or_default() {
+        let role = role.trim().to_ascii_lowercase();
+        if !FEATURE_ROLES.contains(&role.as_str()) {
+            return Err(AppError::bad_request(format!(
+                "roles must be any of: {}",
+                FEATURE_ROLES.join(", ")
+            )));
+        }
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+    }
+    let before = find_feature_flag_state(&pool, definition.key).await?;
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        r#"
+        INSERT INTO feature_flags (flag_key, enabled, rollout_percent, updated_by, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            enabled = VALUES(enabled),
+            rollout_percent = VALUES(rollout_percent),
+            updated_by = VALUES(updated_by),
+            updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(definition.key)
+    .bind(input.enabled)
+    .bind(rollout_percent)
+    .bind(admin.id)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let flag_id: i64 = sqlx::query_scalar("SELECT id FROM feature_flags WHERE flag_key = ?")
+        .bind(definition.key)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM feature_flag_roles WHERE flag_id = ?")
+        .bind(flag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    for role in &roles {
+        sqlx::query("INSERT INTO feature_flag_roles (flag_id, role) VALUES (?, ?)")
+            .bind(flag_id)
+            .bind(role)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+    }
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_FEATURE_FLAG_UPDATE,
+            target_type: AUDIT_TARGET_FEATURE_FLAG,
+            target_id: flag_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "key": before.key,
+                "enabled": before.enabled,
+                "rollout_percent": before.rollout_percent,
+                "roles": before.roles,
+            })),
+            after: Some(serde_json::json!({
+                "key": definition.key,
+                "enabled": input.enabled,
+                "rollout_percent": rollout_percent,
+                "roles": roles,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    reload_feature_flags(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    find_feature_flag_state(&pool, definition.key)
+        .await
+        .map(Json)
+}
+
+// ============================
+// DELETE /admin/feature-flags/:key
+// ============================
+/// Drops the override so the flag's built-in default applies again.
+async fn admin_reset_feature_flag(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let before = find_feature_flag_state(&pool, &key).await?;
+    let Some(flag_id) = before.id else {
+        return Err(AppError::not_found("Feature flag has no override"));
+    };
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM feature_flags WHERE id = ?")
+        .bind(flag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_FEATURE_FLAG_RESET,
+            target_type: AUDIT_TARGET_FEATURE_FLAG,
+            target_id: flag_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "key": before.key,
+                "enabled": before.enabled,
+                "rollout_percent": before.rollout_percent,
+                "roles": before.roles,
+            })),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    reload_feature_flags(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    find_feature_flag_state(&pool, &key).await.map(Json)
+}
+
+// ============================
+// GET /admin/audit-log
+// ============================
+#[derive(Debug, Deserialize)]
+struct AuditLogQuery {
+    actor_id: Option<i64>,
+    action: Option<String>,
+    target_type: Option<String>,
+    target_id: Option<i64>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AuditLogRow {
+    id: i64,
+    actor_id: Option<i64>,
+    actor_username: Option<String>,
+    action: String,
+    target_type: String,
+    target_id: Option<i64>,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    before_snapshot: Option<String>,
+    after_snapshot: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// Newest entries first. `action` also matches a prefix ending in `.`, so
+/// `post.` lists every post action.
+async fn admin_list_audit_log(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let action = query
+        .action
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            if value.ends_with('.') {
+                format!("{}%", value.replace('%', "").replace('_', "\\_"))
+            } else {
+                value.replace('%', "").replace('_', "\\_")
+            }
+        });
+    let target_type = query
+        .target_type
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(50).clamp(1, 200);
+
+    let mut count_builder = QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM audit_log");
+    push_audit_log_filters(
+        &mut count_builder,
+        query.actor_id,
+        action.as_deref(),
+        target_type.as_deref(),
+        query.target_id,
+        query.since,
+        query.until,
+    );
+    let (total,): (i64,) = count_builder
+        .build_query_as()
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT
+            id,
+            actor_id,
+            actor_username,
+            action,
+            target_type,
+            target_id,
+            ip_address,
+            user_agent,
+            CAST(before_snapshot AS CHAR) AS before_snapshot,
+            CAST(after_snapshot AS CHAR) AS after_snapshot,
+            created_at
+        FROM audit_log
+        "#,
+    );
+    push_audit_log_filters(
+        &mut query_builder,
+        query.actor_id,
+        action.as_deref(),
+        target_type.as_deref(),
+        query.target_id,
+        query.since,
+        query.until,
+    );
+    query_builder
+        .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(i64::from(per_page))
+        .push(" OFFSET ")
+        .push_bind(i64::from((page - 1) * per_page));
+    let rows = query_builder
+        .build_query_as::<AuditLogRow>()
+        .fetch_all(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+
+    let parse_snapshot = |raw: Option<String>| raw.and_then(|raw| serde_json::from_str(&raw).ok());
+    let entries = rows
+        .into_iter()
+        .map(|row| AuditLogEntry {
+            id: row.id,
+            actor_id: row.actor_id,
+            actor_username: row.actor_username,
+            action: row.action,
+            target_type: row.target_type,
+            target_id: row.target_id,
+            ip_address: row.ip_address,
+            user_agent: row.user_agent,
+            before: parse_snapshot(row.before_snapshot),
+            after: parse_snapshot(row.after_snapshot),
+            created_at: row.created_at,
+        })
+        .collect();
+
+    Ok(Json(AuditLogListResponse {
+        entries,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+fn push_audit_log_filters(
+    builder: &mut QueryBuilder<'_, MySql>,
+    actor_id: Option<i64>,
+    action: Option<&str>,
+    target_type: Option<&str>,
+    target_id: Option<i64>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) {
+    builder.push(" WHERE 1 = 1");
+    if let Some(actor_id) = actor_id {
+        builder.push(" AND actor_id = ").push_bind(actor_id);
+    }
+    if let Some(action) = action {
+        builder
+            .push(" AND action LIKE ")
+            .push_bind(action.to_string());
+    }
+    if let Some(target_type) = target_type {
+        builder
+            .push(" AND target_type = ")
+            .push_bind(target_type.to_string());
+    }
+    if let Some(target_id) = target_id {
+        builder.push(" AND target_id = ").push_bind(target_id);
+    }
+    if let Some(since) = since {
+        builder.push(" AND created_at >= ").push_bind(since);
+    }
+    if let Some(until) = until {
+        builder.push(" AND created_at < ").push_bind(until);
+    }
+}
+
+// ============================
+// GET /admin/repository-deposits
+// ============================
+async fn admin_list_repository_deposits(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<DoiDepositQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let status = query
+        .status
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+    if let Some(value) = status.as_deref()
+        && ![
+            REPOSITORY_DEPOSIT_PENDING,
+            REPOSITORY_DEPOSIT_DEPOSITED,
+            REPOSITORY_DEPOSIT_FAILED,
+        ]
+        .contains(&value)
+    {
+        return Err(AppError::bad_request(
+            "status must be one of: pending, deposited, failed",
+        ));
+    }
+
+    let deposits = sqlx::query_as::<_, PostRepositoryDeposit>(
+        r#"
+        SELECT *
+        FROM post_repository_deposits
+        WHERE (? IS NULL OR status = ?)
+        ORDER BY updated_at DESC, post_id DESC
+        "#,
+    )
+    .bind(&status)
+    .bind(&status)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(PostRepositoryDepositListResponse {
+        total: deposits.len() as i64,
+        deposits,
+    }))
+}
+
+// ============================
+// GET /admin/posts/:id/repository-deposit
+// ============================
+async fn admin_get_repository_deposit(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let deposit = sqlx::query_as::<_, PostRepositoryDeposit>(
+        "SELECT * FROM post_repository_deposits WHERE post_id = ?",
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post has no repository deposit"))?;
+
+    Ok(Json(deposit))
+}
+
+// ============================
+// POST /admin/posts/:id/repository-deposit/retry
+// ============================
+async fn admin_retry_repository_deposit(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
 
+    let now = Utc::now();
+    let result = sqlx::query(
+        r#"
+        UPDATE post_repository_deposits
+        SET status = ?, attempts = 0, next_attempt_at = ?, updated_at = ?
+        WHERE post_id = ? AND status <> ?
+        "#,
+    )
+    .bind(REPOSITORY_DEPOSIT_PENDING)
+    .bind(now)
+    .bind(now)
+    .bind(post_id)
+    .bind(REPOSITORY_DEPOSIT_DEPOSITED)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
     if result.rows_affected() == 0 {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Post not found"})),
+        return Err(AppError::not_found(
+            "Post has no unfinished repository deposit",
+        ));
+    }
+
+    let deposit = sqlx::query_as::<_, PostRepositoryDeposit>(
+        "SELECT * FROM post_repository_deposits WHERE post_id = ?",
+    )
+    .bind(post_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(deposit))
+}
+
+// ============================
+// PUT /admin/posts/:id/retraction
+// ============================
+#[derive(Debug, Deserialize)]
+struct RetractPost {
+    reason: String,
+}
+
+async fn admin_retract_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Json(input): Json<RetractPost>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let reason = input.reason.trim().to_string();
+    if reason.is_empty() {
+        return Err(AppError::bad_request("Retraction reason is required"));
+    }
+
+    let (category_code, is_published): (String, bool) = sqlx::query_as(
+        r#"
+        SELECT c.code, p.is_published
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.id = ?
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if category_code != "paper" || !is_published {
+        return Err(AppError::bad_request(
+            "Only published papers can be retracted",
         ));
     }
 
-    Ok(Json(serde_json::json!({"detail": "Post deleted"})))
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+
+    let previous: Option<(String, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT reason, retracted_at FROM post_retractions WHERE post_id = ? FOR UPDATE",
+    )
+    .bind(post_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    // Re-retracting only updates the reason; the original retraction date stands.
+    sqlx::query(
+        r#"
+        INSERT INTO post_retractions (post_id, reason, retracted_by, retracted_at)
+        VALUES (?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE reason = VALUES(reason), retracted_by = VALUES(retracted_by)
+        "#,
+    )
+    .bind(post_id)
+    .bind(&reason)
+    .bind(admin.id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    // Citation edges stay in place so the graph remains auditable; they are
+    // flagged instead and skipped by the impact factor calculation.
+    let flagged = sqlx::query(
+        r#"
+        UPDATE post_citations
+        SET retraction_flagged_at = ?
+        WHERE (citing_post_id = ? OR cited_post_id = ?)
+          AND retraction_flagged_at IS NULL
+        "#,
+    )
+    .bind(now)
+    .bind(post_id)
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    let (retracted_at,): (DateTime<Utc>,) =
+        sqlx::query_as("SELECT retracted_at FROM post_retractions WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_POST_RETRACT,
+            target_type: AUDIT_TARGET_POST,
+            target_id: post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: previous.map(|(reason, retracted_at)| {
+                serde_json::json!({"reason": reason, "retracted_at": retracted_at})
+            }),
+            after: Some(serde_json::json!({"reason": reason, "retracted_at": retracted_at})),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(serde_json::json!({
+        "detail": "Paper retracted",
+        "retraction": RetractionNotice { retracted_at, reason },
+        "flagged_citation_count": flagged.rows_affected()
+    })))
+}
+
+// ============================
+// DELETE /admin/posts/:id/retraction
+// ============================
+async fn admin_withdraw_retraction(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+
+    let (reason, retracted_at): (String, DateTime<Utc>) = sqlx::query_as(
+        "SELECT reason, retracted_at FROM post_retractions WHERE post_id = ? FOR UPDATE",
+    )
+    .bind(post_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post is not retracted"))?;
+
+    sqlx::query("DELETE FROM post_retractions WHERE post_id = ?")
+        .bind(post_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+
+    // Edges whose other endpoint is still retracted keep their flag.
+    sqlx::query(
+        r#"
+        UPDATE post_citations pc
+        LEFT JOIN post_retractions citing_retraction ON citing_retraction.post_id = pc.citing_post_id
+        LEFT JOIN post_retractions cited_retraction ON cited_retraction.post_id = pc.cited_post_id
+        SET pc.retraction_flagged_at = NULL
+        WHERE (pc.citing_post_id = ? OR pc.cited_post_id = ?)
+          AND citing_retraction.post_id IS NULL
+          AND cited_retraction.post_id IS NULL
+        "#,
+    )
+    .bind(post_id)
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_POST_RETRACTION_WITHDRAW,
+            target_type: AUDIT_TARGET_POST,
+            target_id: post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({"reason": reason, "retracted_at": retracted_at})),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(serde_json::json!({"detail": "Retraction withdrawn"})))
 }
 
 // ============================
@@ -470,35 +2461,396 @@ async fn admin_delete_comment(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(comment_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let _admin = extract_admin_user(&pool, &headers).await?;
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
 
     let comment = find_comment_target(&pool, comment_id, None)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Comment not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Comment not found"))?;
+
+    let content: String = sqlx::query_scalar("SELECT content FROM comments WHERE id = ?")
+        .bind(comment_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
 
     let delete_mode = apply_comment_delete_policy(&pool, &comment)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+
+    record_audit_event(
+        &pool,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_COMMENT_DELETE,
+            target_type: AUDIT_TARGET_COMMENT,
+            target_id: comment_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "post_id": comment.post_id,
+                "author_id": comment.author_id,
+                "content": content,
+            })),
+            after: Some(serde_json::json!({"delete_mode": delete_mode.as_str()})),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
 
     Ok(Json(serde_json::json!({
         "detail": "Comment deleted",
         "delete_mode": delete_mode.as_str()
     })))
 }
+
+// ============================
+// GET /admin/comment-reports
+// ============================
+#[derive(Debug, Deserialize)]
+struct CommentReportQuery {
+    status: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+}
+
+async fn admin_list_comment_reports(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<CommentReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let _admin = extract_admin_user(&pool, &headers).await?;
+
+    let status_filter = match query.status.as_deref().unwrap_or("open") {
+        "all" => None,
+        status @ ("open" | "dismissed" | "actioned") => Some(status),
+        _ => {
+            return Err(AppError::bad_request(
+                "Invalid status filter. Use open|dismissed|actioned|all",
+            ));
+        }
+    };
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+
+    let reports = sqlx::query_as::<_, CommentReportResponse>(
+        r#"
+        SELECT
+            r.id,
+            r.comment_id,
+            r.post_id,
+            r.comment_author_id,
+            ca.username AS comment_author_username,
+            r.reporter_id,
+            ru.username AS reporter_username,
+            r.comment_excerpt,
+            r.reason,
+            r.status,
+            CAST((
+                SELECT COUNT(*)
+                FROM comment_reports o
+                WHERE o.comment_id = r.comment_id AND o.status = 'open'
+            ) AS SIGNED) AS open_report_count,
+            r.comment_deleted,
+            r.author_banned,
+            r.resolved_by,
+            r.resolution_note,
+            r.resolved_at,
+            r.created_at
+        FROM comment_reports r
+        LEFT JOIN users ca ON ca.id = r.comment_author_id
+        LEFT JOIN users ru ON ru.id = r.reporter_id
+        WHERE (? IS NULL OR r.status = ?)
+        ORDER BY r.created_at ASC, r.id ASC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(status_filter)
+    .bind(status_filter)
+    .bind(i64::from(per_page))
+    .bind(i64::from((page - 1) * per_page))
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let (total,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM comment_reports WHERE (? IS NULL OR status = ?)")
+            .bind(status_filter)
+            .bind(status_filter)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(CommentReportListResponse {
+        reports,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct OpenCommentReport {
+    id: i64,
+    comment_id: Option<i64>,
+    comment_author_id: Option<i64>,
+    reason: String,
+    status: String,
+}
+
+async fn find_open_comment_report(
+    pool: &MySqlPool,
+    report_id: i64,
+) -> Result<OpenCommentReport, AppError> {
+    let report = sqlx::query_as::<_, OpenCommentReport>(
+        "SELECT id, comment_id, comment_author_id, reason, status FROM comment_reports WHERE id = ?",
+    )
+    .bind(report_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| {
+        AppError::not_found("Report not found")
+    })?;
+
+    if report.status != "open" {
+        return Err(AppError::conflict(format!(
+            "Report is already {}",
+            report.status
+        )));
+    }
+
+    Ok(report)
+}
+
+// ============================
+// POST /admin/comment-reports/:id/dismiss
+// ============================
+#[derive(Debug, Deserialize)]
+struct DismissCommentReport {
+    note: Option<String>,
+}
+
+async fn admin_dismiss_comment_report(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(report_id): Path<i64>,
+    Json(input): Json<DismissCommentReport>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let report = find_open_comment_report(&pool, report_id).await?;
+
+    let note = input
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    sqlx::query(
+        "UPDATE comment_reports SET status = 'dismissed', resolved_by = ?, resolution_note = ?, resolved_at = ? WHERE id = ?",
+    )
+    .bind(admin.id)
+    .bind(note)
+    .bind(Utc::now())
+    .bind(report.id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({"detail": "Report dismissed"})))
+}
+
+// ============================
+// POST /admin/comment-reports/:id/action
+// ============================
+#[derive(Debug, Deserialize)]
+struct ActionCommentReport {
+    delete_comment: Option<bool>,
+    ban_author: Option<bool>,
+    note: Option<String>,
+}
+
+async fn admin_action_comment_report(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(report_id): Path<i64>,
+    Json(input): Json<ActionCommentReport>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let report = find_open_comment_report(&pool, report_id).await?;
+
+    let delete_comment = input.delete_comment.unwrap_or(true);
+    let ban_author = input.ban_author.unwrap_or(false);
+    if !delete_comment && !ban_author {
+        return Err(AppError::bad_request(
+            "Choose at least one action: delete_comment or ban_author",
+        ));
+    }
+
+    let ban_target = if ban_author {
+        let author_id = report
+            .comment_author_id
+            .ok_or_else(|| AppError::bad_request("Comment author no longer exists"))?;
+        let (is_admin,): (bool,) = sqlx::query_as("SELECT is_admin FROM users WHERE id = ?")
+            .bind(author_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+        if is_admin {
+            return Err(AppError::bad_request(
+                "Admins cannot be banned from commenting",
+            ));
+        }
+        Some(author_id)
+    } else {
+        None
+    };
+
+    let comment_target = match report.comment_id.filter(|_| delete_comment) {
+        Some(comment_id) => find_comment_target(&pool, comment_id, None)
+            .await
+            .map_err(AppError::internal)?,
+        None => None,
+    };
+
+    let note = input
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    let now = Utc::now();
+
+    // Every open report on the same comment is resolved by this action. This
+    // runs before the delete because a hard delete clears `comment_id`.
+    let resolved = sqlx::query(
+        r#"
+        UPDATE comment_reports
+        SET status = 'actioned',
+            comment_deleted = ?,
+            author_banned = ?,
+            resolved_by = ?,
+            resolution_note = ?,
+            resolved_at = ?
+        WHERE status = 'open' AND (id = ? OR comment_id <=> ?)
+        "#,
+    )
+    .bind(comment_target.is_some())
+    .bind(ban_target.is_some())
+    .bind(admin.id)
+    .bind(&note)
+    .bind(now)
+    .bind(report.id)
+    .bind(report.comment_id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    if let Some(author_id) = ban_target {
+        sqlx::query(
+            r#"
+            INSERT INTO comment_bans (user_id, reason, banned_by, banned_at)
+            VALUES (?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE user_id = user_id
+            "#,
+        )
+        .bind(author_id)
+        .bind(note.as_deref().unwrap_or(&report.reason))
+        .bind(admin.id)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    }
+
+    let delete_mode = match comment_target {
+        Some(comment) => {
+            let mode = apply_comment_delete_policy(&pool, &comment)
+                .await
+                .map_err(AppError::internal)?;
+            Some(mode)
+        }
+        None => None,
+    };
+
+    record_audit_event(
+        &pool,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_COMMENT_REPORT_ACTION,
+            target_type: AUDIT_TARGET_COMMENT_REPORT,
+            target_id: report.id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "status": report.status,
+                "comment_id": report.comment_id,
+                "comment_author_id": report.comment_author_id,
+                "reason": report.reason,
+            })),
+            after: Some(serde_json::json!({
+                "status": "actioned",
+                "delete_mode": delete_mode.as_ref().map(|mode| mode.as_str()),
+                "author_banned": ban_target.is_some(),
+                "resolved_reports": resolved.rows_affected(),
+                "note": note,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({
+        "detail": "Report actioned",
+        "resolved_reports": resolved.rows_affected(),
+        "delete_mode": delete_mode.map(|mode| mode.as_str()),
+        "author_banned": ban_target.is_some()
+    })))
+}
+
+// ============================
+// DELETE /admin/users/:id/comment-ban
+// ============================
+async fn admin_lift_comment_ban(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let ban: Option<(Option<String>, Option<i64>, DateTime<Utc>)> =
+        sqlx::query_as("SELECT reason, banned_by, banned_at FROM comment_bans WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    let result = sqlx::query("DELETE FROM comment_bans WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("User is not banned from commenting"));
+    }
+
+    record_audit_event(
+        &pool,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_COMMENT_BAN_LIFT,
+            target_type: AUDIT_TARGET_USER,
+            target_id: user_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: ban.map(|(reason, banned_by, banned_at)| {
+                serde_json::json!({
+                    "reason": reason,
+                    "banned_by": banned_by,
+                    "banned_at": banned_at,
+                })
+            }),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({"detail": "Comment ban lifted"})))
+}
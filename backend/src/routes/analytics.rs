@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use reqwest::Url;
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::error::AppError;
+use crate::metrics::ROLLUP_GRANULARITY_DAY;
+use crate::models::{
+    DailyPostActivity, PostAnalyticsResponse, PostAnalyticsTotals, ReferrerCount,
+};
+use crate::routes::auth::extract_current_user;
+
+pub const POST_EVENT_VIEW: &str = "view";
+pub const POST_EVENT_LIKE: &str = "like";
+pub const POST_EVENT_UNLIKE: &str = "unlike";
+
+const DEFAULT_ANALYTICS_DAYS: i64 = 30;
+const MAX_ANALYTICS_DAYS: i64 = 365;
+const MAX_REFERRERS: i64 = 20;
+const DIRECT_REFERRER: &str = "(direct)";
+
+pub fn analytics_routes() -> Router<MySqlPool> {
+    Router::new().route("/{post_id}/analytics", get(get_post_analytics))
+}
+
+#[derive(Debug, Deserialize)]
+struct PostAnalyticsQuery {
+    days: Option<i64>,
+}
+
+/// Records a post interaction for the analytics endpoint. Failures are only
+/// logged so analytics never break the request that triggered them.
+pub async fn record_post_event(
+    pool: &MySqlPool,
+    post_id: i64,
+    event_type: &str,
+    user_id: Option<i64>,
+    headers: Option<&HeaderMap>,
+) {
+    let referrer_host = headers.and_then(referrer_host);
+    let result = sqlx::query(
+        "INSERT INTO post_events (post_id, event_type, user_id, referrer_host, created_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(post_id)
+    .bind(event_type)
+    .bind(user_id)
+    .bind(referrer_host)
+    .bind(Utc::now())
+    .execute(pool)
+    .await;
+
+    if let Err(error) = result {
+        tracing::warn!(
+            "Failed to record {} event for post {}: {}",
+            event_type,
+            post_id,
+            error
+        );
+    }
+}
+
+fn referrer_host(headers: &HeaderMap) -> Option<String> {
+    let raw = headers.get(header::REFERER)?.to_str().ok()?;
+    let url = Url::parse(raw.trim()).ok()?;
+    url.host_str()
+        .map(|host| host.trim_start_matches("www.").to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .map(|host| host.chars().take(255).collect())
+}
+
+async fn get_post_analytics(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Query(query): Query<PostAnalyticsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    let days = query.days.unwrap_or(DEFAULT_ANALYTICS_DAYS);
+    if !(1..=MAX_ANALYTICS_DAYS).contains(&days) {
+        return Err(AppError::bad_request(format!(
+            "days must be between 1 and {}",
+            MAX_ANALYTICS_DAYS
+        )));
+    }
+
+    let (author_id,): (i64,) =
+        sqlx::query_as("SELECT author_id FROM posts WHERE id = ? AND deleted_at IS NULL")
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if author_id != current_user.id && !current_user.is_admin {
+        return Err(AppError::forbidden(
+            "Not authorized to view analytics of this post",
+        ));
+    }
+
+    let to = Utc::now().date_naive();
+    let from = to - Duration::days(days - 1);
+    let mut daily: BTreeMap<NaiveDate, DailyPostActivity> = BTreeMap::new();
+    let mut date = from;
+    while date <= to {
+        daily.insert(
+            date,
+            DailyPostActivity {
+                date,
+                ..DailyPostActivity::default()
+            },
+        );
+        date += Duration::days(1);
+    }
+
+    // Views and likes come from the daily rollups, so today's lag by up to
+    // one rollup interval.
+    let rollup_rows: Vec<(NaiveDate, i64, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT DATE(bucket_start), views, likes, unlikes
+        FROM post_stat_rollups
+        WHERE post_id = ? AND granularity = ? AND bucket_start >= ?
+        "#,
+    )
+    .bind(post_id)
+    .bind(ROLLUP_GRANULARITY_DAY)
+    .bind(from)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let mut totals = PostAnalyticsTotals::default();
+    for (day, views, likes, unlikes) in rollup_rows {
+        let Some(entry) = daily.get_mut(&day) else {
+            continue;
+        };
+        entry.views += views;
+        entry.likes += likes;
+        entry.unlikes += unlikes;
+        totals.views += views;
+        totals.likes += likes;
+        totals.unlikes += unlikes;
+    }
+
+    let comment_rows: Vec<(NaiveDate, i64)> = sqlx::query_as(
+        r#"
+        SELECT DATE(created_at) AS day, COUNT(*)
+        FROM comments
+        WHERE post_id = ? AND is_deleted = FALSE AND created_at >= ?
+        GROUP BY day
+        "#,
+    )
+    .bind(post_id)
+    .bind(from)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    for (day, count) in comment_rows {
+        if let Some(entry) = daily.get_mut(&day) {
+            entry.comments += count;
+            totals.comments += count;
+        }
+    }
+
+    // Internal citations count once per citing post, dated by its first edge.
+    let citation_rows: Vec<(NaiveDate, i64)> = sqlx::query_as(
+        r#"
+        SELECT day, CAST(SUM(citation_count) AS SIGNED)
+        FROM (
+            SELECT DATE(first_cited_at) AS day, COUNT(*) AS citation_count
+            FROM (
+                SELECT pc.citing_post_id, MIN(pc.created_at) AS first_cited_at
+                FROM post_citations pc
+                JOIN posts citing ON citing.id = pc.citing_post_id
+                WHERE pc.cited_post_id = ? AND citing.deleted_at IS NULL
+                GROUP BY pc.citing_post_id
+            ) internal_citations
+            GROUP BY day
+            UNION ALL
+            SELECT DATE(first_seen_at) AS day, COUNT(*) AS citation_count
+            FROM post_external_citations
+            WHERE cited_post_id = ?
+            GROUP BY day
+        ) accrual
+        GROUP BY day
+        ORDER BY day ASC
+        "#,
+    )
+    .bind(post_id)
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let mut cumulative_citations = citation_rows
+        .iter()
+        .filter(|(day, _)| *day < from)
+        .map(|(_, count)| *count)
+        .sum::<i64>();
+    for (day, count) in citation_rows.iter().filter(|(day, _)| *day >= from) {
+        if let Some(entry) = daily.get_mut(day) {
+            entry.citations += count;
+            totals.citations += count;
+        }
+    }
+    for entry in daily.values_mut() {
+        cumulative_citations += entry.citations;
+        entry.cumulative_citations = cumulative_citations;
+    }
+
+    let referrers = sqlx::query_as::<_, (Option<String>, i64)>(
+        r#"
+        SELECT referrer_host, COUNT(*) AS views
+        FROM post_events
+        WHERE post_id = ? AND event_type = ? AND created_at >= ?
+        GROUP BY referrer_host
+        ORDER BY views DESC, referrer_host ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(post_id)
+    .bind(POST_EVENT_VIEW)
+    .bind(from)
+    .bind(MAX_REFERRERS)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .into_iter()
+    .map(|(referrer, views)| ReferrerCount {
+        referrer: referrer.unwrap_or_else(|| DIRECT_REFERRER.to_string()),
+        views,
+    })
+    .collect();
+
+    Ok(Json(PostAnalyticsResponse {
+        post_id,
+        from,
+        to,
+        totals,
+        daily: daily.into_values().collect(),
+        referrers,
+    }))
+}
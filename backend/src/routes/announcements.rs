@@ -0,0 +1,312 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, patch},
+};
+use chrono::{DateTime, Utc};
+use sqlx::MySqlPool;
+
+use crate::audit_log::{
+    AUDIT_ACTION_ANNOUNCEMENT_CREATE, AUDIT_ACTION_ANNOUNCEMENT_DELETE,
+    AUDIT_ACTION_ANNOUNCEMENT_UPDATE, AUDIT_TARGET_ANNOUNCEMENT, AuditEvent, RequestMetadata,
+    record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    ANNOUNCEMENT_AUDIENCE_ADMINS, ANNOUNCEMENT_AUDIENCE_ALL, ANNOUNCEMENT_AUDIENCE_USERS,
+    ANNOUNCEMENT_SEVERITY_CRITICAL, ANNOUNCEMENT_SEVERITY_INFO, ANNOUNCEMENT_SEVERITY_WARNING,
+    Announcement, AnnouncementListResponse, CreateAnnouncement, UpdateAnnouncement,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::auth::extract_optional_user;
+
+const MAX_ANNOUNCEMENT_CHARS: usize = 2_000;
+
+const ANNOUNCEMENT_SELECT: &str = "SELECT id, message, severity, audience, starts_at, ends_at, created_by, created_at, updated_at FROM announcements";
+
+pub fn announcements_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/", get(list_announcements).post(create_announcement))
+        .route("/active", get(list_active_announcements))
+        .route(
+            "/{announcement_id}",
+            patch(update_announcement).delete(delete_announcement),
+        )
+}
+
+/// Announcements currently shown to the caller, most severe first.
+/// Anonymous visitors only see those meant for everyone.
+async fn list_active_announcements(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let user = extract_optional_user(&pool, &headers).await?;
+    let audiences: &[&str] = match &user {
+        Some(user) if user.is_admin => &[
+            ANNOUNCEMENT_AUDIENCE_ALL,
+            ANNOUNCEMENT_AUDIENCE_USERS,
+            ANNOUNCEMENT_AUDIENCE_ADMINS,
+        ],
+        Some(_) => &[ANNOUNCEMENT_AUDIENCE_ALL, ANNOUNCEMENT_AUDIENCE_USERS],
+        None => &[ANNOUNCEMENT_AUDIENCE_ALL],
+    };
+
+    let now = Utc::now();
+    let query = format!(
+        r#"
+        {}
+        WHERE starts_at <= ? AND (ends_at IS NULL OR ends_at > ?) AND audience IN ({})
+        ORDER BY FIELD(severity, 'critical', 'warning', 'info'), starts_at DESC, id DESC
+        "#,
+        ANNOUNCEMENT_SELECT,
+        vec!["?"; audiences.len()].join(", ")
+    );
+    let mut announcements_query = sqlx::query_as::<_, Announcement>(&query)
+        .bind(now)
+        .bind(now);
+    for audience in audiences {
+        announcements_query = announcements_query.bind(*audience);
+    }
+    let announcements = announcements_query
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(AnnouncementListResponse {
+        total: announcements.len() as i64,
+        announcements,
+    }))
+}
+
+/// Every announcement including scheduled and expired ones, for admins.
+async fn list_announcements(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let query = format!("{} ORDER BY starts_at DESC, id DESC", ANNOUNCEMENT_SELECT);
+    let announcements = sqlx::query_as::<_, Announcement>(&query)
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(AnnouncementListResponse {
+        total: announcements.len() as i64,
+        announcements,
+    }))
+}
+
+async fn create_announcement(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateAnnouncement>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let message = normalize_message(&input.message)?;
+    let severity = normalize_severity(input.severity.as_deref())?;
+    let audience = normalize_audience(input.audience.as_deref())?;
+    let now = Utc::now();
+    let starts_at = input.starts_at.unwrap_or(now);
+    validate_window(starts_at, input.ends_at)?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let result = sqlx::query(
+        r#"
+        INSERT INTO announcements (message, severity, audience, starts_at, ends_at, created_by, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&message)
+    .bind(severity)
+    .bind(audience)
+    .bind(starts_at)
+    .bind(input.ends_at)
+    .bind(admin.id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let announcement_id = result.last_insert_id() as i64;
+    let announcement = fetch_announcement(&mut *tx, announcement_id).await?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_ANNOUNCEMENT_CREATE,
+            target_type: AUDIT_TARGET_ANNOUNCEMENT,
+            target_id: announcement_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!(announcement)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok((StatusCode::CREATED, Json(announcement)))
+}
+
+async fn update_announcement(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(announcement_id): Path<i64>,
+    Json(input): Json<UpdateAnnouncement>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let current = fetch_announcement(&pool, announcement_id).await?;
+
+    let message = match input.message.as_deref() {
+        Some(raw) => normalize_message(raw)?,
+        None => current.message.clone(),
+    };
+    let severity = match input.severity.as_deref() {
+        Some(raw) => normalize_severity(Some(raw))?.to_string(),
+        None => current.severity.clone(),
+    };
+    let audience = match input.audience.as_deref() {
+        Some(raw) => normalize_audience(Some(raw))?.to_string(),
+        None => current.audience.clone(),
+    };
+    let starts_at = input.starts_at.unwrap_or(current.starts_at);
+    let ends_at = input.ends_at.or(current.ends_at);
+    validate_window(starts_at, ends_at)?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        r#"
+        UPDATE announcements
+        SET message = ?, severity = ?, audience = ?, starts_at = ?, ends_at = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&message)
+    .bind(&severity)
+    .bind(&audience)
+    .bind(starts_at)
+    .bind(ends_at)
+    .bind(Utc::now())
+    .bind(announcement_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let announcement = fetch_announcement(&mut *tx, announcement_id).await?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_ANNOUNCEMENT_UPDATE,
+            target_type: AUDIT_TARGET_ANNOUNCEMENT,
+            target_id: announcement_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(current)),
+            after: Some(serde_json::json!(announcement)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(Json(announcement))
+}
+
+async fn delete_announcement(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(announcement_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let current = fetch_announcement(&pool, announcement_id).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM announcements WHERE id = ?")
+        .bind(announcement_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_ANNOUNCEMENT_DELETE,
+            target_type: AUDIT_TARGET_ANNOUNCEMENT,
+            target_id: announcement_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(current)),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn fetch_announcement<'e, E>(
+    executor: E,
+    announcement_id: i64,
+) -> Result<Announcement, AppError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::MySql>,
+{
+    sqlx::query_as::<_, Announcement>(&format!("{} WHERE id = ?", ANNOUNCEMENT_SELECT))
+        .bind(announcement_id)
+        .fetch_optional(executor)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Announcement not found"))
+}
+
+fn normalize_message(raw: &str) -> Result<String, AppError> {
+    let message = raw.trim();
+    if message.is_empty() || message.chars().count() > MAX_ANNOUNCEMENT_CHARS {
+        return Err(AppError::bad_request(format!(
+            "message must be 1 to {} characters",
+            MAX_ANNOUNCEMENT_CHARS
+        )));
+    }
+    Ok(message.to_string())
+}
+
+fn normalize_severity(raw: Option<&str>) -> Result<&'static str, AppError> {
+    match raw
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("info") => Ok(ANNOUNCEMENT_SEVERITY_INFO),
+        Some("warning") => Ok(ANNOUNCEMENT_SEVERITY_WARNING),
+        Some("critical") => Ok(ANNOUNCEMENT_SEVERITY_CRITICAL),
+        Some(_) => Err(AppError::bad_request(
+            "severity must be one of: info, warning, critical",
+        )),
+    }
+}
+
+pub fn normalize_audience(raw: Option<&str>) -> Result<&'static str, AppError> {
+    match raw
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("all") => Ok(ANNOUNCEMENT_AUDIENCE_ALL),
+        Some("users") => Ok(ANNOUNCEMENT_AUDIENCE_USERS),
+        Some("admins") => Ok(ANNOUNCEMENT_AUDIENCE_ADMINS),
+        Some(_) => Err(AppError::bad_request(
+            "audience must be one of: all, users, admins",
+        )),
+    }
+}
+
+fn validate_window(
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    if ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+        return Err(AppError::bad_request("ends_at must be after starts_at"));
+    }
+    Ok(())
+}
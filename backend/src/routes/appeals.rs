@@ -0,0 +1,519 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{FromRow, MySql, MySqlPool, QueryBuilder};
+
+use crate::ai_review::{ReviewTrigger, schedule_review};
+use crate::audit_log::{
+    AUDIT_ACTION_APPEAL_RESOLVE, AUDIT_TARGET_POST, AuditEvent, RequestMetadata, record_audit_event,
+};
+use crate::cache::invalidate_cached_post;
+use crate::error::AppError;
+use crate::models::{
+    APPEAL_STATUS_OVERTURNED, APPEAL_STATUS_PENDING, APPEAL_STATUS_UPHELD, AppealOutcome,
+    CreatePaperAppeal, PAPER_STATUS_ACCEPTED, PAPER_STATUS_REJECTED, PAPER_STATUS_REVISION,
+    PAPER_STATUS_SUBMITTED, PaperAppealListResponse, PaperAppealResponse, ResolvePaperAppeal,
+};
+use crate::notifications::{
+    NOTIFICATION_APPEAL_RESOLVED, NOTIFICATION_APPEAL_SUBMITTED, NewNotification,
+    dispatch_notifications,
+};
+use crate::paper_status::{STATUS_TRIGGER_APPEAL, record_status_transition};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::auth::{ensure_not_suspended, extract_current_user};
+
+const MAX_JUSTIFICATION_CHARS: usize = 10_000;
+const MAX_RESOLUTION_NOTE_CHARS: usize = 10_000;
+
+/// Statuses an overturned rejection can send the paper back to.
+const OVERTURN_STATUSES: &[&str] = &[
+    PAPER_STATUS_SUBMITTED,
+    PAPER_STATUS_REVISION,
+    PAPER_STATUS_ACCEPTED,
+];
+
+const APPEAL_SELECT: &str = r#"
+    SELECT
+        a.id AS id,
+        a.post_id AS post_id,
+        p.title AS post_title,
+        a.paper_version_id AS paper_version_id,
+        CAST(pv.version_number AS SIGNED) AS version_number,
+        a.author_id AS author_id,
+        u.username AS author_username,
+        a.justification AS justification,
+        a.status AS status,
+        a.ai_review_id AS ai_review_id,
+        a.resolved_by AS resolved_by,
+        a.resolution_note AS resolution_note,
+        a.resulting_status AS resulting_status,
+        a.resolved_at AS resolved_at,
+        a.created_at AS created_at,
+        a.updated_at AS updated_at
+    FROM paper_appeals a
+    JOIN posts p ON p.id = a.post_id
+    JOIN users u ON u.id = a.author_id
+    LEFT JOIN paper_versions pv ON pv.id = a.paper_version_id
+"#;
+
+#[derive(Debug, Deserialize)]
+struct AppealQueueQuery {
+    status: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+}
+
+#[derive(Debug, FromRow)]
+struct AppealPostRow {
+    author_id: i64,
+    title: String,
+    category_code: String,
+    paper_status: String,
+    latest_paper_version_id: Option<i64>,
+}
+
+/// Author-facing appeal routes, nested under `/api/posts`.
+pub fn appeal_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/{post_id}/appeal", post(create_appeal))
+        .route("/{post_id}/appeals", get(list_post_appeals))
+}
+
+/// Editor queue, nested under `/api/admin`.
+pub fn appeal_queue_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/appeals", get(list_appeal_queue))
+        .route(
+            "/appeals/{appeal_id}/ai-review",
+            post(request_appeal_review),
+        )
+        .route("/appeals/{appeal_id}/resolve", post(resolve_appeal))
+}
+
+/// Files the author's appeal against the rejection of the current version.
+/// Each rejected version can be appealed once.
+async fn create_appeal(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Json(input): Json<CreatePaperAppeal>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    let post = fetch_appeal_post(&pool, post_id).await?;
+
+    if post.author_id != current_user.id {
+        return Err(AppError::forbidden(
+            "Only the author can appeal this decision",
+        ));
+    }
+
+    let Some(paper_version_id) = post
+        .latest_paper_version_id
+        .filter(|_| post.paper_status == PAPER_STATUS_REJECTED)
+    else {
+        return Err(AppError::conflict("Only rejected papers can be appealed")
+            .with_field("paper_status", post.paper_status));
+    };
+
+    let justification = input.justification.trim();
+    if justification.is_empty() {
+        return Err(AppError::bad_request("A written justification is required"));
+    }
+    if justification.chars().count() > MAX_JUSTIFICATION_CHARS {
+        return Err(AppError::bad_request(format!(
+            "Justification must be at most {} characters",
+            MAX_JUSTIFICATION_CHARS
+        )));
+    }
+
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO paper_appeals
+            (post_id, paper_version_id, author_id, justification, status, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(post_id)
+    .bind(paper_version_id)
+    .bind(current_user.id)
+    .bind(justification)
+    .bind(APPEAL_STATUS_PENDING)
+    .bind(Utc::now())
+    .execute(&pool)
+    .await;
+
+    let appeal_id = match inserted {
+        Ok(result) => result.last_insert_id() as i64,
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            return Err(AppError::conflict(
+                "This rejection has already been appealed",
+            ));
+        }
+        Err(error) => return Err(AppError::internal(error)),
+    };
+
+    let admin_ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM users WHERE is_admin = TRUE")
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    let message = format!(
+        "{} appealed the rejection of \"{}\"",
+        current_user.username, post.title
+    );
+    dispatch_notifications(
+        &pool,
+        admin_ids
+            .into_iter()
+            .map(|admin_id| NewNotification {
+                user_id: admin_id,
+                actor_id: Some(current_user.id),
+                event_type: NOTIFICATION_APPEAL_SUBMITTED,
+                post_id: Some(post_id),
+                comment_id: None,
+                message: message.clone(),
+            })
+            .collect(),
+    )
+    .await;
+
+    let appeal = fetch_appeal(&pool, appeal_id).await?;
+    Ok((StatusCode::CREATED, Json(appeal)))
+}
+
+async fn list_post_appeals(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let post = fetch_appeal_post(&pool, post_id).await?;
+    if current_user.id != post.author_id && !current_user.is_admin {
+        return Err(AppError::forbidden(
+            "Not authorized to view appeals for this paper",
+        ));
+    }
+
+    let query = format!(
+        "{} WHERE a.post_id = ? ORDER BY a.created_at DESC, a.id DESC",
+        APPEAL_SELECT
+    );
+    let appeals = sqlx::query_as::<_, PaperAppealResponse>(&query)
+        .bind(post_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let total = appeals.len() as i64;
+    Ok(Json(PaperAppealListResponse {
+        per_page: total as i32,
+        appeals,
+        total,
+        page: 1,
+    }))
+}
+
+/// Oldest pending appeals first, so the queue is worked in filing order.
+async fn list_appeal_queue(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<AppealQueueQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let status = match query.status.as_deref().map(str::trim) {
+        None | Some("") => APPEAL_STATUS_PENDING,
+        Some(APPEAL_STATUS_PENDING) => APPEAL_STATUS_PENDING,
+        Some(APPEAL_STATUS_UPHELD) => APPEAL_STATUS_UPHELD,
+        Some(APPEAL_STATUS_OVERTURNED) => APPEAL_STATUS_OVERTURNED,
+        Some(_) => {
+            return Err(AppError::bad_request(
+                "status must be one of: pending, upheld, overturned",
+            ));
+        }
+    };
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+
+    let mut query_builder = QueryBuilder::<MySql>::new(APPEAL_SELECT);
+    query_builder.push(" WHERE a.status = ");
+    query_builder.push_bind(status);
+    query_builder.push(" ORDER BY a.created_at ASC, a.id ASC LIMIT ");
+    query_builder.push_bind(i64::from(per_page));
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(offset);
+    let appeals = query_builder
+        .build_query_as::<PaperAppealResponse>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM paper_appeals WHERE status = ?")
+        .bind(status)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(PaperAppealListResponse {
+        appeals,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+/// Queues a fresh AI review of the rejected version with the appeal text in
+/// the prompt. The result is advisory and does not change `paper_status`.
+async fn request_appeal_review(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(appeal_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let appeal = fetch_appeal(&pool, appeal_id).await?;
+    ensure_pending(&appeal)?;
+
+    let review_id = schedule_review(
+        &pool,
+        appeal.post_id,
+        appeal.paper_version_id,
+        ReviewTrigger::Appeal,
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    sqlx::query("UPDATE paper_appeals SET ai_review_id = ?, updated_at = ? WHERE id = ?")
+        .bind(review_id)
+        .bind(Utc::now())
+        .bind(appeal_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "detail": "AI review queued with the appeal attached",
+            "appeal_id": appeal_id,
+            "review_id": review_id
+        })),
+    ))
+}
+
+/// Upholds the rejection, or overturns it and returns the paper to the
+/// requested status.
+async fn resolve_appeal(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(appeal_id): Path<i64>,
+    Json(input): Json<ResolvePaperAppeal>,
+) -> Result<impl IntoResponse, AppError> {
+    let editor = extract_admin_user(&pool, &headers).await?;
+    let appeal = fetch_appeal(&pool, appeal_id).await?;
+    ensure_pending(&appeal)?;
+
+    let note = input
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    if note.is_some_and(|note| note.chars().count() > MAX_RESOLUTION_NOTE_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "Resolution note must be at most {} characters",
+            MAX_RESOLUTION_NOTE_CHARS
+        )));
+    }
+
+    let (status, resulting_status) = match input.outcome {
+        AppealOutcome::Uphold => {
+            if input.paper_status.is_some() {
+                return Err(AppError::bad_request(
+                    "paper_status can only be set when overturning an appeal",
+                ));
+            }
+            (APPEAL_STATUS_UPHELD, None)
+        }
+        AppealOutcome::Overturn => {
+            let requested = input
+                .paper_status
+                .as_deref()
+                .map(|raw| raw.trim().to_ascii_lowercase())
+                .unwrap_or_else(|| PAPER_STATUS_SUBMITTED.to_string());
+            let Some(resulting_status) = OVERTURN_STATUSES
+                .iter()
+                .copied()
+                .find(|status| *status == requested)
+            else {
+                return Err(AppError::bad_request(
+                    "paper_status must be one of: submitted, revision, accepted",
+                ));
+            };
+            (APPEAL_STATUS_OVERTURNED, Some(resulting_status))
+        }
+    };
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE paper_appeals
+        SET status = ?, resolved_by = ?, resolution_note = ?, resulting_status = ?,
+            resolved_at = ?, updated_at = ?
+        WHERE id = ? AND status = ?
+        "#,
+    )
+    .bind(status)
+    .bind(editor.id)
+    .bind(note)
+    .bind(resulting_status)
+    .bind(now)
+    .bind(now)
+    .bind(appeal_id)
+    .bind(APPEAL_STATUS_PENDING)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::conflict("Appeal has already been resolved"));
+    }
+
+    if let Some(resulting_status) = resulting_status {
+        // The author may have revised the paper since filing; only the
+        // appealed rejection is reversed.
+        let reopened = sqlx::query(
+            r#"
+            UPDATE posts
+            SET paper_status = ?, updated_at = ?
+            WHERE id = ? AND paper_status = ? AND latest_paper_version_id <=> ?
+            "#,
+        )
+        .bind(resulting_status)
+        .bind(now)
+        .bind(appeal.post_id)
+        .bind(PAPER_STATUS_REJECTED)
+        .bind(appeal.paper_version_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+        if reopened.rows_affected() == 0 {
+            return Err(AppError::conflict(
+                "The appealed version is no longer the rejected current version",
+            ));
+        }
+        record_status_transition(
+            &mut *tx,
+            appeal.post_id,
+            Some(editor.id),
+            STATUS_TRIGGER_APPEAL,
+            Some(PAPER_STATUS_REJECTED),
+            resulting_status,
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &editor,
+            action: AUDIT_ACTION_APPEAL_RESOLVE,
+            target_type: AUDIT_TARGET_POST,
+            target_id: appeal.post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "appeal_id": appeal_id,
+                "appeal_status": APPEAL_STATUS_PENDING,
+                "paper_status": PAPER_STATUS_REJECTED,
+            })),
+            after: Some(serde_json::json!({
+                "appeal_id": appeal_id,
+                "appeal_status": status,
+                "paper_status": resulting_status.unwrap_or(PAPER_STATUS_REJECTED),
+                "note": note,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(appeal.post_id).await;
+
+    let message = match resulting_status {
+        Some(resulting_status) => format!(
+            "Your appeal for \"{}\" succeeded and the rejection was overturned; the paper is now {}",
+            appeal.post_title, resulting_status
+        ),
+        None => format!(
+            "Your appeal for \"{}\" was reviewed and the rejection stands",
+            appeal.post_title
+        ),
+    };
+    dispatch_notifications(
+        &pool,
+        vec![NewNotification {
+            user_id: appeal.author_id,
+            actor_id: Some(editor.id),
+            event_type: NOTIFICATION_APPEAL_RESOLVED,
+            post_id: Some(appeal.post_id),
+            comment_id: None,
+            message,
+        }],
+    )
+    .await;
+
+    Ok(Json(fetch_appeal(&pool, appeal_id).await?))
+}
+
+fn ensure_pending(appeal: &PaperAppealResponse) -> Result<(), AppError> {
+    if appeal.status != APPEAL_STATUS_PENDING {
+        return Err(AppError::conflict("Appeal has already been resolved")
+            .with_field("status", &appeal.status));
+    }
+
+    Ok(())
+}
+
+async fn fetch_appeal(pool: &MySqlPool, appeal_id: i64) -> Result<PaperAppealResponse, AppError> {
+    let query = format!("{} WHERE a.id = ?", APPEAL_SELECT);
+    sqlx::query_as::<_, PaperAppealResponse>(&query)
+        .bind(appeal_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Appeal not found"))
+}
+
+async fn fetch_appeal_post(pool: &MySqlPool, post_id: i64) -> Result<AppealPostRow, AppError> {
+    let post = sqlx::query_as::<_, AppealPostRow>(
+        r#"
+        SELECT
+            p.author_id AS author_id,
+            p.title AS title,
+            c.code AS category_code,
+            p.paper_status AS paper_status,
+            p.latest_paper_version_id AS latest_paper_version_id
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.id = ? AND p.deleted_at IS NULL
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if post.category_code != "paper" {
+        return Err(AppError::bad_request(
+            "Appeals are only available for paper posts",
+        ));
+    }
+
+    Ok(post)
+}
@@ -11,59 +11,93 @@ use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode}
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use sqlx::MySqlPool;
+use utoipa::OpenApi;
+
+use crate::account_activity::record_account_event;
+use crate::audit_log::RequestMetadata;
+use crate::error::{AppError, ErrorBody};
+use crate::models::{
+    ACCOUNT_EVENT_LOGIN, ACCOUNT_EVENT_LOGIN_FAILED, AUTH_METHOD_GOOGLE, AUTH_METHOD_PASSWORD,
+    CreateUser, LoginForm, TokenResponse, User, UserResponse, UserSuspension,
+};
+use crate::rate_limit::{RateLimitedRoute, rate_limited};
+use crate::telemetry;
+use crate::validation::{ValidatedJson, validate};
 
-use crate::models::{CreateUser, TokenResponse, User, UserResponse};
+/// Tokens from this version on carry the user id as `sub`, so they survive
+/// username changes.
+const USER_ID_SUBJECT_VERSION: u8 = 2;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
+    /// The user id, or the username in tokens issued before
+    /// `USER_ID_SUBJECT_VERSION`.
     pub sub: String,
     pub exp: usize,
+    #[serde(default)]
+    pub ver: u8,
 }
 
 pub fn auth_routes() -> Router<MySqlPool> {
     Router::new()
-        .route("/register", post(register))
-        .route("/login", post(login))
+        .route(
+            "/register",
+            post(rate_limited(RateLimitedRoute::Auth, register)),
+        )
+        .route("/login", post(rate_limited(RateLimitedRoute::Auth, login)))
         .route("/me", get(get_me))
         .route("/google", get(google_login))
-        .route("/google/callback", get(google_callback))
+        .route(
+            "/google/callback",
+            get(rate_limited(RateLimitedRoute::Auth, google_callback)),
+        )
 }
 
+/// OpenAPI paths for `auth_routes`, relative to `/api/auth`.
+#[derive(OpenApi)]
+#[openapi(paths(register, login, get_me))]
+pub struct AuthApi;
+
 // ============================
 // Standard Auth
 // ============================
 
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "auth",
+    request_body = CreateUser,
+    responses(
+        (status = 201, description = "Account created", body = UserResponse),
+        (status = 400, description = "Username or email already registered", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+        (status = 429, description = "Too many requests; see `Retry-After`", body = ErrorBody),
+    )
+)]
 async fn register(
     State(pool): State<MySqlPool>,
-    Json(input): Json<CreateUser>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ValidatedJson(input): ValidatedJson<CreateUser>,
+) -> Result<impl IntoResponse, AppError> {
     // Check if user exists
     let existing = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ? OR email = ?")
         .bind(&input.username)
         .bind(&input.email)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
-
-    if existing.is_some() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Username or email already registered"})),
+        .map_err(AppError::internal)?;
+
+    let reserved = is_username_taken(&pool, &input.username, None)
+        .await
+        .map_err(AppError::internal)?;
+
+    if existing.is_some() || reserved {
+        return Err(AppError::bad_request(
+            "Username or email already registered",
         ));
     }
 
     // Hash password
-    let hashed = hash(&input.password, DEFAULT_COST).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    let hashed = hash(&input.password, DEFAULT_COST).map_err(AppError::internal)?;
 
     let display_name = input.display_name.unwrap_or_else(|| input.username.clone());
     let now = Utc::now();
@@ -80,12 +114,7 @@ async fn register(
     .bind(now)
     .execute(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    .map_err(AppError::internal)?;
 
     let user_id = result.last_insert_id() as i64;
 
@@ -93,66 +122,67 @@ async fn register(
         .bind(user_id)
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     Ok((StatusCode::CREATED, Json(UserResponse::from(user))))
 }
 
-#[derive(Debug, Deserialize)]
-pub struct LoginForm {
-    pub username: String,
-    pub password: String,
-}
-
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "auth",
+    request_body(content = LoginForm, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "Bearer token for the account", body = TokenResponse),
+        (status = 401, description = "Wrong credentials", body = ErrorBody),
+        (status = 403, description = "Account suspended or deleted", body = ErrorBody),
+        (status = 422, description = "Missing or oversized credentials", body = ErrorBody),
+        (status = 429, description = "Too many requests; see `Retry-After`", body = ErrorBody),
+    )
+)]
 async fn login(
     State(pool): State<MySqlPool>,
+    headers: HeaderMap,
     axum::Form(input): axum::Form<LoginForm>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
+    validate(&input)?;
     let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
         .bind(&input.username)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
-
-    let user = user.ok_or_else(|| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "Incorrect username or password"})),
-        )
-    })?;
+        .map_err(AppError::internal)?;
 
-    let hashed = user.hashed_password.as_ref().ok_or_else(|| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "This account uses Google login"})),
-        )
-    })?;
+    let user = user.ok_or_else(|| AppError::unauthorized("Incorrect username or password"))?;
 
-    let valid = verify(&input.password, hashed).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    let hashed = user
+        .hashed_password
+        .as_ref()
+        .ok_or_else(|| AppError::unauthorized("This account uses Google login"))?;
+
+    let valid = verify(&input.password, hashed).map_err(AppError::internal)?;
 
     if !valid {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "Incorrect username or password"})),
-        ));
+        record_account_event(
+            &pool,
+            user.id,
+            ACCOUNT_EVENT_LOGIN_FAILED,
+            Some(AUTH_METHOD_PASSWORD),
+            RequestMetadata::from_headers(&headers),
+        );
+        return Err(AppError::unauthorized("Incorrect username or password"));
     }
 
-    let token = generate_jwt(&user.username)?;
+    ensure_not_deleted(&pool, user.id).await?;
+    ensure_not_suspended(&pool, user.id).await?;
+
+    let token = generate_jwt(user.id)?;
+    record_account_event(
+        &pool,
+        user.id,
+        ACCOUNT_EVENT_LOGIN,
+        Some(AUTH_METHOD_PASSWORD),
+        RequestMetadata::from_headers(&headers),
+    );
     Ok(Json(TokenResponse {
         access_token: token,
         token_type: "bearer".to_string(),
@@ -162,26 +192,28 @@ async fn login(
 use axum::http::HeaderMap;
 use axum::http::header::AUTHORIZATION;
 
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "The signed-in user", body = UserResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+    )
+)]
 async fn get_me(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let auth_header = headers
         .get(AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"detail": "Missing authorization header"})),
-            )
-        })?;
-
-    let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "Invalid authorization header"})),
-        )
-    })?;
+        .ok_or_else(|| AppError::unauthorized("Missing authorization header"))?;
+
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| AppError::unauthorized("Invalid authorization header"))?;
 
     let secret = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set in .env");
 
@@ -190,53 +222,25 @@ async fn get_me(
         &DecodingKey::from_secret(secret.as_bytes()),
         &Validation::default(),
     )
-    .map_err(|_| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "Invalid token"})),
-        )
-    })?;
+    .map_err(|_| AppError::unauthorized("Invalid token"))?;
 
-    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
-        .bind(&token_data.claims.sub)
-        .fetch_optional(&pool)
+    let user = find_token_user(&pool, &token_data.claims)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"detail": "User not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::unauthorized("User not found"))?;
 
     Ok(Json(UserResponse::from(user)))
 }
 
-pub async fn extract_current_user(
-    pool: &MySqlPool,
-    headers: &HeaderMap,
-) -> Result<User, (StatusCode, Json<serde_json::Value>)> {
+pub async fn extract_current_user(pool: &MySqlPool, headers: &HeaderMap) -> Result<User, AppError> {
     let auth_header = headers
         .get(AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"detail": "Missing authorization header"})),
-            )
-        })?;
-
-    let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "Invalid authorization header"})),
-        )
-    })?;
+        .ok_or_else(|| AppError::unauthorized("Missing authorization header"))?;
+
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| AppError::unauthorized("Invalid authorization header"))?;
 
     let secret = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set in .env");
 
@@ -245,35 +249,18 @@ pub async fn extract_current_user(
         &DecodingKey::from_secret(secret.as_bytes()),
         &Validation::default(),
     )
-    .map_err(|_| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"detail": "Invalid token"})),
-        )
-    })?;
+    .map_err(|_| AppError::unauthorized("Invalid token"))?;
 
-    sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
-        .bind(&token_data.claims.sub)
-        .fetch_optional(pool)
+    find_token_user(pool, &token_data.claims)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"detail": "User not found"})),
-            )
-        })
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::unauthorized("User not found"))
 }
 
 pub async fn extract_optional_user(
     pool: &MySqlPool,
     headers: &HeaderMap,
-) -> Result<Option<User>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Option<User>, AppError> {
     let Some(auth_header) = headers.get(AUTHORIZATION).and_then(|v| v.to_str().ok()) else {
         return Ok(None);
     };
@@ -292,25 +279,167 @@ pub async fn extract_optional_user(
         Err(_) => return Ok(None),
     };
 
-    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
-        .bind(&token_data.claims.sub)
-        .fetch_optional(pool)
+    let user = find_token_user(pool, &token_data.claims)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     Ok(user)
 }
 
+/// The user id behind a valid bearer token, for keying per-user limits
+/// before the handler runs. Tokens of deleted or erased accounts have none.
+pub async fn token_subject(pool: &MySqlPool, headers: &HeaderMap) -> Option<String> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())?
+        .strip_prefix("Bearer ")?;
+    let secret = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set in .env");
+    let token_data = decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )
+    .ok()?;
+    find_token_user(pool, &token_data.claims)
+        .await
+        .ok()
+        .flatten()
+        .map(|user| user.id.to_string())
+}
+
+/// Loads the token's user and tags the request's log lines with their id.
+/// Deleted and erased accounts have no valid tokens.
+async fn find_token_user(pool: &MySqlPool, claims: &Claims) -> Result<Option<User>, sqlx::Error> {
+    let user = if claims.ver < USER_ID_SUBJECT_VERSION {
+        find_user_by_username(pool, &claims.sub).await?
+    } else {
+        let Ok(user_id) = claims.sub.parse::<i64>() else {
+            return Ok(None);
+        };
+        sqlx::query_as::<_, User>(
+            "SELECT * FROM users WHERE id = ? AND deleted_at IS NULL AND erased_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+    };
+    if let Some(user) = &user {
+        tracing::Span::current().record("user_id", user.id);
+    }
+    Ok(user)
+}
+
+/// Looks a user up by current username, falling back to usernames they
+/// changed away from.
+pub async fn find_user_by_username(
+    pool: &MySqlPool,
+    username: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    let user =
+        sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ? AND deleted_at IS NULL")
+            .bind(username)
+            .fetch_optional(pool)
+            .await?;
+    if user.is_some() {
+        return Ok(user);
+    }
+    sqlx::query_as::<_, User>(
+        r#"
+        SELECT u.*
+        FROM username_history h
+        JOIN users u ON u.id = h.user_id
+        WHERE h.old_username = ? AND u.deleted_at IS NULL
+        "#,
+    )
+    .bind(username)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Whether `username` belongs to someone other than `user_id`, either as a
+/// current username or one kept reserved in `username_history`.
+pub async fn is_username_taken(
+    pool: &MySqlPool,
+    username: &str,
+    user_id: Option<i64>,
+) -> Result<bool, sqlx::Error> {
+    let taken: i64 = sqlx::query_scalar(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM users WHERE username = ? AND NOT (id <=> ?))
+            + (SELECT COUNT(*) FROM username_history WHERE old_username = ? AND NOT (user_id <=> ?))
+        "#,
+    )
+    .bind(username)
+    .bind(user_id)
+    .bind(username)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(taken > 0)
+}
+
+/// Columns of `UserSuspension`; a suspension without expiry is a ban.
+pub const USER_SUSPENSION_SELECT: &str = "SELECT id, user_id, CASE WHEN expires_at IS NULL THEN 'ban' ELSE 'suspension' END AS kind, reason, suspended_by, starts_at, expires_at, lifted_at, lifted_by, lift_reason, created_at FROM user_suspensions";
+
+/// The user's suspension or ban that is in effect right now, if any.
+pub async fn fetch_active_suspension(
+    pool: &MySqlPool,
+    user_id: i64,
+) -> Result<Option<UserSuspension>, sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query_as::<_, UserSuspension>(&format!(
+        "{} WHERE user_id = ? AND lifted_at IS NULL AND starts_at <= ? AND (expires_at IS NULL OR expires_at > ?) ORDER BY starts_at DESC LIMIT 1",
+        USER_SUSPENSION_SELECT
+    ))
+    .bind(user_id)
+    .bind(now)
+    .bind(now)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Rejects users an admin moved to the trash. Their rows still hold the
+/// username and Google id, so a login cannot create a duplicate account.
+async fn ensure_not_deleted(pool: &MySqlPool, user_id: i64) -> Result<(), AppError> {
+    let deleted: Option<bool> =
+        sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    if deleted.unwrap_or(false) {
+        return Err(AppError::forbidden("This account has been deleted"));
+    }
+
+    Ok(())
+}
+
+/// Rejects suspended and banned users from logging in or creating content.
+pub async fn ensure_not_suspended(pool: &MySqlPool, user_id: i64) -> Result<(), AppError> {
+    let suspension = fetch_active_suspension(pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
+
+    if let Some(suspension) = suspension {
+        let detail = match suspension.expires_at {
+            Some(_) => "Your account is suspended",
+            None => "Your account has been banned",
+        };
+        return Err(AppError::forbidden(detail)
+            .with_field("reason", suspension.reason)
+            .with_field("expires_at", suspension.expires_at));
+    }
+
+    Ok(())
+}
+
 // ============================
 // Helper: JWT Generation
 // ============================
 
-fn generate_jwt(username: &str) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+fn generate_jwt(user_id: i64) -> Result<String, AppError> {
     let secret = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set in .env");
     let expiration = chrono::Utc::now()
         .checked_add_signed(chrono::Duration::hours(24))
@@ -318,8 +447,9 @@ fn generate_jwt(username: &str) -> Result<String, (StatusCode, Json<serde_json::
         .timestamp() as usize;
 
     let claims = Claims {
-        sub: username.to_string(),
+        sub: user_id.to_string(),
         exp: expiration,
+        ver: USER_ID_SUBJECT_VERSION,
     };
 
     encode(
@@ -327,12 +457,7 @@ fn generate_jwt(username: &str) -> Result<String, (StatusCode, Json<serde_json::
         &claims,
         &EncodingKey::from_secret(secret.as_bytes()),
     )
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })
+    .map_err(AppError::internal)
 }
 
 // ============================
@@ -380,13 +505,9 @@ fn extract_cookie_value(cookie_header: &str, key: &str) -> Option<String> {
         .map(ToString::to_string)
 }
 
-async fn google_login() -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let client_id = std::env::var("GOOGLE_CLIENT_ID").map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": "GOOGLE_CLIENT_ID not configured"})),
-        )
-    })?;
+async fn google_login() -> Result<impl IntoResponse, AppError> {
+    let client_id = std::env::var("GOOGLE_CLIENT_ID")
+        .map_err(|_| AppError::internal("GOOGLE_CLIENT_ID not configured"))?;
 
     let redirect_uri = std::env::var("GOOGLE_REDIRECT_URI")
         .unwrap_or_else(|_| "http://localhost:8000/api/auth/google/callback".to_string());
@@ -464,19 +585,11 @@ async fn google_callback(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Query(params): Query<GoogleCallbackParams>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let client_id = std::env::var("GOOGLE_CLIENT_ID").map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": "GOOGLE_CLIENT_ID not configured"})),
-        )
-    })?;
-    let client_secret = std::env::var("GOOGLE_CLIENT_SECRET").map_err(|_| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": "GOOGLE_CLIENT_SECRET not configured"})),
-        )
-    })?;
+) -> Result<impl IntoResponse, AppError> {
+    let client_id = std::env::var("GOOGLE_CLIENT_ID")
+        .map_err(|_| AppError::internal("GOOGLE_CLIENT_ID not configured"))?;
+    let client_secret = std::env::var("GOOGLE_CLIENT_SECRET")
+        .map_err(|_| AppError::internal("GOOGLE_CLIENT_SECRET not configured"))?;
     let redirect_uri = std::env::var("GOOGLE_REDIRECT_URI")
         .unwrap_or_else(|_| "http://localhost:8000/api/auth/google/callback".to_string());
 
@@ -489,86 +602,66 @@ async fn google_callback(
     let code_verifier = extract_cookie_value(cookie_header, "oauth_verifier").unwrap_or_default();
     let cookie_state = extract_cookie_value(cookie_header, "oauth_state").unwrap_or_default();
 
-    let request_state = params.state.ok_or_else(|| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Missing OAuth state"})),
-        )
-    })?;
+    let request_state = params
+        .state
+        .ok_or_else(|| AppError::bad_request("Missing OAuth state"))?;
 
     if code_verifier.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Missing OAuth code verifier"})),
-        ));
+        return Err(AppError::bad_request("Missing OAuth code verifier"));
     }
 
     if cookie_state.is_empty() || request_state != cookie_state {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Invalid OAuth state"})),
-        ));
+        return Err(AppError::bad_request("Invalid OAuth state"));
     }
 
     // Exchange authorization code for access token
     let http_client = reqwest::Client::new();
-    let token_response = http_client
-        .post("https://oauth2.googleapis.com/token")
-        .form(&[
-            ("code", params.code.as_str()),
-            ("client_id", client_id.as_str()),
-            ("client_secret", client_secret.as_str()),
-            ("redirect_uri", redirect_uri.as_str()),
-            ("grant_type", "authorization_code"),
-            ("code_verifier", code_verifier.as_str()),
-        ])
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to exchange code: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": "Failed to exchange authorization code"})),
-            )
-        })?;
+    let token_response = telemetry::send(
+        "google_oauth",
+        http_client
+            .post("https://oauth2.googleapis.com/token")
+            .form(&[
+                ("code", params.code.as_str()),
+                ("client_id", client_id.as_str()),
+                ("client_secret", client_secret.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("grant_type", "authorization_code"),
+                ("code_verifier", code_verifier.as_str()),
+            ]),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to exchange code: {}", e);
+        AppError::internal("Failed to exchange authorization code")
+    })?;
 
     if !token_response.status().is_success() {
         let error_body = token_response.text().await.unwrap_or_default();
         tracing::error!("Google token error: {}", error_body);
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Failed to get Google access token"})),
-        ));
+        return Err(AppError::bad_request("Failed to get Google access token"));
     }
 
     let google_token: GoogleTokenResponse = token_response.json().await.map_err(|e| {
         tracing::error!("Failed to parse token response: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": "Failed to parse Google token response"})),
-        )
+        AppError::internal("Failed to parse Google token response")
     })?;
 
     // Fetch user info from Google
-    let userinfo_response = http_client
-        .get("https://www.googleapis.com/oauth2/v3/userinfo")
-        .bearer_auth(&google_token.access_token)
-        .send()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch userinfo: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": "Failed to fetch Google user info"})),
-            )
-        })?;
+    let userinfo_response = telemetry::send(
+        "google_oauth",
+        http_client
+            .get("https://www.googleapis.com/oauth2/v3/userinfo")
+            .bearer_auth(&google_token.access_token),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch userinfo: {}", e);
+        AppError::internal("Failed to fetch Google user info")
+    })?;
 
     let google_user: GoogleUserInfo = userinfo_response.json().await.map_err(|e| {
         tracing::error!("Failed to parse userinfo: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": "Failed to parse Google user info"})),
-        )
+        AppError::internal("Failed to parse Google user info")
     })?;
 
     // Find or create user
@@ -576,12 +669,7 @@ async fn google_callback(
         .bind(&google_user.google_id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     let user = match user {
         Some(u) => u,
@@ -591,12 +679,7 @@ async fn google_callback(
                 .bind(&google_user.email)
                 .fetch_optional(&pool)
                 .await
-                .map_err(|e| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(serde_json::json!({"detail": e.to_string()})),
-                    )
-                })?;
+                .map_err(AppError::internal)?;
 
             match existing {
                 Some(existing_user) => {
@@ -607,20 +690,13 @@ async fn google_callback(
                         .bind(existing_user.id)
                         .execute(&pool)
                         .await
-                        .map_err(|e| {
-                            (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({"detail": e.to_string()})))
-                        })?;
+                        .map_err(AppError::internal)?;
 
                     sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
                         .bind(existing_user.id)
                         .fetch_one(&pool)
                         .await
-                        .map_err(|e| {
-                            (
-                                StatusCode::INTERNAL_SERVER_ERROR,
-                                Json(serde_json::json!({"detail": e.to_string()})),
-                            )
-                        })?
+                        .map_err(AppError::internal)?
                 }
                 None => {
                     // Create new user with Google info
@@ -635,17 +711,10 @@ async fn google_callback(
                     let mut final_username = username.clone();
                     let mut counter = 1u32;
                     loop {
-                        let exists = sqlx::query("SELECT id FROM users WHERE username = ?")
-                            .bind(&final_username)
-                            .fetch_optional(&pool)
+                        let exists = is_username_taken(&pool, &final_username, None)
                             .await
-                            .map_err(|e| {
-                                (
-                                    StatusCode::INTERNAL_SERVER_ERROR,
-                                    Json(serde_json::json!({"detail": e.to_string()})),
-                                )
-                            })?;
-                        if exists.is_none() {
+                            .map_err(AppError::internal)?;
+                        if !exists {
                             break;
                         }
                         final_username = format!("{}{}", username, counter);
@@ -667,27 +736,30 @@ async fn google_callback(
                     .bind(now)
                     .execute(&pool)
                     .await
-                    .map_err(|e| {
-                        (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({"detail": e.to_string()})))
-                    })?;
+                    .map_err(AppError::internal)?;
 
                     sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
                         .bind(result.last_insert_id() as i64)
                         .fetch_one(&pool)
                         .await
-                        .map_err(|e| {
-                            (
-                                StatusCode::INTERNAL_SERVER_ERROR,
-                                Json(serde_json::json!({"detail": e.to_string()})),
-                            )
-                        })?
+                        .map_err(AppError::internal)?
                 }
             }
         }
     };
 
+    ensure_not_deleted(&pool, user.id).await?;
+    ensure_not_suspended(&pool, user.id).await?;
+
     // Generate JWT
-    let jwt_token = generate_jwt(&user.username)?;
+    let jwt_token = generate_jwt(user.id)?;
+    record_account_event(
+        &pool,
+        user.id,
+        ACCOUNT_EVENT_LOGIN,
+        Some(AUTH_METHOD_GOOGLE),
+        RequestMetadata::from_headers(&headers),
+    );
 
     // Redirect to frontend with token
     let frontend_url =
@@ -0,0 +1,132 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::get,
+};
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::audit_log::{
+    AUDIT_ACTION_BACKUP_CREATE, AUDIT_TARGET_DATABASE_BACKUP, AuditEvent, RequestMetadata,
+    record_audit_event,
+};
+use crate::backups::{BackupStartError, start_backup};
+use crate::error::AppError;
+use crate::models::{BACKUP_TRIGGER_MANUAL, DatabaseBackup, DatabaseBackupListResponse};
+use crate::routes::admin::extract_admin_user;
+
+const BACKUP_SELECT: &str = "SELECT id, file_name, status, trigger_source, size_bytes, error_message, started_by, started_at, finished_at FROM database_backups";
+
+#[derive(Debug, Deserialize)]
+pub struct BackupListQuery {
+    pub status: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+/// Admin backup routes, nested under `/api/admin`.
+pub fn backup_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/backups", get(list_backups).post(create_backup))
+        .route("/backups/{backup_id}", get(get_backup))
+}
+
+async fn list_backups(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<BackupListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+    let status = query
+        .status
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let backups = sqlx::query_as::<_, DatabaseBackup>(&format!(
+        "{} WHERE (? IS NULL OR status = ?) ORDER BY started_at DESC, id DESC LIMIT ? OFFSET ?",
+        BACKUP_SELECT
+    ))
+    .bind(status)
+    .bind(status)
+    .bind(i64::from(per_page))
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM database_backups WHERE (? IS NULL OR status = ?)")
+            .bind(status)
+            .bind(status)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(DatabaseBackupListResponse {
+        backups,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+async fn get_backup(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(backup_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let backup = fetch_backup(&pool, backup_id).await?;
+    Ok(Json(backup))
+}
+
+/// Starts a backup right away. The dump runs in the background; poll the
+/// returned backup until it leaves the `running` status.
+async fn create_backup(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let backup_id = match start_backup(&pool, BACKUP_TRIGGER_MANUAL, Some(admin.id)).await {
+        Ok(backup_id) => backup_id,
+        Err(BackupStartError::AlreadyRunning(running_id)) => {
+            return Err(AppError::conflict("Another backup is still running")
+                .with_field("backup_id", running_id));
+        }
+        Err(BackupStartError::Database(error)) => return Err(AppError::internal(error)),
+    };
+    let backup = fetch_backup(&pool, backup_id).await?;
+
+    record_audit_event(
+        &pool,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_BACKUP_CREATE,
+            target_type: AUDIT_TARGET_DATABASE_BACKUP,
+            target_id: backup_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!({"file_name": backup.file_name})),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok((StatusCode::ACCEPTED, Json(backup)))
+}
+
+async fn fetch_backup(pool: &MySqlPool, backup_id: i64) -> Result<DatabaseBackup, AppError> {
+    sqlx::query_as::<_, DatabaseBackup>(&format!("{} WHERE id = ?", BACKUP_SELECT))
+        .bind(backup_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Backup not found"))
+}
@@ -0,0 +1,328 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::audit_log::{
+    AUDIT_ACTION_BROADCAST_CANCEL, AUDIT_ACTION_BROADCAST_CREATE, AUDIT_TARGET_BROADCAST,
+    AuditEvent, RequestMetadata, record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    BROADCAST_STATUS_CANCELLED, BROADCAST_STATUS_SCHEDULED, BROADCAST_STATUS_SENDING, Broadcast,
+    BroadcastListResponse, BroadcastPreview, BroadcastRecipient, BroadcastRecipientListResponse,
+    CreateBroadcast,
+};
+use crate::notifications::{
+    BROADCAST_SELECT, BroadcastUser, count_segment, render_broadcast, sample_segment,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::announcements::normalize_audience;
+
+const MAX_BROADCAST_SUBJECT_CHARS: usize = 120;
+/// Subject and body share the notification's 512 characters; a message
+/// that placeholders push past the limit is cut.
+const MAX_BROADCAST_BODY_CHARS: usize = 380;
+const MAX_ACTIVE_WITHIN_DAYS: i64 = 3_650;
+const PREVIEW_SAMPLE_SIZE: i64 = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct BroadcastListQuery {
+    pub status: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+/// Admin broadcast routes, nested under `/api/admin`.
+pub fn broadcast_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/broadcast", get(list_broadcasts).post(create_broadcast))
+        .route("/broadcast/{broadcast_id}", get(get_broadcast))
+        .route(
+            "/broadcast/{broadcast_id}/recipients",
+            get(list_broadcast_recipients),
+        )
+        .route("/broadcast/{broadcast_id}/cancel", post(cancel_broadcast))
+}
+
+/// Schedules an in-app notification to every user in the segment. The
+/// broadcast job sends it once `scheduled_at` has passed; with `preview`
+/// set, only the segment size and a rendered sample are returned.
+async fn create_broadcast(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateBroadcast>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let subject = normalize_text("subject", &input.subject, MAX_BROADCAST_SUBJECT_CHARS)?;
+    let body = normalize_text("body", &input.body, MAX_BROADCAST_BODY_CHARS)?;
+    let audience = normalize_audience(input.audience.as_deref())?;
+    if input
+        .active_within_days
+        .is_some_and(|days| !(1..=MAX_ACTIVE_WITHIN_DAYS).contains(&days))
+    {
+        return Err(AppError::bad_request(format!(
+            "active_within_days must be between 1 and {}",
+            MAX_ACTIVE_WITHIN_DAYS
+        )));
+    }
+
+    if input.preview {
+        let recipient_count = count_segment(&pool, audience, input.active_within_days)
+            .await
+            .map_err(AppError::internal)?;
+        let sample = sample_segment(
+            &pool,
+            audience,
+            input.active_within_days,
+            PREVIEW_SAMPLE_SIZE,
+        )
+        .await
+        .map_err(AppError::internal)?;
+        let preview_user = sample.first().cloned().unwrap_or_else(|| BroadcastUser {
+            id: admin.id,
+            username: admin.username.clone(),
+            display_name: admin.display_name.clone(),
+        });
+        return Ok((
+            StatusCode::OK,
+            Json(serde_json::json!(BroadcastPreview {
+                recipient_count,
+                rendered_message: render_broadcast(&subject, &body, &preview_user),
+                sample_recipients: sample.into_iter().map(|user| user.username).collect(),
+            })),
+        ));
+    }
+
+    let now = Utc::now();
+    let scheduled_at = input.scheduled_at.unwrap_or(now).max(now);
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let result = sqlx::query(
+        r#"
+        INSERT INTO broadcasts
+            (subject, body, audience, active_within_days, status, scheduled_at, created_by, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&subject)
+    .bind(&body)
+    .bind(audience)
+    .bind(input.active_within_days)
+    .bind(BROADCAST_STATUS_SCHEDULED)
+    .bind(scheduled_at)
+    .bind(admin.id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let broadcast_id = result.last_insert_id() as i64;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_BROADCAST_CREATE,
+            target_type: AUDIT_TARGET_BROADCAST,
+            target_id: broadcast_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!({
+                "subject": subject,
+                "audience": audience,
+                "active_within_days": input.active_within_days,
+                "scheduled_at": scheduled_at
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let broadcast = fetch_broadcast(&pool, broadcast_id).await?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!(broadcast))))
+}
+
+async fn list_broadcasts(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<BroadcastListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+    let status = query
+        .status
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let broadcasts = sqlx::query_as::<_, Broadcast>(&format!(
+        "{} WHERE (? IS NULL OR b.status = ?) ORDER BY b.scheduled_at DESC, b.id DESC LIMIT ? OFFSET ?",
+        BROADCAST_SELECT
+    ))
+    .bind(status)
+    .bind(status)
+    .bind(i64::from(per_page))
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM broadcasts WHERE (? IS NULL OR status = ?)")
+            .bind(status)
+            .bind(status)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(BroadcastListResponse {
+        broadcasts,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+async fn get_broadcast(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(broadcast_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let broadcast = fetch_broadcast(&pool, broadcast_id).await?;
+    Ok(Json(broadcast))
+}
+
+/// Per-recipient delivery status, filterable by `pending`, `sent` or
+/// `failed`. Empty until the broadcast starts sending.
+async fn list_broadcast_recipients(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(broadcast_id): Path<i64>,
+    Query(query): Query<BroadcastListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    fetch_broadcast(&pool, broadcast_id).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+    let status = query
+        .status
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let recipients = sqlx::query_as::<_, BroadcastRecipient>(
+        r#"
+        SELECT r.user_id, u.username, r.status, r.error_message, r.delivered_at
+        FROM broadcast_recipients r
+        JOIN users u ON u.id = r.user_id
+        WHERE r.broadcast_id = ? AND (? IS NULL OR r.status = ?)
+        ORDER BY r.user_id ASC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(broadcast_id)
+    .bind(status)
+    .bind(status)
+    .bind(i64::from(per_page))
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM broadcast_recipients WHERE broadcast_id = ? AND (? IS NULL OR status = ?)",
+    )
+    .bind(broadcast_id)
+    .bind(status)
+    .bind(status)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(BroadcastRecipientListResponse {
+        recipients,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+/// Stops a broadcast that has not finished. Recipients already notified
+/// keep their notification; the rest stay pending and are never sent.
+async fn cancel_broadcast(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(broadcast_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let before = fetch_broadcast(&pool, broadcast_id).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let updated = sqlx::query(
+        "UPDATE broadcasts SET status = ?, finished_at = ? WHERE id = ? AND status IN (?, ?)",
+    )
+    .bind(BROADCAST_STATUS_CANCELLED)
+    .bind(Utc::now())
+    .bind(broadcast_id)
+    .bind(BROADCAST_STATUS_SCHEDULED)
+    .bind(BROADCAST_STATUS_SENDING)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    if updated.rows_affected() == 0 {
+        return Err(
+            AppError::conflict("Only scheduled or sending broadcasts can be cancelled")
+                .with_field("status", before.status),
+        );
+    }
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_BROADCAST_CANCEL,
+            target_type: AUDIT_TARGET_BROADCAST,
+            target_id: broadcast_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(before)),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let broadcast = fetch_broadcast(&pool, broadcast_id).await?;
+    Ok(Json(broadcast))
+}
+
+async fn fetch_broadcast(pool: &MySqlPool, broadcast_id: i64) -> Result<Broadcast, AppError> {
+    sqlx::query_as::<_, Broadcast>(&format!("{} WHERE b.id = ?", BROADCAST_SELECT))
+        .bind(broadcast_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Broadcast not found"))
+}
+
+fn normalize_text(field: &str, raw: &str, max_chars: usize) -> Result<String, AppError> {
+    let value = raw.trim();
+    if value.is_empty() || value.chars().count() > max_chars {
+        return Err(AppError::bad_request(format!(
+            "{} must be 1 to {} characters",
+            field, max_chars
+        )));
+    }
+    Ok(value.to_string())
+}
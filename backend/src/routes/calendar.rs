@@ -0,0 +1,138 @@
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::calendar_feed::{
+    build_user_calendar, find_feed_token_user, generate_feed_token, hash_feed_token,
+};
+use crate::error::AppError;
+use crate::models::{CalendarFeedStatus, CalendarFeedTokenResponse};
+use crate::routes::auth::extract_current_user;
+
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+const CALENDAR_FEED_PATH: &str = "/api/v1/users/me/calendar.ics";
+
+#[derive(Debug, Deserialize)]
+struct CalendarFeedQuery {
+    token: Option<String>,
+}
+
+/// The caller's editorial calendar feed, nested under `/api/users`.
+pub fn calendar_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/me/calendar.ics", get(get_calendar_feed))
+        .route(
+            "/me/calendar/token",
+            get(get_feed_status)
+                .post(rotate_feed_token)
+                .delete(revoke_feed_token),
+        )
+}
+
+/// iCalendar feed of the caller's deadlines. Calendar apps cannot send a
+/// bearer header, so the feed token may be passed as `?token=` instead.
+async fn get_calendar_feed(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<CalendarFeedQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = match query.token.as_deref().map(str::trim) {
+        Some(token) if !token.is_empty() => find_feed_token_user(&pool, token)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::unauthorized("Invalid calendar token"))?,
+        _ => extract_current_user(&pool, &headers).await?,
+    };
+
+    let calendar = build_user_calendar(&pool, &user)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE),
+            (header::CACHE_CONTROL, "private, max-age=300"),
+        ],
+        calendar,
+    ))
+}
+
+async fn get_feed_status(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let token: Option<(DateTime<Utc>, Option<DateTime<Utc>>)> = sqlx::query_as(
+        "SELECT created_at, last_used_at FROM calendar_feed_tokens WHERE user_id = ?",
+    )
+    .bind(current_user.id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(CalendarFeedStatus {
+        enabled: token.is_some(),
+        created_at: token.map(|(created_at, _)| created_at),
+        last_used_at: token.and_then(|(_, last_used_at)| last_used_at),
+    }))
+}
+
+/// Issues a new feed token, replacing any earlier one so old subscription
+/// links stop working.
+async fn rotate_feed_token(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let token = generate_feed_token();
+    let now = Utc::now();
+
+    sqlx::query(
+        r#"
+        INSERT INTO calendar_feed_tokens (user_id, token_hash, created_at, last_used_at)
+        VALUES (?, ?, ?, NULL)
+        ON DUPLICATE KEY UPDATE
+            token_hash = VALUES(token_hash),
+            created_at = VALUES(created_at),
+            last_used_at = NULL
+        "#,
+    )
+    .bind(current_user.id)
+    .bind(hash_feed_token(&token))
+    .bind(now)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CalendarFeedTokenResponse {
+            feed_path: format!("{}?token={}", CALENDAR_FEED_PATH, token),
+            token,
+            created_at: now,
+        }),
+    ))
+}
+
+async fn revoke_feed_token(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    sqlx::query("DELETE FROM calendar_feed_tokens WHERE user_id = ?")
+        .bind(current_user.id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(
+        serde_json::json!({"detail": "Calendar feed disabled"}),
+    ))
+}
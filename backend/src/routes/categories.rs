@@ -0,0 +1,297 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, patch, post},
+};
+use sqlx::MySqlPool;
+
+use crate::audit_log::{
+    AUDIT_ACTION_CATEGORY_CREATE, AUDIT_ACTION_CATEGORY_MERGE, AUDIT_ACTION_CATEGORY_UPDATE,
+    AUDIT_TARGET_CATEGORY, AuditEvent, RequestMetadata, record_audit_event,
+};
+use crate::cache::invalidate_cached_post;
+use crate::error::AppError;
+use crate::models::{
+    CreatePostCategory, MergePostCategory, PostCategory, PostCategoryListResponse,
+    PostCategoryMergeResult, UpdatePostCategory,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::posts::{
+    PAPER_CATEGORY, PREPRINT_CATEGORY_ALIAS, category_display_name, normalize_category_code,
+};
+use crate::settings::setting;
+
+const MAX_CATEGORY_CODE_CHARS: usize = 64;
+const MAX_CATEGORY_DISPLAY_NAME_CHARS: usize = 128;
+
+const CATEGORY_SELECT: &str = r#"
+    SELECT
+        CAST(c.id AS SIGNED) AS id,
+        c.code AS code,
+        c.display_name AS display_name,
+        c.is_active AS is_active,
+        (SELECT COUNT(*) FROM posts p WHERE p.category_id = c.id) AS post_count
+    FROM post_categories c
+"#;
+
+/// Admin category routes, nested under `/api/admin`.
+pub fn category_admin_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/categories", get(list_categories).post(create_category))
+        .route("/categories/{category_id}", patch(update_category))
+        .route("/categories/{category_id}/merge", post(merge_category))
+}
+
+async fn list_categories(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let categories =
+        sqlx::query_as::<_, PostCategory>(&format!("{} ORDER BY c.id ASC", CATEGORY_SELECT))
+            .fetch_all(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(PostCategoryListResponse {
+        categories,
+        restricted: setting::<bool>("POST_CATEGORIES_RESTRICTED").unwrap_or(false),
+    }))
+}
+
+async fn create_category(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreatePostCategory>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let code = validate_category_code(&input.code)?;
+    let display_name = match input.display_name.as_deref() {
+        Some(display_name) => validate_display_name(display_name)?,
+        None => category_display_name(&code),
+    };
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let result = sqlx::query("INSERT INTO post_categories (code, display_name) VALUES (?, ?)")
+        .bind(&code)
+        .bind(&display_name)
+        .execute(&mut *tx)
+        .await
+        .map_err(duplicate_code_error)?;
+    let category_id = result.last_insert_id() as i64;
+    let category = fetch_category(&mut *tx, category_id).await?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_CATEGORY_CREATE,
+            target_type: AUDIT_TARGET_CATEGORY,
+            target_id: category_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!(category)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok((StatusCode::CREATED, Json(category)))
+}
+
+/// Renames a category or enables/disables it. The paper category drives
+/// the review workflow, so its code is fixed and it cannot be disabled.
+async fn update_category(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(category_id): Path<i64>,
+    Json(input): Json<UpdatePostCategory>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let before = fetch_category(&mut *tx, category_id).await?;
+
+    let code = input
+        .code
+        .as_deref()
+        .map(validate_category_code)
+        .transpose()?
+        .unwrap_or_else(|| before.code.clone());
+    let display_name = input
+        .display_name
+        .as_deref()
+        .map(validate_display_name)
+        .transpose()?
+        .unwrap_or_else(|| before.display_name.clone());
+    let is_active = input.is_active.unwrap_or(before.is_active);
+    if before.code == PAPER_CATEGORY && (code != PAPER_CATEGORY || !is_active) {
+        return Err(AppError::bad_request(
+            "The paper category cannot be renamed or disabled",
+        ));
+    }
+
+    sqlx::query(
+        "UPDATE post_categories SET code = ?, display_name = ?, is_active = ? WHERE id = ?",
+    )
+    .bind(&code)
+    .bind(&display_name)
+    .bind(is_active)
+    .bind(category_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(duplicate_code_error)?;
+    let category = fetch_category(&mut *tx, category_id).await?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_CATEGORY_UPDATE,
+            target_type: AUDIT_TARGET_CATEGORY,
+            target_id: category_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(before)),
+            after: Some(serde_json::json!(category)),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(Json(category))
+}
+
+/// Moves every post of a category into an enabled target category and
+/// deletes the emptied category. Papers have their own workflow, so the
+/// paper category is never merged in either direction.
+async fn merge_category(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(category_id): Path<i64>,
+    Json(input): Json<MergePostCategory>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    if input.target_category_id == category_id {
+        return Err(AppError::bad_request(
+            "A category cannot be merged into itself",
+        ));
+    }
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let source = fetch_category(&mut *tx, category_id).await?;
+    let target = fetch_category(&mut *tx, input.target_category_id).await?;
+    if source.code == PAPER_CATEGORY || target.code == PAPER_CATEGORY {
+        return Err(AppError::bad_request("The paper category cannot be merged"));
+    }
+    if !target.is_active {
+        return Err(AppError::bad_request(
+            "Cannot merge into a disabled category",
+        ));
+    }
+
+    let moved_post_ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM posts WHERE category_id = ?")
+        .bind(source.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    let moved = sqlx::query("UPDATE posts SET category_id = ? WHERE category_id = ?")
+        .bind(target.id)
+        .bind(source.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM post_categories WHERE id = ?")
+        .bind(source.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    let category = fetch_category(&mut *tx, target.id).await?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_CATEGORY_MERGE,
+            target_type: AUDIT_TARGET_CATEGORY,
+            target_id: source.id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(source)),
+            after: Some(serde_json::json!({
+                "merged_into": category.id,
+                "code": category.code,
+                "moved_posts": moved.rows_affected()
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    for post_id in moved_post_ids {
+        invalidate_cached_post(post_id).await;
+    }
+
+    Ok(Json(PostCategoryMergeResult {
+        category,
+        moved_posts: moved.rows_affected(),
+    }))
+}
+
+async fn fetch_category<'e, E>(executor: E, category_id: i64) -> Result<PostCategory, AppError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::MySql>,
+{
+    sqlx::query_as::<_, PostCategory>(&format!("{} WHERE c.id = ?", CATEGORY_SELECT))
+        .bind(category_id)
+        .fetch_optional(executor)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Category not found"))
+}
+
+/// Codes are stored the way post creation normalizes them: lowercase
+/// letters, digits, `_` and `-`.
+pub fn validate_category_code(raw: &str) -> Result<String, AppError> {
+    let code = normalize_category_code(raw);
+    if raw.trim().is_empty()
+        || code.chars().count() > MAX_CATEGORY_CODE_CHARS
+        || !code
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_' || ch == '-')
+    {
+        return Err(AppError::bad_request(format!(
+            "code must be 1 to {} lowercase letters, digits, '_' or '-'",
+            MAX_CATEGORY_CODE_CHARS
+        )));
+    }
+    if code == PREPRINT_CATEGORY_ALIAS {
+        return Err(AppError::bad_request(
+            "preprint is reserved for preprint papers",
+        ));
+    }
+    Ok(code)
+}
+
+pub fn validate_display_name(raw: &str) -> Result<String, AppError> {
+    let display_name = raw.trim();
+    if display_name.is_empty() || display_name.chars().count() > MAX_CATEGORY_DISPLAY_NAME_CHARS {
+        return Err(AppError::bad_request(format!(
+            "display_name must be 1 to {} characters",
+            MAX_CATEGORY_DISPLAY_NAME_CHARS
+        )));
+    }
+    Ok(display_name.to_string())
+}
+
+fn duplicate_code_error(error: sqlx::Error) -> AppError {
+    match &error {
+        sqlx::Error::Database(db_error) if db_error.code().as_deref() == Some("1062") => {
+            AppError::conflict("A category with this code already exists")
+        }
+        _ => AppError::internal(error),
+    }
+}
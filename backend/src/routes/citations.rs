@@ -0,0 +1,538 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{MySql, MySqlPool, QueryBuilder, Transaction};
+use std::collections::{BTreeSet, HashSet};
+
+use crate::cache::{invalidate_cached_metrics, invalidate_cached_post};
+use crate::error::AppError;
+use crate::metrics::refresh_post_citation_counts;
+use crate::models::{
+    CitationAuditEntry, CitationAuditListResponse, CitationChangeSet, CitationEdge,
+    ExternalCitation, ExternalCitationListResponse, PostCitationsResponse, UpdatePostCitations,
+    UpdatePostCitationsResponse,
+};
+use crate::routes::auth::extract_current_user;
+use crate::routes::comments::ensure_post_visibility;
+use crate::routes::posts::validate_citation_targets;
+
+const CITATION_SOURCE_MANUAL: u8 = 1;
+const MAX_CITATIONS_PER_REQUEST: usize = 500;
+
+pub fn citations_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route(
+            "/{post_id}/citations",
+            get(get_post_citations).put(update_post_citations),
+        )
+        .route("/{post_id}/citations/history", get(list_citation_history))
+        .route(
+            "/{post_id}/external-citations",
+            get(list_external_citations),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct CitationHistoryQuery {
+    limit: Option<i32>,
+    offset: Option<i32>,
+}
+
+async fn get_post_citations(
+    State(pool): State<MySqlPool>,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    ensure_post_visibility(&pool, post_id).await?;
+
+    let response = fetch_post_citations(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(response))
+}
+
+async fn update_post_citations(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Json(input): Json<UpdatePostCitations>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    let (author_id, category_code): (i64, String) = sqlx::query_as(
+        r#"
+        SELECT p.author_id, c.code
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.id = ? AND p.deleted_at IS NULL
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if author_id != current_user.id {
+        return Err(AppError::forbidden(
+            "Not authorized to edit citations of this post",
+        ));
+    }
+
+    if category_code != "paper" {
+        return Err(AppError::bad_request(
+            "Citations are only allowed for paper category posts",
+        ));
+    }
+
+    let (post_ids, dois) = prepare_manual_citations(&pool, post_id, &input).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let changes = sync_manual_citations(
+        &mut tx,
+        post_id,
+        Some(current_user.id),
+        &post_ids,
+        Some(&dois),
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+    invalidate_cached_metrics().await;
+
+    let current = fetch_post_citations(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(UpdatePostCitationsResponse { changes, current }))
+}
+
+/// Checks a citation list for `post_id` and splits it into cited post ids
+/// and external DOIs, resolving DOIs of posts on this site to their ids.
+pub async fn prepare_manual_citations(
+    pool: &MySqlPool,
+    post_id: i64,
+    input: &UpdatePostCitations,
+) -> Result<(Vec<i64>, Vec<String>), AppError> {
+    if input.post_ids.len() + input.dois.len() > MAX_CITATIONS_PER_REQUEST {
+        return Err(AppError::bad_request(format!(
+            "At most {} citations can be set at once",
+            MAX_CITATIONS_PER_REQUEST
+        )));
+    }
+
+    let mut post_ids = Vec::with_capacity(input.post_ids.len());
+    let mut seen_post_ids = HashSet::new();
+    for cited_post_id in &input.post_ids {
+        if *cited_post_id <= 0 {
+            return Err(AppError::bad_request(
+                "Citation post IDs must be positive integers",
+            ));
+        }
+        if seen_post_ids.insert(*cited_post_id) {
+            post_ids.push(*cited_post_id);
+        }
+    }
+
+    let mut dois = Vec::with_capacity(input.dois.len());
+    for raw in &input.dois {
+        let doi = normalize_reference_doi(raw)
+            .ok_or_else(|| AppError::bad_request(format!("Invalid DOI: {}", raw.trim())))?;
+        if !dois.contains(&doi) {
+            dois.push(doi);
+        }
+    }
+
+    // DOIs that belong to posts on this site become internal citation edges.
+    let resolved = resolve_internal_dois(pool, &dois)
+        .await
+        .map_err(AppError::internal)?;
+    dois.retain(|doi| !resolved.iter().any(|(resolved_doi, _)| resolved_doi == doi));
+    for (_, resolved_post_id) in resolved {
+        if seen_post_ids.insert(resolved_post_id) {
+            post_ids.push(resolved_post_id);
+        }
+    }
+
+    if post_ids.contains(&post_id) {
+        return Err(AppError::bad_request("Self-citation is not allowed"));
+    }
+    validate_citation_targets(pool, &post_ids).await?;
+
+    Ok((post_ids, dois))
+}
+
+async fn list_citation_history(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Query(query): Query<CitationHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    let (author_id,): (i64,) =
+        sqlx::query_as("SELECT author_id FROM posts WHERE id = ? AND deleted_at IS NULL")
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if author_id != current_user.id && !current_user.is_admin {
+        return Err(AppError::forbidden(
+            "Not authorized to view citation history",
+        ));
+    }
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let (total,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM post_citation_audit_log WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    let entries = sqlx::query_as::<_, CitationAuditEntry>(
+        r#"
+        SELECT id, post_id, actor_id, action, cited_post_id, cited_doi, created_at
+        FROM post_citation_audit_log
+        WHERE post_id = ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(post_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(CitationAuditListResponse {
+        entries,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+async fn list_external_citations(
+    State(pool): State<MySqlPool>,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    ensure_post_visibility(&pool, post_id).await?;
+
+    let registration = sqlx::query_as::<_, (String, Option<DateTime<Utc>>)>(
+        "SELECT registered_doi, citations_synced_at FROM post_doi_registrations WHERE post_id = ?",
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let citations = sqlx::query_as::<_, ExternalCitation>(
+        r#"
+        SELECT
+            ec.id,
+            ec.cited_post_id,
+            cs.code AS source,
+            ec.external_work_id,
+            ec.citing_doi,
+            ec.citing_title,
+            ec.citing_venue,
+            CAST(ec.citing_year AS SIGNED) AS citing_year,
+            ec.first_seen_at,
+            ec.last_seen_at
+        FROM post_external_citations ec
+        JOIN citation_sources cs ON cs.id = ec.citation_source_id
+        WHERE ec.cited_post_id = ?
+        ORDER BY ec.citing_year IS NULL, ec.citing_year DESC, ec.id DESC
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let (registered_doi, synced_at) = match registration {
+        Some((doi, synced_at)) => (Some(doi), synced_at),
+        None => (None, None),
+    };
+
+    Ok(Json(ExternalCitationListResponse {
+        post_id,
+        registered_doi,
+        synced_at,
+        total: citations.len() as i64,
+        citations,
+    }))
+}
+
+/// Diffs the manual citation edges (and, when given, the external DOI
+/// references) of a post against the requested set, applies only the
+/// difference and records every addition and removal in the audit log.
+pub async fn sync_manual_citations(
+    tx: &mut Transaction<'_, MySql>,
+    post_id: i64,
+    actor_id: Option<i64>,
+    post_ids: &[i64],
+    dois: Option<&[String]>,
+) -> Result<CitationChangeSet, sqlx::Error> {
+    let existing_post_ids: BTreeSet<i64> = sqlx::query_scalar(
+        "SELECT cited_post_id FROM post_citations WHERE citing_post_id = ? AND citation_source_id = ?",
+    )
+    .bind(post_id)
+    .bind(CITATION_SOURCE_MANUAL)
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .collect();
+    let requested_post_ids: BTreeSet<i64> = post_ids
+        .iter()
+        .copied()
+        .filter(|cited_post_id| *cited_post_id != post_id)
+        .collect();
+
+    let mut changes = CitationChangeSet {
+        added_post_ids: requested_post_ids
+            .difference(&existing_post_ids)
+            .copied()
+            .collect(),
+        removed_post_ids: existing_post_ids
+            .difference(&requested_post_ids)
+            .copied()
+            .collect(),
+        ..CitationChangeSet::default()
+    };
+
+    let now = Utc::now();
+    for cited_post_id in &changes.removed_post_ids {
+        sqlx::query(
+            "DELETE FROM post_citations WHERE citing_post_id = ? AND cited_post_id = ? AND citation_source_id = ?",
+        )
+        .bind(post_id)
+        .bind(cited_post_id)
+        .bind(CITATION_SOURCE_MANUAL)
+        .execute(&mut **tx)
+        .await?;
+        insert_audit_entry(tx, post_id, actor_id, "removed", Some(*cited_post_id), None, now).await?;
+    }
+
+    for cited_post_id in &changes.added_post_ids {
+        sqlx::query(
+            r#"
+            INSERT IGNORE INTO post_citations (citing_post_id, cited_post_id, citation_source_id, created_at, retraction_flagged_at)
+            VALUES (?, ?, ?, ?, (SELECT MIN(pr.retracted_at) FROM post_retractions pr WHERE pr.post_id IN (?, ?)))
+            "#,
+        )
+        .bind(post_id)
+        .bind(cited_post_id)
+        .bind(CITATION_SOURCE_MANUAL)
+        .bind(now)
+        .bind(post_id)
+        .bind(cited_post_id)
+        .execute(&mut **tx)
+        .await?;
+        insert_audit_entry(tx, post_id, actor_id, "added", Some(*cited_post_id), None, now).await?;
+    }
+
+    let affected_post_ids: Vec<i64> = changes
+        .added_post_ids
+        .iter()
+        .chain(&changes.removed_post_ids)
+        .copied()
+        .collect();
+    refresh_post_citation_counts(tx, &affected_post_ids).await?;
+
+    let Some(dois) = dois else {
+        return Ok(changes);
+    };
+
+    let existing_dois: BTreeSet<String> =
+        sqlx::query_scalar("SELECT doi FROM post_external_references WHERE citing_post_id = ?")
+            .bind(post_id)
+            .fetch_all(&mut **tx)
+            .await?
+            .into_iter()
+            .collect();
+    let requested_dois: BTreeSet<String> = dois.iter().cloned().collect();
+    changes.added_dois = requested_dois.difference(&existing_dois).cloned().collect();
+    changes.removed_dois = existing_dois.difference(&requested_dois).cloned().collect();
+
+    for doi in &changes.removed_dois {
+        sqlx::query("DELETE FROM post_external_references WHERE citing_post_id = ? AND doi = ?")
+            .bind(post_id)
+            .bind(doi)
+            .execute(&mut **tx)
+            .await?;
+        insert_audit_entry(tx, post_id, actor_id, "removed", None, Some(doi), now).await?;
+    }
+
+    for doi in &changes.added_dois {
+        sqlx::query(
+            "INSERT IGNORE INTO post_external_references (citing_post_id, doi, created_at) VALUES (?, ?, ?)",
+        )
+        .bind(post_id)
+        .bind(doi)
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+        insert_audit_entry(tx, post_id, actor_id, "added", None, Some(doi), now).await?;
+    }
+
+    Ok(changes)
+}
+
+async fn insert_audit_entry(
+    tx: &mut Transaction<'_, MySql>,
+    post_id: i64,
+    actor_id: Option<i64>,
+    action: &str,
+    cited_post_id: Option<i64>,
+    cited_doi: Option<&str>,
+    created_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO post_citation_audit_log (post_id, actor_id, action, cited_post_id, cited_doi, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(post_id)
+    .bind(actor_id)
+    .bind(action)
+    .bind(cited_post_id)
+    .bind(cited_doi)
+    .bind(created_at)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn fetch_post_citations(
+    pool: &MySqlPool,
+    post_id: i64,
+) -> Result<PostCitationsResponse, sqlx::Error> {
+    let citations = sqlx::query_as::<_, CitationEdge>(
+        r#"
+        SELECT
+            pc.cited_post_id,
+            p.title,
+            cs.code AS source,
+            pc.retraction_flagged_at IS NOT NULL AS retraction_flagged
+        FROM post_citations pc
+        JOIN posts p ON p.id = pc.cited_post_id
+        JOIN citation_sources cs ON cs.id = pc.citation_source_id
+        WHERE pc.citing_post_id = ? AND p.deleted_at IS NULL
+        ORDER BY pc.cited_post_id ASC, pc.citation_source_id ASC
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    let external_references: Vec<String> = sqlx::query_scalar(
+        "SELECT doi FROM post_external_references WHERE citing_post_id = ? ORDER BY doi ASC",
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PostCitationsResponse {
+        post_id,
+        citations,
+        external_references,
+    })
+}
+
+/// Splits reference DOIs collected from an external record into posts on this
+/// site and external DOIs. Malformed DOIs are dropped instead of rejected.
+pub async fn resolve_reference_dois(
+    pool: &MySqlPool,
+    raw_dois: &[String],
+) -> Result<(Vec<i64>, Vec<String>), sqlx::Error> {
+    let mut dois: Vec<String> = Vec::new();
+    for doi in raw_dois
+        .iter()
+        .filter_map(|raw| normalize_reference_doi(raw))
+    {
+        if dois.len() >= MAX_CITATIONS_PER_REQUEST {
+            break;
+        }
+        if !dois.contains(&doi) {
+            dois.push(doi);
+        }
+    }
+
+    let resolved = resolve_internal_dois(pool, &dois).await?;
+    dois.retain(|doi| !resolved.iter().any(|(resolved_doi, _)| resolved_doi == doi));
+    let mut post_ids: Vec<i64> = Vec::with_capacity(resolved.len());
+    for (_, post_id) in resolved {
+        if !post_ids.contains(&post_id) {
+            post_ids.push(post_id);
+        }
+    }
+
+    Ok((post_ids, dois))
+}
+
+/// Maps DOIs to posts on this site, either through the internal `TM.` DOI
+/// or through a DOI registered with an external agency.
+async fn resolve_internal_dois(
+    pool: &MySqlPool,
+    dois: &[String],
+) -> Result<Vec<(String, i64)>, sqlx::Error> {
+    if dois.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT LOWER(doi), post_id FROM post_doi_metadata WHERE doi LIKE 'TM.%' AND doi IN (
+        "#,
+    );
+    {
+        let mut separated = query_builder.separated(", ");
+        for doi in dois {
+            separated.push_bind(doi);
+        }
+    }
+    query_builder.push(") UNION SELECT LOWER(registered_doi), post_id FROM post_doi_registrations WHERE registered_doi IN (");
+    {
+        let mut separated = query_builder.separated(", ");
+        for doi in dois {
+            separated.push_bind(doi);
+        }
+    }
+    query_builder.push(")");
+
+    query_builder.build_query_as().fetch_all(pool).await
+}
+
+fn normalize_reference_doi(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    let lowered = trimmed.to_ascii_lowercase();
+    let without_prefix = ["https://doi.org/", "http://doi.org/", "doi:"]
+        .iter()
+        .find_map(|prefix| lowered.strip_prefix(prefix))
+        .unwrap_or(&lowered)
+        .trim();
+
+    let is_doi_shape = (without_prefix.starts_with("10.") || without_prefix.starts_with("tm."))
+        && without_prefix.contains('/')
+        && !without_prefix.ends_with('/')
+        && !without_prefix.chars().any(char::is_whitespace);
+    if !is_doi_shape || without_prefix.len() > 255 {
+        return None;
+    }
+
+    Some(without_prefix.to_string())
+}
@@ -1,16 +1,37 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use std::time::Duration;
+
 use axum::{
     Router,
-    extract::{Json, Path, State},
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
-    routing::get,
+    extract::{Json, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
 };
 use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use sqlx::FromRow;
 use sqlx::MySqlPool;
+use utoipa::{IntoParams, OpenApi};
 
-use crate::models::{Comment, CommentResponse, CreateComment, User, UserResponse};
-use crate::routes::auth::extract_current_user;
+use crate::conditional_get::{CachePolicy, conditional_json};
+use crate::error::{AppError, ErrorBody};
+use crate::markdown::render_markdown;
+use crate::models::{
+    Comment, CommentListResponse, CommentMention, CommentResponse, CommentThread, CreateComment,
+    CreateCommentReport, SubscriptionStatusResponse, UpdateComment, User, UserResponse,
+    WEBHOOK_EVENT_CONTENT_REPORT,
+};
+use crate::notifications::{
+    MentionSource, fetch_mentions, fetch_subscriptions, notify_comment_subscribers,
+    record_mentions, subscribe, unsubscribe,
+};
+use crate::outgoing_webhooks::{WebhookMessage, queue_webhook_message};
+use crate::rate_limit::{RateLimitedRoute, SlidingWindow, SlidingWindowLimiter, rate_limited};
+use crate::routes::auth::{ensure_not_suspended, extract_current_user, extract_optional_user};
+use crate::settings::setting;
+use crate::validation::ValidatedJson;
 
 #[derive(Debug, FromRow)]
 struct CommentWithAuthorRow {
@@ -21,6 +42,7 @@ struct CommentWithAuthorRow {
     content: String,
     is_deleted: bool,
     deleted_at: Option<DateTime<Utc>>,
+    edited_at: Option<DateTime<Utc>>,
     comment_created_at: DateTime<Utc>,
     comment_updated_at: Option<DateTime<Utc>>,
     user_id: i64,
@@ -41,6 +63,63 @@ pub struct CommentDeleteTarget {
     pub parent_comment_id: Option<i64>,
 }
 
+const DEFAULT_COMMENT_EDIT_WINDOW_SECS: i64 = 900;
+const DEFAULT_COMMENT_MAX_DEPTH: i32 = 5;
+/// Guards the ancestry walk against corrupted parent links.
+const MAX_ANCESTRY_STEPS: i32 = 1_000;
+const DEFAULT_COMMENT_RATE_LIMIT_PER_MINUTE: usize = 10;
+const DEFAULT_COMMENT_RATE_LIMIT_BURST: usize = 3;
+const COMMENT_BURST_WINDOW_SECS: u64 = 10;
+const REPORT_EXCERPT_CHARS: usize = 2000;
+const DEFAULT_COMMENT_PAGE_SIZE: i32 = 50;
+const MAX_COMMENT_PAGE_SIZE: i32 = 200;
+const COMMENT_WITH_AUTHOR_SELECT: &str = r#"
+    SELECT
+        c.id AS comment_id,
+        c.post_id AS post_id,
+        c.author_id AS author_id,
+        c.parent_comment_id AS parent_comment_id,
+        c.content AS content,
+        c.is_deleted AS is_deleted,
+        c.deleted_at AS deleted_at,
+        c.edited_at AS edited_at,
+        c.created_at AS comment_created_at,
+        c.updated_at AS comment_updated_at,
+        u.id AS user_id,
+        u.username AS username,
+        u.email AS email,
+        u.display_name AS display_name,
+        u.bio AS bio,
+        u.avatar_url AS avatar_url,
+        u.is_admin AS is_admin,
+        u.created_at AS user_created_at
+    FROM comments c
+    JOIN users u ON u.id = c.author_id
+    WHERE c.post_id = ? AND u.deleted_at IS NULL
+"#;
+
+// Bound to the viewer's id; a NULL viewer hides nothing.
+const HIDE_BLOCKED_AUTHORS: &str =
+    " AND c.author_id NOT IN (SELECT ub.blocked_id FROM user_blocks ub WHERE ub.blocker_id = ?)";
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CommentListQuery {
+    limit: Option<i32>,
+    offset: Option<i32>,
+    /// Nest replies under their parents instead of a flat list.
+    threaded: Option<bool>,
+    /// `html` adds rendered Markdown as `content_html`.
+    render: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CommentRenderQuery {
+    /// `html` adds rendered Markdown as `content_html`.
+    render: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum DeleteCommentMode {
     Soft,
@@ -60,139 +139,329 @@ pub fn comments_routes() -> Router<MySqlPool> {
     Router::new()
         .route(
             "/{post_id}/comments",
-            get(list_comments).post(create_comment),
+            get(list_comments).post(rate_limited(RateLimitedRoute::Comment, create_comment)),
         )
         .route(
             "/{post_id}/comments/{comment_id}",
-            axum::routing::delete(delete_comment),
+            axum::routing::put(update_comment).delete(delete_comment),
+        )
+        .route(
+            "/{post_id}/comments/{comment_id}/report",
+            post(report_comment),
+        )
+        .route(
+            "/{post_id}/subscription",
+            get(get_subscription)
+                .post(subscribe_post)
+                .delete(unsubscribe_post),
+        )
+        .route(
+            "/{post_id}/comments/{comment_id}/subscription",
+            post(subscribe_thread).delete(unsubscribe_thread),
         )
 }
 
+/// OpenAPI paths for `comments_routes`, relative to `/api/posts`.
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_comments,
+    create_comment,
+    update_comment,
+    delete_comment,
+    report_comment
+))]
+pub struct CommentsApi;
+
+/// Comments by users the viewer has blocked are left out.
+#[utoipa::path(
+    get,
+    path = "/{post_id}/comments",
+    tag = "comments",
+    params(("post_id" = i64, Path, description = "Post id"), CommentListQuery),
+    responses(
+        (status = 200, description = "Comments, minus authors the viewer has blocked", body = CommentListResponse),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+        (status = 404, description = "Post not found", body = ErrorBody),
+    )
+)]
 async fn list_comments(
     State(pool): State<MySqlPool>,
+    headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Query(query): Query<CommentListQuery>,
+) -> Result<Response, AppError> {
     ensure_post_visibility(&pool, post_id).await?;
+    let viewer_id = extract_optional_user(&pool, &headers)
+        .await?
+        .map(|viewer| viewer.id);
+    // Blocked authors are hidden, so a signed-in viewer's list is their own.
+    let cache_policy = CachePolicy::for_viewer(viewer_id.is_some());
 
-    let rows = sqlx::query_as::<_, CommentWithAuthorRow>(
-        r#"
-        SELECT
-            c.id AS comment_id,
-            c.post_id AS post_id,
-            c.author_id AS author_id,
-            c.parent_comment_id AS parent_comment_id,
-            c.content AS content,
-            c.is_deleted AS is_deleted,
-            c.deleted_at AS deleted_at,
-            c.created_at AS comment_created_at,
-            c.updated_at AS comment_updated_at,
-            u.id AS user_id,
-            u.username AS username,
-            u.email AS email,
-            u.display_name AS display_name,
-            u.bio AS bio,
-            u.avatar_url AS avatar_url,
-            u.is_admin AS is_admin,
-            u.created_at AS user_created_at
-        FROM comments c
-        JOIN users u ON u.id = c.author_id
-        WHERE c.post_id = ?
-        ORDER BY c.created_at ASC
-        "#,
-    )
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_COMMENT_PAGE_SIZE)
+        .clamp(1, MAX_COMMENT_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let threaded = query.threaded.unwrap_or(false);
+    let render_html = wants_html(query.render.as_deref());
+
+    let (total,): (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM comments c WHERE c.post_id = ?{}",
+        HIDE_BLOCKED_AUTHORS
+    ))
+    .bind(post_id)
+    .bind(viewer_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    if !threaded {
+        let rows = sqlx::query_as::<_, CommentWithAuthorRow>(&format!(
+            "{}{} ORDER BY c.created_at ASC, c.id ASC LIMIT ? OFFSET ?",
+            COMMENT_WITH_AUTHOR_SELECT, HIDE_BLOCKED_AUTHORS
+        ))
+        .bind(post_id)
+        .bind(viewer_id)
+        .bind(i64::from(limit))
+        .bind(i64::from(offset))
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+        let mut mentions = load_comment_mentions(&pool, &rows).await?;
+        let edges: Vec<(i64, Option<i64>)> =
+            sqlx::query_as("SELECT id, parent_comment_id FROM comments WHERE post_id = ?")
+                .bind(post_id)
+                .fetch_all(&pool)
+                .await
+                .map_err(AppError::internal)?;
+        let depths = compute_comment_depths(&edges);
+
+        let response = CommentListResponse {
+            comments: Some(
+                rows.into_iter()
+                    .map(|row| map_comment_row(row, &mut mentions, &depths, render_html))
+                    .collect(),
+            ),
+            threads: None,
+            total,
+            total_threads: None,
+            limit,
+            offset,
+        };
+        return conditional_json(&headers, cache_policy, &response);
+    }
+
+    // Threads are paginated by their root comment, so every reply of a
+    // returned root is included regardless of the page size.
+    let rows = sqlx::query_as::<_, CommentWithAuthorRow>(&format!(
+        "{}{} ORDER BY c.created_at ASC, c.id ASC",
+        COMMENT_WITH_AUTHOR_SELECT, HIDE_BLOCKED_AUTHORS
+    ))
     .bind(post_id)
+    .bind(viewer_id)
     .fetch_all(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    .map_err(AppError::internal)?;
 
-    let responses: Vec<CommentResponse> = rows
+    let mut mentions = load_comment_mentions(&pool, &rows).await?;
+    let edges: Vec<(i64, Option<i64>)> = rows
+        .iter()
+        .map(|row| (row.comment_id, row.parent_comment_id))
+        .collect();
+    let depths = compute_comment_depths(&edges);
+    let comment_ids: HashSet<i64> = rows.iter().map(|row| row.comment_id).collect();
+    let mut root_comments = Vec::new();
+    let mut replies_by_parent: HashMap<i64, Vec<CommentResponse>> = HashMap::new();
+    for comment in rows
         .into_iter()
-        .map(|row| {
-            let author = UserResponse::from(User {
-                id: row.user_id,
-                username: row.username,
-                email: row.email,
-                hashed_password: None,
-                google_id: None,
-                display_name: row.display_name,
-                bio: row.bio,
-                introduction: None,
-                hobbies: None,
-                interests: None,
-                research_areas: None,
-                avatar_url: row.avatar_url,
-                is_admin: row.is_admin,
-                created_at: row.user_created_at,
-                updated_at: None,
-            });
-
-            CommentResponse {
-                id: row.comment_id,
-                post_id: row.post_id,
-                author_id: row.author_id,
-                parent_comment_id: row.parent_comment_id,
-                author,
-                content: if row.is_deleted {
-                    String::new()
-                } else {
-                    row.content
-                },
-                is_deleted: row.is_deleted,
-                deleted_at: row.deleted_at,
-                created_at: row.comment_created_at,
-                updated_at: row.comment_updated_at,
+        .map(|row| map_comment_row(row, &mut mentions, &depths, render_html))
+    {
+        match comment.parent_comment_id {
+            Some(parent_id) if comment_ids.contains(&parent_id) => {
+                replies_by_parent
+                    .entry(parent_id)
+                    .or_default()
+                    .push(comment);
             }
-        })
+            _ => root_comments.push(comment),
+        }
+    }
+
+    let total_threads = root_comments.len() as i64;
+    let threads = root_comments
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|comment| build_comment_thread(comment, &mut replies_by_parent))
         .collect();
 
-    Ok(Json(responses))
+    let response = CommentListResponse {
+        comments: None,
+        threads: Some(threads),
+        total,
+        total_threads: Some(total_threads),
+        limit,
+        offset,
+    };
+    conditional_json(&headers, cache_policy, &response)
 }
 
+fn build_comment_thread(
+    comment: CommentResponse,
+    replies_by_parent: &mut HashMap<i64, Vec<CommentResponse>>,
+) -> CommentThread {
+    let replies: Vec<CommentThread> = replies_by_parent
+        .remove(&comment.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|reply| build_comment_thread(reply, replies_by_parent))
+        .collect();
+    let reply_count = replies.iter().map(|reply| 1 + reply.reply_count).sum();
+
+    CommentThread {
+        comment,
+        reply_count,
+        replies,
+    }
+}
+
+fn wants_html(render: Option<&str>) -> bool {
+    render.is_some_and(|mode| mode.eq_ignore_ascii_case("html"))
+}
+
+async fn load_comment_mentions(
+    pool: &MySqlPool,
+    rows: &[CommentWithAuthorRow],
+) -> Result<HashMap<i64, Vec<CommentMention>>, AppError> {
+    let comment_ids: Vec<i64> = rows
+        .iter()
+        .filter(|row| !row.is_deleted)
+        .map(|row| row.comment_id)
+        .collect();
+
+    fetch_mentions(pool, MentionSource::Comment, &comment_ids)
+        .await
+        .map_err(AppError::internal)
+}
+
+fn map_comment_row(
+    row: CommentWithAuthorRow,
+    mentions: &mut HashMap<i64, Vec<CommentMention>>,
+    depths: &HashMap<i64, i32>,
+    render_html: bool,
+) -> CommentResponse {
+    let author = UserResponse::from(User {
+        id: row.user_id,
+        username: row.username,
+        email: row.email,
+        hashed_password: None,
+        google_id: None,
+        display_name: row.display_name,
+        bio: row.bio,
+        introduction: None,
+        hobbies: None,
+        interests: None,
+        research_areas: None,
+        affiliation: None,
+        website: None,
+        orcid_id: None,
+        avatar_url: row.avatar_url,
+        is_admin: row.is_admin,
+        created_at: row.user_created_at,
+        updated_at: None,
+    });
+
+    CommentResponse {
+        id: row.comment_id,
+        post_id: row.post_id,
+        author_id: row.author_id,
+        parent_comment_id: row.parent_comment_id,
+        depth: depths.get(&row.comment_id).copied().unwrap_or(0),
+        author,
+        content_html: (render_html && !row.is_deleted).then(|| render_markdown(&row.content)),
+        content: if row.is_deleted {
+            String::new()
+        } else {
+            row.content
+        },
+        is_deleted: row.is_deleted,
+        deleted_at: row.deleted_at,
+        edited_at: row.edited_at,
+        created_at: row.comment_created_at,
+        updated_at: row.comment_updated_at,
+        mentions: mentions.remove(&row.comment_id).unwrap_or_default(),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/{post_id}/comments",
+    tag = "comments",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id"), CommentRenderQuery),
+    request_body = CreateComment,
+    responses(
+        (status = 201, description = "Comment created", body = CommentResponse),
+        (status = 403, description = "Suspended, banned or blocked by the author", body = ErrorBody),
+        (status = 404, description = "Post or parent comment not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+        (status = 429, description = "Commenting or requesting too fast", body = ErrorBody),
+    )
+)]
 async fn create_comment(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-    Json(input): Json<CreateComment>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Query(render): Query<CommentRenderQuery>,
+    ValidatedJson(input): ValidatedJson<CreateComment>,
+) -> Result<Response, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     ensure_post_visibility(&pool, post_id).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    ensure_not_comment_banned(&pool, current_user.id).await?;
 
-    if input.content.trim().is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Comment content is required"})),
-        ));
-    }
-
+    let post_author_id: i64 = sqlx::query_scalar("SELECT author_id FROM posts WHERE id = ?")
+        .bind(post_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    let mut blocker_ids = vec![post_author_id];
     if let Some(parent_comment_id) = input.parent_comment_id {
-        let parent_row = sqlx::query_as::<_, (i64, i64)>("SELECT id, post_id FROM comments WHERE id = ?")
-            .bind(parent_comment_id)
-            .fetch_optional(&pool)
-            .await
-            .map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({"detail": e.to_string()})),
-                )
-            })?
-            .ok_or_else(|| {
-                (
-                    StatusCode::NOT_FOUND,
-                    Json(serde_json::json!({"detail": "Parent comment not found"})),
-                )
-            })?;
+        let parent_row = sqlx::query_as::<_, (i64, i64, i64)>(
+            "SELECT id, post_id, author_id FROM comments WHERE id = ?",
+        )
+        .bind(parent_comment_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Parent comment not found"))?;
 
         if parent_row.1 != post_id {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"detail": "Parent comment does not belong to this post"})),
+            return Err(AppError::bad_request(
+                "Parent comment does not belong to this post",
             ));
         }
+        blocker_ids.push(parent_row.2);
+    }
+
+    // Users blocked by the post's author, or by the author of the comment
+    // being replied to, cannot comment there.
+    for blocker_id in blocker_ids {
+        if is_blocked_by(&pool, blocker_id, current_user.id)
+            .await
+            .map_err(AppError::internal)?
+        {
+            return Err(AppError::forbidden("You cannot comment here"));
+        }
+    }
+
+    let (parent_comment_id, depth) =
+        resolve_reply_parent(&pool, "comments", input.parent_comment_id)
+            .await
+            .map_err(AppError::internal)?;
+
+    if let Some(response) = check_comment_rate_limit(current_user.id) {
+        return Ok(response);
     }
 
     let now = Utc::now();
@@ -201,28 +470,49 @@ async fn create_comment(
     )
     .bind(post_id)
     .bind(current_user.id)
-    .bind(input.parent_comment_id)
+    .bind(parent_comment_id)
     .bind(input.content.trim())
     .bind(now)
     .execute(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    .map_err(AppError::internal)?;
 
     let comment = sqlx::query_as::<_, Comment>("SELECT * FROM comments WHERE id = ?")
         .bind(result.last_insert_id() as i64)
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
+
+    let mentions = record_mentions(
+        &pool,
+        MentionSource::Comment,
+        comment.id,
+        post_id,
+        (Some(current_user.id), &current_user.username),
+        &comment.content,
+        None,
+    )
+    .await;
+
+    // Commenters follow replies to their own comment by default.
+    if let Err(error) = subscribe(&pool, current_user.id, post_id, Some(comment.id)).await {
+        tracing::warn!(
+            "Failed to subscribe user {} to comment {}: {}",
+            current_user.id,
+            comment.id,
+            error
+        );
+    }
+    let mentioned_user_ids: Vec<i64> = mentions.iter().map(|mention| mention.user_id).collect();
+    notify_comment_subscribers(
+        &pool,
+        post_id,
+        comment.id,
+        comment.parent_comment_id,
+        (current_user.id, &current_user.username),
+        &mentioned_user_ids,
+    )
+    .await;
 
     Ok((
         StatusCode::CREATED,
@@ -231,53 +521,320 @@ async fn create_comment(
             post_id: comment.post_id,
             author_id: comment.author_id,
             parent_comment_id: comment.parent_comment_id,
+            depth,
             author: UserResponse::from(current_user),
+            content_html: wants_html(render.render.as_deref())
+                .then(|| render_markdown(&comment.content)),
             content: comment.content,
             is_deleted: comment.is_deleted,
             deleted_at: comment.deleted_at,
+            edited_at: comment.edited_at,
             created_at: comment.created_at,
             updated_at: comment.updated_at,
+            mentions,
         }),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/{post_id}/comments/{comment_id}",
+    tag = "comments",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id"), ("comment_id" = i64, Path, description = "Comment id"), CommentRenderQuery),
+    request_body = UpdateComment,
+    responses(
+        (status = 200, description = "The edited comment", body = CommentResponse),
+        (status = 403, description = "Not the author, suspended, banned, or the edit window has passed", body = ErrorBody),
+        (status = 404, description = "Comment not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn update_comment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, comment_id)): Path<(i64, i64)>,
+    Query(render): Query<CommentRenderQuery>,
+    ValidatedJson(input): ValidatedJson<UpdateComment>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    ensure_not_comment_banned(&pool, current_user.id).await?;
+
+    let comment =
+        sqlx::query_as::<_, Comment>("SELECT * FROM comments WHERE id = ? AND post_id = ?")
+            .bind(comment_id)
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .ok_or_else(|| AppError::not_found("Comment not found"))?;
+
+    if comment.author_id != current_user.id {
+        return Err(AppError::forbidden("Not authorized to edit this comment"));
+    }
+
+    if comment.is_deleted {
+        return Err(AppError::bad_request("Deleted comments cannot be edited"));
+    }
+
+    let edit_window_secs = comment_edit_window_secs();
+    if edit_window_secs > 0 && (Utc::now() - comment.created_at).num_seconds() > edit_window_secs {
+        return Err(AppError::forbidden("Comment edit window has expired"));
+    }
+
+    let content = input.content.trim();
+
+    let comment = if content == comment.content {
+        comment
+    } else {
+        let now = Utc::now();
+        let mut tx = pool.begin().await.map_err(AppError::internal)?;
+
+        sqlx::query(
+            "INSERT INTO comment_edit_history (comment_id, editor_id, previous_content, edited_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(comment.id)
+        .bind(current_user.id)
+        .bind(&comment.content)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+
+        sqlx::query("UPDATE comments SET content = ?, edited_at = ?, updated_at = ? WHERE id = ?")
+            .bind(content)
+            .bind(now)
+            .bind(now)
+            .bind(comment.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+
+        tx.commit().await.map_err(AppError::internal)?;
+
+        Comment {
+            content: content.to_string(),
+            edited_at: Some(now),
+            updated_at: Some(now),
+            ..comment
+        }
+    };
+
+    let depth = fetch_comment_ancestry(&pool, "comments", comment.id)
+        .await
+        .map_err(AppError::internal)?
+        .len()
+        .saturating_sub(1) as i32;
+
+    // Users already mentioned by the previous text are not notified again.
+    let mentions = record_mentions(
+        &pool,
+        MentionSource::Comment,
+        comment.id,
+        post_id,
+        (Some(current_user.id), &current_user.username),
+        &comment.content,
+        None,
+    )
+    .await;
+
+    Ok(Json(CommentResponse {
+        id: comment.id,
+        post_id: comment.post_id,
+        author_id: comment.author_id,
+        parent_comment_id: comment.parent_comment_id,
+        depth,
+        author: UserResponse::from(current_user),
+        content_html: wants_html(render.render.as_deref())
+            .then(|| render_markdown(&comment.content)),
+        content: comment.content,
+        is_deleted: comment.is_deleted,
+        deleted_at: comment.deleted_at,
+        edited_at: comment.edited_at,
+        created_at: comment.created_at,
+        updated_at: comment.updated_at,
+        mentions,
+    }))
+}
+
+/// Applies the per-user comment flood limits shared by post comments and
+/// review comments. Returns the 429 response to send when the user is over
+/// either limit. `COMMENT_RATE_LIMIT_PER_MINUTE` and `COMMENT_RATE_LIMIT_BURST`
+/// (hits per 10 seconds) can each be set to 0 to disable that window.
+pub fn check_comment_rate_limit(user_id: i64) -> Option<Response> {
+    static LIMITER: OnceLock<SlidingWindowLimiter> = OnceLock::new();
+    let limiter = LIMITER.get_or_init(|| {
+        SlidingWindowLimiter::new(vec![
+            SlidingWindow {
+                max_hits: comment_limit_from_env(
+                    "COMMENT_RATE_LIMIT_PER_MINUTE",
+                    DEFAULT_COMMENT_RATE_LIMIT_PER_MINUTE,
+                ),
+                window: Duration::from_secs(60),
+            },
+            SlidingWindow {
+                max_hits: comment_limit_from_env(
+                    "COMMENT_RATE_LIMIT_BURST",
+                    DEFAULT_COMMENT_RATE_LIMIT_BURST,
+                ),
+                window: Duration::from_secs(COMMENT_BURST_WINDOW_SECS),
+            },
+        ])
+    });
+
+    let wait = limiter.check(user_id).err()?;
+    let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+    Some(
+        (
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            AppError::too_many_requests(format!(
+                "Too many comments. Try again in {} seconds",
+                retry_after
+            ))
+            .with_field("retry_after", retry_after),
+        )
+            .into_response(),
+    )
+}
+
+fn comment_limit_from_env(key: &str, default: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|raw| raw.parse::<usize>().ok())
+        .unwrap_or(default)
+}
+
+/// Maximum reply nesting level. `COMMENT_MAX_DEPTH=0` removes the limit.
+fn comment_max_depth() -> i32 {
+    setting::<i32>("COMMENT_MAX_DEPTH")
+        .filter(|depth| *depth >= 0)
+        .unwrap_or(DEFAULT_COMMENT_MAX_DEPTH)
+}
+
+/// Returns `comment_id` followed by its ancestors, ending with the top-level
+/// comment. `table` is `comments` or `paper_review_comments`.
+pub async fn fetch_comment_ancestry(
+    pool: &MySqlPool,
+    table: &'static str,
+    comment_id: i64,
+) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar(&format!(
+        r#"
+        WITH RECURSIVE ancestry (id, parent_comment_id, steps) AS (
+            SELECT id, parent_comment_id, 0
+            FROM {table}
+            WHERE id = ?
+            UNION ALL
+            SELECT c.id, c.parent_comment_id, a.steps + 1
+            FROM {table} c
+            JOIN ancestry a ON c.id = a.parent_comment_id
+            WHERE a.steps < ?
+        )
+        SELECT id FROM ancestry ORDER BY steps ASC
+        "#
     ))
+    .bind(comment_id)
+    .bind(MAX_ANCESTRY_STEPS)
+    .fetch_all(pool)
+    .await
 }
 
+/// Picks the parent a new reply is stored under and the depth it ends up at.
+/// Replies that would nest deeper than `COMMENT_MAX_DEPTH` are attached to
+/// the ancestor at the deepest allowed level, so they appear as siblings of
+/// the comment they answered.
+pub async fn resolve_reply_parent(
+    pool: &MySqlPool,
+    table: &'static str,
+    requested_parent_id: Option<i64>,
+) -> Result<(Option<i64>, i32), sqlx::Error> {
+    let Some(parent_id) = requested_parent_id else {
+        return Ok((None, 0));
+    };
+
+    let ancestry = fetch_comment_ancestry(pool, table, parent_id).await?;
+    let parent_depth = ancestry.len().saturating_sub(1) as i32;
+    let max_depth = comment_max_depth();
+    if max_depth == 0 || parent_depth < max_depth {
+        return Ok((Some(parent_id), parent_depth + 1));
+    }
+
+    // `ancestry[i]` sits at depth `parent_depth - i`; pick depth `max_depth - 1`.
+    let index = (parent_depth - max_depth + 1) as usize;
+    Ok((Some(ancestry[index]), max_depth))
+}
+
+/// Computes nesting depths from `(id, parent_comment_id)` pairs. Comments
+/// whose parent is missing from `edges` count as top-level.
+pub fn compute_comment_depths(edges: &[(i64, Option<i64>)]) -> HashMap<i64, i32> {
+    let parents: HashMap<i64, Option<i64>> = edges.iter().copied().collect();
+    let mut depths: HashMap<i64, i32> = HashMap::with_capacity(edges.len());
+
+    for (comment_id, _) in edges {
+        let mut unresolved = Vec::new();
+        let mut current = Some(*comment_id);
+        let mut base_depth = 0;
+        while let Some(id) = current {
+            if let Some(depth) = depths.get(&id) {
+                base_depth = depth + 1;
+                break;
+            }
+            if !parents.contains_key(&id) || unresolved.len() > edges.len() {
+                break;
+            }
+            unresolved.push(id);
+            current = parents.get(&id).copied().flatten();
+        }
+
+        for (offset, id) in unresolved.into_iter().rev().enumerate() {
+            depths.insert(id, base_depth + offset as i32);
+        }
+    }
+
+    depths
+}
+
+/// Seconds after creation during which authors may edit a comment.
+/// `COMMENT_EDIT_WINDOW_SECS=0` removes the limit.
+fn comment_edit_window_secs() -> i64 {
+    setting::<i64>("COMMENT_EDIT_WINDOW_SECS")
+        .filter(|secs| *secs >= 0)
+        .unwrap_or(DEFAULT_COMMENT_EDIT_WINDOW_SECS)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{post_id}/comments/{comment_id}",
+    tag = "comments",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id"), ("comment_id" = i64, Path, description = "Comment id")),
+    responses(
+        (status = 200, description = "Comment deleted; `delete_mode` says whether it was blanked or removed"),
+        (status = 403, description = "Not the author", body = ErrorBody),
+        (status = 404, description = "Comment not found", body = ErrorBody),
+    )
+)]
 async fn delete_comment(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path((post_id, comment_id)): Path<(i64, i64)>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
 
     let comment = find_comment_target(&pool, comment_id, Some(post_id))
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Comment not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Comment not found"))?;
 
     if comment.author_id != current_user.id {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({"detail": "Not authorized to delete this comment"})),
-        ));
+        return Err(AppError::forbidden("Not authorized to delete this comment"));
     }
 
     let delete_mode = apply_comment_delete_policy(&pool, &comment)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     Ok(Json(serde_json::json!({
         "message": "Comment deleted successfully",
@@ -285,6 +842,209 @@ async fn delete_comment(
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/{post_id}/comments/{comment_id}/report",
+    tag = "comments",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id"), ("comment_id" = i64, Path, description = "Comment id")),
+    request_body = CreateCommentReport,
+    responses(
+        (status = 201, description = "Report filed; returns `report_id`"),
+        (status = 404, description = "Comment not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn report_comment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, comment_id)): Path<(i64, i64)>,
+    ValidatedJson(input): ValidatedJson<CreateCommentReport>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_post_visibility(&pool, post_id).await?;
+
+    let reason = input.reason.trim();
+
+    let comment =
+        sqlx::query_as::<_, Comment>("SELECT * FROM comments WHERE id = ? AND post_id = ?")
+            .bind(comment_id)
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?
+            .filter(|comment| !comment.is_deleted)
+            .ok_or_else(|| AppError::not_found("Comment not found"))?;
+
+    if comment.author_id == current_user.id {
+        return Err(AppError::bad_request("You cannot report your own comment"));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT IGNORE INTO comment_reports
+            (comment_id, post_id, comment_author_id, reporter_id, comment_excerpt, reason, status, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, 'open', ?)
+        "#,
+    )
+    .bind(comment.id)
+    .bind(post_id)
+    .bind(comment.author_id)
+    .bind(current_user.id)
+    .bind(comment.content.chars().take(REPORT_EXCERPT_CHARS).collect::<String>())
+    .bind(reason)
+    .bind(Utc::now())
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::conflict("You have already reported this comment"));
+    }
+    queue_webhook_message(
+        &pool,
+        WebhookMessage {
+            event: WEBHOOK_EVENT_CONTENT_REPORT,
+            title: format!("Comment {} reported", comment.id),
+            body: format!(
+                "{} reported a comment: {}\n> {}",
+                current_user.username,
+                reason,
+                comment
+                    .content
+                    .chars()
+                    .take(REPORT_EXCERPT_CHARS)
+                    .collect::<String>()
+            ),
+            post_id: Some(post_id),
+        },
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "message": "Comment reported",
+            "report_id": result.last_insert_id() as i64
+        })),
+    ))
+}
+
+async fn get_subscription(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_post_visibility(&pool, post_id).await?;
+
+    let (following_post, followed_comment_ids) =
+        fetch_subscriptions(&pool, current_user.id, post_id)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(SubscriptionStatusResponse {
+        post_id,
+        following_post,
+        followed_comment_ids,
+    }))
+}
+
+async fn subscribe_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_post_visibility(&pool, post_id).await?;
+    update_subscription(&pool, current_user.id, post_id, None, true).await
+}
+
+async fn unsubscribe_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    update_subscription(&pool, current_user.id, post_id, None, false).await
+}
+
+async fn subscribe_thread(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, comment_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_post_visibility(&pool, post_id).await?;
+    find_comment_target(&pool, comment_id, Some(post_id))
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Comment not found"))?;
+
+    update_subscription(&pool, current_user.id, post_id, Some(comment_id), true).await
+}
+
+async fn unsubscribe_thread(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, comment_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    update_subscription(&pool, current_user.id, post_id, Some(comment_id), false).await
+}
+
+async fn update_subscription(
+    pool: &MySqlPool,
+    user_id: i64,
+    post_id: i64,
+    comment_id: Option<i64>,
+    follow: bool,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let changed = if follow {
+        subscribe(pool, user_id, post_id, comment_id).await
+    } else {
+        unsubscribe(pool, user_id, post_id, comment_id).await
+    }
+    .map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({
+        "post_id": post_id,
+        "comment_id": comment_id,
+        "following": follow,
+        "changed": changed
+    })))
+}
+
+/// Rejects users a moderator has barred from commenting.
+pub async fn ensure_not_comment_banned(pool: &MySqlPool, user_id: i64) -> Result<(), AppError> {
+    let banned: Option<(i64,)> =
+        sqlx::query_as("SELECT user_id FROM comment_bans WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    if banned.is_some() {
+        return Err(AppError::forbidden("You have been banned from commenting"));
+    }
+
+    Ok(())
+}
+
+pub async fn is_blocked_by(
+    pool: &MySqlPool,
+    blocker_id: i64,
+    user_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let blocked: Option<(i64,)> = sqlx::query_as(
+        "SELECT blocker_id FROM user_blocks WHERE blocker_id = ? AND blocked_id = ?",
+    )
+    .bind(blocker_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(blocked.is_some())
+}
+
 pub async fn find_comment_target(
     pool: &MySqlPool,
     comment_id: i64,
@@ -301,10 +1061,10 @@ pub async fn find_comment_target(
         return Ok(None);
     };
 
-    if let Some(expected_post_id) = post_id_filter {
-        if target.post_id != expected_post_id {
-            return Ok(None);
-        }
+    if let Some(expected_post_id) = post_id_filter
+        && target.post_id != expected_post_id
+    {
+        return Ok(None);
     }
 
     Ok(Some(target))
@@ -379,33 +1139,19 @@ async fn prune_soft_deleted_ancestors(
     Ok(())
 }
 
-async fn ensure_post_visibility(
-    pool: &MySqlPool,
-    post_id: i64,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
-    let post_row = sqlx::query_as::<_, (bool,)>("SELECT is_published FROM posts WHERE id = ?")
-        .bind(post_id)
-        .fetch_optional(pool)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Post not found"})),
-            )
-        })?;
+pub async fn ensure_post_visibility(pool: &MySqlPool, post_id: i64) -> Result<(), AppError> {
+    let post_row = sqlx::query_as::<_, (bool,)>(
+        "SELECT is_published FROM posts WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
 
     let (is_published,) = post_row;
     if !is_published {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Post not found"})),
-        ));
+        return Err(AppError::not_found("Post not found"));
     }
 
     Ok(())
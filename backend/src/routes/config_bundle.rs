@@ -0,0 +1,553 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::ai_review::AI_REVIEW_PROMPT_VERSION;
+use crate::audit_log::{
+    AUDIT_ACTION_CATEGORY_CREATE, AUDIT_ACTION_CATEGORY_UPDATE, AUDIT_ACTION_FEATURE_FLAG_RESET,
+    AUDIT_ACTION_FEATURE_FLAG_UPDATE, AUDIT_ACTION_SETTING_RESET, AUDIT_ACTION_SETTING_UPDATE,
+    AUDIT_TARGET_CATEGORY, AUDIT_TARGET_FEATURE_FLAG, AUDIT_TARGET_SYSTEM_SETTING, AuditEvent,
+    RequestMetadata, record_audit_event,
+};
+use crate::error::AppError;
+use crate::feature_flags::{
+    FEATURE_ROLES, find_feature_flag, list_feature_flags, reload_feature_flags,
+};
+use crate::models::{
+    CONFIG_BUNDLE_FORMAT_VERSION, ConfigBundle, ConfigBundleCategory, ConfigBundleFeatureFlag,
+    ConfigBundleSetting, ConfigImportResult,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::categories::{validate_category_code, validate_display_name};
+use crate::routes::posts::PAPER_CATEGORY;
+use crate::settings::{
+    SETTING_SOURCE_DATABASE, find_setting, list_system_settings, reload_settings,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Admin configuration bundle routes, nested under `/api/admin`.
+pub fn config_bundle_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/config/export", get(export_config_bundle))
+        .route("/config/import", post(import_config_bundle))
+}
+
+async fn export_config_bundle(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let settings = list_system_settings(&pool)
+        .await
+        .map_err(AppError::internal)?
+        .into_iter()
+        .filter(|setting| setting.source == SETTING_SOURCE_DATABASE)
+        .filter_map(|setting| {
+            setting.value.map(|value| ConfigBundleSetting {
+                key: setting.key,
+                value,
+            })
+        })
+        .collect();
+    let feature_flags = list_feature_flags(&pool)
+        .await
+        .map_err(AppError::internal)?
+        .into_iter()
+        .filter(|flag| flag.id.is_some())
+        .map(|flag| ConfigBundleFeatureFlag {
+            key: flag.key,
+            enabled: flag.enabled,
+            rollout_percent: flag.rollout_percent,
+            roles: flag.roles,
+        })
+        .collect();
+    let categories = sqlx::query_as::<_, (String, String, bool)>(
+        "SELECT code, display_name, is_active FROM post_categories ORDER BY id ASC",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .into_iter()
+    .map(|(code, display_name, is_active)| ConfigBundleCategory {
+        code,
+        display_name,
+        is_active,
+    })
+    .collect();
+
+    Ok(Json(ConfigBundle {
+        format_version: CONFIG_BUNDLE_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        ai_review_prompt_version: AI_REVIEW_PROMPT_VERSION.to_string(),
+        settings,
+        feature_flags,
+        categories,
+    }))
+}
+
+/// Applies an exported bundle in one transaction. Settings and feature flag
+/// overrides are replaced as a whole; categories are created or updated by
+/// code, and categories missing from the bundle are left alone because
+/// posts may still use them. Nothing is written when `dry_run` is set.
+async fn import_config_bundle(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ConfigImportQuery>,
+    Json(bundle): Json<ConfigBundle>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    if bundle.format_version != CONFIG_BUNDLE_FORMAT_VERSION {
+        return Err(AppError::bad_request(format!(
+            "Unsupported bundle format_version {}; expected {}",
+            bundle.format_version, CONFIG_BUNDLE_FORMAT_VERSION
+        )));
+    }
+
+    let settings = validate_bundle_settings(&bundle.settings)?;
+    let feature_flags = validate_bundle_feature_flags(&bundle.feature_flags)?;
+    let categories = validate_bundle_categories(&bundle.categories)?;
+
+    let mut result = ConfigImportResult {
+        dry_run: query.dry_run,
+        ..Default::default()
+    };
+    if bundle.ai_review_prompt_version != AI_REVIEW_PROMPT_VERSION {
+        result.warnings.push(format!(
+            "Bundle was exported with AI review prompt {}; this server uses {}",
+            bundle.ai_review_prompt_version, AI_REVIEW_PROMPT_VERSION
+        ));
+    }
+
+    let current_settings = list_system_settings(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    let current_flags = list_feature_flags(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    let request = RequestMetadata::from_headers(&headers);
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+
+    for current in &current_settings {
+        let Some(setting_id) = current.id else {
+            continue;
+        };
+        if settings.iter().any(|setting| setting.key == current.key) {
+            continue;
+        }
+        result.settings_reset.push(current.key.clone());
+        if query.dry_run {
+            continue;
+        }
+        sqlx::query("DELETE FROM system_settings WHERE id = ?")
+            .bind(setting_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_SETTING_RESET,
+                target_type: AUDIT_TARGET_SYSTEM_SETTING,
+                target_id: setting_id,
+                request: &request,
+                before: Some(serde_json::json!({"key": current.key, "value": current.value})),
+                after: None,
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+    for setting in &settings {
+        let current = current_settings
+            .iter()
+            .find(|current| current.key == setting.key);
+        if current.is_some_and(|current| {
+            current.id.is_some() && current.value.as_deref() == Some(setting.value.as_str())
+        }) {
+            continue;
+        }
+        result.settings_updated.push(setting.key.clone());
+        if query.dry_run {
+            continue;
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO system_settings (setting_key, setting_value, updated_by, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                setting_value = VALUES(setting_value),
+                updated_by = VALUES(updated_by),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(&setting.key)
+        .bind(&setting.value)
+        .bind(admin.id)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+        let setting_id: i64 =
+            sqlx::query_scalar("SELECT id FROM system_settings WHERE setting_key = ?")
+                .bind(&setting.key)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::internal)?;
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_SETTING_UPDATE,
+                target_type: AUDIT_TARGET_SYSTEM_SETTING,
+                target_id: setting_id,
+                request: &request,
+                before: current.map(|current| {
+                    serde_json::json!({
+                        "key": current.key,
+                        "value": current.value,
+                        "source": current.source,
+                    })
+                }),
+                after: Some(serde_json::json!({"key": setting.key, "value": setting.value})),
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+
+    for current in &current_flags {
+        let Some(flag_id) = current.id else {
+            continue;
+        };
+        if feature_flags.iter().any(|flag| flag.key == current.key) {
+            continue;
+        }
+        result.feature_flags_reset.push(current.key.clone());
+        if query.dry_run {
+            continue;
+        }
+        sqlx::query("DELETE FROM feature_flags WHERE id = ?")
+            .bind(flag_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_FEATURE_FLAG_RESET,
+                target_type: AUDIT_TARGET_FEATURE_FLAG,
+                target_id: flag_id,
+                request: &request,
+                before: Some(serde_json::json!({
+                    "key": current.key,
+                    "enabled": current.enabled,
+                    "rollout_percent": current.rollout_percent,
+                    "roles": current.roles,
+                })),
+                after: None,
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+    for flag in &feature_flags {
+        let current = current_flags.iter().find(|current| current.key == flag.key);
+        if current.is_some_and(|current| {
+            current.id.is_some()
+                && current.enabled == flag.enabled
+                && current.rollout_percent == flag.rollout_percent
+                && current.roles.iter().collect::<HashSet<_>>()
+                    == flag.roles.iter().collect::<HashSet<_>>()
+        }) {
+            continue;
+        }
+        result.feature_flags_updated.push(flag.key.clone());
+        if query.dry_run {
+            continue;
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO feature_flags (flag_key, enabled, rollout_percent, updated_by, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                enabled = VALUES(enabled),
+                rollout_percent = VALUES(rollout_percent),
+                updated_by = VALUES(updated_by),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(&flag.key)
+        .bind(flag.enabled)
+        .bind(flag.rollout_percent)
+        .bind(admin.id)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+        let flag_id: i64 = sqlx::query_scalar("SELECT id FROM feature_flags WHERE flag_key = ?")
+            .bind(&flag.key)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        sqlx::query("DELETE FROM feature_flag_roles WHERE flag_id = ?")
+            .bind(flag_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        for role in &flag.roles {
+            sqlx::query("INSERT INTO feature_flag_roles (flag_id, role) VALUES (?, ?)")
+                .bind(flag_id)
+                .bind(role)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::internal)?;
+        }
+        record_audit_event(
+            &mut *tx,
+            AuditEvent {
+                actor: &admin,
+                action: AUDIT_ACTION_FEATURE_FLAG_UPDATE,
+                target_type: AUDIT_TARGET_FEATURE_FLAG,
+                target_id: flag_id,
+                request: &request,
+                before: current.map(|current| {
+                    serde_json::json!({
+                        "key": current.key,
+                        "enabled": current.enabled,
+                        "rollout_percent": current.rollout_percent,
+                        "roles": current.roles,
+                    })
+                }),
+                after: Some(serde_json::json!({
+                    "key": flag.key,
+                    "enabled": flag.enabled,
+                    "rollout_percent": flag.rollout_percent,
+                    "roles": flag.roles,
+                })),
+            },
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+
+    let current_categories: HashMap<String, (i64, String, bool)> = sqlx::query_as::<
+        _,
+        (i64, String, String, bool),
+    >(
+        "SELECT CAST(id AS SIGNED), code, display_name, is_active FROM post_categories FOR UPDATE",
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(AppError::internal)?
+    .into_iter()
+    .map(|(id, code, display_name, is_active)| (code, (id, display_name, is_active)))
+    .collect();
+    for category in &categories {
+        match current_categories.get(&category.code) {
+            Some((category_id, display_name, is_active)) => {
+                if *display_name == category.display_name && *is_active == category.is_active {
+                    continue;
+                }
+                result.categories_updated.push(category.code.clone());
+                if query.dry_run {
+                    continue;
+                }
+                sqlx::query(
+                    "UPDATE post_categories SET display_name = ?, is_active = ? WHERE id = ?",
+                )
+                .bind(&category.display_name)
+                .bind(category.is_active)
+                .bind(category_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::internal)?;
+                record_audit_event(
+                    &mut *tx,
+                    AuditEvent {
+                        actor: &admin,
+                        action: AUDIT_ACTION_CATEGORY_UPDATE,
+                        target_type: AUDIT_TARGET_CATEGORY,
+                        target_id: *category_id,
+                        request: &request,
+                        before: Some(serde_json::json!({
+                            "code": category.code,
+                            "display_name": display_name,
+                            "is_active": is_active,
+                        })),
+                        after: Some(serde_json::json!(category)),
+                    },
+                )
+                .await
+                .map_err(AppError::internal)?;
+            }
+            None => {
+                result.categories_created.push(category.code.clone());
+                if query.dry_run {
+                    continue;
+                }
+                let inserted = sqlx::query(
+                    "INSERT INTO post_categories (code, display_name, is_active) VALUES (?, ?, ?)",
+                )
+                .bind(&category.code)
+                .bind(&category.display_name)
+                .bind(category.is_active)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::internal)?;
+                record_audit_event(
+                    &mut *tx,
+                    AuditEvent {
+                        actor: &admin,
+                        action: AUDIT_ACTION_CATEGORY_CREATE,
+                        target_type: AUDIT_TARGET_CATEGORY,
+                        target_id: inserted.last_insert_id() as i64,
+                        request: &request,
+                        before: None,
+                        after: Some(serde_json::json!(category)),
+                    },
+                )
+                .await
+                .map_err(AppError::internal)?;
+            }
+        }
+    }
+    let mut untouched: Vec<&str> = current_categories
+        .keys()
+        .filter(|code| !categories.iter().any(|category| &category.code == *code))
+        .map(String::as_str)
+        .collect();
+    if !untouched.is_empty() {
+        untouched.sort_unstable();
+        result.warnings.push(format!(
+            "Categories not in the bundle were left unchanged: {}",
+            untouched.join(", ")
+        ));
+    }
+
+    if query.dry_run {
+        tx.rollback().await.map_err(AppError::internal)?;
+        return Ok(Json(result));
+    }
+    tx.commit().await.map_err(AppError::internal)?;
+
+    reload_settings(&pool).await.map_err(AppError::internal)?;
+    reload_feature_flags(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(result))
+}
+
+/// Normalizes the bundle's setting overrides, rejecting unknown keys,
+/// duplicates and values the setting would not accept.
+fn validate_bundle_settings(
+    settings: &[ConfigBundleSetting],
+) -> Result<Vec<ConfigBundleSetting>, AppError> {
+    let mut validated: Vec<ConfigBundleSetting> = Vec::with_capacity(settings.len());
+    for setting in settings {
+        let definition = find_setting(setting.key.trim())
+            .ok_or_else(|| AppError::bad_request(format!("Unknown setting {}", setting.key)))?;
+        if validated.iter().any(|seen| seen.key == definition.key) {
+            return Err(AppError::bad_request(format!(
+                "Setting {} appears more than once",
+                definition.key
+            )));
+        }
+        let value = definition
+            .validate(&setting.value)
+            .map_err(|message| AppError::bad_request(format!("{}: {}", definition.key, message)))?;
+        validated.push(ConfigBundleSetting {
+            key: definition.key.to_string(),
+            value,
+        });
+    }
+    Ok(validated)
+}
+
+fn validate_bundle_feature_flags(
+    flags: &[ConfigBundleFeatureFlag],
+) -> Result<Vec<ConfigBundleFeatureFlag>, AppError> {
+    let mut validated: Vec<ConfigBundleFeatureFlag> = Vec::with_capacity(flags.len());
+    for flag in flags {
+        let definition = find_feature_flag(flag.key.trim())
+            .ok_or_else(|| AppError::bad_request(format!("Unknown feature flag {}", flag.key)))?;
+        if validated.iter().any(|seen| seen.key == definition.key) {
+            return Err(AppError::bad_request(format!(
+                "Feature flag {} appears more than once",
+                definition.key
+            )));
+        }
+        if !(0..=100).contains(&flag.rollout_percent) {
+            return Err(AppError::bad_request(format!(
+                "{}: rollout_percent must be between 0 and 100",
+                definition.key
+            )));
+        }
+        let mut roles: Vec<String> = Vec::new();
+        for role in &flag.roles {
+            let role = role.trim().to_ascii_lowercase();
+            if !FEATURE_ROLES.contains(&role.as_str()) {
+                return Err(AppError::bad_request(format!(
+                    "{}: roles must be any of: {}",
+                    definition.key,
+                    FEATURE_ROLES.join(", ")
+                )));
+            }
+            if !roles.contains(&role) {
+                roles.push(role);
+            }
+        }
+        validated.push(ConfigBundleFeatureFlag {
+            key: definition.key.to_string(),
+            enabled: flag.enabled,
+            rollout_percent: flag.rollout_percent,
+            roles,
+        });
+    }
+    Ok(validated)
+}
+
+fn validate_bundle_categories(
+    categories: &[ConfigBundleCategory],
+) -> Result<Vec<ConfigBundleCategory>, AppError> {
+    let mut validated: Vec<ConfigBundleCategory> = Vec::with_capacity(categories.len());
+    for category in categories {
+        let code = validate_category_code(&category.code)?;
+        if validated.iter().any(|seen| seen.code == code) {
+            return Err(AppError::bad_request(format!(
+                "Category {} appears more than once",
+                code
+            )));
+        }
+        if code == PAPER_CATEGORY && !category.is_active {
+            return Err(AppError::bad_request(
+                "The paper category cannot be disabled".to_string(),
+            ));
+        }
+        validated.push(ConfigBundleCategory {
+            display_name: validate_display_name(&category.display_name)?,
+            code,
+            is_active: category.is_active,
+        });
+    }
+    Ok(validated)
+}
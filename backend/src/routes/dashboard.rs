@@ -0,0 +1,177 @@
+use axum::{Json, Router, extract::State, http::HeaderMap, response::IntoResponse, routing::get};
+use sqlx::MySqlPool;
+
+use crate::error::AppError;
+use crate::models::{
+    DashboardComment, DashboardDraft, DashboardPendingActions, DashboardReviewAssignment,
+    DashboardRevision, DashboardStatusCount, DashboardTotals, UserDashboardResponse,
+};
+use crate::routes::auth::extract_current_user;
+
+const DASHBOARD_DRAFT_LIMIT: i64 = 20;
+const DASHBOARD_COMMENT_LIMIT: i64 = 10;
+const COMMENT_PREVIEW_CHARS: usize = 280;
+
+/// The caller's own overview, nested under `/api/users`.
+pub fn dashboard_routes() -> Router<MySqlPool> {
+    Router::new().route("/me/dashboard", get(get_dashboard))
+}
+
+/// Drafts, submissions, comments received, totals and pending actions of the
+/// caller in one response.
+async fn get_dashboard(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let user_id = current_user.id;
+
+    // Unpublished posts outside the paper workflow are drafts too.
+    let drafts = sqlx::query_as::<_, DashboardDraft>(
+        r#"
+        SELECT p.id, p.title, c.code AS category, p.paper_status, p.created_at, p.updated_at
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.author_id = ?
+          AND p.deleted_at IS NULL
+          AND p.is_published = FALSE
+          AND (p.paper_status = 'draft' OR c.code <> 'paper')
+        ORDER BY COALESCE(p.updated_at, p.created_at) DESC, p.id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(DASHBOARD_DRAFT_LIMIT)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let submissions_by_status = sqlx::query_as::<_, DashboardStatusCount>(
+        r#"
+        SELECT p.paper_status, COUNT(*) AS count
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.author_id = ? AND p.deleted_at IS NULL AND c.code = 'paper'
+        GROUP BY p.paper_status
+        ORDER BY p.paper_status ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let recent_comments = sqlx::query_as::<_, DashboardComment>(
+        r#"
+        SELECT
+            cm.id AS comment_id,
+            p.id AS post_id,
+            p.title AS post_title,
+            u.username AS author_username,
+            cm.content,
+            cm.created_at
+        FROM comments cm
+        JOIN posts p ON p.id = cm.post_id
+        JOIN users u ON u.id = cm.author_id
+        WHERE p.author_id = ?
+          AND cm.author_id <> ?
+          AND cm.is_deleted = FALSE
+          AND p.deleted_at IS NULL
+        ORDER BY cm.created_at DESC, cm.id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .bind(DASHBOARD_COMMENT_LIMIT)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .into_iter()
+    .map(|mut comment| {
+        comment.content = preview(&comment.content);
+        comment
+    })
+    .collect();
+
+    let totals = sqlx::query_as::<_, DashboardTotals>(
+        r#"
+        SELECT
+            COUNT(*) AS post_count,
+            CAST(COALESCE(SUM(ps.view_count), 0) AS SIGNED) AS view_count,
+            CAST(COALESCE(SUM(ps.like_count), 0) AS SIGNED) AS like_count,
+            CAST(COALESCE(SUM(ps.citation_count), 0) AS SIGNED) AS citation_count,
+            CAST(COALESCE(SUM(ps.external_citation_count), 0) AS SIGNED) AS external_citation_count
+        FROM posts p
+        LEFT JOIN post_stats ps ON ps.post_id = p.id
+        WHERE p.author_id = ? AND p.deleted_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let revisions_due = sqlx::query_as::<_, DashboardRevision>(
+        r#"
+        SELECT
+            p.id AS post_id,
+            p.title,
+            (
+                SELECT ed.revision_due_at
+                FROM editorial_decisions ed
+                WHERE ed.post_id = p.id
+                ORDER BY ed.id DESC
+                LIMIT 1
+            ) AS revision_due_at
+        FROM posts p
+        WHERE p.author_id = ? AND p.paper_status = 'revision' AND p.deleted_at IS NULL
+        ORDER BY revision_due_at IS NULL, revision_due_at ASC, p.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let reviews_assigned = sqlx::query_as::<_, DashboardReviewAssignment>(
+        r#"
+        SELECT
+            ra.id AS assignment_id,
+            p.id AS post_id,
+            p.title,
+            ra.status,
+            ra.due_at
+        FROM reviewer_assignments ra
+        JOIN posts p ON p.id = ra.post_id
+        WHERE ra.reviewer_id = ?
+          AND ra.status IN ('invited', 'accepted')
+          AND p.deleted_at IS NULL
+        ORDER BY ra.due_at IS NULL, ra.due_at ASC, ra.id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(UserDashboardResponse {
+        drafts,
+        submissions_by_status,
+        recent_comments,
+        totals,
+        pending_actions: DashboardPendingActions {
+            revisions_due,
+            reviews_assigned,
+        },
+    }))
+}
+
+fn preview(content: &str) -> String {
+    if content.chars().count() <= COMMENT_PREVIEW_CHARS {
+        return content.to_string();
+    }
+    let mut truncated: String = content.chars().take(COMMENT_PREVIEW_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, MySql, MySqlPool, QueryBuilder};
+
+use crate::ai_review::{decision_code, map_decision_code, paper_status_for_decision};
+use crate::audit_log::{
+    AUDIT_ACTION_EDITORIAL_DECISION, AUDIT_TARGET_POST, AuditEvent, RequestMetadata,
+    record_audit_event,
+};
+use crate::cache::invalidate_cached_post;
+use crate::error::AppError;
+use crate::models::{
+    CreateEditorialDecision, EditorialDecisionListResponse, EditorialDecisionResponse,
+    PAPER_STATUS_ACCEPTED, PAPER_STATUS_REJECTED, PAPER_STATUS_REVISION, PAPER_STATUS_SUBMITTED,
+    REVIEWER_ASSIGNMENT_SUBMITTED,
+};
+use crate::notifications::{
+    NOTIFICATION_EDITORIAL_DECISION, NewNotification, dispatch_notifications,
+};
+use crate::paper_status::{STATUS_TRIGGER_EDITORIAL_DECISION, record_status_transition};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::auth::extract_current_user;
+
+const MAX_NOTE_CHARS: usize = 10_000;
+
+/// Statuses an editor can decide on. Drafts have not been submitted yet and
+/// published papers are out of the review workflow.
+const DECIDABLE_STATUSES: &[&str] = &[
+    PAPER_STATUS_SUBMITTED,
+    PAPER_STATUS_REVISION,
+    PAPER_STATUS_ACCEPTED,
+    PAPER_STATUS_REJECTED,
+];
+
+#[derive(Debug, FromRow)]
+struct DecisionPostRow {
+    author_id: i64,
+    title: String,
+    category_code: String,
+    paper_status: String,
+    latest_paper_version_id: Option<i64>,
+}
+
+#[derive(Debug, FromRow)]
+struct EditorialDecisionRow {
+    id: i64,
+    post_id: i64,
+    paper_version_id: Option<i64>,
+    version_number: Option<i32>,
+    editor_id: Option<i64>,
+    editor_username: Option<String>,
+    decision: String,
+    ai_review_id: Option<i64>,
+    previous_status: String,
+    new_status: String,
+    note: Option<String>,
+    revision_due_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+const DECISION_SELECT: &str = r#"
+    SELECT
+        ed.id AS id,
+        ed.post_id AS post_id,
+        ed.paper_version_id AS paper_version_id,
+        CAST(pv.version_number AS SIGNED) AS version_number,
+        ed.editor_id AS editor_id,
+        u.username AS editor_username,
+        d.code AS decision,
+        ed.ai_review_id AS ai_review_id,
+        ed.previous_status AS previous_status,
+        ed.new_status AS new_status,
+        ed.note AS note,
+        ed.revision_due_at AS revision_due_at,
+        ed.created_at AS created_at
+    FROM editorial_decisions ed
+    JOIN ai_review_decisions d ON d.id = ed.decision_id
+    LEFT JOIN paper_versions pv ON pv.id = ed.paper_version_id
+    LEFT JOIN users u ON u.id = ed.editor_id
+"#;
+
+pub fn editorial_decision_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/{post_id}/decision", post(create_decision))
+        .route("/{post_id}/decisions", get(list_decisions))
+}
+
+/// Records an editor's decision on the current paper version and moves
+/// `paper_status` accordingly. The decision links the AI review and the
+/// submitted human reviews that informed it.
+async fn create_decision(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Json(input): Json<CreateEditorialDecision>,
+) -> Result<impl IntoResponse, AppError> {
+    let editor = extract_admin_user(&pool, &headers).await?;
+    let post = fetch_decision_post(&pool, post_id).await?;
+    if !DECIDABLE_STATUSES.contains(&post.paper_status.as_str()) {
+        return Err(
+            AppError::conflict("Paper is not awaiting an editorial decision")
+                .with_field("paper_status", post.paper_status),
+        );
+    }
+
+    let note = input
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    if note.is_some_and(|note| note.chars().count() > MAX_NOTE_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "Decision note must be at most {} characters",
+            MAX_NOTE_CHARS
+        )));
+    }
+
+    let new_status = paper_status_for_decision(input.decision);
+    let now = Utc::now();
+    if let Some(revision_due_at) = input.revision_due_at {
+        if new_status != PAPER_STATUS_REVISION {
+            return Err(AppError::bad_request(
+                "A revision due date can only be set when requesting a revision",
+            ));
+        }
+        if revision_due_at <= now {
+            return Err(AppError::bad_request(
+                "Revision due date must be in the future",
+            ));
+        }
+    }
+
+    let ai_review_id = resolve_ai_review(
+        &pool,
+        post_id,
+        post.latest_paper_version_id,
+        input.ai_review_id,
+    )
+    .await?;
+    let assignment_ids =
+        resolve_reviewer_assignments(&pool, post_id, input.reviewer_assignment_ids).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let insert = sqlx::query(
+        r#"
+        INSERT INTO editorial_decisions
+            (post_id, paper_version_id, editor_id, decision_id, ai_review_id,
+             previous_status, new_status, note, revision_due_at, created_at)
+        VALUES (?, ?, ?, (SELECT id FROM ai_review_decisions WHERE code = ?), ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(post_id)
+    .bind(post.latest_paper_version_id)
+    .bind(editor.id)
+    .bind(decision_code(input.decision))
+    .bind(ai_review_id)
+    .bind(&post.paper_status)
+    .bind(new_status)
+    .bind(note)
+    .bind(input.revision_due_at)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let decision_id = insert.last_insert_id() as i64;
+
+    if !assignment_ids.is_empty() {
+        let mut query_builder = QueryBuilder::<MySql>::new(
+            "INSERT INTO editorial_decision_reviews (decision_id, assignment_id) ",
+        );
+        query_builder.push_values(&assignment_ids, |mut row, assignment_id| {
+            row.push_bind(decision_id).push_bind(*assignment_id);
+        });
+        query_builder
+            .build()
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE posts
+        SET
+            paper_status = ?,
+            is_published = is_preprint AND published_at IS NOT NULL,
+            published_at = CASE WHEN is_preprint THEN published_at END,
+            updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(new_status)
+    .bind(now)
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    record_status_transition(
+        &mut *tx,
+        post_id,
+        Some(editor.id),
+        STATUS_TRIGGER_EDITORIAL_DECISION,
+        Some(&post.paper_status),
+        new_status,
+    )
+    .await
+    .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &editor,
+            action: AUDIT_ACTION_EDITORIAL_DECISION,
+            target_type: AUDIT_TARGET_POST,
+            target_id: post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({"paper_status": post.paper_status})),
+            after: Some(serde_json::json!({
+                "paper_status": new_status,
+                "decision_id": decision_id,
+                "decision": decision_code(input.decision),
+                "ai_review_id": ai_review_id,
+                "note": note,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    let decision = fetch_decisions(&pool, post_id, Some(decision_id))
+        .await
+        .map_err(AppError::internal)?
+        .pop()
+        .ok_or_else(|| AppError::internal("Editorial decision was not stored"))?;
+
+    let mut message = format!(
+        "Editorial decision on \"{}\": {}",
+        post.title,
+        decision_code(input.decision)
+    );
+    if let Some(revision_due_at) = input.revision_due_at {
+        message.push_str(&format!(
+            " (revision due {})",
+            revision_due_at.format("%Y-%m-%d")
+        ));
+    }
+    if let Some(note) = note {
+        message.push_str(&format!(". {}", note));
+    }
+    dispatch_notifications(
+        &pool,
+        vec![NewNotification {
+            user_id: post.author_id,
+            actor_id: Some(editor.id),
+            event_type: NOTIFICATION_EDITORIAL_DECISION,
+            post_id: Some(post_id),
+            comment_id: None,
+            message,
+        }],
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(decision)))
+}
+
+async fn list_decisions(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let post = fetch_decision_post(&pool, post_id).await?;
+    if current_user.id != post.author_id && !current_user.is_admin {
+        return Err(AppError::forbidden(
+            "Not authorized to view decisions for this paper",
+        ));
+    }
+
+    let decisions = fetch_decisions(&pool, post_id, None)
+        .await
+        .map_err(AppError::internal)?;
+    let total = decisions.len() as i64;
+
+    Ok(Json(EditorialDecisionListResponse { decisions, total }))
+}
+
+async fn fetch_decision_post(pool: &MySqlPool, post_id: i64) -> Result<DecisionPostRow, AppError> {
+    let post = sqlx::query_as::<_, DecisionPostRow>(
+        r#"
+        SELECT
+            p.author_id AS author_id,
+            p.title AS title,
+            c.code AS category_code,
+            p.paper_status AS paper_status,
+            p.latest_paper_version_id AS latest_paper_version_id
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.id = ? AND p.deleted_at IS NULL
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if post.category_code != "paper" {
+        return Err(AppError::bad_request(
+            "Editorial decisions are only available for paper posts",
+        ));
+    }
+
+    Ok(post)
+}
+
+/// Checks an explicitly referenced AI review, or falls back to the latest
+/// completed review of the current version when none is given.
+async fn resolve_ai_review(
+    pool: &MySqlPool,
+    post_id: i64,
+    latest_paper_version_id: Option<i64>,
+    requested_review_id: Option<i64>,
+) -> Result<Option<i64>, AppError> {
+    let Some(review_id) = requested_review_id else {
+        return sqlx::query_scalar(
+            r#"
+            SELECT r.id
+            FROM post_ai_reviews r
+            JOIN ai_review_statuses s ON s.id = r.status_id
+            WHERE r.post_id = ? AND r.paper_version_id <=> ? AND s.code = 'completed'
+            ORDER BY r.completed_at DESC, r.id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(post_id)
+        .bind(latest_paper_version_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal);
+    };
+
+    let status: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT s.code
+        FROM post_ai_reviews r
+        JOIN ai_review_statuses s ON s.id = r.status_id
+        WHERE r.id = ? AND r.post_id = ?
+        "#,
+    )
+    .bind(review_id)
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    match status.as_deref() {
+        Some("completed") => Ok(Some(review_id)),
+        Some(_) => Err(AppError::bad_request(
+            "Referenced AI review has not completed",
+        )),
+        None => Err(AppError::not_found("AI review not found for this paper")),
+    }
+}
+
+/// Only submitted reviews can inform a decision. Without an explicit list,
+/// every submitted assignment on the paper is linked.
+async fn resolve_reviewer_assignments(
+    pool: &MySqlPool,
+    post_id: i64,
+    requested_ids: Option<Vec<i64>>,
+) -> Result<Vec<i64>, AppError> {
+    let submitted: Vec<i64> = sqlx::query_scalar(
+        "SELECT id FROM reviewer_assignments WHERE post_id = ? AND status = ? ORDER BY id ASC",
+    )
+    .bind(post_id)
+    .bind(REVIEWER_ASSIGNMENT_SUBMITTED)
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let Some(mut requested_ids) = requested_ids else {
+        return Ok(submitted);
+    };
+    requested_ids.sort_unstable();
+    requested_ids.dedup();
+    if let Some(unknown) = requested_ids.iter().find(|id| !submitted.contains(id)) {
+        return Err(AppError::bad_request(format!(
+            "Reviewer assignment {} is not a submitted review of this paper",
+            unknown
+        )));
+    }
+
+    Ok(requested_ids)
+}
+
+async fn fetch_decisions(
+    pool: &MySqlPool,
+    post_id: i64,
+    decision_id: Option<i64>,
+) -> Result<Vec<EditorialDecisionResponse>, sqlx::Error> {
+    let mut query_builder = QueryBuilder::<MySql>::new(DECISION_SELECT);
+    query_builder
+        .push(" WHERE ed.post_id = ")
+        .push_bind(post_id);
+    if let Some(decision_id) = decision_id {
+        query_builder.push(" AND ed.id = ").push_bind(decision_id);
+    }
+    query_builder.push(" ORDER BY ed.created_at DESC, ed.id DESC");
+    let rows = query_builder
+        .build_query_as::<EditorialDecisionRow>()
+        .fetch_all(pool)
+        .await?;
+
+    let links: Vec<(i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT edr.decision_id, edr.assignment_id
+        FROM editorial_decision_reviews edr
+        JOIN editorial_decisions ed ON ed.id = edr.decision_id
+        WHERE ed.post_id = ?
+        ORDER BY edr.assignment_id ASC
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+    let mut assignments_by_decision: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (linked_decision_id, assignment_id) in links {
+        assignments_by_decision
+            .entry(linked_decision_id)
+            .or_default()
+            .push(assignment_id);
+    }
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(EditorialDecisionResponse {
+                decision: map_decision_code(&row.decision)?,
+                reviewer_assignment_ids: assignments_by_decision
+                    .remove(&row.id)
+                    .unwrap_or_default(),
+                id: row.id,
+                post_id: row.post_id,
+                paper_version_id: row.paper_version_id,
+                version_number: row.version_number,
+                editor_id: row.editor_id,
+                editor_username: row.editor_username,
+                ai_review_id: row.ai_review_id,
+                previous_status: row.previous_status,
+                paper_status: row.new_status,
+                note: row.note,
+                revision_due_at: row.revision_due_at,
+                created_at: row.created_at,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,302 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, put},
+};
+use chrono::Utc;
+use sqlx::{FromRow, MySqlPool};
+
+use crate::cache::invalidate_cached_post;
+use crate::error::AppError;
+use crate::models::{
+    CreateIssue, IssueListResponse, IssueResponse, PAPER_STATUS_ACCEPTED, PAPER_STATUS_PUBLISHED,
+    PostQuery, UpdateIssue,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::posts::{BIBTEX_CONTENT_TYPE, export_posts_bibtex, list_posts};
+
+const MAX_ISSUE_TITLE_CHARS: usize = 255;
+
+const ISSUE_SELECT: &str = r#"
+    SELECT
+        i.id AS id,
+        CAST(i.volume AS SIGNED) AS volume,
+        CAST(i.number AS SIGNED) AS number,
+        i.title AS title,
+        i.publication_date AS publication_date,
+        (
+            SELECT COUNT(*)
+            FROM posts p
+            WHERE p.issue_id = i.id AND p.is_published = TRUE AND p.deleted_at IS NULL
+        ) AS paper_count,
+        i.created_at AS created_at,
+        i.updated_at AS updated_at
+    FROM issues i
+"#;
+
+#[derive(Debug, FromRow)]
+struct IssuePostRow {
+    category_code: String,
+    paper_status: String,
+    issue_id: Option<i64>,
+}
+
+pub fn issues_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/", get(list_issues).post(create_issue))
+        .route("/{issue_id}", get(get_issue).patch(update_issue))
+        .route("/{issue_id}/posts", get(list_issue_posts))
+        .route("/{issue_id}/export.bib", get(export_issue_bibtex))
+        .route(
+            "/{issue_id}/posts/{post_id}",
+            put(assign_post_to_issue).delete(remove_post_from_issue),
+        )
+}
+
+async fn list_issues(State(pool): State<MySqlPool>) -> Result<impl IntoResponse, AppError> {
+    let query = format!("{} ORDER BY i.volume DESC, i.number DESC", ISSUE_SELECT);
+    let issues = sqlx::query_as::<_, IssueResponse>(&query)
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(IssueListResponse {
+        total: issues.len() as i64,
+        issues,
+    }))
+}
+
+async fn get_issue(
+    State(pool): State<MySqlPool>,
+    Path(issue_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(Json(fetch_issue(&pool, issue_id).await?))
+}
+
+async fn create_issue(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateIssue>,
+) -> Result<impl IntoResponse, AppError> {
+    let editor = extract_admin_user(&pool, &headers).await?;
+
+    if input.volume < 1 || input.number < 1 {
+        return Err(AppError::bad_request("volume and number must be positive"));
+    }
+    let title = normalize_issue_title(input.title.as_deref())?;
+
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO issues (volume, number, title, publication_date, created_by, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(input.volume)
+    .bind(input.number)
+    .bind(title)
+    .bind(input.publication_date)
+    .bind(editor.id)
+    .bind(Utc::now())
+    .execute(&pool)
+    .await;
+
+    let issue_id = match inserted {
+        Ok(result) => result.last_insert_id() as i64,
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            return Err(AppError::conflict(format!(
+                "Volume {} issue {} already exists",
+                input.volume, input.number
+            )));
+        }
+        Err(error) => return Err(AppError::internal(error)),
+    };
+
+    let issue = fetch_issue(&pool, issue_id).await?;
+    Ok((StatusCode::CREATED, Json(issue)))
+}
+
+async fn update_issue(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(issue_id): Path<i64>,
+    Json(input): Json<UpdateIssue>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let current = fetch_issue(&pool, issue_id).await?;
+
+    let title = match input.title.as_deref() {
+        Some(raw) => normalize_issue_title(Some(raw))?,
+        None => current.title,
+    };
+    let publication_date = input.publication_date.or(current.publication_date);
+
+    sqlx::query("UPDATE issues SET title = ?, publication_date = ?, updated_at = ? WHERE id = ?")
+        .bind(title)
+        .bind(publication_date)
+        .bind(Utc::now())
+        .bind(issue_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(fetch_issue(&pool, issue_id).await?))
+}
+
+/// Published papers of the issue, with the same filters and paging as
+/// `GET /api/posts`.
+async fn list_issue_posts(
+    State(pool): State<MySqlPool>,
+    Path(issue_id): Path<i64>,
+    Query(mut query): Query<PostQuery>,
+) -> Result<Response, AppError> {
+    fetch_issue(&pool, issue_id).await?;
+    query.issue_id = Some(issue_id);
+
+    Ok(list_posts(State(pool), Query(query)).await?.into_response())
+}
+
+/// BibTeX of every public paper in the issue, in publication order.
+async fn export_issue_bibtex(
+    State(pool): State<MySqlPool>,
+    Path(issue_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let issue = fetch_issue(&pool, issue_id).await?;
+    let post_ids: Vec<i64> = sqlx::query_scalar(
+        r#"
+        SELECT p.id
+        FROM posts p
+        WHERE p.issue_id = ? AND p.is_published = TRUE AND p.deleted_at IS NULL
+        ORDER BY COALESCE(p.published_at, p.created_at) ASC, p.id ASC
+        "#,
+    )
+    .bind(issue_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let bibtex = export_posts_bibtex(&pool, &post_ids)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, BIBTEX_CONTENT_TYPE.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"thought-manifold-vol{}-no{}.bib\"",
+                    issue.volume, issue.number
+                ),
+            ),
+        ],
+        bibtex,
+    ))
+}
+
+/// Places an accepted or published paper in the issue, moving it out of any
+/// issue it was in before.
+async fn assign_post_to_issue(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((issue_id, post_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let issue = fetch_issue(&pool, issue_id).await?;
+    let post = fetch_issue_post(&pool, post_id).await?;
+
+    if post.category_code != "paper" {
+        return Err(AppError::bad_request(
+            "Only paper posts can be assigned to an issue",
+        ));
+    }
+
+    if post.paper_status != PAPER_STATUS_ACCEPTED && post.paper_status != PAPER_STATUS_PUBLISHED {
+        return Err(AppError::conflict(
+            "Only accepted or published papers can be assigned to an issue",
+        )
+        .with_field("paper_status", post.paper_status));
+    }
+
+    sqlx::query("UPDATE posts SET issue_id = ? WHERE id = ?")
+        .bind(issue_id)
+        .bind(post_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(serde_json::json!({
+        "detail": "Paper assigned to issue",
+        "post_id": post_id,
+        "issue_id": issue_id,
+        "previous_issue_id": post.issue_id,
+        "volume": issue.volume,
+        "number": issue.number
+    })))
+}
+
+async fn remove_post_from_issue(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((issue_id, post_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let post = fetch_issue_post(&pool, post_id).await?;
+
+    if post.issue_id != Some(issue_id) {
+        return Err(AppError::not_found("Paper is not assigned to this issue"));
+    }
+
+    sqlx::query("UPDATE posts SET issue_id = NULL WHERE id = ? AND issue_id = ?")
+        .bind(post_id)
+        .bind(issue_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(serde_json::json!({
+        "detail": "Paper removed from issue",
+        "post_id": post_id,
+        "issue_id": issue_id
+    })))
+}
+
+async fn fetch_issue(pool: &MySqlPool, issue_id: i64) -> Result<IssueResponse, AppError> {
+    let query = format!("{} WHERE i.id = ?", ISSUE_SELECT);
+    sqlx::query_as::<_, IssueResponse>(&query)
+        .bind(issue_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Issue not found"))
+}
+
+async fn fetch_issue_post(pool: &MySqlPool, post_id: i64) -> Result<IssuePostRow, AppError> {
+    sqlx::query_as::<_, IssuePostRow>(
+        r#"
+        SELECT c.code AS category_code, p.paper_status AS paper_status, p.issue_id AS issue_id
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        WHERE p.id = ? AND p.deleted_at IS NULL
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))
+}
+
+fn normalize_issue_title(raw: Option<&str>) -> Result<Option<String>, AppError> {
+    let title = raw.map(str::trim).filter(|title| !title.is_empty());
+    if title.is_some_and(|title| title.chars().count() > MAX_ISSUE_TITLE_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "Issue title must be at most {} characters",
+            MAX_ISSUE_TITLE_CHARS
+        )));
+    }
+
+    Ok(title.map(ToOwned::to_owned))
+}
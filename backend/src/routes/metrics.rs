@@ -1,47 +1,247 @@
 use axum::{
     Json, Router,
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::IntoResponse,
     routing::get,
 };
-use chrono::{Datelike, Utc};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
 use serde::Deserialize;
 use sqlx::MySqlPool;
 
-use crate::metrics::compute_impact_factor;
+use crate::cache::{CacheKey, fetch_cached, store_cached};
+use crate::error::AppError;
+use crate::metrics::{
+    LeaderboardBoard, LeaderboardMetric, LeaderboardPeriod, LeaderboardRequest,
+    SNAPSHOT_SCOPE_AUTHOR, SNAPSHOT_SCOPE_JOURNAL, compute_category_metrics, compute_impact_factor,
+    fetch_leaderboard,
+};
+use crate::models::{
+    AuthorMetricsHistoryResponse, AuthorMetricsSnapshot, CategoryMetricsResponse, JournalMetrics,
+    JournalMetricsHistoryResponse, JournalMetricsSnapshot,
+};
+
+const DEFAULT_HISTORY_DAYS: i64 = 365;
+const MAX_HISTORY_DAYS: i64 = 3650;
+const DEFAULT_LEADERBOARD_PER_PAGE: i64 = 10;
+const MAX_LEADERBOARD_PER_PAGE: i64 = 50;
 
 #[derive(Debug, Deserialize)]
 struct JournalMetricsQuery {
     year: Option<i32>,
 }
 
+#[derive(Debug, Deserialize)]
+struct CategoryMetricsQuery {
+    year: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetricsHistoryQuery {
+    days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LeaderboardQuery {
+    board: Option<String>,
+    metric: Option<String>,
+    period: Option<String>,
+    page: Option<i64>,
+    per_page: Option<i64>,
+}
+
 pub fn metrics_routes() -> Router<MySqlPool> {
-    Router::new().route("/journal", get(get_journal_metrics))
+    Router::new()
+        .route("/journal", get(get_journal_metrics))
+        .route("/journal/history", get(get_journal_metrics_history))
+        .route("/authors/{user_id}/history", get(get_author_metrics_history))
+        .route("/leaderboards", get(get_leaderboards))
+        .route("/categories", get(get_category_metrics))
+}
+
+async fn get_category_metrics(
+    State(pool): State<MySqlPool>,
+    Query(query): Query<CategoryMetricsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Some(year) = query.year
+        && !(1900..=3000).contains(&year)
+    {
+        return Err(AppError::bad_request("Year must be between 1900 and 3000"));
+    }
+
+    let cache_key = CacheKey::Metrics(format!("categories year={:?}", query.year));
+    if let Some(metrics) = fetch_cached::<CategoryMetricsResponse>(&cache_key).await {
+        return Ok(Json(metrics));
+    }
+
+    let metrics = compute_category_metrics(&pool, query.year)
+        .await
+        .map_err(AppError::internal)?;
+    store_cached(&cache_key, &metrics).await;
+    Ok(Json(metrics))
+}
+
+async fn get_leaderboards(
+    State(pool): State<MySqlPool>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let board = match query.board.as_deref() {
+        None => LeaderboardBoard::Authors,
+        Some(raw) => LeaderboardBoard::parse(raw)
+            .ok_or_else(|| AppError::bad_request("board must be one of: authors, papers"))?,
+    };
+    let metric = match query.metric.as_deref() {
+        None => match board {
+            LeaderboardBoard::Authors => LeaderboardMetric::GIndex,
+            LeaderboardBoard::Papers => LeaderboardMetric::Citations,
+        },
+        Some(raw) => LeaderboardMetric::parse(raw)
+            .filter(|metric| metric.supports(board))
+            .ok_or_else(|| match board {
+                LeaderboardBoard::Authors => {
+                    AppError::bad_request("metric must be one of: g_index, h_index, citations")
+                }
+                LeaderboardBoard::Papers => {
+                    AppError::bad_request("metric must be one of: citations, views")
+                }
+            })?,
+    };
+    let period = match query.period.as_deref() {
+        None => LeaderboardPeriod::All,
+        Some(raw) => LeaderboardPeriod::parse(raw).ok_or_else(|| {
+            AppError::bad_request("period must be one of: all, year, month, week")
+        })?,
+    };
+    if period != LeaderboardPeriod::All && !metric.supports_period() {
+        return Err(AppError::bad_request(
+            "Index leaderboards are only available for period=all",
+        ));
+    }
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query
+        .per_page
+        .unwrap_or(DEFAULT_LEADERBOARD_PER_PAGE)
+        .clamp(1, MAX_LEADERBOARD_PER_PAGE);
+    // The offset is computed from these, so a page past i64 is rejected here.
+    if (page - 1).checked_mul(per_page).is_none() {
+        return Err(AppError::bad_request("page is out of range"));
+    }
+
+    let response = fetch_leaderboard(
+        &pool,
+        LeaderboardRequest {
+            board,
+            metric,
+            period,
+            page,
+            per_page,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    Ok(Json(response))
 }
 
 async fn get_journal_metrics(
     State(pool): State<MySqlPool>,
     Query(query): Query<JournalMetricsQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let default_year = Utc::now().year();
     let year = query.year.unwrap_or(default_year);
-    if year < 1900 || year > 3000 {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Year must be between 1900 and 3000"})),
-        ));
+    if !(1900..=3000).contains(&year) {
+        return Err(AppError::bad_request("Year must be between 1900 and 3000"));
+    }
+
+    let cache_key = CacheKey::Metrics(format!("journal year={}", year));
+    if let Some(metrics) = fetch_cached::<JournalMetrics>(&cache_key).await {
+        return Ok(Json(metrics));
     }
 
     let metrics = compute_impact_factor(&pool, year)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+    store_cached(&cache_key, &metrics).await;
     Ok(Json(metrics))
 }
 
-fn internal_error<E: ToString>(error: E) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({"detail": error.to_string()})),
+async fn get_journal_metrics_history(
+    State(pool): State<MySqlPool>,
+    Query(query): Query<MetricsHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let since = resolve_history_start(&query)?;
+
+    let snapshots = sqlx::query_as::<_, JournalMetricsSnapshot>(
+        r#"
+        SELECT
+            snapshot_date,
+            journal_year AS year,
+            impact_factor,
+            numerator_citations,
+            denominator_papers,
+            metric_version
+        FROM metric_snapshots
+        WHERE scope = ? AND snapshot_date >= ?
+        ORDER BY snapshot_date ASC
+        "#,
     )
+    .bind(SNAPSHOT_SCOPE_JOURNAL)
+    .bind(since)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(JournalMetricsHistoryResponse { snapshots }))
+}
+
+async fn get_author_metrics_history(
+    State(pool): State<MySqlPool>,
+    Path(user_id): Path<i64>,
+    Query(query): Query<MetricsHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let since = resolve_history_start(&query)?;
+
+    let user_exists: Option<(i64,)> = sqlx::query_as("SELECT id FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    if user_exists.is_none() {
+        return Err(AppError::not_found("User not found"));
+    }
+
+    let snapshots = sqlx::query_as::<_, AuthorMetricsSnapshot>(
+        r#"
+        SELECT
+            snapshot_date,
+            g_index,
+            h_index,
+            i10_index,
+            total_citations,
+            paper_count,
+            metric_version
+        FROM metric_snapshots
+        WHERE scope = ? AND user_id = ? AND snapshot_date >= ?
+        ORDER BY snapshot_date ASC
+        "#,
+    )
+    .bind(SNAPSHOT_SCOPE_AUTHOR)
+    .bind(user_id)
+    .bind(since)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(AuthorMetricsHistoryResponse { user_id, snapshots }))
+}
+
+fn resolve_history_start(query: &MetricsHistoryQuery) -> Result<NaiveDate, AppError> {
+    let days = query.days.unwrap_or(DEFAULT_HISTORY_DAYS);
+    if !(1..=MAX_HISTORY_DAYS).contains(&days) {
+        return Err(AppError::bad_request(format!(
+            "days must be between 1 and {}",
+            MAX_HISTORY_DAYS
+        )));
+    }
+
+    Ok(Utc::now().date_naive() - Duration::days(days))
 }
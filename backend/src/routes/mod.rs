@@ -1,17 +1,63 @@
+pub mod abuse;
+pub mod account_data;
 pub mod admin;
+pub mod analytics;
+pub mod announcements;
+pub mod appeals;
 pub mod auth;
+pub mod backups;
+pub mod broadcasts;
+pub mod calendar;
+pub mod categories;
+pub mod citations;
 pub mod comments;
+pub mod config_bundle;
+pub mod dashboard;
+pub mod editorial_decisions;
+pub mod issues;
 pub mod metrics;
+pub mod notifications;
+pub mod oai;
+pub mod openapi;
+pub mod orcid;
+pub mod outgoing_webhooks;
 pub mod paper_workflow;
 pub mod posts;
+pub mod review_backfills;
+pub mod reviewer_assignments;
 pub mod reviews;
+pub mod scholar_meta;
+pub mod trash;
 pub mod users;
 
+pub use abuse::abuse_routes;
+pub use account_data::{account_data_routes, erasure_queue_routes};
 pub use admin::admin_routes;
+pub use analytics::analytics_routes;
+pub use announcements::announcements_routes;
+pub use appeals::{appeal_queue_routes, appeal_routes};
 pub use auth::auth_routes;
+pub use backups::backup_routes;
+pub use broadcasts::broadcast_routes;
+pub use calendar::calendar_routes;
+pub use categories::category_admin_routes;
+pub use citations::citations_routes;
 pub use comments::comments_routes;
+pub use config_bundle::config_bundle_routes;
+pub use dashboard::dashboard_routes;
+pub use editorial_decisions::editorial_decision_routes;
+pub use issues::issues_routes;
 pub use metrics::metrics_routes;
+pub use notifications::{notification_preference_routes, notifications_routes};
+pub use oai::oai_routes;
+pub use openapi::openapi_routes;
+pub use orcid::orcid_routes;
+pub use outgoing_webhooks::outgoing_webhook_routes;
 pub use paper_workflow::paper_workflow_routes;
 pub use posts::posts_routes;
+pub use review_backfills::review_backfill_routes;
+pub use reviewer_assignments::{assigned_review_routes, reviewer_assignment_routes};
 pub use reviews::{review_center_routes, reviews_routes};
+pub use scholar_meta::scholar_meta_routes;
+pub use trash::trash_routes;
 pub use users::users_routes;
@@ -0,0 +1,350 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header::AUTHORIZATION},
+    response::{
+        IntoResponse,
+        sse::{KeepAlive, Sse},
+    },
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::error::AppError;
+use crate::models::{
+    NotificationListResponse, NotificationPreferenceListResponse, NotificationResponse,
+    RegisterPushSubscription, UnregisterPushSubscription, UpdateNotificationPreferences,
+};
+use crate::notifications::{
+    NOTIFICATION_SELECT, fetch_notification_preferences, is_configurable_event_type,
+    notification_stream, publish_unread_count, save_notification_preferences,
+};
+use crate::public_url::check_public_https_url;
+use crate::routes::auth::extract_current_user;
+use crate::web_push::{validate_subscription_keys, vapid_public_key};
+
+const DEFAULT_NOTIFICATION_PAGE_SIZE: i32 = 20;
+const MAX_NOTIFICATION_PAGE_SIZE: i32 = 100;
+const MAX_PUSH_ENDPOINT_CHARS: usize = 768;
+const MAX_USER_AGENT_CHARS: usize = 512;
+
+#[derive(Debug, Deserialize)]
+struct NotificationListQuery {
+    limit: Option<i32>,
+    offset: Option<i32>,
+    unread_only: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotificationStreamQuery {
+    access_token: Option<String>,
+}
+
+pub fn notifications_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/", get(list_notifications))
+        .route("/unread-count", get(unread_count))
+        .route("/stream", get(stream_notifications))
+        .route("/read-all", post(mark_all_read))
+        .route("/{notification_id}/read", post(mark_read))
+        .route("/push/public-key", get(get_push_public_key))
+        .route(
+            "/push/subscriptions",
+            post(register_push_subscription).delete(unregister_push_subscription),
+        )
+}
+
+/// The caller's notification preferences, nested under `/api/users`.
+pub fn notification_preference_routes() -> Router<MySqlPool> {
+    Router::new().route(
+        "/me/notification-preferences",
+        get(get_notification_preferences).put(update_notification_preferences),
+    )
+}
+
+async fn list_notifications(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<NotificationListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_NOTIFICATION_PAGE_SIZE)
+        .clamp(1, MAX_NOTIFICATION_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let unread_only = query.unread_only.unwrap_or(false);
+
+    let notifications = sqlx::query_as::<_, NotificationResponse>(&format!(
+        r#"
+        {}
+        WHERE n.user_id = ? AND (? = FALSE OR n.is_read = FALSE)
+        ORDER BY COALESCE(n.updated_at, n.created_at) DESC, n.id DESC
+        LIMIT ? OFFSET ?
+        "#,
+        NOTIFICATION_SELECT
+    ))
+    .bind(current_user.id)
+    .bind(unread_only)
+    .bind(i64::from(limit))
+    .bind(i64::from(offset))
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let (total, unread): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            CAST(COUNT(*) AS SIGNED),
+            CAST(COALESCE(SUM(is_read = FALSE), 0) AS SIGNED)
+        FROM notifications
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(current_user.id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(NotificationListResponse {
+        notifications,
+        total: if unread_only { unread } else { total },
+        unread,
+        limit,
+        offset,
+    }))
+}
+
+async fn unread_count(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let (unread,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM notifications WHERE user_id = ? AND is_read = FALSE")
+            .bind(current_user.id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({ "unread": unread })))
+}
+
+/// Server-sent events with the caller's unread count and each notification
+/// as it is created. `EventSource` cannot set headers, so browsers may pass
+/// the token as `?access_token=` instead.
+async fn stream_notifications(
+    State(pool): State<MySqlPool>,
+    mut headers: HeaderMap,
+    Query(query): Query<NotificationStreamQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if !headers.contains_key(AUTHORIZATION)
+        && let Some(token) = query.access_token.as_deref()
+        && let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token))
+    {
+        headers.insert(AUTHORIZATION, value);
+    }
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    Ok(Sse::new(notification_stream(pool, current_user.id)).keep_alive(KeepAlive::default()))
+}
+
+async fn mark_read(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(notification_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let exists: Option<(i64,)> =
+        sqlx::query_as("SELECT id FROM notifications WHERE id = ? AND user_id = ?")
+            .bind(notification_id)
+            .bind(current_user.id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?;
+    if exists.is_none() {
+        return Err(AppError::not_found("Notification not found"));
+    }
+
+    sqlx::query(
+        "UPDATE notifications SET is_read = TRUE, read_at = COALESCE(read_at, ?) WHERE id = ?",
+    )
+    .bind(Utc::now())
+    .bind(notification_id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    publish_unread_count(current_user.id);
+
+    Ok(Json(
+        serde_json::json!({"message": "Notification marked as read"}),
+    ))
+}
+
+async fn mark_all_read(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let result = sqlx::query(
+        "UPDATE notifications SET is_read = TRUE, read_at = ? WHERE user_id = ? AND is_read = FALSE",
+    )
+    .bind(Utc::now())
+    .bind(current_user.id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    publish_unread_count(current_user.id);
+
+    Ok(Json(serde_json::json!({
+        "message": "Notifications marked as read",
+        "updated": result.rows_affected()
+    })))
+}
+
+async fn get_notification_preferences(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let preferences = fetch_notification_preferences(&pool, current_user.id)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(NotificationPreferenceListResponse { preferences }))
+}
+
+/// Changes the listed event types and returns the full set. Event types
+/// not listed keep their current setting.
+async fn update_notification_preferences(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<UpdateNotificationPreferences>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    if let Some(unknown) = input
+        .preferences
+        .iter()
+        .find(|update| !is_configurable_event_type(&update.event_type))
+    {
+        return Err(AppError::bad_request(format!(
+            "Unknown notification event type: {}",
+            unknown.event_type
+        )));
+    }
+
+    let mut preferences = fetch_notification_preferences(&pool, current_user.id)
+        .await
+        .map_err(AppError::internal)?;
+    let mut changed = Vec::new();
+    for update in &input.preferences {
+        if let Some(preference) = preferences
+            .iter_mut()
+            .find(|preference| preference.event_type == update.event_type)
+        {
+            preference.in_app = update.in_app.unwrap_or(preference.in_app);
+            preference.email = update.email.unwrap_or(preference.email);
+            changed.push(preference.clone());
+        }
+    }
+    save_notification_preferences(&pool, current_user.id, &changed)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(NotificationPreferenceListResponse { preferences }))
+}
+
+/// The VAPID key to pass as `applicationServerKey` when subscribing.
+async fn get_push_public_key() -> Result<impl IntoResponse, AppError> {
+    let public_key = vapid_public_key().ok_or_else(push_not_configured)?;
+    Ok(Json(serde_json::json!({ "public_key": public_key })))
+}
+
+/// Registers a browser for push messages. An endpoint registered before,
+/// even by another account, now belongs to the caller with the new keys.
+async fn register_push_subscription(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<RegisterPushSubscription>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    vapid_public_key().ok_or_else(push_not_configured)?;
+
+    let endpoint = input.endpoint.trim();
+    if endpoint.chars().count() > MAX_PUSH_ENDPOINT_CHARS {
+        return Err(AppError::bad_request(format!(
+            "endpoint must be at most {} characters",
+            MAX_PUSH_ENDPOINT_CHARS
+        )));
+    }
+    // Deliveries are POSTed to the endpoint, so it must be a public host.
+    check_public_https_url(endpoint)
+        .await
+        .map_err(|error| AppError::bad_request(format!("endpoint {}", error)))?;
+    let p256dh = input.keys.p256dh.trim();
+    let auth_secret = input.keys.auth.trim();
+    if !validate_subscription_keys(p256dh, auth_secret) {
+        return Err(AppError::bad_request("Invalid subscription keys"));
+    }
+    let user_agent: Option<String> = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(MAX_USER_AGENT_CHARS).collect());
+
+    sqlx::query(
+        r#"
+        INSERT INTO push_subscriptions
+            (user_id, endpoint, p256dh, auth_secret, user_agent, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            user_id = VALUES(user_id),
+            p256dh = VALUES(p256dh),
+            auth_secret = VALUES(auth_secret),
+            user_agent = VALUES(user_agent),
+            created_at = VALUES(created_at),
+            last_failure_at = NULL,
+            failure_count = 0
+        "#,
+    )
+    .bind(current_user.id)
+    .bind(endpoint)
+    .bind(p256dh)
+    .bind(auth_secret)
+    .bind(user_agent)
+    .bind(Utc::now())
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({"message": "Push subscription registered"})),
+    ))
+}
+
+async fn unregister_push_subscription(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<UnregisterPushSubscription>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let result = sqlx::query("DELETE FROM push_subscriptions WHERE endpoint = ? AND user_id = ?")
+        .bind(input.endpoint.trim())
+        .bind(current_user.id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("Push subscription not found"));
+    }
+
+    Ok(Json(
+        serde_json::json!({"message": "Push subscription removed"}),
+    ))
+}
+
+fn push_not_configured() -> AppError {
+    AppError::service_unavailable("Push notifications are not configured")
+}
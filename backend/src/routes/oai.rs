@@ -0,0 +1,721 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use reqwest::Url;
+use sqlx::{FromRow, MySqlPool};
+
+use crate::error::AppError;
+use crate::feature_flags::{FEATURE_OAI_PMH, is_feature_enabled};
+use crate::routes::posts::frontend_base_url_for_links;
+
+const OAI_DC_PREFIX: &str = "oai_dc";
+const OAI_DC_SCHEMA: &str = "http://www.openarchives.org/OAI/2.0/oai_dc.xsd";
+const OAI_DC_NAMESPACE: &str = "http://www.openarchives.org/OAI/2.0/oai_dc/";
+const OAI_PAGE_SIZE: i64 = 100;
+const DEFAULT_OAI_ADMIN_EMAIL: &str = "admin@thought-manifold.local";
+const DEFAULT_OAI_REPOSITORY_IDENTIFIER: &str = "thought-manifold";
+const REPOSITORY_NAME: &str = "Thought Manifold";
+
+// Harvestable records are published papers. The datestamp moves whenever a
+// paper is edited so incremental harvests pick up the change.
+const OAI_RECORD_SELECT: &str = r#"
+    SELECT
+        p.id AS id,
+        p.title AS title,
+        p.summary AS summary,
+        COALESCE(NULLIF(TRIM(u.display_name), ''), u.username) AS author_name,
+        COALESCE(p.published_at, p.created_at) AS published_on,
+        COALESCE(p.updated_at, p.published_at, p.created_at) AS datestamp,
+        (SELECT r.registered_doi FROM post_doi_registrations r WHERE r.post_id = p.id) AS registered_doi,
+        (
+            SELECT GROUP_CONCAT(t.name ORDER BY t.name SEPARATOR '\n')
+            FROM post_tags pt
+            JOIN tags t ON t.id = pt.tag_id
+            WHERE pt.post_id = p.id
+        ) AS tags
+    FROM posts p
+    JOIN post_categories c ON c.id = p.category_id
+    JOIN users u ON u.id = p.author_id
+"#;
+
+const OAI_RECORD_FILTER: &str = r#"
+    WHERE c.code = 'paper'
+      AND p.paper_status = 'published'
+      AND p.is_published = TRUE
+      AND p.deleted_at IS NULL
+"#;
+
+const OAI_DATESTAMP: &str = "COALESCE(p.updated_at, p.published_at, p.created_at)";
+
+#[derive(Debug, FromRow)]
+struct OaiRecordRow {
+    id: i64,
+    title: String,
+    summary: Option<String>,
+    author_name: String,
+    published_on: DateTime<Utc>,
+    datestamp: DateTime<Utc>,
+    registered_doi: Option<String>,
+    tags: Option<String>,
+}
+
+#[derive(Debug)]
+struct OaiError {
+    code: &'static str,
+    message: String,
+}
+
+impl OaiError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn bad_argument(message: impl Into<String>) -> Self {
+        Self::new("badArgument", message)
+    }
+
+    /// badVerb and badArgument responses must not echo the request arguments.
+    fn echoes_arguments(&self) -> bool {
+        !matches!(self.code, "badVerb" | "badArgument")
+    }
+}
+
+/// A protocol error is reported inside the OAI-PMH envelope; a database
+/// failure is a plain server error so harvesters retry later.
+#[derive(Debug)]
+enum OaiFailure {
+    Protocol(OaiError),
+    Internal(sqlx::Error),
+}
+
+impl From<OaiError> for OaiFailure {
+    fn from(error: OaiError) -> Self {
+        Self::Protocol(error)
+    }
+}
+
+impl From<sqlx::Error> for OaiFailure {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Internal(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OaiVerb {
+    Identify,
+    ListMetadataFormats,
+    ListSets,
+    GetRecord,
+    ListIdentifiers,
+    ListRecords,
+}
+
+impl OaiVerb {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Identify" => Some(Self::Identify),
+            "ListMetadataFormats" => Some(Self::ListMetadataFormats),
+            "ListSets" => Some(Self::ListSets),
+            "GetRecord" => Some(Self::GetRecord),
+            "ListIdentifiers" => Some(Self::ListIdentifiers),
+            "ListRecords" => Some(Self::ListRecords),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Identify => "Identify",
+            Self::ListMetadataFormats => "ListMetadataFormats",
+            Self::ListSets => "ListSets",
+            Self::GetRecord => "GetRecord",
+            Self::ListIdentifiers => "ListIdentifiers",
+            Self::ListRecords => "ListRecords",
+        }
+    }
+
+    fn allowed_arguments(self) -> &'static [&'static str] {
+        match self {
+            Self::Identify => &[],
+            Self::ListMetadataFormats => &["identifier"],
+            Self::ListSets => &["resumptionToken"],
+            Self::GetRecord => &["identifier", "metadataPrefix"],
+            Self::ListIdentifiers | Self::ListRecords => {
+                &["metadataPrefix", "from", "until", "set", "resumptionToken"]
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct OaiRequest {
+    verb: OaiVerb,
+    arguments: Vec<(String, String)>,
+}
+
+impl OaiRequest {
+    fn argument(&self, name: &str) -> Option<&str> {
+        self.arguments
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Position in a ListIdentifiers/ListRecords walk. Records are listed by id,
+/// so a token stays valid while new papers are published.
+#[derive(Debug, Clone)]
+struct ResumptionToken {
+    from: Option<String>,
+    until: Option<String>,
+    after_id: i64,
+    cursor: i64,
+}
+
+impl ResumptionToken {
+    fn encode(&self) -> String {
+        let raw = format!(
+            "{}|{}|{}|{}|{}",
+            OAI_DC_PREFIX,
+            self.from.as_deref().unwrap_or_default(),
+            self.until.as_deref().unwrap_or_default(),
+            self.after_id,
+            self.cursor
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    fn decode(token: &str) -> Option<Self> {
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(token).ok()?).ok()?;
+        let parts: Vec<&str> = raw.split('|').collect();
+        let [prefix, from, until, after_id, cursor] = parts.as_slice() else {
+            return None;
+        };
+        if *prefix != OAI_DC_PREFIX {
+            return None;
+        }
+
+        Some(Self {
+            from: Some(from.to_string()).filter(|value| !value.is_empty()),
+            until: Some(until.to_string()).filter(|value| !value.is_empty()),
+            after_id: after_id.parse().ok()?,
+            cursor: cursor.parse().ok()?,
+        })
+    }
+}
+
+/// Inclusive lower and exclusive upper datestamp bounds of a list request.
+#[derive(Debug, Default, Clone, Copy)]
+struct DatestampRange {
+    from: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+pub fn oai_routes() -> Router<MySqlPool> {
+    Router::new().route("/", get(oai_pmh))
+}
+
+// ============================
+// GET /oai
+// ============================
+async fn oai_pmh(
+    State(pool): State<MySqlPool>,
+    Query(arguments): Query<Vec<(String, String)>>,
+) -> Response {
+    if !is_feature_enabled(FEATURE_OAI_PMH, None) {
+        return AppError::not_found("OAI-PMH is disabled").into_response();
+    }
+
+    let base_url = oai_base_url();
+    let (request, result) = match parse_request(arguments) {
+        Ok(request) => match handle_request(&pool, &request, &base_url).await {
+            Ok(body) => (Some(request), Ok(body)),
+            Err(OaiFailure::Protocol(error)) => (Some(request), Err(error)),
+            Err(OaiFailure::Internal(error)) => {
+                return AppError::internal(format!("OAI-PMH request failed: {}", error))
+                    .into_response();
+            }
+        },
+        Err(error) => (None, Err(error)),
+    };
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<OAI-PMH xmlns=\"http://www.openarchives.org/OAI/2.0/\" \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+         xsi:schemaLocation=\"http://www.openarchives.org/OAI/2.0/ \
+         http://www.openarchives.org/OAI/2.0/OAI-PMH.xsd\">\n",
+    );
+    xml.push_str(&format!(
+        "  <responseDate>{}</responseDate>\n",
+        format_datestamp(Utc::now())
+    ));
+
+    let echoed = request
+        .as_ref()
+        .filter(|_| result.as_ref().err().is_none_or(OaiError::echoes_arguments));
+    xml.push_str("  <request");
+    if let Some(request) = echoed {
+        xml.push_str(&format!(" verb=\"{}\"", request.verb.name()));
+        for (name, value) in &request.arguments {
+            xml.push_str(&format!(" {}=\"{}\"", name, xml_text(value)));
+        }
+    }
+    xml.push_str(&format!(">{}</request>\n", xml_text(&base_url)));
+
+    match result {
+        Ok(body) => xml.push_str(&body),
+        Err(error) => xml.push_str(&format!(
+            "  <error code=\"{}\">{}</error>\n",
+            error.code,
+            xml_text(&error.message)
+        )),
+    }
+    xml.push_str("</OAI-PMH>\n");
+
+    ([(header::CONTENT_TYPE, "text/xml; charset=utf-8")], xml).into_response()
+}
+
+fn parse_request(arguments: Vec<(String, String)>) -> Result<OaiRequest, OaiError> {
+    let mut verb = None;
+    let mut rest: Vec<(String, String)> = Vec::new();
+    for (name, value) in arguments {
+        let duplicate = if name == "verb" {
+            verb.replace(value).is_some()
+        } else {
+            let duplicate = rest.iter().any(|(key, _)| *key == name);
+            rest.push((name, value));
+            duplicate
+        };
+        if duplicate {
+            return Err(OaiError::bad_argument("Arguments must not be repeated"));
+        }
+    }
+
+    let verb = verb
+        .as_deref()
+        .and_then(OaiVerb::parse)
+        .ok_or_else(|| OaiError::new("badVerb", "Missing or illegal verb"))?;
+    if let Some((name, _)) = rest
+        .iter()
+        .find(|(name, _)| !verb.allowed_arguments().contains(&name.as_str()))
+    {
+        return Err(OaiError::bad_argument(format!(
+            "Illegal argument {} for {}",
+            name,
+            verb.name()
+        )));
+    }
+
+    let request = OaiRequest {
+        verb,
+        arguments: rest,
+    };
+    let has_token = request.argument("resumptionToken").is_some();
+    if has_token && request.arguments.len() > 1 {
+        return Err(OaiError::bad_argument(
+            "resumptionToken is an exclusive argument",
+        ));
+    }
+    let required: &[&str] = match verb {
+        OaiVerb::GetRecord => &["identifier", "metadataPrefix"],
+        OaiVerb::ListIdentifiers | OaiVerb::ListRecords if !has_token => &["metadataPrefix"],
+        _ => &[],
+    };
+    if let Some(missing) = required
+        .iter()
+        .find(|name| request.argument(name).is_none())
+    {
+        return Err(OaiError::bad_argument(format!(
+            "Missing required argument {}",
+            missing
+        )));
+    }
+
+    Ok(request)
+}
+
+async fn handle_request(
+    pool: &MySqlPool,
+    request: &OaiRequest,
+    base_url: &str,
+) -> Result<String, OaiFailure> {
+    match request.verb {
+        OaiVerb::Identify => identify(pool, base_url).await,
+        OaiVerb::ListMetadataFormats => list_metadata_formats(pool, request).await,
+        OaiVerb::ListSets => {
+            Err(OaiError::new("noSetHierarchy", "This repository does not support sets").into())
+        }
+        OaiVerb::GetRecord => get_record(pool, request).await,
+        OaiVerb::ListIdentifiers => list_records(pool, request, false).await,
+        OaiVerb::ListRecords => list_records(pool, request, true).await,
+    }
+}
+
+async fn identify(pool: &MySqlPool, base_url: &str) -> Result<String, OaiFailure> {
+    let query = format!(
+        "SELECT MIN({}) FROM posts p JOIN post_categories c ON c.id = p.category_id {}",
+        OAI_DATESTAMP, OAI_RECORD_FILTER
+    );
+    let earliest: Option<DateTime<Utc>> = sqlx::query_scalar(&query).fetch_one(pool).await?;
+
+    Ok(format!(
+        "  <Identify>\n\
+         \x20   <repositoryName>{}</repositoryName>\n\
+         \x20   <baseURL>{}</baseURL>\n\
+         \x20   <protocolVersion>2.0</protocolVersion>\n\
+         \x20   <adminEmail>{}</adminEmail>\n\
+         \x20   <earliestDatestamp>{}</earliestDatestamp>\n\
+         \x20   <deletedRecord>no</deletedRecord>\n\
+         \x20   <granularity>YYYY-MM-DDThh:mm:ssZ</granularity>\n\
+         \x20 </Identify>\n",
+        REPOSITORY_NAME,
+        xml_text(base_url),
+        xml_text(&oai_admin_email()),
+        format_datestamp(earliest.unwrap_or_else(Utc::now))
+    ))
+}
+
+async fn list_metadata_formats(
+    pool: &MySqlPool,
+    request: &OaiRequest,
+) -> Result<String, OaiFailure> {
+    if let Some(identifier) = request.argument("identifier") {
+        fetch_record(pool, identifier).await?;
+    }
+
+    Ok(format!(
+        "  <ListMetadataFormats>\n\
+         \x20   <metadataFormat>\n\
+         \x20     <metadataPrefix>{}</metadataPrefix>\n\
+         \x20     <schema>{}</schema>\n\
+         \x20     <metadataNamespace>{}</metadataNamespace>\n\
+         \x20   </metadataFormat>\n\
+         \x20 </ListMetadataFormats>\n",
+        OAI_DC_PREFIX, OAI_DC_SCHEMA, OAI_DC_NAMESPACE
+    ))
+}
+
+async fn get_record(pool: &MySqlPool, request: &OaiRequest) -> Result<String, OaiFailure> {
+    let identifier = request.argument("identifier").unwrap_or_default();
+    let row = fetch_record(pool, identifier).await?;
+    ensure_supported_prefix(request.argument("metadataPrefix"))?;
+
+    Ok(format!(
+        "  <GetRecord>\n{}  </GetRecord>\n",
+        render_record(&row)
+    ))
+}
+
+async fn list_records(
+    pool: &MySqlPool,
+    request: &OaiRequest,
+    with_metadata: bool,
+) -> Result<String, OaiFailure> {
+    let token = match request.argument("resumptionToken") {
+        Some(raw) => ResumptionToken::decode(raw)
+            .ok_or_else(|| OaiError::new("badResumptionToken", "The resumptionToken is invalid"))?,
+        None => {
+            ensure_supported_prefix(request.argument("metadataPrefix"))?;
+            if request.argument("set").is_some() {
+                return Err(OaiError::new(
+                    "noSetHierarchy",
+                    "This repository does not support sets",
+                )
+                .into());
+            }
+            ResumptionToken {
+                from: request.argument("from").map(ToOwned::to_owned),
+                until: request.argument("until").map(ToOwned::to_owned),
+                after_id: 0,
+                cursor: 0,
+            }
+        }
+    };
+    let range =
+        parse_datestamp_range(token.from.as_deref(), token.until.as_deref()).map_err(|error| {
+            if request.argument("resumptionToken").is_some() {
+                OaiError::new("badResumptionToken", "The resumptionToken is invalid")
+            } else {
+                error
+            }
+        })?;
+
+    let count_query = format!(
+        "SELECT COUNT(*) FROM posts p JOIN post_categories c ON c.id = p.category_id {} \
+         AND (? IS NULL OR {datestamp} >= ?) AND (? IS NULL OR {datestamp} < ?)",
+        OAI_RECORD_FILTER,
+        datestamp = OAI_DATESTAMP
+    );
+    let complete_list_size: i64 = sqlx::query_scalar(&count_query)
+        .bind(range.from)
+        .bind(range.from)
+        .bind(range.until)
+        .bind(range.until)
+        .fetch_one(pool)
+        .await?;
+
+    let list_query = format!(
+        "{} {} AND (? IS NULL OR {datestamp} >= ?) AND (? IS NULL OR {datestamp} < ?) \
+         AND p.id > ? ORDER BY p.id ASC LIMIT ?",
+        OAI_RECORD_SELECT,
+        OAI_RECORD_FILTER,
+        datestamp = OAI_DATESTAMP
+    );
+    let mut rows = sqlx::query_as::<_, OaiRecordRow>(&list_query)
+        .bind(range.from)
+        .bind(range.from)
+        .bind(range.until)
+        .bind(range.until)
+        .bind(token.after_id)
+        .bind(OAI_PAGE_SIZE + 1)
+        .fetch_all(pool)
+        .await?;
+    if rows.is_empty() {
+        return Err(OaiError::new("noRecordsMatch", "No records match the request").into());
+    }
+
+    let has_more = rows.len() as i64 > OAI_PAGE_SIZE;
+    rows.truncate(OAI_PAGE_SIZE as usize);
+
+    let element = request.verb.name();
+    let mut body = format!("  <{}>\n", element);
+    for row in &rows {
+        if with_metadata {
+            body.push_str(&render_record(row));
+        } else {
+            body.push_str(&render_header(row, 4));
+        }
+    }
+
+    // The last page carries an empty token, or none when it is the only page.
+    let next_token = has_more.then(|| {
+        ResumptionToken {
+            after_id: rows.last().map(|row| row.id).unwrap_or(token.after_id),
+            cursor: token.cursor + rows.len() as i64,
+            ..token.clone()
+        }
+        .encode()
+    });
+    if next_token.is_some() || token.cursor > 0 {
+        body.push_str(&format!(
+            "    <resumptionToken completeListSize=\"{}\" cursor=\"{}\">{}</resumptionToken>\n",
+            complete_list_size,
+            token.cursor,
+            next_token.unwrap_or_default()
+        ));
+    }
+    body.push_str(&format!("  </{}>\n", element));
+
+    Ok(body)
+}
+
+async fn fetch_record(pool: &MySqlPool, identifier: &str) -> Result<OaiRecordRow, OaiFailure> {
+    let not_found = || {
+        OaiError::new(
+            "idDoesNotExist",
+            format!("{} is not a known identifier", identifier),
+        )
+    };
+    let post_id = identifier
+        .strip_prefix(&format!("oai:{}:", oai_repository_identifier()))
+        .and_then(|value| value.parse::<i64>().ok())
+        .ok_or_else(not_found)?;
+
+    let query = format!("{} {} AND p.id = ?", OAI_RECORD_SELECT, OAI_RECORD_FILTER);
+    let row = sqlx::query_as::<_, OaiRecordRow>(&query)
+        .bind(post_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(not_found)?;
+
+    Ok(row)
+}
+
+fn ensure_supported_prefix(prefix: Option<&str>) -> Result<(), OaiError> {
+    if prefix == Some(OAI_DC_PREFIX) {
+        return Ok(());
+    }
+    Err(OaiError::new(
+        "cannotDisseminateFormat",
+        format!("Only the {} metadata format is supported", OAI_DC_PREFIX),
+    ))
+}
+
+/// Parses `from`/`until`, which must share one granularity. A day-granular
+/// `until` covers the whole day.
+fn parse_datestamp_range(
+    from: Option<&str>,
+    until: Option<&str>,
+) -> Result<DatestampRange, OaiError> {
+    let from = from
+        .map(|value| parse_datestamp(value, "from"))
+        .transpose()?;
+    let until = until
+        .map(|value| parse_datestamp(value, "until"))
+        .transpose()?;
+    if let (Some((_, from_is_day)), Some((_, until_is_day))) = (from, until)
+        && from_is_day != until_is_day
+    {
+        return Err(OaiError::bad_argument(
+            "from and until must have the same granularity",
+        ));
+    }
+
+    let range = DatestampRange {
+        from: from.map(|(value, _)| value),
+        until: until.map(|(value, is_day)| {
+            value
+                + if is_day {
+                    chrono::Duration::days(1)
+                } else {
+                    chrono::Duration::seconds(1)
+                }
+        }),
+    };
+    if let (Some(from), Some(until)) = (range.from, range.until)
+        && from >= until
+    {
+        return Err(OaiError::bad_argument("from must not be later than until"));
+    }
+
+    Ok(range)
+}
+
+fn parse_datestamp(value: &str, name: &str) -> Result<(DateTime<Utc>, bool), OaiError> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        && value.len() == 10
+    {
+        return Ok((date.and_time(chrono::NaiveTime::MIN).and_utc(), true));
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%SZ")
+        .map(|datetime| (datetime.and_utc(), false))
+        .map_err(|_| {
+            OaiError::bad_argument(format!(
+                "{} must be YYYY-MM-DD or YYYY-MM-DDThh:mm:ssZ",
+                name
+            ))
+        })
+}
+
+fn render_header(row: &OaiRecordRow, indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    format!(
+        "{pad}<header>\n\
+         {pad}  <identifier>{}</identifier>\n\
+         {pad}  <datestamp>{}</datestamp>\n\
+         {pad}</header>\n",
+        xml_text(&oai_identifier(row.id)),
+        format_datestamp(row.datestamp),
+        pad = pad
+    )
+}
+
+fn render_record(row: &OaiRecordRow) -> String {
+    let mut record = String::from("    <record>\n");
+    record.push_str(&render_header(row, 6));
+    record.push_str("      <metadata>\n");
+    record.push_str(&render_oai_dc(row));
+    record.push_str("      </metadata>\n");
+    record.push_str("    </record>\n");
+    record
+}
+
+fn render_oai_dc(row: &OaiRecordRow) -> String {
+    let mut fields: Vec<(&str, String)> = vec![
+        ("title", row.title.clone()),
+        ("creator", row.author_name.clone()),
+    ];
+    for tag in row.tags.as_deref().unwrap_or_default().lines() {
+        fields.push(("subject", tag.to_string()));
+    }
+    if let Some(summary) = row
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        fields.push(("description", summary.to_string()));
+    }
+    fields.push(("publisher", REPOSITORY_NAME.to_string()));
+    fields.push(("date", row.published_on.format("%Y-%m-%d").to_string()));
+    fields.push(("type", "Text".to_string()));
+    fields.push((
+        "identifier",
+        format!("{}/posts/{}", frontend_base_url_for_links(), row.id),
+    ));
+    if let Some(doi) = &row.registered_doi {
+        fields.push(("identifier", format!("https://doi.org/{}", doi)));
+    }
+
+    let mut dc = format!(
+        "        <oai_dc:dc xmlns:oai_dc=\"{}\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\" \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+         xsi:schemaLocation=\"{} {}\">\n",
+        OAI_DC_NAMESPACE, OAI_DC_NAMESPACE, OAI_DC_SCHEMA
+    );
+    for (name, value) in fields {
+        dc.push_str(&format!(
+            "          <dc:{name}>{}</dc:{name}>\n",
+            xml_text(&value),
+            name = name
+        ));
+    }
+    dc.push_str("        </oai_dc:dc>\n");
+    dc
+}
+
+/// Escapes markup and drops control characters XML 1.0 cannot carry.
+fn xml_text(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .filter(|ch| !ch.is_control() || matches!(ch, '\t' | '\n' | '\r'))
+        .collect();
+    quick_xml::escape::escape(cleaned.as_str()).into_owned()
+}
+
+fn format_datestamp(value: DateTime<Utc>) -> String {
+    value.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn oai_identifier(post_id: i64) -> String {
+    format!("oai:{}:{}", oai_repository_identifier(), post_id)
+}
+
+fn oai_base_url() -> String {
+    format!("{}/oai", frontend_base_url_for_links())
+}
+
+/// `OAI_REPOSITORY_IDENTIFIER`, else the host of `FRONTEND_URL`.
+fn oai_repository_identifier() -> String {
+    std::env::var("OAI_REPOSITORY_IDENTIFIER")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| {
+            Url::parse(&frontend_base_url_for_links())
+                .ok()
+                .and_then(|url| url.host_str().map(ToOwned::to_owned))
+        })
+        .unwrap_or_else(|| DEFAULT_OAI_REPOSITORY_IDENTIFIER.to_string())
+}
+
+fn oai_admin_email() -> String {
+    std::env::var("OAI_ADMIN_EMAIL")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| DEFAULT_OAI_ADMIN_EMAIL.to_string())
+}
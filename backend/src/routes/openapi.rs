@@ -0,0 +1,67 @@
+use axum::Router;
+use sqlx::MySqlPool;
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::error::ErrorBody;
+use crate::routes::auth::AuthApi;
+use crate::routes::comments::CommentsApi;
+use crate::routes::posts::PostsApi;
+use crate::routes::users::UsersApi;
+
+pub const OPENAPI_JSON_PATH: &str = "/api/openapi.json";
+pub const SWAGGER_UI_PATH: &str = "/api/docs";
+
+/// The API description served at `/api/openapi.json`. Each route module
+/// documents its own handlers and is nested here under the same prefix it
+/// is mounted at in `main`; schemas come from the types in `models`. Only
+/// the versioned paths are listed, not their deprecated `/api/...` aliases.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Thought Manifold API",
+        description = "Papers, peer review and discussion for Thought Manifold."
+    ),
+    nest(
+        (path = "/api/v1/auth", api = AuthApi),
+        (path = "/api/v1/users", api = UsersApi),
+        (path = "/api/v1/posts", api = PostsApi),
+        (path = "/api/v1/posts", api = CommentsApi),
+    ),
+    components(schemas(ErrorBody)),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "auth", description = "Registration, sign-in and the current session"),
+        (name = "users", description = "Profiles, follows, blocks and account settings"),
+        (name = "posts", description = "Papers and posts"),
+        (name = "comments", description = "Discussion threads on posts"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Serves the OpenAPI document and a Swagger UI that reads it.
+pub fn openapi_routes() -> Router<MySqlPool> {
+    SwaggerUi::new(SWAGGER_UI_PATH)
+        .url(OPENAPI_JSON_PATH, ApiDoc::openapi())
+        .into()
+}
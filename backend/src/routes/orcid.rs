@@ -0,0 +1,320 @@
+use axum::{
+    Router,
+    extract::{Json, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect},
+    routing::{get, post},
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::error::AppError;
+use crate::models::{OrcidAuthorizeResponse, OrcidSyncStatus, OrcidWorkSync, UpdateOrcidSync};
+use crate::orcid::{OrcidConfig, orcid_client, remove_user_works, sync_user_works};
+use crate::routes::auth::extract_current_user;
+
+const STATE_TTL_MINUTES: i64 = 10;
+
+/// Signed OAuth `state` for the ORCID callback, which arrives without the
+/// user's bearer token.
+#[derive(Debug, Serialize, Deserialize)]
+struct OrcidStateClaims {
+    user_id: i64,
+    exp: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrcidCallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct OrcidLinkRow {
+    orcid_id: String,
+    sync_enabled: bool,
+    last_synced_at: Option<chrono::DateTime<Utc>>,
+    last_sync_error: Option<String>,
+}
+
+pub fn orcid_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route(
+            "/me/orcid",
+            get(get_orcid_status)
+                .put(update_orcid_sync)
+                .delete(unlink_orcid),
+        )
+        .route("/me/orcid/authorize", post(authorize_orcid))
+        .route("/me/orcid/callback", get(orcid_callback))
+        .route("/me/orcid/sync", post(sync_orcid_now))
+}
+
+async fn get_orcid_status(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<Json<OrcidSyncStatus>, AppError> {
+    let user = extract_current_user(&pool, &headers).await?;
+    load_status(&pool, user.id).await.map(Json)
+}
+
+async fn authorize_orcid(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<Json<OrcidAuthorizeResponse>, AppError> {
+    let user = extract_current_user(&pool, &headers).await?;
+    let config = require_config()?;
+
+    let claims = OrcidStateClaims {
+        user_id: user.id,
+        exp: (Utc::now() + Duration::minutes(STATE_TTL_MINUTES)).timestamp() as usize,
+    };
+    let state = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(&state_secret()),
+    )
+    .map_err(AppError::internal)?;
+
+    Ok(Json(OrcidAuthorizeResponse {
+        authorize_url: config.authorize_url(&state),
+    }))
+}
+
+/// Redirect target of the ORCID consent screen. Always sends the browser
+/// back to the profile page with `?orcid=linked` or `?orcid=error`.
+async fn orcid_callback(
+    State(pool): State<MySqlPool>,
+    Query(query): Query<OrcidCallbackQuery>,
+) -> impl IntoResponse {
+    let outcome = match link_from_callback(&pool, &query).await {
+        Ok(()) => "linked",
+        Err(error) => {
+            tracing::warn!("ORCID link failed: {}", error);
+            "error"
+        }
+    };
+
+    let frontend_url =
+        std::env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    Redirect::temporary(&format!("{}/profile?orcid={}", frontend_url, outcome))
+}
+
+async fn link_from_callback(pool: &MySqlPool, query: &OrcidCallbackQuery) -> anyhow::Result<()> {
+    if let Some(error) = &query.error {
+        anyhow::bail!("authorization denied: {}", error);
+    }
+    let (Some(code), Some(state)) = (query.code.as_deref(), query.state.as_deref()) else {
+        anyhow::bail!("missing code or state");
+    };
+    let Some(config) = OrcidConfig::from_env() else {
+        anyhow::bail!("ORCID is not configured");
+    };
+
+    let claims = decode::<OrcidStateClaims>(
+        state,
+        &DecodingKey::from_secret(&state_secret()),
+        &Validation::default(),
+    )?
+    .claims;
+    let token = config.exchange_code(&orcid_client()?, code).await?;
+
+    let linked_user: Option<i64> =
+        sqlx::query_scalar("SELECT user_id FROM user_orcid_links WHERE orcid_id = ?")
+            .bind(&token.orcid)
+            .fetch_optional(pool)
+            .await?;
+    if linked_user.is_some_and(|user_id| user_id != claims.user_id) {
+        anyhow::bail!(
+            "ORCID iD {} is already linked to another account",
+            token.orcid
+        );
+    }
+
+    let now = Utc::now();
+    let expires_at = token
+        .expires_in
+        .map(|seconds| now + Duration::seconds(seconds));
+    // Linking a different iD starts over; the old works belong to the old
+    // record.
+    sqlx::query(
+        "DELETE FROM orcid_work_syncs WHERE user_id = ? AND NOT EXISTS (SELECT 1 FROM user_orcid_links l WHERE l.user_id = ? AND l.orcid_id = ?)",
+    )
+    .bind(claims.user_id)
+    .bind(claims.user_id)
+    .bind(&token.orcid)
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO user_orcid_links
+            (user_id, orcid_id, access_token, refresh_token, scope, token_expires_at, last_sync_error, linked_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, NULL, ?, ?)
+        ON DUPLICATE KEY UPDATE
+            orcid_id = VALUES(orcid_id),
+            access_token = VALUES(access_token),
+            refresh_token = VALUES(refresh_token),
+            scope = VALUES(scope),
+            token_expires_at = VALUES(token_expires_at),
+            last_sync_error = NULL,
+            linked_at = VALUES(linked_at),
+            updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(claims.user_id)
+    .bind(&token.orcid)
+    .bind(&token.access_token)
+    .bind(&token.refresh_token)
+    .bind(&token.scope)
+    .bind(expires_at)
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_orcid_sync(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<UpdateOrcidSync>,
+) -> Result<Json<OrcidSyncStatus>, AppError> {
+    let user = extract_current_user(&pool, &headers).await?;
+
+    let result = sqlx::query(
+        "UPDATE user_orcid_links SET sync_enabled = ?, updated_at = ? WHERE user_id = ?",
+    )
+    .bind(input.sync_enabled)
+    .bind(Utc::now())
+    .bind(user.id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(not_linked());
+    }
+
+    load_status(&pool, user.id).await.map(Json)
+}
+
+/// Removes the synced works from the ORCID record when possible, then
+/// forgets the link and its tokens.
+async fn unlink_orcid(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<StatusCode, AppError> {
+    let user = extract_current_user(&pool, &headers).await?;
+
+    if let Some(config) = OrcidConfig::from_env() {
+        let client = orcid_client().map_err(AppError::internal)?;
+        remove_user_works(&pool, &config, &client, user.id)
+            .await
+            .map_err(AppError::internal)?;
+    }
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM orcid_work_syncs WHERE user_id = ?")
+        .bind(user.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    let result = sqlx::query("DELETE FROM user_orcid_links WHERE user_id = ?")
+        .bind(user.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(not_linked());
+    }
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn sync_orcid_now(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<Json<OrcidSyncStatus>, AppError> {
+    let user = extract_current_user(&pool, &headers).await?;
+    let config = require_config()?;
+
+    let linked: Option<i64> =
+        sqlx::query_scalar("SELECT user_id FROM user_orcid_links WHERE user_id = ?")
+            .bind(user.id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?;
+    if linked.is_none() {
+        return Err(not_linked());
+    }
+
+    let client = orcid_client().map_err(AppError::internal)?;
+    sync_user_works(&pool, &config, &client, user.id)
+        .await
+        .map_err(|error| AppError::bad_gateway(format!("ORCID sync failed: {}", error)))?;
+
+    load_status(&pool, user.id).await.map(Json)
+}
+
+async fn load_status(pool: &MySqlPool, user_id: i64) -> Result<OrcidSyncStatus, AppError> {
+    let link = sqlx::query_as::<_, OrcidLinkRow>(
+        "SELECT orcid_id, sync_enabled, last_synced_at, last_sync_error FROM user_orcid_links WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?;
+    let Some(link) = link else {
+        return Ok(OrcidSyncStatus {
+            linked: false,
+            orcid_id: None,
+            sync_enabled: false,
+            last_synced_at: None,
+            last_sync_error: None,
+            works: Vec::new(),
+        });
+    };
+
+    let works = sqlx::query_as::<_, OrcidWorkSync>(
+        r#"
+        SELECT s.post_id, p.title, s.put_code, s.status, s.last_error, s.synced_at
+        FROM orcid_work_syncs s
+        LEFT JOIN posts p ON p.id = s.post_id
+        WHERE s.user_id = ?
+        ORDER BY s.post_id DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(OrcidSyncStatus {
+        linked: true,
+        orcid_id: Some(link.orcid_id),
+        sync_enabled: link.sync_enabled,
+        last_synced_at: link.last_synced_at,
+        last_sync_error: link.last_sync_error,
+        works,
+    })
+}
+
+fn require_config() -> Result<OrcidConfig, AppError> {
+    OrcidConfig::from_env()
+        .ok_or_else(|| AppError::service_unavailable("ORCID integration is not configured"))
+}
+
+/// Derived from SECRET_KEY but distinct from it, so a state token can never
+/// pass as a login token.
+fn state_secret() -> Vec<u8> {
+    let secret = std::env::var("SECRET_KEY").expect("SECRET_KEY must be set in .env");
+    format!("{}:orcid-state", secret).into_bytes()
+}
+
+fn not_linked() -> AppError {
+    AppError::not_found("No ORCID iD is linked")
+}
@@ -0,0 +1,290 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post, put},
+};
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::audit_log::{
+    AUDIT_ACTION_WEBHOOK_CREATE, AUDIT_ACTION_WEBHOOK_DELETE, AUDIT_ACTION_WEBHOOK_UPDATE,
+    AUDIT_TARGET_OUTGOING_WEBHOOK, AuditEvent, RequestMetadata, record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    CreateOutgoingWebhook, OutgoingWebhook, OutgoingWebhookListResponse, UpdateOutgoingWebhook,
+};
+use crate::outgoing_webhooks::{OUTGOING_WEBHOOK_SELECT, send_test_message, validate_webhook_url};
+use crate::routes::admin::extract_admin_user;
+
+const MAX_WEBHOOK_NAME_CHARS: usize = 100;
+const MAX_WEBHOOK_URL_CHARS: usize = 512;
+
+/// Admin Slack / Discord integration routes, nested under `/api/admin`.
+pub fn outgoing_webhook_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route(
+            "/integrations/webhooks",
+            get(list_webhooks).post(create_webhook),
+        )
+        .route(
+            "/integrations/webhooks/{webhook_id}",
+            put(update_webhook).delete(delete_webhook),
+        )
+        .route(
+            "/integrations/webhooks/{webhook_id}/test",
+            post(test_webhook),
+        )
+}
+
+async fn list_webhooks(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let webhooks = sqlx::query_as::<_, OutgoingWebhook>(&format!(
+        "{} ORDER BY id ASC",
+        OUTGOING_WEBHOOK_SELECT
+    ))
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(OutgoingWebhookListResponse { webhooks }))
+}
+
+async fn create_webhook(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateOutgoingWebhook>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let name = normalize_name(&input.name)?;
+    let platform = input.platform.trim().to_ascii_lowercase();
+    let url = normalize_url(&platform, &input.url)?;
+    let is_active = input.is_active.unwrap_or(true);
+    let on_submission = input.on_submission.unwrap_or(true);
+    let on_review_failure = input.on_review_failure.unwrap_or(true);
+    let on_content_report = input.on_content_report.unwrap_or(true);
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let result = sqlx::query(
+        r#"
+        INSERT INTO outgoing_webhooks
+            (name, platform, url, is_active, on_submission, on_review_failure,
+             on_content_report, created_by, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&name)
+    .bind(&platform)
+    .bind(&url)
+    .bind(is_active)
+    .bind(on_submission)
+    .bind(on_review_failure)
+    .bind(on_content_report)
+    .bind(admin.id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let webhook_id = result.last_insert_id() as i64;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_WEBHOOK_CREATE,
+            target_type: AUDIT_TARGET_OUTGOING_WEBHOOK,
+            target_id: webhook_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!({
+                "name": name,
+                "platform": platform,
+                "is_active": is_active,
+                "on_submission": on_submission,
+                "on_review_failure": on_review_failure,
+                "on_content_report": on_content_report,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let webhook = fetch_webhook(&pool, webhook_id).await?;
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+/// Changes the given fields. A new URL must belong to the webhook's
+/// platform; the platform itself cannot change.
+async fn update_webhook(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(webhook_id): Path<i64>,
+    Json(input): Json<UpdateOutgoingWebhook>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let current = fetch_webhook(&pool, webhook_id).await?;
+
+    let name = match input.name.as_deref() {
+        Some(name) => normalize_name(name)?,
+        None => current.name.clone(),
+    };
+    let url = match input.url.as_deref() {
+        Some(url) => normalize_url(&current.platform, url)?,
+        None => current.url.clone(),
+    };
+    let is_active = input.is_active.unwrap_or(current.is_active);
+    let on_submission = input.on_submission.unwrap_or(current.on_submission);
+    let on_review_failure = input.on_review_failure.unwrap_or(current.on_review_failure);
+    let on_content_report = input.on_content_report.unwrap_or(current.on_content_report);
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        r#"
+        UPDATE outgoing_webhooks
+        SET name = ?, url = ?, is_active = ?, on_submission = ?, on_review_failure = ?,
+            on_content_report = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&name)
+    .bind(&url)
+    .bind(is_active)
+    .bind(on_submission)
+    .bind(on_review_failure)
+    .bind(on_content_report)
+    .bind(Utc::now())
+    .bind(webhook_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_WEBHOOK_UPDATE,
+            target_type: AUDIT_TARGET_OUTGOING_WEBHOOK,
+            target_id: webhook_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(webhook_snapshot(&current)),
+            after: Some(serde_json::json!({
+                "name": name,
+                "platform": current.platform,
+                "url_changed": url != current.url,
+                "is_active": is_active,
+                "on_submission": on_submission,
+                "on_review_failure": on_review_failure,
+                "on_content_report": on_content_report,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let webhook = fetch_webhook(&pool, webhook_id).await?;
+    Ok(Json(webhook))
+}
+
+async fn delete_webhook(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(webhook_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let current = fetch_webhook(&pool, webhook_id).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM outgoing_webhooks WHERE id = ?")
+        .bind(webhook_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_WEBHOOK_DELETE,
+            target_type: AUDIT_TARGET_OUTGOING_WEBHOOK,
+            target_id: webhook_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(webhook_snapshot(&current)),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({"detail": "Webhook deleted"})))
+}
+
+/// Posts a test message, even to an inactive webhook, and reports whether
+/// the platform accepted it.
+async fn test_webhook(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(webhook_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let webhook = fetch_webhook(&pool, webhook_id).await?;
+
+    send_test_message(&pool, &webhook)
+        .await
+        .map_err(|error| AppError::bad_gateway(format!("Test message failed: {}", error)))?;
+
+    Ok(Json(serde_json::json!({"detail": "Test message sent"})))
+}
+
+async fn fetch_webhook(pool: &MySqlPool, webhook_id: i64) -> Result<OutgoingWebhook, AppError> {
+    sqlx::query_as::<_, OutgoingWebhook>(&format!("{} WHERE id = ?", OUTGOING_WEBHOOK_SELECT))
+        .bind(webhook_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Webhook not found"))
+}
+
+fn webhook_snapshot(webhook: &OutgoingWebhook) -> serde_json::Value {
+    serde_json::json!({
+        "name": webhook.name,
+        "platform": webhook.platform,
+        "is_active": webhook.is_active,
+        "on_submission": webhook.on_submission,
+        "on_review_failure": webhook.on_review_failure,
+        "on_content_report": webhook.on_content_report,
+    })
+}
+
+fn normalize_name(raw: &str) -> Result<String, AppError> {
+    let name = raw.trim();
+    if name.is_empty() || name.chars().count() > MAX_WEBHOOK_NAME_CHARS {
+        return Err(AppError::bad_request(format!(
+            "name must be 1 to {} characters",
+            MAX_WEBHOOK_NAME_CHARS
+        )));
+    }
+    Ok(name.to_string())
+}
+
+fn normalize_url(platform: &str, raw: &str) -> Result<String, AppError> {
+    let url = raw.trim();
+    let result = if url.chars().count() > MAX_WEBHOOK_URL_CHARS {
+        Err(format!(
+            "url must be at most {} characters",
+            MAX_WEBHOOK_URL_CHARS
+        ))
+    } else {
+        validate_webhook_url(platform, url)
+    };
+    result.map_err(AppError::bad_request)?;
+    Ok(url.to_string())
+}
@@ -1,19 +1,43 @@
+use std::collections::HashMap;
+
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
-    routing::{delete, get},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch},
 };
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use sqlx::{FromRow, MySqlPool};
 
+use crate::error::AppError;
 use crate::models::{
-    CreateReviewComment, PaperVersion, PaperVersionListResponse, PaperVersionResponse,
-    ReviewComment, ReviewCommentListResponse, ReviewCommentResponse, User, UserResponse,
+    CommentMention, CreateReviewComment, PAPER_STATUS_PUBLISHED, PaperStatusHistoryResponse,
+    PaperStatusTransition, PaperTimelineEvent, PaperTimelineResponse, PaperVersion,
+    PaperVersionListResponse, PaperVersionResponse, REVIEW_COMMENT_RESOLUTION_ADDRESSED,
+    REVIEW_COMMENT_RESOLUTION_OPEN, REVIEW_COMMENT_RESOLUTION_WONT_FIX,
+    REVIEWER_ASSIGNMENT_ACCEPTED, REVIEWER_ASSIGNMENT_DECLINED, REVIEWER_ASSIGNMENT_SUBMITTED,
+    ReviewComment, ReviewCommentAnchor, ReviewCommentAnchorInput, ReviewCommentListResponse,
+    ReviewCommentResponse, TIMELINE_AI_REVIEW_COMPLETED, TIMELINE_AI_REVIEW_FAILED,
+    TIMELINE_AI_REVIEW_REQUESTED, TIMELINE_PUBLISHED, TIMELINE_REVIEW_COMMENT,
+    TIMELINE_STATUS_CHANGED, TIMELINE_VERSION_SUBMITTED, UpdateReviewCommentResolution, User,
+    UserResponse, is_blind_review_active,
+};
+use crate::notifications::{MentionSource, fetch_mentions, record_mentions};
+use crate::rate_limit::{RateLimitedRoute, rate_limited};
+use crate::routes::auth::{ensure_not_suspended, extract_current_user};
+use crate::routes::comments::{
+    check_comment_rate_limit, compute_comment_depths, ensure_not_comment_banned,
+    resolve_reply_parent,
 };
-use crate::routes::auth::extract_current_user;
+use crate::routes::reviewer_assignments::fetch_reviewer_assignment_status;
+
+const MAX_RESOLUTION_NOTE_CHARS: usize = 2_000;
+const MAX_ANCHOR_CHARS: usize = 5_000;
+const TIMELINE_EXCERPT_CHARS: usize = 200;
+/// Shown in place of the paper author while double-blind review is active.
+pub const BLIND_AUTHOR_LABEL: &str = "Anonymous author";
 
 #[derive(Debug, Deserialize)]
 struct VersionListQuery {
@@ -33,9 +57,26 @@ struct PostAccessRow {
     author_id: i64,
     is_published: bool,
     category_code: String,
+    paper_status: String,
+    is_double_blind: bool,
     latest_paper_version_id: Option<i64>,
 }
 
+impl PostAccessRow {
+    /// Whether the paper author must be hidden from this viewer.
+    fn hides_author_from(&self, viewer: &User) -> bool {
+        is_blind_review_active(self.is_double_blind, &self.paper_status)
+            && viewer.id != self.author_id
+            && !viewer.is_admin
+    }
+
+    /// Reviewer anonymity lasts until the paper is published. A preprint is
+    /// public earlier, but its reviewers stay anonymous while review runs.
+    fn allows_anonymous_review(&self) -> bool {
+        self.paper_status != PAPER_STATUS_PUBLISHED
+    }
+}
+
 #[derive(Debug, FromRow)]
 struct ReviewCommentWithAuthorRow {
     comment_id: i64,
@@ -46,6 +87,15 @@ struct ReviewCommentWithAuthorRow {
     content: String,
     is_deleted: bool,
     deleted_at: Option<DateTime<Utc>>,
+    is_anonymous: bool,
+    pseudonym_number: Option<i32>,
+    resolution_status: String,
+    resolved_by: Option<i64>,
+    resolved_at: Option<DateTime<Utc>>,
+    resolution_note: Option<String>,
+    anchor_start: Option<i32>,
+    anchor_end: Option<i32>,
+    anchor_quote: Option<String>,
     comment_created_at: DateTime<Utc>,
     comment_updated_at: Option<DateTime<Utc>>,
     user_id: i64,
@@ -58,6 +108,42 @@ struct ReviewCommentWithAuthorRow {
     user_created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, FromRow)]
+struct TimelineVersionRow {
+    id: i64,
+    version_number: i32,
+    submitted_by: Option<i64>,
+    submitter_username: Option<String>,
+    submitted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct TimelineAiReviewRow {
+    id: i64,
+    paper_version_id: Option<i64>,
+    status_code: String,
+    trigger_code: String,
+    decision_code: Option<String>,
+    overall_score: Option<i32>,
+    error_message: Option<String>,
+    created_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+struct TimelineReviewCommentRow {
+    id: i64,
+    paper_version_id: Option<i64>,
+    author_id: i64,
+    username: String,
+    is_anonymous: bool,
+    pseudonym_number: Option<i32>,
+    parent_comment_id: Option<i64>,
+    content: String,
+    resolution_status: String,
+    created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, FromRow)]
 struct ReviewCommentDeleteTarget {
     id: i64,
@@ -66,6 +152,13 @@ struct ReviewCommentDeleteTarget {
     parent_comment_id: Option<i64>,
 }
 
+#[derive(Debug, FromRow)]
+struct ReviewCommentResolutionTarget {
+    author_id: i64,
+    parent_comment_id: Option<i64>,
+    is_deleted: bool,
+}
+
 #[derive(Debug, Clone, Copy)]
 enum DeleteReviewCommentMode {
     Soft,
@@ -85,11 +178,27 @@ pub fn paper_workflow_routes() -> Router<MySqlPool> {
     Router::new()
         .route("/{post_id}/versions", get(list_paper_versions))
         .route("/{post_id}/versions/latest", get(get_latest_paper_version))
-        .route("/{post_id}/review-comments", get(list_review_comments).post(create_review_comment))
+        .route(
+            "/{post_id}/versions/{version_id}/file",
+            get(download_paper_version_file),
+        )
+        .route("/{post_id}/status-history", get(list_status_history))
+        .route("/{post_id}/timeline", get(get_paper_timeline))
+        .route(
+            "/{post_id}/review-comments",
+            get(list_review_comments).post(rate_limited(
+                RateLimitedRoute::Comment,
+                create_review_comment,
+            )),
+        )
         .route(
             "/{post_id}/review-comments/{comment_id}",
             delete(delete_review_comment),
         )
+        .route(
+            "/{post_id}/review-comments/{comment_id}/resolution",
+            patch(update_review_comment_resolution),
+        )
 }
 
 async fn list_paper_versions(
@@ -97,10 +206,10 @@ async fn list_paper_versions(
     headers: HeaderMap,
     Path(post_id): Path<i64>,
     Query(query): Query<VersionListQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     let post_access = fetch_post_access(&pool, post_id).await?;
-    ensure_paper_author_or_admin(&current_user, &post_access)?;
+    ensure_paper_version_access(&pool, post_id, &current_user, &post_access).await?;
 
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
     let offset = query.offset.unwrap_or(0).max(0);
@@ -117,6 +226,7 @@ async fn list_paper_versions(
             github_url,
             file_path,
             file_name,
+            file_sha256,
             CAST(tags_json AS CHAR) AS tags_json,
             CAST(citations_json AS CHAR) AS citations_json,
             submitted_by,
@@ -133,15 +243,19 @@ async fn list_paper_versions(
     .bind(i64::from(offset))
     .fetch_all(&pool)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM paper_versions WHERE post_id = ?")
         .bind(post_id)
         .fetch_one(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
-    let versions = rows.into_iter().map(map_paper_version).collect();
+    let hide_author = post_access.hides_author_from(&current_user);
+    let versions = rows
+        .into_iter()
+        .map(|row| map_paper_version(row, hide_author))
+        .collect();
     Ok(Json(PaperVersionListResponse {
         versions,
         total,
@@ -154,10 +268,10 @@ async fn get_latest_paper_version(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     let post_access = fetch_post_access(&pool, post_id).await?;
-    ensure_paper_author_or_admin(&current_user, &post_access)?;
+    ensure_paper_version_access(&pool, post_id, &current_user, &post_access).await?;
 
     let row = sqlx::query_as::<_, PaperVersion>(
         r#"
@@ -171,6 +285,7 @@ async fn get_latest_paper_version(
             github_url,
             file_path,
             file_name,
+            file_sha256,
             CAST(tags_json AS CHAR) AS tags_json,
             CAST(citations_json AS CHAR) AS citations_json,
             submitted_by,
@@ -185,15 +300,397 @@ async fn get_latest_paper_version(
     .bind(post_id)
     .fetch_optional(&pool)
     .await
-    .map_err(internal_error)?
-    .ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "No paper version found"})),
-        )
-    })?;
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("No paper version found"))?;
+
+    Ok(Json(map_paper_version(
+        row,
+        post_access.hides_author_from(&current_user),
+    )))
+}
+
+/// Serves the attachment exactly as it was when the version was submitted.
+async fn download_paper_version_file(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, version_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let post_access = fetch_post_access(&pool, post_id).await?;
+    ensure_paper_version_access(&pool, post_id, &current_user, &post_access).await?;
+
+    let not_found = || AppError::not_found("This paper version has no stored attachment");
+    let (file_path, file_name, file_sha256) = sqlx::query_as::<_, (String, String, String)>(
+        r#"
+        SELECT file_path, file_name, file_sha256
+        FROM paper_versions
+        WHERE id = ?
+          AND post_id = ?
+          AND file_path IS NOT NULL
+          AND file_name IS NOT NULL
+          AND file_sha256 IS NOT NULL
+        "#,
+    )
+    .bind(version_id)
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(not_found)?;
+    let data = match tokio::fs::read(&file_path).await {
+        Ok(data) => data,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            tracing::warn!(
+                "Attachment {} of paper version {} is missing from the version store",
+                file_path,
+                version_id
+            );
+            return Err(not_found());
+        }
+        Err(error) => return Err(AppError::internal(error)),
+    };
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                attachment_content_type(&file_name).to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name.replace('"', "")),
+            ),
+            (header::ETAG, format!("\"{}\"", file_sha256)),
+        ],
+        data,
+    ))
+}
 
-    Ok(Json(map_paper_version(row)))
+fn attachment_content_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "txt" => "text/plain; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Every recorded `paper_status` change, newest first. Visible to the author
+/// and admins.
+async fn list_status_history(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Query(query): Query<VersionListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let post_access = fetch_post_access(&pool, post_id).await?;
+    if current_user.id != post_access.author_id && !current_user.is_admin {
+        return Err(AppError::forbidden("Not authorized to view status history"));
+    }
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let transitions = sqlx::query_as::<_, PaperStatusTransition>(
+        r#"
+        SELECT
+            t.id AS id,
+            t.post_id AS post_id,
+            t.actor_id AS actor_id,
+            u.username AS actor_username,
+            t.trigger_source AS trigger_source,
+            t.from_status AS from_status,
+            t.to_status AS to_status,
+            t.created_at AS created_at
+        FROM paper_status_transitions t
+        LEFT JOIN users u ON u.id = t.actor_id
+        WHERE t.post_id = ?
+        ORDER BY t.created_at DESC, t.id DESC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(post_id)
+    .bind(i64::from(limit))
+    .bind(i64::from(offset))
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let (total,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM paper_status_transitions WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    Ok(Json(PaperStatusHistoryResponse {
+        transitions,
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// Everything that happened to a paper, oldest first: submitted versions, AI
+/// review runs, status changes, review comments and publication. Visible to
+/// the author and admins; anonymous reviewers keep their pseudonym.
+async fn get_paper_timeline(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let post_access = fetch_post_access(&pool, post_id).await?;
+    if current_user.id != post_access.author_id && !current_user.is_admin {
+        return Err(AppError::forbidden("Not authorized to view this timeline"));
+    }
+
+    let mut events = Vec::new();
+
+    let versions = sqlx::query_as::<_, TimelineVersionRow>(
+        r#"
+        SELECT
+            v.id AS id,
+            v.version_number AS version_number,
+            v.submitted_by AS submitted_by,
+            u.username AS submitter_username,
+            v.submitted_at AS submitted_at
+        FROM paper_versions v
+        LEFT JOIN users u ON u.id = v.submitted_by
+        WHERE v.post_id = ?
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    events.extend(versions.into_iter().map(|row| PaperTimelineEvent {
+        event_type: TIMELINE_VERSION_SUBMITTED,
+        occurred_at: row.submitted_at,
+        reference_id: Some(row.id),
+        paper_version_id: Some(row.id),
+        actor_id: row.submitted_by,
+        actor_label: row.submitter_username,
+        summary: format!("Version {} submitted", row.version_number),
+        details: serde_json::json!({"version_number": row.version_number}),
+    }));
+
+    let reviews = sqlx::query_as::<_, TimelineAiReviewRow>(
+        r#"
+        SELECT
+            r.id AS id,
+            r.paper_version_id AS paper_version_id,
+            s.code AS status_code,
+            t.code AS trigger_code,
+            d.code AS decision_code,
+            CAST(r.overall_score AS SIGNED) AS overall_score,
+            r.error_message AS error_message,
+            r.created_at AS created_at,
+            r.completed_at AS completed_at
+        FROM post_ai_reviews r
+        JOIN ai_review_statuses s ON s.id = r.status_id
+        JOIN ai_review_triggers t ON t.id = r.trigger_id
+        LEFT JOIN ai_review_decisions d ON d.id = r.decision_id
+        WHERE r.post_id = ?
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    for row in reviews {
+        events.push(PaperTimelineEvent {
+            event_type: TIMELINE_AI_REVIEW_REQUESTED,
+            occurred_at: row.created_at,
+            reference_id: Some(row.id),
+            paper_version_id: row.paper_version_id,
+            actor_id: None,
+            actor_label: None,
+            summary: "AI review requested".to_string(),
+            details: serde_json::json!({"trigger": row.trigger_code}),
+        });
+        let Some(completed_at) = row.completed_at else {
+            continue;
+        };
+        let (event_type, summary, details) = match row.status_code.as_str() {
+            "completed" => (
+                TIMELINE_AI_REVIEW_COMPLETED,
+                match row.decision_code.as_deref() {
+                    Some(decision) => format!("AI review completed: {}", decision),
+                    None => "AI review completed".to_string(),
+                },
+                serde_json::json!({
+                    "decision": row.decision_code,
+                    "overall_score": row.overall_score,
+                }),
+            ),
+            "failed" => (
+                TIMELINE_AI_REVIEW_FAILED,
+                "AI review failed".to_string(),
+                serde_json::json!({"error_message": row.error_message}),
+            ),
+            _ => continue,
+        };
+        events.push(PaperTimelineEvent {
+            event_type,
+            occurred_at: completed_at,
+            reference_id: Some(row.id),
+            paper_version_id: row.paper_version_id,
+            actor_id: None,
+            actor_label: None,
+            summary,
+            details,
+        });
+    }
+
+    let transitions = sqlx::query_as::<_, PaperStatusTransition>(
+        r#"
+        SELECT
+            t.id AS id,
+            t.post_id AS post_id,
+            t.actor_id AS actor_id,
+            u.username AS actor_username,
+            t.trigger_source AS trigger_source,
+            t.from_status AS from_status,
+            t.to_status AS to_status,
+            t.created_at AS created_at
+        FROM paper_status_transitions t
+        LEFT JOIN users u ON u.id = t.actor_id
+        WHERE t.post_id = ?
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let last_published_at = transitions
+        .iter()
+        .filter(|row| row.to_status == PAPER_STATUS_PUBLISHED)
+        .map(|row| row.created_at)
+        .max();
+    events.extend(transitions.into_iter().map(|row| PaperTimelineEvent {
+        event_type: TIMELINE_STATUS_CHANGED,
+        occurred_at: row.created_at,
+        reference_id: Some(row.id),
+        paper_version_id: None,
+        actor_id: row.actor_id,
+        actor_label: row.actor_username,
+        summary: match &row.from_status {
+            Some(from_status) => {
+                format!("Status changed from {} to {}", from_status, row.to_status)
+            }
+            None => format!("Created as {}", row.to_status),
+        },
+        details: serde_json::json!({
+            "from_status": row.from_status,
+            "to_status": row.to_status,
+            "trigger_source": row.trigger_source,
+        }),
+    }));
+
+    let comments = sqlx::query_as::<_, TimelineReviewCommentRow>(
+        r#"
+        SELECT
+            rc.id AS id,
+            rc.paper_version_id AS paper_version_id,
+            rc.author_id AS author_id,
+            u.username AS username,
+            rc.is_anonymous AS is_anonymous,
+            ps.pseudonym_number AS pseudonym_number,
+            rc.parent_comment_id AS parent_comment_id,
+            rc.content AS content,
+            rc.resolution_status AS resolution_status,
+            rc.created_at AS created_at
+        FROM paper_review_comments rc
+        JOIN users u ON u.id = rc.author_id
+        LEFT JOIN review_comment_pseudonyms ps
+            ON ps.post_id = rc.post_id AND ps.user_id = rc.author_id
+        WHERE rc.post_id = ? AND rc.is_deleted = FALSE
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    for row in comments {
+        let hide_identity = row.is_anonymous
+            && post_access.allows_anonymous_review()
+            && !current_user.is_admin
+            && current_user.id != row.author_id;
+        let (actor_id, actor_label) = if hide_identity {
+            (None, review_pseudonym(row.pseudonym_number.unwrap_or(0)))
+        } else {
+            (Some(row.author_id), row.username)
+        };
+        let mut excerpt: String = row.content.chars().take(TIMELINE_EXCERPT_CHARS).collect();
+        if row.content.chars().count() > TIMELINE_EXCERPT_CHARS {
+            excerpt.push('…');
+        }
+        events.push(PaperTimelineEvent {
+            event_type: TIMELINE_REVIEW_COMMENT,
+            occurred_at: row.created_at,
+            reference_id: Some(row.id),
+            paper_version_id: row.paper_version_id,
+            actor_id,
+            actor_label: Some(actor_label.clone()),
+            summary: if row.parent_comment_id.is_some() {
+                format!("{} replied to a review comment", actor_label)
+            } else {
+                format!("{} left a review comment", actor_label)
+            },
+            details: serde_json::json!({
+                "excerpt": excerpt,
+                "parent_comment_id": row.parent_comment_id,
+                "resolution_status": row.resolution_status,
+            }),
+        });
+    }
+
+    let (published_at, is_preprint): (Option<DateTime<Utc>>, bool) =
+        sqlx::query_as("SELECT published_at, is_preprint FROM posts WHERE id = ?")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+    // A preprint keeps its posting date when the paper is later published,
+    // so the two are separate events.
+    let preprint_at = published_at.filter(|_| is_preprint);
+    let journal_published_at = (post_access.paper_status == PAPER_STATUS_PUBLISHED)
+        .then(|| last_published_at.or(published_at))
+        .flatten();
+    for (occurred_at, as_preprint) in [(preprint_at, true), (journal_published_at, false)] {
+        let Some(occurred_at) = occurred_at else {
+            continue;
+        };
+        events.push(PaperTimelineEvent {
+            event_type: TIMELINE_PUBLISHED,
+            occurred_at,
+            reference_id: None,
+            paper_version_id: None,
+            actor_id: None,
+            actor_label: None,
+            summary: if as_preprint {
+                "Posted as a preprint".to_string()
+            } else {
+                "Published".to_string()
+            },
+            details: serde_json::json!({"is_preprint": as_preprint}),
+        });
+    }
+
+    // Stable, so events recorded at the same instant keep the order above.
+    events.sort_by_key(|event| event.occurred_at);
+
+    Ok(Json(PaperTimelineResponse { post_id, events }))
 }
 
 async fn list_review_comments(
@@ -201,10 +698,10 @@ async fn list_review_comments(
     headers: HeaderMap,
     Path(post_id): Path<i64>,
     Query(query): Query<ReviewCommentListQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     let post_access = fetch_post_access(&pool, post_id).await?;
-    ensure_review_comment_access(&current_user, &post_access)?;
+    ensure_review_comment_access(&pool, post_id, &current_user, &post_access).await?;
 
     let target_version_id =
         resolve_target_version_id(&pool, post_id, post_access.latest_paper_version_id, query.paper_version_id)
@@ -223,6 +720,15 @@ async fn list_review_comments(
             rc.content AS content,
             rc.is_deleted AS is_deleted,
             rc.deleted_at AS deleted_at,
+            rc.is_anonymous AS is_anonymous,
+            ps.pseudonym_number AS pseudonym_number,
+            rc.resolution_status AS resolution_status,
+            rc.resolved_by AS resolved_by,
+            rc.resolved_at AS resolved_at,
+            rc.resolution_note AS resolution_note,
+            rc.anchor_start AS anchor_start,
+            rc.anchor_end AS anchor_end,
+            rc.anchor_quote AS anchor_quote,
             rc.created_at AS comment_created_at,
             rc.updated_at AS comment_updated_at,
             u.id AS user_id,
@@ -235,6 +741,8 @@ async fn list_review_comments(
             u.created_at AS user_created_at
         FROM paper_review_comments rc
         JOIN users u ON u.id = rc.author_id
+        LEFT JOIN review_comment_pseudonyms ps
+            ON ps.post_id = rc.post_id AND ps.user_id = rc.author_id
         WHERE rc.post_id = ? AND rc.paper_version_id <=> ?
         ORDER BY rc.created_at ASC
         LIMIT ? OFFSET ?
@@ -246,7 +754,7 @@ async fn list_review_comments(
     .bind(i64::from(offset))
     .fetch_all(&pool)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     let (total,): (i64,) = sqlx::query_as(
         "SELECT COUNT(*) FROM paper_review_comments WHERE post_id = ? AND paper_version_id <=> ?",
@@ -255,9 +763,42 @@ async fn list_review_comments(
     .bind(target_version_id)
     .fetch_one(&pool)
     .await
-    .map_err(internal_error)?;
-
-    let comments = rows.into_iter().map(map_review_comment_row).collect();
+    .map_err(AppError::internal)?;
+
+    let comment_ids: Vec<i64> = rows
+        .iter()
+        .filter(|row| !row.is_deleted)
+        .map(|row| row.comment_id)
+        .collect();
+    let mut mentions = fetch_mentions(&pool, MentionSource::ReviewComment, &comment_ids)
+        .await
+        .map_err(AppError::internal)?;
+    let edges: Vec<(i64, Option<i64>)> = sqlx::query_as(
+        "SELECT id, parent_comment_id FROM paper_review_comments WHERE post_id = ? AND paper_version_id <=> ?",
+    )
+    .bind(post_id)
+    .bind(target_version_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let depths = compute_comment_depths(&edges);
+
+    let blind_author_id = post_access
+        .hides_author_from(&current_user)
+        .then_some(post_access.author_id);
+    let comments = rows
+        .into_iter()
+        .map(|row| {
+            map_review_comment_row(
+                row,
+                &mut mentions,
+                &depths,
+                &current_user,
+                post_access.allows_anonymous_review(),
+                blind_author_id,
+            )
+        })
+        .collect();
     Ok(Json(ReviewCommentListResponse {
         comments,
         total,
@@ -271,22 +812,25 @@ async fn create_review_comment(
     headers: HeaderMap,
     Path(post_id): Path<i64>,
     Json(input): Json<CreateReviewComment>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Response, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     let post_access = fetch_post_access(&pool, post_id).await?;
-    ensure_review_comment_access(&current_user, &post_access)?;
+    ensure_review_comment_access(&pool, post_id, &current_user, &post_access).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    ensure_not_comment_banned(&pool, current_user.id).await?;
 
     let content = input.content.trim();
     if content.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Comment content is required"})),
-        ));
+        return Err(AppError::bad_request("Comment content is required"));
     }
 
-    let target_version_id =
-        resolve_target_version_id(&pool, post_id, post_access.latest_paper_version_id, input.paper_version_id)
-            .await?;
+    let target_version_id = resolve_target_version_id(
+        &pool,
+        post_id,
+        post_access.latest_paper_version_id,
+        input.paper_version_id,
+    )
+    .await?;
 
     if let Some(parent_comment_id) = input.parent_comment_id {
         let parent_row = sqlx::query_as::<_, (i64, Option<i64>)>(
@@ -295,29 +839,74 @@ async fn create_review_comment(
         .bind(parent_comment_id)
         .fetch_optional(&pool)
         .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Parent review comment not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Parent review comment not found"))?;
 
         if parent_row.0 != post_id {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"detail": "Parent comment does not belong to this post"})),
+            return Err(AppError::bad_request(
+                "Parent comment does not belong to this post",
             ));
         }
 
         if parent_row.1 != target_version_id {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"detail": "Parent comment belongs to a different paper version"})),
+            return Err(AppError::bad_request(
+                "Parent comment belongs to a different paper version",
             ));
         }
     }
 
+    let (parent_comment_id, depth) =
+        resolve_reply_parent(&pool, "paper_review_comments", input.parent_comment_id)
+            .await
+            .map_err(AppError::internal)?;
+
+    // Replies belong to their thread's passage, so only top-level comments
+    // carry an anchor.
+    let anchor = match input.anchor.as_ref() {
+        Some(_) if parent_comment_id.is_some() => {
+            return Err(AppError::bad_request(
+                "Replies cannot be anchored to the manuscript",
+            ));
+        }
+        Some(anchor_input) => {
+            let version_content: String =
+                sqlx::query_scalar("SELECT content FROM paper_versions WHERE id = ?")
+                    .bind(target_version_id)
+                    .fetch_one(&pool)
+                    .await
+                    .map_err(AppError::internal)?;
+            let anchor = resolve_review_comment_anchor(&version_content, anchor_input)
+                .map_err(AppError::bad_request)?;
+            Some(anchor)
+        }
+        None => None,
+    };
+
+    let is_anonymous = input.anonymous.unwrap_or(false);
+    if is_anonymous && !post_access.allows_anonymous_review() {
+        return Err(AppError::bad_request(
+            "Anonymous review comments are only allowed before publication",
+        ));
+    }
+    if is_anonymous && current_user.id == post_access.author_id {
+        return Err(AppError::bad_request(
+            "Paper authors cannot comment anonymously",
+        ));
+    }
+
+    if let Some(response) = check_comment_rate_limit(current_user.id) {
+        return Ok(response);
+    }
+
+    let pseudonym = if is_anonymous {
+        let number = assign_review_pseudonym(&pool, post_id, current_user.id)
+            .await
+            .map_err(AppError::internal)?;
+        Some(review_pseudonym(number))
+    } else {
+        None
+    };
+
     let now = Utc::now();
     let insert = sqlx::query(
         r#"
@@ -329,25 +918,59 @@ async fn create_review_comment(
             content,
             is_deleted,
             deleted_at,
+            is_anonymous,
+            anchor_start,
+            anchor_end,
+            anchor_quote,
             created_at
-        ) VALUES (?, ?, ?, ?, ?, FALSE, NULL, ?)
+        ) VALUES (?, ?, ?, ?, ?, FALSE, NULL, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(post_id)
     .bind(target_version_id)
     .bind(current_user.id)
-    .bind(input.parent_comment_id)
+    .bind(parent_comment_id)
     .bind(content)
+    .bind(is_anonymous)
+    .bind(anchor.as_ref().map(|anchor| anchor.start))
+    .bind(anchor.as_ref().map(|anchor| anchor.end))
+    .bind(anchor.as_ref().map(|anchor| anchor.quote.as_str()))
     .bind(now)
     .execute(&pool)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
+
+    let comment =
+        sqlx::query_as::<_, ReviewComment>("SELECT * FROM paper_review_comments WHERE id = ?")
+            .bind(insert.last_insert_id() as i64)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+
+    // Mention notifications for anonymous comments carry only the pseudonym,
+    // and those from the author of a double-blind paper carry no name at all.
+    let actor = match &pseudonym {
+        Some(label) => (None, label.as_str()),
+        None if current_user.id == post_access.author_id
+            && is_blind_review_active(post_access.is_double_blind, &post_access.paper_status) =>
+        {
+            (None, BLIND_AUTHOR_LABEL)
+        }
+        None => (Some(current_user.id), current_user.username.as_str()),
+    };
 
-    let comment = sqlx::query_as::<_, ReviewComment>("SELECT * FROM paper_review_comments WHERE id = ?")
-        .bind(insert.last_insert_id() as i64)
-        .fetch_one(&pool)
-        .await
-        .map_err(internal_error)?;
+    // Unpublished papers are only visible to their author, admins and
+    // assigned reviewers, so nobody else can be pulled in by a mention.
+    let mentions = record_mentions(
+        &pool,
+        MentionSource::ReviewComment,
+        comment.id,
+        post_id,
+        actor,
+        &comment.content,
+        (!post_access.is_published).then_some(post_access.author_id),
+    )
+    .await;
 
     Ok((
         StatusCode::CREATED,
@@ -357,33 +980,38 @@ async fn create_review_comment(
             paper_version_id: comment.paper_version_id,
             author_id: comment.author_id,
             parent_comment_id: comment.parent_comment_id,
+            depth,
             author: UserResponse::from(current_user),
             content: comment.content,
             is_deleted: comment.is_deleted,
             deleted_at: comment.deleted_at,
+            is_anonymous: comment.is_anonymous,
+            pseudonym,
+            resolution_status: comment.resolution_status,
+            resolved_by: comment.resolved_by,
+            resolved_at: comment.resolved_at,
+            resolution_note: comment.resolution_note,
+            anchor,
             created_at: comment.created_at,
             updated_at: comment.updated_at,
+            mentions,
         }),
-    ))
+    )
+        .into_response())
 }
 
 async fn delete_review_comment(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path((post_id, comment_id)): Path<(i64, i64)>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     let post_access = fetch_post_access(&pool, post_id).await?;
 
     let comment = find_review_comment_target(&pool, comment_id, post_id)
         .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Review comment not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Review comment not found"))?;
 
     let can_delete = if post_access.is_published {
         current_user.is_admin || current_user.id == comment.author_id
@@ -394,15 +1022,14 @@ async fn delete_review_comment(
     };
 
     if !can_delete {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({"detail": "Not authorized to delete this review comment"})),
+        return Err(AppError::forbidden(
+            "Not authorized to delete this review comment",
         ));
     }
 
     let delete_mode = apply_review_comment_delete_policy(&pool, &comment)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     Ok(Json(serde_json::json!({
         "message": "Review comment deleted successfully",
@@ -410,61 +1037,165 @@ async fn delete_review_comment(
     })))
 }
 
-async fn fetch_post_access(
-    pool: &MySqlPool,
-    post_id: i64,
-) -> Result<PostAccessRow, (StatusCode, Json<serde_json::Value>)> {
+/// Marks a top-level review comment as addressed or won't-fix, or reopens
+/// it. The paper author, the commenter and admins may change it.
+async fn update_review_comment_resolution(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, comment_id)): Path<(i64, i64)>,
+    Json(input): Json<UpdateReviewCommentResolution>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let post_access = fetch_post_access(&pool, post_id).await?;
+    ensure_review_comment_access(&pool, post_id, &current_user, &post_access).await?;
+
+    let resolution_status = match input.resolution_status.trim() {
+        REVIEW_COMMENT_RESOLUTION_OPEN => REVIEW_COMMENT_RESOLUTION_OPEN,
+        REVIEW_COMMENT_RESOLUTION_ADDRESSED => REVIEW_COMMENT_RESOLUTION_ADDRESSED,
+        REVIEW_COMMENT_RESOLUTION_WONT_FIX => REVIEW_COMMENT_RESOLUTION_WONT_FIX,
+        _ => {
+            return Err(AppError::bad_request(
+                "resolution_status must be one of: open, addressed, wont_fix",
+            ));
+        }
+    };
+
+    let resolution_note = input
+        .resolution_note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+    if resolution_note.is_some_and(|note| note.chars().count() > MAX_RESOLUTION_NOTE_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "Resolution note must be at most {} characters",
+            MAX_RESOLUTION_NOTE_CHARS
+        )));
+    }
+
+    let target = sqlx::query_as::<_, ReviewCommentResolutionTarget>(
+        "SELECT author_id, parent_comment_id, is_deleted FROM paper_review_comments WHERE id = ? AND post_id = ?",
+    )
+    .bind(comment_id)
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| {
+        AppError::not_found("Review comment not found")
+    })?;
+
+    if current_user.id != post_access.author_id
+        && current_user.id != target.author_id
+        && !current_user.is_admin
+    {
+        return Err(AppError::forbidden(
+            "Not authorized to resolve this review comment",
+        ));
+    }
+
+    if target.parent_comment_id.is_some() || target.is_deleted {
+        return Err(AppError::bad_request(
+            "Only top-level review comments that are not deleted can be resolved",
+        ));
+    }
+
+    let now = Utc::now();
+    // Reopening clears the previous resolver and note.
+    let (resolved_by, resolved_at, resolution_note) =
+        if resolution_status == REVIEW_COMMENT_RESOLUTION_OPEN {
+            (None, None, None)
+        } else {
+            (Some(current_user.id), Some(now), resolution_note)
+        };
+
+    sqlx::query(
+        r#"
+        UPDATE paper_review_comments
+        SET resolution_status = ?, resolved_by = ?, resolved_at = ?, resolution_note = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(resolution_status)
+    .bind(resolved_by)
+    .bind(resolved_at)
+    .bind(resolution_note)
+    .bind(comment_id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({
+        "id": comment_id,
+        "post_id": post_id,
+        "resolution_status": resolution_status,
+        "resolved_by": resolved_by,
+        "resolved_at": resolved_at,
+        "resolution_note": resolution_note
+    })))
+}
+
+async fn fetch_post_access(pool: &MySqlPool, post_id: i64) -> Result<PostAccessRow, AppError> {
     let row = sqlx::query_as::<_, PostAccessRow>(
         r#"
         SELECT
             p.author_id AS author_id,
             p.is_published AS is_published,
             c.code AS category_code,
+            p.paper_status AS paper_status,
+            p.is_double_blind AS is_double_blind,
             p.latest_paper_version_id AS latest_paper_version_id
         FROM posts p
         JOIN post_categories c ON c.id = p.category_id
-        WHERE p.id = ?
+        WHERE p.id = ? AND p.deleted_at IS NULL
         "#,
     )
     .bind(post_id)
     .fetch_optional(pool)
     .await
-    .map_err(internal_error)?
-    .ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Post not found"})),
-        )
-    })?;
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
 
     if row.category_code != "paper" {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Paper workflow is only available for paper posts"})),
+        return Err(AppError::bad_request(
+            "Paper workflow is only available for paper posts",
         ));
     }
 
     Ok(row)
 }
 
-fn ensure_paper_author_or_admin(
+/// Authors, admins and reviewers who have not declined an invitation can
+/// read the submitted versions.
+async fn ensure_paper_version_access(
+    pool: &MySqlPool,
+    post_id: i64,
     current_user: &User,
     post_access: &PostAccessRow,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<(), AppError> {
     if current_user.id == post_access.author_id || current_user.is_admin {
         return Ok(());
     }
 
-    Err((
-        StatusCode::FORBIDDEN,
-        Json(serde_json::json!({"detail": "Not authorized to access paper versions"})),
+    let assignment_status = fetch_reviewer_assignment_status(pool, post_id, current_user.id)
+        .await
+        .map_err(AppError::internal)?;
+    if assignment_status.is_some_and(|status| status != REVIEWER_ASSIGNMENT_DECLINED) {
+        return Ok(());
+    }
+
+    Err(AppError::forbidden(
+        "Not authorized to access paper versions",
     ))
 }
 
-fn ensure_review_comment_access(
+/// Unpublished papers are open to their author, admins and reviewers who
+/// accepted an assignment.
+async fn ensure_review_comment_access(
+    pool: &MySqlPool,
+    post_id: i64,
     current_user: &User,
     post_access: &PostAccessRow,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<(), AppError> {
     if post_access.is_published {
         return Ok(());
     }
@@ -473,9 +1204,18 @@ fn ensure_review_comment_access(
         return Ok(());
     }
 
-    Err((
-        StatusCode::FORBIDDEN,
-        Json(serde_json::json!({"detail": "Not authorized to access review comments for this paper"})),
+    let assignment_status = fetch_reviewer_assignment_status(pool, post_id, current_user.id)
+        .await
+        .map_err(AppError::internal)?;
+    if matches!(
+        assignment_status.as_deref(),
+        Some(REVIEWER_ASSIGNMENT_ACCEPTED | REVIEWER_ASSIGNMENT_SUBMITTED)
+    ) {
+        return Ok(());
+    }
+
+    Err(AppError::forbidden(
+        "Not authorized to access review comments for this paper",
     ))
 }
 
@@ -484,13 +1224,10 @@ async fn resolve_target_version_id(
     post_id: i64,
     latest_version_id: Option<i64>,
     requested_version_id: Option<i64>,
-) -> Result<Option<i64>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Option<i64>, AppError> {
     let target_version_id = requested_version_id.or(latest_version_id);
     let Some(version_id) = target_version_id else {
-        return Err((
-            StatusCode::CONFLICT,
-            Json(serde_json::json!({"detail": "No submitted revision available"})),
-        ));
+        return Err(AppError::conflict("No submitted revision available"));
     };
 
     let exists = sqlx::query("SELECT id FROM paper_versions WHERE id = ? AND post_id = ?")
@@ -498,19 +1235,16 @@ async fn resolve_target_version_id(
         .bind(post_id)
         .fetch_optional(pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     if exists.is_none() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Paper version not found"})),
-        ));
+        return Err(AppError::not_found("Paper version not found"));
     }
 
     Ok(Some(version_id))
 }
 
-fn map_paper_version(version: PaperVersion) -> PaperVersionResponse {
+fn map_paper_version(version: PaperVersion, hide_author: bool) -> PaperVersionResponse {
     PaperVersionResponse {
         id: version.id,
         post_id: version.post_id,
@@ -519,17 +1253,48 @@ fn map_paper_version(version: PaperVersion) -> PaperVersionResponse {
         content: version.content,
         summary: version.summary,
         github_url: version.github_url,
-        file_path: version.file_path,
+        file_url: version.file_sha256.as_ref().map(|_| {
+            format!(
+                "/api/v1/posts/{}/versions/{}/file",
+                version.post_id, version.id
+            )
+        }),
         file_name: version.file_name,
+        file_sha256: version.file_sha256,
         tags: parse_string_list_json(version.tags_json),
         citations: parse_i64_list_json(version.citations_json),
-        submitted_by: version.submitted_by,
+        submitted_by: version.submitted_by.filter(|_| !hide_author),
         submitted_at: version.submitted_at,
         created_at: version.created_at,
     }
 }
 
-fn map_review_comment_row(row: ReviewCommentWithAuthorRow) -> ReviewCommentResponse {
+fn map_review_comment_row(
+    row: ReviewCommentWithAuthorRow,
+    mentions: &mut HashMap<i64, Vec<CommentMention>>,
+    depths: &HashMap<i64, i32>,
+    viewer: &User,
+    allows_anonymous_review: bool,
+    blind_author_id: Option<i64>,
+) -> ReviewCommentResponse {
+    let pseudonym = row
+        .is_anonymous
+        .then(|| review_pseudonym(row.pseudonym_number.unwrap_or(0)));
+    let hide_identity = row.is_anonymous
+        && allows_anonymous_review
+        && !viewer.is_admin
+        && viewer.id != row.author_id;
+    let hide_paper_author = blind_author_id == Some(row.author_id);
+    let author_id = if hide_identity || hide_paper_author {
+        0
+    } else {
+        row.author_id
+    };
+    // A hidden reviewer who resolved their own comment must stay hidden, and
+    // so must the author of a double-blind paper.
+    let resolved_by = row.resolved_by.filter(|resolver_id| {
+        !(hide_identity && *resolver_id == row.author_id) && blind_author_id != Some(*resolver_id)
+    });
     let author = UserResponse::from(User {
         id: row.user_id,
         username: row.username,
@@ -542,18 +1307,28 @@ fn map_review_comment_row(row: ReviewCommentWithAuthorRow) -> ReviewCommentRespo
         hobbies: None,
         interests: None,
         research_areas: None,
+        affiliation: None,
+        website: None,
+        orcid_id: None,
         avatar_url: row.avatar_url,
         is_admin: row.is_admin,
         created_at: row.user_created_at,
         updated_at: None,
     });
 
+    let author = match &pseudonym {
+        Some(label) if hide_identity => anonymous_author(label, row.comment_created_at),
+        _ if hide_paper_author => anonymous_author(BLIND_AUTHOR_LABEL, row.comment_created_at),
+        _ => author,
+    };
+
     ReviewCommentResponse {
         id: row.comment_id,
         post_id: row.post_id,
         paper_version_id: row.paper_version_id,
-        author_id: row.author_id,
+        author_id,
         parent_comment_id: row.parent_comment_id,
+        depth: depths.get(&row.comment_id).copied().unwrap_or(0),
         author,
         content: if row.is_deleted {
             String::new()
@@ -562,8 +1337,154 @@ fn map_review_comment_row(row: ReviewCommentWithAuthorRow) -> ReviewCommentRespo
         },
         is_deleted: row.is_deleted,
         deleted_at: row.deleted_at,
+        is_anonymous: row.is_anonymous,
+        pseudonym,
+        resolution_status: row.resolution_status,
+        resolved_by,
+        resolved_at: row.resolved_at,
+        resolution_note: row.resolution_note,
+        anchor: match (row.anchor_start, row.anchor_end, row.anchor_quote) {
+            (Some(start), Some(end), Some(quote)) => {
+                Some(ReviewCommentAnchor { start, end, quote })
+            }
+            _ => None,
+        },
         created_at: row.comment_created_at,
         updated_at: row.comment_updated_at,
+        mentions: mentions.remove(&row.comment_id).unwrap_or_default(),
+    }
+}
+
+/// Checks an anchor against the version content and fills in whichever of
+/// the range or the quote was left out.
+fn resolve_review_comment_anchor(
+    content: &str,
+    input: &ReviewCommentAnchorInput,
+) -> Result<ReviewCommentAnchor, String> {
+    let quote = input
+        .quote
+        .as_deref()
+        .filter(|quote| !quote.trim().is_empty());
+    let (start, end) = match (input.start, input.end) {
+        (Some(start), Some(end)) => {
+            let char_count = content.chars().count();
+            if start < 0 || end <= start || end as usize > char_count {
+                return Err(format!(
+                    "Anchor range must satisfy 0 <= start < end <= {}",
+                    char_count
+                ));
+            }
+            (start as usize, end as usize)
+        }
+        (None, None) => {
+            let Some(quote) = quote else {
+                return Err("Anchor requires a character range or a quoted snippet".to_string());
+            };
+            let mut matches = content.match_indices(quote);
+            let Some((byte_start, _)) = matches.next() else {
+                return Err("Quoted snippet was not found in this paper version".to_string());
+            };
+            if matches.next().is_some() {
+                return Err(
+                    "Quoted snippet appears more than once; include a character range".to_string(),
+                );
+            }
+            let start = content[..byte_start].chars().count();
+            (start, start + quote.chars().count())
+        }
+        _ => return Err("Anchor start and end must be given together".to_string()),
+    };
+
+    if end - start > MAX_ANCHOR_CHARS {
+        return Err(format!(
+            "Anchored passage must be at most {} characters",
+            MAX_ANCHOR_CHARS
+        ));
+    }
+
+    let selected: String = content.chars().skip(start).take(end - start).collect();
+    if quote.is_some_and(|quote| quote != selected) {
+        return Err("Quoted snippet does not match the anchored range".to_string());
+    }
+
+    Ok(ReviewCommentAnchor {
+        start: start as i32,
+        end: end as i32,
+        quote: selected,
+    })
+}
+
+fn review_pseudonym(number: i32) -> String {
+    format!("Reviewer {}", number)
+}
+
+/// Placeholder author shown in place of a hidden reviewer or paper author.
+pub fn anonymous_author(pseudonym: &str, created_at: DateTime<Utc>) -> UserResponse {
+    UserResponse {
+        id: 0,
+        username: pseudonym.to_string(),
+        email: String::new(),
+        display_name: Some(pseudonym.to_string()),
+        bio: None,
+        introduction: None,
+        hobbies: None,
+        interests: None,
+        research_areas: None,
+        affiliation: None,
+        website: None,
+        orcid_id: None,
+        avatar_url: None,
+        is_admin: false,
+        created_at,
+        suspension: None,
+        follower_count: None,
+        following_count: None,
+    }
+}
+
+/// Returns the user's pseudonym number on this paper, assigning the next free
+/// number on their first anonymous comment. Numbers never change afterwards.
+async fn assign_review_pseudonym(
+    pool: &MySqlPool,
+    post_id: i64,
+    user_id: i64,
+) -> Result<i32, sqlx::Error> {
+    let mut attempts = 0;
+    loop {
+        let existing: Option<i32> = sqlx::query_scalar(
+            "SELECT pseudonym_number FROM review_comment_pseudonyms WHERE post_id = ? AND user_id = ?",
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+        if let Some(number) = existing {
+            return Ok(number);
+        }
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO review_comment_pseudonyms (post_id, user_id, pseudonym_number, created_at)
+            SELECT ?, ?, COALESCE(MAX(pseudonym_number), 0) + 1, ?
+            FROM review_comment_pseudonyms
+            WHERE post_id = ?
+            "#,
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .bind(Utc::now())
+        .bind(post_id)
+        .execute(pool)
+        .await;
+
+        match inserted {
+            Ok(_) => {}
+            // Another request took the same number; read again and retry.
+            Err(sqlx::Error::Database(error)) if error.is_unique_violation() && attempts < 3 => {
+                attempts += 1;
+            }
+            Err(error) => return Err(error),
+        }
     }
 }
 
@@ -669,10 +1590,3 @@ fn parse_i64_list_json(raw: Option<String>) -> Vec<i64> {
     raw.and_then(|json_text| serde_json::from_str::<Vec<i64>>(&json_text).ok())
         .unwrap_or_default()
 }
-
-fn internal_error<E: ToString>(error: E) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({"detail": error.to_string()})),
-    )
-}
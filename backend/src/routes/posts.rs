@@ -1,43 +1,92 @@
 use axum::{
     Json, Router,
-    extract::{DefaultBodyLimit, Multipart, Path, Query, State, multipart::MultipartError},
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use chrono::{DateTime, Datelike, Utc};
 use regex::Regex;
-use reqwest::{Client, Url};
-use serde::Deserialize;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use sqlx::{MySql, MySqlPool, QueryBuilder};
+use sqlx::{MySql, MySqlPool, QueryBuilder, Transaction};
 use std::{
     collections::{HashMap, HashSet},
     path::{Path as FsPath, PathBuf},
-    time::Duration,
 };
+use utoipa::{IntoParams, OpenApi};
 use uuid::Uuid;
 
 use crate::ai_review::{ReviewTrigger, schedule_review};
-use crate::metrics::{METRIC_VERSION, compute_citation_count, compute_citation_counts_for_posts};
+use crate::audit_log::{
+    AUDIT_ACTION_POST_DELETE, AUDIT_ACTION_POST_PUBLISH, AUDIT_TARGET_POST, AuditEvent,
+    RequestMetadata, record_audit_event, snapshot_post,
+};
+use crate::cache::{
+    CacheKey, fetch_cached, invalidate_cached_metrics, invalidate_cached_post, store_cached,
+};
+use crate::conditional_get::{CachePolicy, conditional_json_ignoring};
+use crate::crossref_cache::lookup_cached_metadata;
+use crate::doi_registration::upsert_registered_doi_metadata;
+use crate::error::{AppError, ErrorBody};
+use crate::feature_flags::{FEATURE_AI_REVIEW_AUTO_SCHEDULE, is_feature_enabled};
+use crate::metrics::{
+    build_post_metrics, fetch_cited_post_ids, refresh_author_metrics_cache,
+    refresh_post_citation_counts,
+};
 use crate::models::{
+    BatchPostRequest, BatchPostResponse, ImportPost, ImportPostResponse, IssueCitation,
     PAPER_STATUS_ACCEPTED, PAPER_STATUS_DRAFT, PAPER_STATUS_PUBLISHED, PAPER_STATUS_REJECTED,
-    PAPER_STATUS_REVISION, PAPER_STATUS_SUBMITTED, Post, PostDoiMetadata, PostListResponse,
-    PostMetrics, PostQuery, PostResponse, User, UserResponse,
+    PAPER_STATUS_REVISION, PAPER_STATUS_SUBMITTED, PatchPost, Post, PostBulkUpdate,
+    PostBulkUpdateResponse, PostDoiMetadata, PostFields, PostImportSource, PostListResponse,
+    PostQuery, PostResponse, PostSummary, PostTagsResponse, REVIEWER_ASSIGNMENT_DECLINED,
+    TagBatchRequest, User, UserResponse, WEBHOOK_EVENT_SUBMISSION, is_blind_review_active,
+};
+use crate::notifications::{notify_followers_of_post, subscribe};
+use crate::outgoing_webhooks::{WebhookMessage, queue_webhook_message};
+use crate::paper_status::{
+    STATUS_TRIGGER_CREATE, STATUS_TRIGGER_PUBLISH, STATUS_TRIGGER_RESTORE, STATUS_TRIGGER_UPDATE,
+    record_status_transition,
+};
+use crate::pdf_export::{PdfDocument, PdfRenderError, paper_license, render_pdf};
+use crate::post_import::{
+    ImportIdentifier, ImportedWork, download_pdf, fetch_imported_work, import_client,
+};
+use crate::rate_limit::{RateLimitedRoute, rate_limited};
+use crate::routes::analytics::{
+    POST_EVENT_LIKE, POST_EVENT_UNLIKE, POST_EVENT_VIEW, record_post_event,
+};
+use crate::routes::auth::{ensure_not_suspended, extract_current_user, extract_optional_user};
+use crate::routes::citations::{
+    fetch_post_citations, prepare_manual_citations, resolve_reference_dois, sync_manual_citations,
+};
+use crate::routes::paper_workflow::{BLIND_AUTHOR_LABEL, anonymous_author};
+use crate::routes::reviewer_assignments::fetch_reviewer_assignment_status;
+use crate::settings::{MAX_UPLOAD_SIZE_MB_CEILING, setting};
+use crate::sparse_fields::{FieldSelection, POST_LARGE_FIELDS};
+use crate::uploads::{StagedUpload, multipart_error, stage_upload};
+use crate::validation::{ValidatedJson, tag_names, validate};
+use crate::version_files::{
+    fetch_version_file_paths, remove_unreferenced_version_files, store_version_file,
 };
-use crate::routes::auth::{extract_current_user, extract_optional_user};
 
-const MAX_UPLOAD_SIZE_BYTES: usize = 10 * 1024 * 1024;
-const MULTIPART_BODY_LIMIT_BYTES: usize = 12 * 1024 * 1024;
-const PAPER_CATEGORY: &str = "paper";
-const CITATION_SOURCE_MANUAL: u8 = 1;
+const DEFAULT_MAX_UPLOAD_SIZE_MB: usize = 10;
+const MULTIPART_BODY_LIMIT_BYTES: usize = (MAX_UPLOAD_SIZE_MB_CEILING as usize + 2) * 1024 * 1024;
+pub const PAPER_CATEGORY: &str = "paper";
+/// Accepted as a category on create and update; stored as a paper with the
+/// preprint flag set.
+pub const PREPRINT_CATEGORY_ALIAS: &str = "preprint";
 const CITATION_SOURCE_AUTO: u8 = 2;
 const POST_SELECT_FROM_CLAUSE: &str = r#"
     FROM posts p
     JOIN post_categories c ON c.id = p.category_id
     LEFT JOIN post_files pf ON pf.post_id = p.id
     LEFT JOIN post_stats ps ON ps.post_id = p.id
+    LEFT JOIN post_retractions pr ON pr.post_id = p.id
 "#;
+/// Every column of `Post`. Posts are only loaded through this list, so a
+/// field added to `Post` is selected by every endpoint at once.
 const POST_SELECT_COLUMNS: &str = r#"
     SELECT
         p.id,
@@ -55,40 +104,121 @@ const POST_SELECT_COLUMNS: &str = r#"
         CAST(p.current_revision AS SIGNED) AS current_revision,
         COALESCE(ps.view_count, 0) AS view_count,
         COALESCE(ps.like_count, 0) AS like_count,
+        COALESCE(ps.citation_count, 0) AS citation_count,
+        COALESCE(ps.external_citation_count, 0) AS external_citation_count,
+        COALESCE(ps.influence_score, 0) AS influence_score,
+        pr.retracted_at,
+        pr.reason AS retraction_reason,
+        p.is_double_blind,
+        p.is_preprint,
         p.created_at,
         p.updated_at
 "#;
+/// Columns of `PostSummary`, for checks that need no content or counters.
+const POST_SUMMARY_SELECT: &str = r#"
+    SELECT
+        p.title,
+        c.code AS category,
+        pf.file_path,
+        p.author_id,
+        p.paper_status
+    FROM posts p
+    JOIN post_categories c ON c.id = p.category_id
+    LEFT JOIN post_files pf ON pf.post_id = p.id
+"#;
 const ALLOWED_UPLOAD_EXTENSIONS: &[&str] = &[
     "pdf", "doc", "docx", "txt", "md", "pptx", "xlsx", "zip", "png", "jpg", "jpeg", "gif",
 ];
-const CROSSREF_API_BASE: &str = "https://api.crossref.org/works/";
 const DOI_PATTERN: &str = r#"(?i)\b10\.\d{4,9}/[-._;()/:A-Z0-9]+"#;
-const DEFAULT_CROSSREF_TIMEOUT_SECS: u64 = 8;
 const DEFAULT_CROSSREF_MAX_DOIS: usize = 10;
 const INTERNAL_DOI_PREFIX: &str = "TM";
+pub const BIBTEX_CONTENT_TYPE: &str = "application/x-bibtex; charset=utf-8";
 const INTERNAL_DOI_HASH_LENGTH: usize = 12;
+const MIN_IDENTITY_NAME_CHARS: usize = 3;
+const DOUBLE_BLIND_FLAG_NAME: &str = "Double-blind review";
+const PREPRINT_FLAG_NAME: &str = "Preprint mode";
 
 pub fn posts_routes() -> Router<MySqlPool> {
     Router::new()
-        .route("/", get(list_posts).post(create_post))
+        .route(
+            "/",
+            get(list_posts).post(rate_limited(RateLimitedRoute::PostCreate, create_post)),
+        )
+        .route(
+            "/import",
+            post(rate_limited(RateLimitedRoute::PostCreate, import_post)),
+        )
+        .route("/batch", post(batch_get_posts))
         .route(
             "/{post_id}",
-            get(get_post).put(update_post).delete(delete_post),
+            get(get_post)
+                .put(update_post)
+                .patch(patch_post)
+                .delete(delete_post),
         )
         .route("/{post_id}/publish", post(publish_post))
+        .route("/{post_id}/tags:batch", post(batch_update_tags))
+        .route("/{post_id}/bulk", post(bulk_update_post))
+        .route(
+            "/{post_id}/versions/{version_id}/restore",
+            post(restore_paper_version),
+        )
         .route("/{post_id}/like", post(like_post))
-        // Keep multipart parsing above the 10MB policy threshold so route-level validation can return a precise 413.
+        .route("/{post_id}/import-source", get(get_post_import_source))
+        .route("/{post_id}/export.pdf", get(export_post_pdf))
+        // Keep multipart parsing above the largest allowed upload size so route-level validation can return a precise 413.
         .layer(DefaultBodyLimit::max(MULTIPART_BODY_LIMIT_BYTES))
 }
 
-async fn list_posts(
+/// OpenAPI paths for `posts_routes`, relative to `/api/posts`.
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_posts,
+    create_post,
+    import_post,
+    batch_get_posts,
+    get_post,
+    update_post,
+    patch_post,
+    delete_post,
+    publish_post,
+    batch_update_tags,
+    bulk_update_post,
+    like_post
+))]
+pub struct PostsApi;
+
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "posts",
+    params(PostQuery),
+    responses((status = 200, description = "One page of published posts", body = PostListResponse))
+)]
+pub async fn list_posts(
     State(pool): State<MySqlPool>,
     Query(query): Query<PostQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Response, AppError> {
     let page = query.page.unwrap_or(1).max(1);
     let per_page = query.per_page.unwrap_or(10).clamp(1, 100);
     let offset = i64::from(page - 1) * i64::from(per_page);
     let filters = resolve_post_filters(&query)?;
+    let selection = FieldSelection::new(
+        query.fields.as_deref(),
+        query.summary_only,
+        POST_LARGE_FIELDS,
+    );
+
+    // The following feed differs per user, so only shared listings are cached.
+    let cache_key = filters
+        .followed_by
+        .is_none()
+        .then(|| CacheKey::PostList(format!("{:?} page={} per_page={}", filters, page, per_page)));
+    if let Some(cache_key) = cache_key.as_ref()
+        && let Some(cached) = fetch_cached::<PostListResponse>(cache_key).await
+    {
+        return selection.respond(cached, "posts");
+    }
 
     let mut posts_qb = QueryBuilder::<MySql>::new(format!(
         "{}{}",
@@ -97,7 +227,7 @@ async fn list_posts(
     let mut posts_has_where = false;
     push_post_filters(&mut posts_qb, &filters, &mut posts_has_where);
     push_visibility_filter(&mut posts_qb, &mut posts_has_where);
-    posts_qb.push(" ORDER BY p.created_at DESC LIMIT ");
+    posts_qb.push(format!(" ORDER BY {} LIMIT ", filters.order_by));
     posts_qb.push_bind(i64::from(per_page));
     posts_qb.push(" OFFSET ");
     posts_qb.push_bind(offset);
@@ -106,7 +236,7 @@ async fn list_posts(
         .build_query_as::<Post>()
         .fetch_all(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     let mut count_qb = QueryBuilder::<MySql>::new(
         "SELECT COUNT(*) FROM posts p JOIN post_categories c ON c.id = p.category_id",
@@ -118,30 +248,30 @@ async fn list_posts(
         .build_query_as()
         .fetch_one(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     let author_map = fetch_authors_map(&pool, &posts)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
     let tags_map = fetch_tags_map(&pool, &posts)
         .await
-        .map_err(internal_error)?;
-    let post_ids: Vec<i64> = posts.iter().map(|post| post.id).collect();
-    let citation_count_map = compute_citation_counts_for_posts(&pool, &post_ids)
-        .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     let mut post_responses = Vec::with_capacity(posts.len());
     for post in posts {
-        let author = author_map.get(&post.author_id).cloned().ok_or_else(|| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": "Post author not found"})),
-            )
-        })?;
+        let author = author_map
+            .get(&post.author_id)
+            .cloned()
+            .ok_or_else(|| AppError::internal("Post author not found"))?;
 
         let tags = tags_map.get(&post.id).cloned().unwrap_or_default();
-        let citation_count = *citation_count_map.get(&post.id).unwrap_or(&0);
+        let retraction = post.retraction_notice();
+        let preprint_badge = post.preprint_badge();
+        let metrics = build_post_metrics(
+            post.citation_count,
+            post.external_citation_count,
+            post.influence_score,
+        );
 
         post_responses.push(PostResponse {
             id: post.id,
@@ -161,85 +291,109 @@ async fn list_posts(
             view_count: post.view_count,
             like_count: post.like_count,
             user_liked: None,
-            metrics: PostMetrics {
-                citation_count,
-                metric_version: METRIC_VERSION.to_string(),
-            },
+            metrics,
+            retraction,
             doi_metadata: Vec::new(),
+            is_double_blind: post.is_double_blind,
+            is_preprint: post.is_preprint,
+            preprint_badge,
+            blind_review_warnings: Vec::new(),
             created_at: post.created_at,
             updated_at: post.updated_at,
             tags,
         });
     }
 
-    Ok(Json(PostListResponse {
+    let response = PostListResponse {
         posts: post_responses,
         total,
         page,
         per_page,
-    }))
+    };
+    if let Some(cache_key) = cache_key.as_ref() {
+        store_cached(cache_key, &response).await;
+    }
+    selection.respond(response, "posts")
 }
 
+#[utoipa::path(
+    get,
+    path = "/{post_id}",
+    tag = "posts",
+    params(("post_id" = i64, Path, description = "Post id"), PostDetailQuery),
+    responses(
+        (status = 200, description = "The post; counts as a view", body = PostResponse),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
+        (status = 404, description = "Post not found or not visible", body = ErrorBody),
+    )
+)]
 async fn get_post(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
     Query(query): Query<PostDetailQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Response, AppError> {
+    let current_user = extract_optional_user(&pool, &headers).await?;
+    // Published posts are cached as other readers see them. Authors and
+    // admins can see more of a blind-review paper, so they skip the cache.
+    if let Some(cached) = fetch_cached::<CachedPost>(&CacheKey::Post(post_id)).await
+        && !current_user
+            .as_ref()
+            .is_some_and(|user| user.id == cached.author_id || user.is_admin)
+    {
+        record_post_view(&pool, post_id, current_user.as_ref(), &headers).await?;
+        let mut post = cached.post;
+        if let Some(user) = current_user.as_ref() {
+            post.user_liked = Some(
+                fetch_user_liked(&pool, user.id, post_id)
+                    .await
+                    .map_err(AppError::internal)?,
+            );
+        }
+        return respond_with_post(&headers, current_user.as_ref(), &post);
+    }
+
     let post_query = format!(
-        "{}{} WHERE p.id = ?",
+        "{}{} WHERE p.id = ? AND p.deleted_at IS NULL",
         POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
     );
     let post = sqlx::query_as::<_, Post>(&post_query)
         .bind(post_id)
         .fetch_optional(&pool)
         .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Post not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Post not found"))?;
 
-    let current_user = extract_optional_user(&pool, &headers).await?;
     if !post.is_published {
         let allow_review_center_access = query.source.as_deref() == Some("review_center");
-        let has_private_access = current_user
-            .as_ref()
-            .map(|user| user.id == post.author_id || user.is_admin)
-            .unwrap_or(false);
+        let has_private_access = match current_user.as_ref() {
+            Some(user) if user.id == post.author_id || user.is_admin => true,
+            // Invited and active reviewers open the paper from the review center.
+            Some(user) => fetch_reviewer_assignment_status(&pool, post_id, user.id)
+                .await
+                .map_err(AppError::internal)?
+                .is_some_and(|status| status != REVIEWER_ASSIGNMENT_DECLINED),
+            None => false,
+        };
         if !allow_review_center_access || !has_private_access {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Post not found"})),
-            ));
+            return Err(AppError::not_found("Post not found"));
         }
     }
 
-    sqlx::query(
-        r#"
-        INSERT INTO post_stats (post_id, view_count, like_count, updated_at)
-        VALUES (?, 1, 0, ?)
-        ON DUPLICATE KEY UPDATE view_count = view_count + 1, updated_at = VALUES(updated_at)
-        "#,
-    )
-    .bind(post_id)
-    .bind(Utc::now())
-    .execute(&pool)
-    .await
-    .map_err(internal_error)?;
+    record_post_view(&pool, post_id, current_user.as_ref(), &headers).await?;
 
     let author = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
         .bind(post.author_id)
         .fetch_one(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     let tags = fetch_tags(&pool, post.id).await.unwrap_or_default();
-    let citation_count = compute_citation_count(&pool, post.id)
-        .await
-        .map_err(internal_error)?;
+    let metrics = build_post_metrics(
+        post.citation_count,
+        post.external_citation_count,
+        post.influence_score,
+    );
     if let Err(error) = ensure_internal_doi_metadata(&pool, post.id).await {
         tracing::warn!(
             "Failed to ensure internal DOI for post {}: {}",
@@ -249,63 +403,396 @@ async fn get_post(
     }
     let doi_metadata = fetch_post_doi_metadata(&pool, post.id)
         .await
-        .map_err(internal_error)?;
-    let user_liked = if let Some(user) = current_user {
+        .map_err(AppError::internal)?;
+    let user_liked = if let Some(user) = current_user.as_ref() {
         Some(
             fetch_user_liked(&pool, user.id, post_id)
                 .await
-                .map_err(internal_error)?,
+                .map_err(AppError::internal)?,
         )
     } else {
         None
     };
 
-    Ok(Json(PostResponse {
-        id: post.id,
-        title: post.title,
-        content: post.content,
-        summary: post.summary,
-        github_url: post.github_url,
-        category: post.category,
-        file_path: post.file_path,
-        file_name: post.file_name,
+    // Reviewers of a double-blind paper see neither the author nor the
+    // citation record, which names them.
+    let hide_author = post.is_blind_review_active()
+        && !current_user
+            .as_ref()
+            .is_some_and(|user| user.id == post.author_id || user.is_admin);
+    let (author_id, author, doi_metadata) = if hide_author {
+        (
+            0,
+            anonymous_author(BLIND_AUTHOR_LABEL, post.created_at),
+            Vec::new(),
+        )
+    } else {
+        (post.author_id, UserResponse::from(author), doi_metadata)
+    };
+
+    let retraction = post.retraction_notice();
+    let preprint_badge = post.preprint_badge();
+    let cacheable = post.is_published
+        && !current_user
+            .as_ref()
+            .is_some_and(|user| user.id == post.author_id || user.is_admin);
+    let cached = CachedPost {
         author_id: post.author_id,
-        author: UserResponse::from(author),
-        is_published: post.is_published,
-        published_at: post.published_at,
-        paper_status: post.paper_status,
-        current_revision: post.current_revision,
-        view_count: post.view_count + 1,
-        like_count: post.like_count,
-        user_liked,
-        metrics: PostMetrics {
-            citation_count,
-            metric_version: METRIC_VERSION.to_string(),
+        post: PostResponse {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            summary: post.summary,
+            github_url: post.github_url,
+            category: post.category,
+            file_path: post.file_path,
+            file_name: post.file_name,
+            author_id,
+            author,
+            is_published: post.is_published,
+            published_at: post.published_at,
+            paper_status: post.paper_status,
+            current_revision: post.current_revision,
+            view_count: post.view_count + 1,
+            like_count: post.like_count,
+            user_liked: None,
+            metrics,
+            retraction,
+            doi_metadata,
+            is_double_blind: post.is_double_blind,
+            is_preprint: post.is_preprint,
+            preprint_badge,
+            blind_review_warnings: Vec::new(),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            tags,
         },
-        doi_metadata,
-        created_at: post.created_at,
-        updated_at: post.updated_at,
-        tags,
+    };
+    if cacheable {
+        store_cached(&CacheKey::Post(post_id), &cached).await;
+    }
+
+    let mut post = cached.post;
+    post.user_liked = user_liked;
+    respond_with_post(&headers, current_user.as_ref(), &post)
+}
+
+/// The post with an ETag that ignores its view count, which every read
+/// bumps, so polling clients get a 304 until the post itself changes.
+fn respond_with_post(
+    headers: &HeaderMap,
+    viewer: Option<&User>,
+    post: &PostResponse,
+) -> Result<Response, AppError> {
+    conditional_json_ignoring(
+        headers,
+        CachePolicy::for_viewer(viewer.is_some()),
+        post,
+        &["view_count"],
+    )
+}
+
+/// A post as readers other than its author and admins see it, kept with
+/// the author id the response may hide.
+#[derive(Serialize, Deserialize)]
+struct CachedPost {
+    author_id: i64,
+    post: PostResponse,
+}
+
+/// Counts a view of the post and records it for analytics.
+async fn record_post_view(
+    pool: &MySqlPool,
+    post_id: i64,
+    current_user: Option<&User>,
+    headers: &HeaderMap,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO post_stats (post_id, view_count, like_count, updated_at)
+        VALUES (?, 1, 0, ?)
+        ON DUPLICATE KEY UPDATE view_count = view_count + 1, updated_at = VALUES(updated_at)
+        "#,
+    )
+    .bind(post_id)
+    .bind(Utc::now())
+    .execute(pool)
+    .await
+    .map_err(AppError::internal)?;
+    record_post_event(
+        pool,
+        post_id,
+        POST_EVENT_VIEW,
+        current_user.map(|user| user.id),
+        Some(headers),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Full posts for a list of ids in one call, resolved with batched queries.
+/// Unlike the detail endpoint this does not count views, and only published
+/// posts are returned.
+#[utoipa::path(
+    post,
+    path = "/batch",
+    tag = "posts",
+    request_body = BatchPostRequest,
+    responses(
+        (status = 200, description = "The visible posts, in request order", body = BatchPostResponse),
+        (status = 422, description = "Empty or more than 100 ids", body = ErrorBody),
+    )
+)]
+async fn batch_get_posts(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    ValidatedJson(input): ValidatedJson<BatchPostRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_optional_user(&pool, &headers).await?;
+
+    let mut seen = HashSet::new();
+    let post_ids: Vec<i64> = input
+        .ids
+        .into_iter()
+        .filter(|post_id| seen.insert(*post_id))
+        .collect();
+
+    let mut query_builder = QueryBuilder::<MySql>::new(format!(
+        "{}{}",
+        POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
+    ));
+    let mut has_where = false;
+    push_condition(&mut query_builder, &mut has_where);
+    query_builder.push("p.id IN (");
+    {
+        let mut separated = query_builder.separated(", ");
+        for post_id in &post_ids {
+            separated.push_bind(post_id);
+        }
+    }
+    query_builder.push(")");
+    push_visibility_filter(&mut query_builder, &mut has_where);
+    let posts = query_builder
+        .build_query_as::<Post>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let author_map = fetch_authors_map(&pool, &posts)
+        .await
+        .map_err(AppError::internal)?;
+    let tags_map = fetch_tags_map(&pool, &posts)
+        .await
+        .map_err(AppError::internal)?;
+    let mut doi_metadata_map = fetch_doi_metadata_map(&pool, &posts, &author_map)
+        .await
+        .map_err(AppError::internal)?;
+    let liked_ids = match current_user.as_ref() {
+        Some(user) => fetch_liked_post_ids(&pool, user.id, &posts)
+            .await
+            .map_err(AppError::internal)?,
+        None => HashSet::new(),
+    };
+
+    let mut posts_by_id: HashMap<i64, Post> =
+        posts.into_iter().map(|post| (post.id, post)).collect();
+    let mut post_responses = Vec::with_capacity(posts_by_id.len());
+    let mut missing_ids = Vec::new();
+    for post_id in post_ids {
+        let Some(post) = posts_by_id.remove(&post_id) else {
+            missing_ids.push(post_id);
+            continue;
+        };
+        let author = author_map
+            .get(&post.author_id)
+            .cloned()
+            .ok_or_else(|| AppError::internal("Post author not found"))?;
+        let tags = tags_map.get(&post.id).cloned().unwrap_or_default();
+        let doi_metadata = doi_metadata_map.remove(&post.id).unwrap_or_default();
+        let user_liked = current_user.as_ref().map(|_| liked_ids.contains(&post.id));
+        let metrics = build_post_metrics(
+            post.citation_count,
+            post.external_citation_count,
+            post.influence_score,
+        );
+
+        // Same rule as the detail endpoint: a double-blind paper still under
+        // review can be loaded here, but only its author and admins see who
+        // wrote it.
+        let hide_author = post.is_blind_review_active()
+            && !current_user
+                .as_ref()
+                .is_some_and(|user| user.id == post.author_id || user.is_admin);
+        let (author_id, author, doi_metadata) = if hide_author {
+            (
+                0,
+                anonymous_author(BLIND_AUTHOR_LABEL, post.created_at),
+                Vec::new(),
+            )
+        } else {
+            (post.author_id, author, doi_metadata)
+        };
+
+        let retraction = post.retraction_notice();
+        let preprint_badge = post.preprint_badge();
+        post_responses.push(PostResponse {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            summary: post.summary,
+            github_url: post.github_url,
+            category: post.category,
+            file_path: post.file_path,
+            file_name: post.file_name,
+            author_id,
+            author,
+            is_published: post.is_published,
+            published_at: post.published_at,
+            paper_status: post.paper_status,
+            current_revision: post.current_revision,
+            view_count: post.view_count,
+            like_count: post.like_count,
+            user_liked,
+            metrics,
+            retraction,
+            doi_metadata,
+            is_double_blind: post.is_double_blind,
+            is_preprint: post.is_preprint,
+            preprint_badge,
+            blind_review_warnings: Vec::new(),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            tags,
+        });
+    }
+
+    Ok(Json(BatchPostResponse {
+        posts: post_responses,
+        missing_ids,
     }))
 }
 
+/// Every published post by one author, newest first, for the profile posts
+/// tab.
+pub async fn list_author_posts(
+    pool: &MySqlPool,
+    author_id: i64,
+) -> Result<Vec<PostResponse>, AppError> {
+    let mut query_builder = QueryBuilder::<MySql>::new(format!(
+        "{}{}",
+        POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
+    ));
+    let mut has_where = false;
+    push_condition(&mut query_builder, &mut has_where);
+    query_builder.push("p.author_id = ");
+    query_builder.push_bind(author_id);
+    push_visibility_filter(&mut query_builder, &mut has_where);
+    query_builder.push(" ORDER BY p.created_at DESC");
+    let posts = query_builder
+        .build_query_as::<Post>()
+        .fetch_all(pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let author_map = fetch_authors_map(pool, &posts)
+        .await
+        .map_err(AppError::internal)?;
+    let tags_map = fetch_tags_map(pool, &posts)
+        .await
+        .map_err(AppError::internal)?;
+    let mut doi_metadata_map = fetch_doi_metadata_map(pool, &posts, &author_map)
+        .await
+        .map_err(AppError::internal)?;
+
+    let mut post_responses = Vec::with_capacity(posts.len());
+    for post in posts {
+        let author = author_map
+            .get(&post.author_id)
+            .cloned()
+            .ok_or_else(|| AppError::internal("Post author not found"))?;
+        let tags = tags_map.get(&post.id).cloned().unwrap_or_default();
+        let doi_metadata = doi_metadata_map.remove(&post.id).unwrap_or_default();
+        let retraction = post.retraction_notice();
+        let preprint_badge = post.preprint_badge();
+        let metrics = build_post_metrics(
+            post.citation_count,
+            post.external_citation_count,
+            post.influence_score,
+        );
+
+        post_responses.push(PostResponse {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            summary: post.summary,
+            github_url: post.github_url,
+            category: post.category,
+            file_path: post.file_path,
+            file_name: post.file_name,
+            author_id: post.author_id,
+            author,
+            is_published: post.is_published,
+            published_at: post.published_at,
+            paper_status: post.paper_status,
+            current_revision: post.current_revision,
+            view_count: post.view_count,
+            like_count: post.like_count,
+            user_liked: None,
+            metrics,
+            retraction,
+            doi_metadata,
+            is_double_blind: post.is_double_blind,
+            is_preprint: post.is_preprint,
+            preprint_badge,
+            blind_review_warnings: Vec::new(),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            tags,
+        });
+    }
+
+    Ok(post_responses)
+}
+
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "posts",
+    security(("bearer" = [])),
+    request_body(
+        content_type = "multipart/form-data",
+        description = "Fields `title`, `content`, `summary`, `github_url`, `category`, `tags` \
+                       (comma-separated), `citations`, `paper_status`, `double_blind`, \
+                       `preprint` and an optional `file`."
+    ),
+    responses(
+        (status = 201, description = "Post created", body = PostResponse),
+        (status = 400, description = "Invalid category, status or citations", body = ErrorBody),
+        (status = 413, description = "Uploaded file is too large", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+        (status = 429, description = "Too many requests; see `Retry-After`", body = ErrorBody),
+    )
+)]
 async fn create_post(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     mut multipart: Multipart,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
 
     let mut title = String::new();
     let mut content = String::new();
     let mut summary: Option<String> = None;
     let mut github_url: Option<String> = None;
     let mut category = "other".to_string();
-    let mut file_path: Option<String> = None;
-    let mut file_name: Option<String> = None;
+    let mut upload: Option<StagedUpload> = None;
     let mut tags_str = String::new();
     let mut citations_str: Option<String> = None;
     let mut requested_paper_status: Option<String> = None;
+    let mut double_blind = false;
+    let mut preprint = false;
 
     while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
         let name = field.name().unwrap_or_default().to_string();
@@ -336,29 +823,23 @@ async fn create_post(
             "paper_status" => {
                 requested_paper_status = Some(field.text().await.map_err(multipart_error)?);
             }
+            "double_blind" => {
+                let val = field.text().await.map_err(multipart_error)?;
+                double_blind = val == "true";
+            }
+            "preprint" => {
+                let val = field.text().await.map_err(multipart_error)?;
+                preprint = preprint || val == "true";
+            }
             "file" => {
                 if let Some(original_name) = field.file_name() {
                     let original_name = original_name.to_string();
                     if !original_name.is_empty() {
-                        let data = field.bytes().await.map_err(multipart_error)?;
-                        validate_upload_file(&original_name, data.len())?;
-
-                        let ext = normalized_extension(&original_name).ok_or_else(|| {
-                            (
-                                StatusCode::BAD_REQUEST,
-                                Json(serde_json::json!({"detail": "Invalid file extension"})),
-                            )
-                        })?;
-
-                        let unique_name = format!("{}.{}", Uuid::new_v4(), ext);
-                        let upload_path = PathBuf::from("uploads").join(&unique_name);
-
-                        tokio::fs::write(&upload_path, &data)
-                            .await
-                            .map_err(internal_error)?;
-
-                        file_path = Some(upload_path.to_string_lossy().to_string());
-                        file_name = Some(original_name);
+                        let extension = validate_upload_name(&original_name)?;
+                        upload = Some(
+                            stage_upload(field, original_name, extension, max_upload_size_bytes())
+                                .await?,
+                        );
                     }
                 }
             }
@@ -366,14 +847,35 @@ async fn create_post(
         }
     }
 
-    if title.is_empty() || content.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Title and content are required"})),
-        ));
+    validate(&PostFields {
+        title: &title,
+        content: &content,
+        summary: summary.as_deref(),
+        category: &category,
+        tags: Some(&tags_str),
+    })?;
+
+    let mut file_path: Option<String> = None;
+    let mut file_name: Option<String> = None;
+    if let Some(upload) = upload {
+        file_name = Some(upload.original_name.clone());
+        file_path = Some(upload.persist().await?);
     }
 
-    let (category_id, category_code) = resolve_or_create_category(&pool, &category).await?;
+    if normalize_category_code(&category) == PREPRINT_CATEGORY_ALIAS {
+        category = PAPER_CATEGORY.to_string();
+        preprint = true;
+    }
+    let (category_id, category_code) = resolve_or_create_category(&pool, &category, None).await?;
+    for (enabled, flag_name) in [
+        (double_blind, DOUBLE_BLIND_FLAG_NAME),
+        (preprint, PREPRINT_FLAG_NAME),
+    ] {
+        if enabled && category_code != PAPER_CATEGORY {
+            return Err(paper_only_flag_error(flag_name));
+        }
+    }
+    ensure_compatible_paper_flags(double_blind, preprint)?;
     let manual_citation_ids =
         prepare_citations_for_create(&pool, &category_code, citations_str.as_deref()).await?;
     let auto_citation_ids =
@@ -382,11 +884,13 @@ async fn create_post(
     let now = Utc::now();
     let paper_status =
         resolve_create_paper_status(&category_code, requested_paper_status.as_deref())?;
-    let is_published = paper_status == PAPER_STATUS_PUBLISHED;
+    // A preprint goes public as soon as it leaves draft.
+    let is_published =
+        paper_status == PAPER_STATUS_PUBLISHED || (preprint && paper_status != PAPER_STATUS_DRAFT);
     let published_at = if is_published { Some(now) } else { None };
     let result = sqlx::query(
-        r#"INSERT INTO posts (title, content, summary, github_url, category_id, author_id, is_published, published_at, paper_status, created_at)
-           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        r#"INSERT INTO posts (title, content, summary, github_url, category_id, author_id, is_published, published_at, paper_status, is_double_blind, is_preprint, created_at)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
     )
     .bind(&title)
     .bind(&content)
@@ -397,21 +901,36 @@ async fn create_post(
     .bind(is_published)
     .bind(published_at)
     .bind(&paper_status)
+    .bind(double_blind)
+    .bind(preprint)
     .bind(now)
     .execute(&pool)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     let post_id = result.last_insert_id() as i64;
 
-    sqlx::query(
-        "INSERT INTO post_stats (post_id, view_count, like_count, updated_at) VALUES (?, 0, 0, ?)",
-    )
-    .bind(post_id)
-    .bind(now)
+    if category_code == PAPER_CATEGORY {
+        record_status_transition(
+            &pool,
+            post_id,
+            Some(current_user.id),
+            STATUS_TRIGGER_CREATE,
+            None,
+            &paper_status,
+        )
+        .await
+        .map_err(AppError::internal)?;
+    }
+
+    sqlx::query(
+        "INSERT INTO post_stats (post_id, view_count, like_count, updated_at) VALUES (?, 0, 0, ?)",
+    )
+    .bind(post_id)
+    .bind(now)
     .execute(&pool)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     if let (Some(saved_path), Some(saved_name)) = (file_path.as_ref(), file_name.as_ref()) {
         sqlx::query(
@@ -424,11 +943,21 @@ async fn create_post(
         .bind(now)
         .execute(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
     }
 
-    replace_post_citations(&pool, post_id, &manual_citation_ids).await?;
+    if let Err(error) = subscribe(&pool, current_user.id, post_id, None).await {
+        tracing::warn!(
+            "Failed to subscribe author {} to post {}: {}",
+            current_user.id,
+            post_id,
+            error
+        );
+    }
+
+    replace_post_citations(&pool, post_id, current_user.id, &manual_citation_ids).await?;
     replace_post_auto_citations(&pool, post_id, &auto_citation_ids).await?;
+    refresh_author_metrics(&pool, current_user.id).await?;
     if let Err(error) = sync_post_doi_metadata(
         &pool,
         post_id,
@@ -446,23 +975,21 @@ async fn create_post(
         );
     }
 
-    let tags_vec = process_tags(&pool, post_id, &tags_str).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    let tags_vec = process_tags(&pool, post_id, &tags_str)
+        .await
+        .map_err(AppError::internal)?;
 
     if category_code == PAPER_CATEGORY && paper_status == PAPER_STATUS_SUBMITTED {
         let (paper_version_id, _) =
             create_paper_version_snapshot(&pool, post_id, current_user.id).await?;
-        if let Err(error) = schedule_review(
-            &pool,
-            post_id,
-            Some(paper_version_id),
-            ReviewTrigger::AutoCreate,
-        )
-        .await
+        if is_feature_enabled(FEATURE_AI_REVIEW_AUTO_SCHEDULE, Some(&current_user))
+            && let Err(error) = schedule_review(
+                &pool,
+                post_id,
+                Some(paper_version_id),
+                ReviewTrigger::AutoCreate,
+            )
+            .await
         {
             tracing::error!(
                 "Failed to schedule auto AI review on create for post {}: {}",
@@ -470,24 +997,52 @@ async fn create_post(
                 error
             );
         }
+        queue_webhook_message(
+            &pool,
+            WebhookMessage {
+                event: WEBHOOK_EVENT_SUBMISSION,
+                title: format!("New submission: {}", title),
+                body: format!("{} submitted a paper for review.", current_user.username),
+                post_id: Some(post_id),
+            },
+        );
+    }
+    if is_published && !is_blind_review_active(double_blind, &paper_status) {
+        notify_followers_of_post(&pool, current_user.id, post_id, &title).await;
     }
 
     let post_query = format!(
-        "{}{} WHERE p.id = ?",
+        "{}{} WHERE p.id = ? AND p.deleted_at IS NULL",
         POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
     );
     let post = sqlx::query_as::<_, Post>(&post_query)
         .bind(post_id)
         .fetch_one(&pool)
         .await
-        .map_err(internal_error)?;
-    let citation_count = compute_citation_count(&pool, post_id)
-        .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+    let metrics = build_post_metrics(
+        post.citation_count,
+        post.external_citation_count,
+        post.influence_score,
+    );
     let doi_metadata = fetch_post_doi_metadata(&pool, post_id)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+
+    let blind_review_warnings = if post.is_double_blind {
+        find_author_identity_mentions(
+            &current_user,
+            &post.title,
+            post.summary.as_deref(),
+            &post.content,
+        )
+    } else {
+        Vec::new()
+    };
 
+    let retraction = post.retraction_notice();
+    let preprint_badge = post.preprint_badge();
+    invalidate_cached_post(post.id).await;
     Ok((
         StatusCode::CREATED,
         Json(PostResponse {
@@ -508,11 +1063,13 @@ async fn create_post(
             view_count: post.view_count,
             like_count: post.like_count,
             user_liked: Some(false),
-            metrics: PostMetrics {
-                citation_count,
-                metric_version: METRIC_VERSION.to_string(),
-            },
+            metrics,
+            retraction,
             doi_metadata,
+            is_double_blind: post.is_double_blind,
+            is_preprint: post.is_preprint,
+            preprint_badge,
+            blind_review_warnings,
             created_at: post.created_at,
             updated_at: post.updated_at,
             tags: tags_vec,
@@ -520,50 +1077,423 @@ async fn create_post(
     ))
 }
 
-async fn update_post(
+#[utoipa::path(
+    post,
+    path = "/import",
+    tag = "posts",
+    security(("bearer" = [])),
+    request_body = ImportPost,
+    responses(
+        (status = 201, description = "Draft created from the arXiv or DOI record", body = ImportPostResponse),
+        (status = 404, description = "No record found for the identifier", body = ErrorBody),
+        (status = 409, description = "The work was already imported", body = ErrorBody),
+        (status = 429, description = "Too many requests; see `Retry-After`", body = ErrorBody),
+    )
+)]
+async fn import_post(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
-    Path(post_id): Path<i64>,
-    mut multipart: Multipart,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Json(input): Json<ImportPost>,
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+
+    let identifier = ImportIdentifier::parse(&input.identifier)
+        .ok_or_else(|| AppError::bad_request("Identifier must be an arXiv ID or a DOI"))?;
+
+    let existing_post_id: Option<i64> = sqlx::query_scalar(
+        r#"
+        SELECT s.post_id
+        FROM post_import_sources s
+        JOIN posts p ON p.id = s.post_id
+        WHERE s.source_type = ? AND s.source_identifier = ? AND p.author_id = ? AND p.deleted_at IS NULL
+        LIMIT 1
+        "#,
+    )
+    .bind(identifier.source_type())
+    .bind(identifier.value())
+    .bind(current_user.id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    if let Some(existing_post_id) = existing_post_id {
+        return Err(AppError::conflict("This work has already been imported")
+            .with_field("post_id", existing_post_id));
+    }
+
+    let client = import_client().map_err(AppError::internal)?;
+    let work = fetch_imported_work(&client, &identifier)
+        .await
+        .map_err(|error| {
+            tracing::warn!(
+                "Failed to fetch {} {} for import: {}",
+                identifier.source_type(),
+                identifier.value(),
+                error
+            );
+            AppError::bad_gateway("Failed to fetch metadata from the source")
+        })?
+        .ok_or_else(|| AppError::not_found("No work found for this identifier"))?;
+
+    // The draft is still usable without the full text, so a failed download
+    // only drops the attachment.
+    let mut pdf_file: Option<(String, String)> = None;
+    if let Some(pdf_url) = work.pdf_url.as_deref() {
+        match download_pdf(pdf_url, max_upload_size_bytes()).await {
+            Ok(data) => {
+                let upload_path = PathBuf::from("uploads").join(format!("{}.pdf", Uuid::new_v4()));
+                tokio::fs::write(&upload_path, &data)
+                    .await
+                    .map_err(AppError::internal)?;
+                let file_name = format!(
+                    "{}.pdf",
+                    sanitize_bibtex_key_fragment(identifier.value()).to_ascii_lowercase()
+                );
+                pdf_file = Some((upload_path.to_string_lossy().to_string(), file_name));
+            }
+            Err(error) => tracing::warn!("Failed to download PDF from {}: {}", pdf_url, error),
+        }
+    }
+
+    let (category_id, category_code) =
+        resolve_or_create_category(&pool, PAPER_CATEGORY, None).await?;
+    let title: String = work.title.chars().take(255).collect();
+    let source_url = identifier.source_url();
+    let content = build_imported_content(&work, &source_url);
+    let now = Utc::now();
+    let result = sqlx::query(
+        r#"INSERT INTO posts (title, content, summary, category_id, author_id, is_published, paper_status, created_at)
+           VALUES (?, ?, ?, ?, ?, FALSE, ?, ?)"#,
+    )
+    .bind(&title)
+    .bind(&content)
+    .bind(&work.abstract_text)
+    .bind(category_id)
+    .bind(current_user.id)
+    .bind(PAPER_STATUS_DRAFT)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let post_id = result.last_insert_id() as i64;
+
+    record_status_transition(
+        &pool,
+        post_id,
+        Some(current_user.id),
+        STATUS_TRIGGER_CREATE,
+        None,
+        PAPER_STATUS_DRAFT,
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    sqlx::query(
+        "INSERT INTO post_stats (post_id, view_count, like_count, updated_at) VALUES (?, 0, 0, ?)",
+    )
+    .bind(post_id)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    if let Some((saved_path, saved_name)) = pdf_file.as_ref() {
+        sqlx::query(
+            "INSERT INTO post_files (post_id, file_path, file_name, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(post_id)
+        .bind(saved_path)
+        .bind(saved_name)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    }
+
+    if let Err(error) = subscribe(&pool, current_user.id, post_id, None).await {
+        tracing::warn!(
+            "Failed to subscribe author {} to post {}: {}",
+            current_user.id,
+            post_id,
+            error
+        );
+    }
+
+    if let Err(error) = sync_post_doi_metadata(
+        &pool,
+        post_id,
+        &category_code,
+        &title,
+        work.abstract_text.as_deref(),
+        &content,
+    )
+    .await
+    {
+        tracing::warn!(
+            "Failed to auto-collect DOI metadata for imported post {}: {}",
+            post_id,
+            error
+        );
+    }
+
+    // References of the imported work that are papers on this site become
+    // citation edges; the rest are kept as external DOI references.
+    let (resolved_post_ids, external_dois) = resolve_reference_dois(&pool, &work.reference_dois)
+        .await
+        .map_err(AppError::internal)?;
+    let cited_post_ids = retain_existing_papers(
+        &pool,
+        resolved_post_ids
+            .into_iter()
+            .filter(|cited_post_id| *cited_post_id != post_id)
+            .collect(),
+    )
+    .await?;
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sync_manual_citations(
+        &mut tx,
+        post_id,
+        Some(current_user.id),
+        &cited_post_ids,
+        Some(&external_dois),
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    refresh_author_metrics(&pool, current_user.id).await?;
+
+    let source = PostImportSource {
+        post_id,
+        source_type: identifier.source_type().to_string(),
+        source_identifier: identifier.value().to_string(),
+        source_url,
+        source_doi: work.doi.clone(),
+        authors: work.authors.clone(),
+        license_url: work.license_url.clone(),
+        pdf_imported: pdf_file.is_some(),
+        imported_by: Some(current_user.id),
+        imported_at: now,
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO post_import_sources
+            (post_id, source_type, source_identifier, source_url, source_doi, authors_json, license_url, pdf_imported, imported_by, imported_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(post_id)
+    .bind(&source.source_type)
+    .bind(&source.source_identifier)
+    .bind(&source.source_url)
+    .bind(&source.source_doi)
+    .bind(serde_json::to_string(&source.authors).map_err(AppError::internal)?)
+    .bind(&source.license_url)
+    .bind(source.pdf_imported)
+    .bind(source.imported_by)
+    .bind(source.imported_at)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImportPostResponse {
+            post_id,
+            title,
+            paper_status: PAPER_STATUS_DRAFT.to_string(),
+            source,
+            reference_count: cited_post_ids.len() + external_dois.len(),
+        }),
+    ))
+}
 
+async fn get_post_import_source(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let not_found = || AppError::not_found("Import source not found");
+
+    let row = sqlx::query_as::<_, PostImportSourceRow>(
+        r#"
+        SELECT
+            s.post_id,
+            s.source_type,
+            s.source_identifier,
+            s.source_url,
+            s.source_doi,
+            CAST(s.authors_json AS CHAR) AS authors_json,
+            s.license_url,
+            s.pdf_imported,
+            s.imported_by,
+            s.imported_at,
+            p.author_id,
+            p.is_published
+        FROM post_import_sources s
+        JOIN posts p ON p.id = s.post_id
+        WHERE s.post_id = ? AND p.deleted_at IS NULL
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(not_found)?;
+
+    if !row.is_published {
+        let current_user = extract_optional_user(&pool, &headers).await?;
+        if !current_user.is_some_and(|user| user.id == row.author_id || user.is_admin) {
+            return Err(not_found());
+        }
+    }
+
+    Ok(Json(PostImportSource {
+        post_id: row.post_id,
+        source_type: row.source_type,
+        source_identifier: row.source_identifier,
+        source_url: row.source_url,
+        source_doi: row.source_doi,
+        authors: row
+            .authors_json
+            .as_deref()
+            .and_then(|raw| serde_json::from_str(raw).ok())
+            .unwrap_or_default(),
+        license_url: row.license_url,
+        pdf_imported: row.pdf_imported,
+        imported_by: row.imported_by,
+        imported_at: row.imported_at,
+    }))
+}
+
+async fn export_post_pdf(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
     let post_query = format!(
-        "{}{} WHERE p.id = ?",
+        "{}{} WHERE p.id = ? AND p.deleted_at IS NULL",
         POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
     );
     let post = sqlx::query_as::<_, Post>(&post_query)
         .bind(post_id)
         .fetch_optional(&pool)
         .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Post not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Post not found"))?;
 
-    if post.author_id != current_user.id {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({"detail": "Not authorized to edit this post"})),
-        ));
+    let current_user = extract_optional_user(&pool, &headers).await?;
+    let is_owner_or_admin = current_user
+        .as_ref()
+        .is_some_and(|user| user.id == post.author_id || user.is_admin);
+    if !post.is_published && !is_owner_or_admin {
+        return Err(AppError::not_found("Post not found"));
     }
 
-    let mut title = post.title.clone();
-    let mut content = post.content.clone();
-    let mut summary = post.summary.clone();
-    let mut github_url = post.github_url.clone();
-    let mut category = post.category.clone();
-    let mut file_path = post.file_path.clone();
-    let mut file_name = post.file_name.clone();
+    let authors = if post.is_blind_review_active() && !is_owner_or_admin {
+        vec![BLIND_AUTHOR_LABEL.to_string()]
+    } else {
+        fetch_post_bibtex_author(&pool, post_id)
+            .await
+            .map_err(AppError::internal)?
+            .into_iter()
+            .collect()
+    };
+
+    if let Err(error) = ensure_internal_doi_metadata(&pool, post_id).await {
+        tracing::warn!(
+            "Failed to ensure internal DOI for post {}: {}",
+            post_id,
+            error
+        );
+    }
+    let registered_doi: Option<String> =
+        sqlx::query_scalar("SELECT registered_doi FROM post_doi_registrations WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?;
+    let doi = match registered_doi {
+        Some(doi) => Some(doi),
+        None => sqlx::query_scalar(
+            "SELECT doi FROM post_doi_metadata WHERE post_id = ? AND doi LIKE 'TM.%' LIMIT 1",
+        )
+        .bind(post_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::internal)?,
+    };
+
+    let document = PdfDocument {
+        title: post.title.clone(),
+        authors,
+        doi,
+        license: paper_license(post.published_at.unwrap_or(post.created_at)),
+        published_on: post
+            .published_at
+            .map(|published_at| published_at.format("%Y-%m-%d").to_string()),
+        summary: post.summary.clone(),
+        content: post.content.clone(),
+        landing_url: format!("{}/posts/{}", frontend_base_url_for_links(), post_id),
+        base_url: frontend_base_url_for_links(),
+    };
+
+    let data = render_pdf(&document).await.map_err(|error| match error {
+        PdfRenderError::Unavailable => {
+            AppError::service_unavailable("PDF export is not available on this server")
+        }
+        PdfRenderError::Failed(_) => AppError::internal(format!(
+            "Failed to render PDF for post {}: {}",
+            post_id, error
+        )),
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"thought-manifold-{}.pdf\"", post_id),
+            ),
+        ],
+        data,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{post_id}",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    request_body(
+        content_type = "multipart/form-data",
+        description = "Fields `title`, `content`, `summary`, `github_url`, `category`, `tags` \
+                       (comma-separated), `citations`, `paper_status`, `double_blind`, \
+                       `preprint` and an optional `file`."
+    ),
+    responses(
+        (status = 200, description = "The updated post", body = PostResponse),
+        (status = 403, description = "Not the author", body = ErrorBody),
+        (status = 404, description = "Post not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn update_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    let post = fetch_editable_post(&pool, post_id, &current_user).await?;
+
+    // Empty title, content and category fields keep the current values.
+    let mut edits = PostEdits::default();
     let mut remove_file = false;
-    let mut file_changed = false;
-    let mut tags_str: Option<String> = None;
-    let mut citations_str: Option<String> = None;
-    let mut requested_paper_status: Option<String> = None;
-    let mut replacement_file: Option<(String, Vec<u8>)> = None;
+    let mut replacement_file: Option<StagedUpload> = None;
 
     while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
         let name = field.name().unwrap_or_default().to_string();
@@ -572,36 +1502,44 @@ async fn update_post(
             "title" => {
                 let val = field.text().await.map_err(multipart_error)?;
                 if !val.is_empty() {
-                    title = val;
+                    edits.title = Some(val);
                 }
             }
             "content" => {
                 let val = field.text().await.map_err(multipart_error)?;
                 if !val.is_empty() {
-                    content = val;
+                    edits.content = Some(val);
                 }
             }
             "summary" => {
-                summary = Some(field.text().await.map_err(multipart_error)?);
+                edits.summary = Some(Some(field.text().await.map_err(multipart_error)?));
             }
             "github_url" => {
                 let value = field.text().await.map_err(multipart_error)?;
-                github_url = validate_github_url(&value)?;
+                edits.github_url = Some(validate_github_url(&value)?);
             }
             "category" => {
                 let val = field.text().await.map_err(multipart_error)?;
                 if !val.is_empty() {
-                    category = val;
+                    edits.category = Some(val);
                 }
             }
             "tags" => {
-                tags_str = Some(field.text().await.map_err(multipart_error)?);
+                edits.tags = Some(field.text().await.map_err(multipart_error)?);
             }
             "citations" => {
-                citations_str = Some(field.text().await.map_err(multipart_error)?);
+                edits.citations = Some(field.text().await.map_err(multipart_error)?);
             }
             "paper_status" => {
-                requested_paper_status = Some(field.text().await.map_err(multipart_error)?);
+                edits.paper_status = Some(field.text().await.map_err(multipart_error)?);
+            }
+            "double_blind" => {
+                let val = field.text().await.map_err(multipart_error)?;
+                edits.double_blind = Some(val == "true");
+            }
+            "preprint" => {
+                let val = field.text().await.map_err(multipart_error)?;
+                edits.preprint = Some(edits.preprint == Some(true) || val == "true");
             }
             "remove_file" => {
                 let val = field.text().await.map_err(multipart_error)?;
@@ -611,49 +1549,374 @@ async fn update_post(
                 if let Some(original_name) = field.file_name() {
                     let original_name = original_name.to_string();
                     if !original_name.is_empty() {
-                        let data = field.bytes().await.map_err(multipart_error)?;
-                        validate_upload_file(&original_name, data.len())?;
-                        replacement_file = Some((original_name, data.to_vec()));
+                        let extension = validate_upload_name(&original_name)?;
+                        replacement_file = Some(
+                            stage_upload(field, original_name, extension, max_upload_size_bytes())
+                                .await?,
+                        );
                     }
                 }
             }
             _ => {}
         }
     }
+    edits.file = match replacement_file {
+        Some(upload) => FileEdit::Replace(upload),
+        None if remove_file => FileEdit::Remove,
+        None => FileEdit::Keep,
+    };
+
+    let response = apply_post_edits(&pool, current_user, post, edits).await?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{post_id}",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    request_body = PatchPost,
+    responses(
+        (status = 200, description = "The updated post", body = PostResponse),
+        (status = 403, description = "Not the author", body = ErrorBody),
+        (status = 404, description = "Post not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn patch_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    ValidatedJson(input): ValidatedJson<PatchPost>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    let post = fetch_editable_post(&pool, post_id, &current_user).await?;
+
+    let github_url = match input.github_url {
+        Some(Some(raw)) => Some(validate_github_url(&raw)?),
+        Some(None) => Some(None),
+        None => None,
+    };
+    let citations = input.citations.flatten().map(|ids| {
+        ids.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    });
+    let edits = PostEdits {
+        title: input.title.flatten(),
+        content: input.content.flatten(),
+        summary: input.summary,
+        github_url,
+        category: input.category.flatten(),
+        tags: input.tags.flatten().map(|tags| tags.join(",")),
+        citations,
+        paper_status: input.paper_status.flatten(),
+        double_blind: input.double_blind.flatten(),
+        preprint: input.preprint.flatten(),
+        file: FileEdit::Keep,
+    };
+
+    let response = apply_post_edits(&pool, current_user, post, edits).await?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{post_id}/tags:batch",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    request_body = TagBatchRequest,
+    responses(
+        (status = 200, description = "The post's tags after the change", body = PostTagsResponse),
+        (status = 403, description = "Not the author", body = ErrorBody),
+        (status = 404, description = "Post not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn batch_update_tags(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    ValidatedJson(input): ValidatedJson<TagBatchRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    fetch_editable_post_summary(&pool, post_id, &current_user).await?;
+
+    let removed = parse_tag_list(&input.remove.join(","));
+    let mut tags = fetch_tags(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
+    tags.retain(|tag| !removed.contains(tag));
+    for tag in parse_tag_list(&input.add.join(",")) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    // The additions were checked on their own; the limits apply to the result.
+    tag_names(&tags).map_err(|error| AppError::validation("tags", error.to_string()))?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    replace_post_tags(&mut tx, post_id, &tags)
+        .await
+        .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(PostTagsResponse { post_id, tags }))
+}
+
+/// Tags and citations in one transaction, without resending the post.
+#[utoipa::path(
+    post,
+    path = "/{post_id}/bulk",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    request_body = PostBulkUpdate,
+    responses(
+        (status = 200, description = "The post's tags and citations after the change", body = PostBulkUpdateResponse),
+        (status = 400, description = "Invalid citations, or citations on a non-paper post", body = ErrorBody),
+        (status = 403, description = "Not the author", body = ErrorBody),
+        (status = 404, description = "Post not found", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn bulk_update_post(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    ValidatedJson(input): ValidatedJson<PostBulkUpdate>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    ensure_not_suspended(&pool, current_user.id).await?;
+    let post = fetch_editable_post_summary(&pool, post_id, &current_user).await?;
+
+    if input.tags.is_none() && input.citations.is_none() {
+        return Err(AppError::validation(
+            "body",
+            "tags or citations must be given",
+        ));
+    }
+    let tags = input
+        .tags
+        .as_ref()
+        .map(|tags| parse_tag_list(&tags.join(",")));
+    let citations = match input.citations.as_ref() {
+        Some(_) if post.category != PAPER_CATEGORY => {
+            return Err(AppError::bad_request(
+                "Citations are only allowed for paper category posts",
+            ));
+        }
+        Some(citations) => Some(prepare_manual_citations(&pool, post_id, citations).await?),
+        None => None,
+    };
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    if let Some(tags) = tags.as_ref() {
+        replace_post_tags(&mut tx, post_id, tags)
+            .await
+            .map_err(AppError::internal)?;
+    }
+    let citation_changes = match citations {
+        Some((post_ids, dois)) => Some(
+            sync_manual_citations(
+                &mut tx,
+                post_id,
+                Some(current_user.id),
+                &post_ids,
+                Some(&dois),
+            )
+            .await
+            .map_err(AppError::internal)?,
+        ),
+        None => None,
+    };
+    tx.commit().await.map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+    if citation_changes.is_some() {
+        invalidate_cached_metrics().await;
+    }
+
+    let tags = match tags {
+        Some(tags) => tags,
+        None => fetch_tags(&pool, post_id)
+            .await
+            .map_err(AppError::internal)?,
+    };
+    let citations = fetch_post_citations(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(PostBulkUpdateResponse {
+        post_id,
+        tags,
+        citation_changes,
+        citations,
+    }))
+}
+
+/// Changes requested through `PUT` or `PATCH`; `None` keeps the current
+/// value, and `Some(None)` clears a nullable one.
+#[derive(Debug, Default)]
+struct PostEdits {
+    title: Option<String>,
+    content: Option<String>,
+    summary: Option<Option<String>>,
+    github_url: Option<Option<String>>,
+    category: Option<String>,
+    /// Comma-separated, as the form sends them.
+    tags: Option<String>,
+    /// Comma-separated post ids, as the form sends them.
+    citations: Option<String>,
+    paper_status: Option<String>,
+    double_blind: Option<bool>,
+    preprint: Option<bool>,
+    file: FileEdit,
+}
+
+#[derive(Debug, Default)]
+enum FileEdit {
+    #[default]
+    Keep,
+    Replace(StagedUpload),
+    Remove,
+}
+
+/// Loads a post for editing; only its author may change it.
+async fn fetch_editable_post(
+    pool: &MySqlPool,
+    post_id: i64,
+    current_user: &User,
+) -> Result<Post, AppError> {
+    let post_query = format!(
+        "{}{} WHERE p.id = ? AND p.deleted_at IS NULL",
+        POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
+    );
+    let post = sqlx::query_as::<_, Post>(&post_query)
+        .bind(post_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if post.author_id != current_user.id {
+        return Err(AppError::forbidden("Not authorized to edit this post"));
+    }
+    Ok(post)
+}
 
-    if let Some((new_original_name, new_data)) = replacement_file {
-        let ext = normalized_extension(&new_original_name).ok_or_else(|| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"detail": "Invalid file extension"})),
-            )
-        })?;
-        let unique_name = format!("{}.{}", Uuid::new_v4(), ext);
-        let upload_path = PathBuf::from("uploads").join(&unique_name);
+/// Loads a post's metadata, leaving out its content.
+async fn fetch_post_summary(pool: &MySqlPool, post_id: i64) -> Result<PostSummary, AppError> {
+    sqlx::query_as::<_, PostSummary>(&format!(
+        "{} WHERE p.id = ? AND p.deleted_at IS NULL",
+        POST_SUMMARY_SELECT
+    ))
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))
+}
 
-        tokio::fs::write(&upload_path, &new_data)
-            .await
-            .map_err(internal_error)?;
+/// Like `fetch_editable_post`, for changes that do not touch the content.
+async fn fetch_editable_post_summary(
+    pool: &MySqlPool,
+    post_id: i64,
+    current_user: &User,
+) -> Result<PostSummary, AppError> {
+    let post = fetch_post_summary(pool, post_id).await?;
+    if post.author_id != current_user.id {
+        return Err(AppError::forbidden("Not authorized to edit this post"));
+    }
+    Ok(post)
+}
 
-        if let Some(ref old_path) = post.file_path {
-            let _ = tokio::fs::remove_file(old_path).await;
-        }
+async fn apply_post_edits(
+    pool: &MySqlPool,
+    current_user: User,
+    post: Post,
+    mut edits: PostEdits,
+) -> Result<PostResponse, AppError> {
+    let post_id = post.id;
+    let post_query = format!(
+        "{}{} WHERE p.id = ? AND p.deleted_at IS NULL",
+        POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
+    );
+
+    let title = edits.title.take().unwrap_or_else(|| post.title.clone());
+    let content = edits.content.take().unwrap_or_else(|| post.content.clone());
+    let summary = edits.summary.take().unwrap_or_else(|| post.summary.clone());
+    let github_url = edits
+        .github_url
+        .take()
+        .unwrap_or_else(|| post.github_url.clone());
+    let mut category = edits
+        .category
+        .take()
+        .unwrap_or_else(|| post.category.clone());
+    let mut file_path = post.file_path.clone();
+    let mut file_name = post.file_name.clone();
+    let mut file_changed = false;
+
+    validate(&PostFields {
+        title: &title,
+        content: &content,
+        summary: summary.as_deref(),
+        category: &category,
+        tags: edits.tags.as_deref(),
+    })?;
+
+    match std::mem::take(&mut edits.file) {
+        FileEdit::Replace(upload) => {
+            let new_original_name = upload.original_name.clone();
+            let upload_path = upload.persist().await?;
+
+            if let Some(ref old_path) = post.file_path {
+                let _ = tokio::fs::remove_file(old_path).await;
+            }
 
-        file_path = Some(upload_path.to_string_lossy().to_string());
-        file_name = Some(new_original_name);
-        file_changed = true;
-    } else if remove_file && file_path.is_some() {
-        if let Some(ref path) = post.file_path {
-            let _ = tokio::fs::remove_file(path).await;
+            file_path = Some(upload_path);
+            file_name = Some(new_original_name);
+            file_changed = true;
         }
-        file_path = None;
-        file_name = None;
-        file_changed = true;
+        FileEdit::Remove if file_path.is_some() => {
+            if let Some(ref path) = post.file_path {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+            file_path = None;
+            file_name = None;
+            file_changed = true;
+        }
+        FileEdit::Remove | FileEdit::Keep => {}
     }
 
-    let (category_id, category_code) = resolve_or_create_category(&pool, &category).await?;
-    let manual_citation_ids = if let Some(raw) = citations_str.as_deref() {
-        Some(prepare_citations_for_update(&pool, post_id, &category_code, raw).await?)
+    if normalize_category_code(&category) == PREPRINT_CATEGORY_ALIAS {
+        category = PAPER_CATEGORY.to_string();
+        edits.preprint = Some(true);
+    }
+    let (category_id, category_code) =
+        resolve_or_create_category(pool, &category, Some(&post.category)).await?;
+    let is_double_blind = resolve_paper_flag_update(
+        DOUBLE_BLIND_FLAG_NAME,
+        &category_code,
+        &post,
+        post.is_double_blind,
+        edits.double_blind,
+    )?;
+    let is_preprint = resolve_paper_flag_update(
+        PREPRINT_FLAG_NAME,
+        &category_code,
+        &post,
+        post.is_preprint,
+        edits.preprint,
+    )?;
+    ensure_compatible_paper_flags(is_double_blind, is_preprint)?;
+    let manual_citation_ids = if let Some(raw) = edits.citations.as_deref() {
+        Some(prepare_citations_for_update(pool, post_id, &category_code, raw).await?)
     } else {
         None
     };
@@ -662,12 +1925,19 @@ async fn update_post(
     let paper_status = resolve_update_paper_status(
         &category_code,
         post.paper_status.as_str(),
-        requested_paper_status.as_deref(),
+        edits.paper_status.as_deref(),
     )?;
-    let is_published = paper_status == PAPER_STATUS_PUBLISHED;
-    let published_at = if is_published { Some(now) } else { None };
+    // A posted preprint stays public through resubmission and keeps its
+    // original posting date.
+    let is_published = paper_status == PAPER_STATUS_PUBLISHED
+        || (is_preprint && (paper_status != PAPER_STATUS_DRAFT || post.published_at.is_some()));
+    let published_at = match (is_published, is_preprint) {
+        (false, _) => None,
+        (true, true) => Some(post.published_at.unwrap_or(now)),
+        (true, false) => Some(now),
+    };
     sqlx::query(
-        "UPDATE posts SET title = ?, content = ?, summary = ?, github_url = ?, category_id = ?, is_published = ?, published_at = ?, paper_status = ?, updated_at = ? WHERE id = ?",
+        "UPDATE posts SET title = ?, content = ?, summary = ?, github_url = ?, category_id = ?, is_published = ?, published_at = ?, paper_status = ?, is_double_blind = ?, is_preprint = ?, updated_at = ? WHERE id = ?",
     )
     .bind(&title)
     .bind(&content)
@@ -677,11 +1947,23 @@ async fn update_post(
     .bind(is_published)
     .bind(published_at)
     .bind(&paper_status)
+    .bind(is_double_blind)
+    .bind(is_preprint)
     .bind(now)
     .bind(post_id)
-    .execute(&pool)
+    .execute(pool)
+    .await
+    .map_err(AppError::internal)?;
+    record_status_transition(
+        pool,
+        post_id,
+        Some(current_user.id),
+        STATUS_TRIGGER_UPDATE,
+        Some(&post.paper_status),
+        &paper_status,
+    )
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     if file_changed {
         if let (Some(saved_path), Some(saved_name)) = (file_path.as_ref(), file_name.as_ref()) {
@@ -700,49 +1982,49 @@ async fn update_post(
             .bind(saved_name)
             .bind(now)
             .bind(now)
-            .execute(&pool)
+            .execute(pool)
             .await
-            .map_err(internal_error)?;
+            .map_err(AppError::internal)?;
         } else {
             sqlx::query("DELETE FROM post_files WHERE post_id = ?")
                 .bind(post_id)
-                .execute(&pool)
+                .execute(pool)
                 .await
-                .map_err(internal_error)?;
+                .map_err(AppError::internal)?;
         }
     }
 
-    let tags_vec = if let Some(t_str) = tags_str {
-        process_tags(&pool, post_id, &t_str).await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
+    let tags_vec = if let Some(t_str) = edits.tags {
+        process_tags(pool, post_id, &t_str)
+            .await
+            .map_err(AppError::internal)?
     } else {
-        fetch_tags(&pool, post_id).await.unwrap_or_default()
+        fetch_tags(pool, post_id).await.unwrap_or_default()
     };
 
     if category_code != PAPER_CATEGORY {
-        clear_all_post_citations(&pool, post_id).await?;
-        sqlx::query("UPDATE posts SET current_revision = 0, latest_paper_version_id = NULL WHERE id = ?")
-            .bind(post_id)
-            .execute(&pool)
-            .await
-            .map_err(internal_error)?;
+        clear_all_post_citations(pool, post_id).await?;
+        sqlx::query(
+            "UPDATE posts SET current_revision = 0, latest_paper_version_id = NULL WHERE id = ?",
+        )
+        .bind(post_id)
+        .execute(pool)
+        .await
+        .map_err(AppError::internal)?;
     } else {
         if let Some(ids) = manual_citation_ids {
-            replace_post_citations(&pool, post_id, &ids).await?;
+            replace_post_citations(pool, post_id, current_user.id, &ids).await?;
         }
 
         let auto_citation_ids =
-            prepare_auto_citations_for_content(&pool, &category_code, &content, Some(post_id))
+            prepare_auto_citations_for_content(pool, &category_code, &content, Some(post_id))
                 .await?;
-        replace_post_auto_citations(&pool, post_id, &auto_citation_ids).await?;
+        replace_post_auto_citations(pool, post_id, &auto_citation_ids).await?;
     }
+    refresh_author_metrics(pool, current_user.id).await?;
 
     if let Err(error) = sync_post_doi_metadata(
-        &pool,
+        pool,
         post_id,
         &category_code,
         &title,
@@ -760,14 +2042,15 @@ async fn update_post(
 
     if category_code == PAPER_CATEGORY && paper_status == PAPER_STATUS_SUBMITTED {
         let (paper_version_id, _) =
-            create_paper_version_snapshot(&pool, post_id, current_user.id).await?;
-        if let Err(error) = schedule_review(
-            &pool,
-            post_id,
-            Some(paper_version_id),
-            ReviewTrigger::AutoUpdate,
-        )
-        .await
+            create_paper_version_snapshot(pool, post_id, current_user.id).await?;
+        if is_feature_enabled(FEATURE_AI_REVIEW_AUTO_SCHEDULE, Some(&current_user))
+            && let Err(error) = schedule_review(
+                pool,
+                post_id,
+                Some(paper_version_id),
+                ReviewTrigger::AutoUpdate,
+            )
+            .await
         {
             tracing::error!(
                 "Failed to schedule auto AI review on update for post {}: {}",
@@ -775,25 +2058,52 @@ async fn update_post(
                 error
             );
         }
+        if post.paper_status != PAPER_STATUS_SUBMITTED {
+            queue_webhook_message(
+                pool,
+                WebhookMessage {
+                    event: WEBHOOK_EVENT_SUBMISSION,
+                    title: format!("New submission: {}", title),
+                    body: format!("{} submitted a paper for review.", current_user.username),
+                    post_id: Some(post_id),
+                },
+            );
+        }
     }
 
     let updated_post = sqlx::query_as::<_, Post>(&post_query)
         .bind(post_id)
-        .fetch_one(&pool)
+        .fetch_one(pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
-    let user_liked = fetch_user_liked(&pool, current_user.id, post_id)
-        .await
-        .map_err(internal_error)?;
-    let citation_count = compute_citation_count(&pool, post_id)
+    let user_liked = fetch_user_liked(pool, current_user.id, post_id)
         .await
-        .map_err(internal_error)?;
-    let doi_metadata = fetch_post_doi_metadata(&pool, post_id)
+        .map_err(AppError::internal)?;
+    let metrics = build_post_metrics(
+        post.citation_count,
+        post.external_citation_count,
+        post.influence_score,
+    );
+    let doi_metadata = fetch_post_doi_metadata(pool, post_id)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+
+    let blind_review_warnings = if updated_post.is_double_blind {
+        find_author_identity_mentions(
+            &current_user,
+            &updated_post.title,
+            updated_post.summary.as_deref(),
+            &updated_post.content,
+        )
+    } else {
+        Vec::new()
+    };
 
-    Ok(Json(PostResponse {
+    let retraction = updated_post.retraction_notice();
+    let preprint_badge = updated_post.preprint_badge();
+    invalidate_cached_post(post_id).await;
+    Ok(PostResponse {
         id: updated_post.id,
         title: updated_post.title,
         content: updated_post.content,
@@ -811,102 +2121,118 @@ async fn update_post(
         view_count: updated_post.view_count,
         like_count: updated_post.like_count,
         user_liked: Some(user_liked),
-        metrics: PostMetrics {
-            citation_count,
-            metric_version: METRIC_VERSION.to_string(),
-        },
+        metrics,
+        retraction,
         doi_metadata,
+        is_double_blind: updated_post.is_double_blind,
+        is_preprint: updated_post.is_preprint,
+        preprint_badge,
+        blind_review_warnings,
         created_at: updated_post.created_at,
         updated_at: updated_post.updated_at,
         tags: tags_vec,
-    }))
+    })
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{post_id}",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    responses(
+        (status = 200, description = "Post deleted"),
+        (status = 403, description = "Not the author", body = ErrorBody),
+        (status = 404, description = "Post not found", body = ErrorBody),
+    )
+)]
 async fn delete_post(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
 
-    let post_query = format!(
-        "{}{} WHERE p.id = ?",
-        POST_SELECT_COLUMNS, POST_SELECT_FROM_CLAUSE
-    );
-    let post = sqlx::query_as::<_, Post>(&post_query)
-        .bind(post_id)
-        .fetch_optional(&pool)
-        .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Post not found"})),
-            )
-        })?;
-
+    let post = fetch_post_summary(&pool, post_id).await?;
     if post.author_id != current_user.id {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({"detail": "Not authorized to delete this post"})),
-        ));
+        return Err(AppError::forbidden("Not authorized to delete this post"));
     }
 
     if let Some(ref path) = post.file_path {
         let _ = tokio::fs::remove_file(path).await;
     }
+    let version_file_paths = fetch_version_file_paths(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
 
+    let before = snapshot_post(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?;
     clear_all_post_citations(&pool, post_id).await?;
 
     sqlx::query("DELETE FROM posts WHERE id = ?")
         .bind(post_id)
         .execute(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+    record_audit_event(
+        &pool,
+        AuditEvent {
+            actor: &current_user,
+            action: AUDIT_ACTION_POST_DELETE,
+            target_type: AUDIT_TARGET_POST,
+            target_id: post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before,
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    remove_unreferenced_version_files(&pool, &version_file_paths)
+        .await
+        .map_err(AppError::internal)?;
+    refresh_author_metrics(&pool, current_user.id).await?;
+    invalidate_cached_post(post_id).await;
 
     Ok(Json(
         serde_json::json!({"message": "Post deleted successfully"}),
     ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/{post_id}/publish",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    responses(
+        (status = 200, description = "Paper published, or already published"),
+        (status = 400, description = "Only accepted papers can be published", body = ErrorBody),
+        (status = 403, description = "Not the author", body = ErrorBody),
+    )
+)]
 async fn publish_post(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
 
-    let row = sqlx::query_as::<_, (i64, String, String)>(
-        r#"
-        SELECT p.author_id, c.code AS category_code, p.paper_status
-        FROM posts p
-        JOIN post_categories c ON c.id = p.category_id
-        WHERE p.id = ?
-        "#,
-    )
-    .bind(post_id)
-    .fetch_optional(&pool)
-    .await
-    .map_err(internal_error)?
-    .ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Post not found"})),
-        )
-    })?;
-
-    let (author_id, category_code, paper_status) = row;
+    let PostSummary {
+        author_id,
+        category,
+        paper_status,
+        title,
+        ..
+    } = fetch_post_summary(&pool, post_id).await?;
     if current_user.id != author_id && !current_user.is_admin {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(serde_json::json!({"detail": "Not authorized to publish this post"})),
-        ));
+        return Err(AppError::forbidden("Not authorized to publish this post"));
     }
 
-    if category_code != PAPER_CATEGORY {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Only paper posts can use publish transition"})),
+    if category != PAPER_CATEGORY {
+        return Err(AppError::bad_request(
+            "Only paper posts can use publish transition",
         ));
     }
 
@@ -919,16 +2245,14 @@ async fn publish_post(
     }
 
     if paper_status != PAPER_STATUS_ACCEPTED {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "Only accepted papers can be published",
-                "paper_status": paper_status
-            })),
-        ));
+        return Err(
+            AppError::bad_request("Only accepted papers can be published")
+                .with_field("paper_status", paper_status),
+        );
     }
 
     let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
     sqlx::query(
         r#"
         UPDATE posts
@@ -940,46 +2264,326 @@ async fn publish_post(
         WHERE id = ?
         "#,
     )
-    .bind(PAPER_STATUS_PUBLISHED)
-    .bind(now)
-    .bind(now)
-    .bind(post_id)
-    .execute(&pool)
+    .bind(PAPER_STATUS_PUBLISHED)
+    .bind(now)
+    .bind(now)
+    .bind(post_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    record_status_transition(
+        &mut *tx,
+        post_id,
+        Some(current_user.id),
+        STATUS_TRIGGER_PUBLISH,
+        Some(&paper_status),
+        PAPER_STATUS_PUBLISHED,
+    )
+    .await
+    .map_err(AppError::internal)?;
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &current_user,
+            action: AUDIT_ACTION_POST_PUBLISH,
+            target_type: AUDIT_TARGET_POST,
+            target_id: post_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "paper_status": paper_status,
+                "is_published": false,
+            })),
+            after: Some(serde_json::json!({
+                "paper_status": PAPER_STATUS_PUBLISHED,
+                "is_published": true,
+                "published_at": now,
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    notify_followers_of_post(&pool, author_id, post_id, &title).await;
+    invalidate_cached_post(post_id).await;
+
+    Ok(Json(serde_json::json!({
+        "detail": "Paper published successfully",
+        "paper_status": PAPER_STATUS_PUBLISHED,
+        "is_published": true,
+        "published_at": now
+    })))
+}
+
+/// Copies an earlier submitted version back into the post as the working
+/// copy. Version history is untouched; the restored content becomes a new
+/// version only when the author submits it again.
+async fn restore_paper_version(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, version_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    let post = fetch_editable_post_summary(&pool, post_id, &current_user).await?;
+
+    if post.category != PAPER_CATEGORY {
+        return Err(AppError::bad_request(
+            "Only paper posts have versions to restore",
+        ));
+    }
+
+    if post.paper_status == PAPER_STATUS_PUBLISHED {
+        return Err(AppError::conflict(
+            "Published papers cannot be restored to an earlier version",
+        ));
+    }
+
+    let version = sqlx::query_as::<
+        _,
+        (
+            i32,
+            String,
+            String,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+        ),
+    >(
+        r#"
+        SELECT
+            CAST(version_number AS SIGNED),
+            title,
+            content,
+            summary,
+            github_url,
+            file_path,
+            file_name,
+            CAST(tags_json AS CHAR),
+            CAST(citations_json AS CHAR)
+        FROM paper_versions
+        WHERE id = ? AND post_id = ?
+        "#,
+    )
+    .bind(version_id)
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Paper version not found"))?;
+    let (
+        version_number,
+        title,
+        content,
+        summary,
+        github_url,
+        version_file_path,
+        version_file_name,
+        tags_json,
+        citations_json,
+    ) = version;
+
+    // The stored blob is copied back so later edits to the working copy
+    // leave the version untouched. Versions from before the version store
+    // may point at a file that has since been deleted.
+    let mut attachment_restored = false;
+    let mut restored_file = None;
+    if let (Some(path), Some(name)) = (version_file_path, version_file_name) {
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            let ext = normalized_extension(&name).unwrap_or_else(|| "bin".to_string());
+            let upload_path = PathBuf::from("uploads").join(format!("{}.{}", Uuid::new_v4(), ext));
+            tokio::fs::copy(&path, &upload_path)
+                .await
+                .map_err(AppError::internal)?;
+            attachment_restored = true;
+            restored_file = Some((upload_path.to_string_lossy().to_string(), name));
+        } else {
+            tracing::warn!(
+                "Attachment {} of paper version {} is missing; restoring without it",
+                path,
+                version_id
+            );
+        }
+    }
+
+    // A revision request stays open so the restored copy can answer it;
+    // anything else goes back to draft until the author submits again.
+    let paper_status = if post.paper_status == PAPER_STATUS_REVISION {
+        PAPER_STATUS_REVISION
+    } else {
+        PAPER_STATUS_DRAFT
+    };
+
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        UPDATE posts
+        SET
+            title = ?,
+            content = ?,
+            summary = ?,
+            github_url = ?,
+            paper_status = ?,
+            is_published = is_preprint AND published_at IS NOT NULL,
+            published_at = CASE WHEN is_preprint THEN published_at END,
+            updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&title)
+    .bind(&content)
+    .bind(&summary)
+    .bind(&github_url)
+    .bind(paper_status)
+    .bind(now)
+    .bind(post_id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    record_status_transition(
+        &pool,
+        post_id,
+        Some(current_user.id),
+        STATUS_TRIGGER_RESTORE,
+        Some(&post.paper_status),
+        paper_status,
+    )
+    .await
+    .map_err(AppError::internal)?;
+
+    if let Some((saved_path, saved_name)) = restored_file.as_ref() {
+        sqlx::query(
+            r#"
+            INSERT INTO post_files (post_id, file_path, file_name, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                file_path = VALUES(file_path),
+                file_name = VALUES(file_name),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(post_id)
+        .bind(saved_path)
+        .bind(saved_name)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    } else {
+        sqlx::query("DELETE FROM post_files WHERE post_id = ?")
+            .bind(post_id)
+            .execute(&pool)
+            .await
+            .map_err(AppError::internal)?;
+    }
+
+    // The working copy's file is only removed when no version still points
+    // at it.
+    if let Some(old_path) = post.file_path.as_ref()
+        && restored_file.as_ref().map(|(path, _)| path) != Some(old_path)
+    {
+        let referenced = sqlx::query("SELECT id FROM paper_versions WHERE file_path = ? LIMIT 1")
+            .bind(old_path)
+            .fetch_optional(&pool)
+            .await
+            .map_err(AppError::internal)?;
+        if referenced.is_none() {
+            let _ = tokio::fs::remove_file(old_path).await;
+        }
+    }
+
+    let tags: Vec<String> = tags_json
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    let tags = process_tags(&pool, post_id, &tags.join(","))
+        .await
+        .map_err(AppError::internal)?;
+
+    // Cited papers deleted since the snapshot are dropped instead of failing
+    // the restore.
+    let auto_citation_ids = retain_existing_papers(
+        &pool,
+        extract_auto_citation_ids(&content)
+            .into_iter()
+            .filter(|id| *id != post_id)
+            .collect(),
+    )
+    .await?;
+    let version_citation_ids: Vec<i64> = citations_json
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    let manual_citation_ids = retain_existing_papers(
+        &pool,
+        version_citation_ids
+            .into_iter()
+            .filter(|id| *id != post_id && !auto_citation_ids.contains(id))
+            .collect(),
+    )
+    .await?;
+    replace_post_citations(&pool, post_id, current_user.id, &manual_citation_ids).await?;
+    replace_post_auto_citations(&pool, post_id, &auto_citation_ids).await?;
+    refresh_author_metrics(&pool, current_user.id).await?;
+
+    if let Err(error) = sync_post_doi_metadata(
+        &pool,
+        post_id,
+        PAPER_CATEGORY,
+        &title,
+        summary.as_deref(),
+        &content,
+    )
     .await
-    .map_err(internal_error)?;
+    {
+        tracing::warn!(
+            "Failed to auto-collect DOI metadata for post {} on restore: {}",
+            post_id,
+            error
+        );
+    }
 
+    invalidate_cached_post(post_id).await;
     Ok(Json(serde_json::json!({
-        "detail": "Paper published successfully",
-        "paper_status": PAPER_STATUS_PUBLISHED,
-        "is_published": true,
-        "published_at": now
+        "detail": "Paper version restored as the working copy",
+        "restored_version_id": version_id,
+        "restored_version_number": version_number,
+        "paper_status": paper_status,
+        "attachment_restored": attachment_restored,
+        "tags": tags
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/{post_id}/like",
+    tag = "posts",
+    security(("bearer" = [])),
+    params(("post_id" = i64, Path, description = "Post id")),
+    responses(
+        (status = 200, description = "Like toggled; returns `user_liked` and `like_count`"),
+        (status = 404, description = "Post not found", body = ErrorBody),
+    )
+)]
 async fn like_post(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
 
-    let post_row = sqlx::query_as::<_, (bool,)>("SELECT is_published FROM posts WHERE id = ?")
-        .bind(post_id)
-        .fetch_optional(&pool)
-        .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "Post not found"})),
-            )
-        })?;
+    let post_row = sqlx::query_as::<_, (bool,)>(
+        "SELECT is_published FROM posts WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(post_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
     let (is_published,) = post_row;
     if !is_published {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Post not found"})),
-        ));
+        return Err(AppError::not_found("Post not found"));
     }
 
     let existing = sqlx::query("SELECT id FROM post_likes WHERE user_id = ? AND post_id = ?")
@@ -987,46 +2591,59 @@ async fn like_post(
         .bind(post_id)
         .fetch_optional(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
-    let user_liked = if existing.is_some() {
-        sqlx::query("DELETE FROM post_likes WHERE user_id = ? AND post_id = ?")
+    // Adjust the counter by the rows actually changed rather than recounting
+    // every like; a toggle that lost a race changes nothing. The rollup job
+    // recounts posts with recent like activity to correct any drift.
+    let (user_liked, changed) = if existing.is_some() {
+        let result = sqlx::query("DELETE FROM post_likes WHERE user_id = ? AND post_id = ?")
             .bind(current_user.id)
             .bind(post_id)
             .execute(&pool)
             .await
-            .map_err(internal_error)?;
-        false
+            .map_err(AppError::internal)?;
+        (false, -(result.rows_affected() as i64))
     } else {
-        sqlx::query("INSERT INTO post_likes (user_id, post_id, created_at) VALUES (?, ?, ?)")
-            .bind(current_user.id)
-            .bind(post_id)
-            .bind(Utc::now())
-            .execute(&pool)
-            .await
-            .map_err(internal_error)?;
-        true
-    };
-
-    let (new_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM post_likes WHERE post_id = ?")
+        let result = sqlx::query(
+            "INSERT IGNORE INTO post_likes (user_id, post_id, created_at) VALUES (?, ?, ?)",
+        )
+        .bind(current_user.id)
         .bind(post_id)
-        .fetch_one(&pool)
+        .bind(Utc::now())
+        .execute(&pool)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+        (true, result.rows_affected() as i64)
+    };
 
     sqlx::query(
         r#"
         INSERT INTO post_stats (post_id, view_count, like_count, updated_at)
-        VALUES (?, 0, ?, ?)
-        ON DUPLICATE KEY UPDATE like_count = VALUES(like_count), updated_at = VALUES(updated_at)
+        VALUES (?, 0, GREATEST(?, 0), ?)
+        ON DUPLICATE KEY UPDATE like_count = GREATEST(like_count + ?, 0), updated_at = VALUES(updated_at)
         "#,
     )
     .bind(post_id)
-    .bind(new_count)
+    .bind(changed)
     .bind(Utc::now())
+    .bind(changed)
     .execute(&pool)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
+    let (new_count,): (i64,) =
+        sqlx::query_as("SELECT like_count FROM post_stats WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+    let event_type = if user_liked {
+        POST_EVENT_LIKE
+    } else {
+        POST_EVENT_UNLIKE
+    };
+    record_post_event(&pool, post_id, event_type, Some(current_user.id), None).await;
 
     Ok(Json(serde_json::json!({
         "message": if user_liked { "Post liked" } else { "Post unliked" },
@@ -1099,7 +2716,7 @@ fn push_post_filters(
     if let Some(min_citations) = filters.min_citation_count {
         push_condition(query_builder, has_where);
         query_builder.push(
-            "(SELECT COUNT(*) FROM (SELECT DISTINCT pc.citing_post_id, pc.cited_post_id FROM post_citations pc) citation_edges WHERE citation_edges.cited_post_id = p.id) >= ",
+            "COALESCE((SELECT ps_filter.citation_count FROM post_stats ps_filter WHERE ps_filter.post_id = p.id), 0) >= ",
         );
         query_builder.push_bind(min_citations);
     }
@@ -1107,7 +2724,7 @@ fn push_post_filters(
     if let Some(max_citations) = filters.max_citation_count {
         push_condition(query_builder, has_where);
         query_builder.push(
-            "(SELECT COUNT(*) FROM (SELECT DISTINCT pc.citing_post_id, pc.cited_post_id FROM post_citations pc) citation_edges WHERE citation_edges.cited_post_id = p.id) <= ",
+            "COALESCE((SELECT ps_filter.citation_count FROM post_stats ps_filter WHERE ps_filter.post_id = p.id), 0) <= ",
         );
         query_builder.push_bind(max_citations);
     }
@@ -1115,51 +2732,62 @@ fn push_post_filters(
     if let Some(min_author_g_index) = filters.min_author_g_index {
         push_condition(query_builder, has_where);
         query_builder.push(
-            r#"
-            (
-                SELECT COALESCE(MAX(gcalc.rn), 0)
-                FROM (
-                    SELECT ranked.rn, ranked.cum_citations
-                    FROM (
-                        SELECT
-                            ROW_NUMBER() OVER (ORDER BY author_papers.citation_count DESC, author_papers.post_id ASC) AS rn,
-                            SUM(author_papers.citation_count) OVER (
-                                ORDER BY author_papers.citation_count DESC, author_papers.post_id ASC
-                                ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
-                            ) AS cum_citations
-                        FROM (
-                            SELECT
-                                ap.id AS post_id,
-                                COALESCE(citation_counts.citation_count, 0) AS citation_count
-                            FROM posts ap
-                            JOIN post_categories apc ON apc.id = ap.category_id
-                            LEFT JOIN (
-                                SELECT distinct_edges.cited_post_id, COUNT(*) AS citation_count
-                                FROM (
-                                    SELECT DISTINCT citing_post_id, cited_post_id
-                                    FROM post_citations
-                                ) distinct_edges
-                                GROUP BY distinct_edges.cited_post_id
-                            ) citation_counts ON citation_counts.cited_post_id = ap.id
-                            WHERE ap.author_id = p.author_id AND apc.code = 'paper'
-                        ) author_papers
-                    ) ranked
-                    WHERE ranked.cum_citations >= (ranked.rn * ranked.rn)
-                ) gcalc
-            ) >= 
-            "#,
+            "COALESCE((SELECT amc.g_index FROM author_metrics_cache amc WHERE amc.user_id = p.author_id), 0) >= ",
         );
         query_builder.push_bind(min_author_g_index);
     }
+
+    if let Some(min_author_h_index) = filters.min_author_h_index {
+        push_condition(query_builder, has_where);
+        query_builder.push(
+            "COALESCE((SELECT amc.h_index FROM author_metrics_cache amc WHERE amc.user_id = p.author_id), 0) >= ",
+        );
+        query_builder.push_bind(min_author_h_index);
+    }
+
+    if let Some(min_author_i10_index) = filters.min_author_i10_index {
+        push_condition(query_builder, has_where);
+        query_builder.push(
+            "COALESCE((SELECT amc.i10_index FROM author_metrics_cache amc WHERE amc.user_id = p.author_id), 0) >= ",
+        );
+        query_builder.push_bind(min_author_i10_index);
+    }
+
+    if let Some(issue_id) = filters.issue_id {
+        push_condition(query_builder, has_where);
+        query_builder.push("p.issue_id = ");
+        query_builder.push_bind(issue_id);
+    }
+
+    if let Some(preprint) = filters.preprint {
+        push_condition(query_builder, has_where);
+        query_builder.push("p.is_preprint = ");
+        query_builder.push_bind(preprint);
+    }
+
+    // Preprints still under double-blind review would give their authors
+    // away, so the following feed leaves them out.
+    if let Some(follower_id) = filters.followed_by {
+        push_condition(query_builder, has_where);
+        query_builder.push(
+            "p.author_id IN (SELECT uf.followed_id FROM user_follows uf WHERE uf.follower_id = ",
+        );
+        query_builder.push_bind(follower_id);
+        query_builder.push(
+            ") AND NOT (p.is_double_blind = TRUE AND p.paper_status IN ('draft', 'submitted', 'revision'))",
+        );
+    }
 }
 
 fn push_visibility_filter(query_builder: &mut QueryBuilder<MySql>, has_where: &mut bool) {
     push_condition(query_builder, has_where);
-    query_builder.push("p.is_published = TRUE");
+    query_builder.push("p.is_published = TRUE AND p.deleted_at IS NULL");
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct PostDetailQuery {
+    /// `review_center` lets assigned reviewers open an unpublished paper.
     source: Option<String>,
 }
 
@@ -1175,6 +2803,12 @@ struct ResolvedPostFilters {
     min_citation_count: Option<i64>,
     max_citation_count: Option<i64>,
     min_author_g_index: Option<i64>,
+    min_author_h_index: Option<i64>,
+    min_author_i10_index: Option<i64>,
+    issue_id: Option<i64>,
+    preprint: Option<bool>,
+    followed_by: Option<i64>,
+    order_by: &'static str,
 }
 
 #[derive(Debug, Clone)]
@@ -1188,6 +2822,42 @@ struct DoiMetadataRecord {
     raw_json: Option<String>,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct PostImportSourceRow {
+    post_id: i64,
+    source_type: String,
+    source_identifier: String,
+    source_url: String,
+    source_doi: Option<String>,
+    authors_json: Option<String>,
+    license_url: Option<String>,
+    pdf_imported: bool,
+    imported_by: Option<i64>,
+    imported_at: DateTime<Utc>,
+    author_id: i64,
+    is_published: bool,
+}
+
+type DoiMetadataRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// A `DoiMetadataRow` led by its post id.
+type PostDoiMetadataRow = (
+    i64,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
 fn normalize_query_value(value: &Option<String>) -> Option<String> {
     value
         .as_deref()
@@ -1196,10 +2866,14 @@ fn normalize_query_value(value: &Option<String>) -> Option<String> {
         .map(ToOwned::to_owned)
 }
 
-fn resolve_post_filters(
-    query: &PostQuery,
-) -> Result<ResolvedPostFilters, (StatusCode, Json<serde_json::Value>)> {
-    let category = normalize_query_value(&query.category).map(|value| value.to_ascii_lowercase());
+fn resolve_post_filters(query: &PostQuery) -> Result<ResolvedPostFilters, AppError> {
+    let mut category =
+        normalize_query_value(&query.category).map(|value| value.to_ascii_lowercase());
+    let mut preprint = query.preprint;
+    if category.as_deref() == Some(PREPRINT_CATEGORY_ALIAS) {
+        category = Some(PAPER_CATEGORY.to_string());
+        preprint = Some(true);
+    }
     let search_pattern = normalize_query_value(&query.search).map(|value| format!("%{}%", value));
     let tag = normalize_query_value(&query.tag);
     let author_pattern = normalize_query_value(&query.author).map(|value| format!("%{}%", value));
@@ -1215,60 +2889,64 @@ fn resolve_post_filters(
     let min_citation_count = query.min_citation_count;
     let max_citation_count = query.max_citation_count;
     let min_author_g_index = query.min_author_g_index;
+    let min_author_h_index = query.min_author_h_index;
+    let min_author_i10_index = query.min_author_i10_index;
+    let issue_id = query.issue_id;
+    let followed_by = query.followed_by;
+    let order_by = resolve_post_order(normalize_query_value(&query.sort).as_deref())?;
+
+    if let Some(filter_year) = year
+        && !(1900..=2100).contains(&filter_year)
+    {
+        return Err(AppError::bad_request("year must be between 1900 and 2100"));
+    }
 
-    if let Some(filter_year) = year {
-        if !(1900..=2100).contains(&filter_year) {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "detail": "year must be between 1900 and 2100"
-                })),
-            ));
-        }
+    if let Some(min_value) = min_citation_count
+        && min_value < 0
+    {
+        return Err(AppError::bad_request(
+            "min_citation_count must be 0 or greater",
+        ));
     }
 
-    if let Some(min_value) = min_citation_count {
-        if min_value < 0 {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "detail": "min_citation_count must be 0 or greater"
-                })),
-            ));
-        }
+    if let Some(max_value) = max_citation_count
+        && max_value < 0
+    {
+        return Err(AppError::bad_request(
+            "max_citation_count must be 0 or greater",
+        ));
     }
 
-    if let Some(max_value) = max_citation_count {
-        if max_value < 0 {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "detail": "max_citation_count must be 0 or greater"
-                })),
-            ));
-        }
+    if let (Some(min_value), Some(max_value)) = (min_citation_count, max_citation_count)
+        && min_value > max_value
+    {
+        return Err(AppError::bad_request(
+            "min_citation_count cannot be greater than max_citation_count",
+        ));
     }
 
-    if let (Some(min_value), Some(max_value)) = (min_citation_count, max_citation_count) {
-        if min_value > max_value {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "detail": "min_citation_count cannot be greater than max_citation_count"
-                })),
-            ));
-        }
+    if let Some(min_g_index) = min_author_g_index
+        && min_g_index < 0
+    {
+        return Err(AppError::bad_request(
+            "min_author_g_index must be 0 or greater",
+        ));
     }
 
-    if let Some(min_g_index) = min_author_g_index {
-        if min_g_index < 0 {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "detail": "min_author_g_index must be 0 or greater"
-                })),
-            ));
-        }
+    if let Some(min_h_index) = min_author_h_index
+        && min_h_index < 0
+    {
+        return Err(AppError::bad_request(
+            "min_author_h_index must be 0 or greater",
+        ));
+    }
+
+    if let Some(min_i10_index) = min_author_i10_index
+        && min_i10_index < 0
+    {
+        return Err(AppError::bad_request(
+            "min_author_i10_index must be 0 or greater",
+        ));
     }
 
     Ok(ResolvedPostFilters {
@@ -1282,12 +2960,28 @@ fn resolve_post_filters(
         min_citation_count,
         max_citation_count,
         min_author_g_index,
+        min_author_h_index,
+        min_author_i10_index,
+        issue_id,
+        preprint,
+        followed_by,
+        order_by,
     })
 }
 
-fn validate_paper_status_filter(
-    raw: &str,
-) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+fn resolve_post_order(raw: Option<&str>) -> Result<&'static str, AppError> {
+    match raw.map(|value| value.to_ascii_lowercase()).as_deref() {
+        None | Some("latest") => Ok("p.created_at DESC"),
+        Some("citations") => Ok("COALESCE(ps.citation_count, 0) DESC, p.created_at DESC"),
+        Some("influence") => Ok("COALESCE(ps.influence_score, 0) DESC, p.created_at DESC"),
+        Some("trending") => Ok("COALESCE(ps.trending_score, 0) DESC, p.created_at DESC"),
+        Some(_) => Err(AppError::bad_request(
+            "sort must be one of: latest, citations, influence, trending",
+        )),
+    }
+}
+
+pub fn validate_paper_status_filter(raw: &str) -> Result<String, AppError> {
     let valid = [
         PAPER_STATUS_DRAFT,
         PAPER_STATUS_SUBMITTED,
@@ -1301,27 +2995,19 @@ fn validate_paper_status_filter(
         return Ok(raw.to_string());
     }
 
-    Err((
-        StatusCode::BAD_REQUEST,
-        Json(serde_json::json!({
-            "detail": "paper_status must be one of: draft, submitted, revision, accepted, published, rejected"
-        })),
+    Err(AppError::bad_request(
+        "paper_status must be one of: draft, submitted, revision, accepted, published, rejected",
     ))
 }
 
-fn validate_ai_decision_filter(
-    raw: &str,
-) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+fn validate_ai_decision_filter(raw: &str) -> Result<String, AppError> {
     let valid = ["accept", "minor_revision", "major_revision", "reject"];
     if valid.contains(&raw) {
         return Ok(raw.to_string());
     }
 
-    Err((
-        StatusCode::BAD_REQUEST,
-        Json(serde_json::json!({
-            "detail": "ai_decision must be one of: accept, minor_revision, major_revision, reject"
-        })),
+    Err(AppError::bad_request(
+        "ai_decision must be one of: accept, minor_revision, major_revision, reject",
     ))
 }
 
@@ -1341,21 +3027,18 @@ fn normalize_paper_status(raw: &str) -> String {
 fn resolve_create_paper_status(
     category_code: &str,
     requested_status: Option<&str>,
-) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<String, AppError> {
     let requested = requested_status
         .map(normalize_paper_status)
         .filter(|value| !value.is_empty());
 
     if category_code != PAPER_CATEGORY {
-        if let Some(value) = requested {
-            if value != PAPER_STATUS_PUBLISHED {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({
-                        "detail": "paper_status can only be set to 'published' for non-paper posts"
-                    })),
-                ));
-            }
+        if let Some(value) = requested
+            && value != PAPER_STATUS_PUBLISHED
+        {
+            return Err(AppError::bad_request(
+                "paper_status can only be set to 'published' for non-paper posts",
+            ));
         }
         return Ok(PAPER_STATUS_PUBLISHED.to_string());
     }
@@ -1363,15 +3046,10 @@ fn resolve_create_paper_status(
     match requested.as_deref() {
         None | Some(PAPER_STATUS_SUBMITTED) => Ok(PAPER_STATUS_SUBMITTED.to_string()),
         Some(PAPER_STATUS_DRAFT) => Ok(PAPER_STATUS_DRAFT.to_string()),
-        Some(other) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": format!(
-                    "Invalid paper_status '{}' for paper create. Allowed: draft, submitted",
-                    other
-                )
-            })),
-        )),
+        Some(other) => Err(AppError::bad_request(format!(
+            "Invalid paper_status '{}' for paper create. Allowed: draft, submitted",
+            other
+        ))),
     }
 }
 
@@ -1379,21 +3057,18 @@ fn resolve_update_paper_status(
     category_code: &str,
     _current_status: &str,
     requested_status: Option<&str>,
-) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<String, AppError> {
     let requested = requested_status
         .map(normalize_paper_status)
         .filter(|value| !value.is_empty());
 
     if category_code != PAPER_CATEGORY {
-        if let Some(value) = requested {
-            if value != PAPER_STATUS_PUBLISHED {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({
-                        "detail": "paper_status can only be set to 'published' for non-paper posts"
-                    })),
-                ));
-            }
+        if let Some(value) = requested
+            && value != PAPER_STATUS_PUBLISHED
+        {
+            return Err(AppError::bad_request(
+                "paper_status can only be set to 'published' for non-paper posts",
+            ));
         }
         return Ok(PAPER_STATUS_PUBLISHED.to_string());
     }
@@ -1401,40 +3076,121 @@ fn resolve_update_paper_status(
     match requested.as_deref() {
         None | Some(PAPER_STATUS_SUBMITTED) => Ok(PAPER_STATUS_SUBMITTED.to_string()),
         Some(PAPER_STATUS_DRAFT) => Ok(PAPER_STATUS_DRAFT.to_string()),
-        Some(other) => Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": format!(
-                    "Invalid paper_status '{}' for paper update. Allowed: draft, submitted",
-                    other
-                )
-            })),
-        )),
+        Some(other) => Err(AppError::bad_request(format!(
+            "Invalid paper_status '{}' for paper update. Allowed: draft, submitted",
+            other
+        ))),
+    }
+}
+
+/// Double-blind and preprint modes are fixed once a paper has been
+/// submitted, so reviewers and readers never see a paper change its terms.
+fn resolve_paper_flag_update(
+    flag_name: &str,
+    category_code: &str,
+    post: &Post,
+    current: bool,
+    requested: Option<bool>,
+) -> Result<bool, AppError> {
+    let Some(requested) = requested else {
+        return Ok(current && category_code == PAPER_CATEGORY);
+    };
+    if requested && category_code != PAPER_CATEGORY {
+        return Err(paper_only_flag_error(flag_name));
+    }
+    if requested != current
+        && post.category == PAPER_CATEGORY
+        && category_code == PAPER_CATEGORY
+        && post.paper_status != PAPER_STATUS_DRAFT
+    {
+        return Err(AppError::conflict(format!(
+            "{} can only be changed before the paper is submitted",
+            flag_name
+        ))
+        .with_field("paper_status", &post.paper_status));
+    }
+
+    Ok(requested)
+}
+
+fn paper_only_flag_error(flag_name: &str) -> AppError {
+    AppError::bad_request(format!("{} is only available for papers", flag_name))
+}
+
+/// A preprint is public from the start, so its author cannot be hidden.
+fn ensure_compatible_paper_flags(is_double_blind: bool, is_preprint: bool) -> Result<(), AppError> {
+    if is_double_blind && is_preprint {
+        return Err(AppError::bad_request(
+            "Preprints cannot use double-blind review",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Lists where a double-blind manuscript names its author, so the author can
+/// remove it before reviewers read the paper. Attachments are not scanned.
+fn find_author_identity_mentions(
+    author: &User,
+    title: &str,
+    summary: Option<&str>,
+    content: &str,
+) -> Vec<String> {
+    let email_name = author.email.split('@').next();
+    let mut names: Vec<&str> = [
+        Some(author.username.as_str()),
+        author.display_name.as_deref(),
+        email_name,
+    ]
+    .into_iter()
+    .flatten()
+    .map(str::trim)
+    .filter(|name| name.chars().count() >= MIN_IDENTITY_NAME_CHARS)
+    .collect();
+    let mut seen = HashSet::new();
+    names.retain(|name| seen.insert(name.to_lowercase()));
+
+    let mut warnings = Vec::new();
+    for (field, text) in [
+        ("title", Some(title)),
+        ("summary", summary),
+        ("content", Some(content)),
+    ] {
+        let Some(text) = text.map(str::to_lowercase) else {
+            continue;
+        };
+        for name in &names {
+            if contains_whole_word(&text, &name.to_lowercase()) {
+                warnings.push(format!(
+                    "The {} mentions the author name \"{}\"",
+                    field, name
+                ));
+            }
+        }
     }
+
+    warnings
+}
+
+fn contains_whole_word(haystack: &str, needle: &str) -> bool {
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
 }
 
-fn validate_github_url(raw: &str) -> Result<Option<String>, (StatusCode, Json<serde_json::Value>)> {
+fn validate_github_url(raw: &str) -> Result<Option<String>, AppError> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return Ok(None);
     }
 
-    let parsed = Url::parse(trimmed).map_err(|_| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "github_url must be a valid URL"
-            })),
-        )
-    })?;
+    let parsed =
+        Url::parse(trimmed).map_err(|_| AppError::bad_request("github_url must be a valid URL"))?;
 
     if parsed.scheme() != "http" && parsed.scheme() != "https" {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "github_url must use http or https"
-            })),
-        ));
+        return Err(AppError::bad_request("github_url must use http or https"));
     }
 
     let host = parsed
@@ -1445,12 +3201,7 @@ fn validate_github_url(raw: &str) -> Result<Option<String>, (StatusCode, Json<se
         host == "github.com" || host == "www.github.com" || host.ends_with(".github.com");
 
     if !is_github_host {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "github_url must point to github.com"
-            })),
-        ));
+        return Err(AppError::bad_request("github_url must point to github.com"));
     }
 
     Ok(Some(parsed.to_string()))
@@ -1463,36 +3214,29 @@ fn normalized_extension(filename: &str) -> Option<String> {
         .map(|ext| ext.to_ascii_lowercase())
 }
 
-fn validate_upload_file(
-    original_name: &str,
-    file_size_bytes: usize,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
-    if file_size_bytes > MAX_UPLOAD_SIZE_BYTES {
-        return Err((
-            StatusCode::PAYLOAD_TOO_LARGE,
-            Json(serde_json::json!({
-                "detail": format!("File too large. Max size is {}MB", MAX_UPLOAD_SIZE_BYTES / 1024 / 1024)
-            })),
-        ));
-    }
+/// `UPLOAD_MAX_SIZE_MB`, capped at the ceiling the body limit was sized for.
+fn max_upload_size_bytes() -> usize {
+    setting::<usize>("UPLOAD_MAX_SIZE_MB")
+        .filter(|megabytes| *megabytes > 0)
+        .unwrap_or(DEFAULT_MAX_UPLOAD_SIZE_MB)
+        .min(MAX_UPLOAD_SIZE_MB_CEILING as usize)
+        * 1024
+        * 1024
+}
 
-    let extension = normalized_extension(original_name).ok_or_else(|| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "File extension is required"})),
-        )
-    })?;
+/// Checks the file type before any of the upload is read and returns its
+/// lower-cased extension. The size is checked while the file streams in.
+fn validate_upload_name(original_name: &str) -> Result<String, AppError> {
+    let extension = normalized_extension(original_name)
+        .ok_or_else(|| AppError::bad_request("File extension is required"))?;
 
     if !ALLOWED_UPLOAD_EXTENSIONS.contains(&extension.as_str()) {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "Unsupported file type. Allowed types: pdf, doc, docx, txt, md, pptx, xlsx, zip, png, jpg, jpeg, gif"
-            })),
+        return Err(AppError::bad_request(
+            "Unsupported file type. Allowed types: pdf, doc, docx, txt, md, pptx, xlsx, zip, png, jpg, jpeg, gif",
         ));
     }
 
-    Ok(())
+    Ok(extension)
 }
 
 async fn fetch_user_liked(
@@ -1509,6 +3253,31 @@ async fn fetch_user_liked(
     Ok(liked.is_some())
 }
 
+async fn fetch_liked_post_ids(
+    pool: &MySqlPool,
+    user_id: i64,
+    posts: &[Post],
+) -> Result<HashSet<i64>, sqlx::Error> {
+    if posts.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let mut query_builder =
+        QueryBuilder::<MySql>::new("SELECT post_id FROM post_likes WHERE user_id = ");
+    query_builder.push_bind(user_id);
+    query_builder.push(" AND post_id IN (");
+    {
+        let mut separated = query_builder.separated(", ");
+        for post in posts {
+            separated.push_bind(post.id);
+        }
+    }
+    query_builder.push(")");
+
+    let rows: Vec<(i64,)> = query_builder.build_query_as().fetch_all(pool).await?;
+    Ok(rows.into_iter().map(|(post_id,)| post_id).collect())
+}
+
 async fn fetch_authors_map(
     pool: &MySqlPool,
     posts: &[Post],
@@ -1591,62 +3360,75 @@ async fn process_tags(
     post_id: i64,
     tags_str: &str,
 ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let tags = parse_tag_list(tags_str);
+
+    let mut tx = pool.begin().await?;
+    replace_post_tags(&mut tx, post_id, &tags).await?;
+    tx.commit().await?;
+
+    Ok(tags)
+}
+
+/// Trimmed, non-empty names of a comma-separated tag list, without repeats.
+fn parse_tag_list(tags_str: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in tags_str.split(',').map(str::trim) {
+        if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Replaces the post's tags, creating the ones that do not exist yet.
+async fn replace_post_tags(
+    tx: &mut Transaction<'_, MySql>,
+    post_id: i64,
+    tags: &[String],
+) -> Result<(), sqlx::Error> {
     sqlx::query("DELETE FROM post_tags WHERE post_id = ?")
         .bind(post_id)
-        .execute(pool)
+        .execute(&mut **tx)
         .await?;
 
-    let tags: Vec<String> = tags_str
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    let mut final_tags = Vec::new();
-
     for tag in tags {
         let tag_id: i64 = if let Some(row) =
             sqlx::query_as::<_, (i64,)>("SELECT id FROM tags WHERE name = ?")
-                .bind(&tag)
-                .fetch_optional(pool)
+                .bind(tag)
+                .fetch_optional(&mut **tx)
                 .await?
         {
             row.0
         } else {
             let res = sqlx::query("INSERT INTO tags (name) VALUES (?)")
-                .bind(&tag)
-                .execute(pool)
+                .bind(tag)
+                .execute(&mut **tx)
                 .await?;
             res.last_insert_id() as i64
         };
 
-        let _ = sqlx::query("INSERT IGNORE INTO post_tags (post_id, tag_id) VALUES (?, ?)")
+        sqlx::query("INSERT IGNORE INTO post_tags (post_id, tag_id) VALUES (?, ?)")
             .bind(post_id)
             .bind(tag_id)
-            .execute(pool)
-            .await;
-
-        final_tags.push(tag);
+            .execute(&mut **tx)
+            .await?;
     }
 
-    Ok(final_tags)
+    Ok(())
 }
 
 async fn prepare_citations_for_create(
     pool: &MySqlPool,
     category: &str,
     citations_raw: Option<&str>,
-) -> Result<Vec<i64>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Vec<i64>, AppError> {
     if category != PAPER_CATEGORY {
         if citations_raw.unwrap_or_default().trim().is_empty() {
             return Ok(Vec::new());
         }
 
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "Citations are only allowed for paper category posts"
-            })),
+        return Err(AppError::bad_request(
+            "Citations are only allowed for paper category posts",
         ));
     }
 
@@ -1660,26 +3442,20 @@ async fn prepare_citations_for_update(
     post_id: i64,
     category: &str,
     citations_raw: &str,
-) -> Result<Vec<i64>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Vec<i64>, AppError> {
     if category != PAPER_CATEGORY {
         if citations_raw.trim().is_empty() {
             return Ok(Vec::new());
         }
 
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "Citations are only allowed for paper category posts"
-            })),
+        return Err(AppError::bad_request(
+            "Citations are only allowed for paper category posts",
         ));
     }
 
     let citation_ids = parse_citation_ids(citations_raw)?;
     if citation_ids.contains(&post_id) {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"detail": "Self-citation is not allowed"})),
-        ));
+        return Err(AppError::bad_request("Self-citation is not allowed"));
     }
 
     validate_citation_targets(pool, &citation_ids).await?;
@@ -1691,7 +3467,7 @@ async fn prepare_auto_citations_for_content(
     category: &str,
     content: &str,
     current_post_id: Option<i64>,
-) -> Result<Vec<i64>, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Vec<i64>, AppError> {
     if category != PAPER_CATEGORY {
         return Ok(Vec::new());
     }
@@ -1705,7 +3481,7 @@ async fn prepare_auto_citations_for_content(
     Ok(citation_ids)
 }
 
-fn parse_citation_ids(raw: &str) -> Result<Vec<i64>, (StatusCode, Json<serde_json::Value>)> {
+fn parse_citation_ids(raw: &str) -> Result<Vec<i64>, AppError> {
     if raw.trim().is_empty() {
         return Ok(Vec::new());
     }
@@ -1720,18 +3496,12 @@ fn parse_citation_ids(raw: &str) -> Result<Vec<i64>, (StatusCode, Json<serde_jso
         }
 
         let parsed = trimmed.parse::<i64>().map_err(|_| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "detail": "Citations must be comma-separated numeric post IDs"
-                })),
-            )
+            AppError::bad_request("Citations must be comma-separated numeric post IDs")
         })?;
 
         if parsed <= 0 {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"detail": "Citation post IDs must be positive integers"})),
+            return Err(AppError::bad_request(
+                "Citation post IDs must be positive integers",
             ));
         }
 
@@ -1772,30 +3542,28 @@ fn extract_ids_after_pattern(content: &str, pattern: &str, target: &mut HashSet<
             end += 1;
         }
 
-        if end > start {
-            if let Ok(id_str) = std::str::from_utf8(&bytes[start..end]) {
-                if let Ok(id) = id_str.parse::<i64>() {
-                    if id > 0 {
-                        target.insert(id);
-                    }
-                }
-            }
+        if end > start
+            && let Ok(id_str) = std::str::from_utf8(&bytes[start..end])
+            && let Ok(id) = id_str.parse::<i64>()
+            && id > 0
+        {
+            target.insert(id);
         }
 
         cursor = start;
     }
 }
 
-async fn validate_citation_targets(
+pub async fn validate_citation_targets(
     pool: &MySqlPool,
     citation_ids: &[i64],
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<(), AppError> {
     if citation_ids.is_empty() {
         return Ok(());
     }
 
     let mut query_builder = QueryBuilder::<MySql>::new(
-        "SELECT p.id FROM posts p JOIN post_categories c ON c.id = p.category_id WHERE c.code = 'paper' AND p.id IN (",
+        "SELECT p.id FROM posts p JOIN post_categories c ON c.id = p.category_id WHERE c.code = 'paper' AND p.deleted_at IS NULL AND p.id IN (",
     );
     {
         let mut separated = query_builder.separated(", ");
@@ -1809,28 +3577,59 @@ async fn validate_citation_targets(
         .build_query_as()
         .fetch_all(pool)
         .await
-        .map_err(internal_error)?;
-    let valid_ids: HashSet<i64> = rows.into_iter().map(|(id,)| id).collect();
-
-    if valid_ids.len() != citation_ids.len() {
-        let invalid_ids: Vec<String> = citation_ids
-            .iter()
-            .filter(|id| !valid_ids.contains(id))
-            .map(|id| id.to_string())
-            .collect();
-
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": format!("Invalid citation target post IDs: {}", invalid_ids.join(", "))
-            })),
-        ));
-    }
+        .map_err(AppError::internal)?;
+    let valid_ids: HashSet<i64> = rows.into_iter().map(|(id,)| id).collect();
+
+    if valid_ids.len() != citation_ids.len() {
+        let invalid_ids: Vec<String> = citation_ids
+            .iter()
+            .filter(|id| !valid_ids.contains(id))
+            .map(|id| id.to_string())
+            .collect();
+
+        return Err(AppError::bad_request(format!(
+            "Invalid citation target post IDs: {}",
+            invalid_ids.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+async fn retain_existing_papers(
+    pool: &MySqlPool,
+    post_ids: Vec<i64>,
+) -> Result<Vec<i64>, AppError> {
+    if post_ids.is_empty() {
+        return Ok(post_ids);
+    }
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        "SELECT p.id FROM posts p JOIN post_categories c ON c.id = p.category_id WHERE c.code = 'paper' AND p.deleted_at IS NULL AND p.id IN (",
+    );
+    {
+        let mut separated = query_builder.separated(", ");
+        for post_id in &post_ids {
+            separated.push_bind(post_id);
+        }
+    }
+    query_builder.push(")");
+
+    let valid_ids: HashSet<i64> = query_builder
+        .build_query_scalar()
+        .fetch_all(pool)
+        .await
+        .map_err(AppError::internal)?
+        .into_iter()
+        .collect();
 
-    Ok(())
+    Ok(post_ids
+        .into_iter()
+        .filter(|id| valid_ids.contains(id))
+        .collect())
 }
 
-fn normalize_category_code(raw: &str) -> String {
+pub fn normalize_category_code(raw: &str) -> String {
     let normalized = raw.trim().to_ascii_lowercase();
     if normalized.is_empty() {
         "other".to_string()
@@ -1839,7 +3638,7 @@ fn normalize_category_code(raw: &str) -> String {
     }
 }
 
-fn category_display_name(code: &str) -> String {
+pub fn category_display_name(code: &str) -> String {
     code.split('_')
         .filter(|segment| !segment.is_empty())
         .map(|segment| {
@@ -1858,23 +3657,36 @@ fn category_display_name(code: &str) -> String {
         .join(" ")
 }
 
+/// Looks up a category by code, creating it unless posts are restricted to
+/// the existing categories. Disabled categories are refused, except for
+/// `current_code`, the category a post being edited already has.
 async fn resolve_or_create_category(
     pool: &MySqlPool,
     raw_category: &str,
-) -> Result<(i64, String), (StatusCode, Json<serde_json::Value>)> {
+    current_code: Option<&str>,
+) -> Result<(i64, String), AppError> {
     let code = normalize_category_code(raw_category);
 
-    if let Some((id, existing_code)) = sqlx::query_as::<_, (i64, String)>(
-        "SELECT CAST(id AS SIGNED) AS id, code FROM post_categories WHERE code = ?",
+    if let Some((id, existing_code, is_active)) = sqlx::query_as::<_, (i64, String, bool)>(
+        "SELECT CAST(id AS SIGNED) AS id, code, is_active FROM post_categories WHERE code = ?",
     )
     .bind(&code)
     .fetch_optional(pool)
     .await
-    .map_err(internal_error)?
+    .map_err(AppError::internal)?
     {
+        if !is_active && current_code != Some(existing_code.as_str()) {
+            return Err(AppError::bad_request(
+                "This category is no longer available",
+            ));
+        }
         return Ok((id, existing_code));
     }
 
+    if setting::<bool>("POST_CATEGORIES_RESTRICTED").unwrap_or(false) {
+        return Err(AppError::bad_request("Unknown category"));
+    }
+
     let display_name = category_display_name(&code);
     let insert_result =
         sqlx::query("INSERT INTO post_categories (code, display_name) VALUES (?, ?)")
@@ -1886,7 +3698,7 @@ async fn resolve_or_create_category(
     if let Err(error) = insert_result {
         match &error {
             sqlx::Error::Database(db_error) if db_error.code().as_deref() == Some("1062") => {}
-            _ => return Err(internal_error(error)),
+            _ => return Err(AppError::internal(error)),
         }
     }
 
@@ -1895,52 +3707,59 @@ async fn resolve_or_create_category(
             .bind(&code)
             .fetch_one(pool)
             .await
-            .map_err(internal_error)?;
+            .map_err(AppError::internal)?;
 
     Ok((id, existing_code))
 }
 
-async fn clear_all_post_citations(
-    pool: &MySqlPool,
-    post_id: i64,
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+async fn clear_all_post_citations(pool: &MySqlPool, post_id: i64) -> Result<(), AppError> {
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let mut affected_post_ids = fetch_cited_post_ids(&mut *tx, &[post_id])
+        .await
+        .map_err(AppError::internal)?;
+    affected_post_ids.push(post_id);
+
     sqlx::query("DELETE FROM post_citations WHERE citing_post_id = ? OR cited_post_id = ?")
         .bind(post_id)
         .bind(post_id)
-        .execute(pool)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+
+    sqlx::query("DELETE FROM post_external_references WHERE citing_post_id = ?")
+        .bind(post_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+
+    refresh_post_citation_counts(&mut tx, &affected_post_ids)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
 
     Ok(())
 }
 
+/// Paper count and category changes shift the author indices even when no
+/// citation edge changed.
+async fn refresh_author_metrics(pool: &MySqlPool, author_id: i64) -> Result<(), AppError> {
+    let mut conn = pool.acquire().await.map_err(AppError::internal)?;
+    refresh_author_metrics_cache(&mut conn, &[author_id])
+        .await
+        .map_err(AppError::internal)
+}
+
 async fn replace_post_citations(
     pool: &MySqlPool,
     post_id: i64,
+    actor_id: i64,
     citation_ids: &[i64],
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
-    sqlx::query("DELETE FROM post_citations WHERE citing_post_id = ? AND citation_source_id = ?")
-        .bind(post_id)
-        .bind(CITATION_SOURCE_MANUAL)
-        .execute(pool)
-        .await
-        .map_err(internal_error)?;
-
-    for cited_post_id in citation_ids {
-        if *cited_post_id == post_id {
-            continue;
-        }
-        sqlx::query(
-            "INSERT IGNORE INTO post_citations (citing_post_id, cited_post_id, citation_source_id, created_at) VALUES (?, ?, ?, ?)",
-        )
-        .bind(post_id)
-        .bind(cited_post_id)
-        .bind(CITATION_SOURCE_MANUAL)
-        .bind(Utc::now())
-        .execute(pool)
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sync_manual_citations(&mut tx, post_id, Some(actor_id), citation_ids, None)
         .await
-        .map_err(internal_error)?;
-    }
+        .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
 
     Ok(())
 }
@@ -1949,30 +3768,53 @@ async fn replace_post_auto_citations(
     pool: &MySqlPool,
     post_id: i64,
     citation_ids: &[i64],
-) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let mut affected_post_ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT cited_post_id FROM post_citations WHERE citing_post_id = ? AND citation_source_id = ?",
+    )
+    .bind(post_id)
+    .bind(CITATION_SOURCE_AUTO)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
     sqlx::query("DELETE FROM post_citations WHERE citing_post_id = ? AND citation_source_id = ?")
         .bind(post_id)
         .bind(CITATION_SOURCE_AUTO)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
     for cited_post_id in citation_ids {
         if *cited_post_id == post_id {
             continue;
         }
         sqlx::query(
-            "INSERT IGNORE INTO post_citations (citing_post_id, cited_post_id, citation_source_id, created_at) VALUES (?, ?, ?, ?)",
+            r#"
+            INSERT IGNORE INTO post_citations (citing_post_id, cited_post_id, citation_source_id, created_at, retraction_flagged_at)
+            VALUES (?, ?, ?, ?, (SELECT MIN(pr.retracted_at) FROM post_retractions pr WHERE pr.post_id IN (?, ?)))
+            "#,
         )
         .bind(post_id)
         .bind(cited_post_id)
         .bind(CITATION_SOURCE_AUTO)
         .bind(Utc::now())
-        .execute(pool)
+        .bind(post_id)
+        .bind(cited_post_id)
+        .execute(&mut *tx)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+        affected_post_ids.push(*cited_post_id);
     }
 
+    affected_post_ids.sort_unstable();
+    affected_post_ids.dedup();
+    refresh_post_citation_counts(&mut tx, &affected_post_ids)
+        .await
+        .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
     Ok(())
 }
 
@@ -2170,36 +4012,27 @@ async fn sync_post_doi_metadata(
         return Ok(());
     }
 
-    let max_dois = std::env::var("CROSSREF_MAX_DOIS")
-        .ok()
-        .and_then(|value| value.parse::<usize>().ok())
+    let max_dois = setting::<usize>("CROSSREF_MAX_DOIS")
         .filter(|value| *value > 0)
         .unwrap_or(DEFAULT_CROSSREF_MAX_DOIS);
-    let timeout_secs = std::env::var("CROSSREF_TIMEOUT_SECS")
-        .ok()
-        .and_then(|value| value.parse::<u64>().ok())
-        .filter(|value| *value > 0)
-        .unwrap_or(DEFAULT_CROSSREF_TIMEOUT_SECS);
 
     let dois = extract_doi_candidates(title, summary, content, max_dois);
-    if dois.is_empty() {
-        replace_post_doi_metadata(pool, post_id, &records).await?;
-        return Ok(());
-    }
-
-    let client = Client::builder()
-        .timeout(Duration::from_secs(timeout_secs))
-        .user_agent("ThoughtManifold/1.0 (mailto:admin@thought-manifold.local)")
-        .build()?;
-
+    // Crossref is never called while saving; DOIs not cached yet are stored
+    // without metadata and filled in by the refresh job.
+    let mut cached = lookup_cached_metadata(pool, &dois).await?;
     records.reserve(dois.len());
     for doi in dois {
-        match fetch_crossref_metadata_for_doi(&client, &doi).await {
-            Ok(Some(mut record)) => {
-                record.doi = doi;
-                records.push(record);
-            }
-            Ok(None) => records.push(DoiMetadataRecord {
+        let record = match cached.remove(&doi) {
+            Some(entry) => DoiMetadataRecord {
+                doi,
+                title: entry.title,
+                journal: entry.journal,
+                publisher: entry.publisher,
+                published_at: entry.published_at,
+                source_url: entry.source_url,
+                raw_json: entry.raw_json,
+            },
+            None => DoiMetadataRecord {
                 doi,
                 title: None,
                 journal: None,
@@ -2207,20 +4040,9 @@ async fn sync_post_doi_metadata(
                 published_at: None,
                 source_url: None,
                 raw_json: None,
-            }),
-            Err(error) => {
-                tracing::warn!("Crossref lookup failed for DOI {}: {}", doi, error);
-                records.push(DoiMetadataRecord {
-                    doi,
-                    title: None,
-                    journal: None,
-                    publisher: None,
-                    published_at: None,
-                    source_url: None,
-                    raw_json: None,
-                });
-            }
-        }
+            },
+        };
+        records.push(record);
     }
 
     replace_post_doi_metadata(pool, post_id, &records).await?;
@@ -2269,11 +4091,37 @@ async fn replace_post_doi_metadata(
         .execute(&mut *tx)
         .await?;
     }
+    // The registered DOI is not derived from the content, so it is put back
+    // after the rows above are rebuilt.
+    upsert_registered_doi_metadata(&mut tx, post_id).await?;
 
     tx.commit().await?;
     Ok(())
 }
 
+/// Markdown body of an imported draft. The source DOI is written out so the
+/// DOI metadata sync attaches the published record to the draft.
+fn build_imported_content(work: &ImportedWork, source_url: &str) -> String {
+    let mut content = String::new();
+    if let Some(abstract_text) = work.abstract_text.as_deref() {
+        content.push_str(&format!("## Abstract\n\n{}\n\n", abstract_text));
+    }
+    if !work.authors.is_empty() {
+        content.push_str(&format!("**Authors:** {}\n\n", work.authors.join(", ")));
+    }
+    if let Some(published_at) = work.published_at.as_deref() {
+        content.push_str(&format!("**Published:** {}\n\n", published_at));
+    }
+    content.push_str(&format!("**Source:** <{}>\n", source_url));
+    if let Some(doi) = work.doi.as_deref() {
+        content.push_str(&format!("\n**DOI:** {}\n", doi));
+    }
+    if let Some(license_url) = work.license_url.as_deref() {
+        content.push_str(&format!("\n**License:** <{}>\n", license_url));
+    }
+    content
+}
+
 fn collapse_bibtex_whitespace(value: &str) -> String {
     value.split_whitespace().collect::<Vec<_>>().join(" ")
 }
@@ -2343,7 +4191,7 @@ fn extract_bibtex_month(published_at: Option<&str>) -> Option<String> {
         .then_some(normalized)
 }
 
-fn frontend_base_url_for_links() -> String {
+pub fn frontend_base_url_for_links() -> String {
     std::env::var("FRONTEND_URL")
         .ok()
         .map(|value| value.trim().trim_end_matches('/').to_string())
@@ -2351,6 +4199,13 @@ fn frontend_base_url_for_links() -> String {
         .unwrap_or_else(|| "http://localhost:5173".to_string())
 }
 
+fn is_internal_doi(doi: &str) -> bool {
+    doi.split('.')
+        .next()
+        .map(|segment| segment.eq_ignore_ascii_case(INTERNAL_DOI_PREFIX))
+        .unwrap_or(false)
+}
+
 fn resolve_bibtex_link(post_id: i64, doi: &str, source_url: Option<&str>) -> String {
     if let Some(source) = source_url.map(str::trim).filter(|value| !value.is_empty()) {
         if source.starts_with("http://") || source.starts_with("https://") {
@@ -2364,12 +4219,7 @@ fn resolve_bibtex_link(post_id: i64, doi: &str, source_url: Option<&str>) -> Str
         return format!("{}/{}", base, source);
     }
 
-    if doi
-        .split('.')
-        .next()
-        .map(|segment| segment.eq_ignore_ascii_case(INTERNAL_DOI_PREFIX))
-        .unwrap_or(false)
-    {
+    if is_internal_doi(doi) {
         return format!("{}/posts/{}", frontend_base_url_for_links(), post_id);
     }
 
@@ -2391,6 +4241,23 @@ async fn fetch_post_bibtex_author(pool: &MySqlPool, post_id: i64) -> Result<Opti
     .await
 }
 
+fn bibtex_entry_key(post_id: i64, doi: &str) -> String {
+    let key = sanitize_bibtex_key_fragment(doi);
+    if key.is_empty() {
+        format!("tm_post_{}", post_id)
+    } else if key
+        .chars()
+        .next()
+        .map(|ch| ch.is_ascii_digit())
+        .unwrap_or(false)
+    {
+        format!("tm_{}", key)
+    } else {
+        key
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn build_bibtex_from_doi_metadata(
     post_id: i64,
     doi: &str,
@@ -2400,19 +4267,10 @@ fn build_bibtex_from_doi_metadata(
     publisher: Option<&str>,
     published_at: Option<&str>,
     source_url: Option<&str>,
+    issue: Option<&IssueCitation>,
 ) -> String {
     let entry_type = if journal.is_some() { "article" } else { "misc" };
-    let mut key = sanitize_bibtex_key_fragment(doi);
-    if key.is_empty() {
-        key = format!("tm_post_{}", post_id);
-    } else if key
-        .chars()
-        .next()
-        .map(|ch| ch.is_ascii_digit())
-        .unwrap_or(false)
-    {
-        key = format!("tm_{}", key);
-    }
+    let key = bibtex_entry_key(post_id, doi);
 
     let mut fields: Vec<(&str, String)> = Vec::new();
     let resolved_title = title
@@ -2428,6 +4286,10 @@ fn build_bibtex_from_doi_metadata(
     if let Some(value) = journal.map(str::trim).filter(|value| !value.is_empty()) {
         fields.push(("journal", value.to_string()));
     }
+    if let Some(value) = issue {
+        fields.push(("volume", value.volume.to_string()));
+        fields.push(("number", value.number.to_string()));
+    }
     if let Some(value) = publisher.map(str::trim).filter(|value| !value.is_empty()) {
         fields.push(("publisher", value.to_string()));
     }
@@ -2462,15 +4324,24 @@ async fn fetch_post_doi_metadata(
     post_id: i64,
 ) -> Result<Vec<PostDoiMetadata>, sqlx::Error> {
     let bibtex_author = fetch_post_bibtex_author(pool, post_id).await?;
+    let issue = sqlx::query_as::<_, IssueCitation>(
+        r#"
+        SELECT i.id AS issue_id, CAST(i.volume AS SIGNED) AS volume, CAST(i.number AS SIGNED) AS number
+        FROM posts p
+        JOIN issues i ON i.id = p.issue_id
+        WHERE p.id = ?
+        "#,
+    )
+    .bind(post_id)
+    .fetch_optional(pool)
+    .await?;
+    let registered_doi: Option<String> =
+        sqlx::query_scalar("SELECT registered_doi FROM post_doi_registrations WHERE post_id = ?")
+            .bind(post_id)
+            .fetch_optional(pool)
+            .await?;
 
-    let rows: Vec<(
-        String,
-        Option<String>,
-        Option<String>,
-        Option<String>,
-        Option<String>,
-        Option<String>,
-    )> = sqlx::query_as(
+    let rows: Vec<DoiMetadataRow> = sqlx::query_as(
         r#"
         SELECT doi, title, journal, publisher, published_at, source_url
         FROM post_doi_metadata
@@ -2482,29 +4353,205 @@ async fn fetch_post_doi_metadata(
     .fetch_all(pool)
     .await?;
 
-    Ok(rows
+    Ok(map_doi_metadata_rows(
+        post_id,
+        rows,
+        registered_doi.as_deref(),
+        issue.as_ref(),
+        bibtex_author.as_deref(),
+    ))
+}
+
+/// `fetch_post_doi_metadata` for many posts at once, taking the BibTeX
+/// author from the already loaded authors.
+async fn fetch_doi_metadata_map(
+    pool: &MySqlPool,
+    posts: &[Post],
+    author_map: &HashMap<i64, UserResponse>,
+) -> Result<HashMap<i64, Vec<PostDoiMetadata>>, sqlx::Error> {
+    if posts.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut issues_qb = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT p.id, i.id, CAST(i.volume AS SIGNED), CAST(i.number AS SIGNED)
+        FROM posts p
+        JOIN issues i ON i.id = p.issue_id
+        WHERE p.id IN ("#,
+    );
+    let mut registrations_qb = QueryBuilder::<MySql>::new(
+        "SELECT post_id, registered_doi FROM post_doi_registrations WHERE post_id IN (",
+    );
+    let mut metadata_qb = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT post_id, doi, title, journal, publisher, published_at, source_url
+        FROM post_doi_metadata
+        WHERE post_id IN ("#,
+    );
+    for query_builder in [&mut issues_qb, &mut registrations_qb, &mut metadata_qb] {
+        let mut separated = query_builder.separated(", ");
+        for post in posts {
+            separated.push_bind(post.id);
+        }
+        query_builder.push(")");
+    }
+    metadata_qb.push(" ORDER BY post_id, created_at DESC, id DESC");
+
+    let issues: HashMap<i64, IssueCitation> = issues_qb
+        .build_query_as::<(i64, i64, i32, i32)>()
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(post_id, issue_id, volume, number)| {
+            (
+                post_id,
+                IssueCitation {
+                    issue_id,
+                    volume,
+                    number,
+                },
+            )
+        })
+        .collect();
+    let registered_dois: HashMap<i64, String> = registrations_qb
+        .build_query_as::<(i64, String)>()
+        .fetch_all(pool)
+        .await?
         .into_iter()
+        .collect();
+
+    let mut rows_by_post = HashMap::<i64, Vec<DoiMetadataRow>>::new();
+    let rows: Vec<PostDoiMetadataRow> = metadata_qb.build_query_as().fetch_all(pool).await?;
+    for (post_id, doi, title, journal, publisher, published_at, source_url) in rows {
+        rows_by_post.entry(post_id).or_default().push((
+            doi,
+            title,
+            journal,
+            publisher,
+            published_at,
+            source_url,
+        ));
+    }
+
+    Ok(posts
+        .iter()
+        .map(|post| {
+            let rows = rows_by_post.remove(&post.id).unwrap_or_default();
+            let bibtex_author = author_map.get(&post.author_id).map(|author| {
+                author
+                    .display_name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or(&author.username)
+            });
+            let metadata = map_doi_metadata_rows(
+                post.id,
+                rows,
+                registered_dois.get(&post.id).map(String::as_str),
+                issues.get(&post.id),
+                bibtex_author,
+            );
+            (post.id, metadata)
+        })
+        .collect())
+}
+
+fn map_doi_metadata_rows(
+    post_id: i64,
+    rows: Vec<DoiMetadataRow>,
+    registered_doi: Option<&str>,
+    issue: Option<&IssueCitation>,
+    bibtex_author: Option<&str>,
+) -> Vec<PostDoiMetadata> {
+    rows.into_iter()
         .map(
-            |(doi, title, journal, publisher, published_at, source_url)| PostDoiMetadata {
-                bibtex: build_bibtex_from_doi_metadata(
-                    post_id,
-                    &doi,
-                    title.as_deref(),
-                    bibtex_author.as_deref(),
-                    journal.as_deref(),
-                    publisher.as_deref(),
-                    published_at.as_deref(),
-                    source_url.as_deref(),
-                ),
-                doi,
-                title,
-                journal,
-                publisher,
-                published_at,
-                source_url,
+            |(doi, title, journal, publisher, published_at, source_url)| {
+                // Cited DOIs found in the content belong to other journals.
+                let is_own_doi = is_internal_doi(&doi)
+                    || registered_doi
+                        .is_some_and(|registered| registered.eq_ignore_ascii_case(&doi));
+                let issue = issue.cloned().filter(|_| is_own_doi);
+                PostDoiMetadata {
+                    bibtex: build_bibtex_from_doi_metadata(
+                        post_id,
+                        &doi,
+                        title.as_deref(),
+                        bibtex_author,
+                        journal.as_deref(),
+                        publisher.as_deref(),
+                        published_at.as_deref(),
+                        source_url.as_deref(),
+                        issue.as_ref(),
+                    ),
+                    doi,
+                    title,
+                    journal,
+                    publisher,
+                    published_at,
+                    source_url,
+                    issue,
+                }
             },
         )
-        .collect())
+        .collect()
+}
+
+/// Concatenates the BibTeX entry of each post's own DOI, preferring the
+/// registered DOI over the internal one. Keys come from the DOI so they stay
+/// the same between exports; a clash is broken with the post ID.
+pub async fn export_posts_bibtex(
+    pool: &MySqlPool,
+    post_ids: &[i64],
+) -> Result<String, sqlx::Error> {
+    let mut used_keys = HashSet::new();
+    let mut entries = Vec::with_capacity(post_ids.len());
+    for post_id in post_ids {
+        if let Err(error) = ensure_internal_doi_metadata(pool, *post_id).await {
+            tracing::warn!(
+                "Failed to ensure internal DOI for post {}: {}",
+                post_id,
+                error
+            );
+        }
+
+        let registered_doi: Option<String> = sqlx::query_scalar(
+            "SELECT registered_doi FROM post_doi_registrations WHERE post_id = ?",
+        )
+        .bind(post_id)
+        .fetch_optional(pool)
+        .await?;
+        let metadata = fetch_post_doi_metadata(pool, *post_id).await?;
+        let Some(own) = metadata
+            .iter()
+            .find(|record| {
+                registered_doi
+                    .as_deref()
+                    .is_some_and(|registered| registered.eq_ignore_ascii_case(&record.doi))
+            })
+            .or_else(|| metadata.iter().find(|record| is_internal_doi(&record.doi)))
+        else {
+            continue;
+        };
+
+        let key = bibtex_entry_key(*post_id, &own.doi);
+        let bibtex = if used_keys.insert(key.clone()) {
+            own.bibtex.clone()
+        } else {
+            let unique_key = format!("{}_{}", key, post_id);
+            used_keys.insert(unique_key.clone());
+            own.bibtex
+                .replacen(&format!("{{{},", key), &format!("{{{},", unique_key), 1)
+        };
+        entries.push(bibtex);
+    }
+
+    let mut bibtex = entries.join("\n\n");
+    if !bibtex.is_empty() {
+        bibtex.push('\n');
+    }
+    Ok(bibtex)
 }
 
 fn extract_doi_candidates(
@@ -2569,99 +4616,12 @@ fn normalize_doi(raw: &str) -> Option<String> {
     Some(trimmed.to_ascii_lowercase())
 }
 
-async fn fetch_crossref_metadata_for_doi(
-    client: &Client,
-    doi: &str,
-) -> anyhow::Result<Option<DoiMetadataRecord>> {
-    let url = format!("{}{}", CROSSREF_API_BASE, urlencoding::encode(doi));
-    let response = client.get(url).send().await?;
-
-    if !response.status().is_success() {
-        return Ok(None);
-    }
-
-    let payload = response.json::<serde_json::Value>().await?;
-    let message = payload
-        .get("message")
-        .and_then(|value| value.as_object())
-        .cloned()
-        .unwrap_or_default();
-    let message_value = serde_json::Value::Object(message);
-
-    Ok(Some(DoiMetadataRecord {
-        doi: doi.to_string(),
-        title: extract_crossref_title(&message_value),
-        journal: extract_crossref_first_array_text(&message_value, "container-title"),
-        publisher: extract_crossref_text(&message_value, "publisher"),
-        published_at: extract_crossref_published_at(&message_value),
-        source_url: extract_crossref_text(&message_value, "URL")
-            .or_else(|| Some(format!("https://doi.org/{}", doi))),
-        raw_json: Some(payload.to_string()),
-    }))
-}
-
-fn extract_crossref_text(value: &serde_json::Value, key: &str) -> Option<String> {
-    value
-        .get(key)
-        .and_then(|item| item.as_str())
-        .map(str::trim)
-        .filter(|item| !item.is_empty())
-        .map(ToOwned::to_owned)
-}
-
-fn extract_crossref_first_array_text(value: &serde_json::Value, key: &str) -> Option<String> {
-    value
-        .get(key)
-        .and_then(|item| item.as_array())
-        .and_then(|items| items.iter().find_map(|entry| entry.as_str()))
-        .map(str::trim)
-        .filter(|item| !item.is_empty())
-        .map(ToOwned::to_owned)
-}
-
-fn extract_crossref_title(value: &serde_json::Value) -> Option<String> {
-    extract_crossref_first_array_text(value, "title").or_else(|| extract_crossref_text(value, "title"))
-}
-
-fn extract_crossref_published_at(value: &serde_json::Value) -> Option<String> {
-    for key in ["published-print", "published-online", "issued"] {
-        let Some(date_parts) = value
-            .get(key)
-            .and_then(|entry| entry.get("date-parts"))
-            .and_then(|entry| entry.as_array())
-            .and_then(|outer| outer.first())
-            .and_then(|entry| entry.as_array())
-        else {
-            continue;
-        };
-
-        let year = date_parts.first().and_then(|value| value.as_i64());
-        let month = date_parts.get(1).and_then(|value| value.as_i64());
-        let day = date_parts.get(2).and_then(|value| value.as_i64());
-
-        if let Some(year_value) = year {
-            if let (Some(month_value), Some(day_value)) = (month, day) {
-                return Some(format!(
-                    "{:04}-{:02}-{:02}",
-                    year_value, month_value, day_value
-                ));
-            }
-            if let Some(month_value) = month {
-                return Some(format!("{:04}-{:02}", year_value, month_value));
-            }
-            return Some(format!("{:04}", year_value));
-        }
-    }
-
-    None
-}
-
 async fn create_paper_version_snapshot(
     pool: &MySqlPool,
     post_id: i64,
     submitted_by: i64,
-) -> Result<(i64, i32), (StatusCode, Json<serde_json::Value>)> {
-    let mut tx = pool.begin().await.map_err(internal_error)?;
+) -> Result<(i64, i32), AppError> {
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
 
     let (next_version,): (i32,) = sqlx::query_as(
         "SELECT CAST(COALESCE(MAX(version_number), 0) + 1 AS SIGNED) FROM paper_versions WHERE post_id = ?",
@@ -2669,7 +4629,7 @@ async fn create_paper_version_snapshot(
     .bind(post_id)
     .fetch_one(&mut *tx)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     let source = sqlx::query_as::<
         _,
@@ -2698,7 +4658,25 @@ async fn create_paper_version_snapshot(
     .bind(post_id)
     .fetch_one(&mut *tx)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
+
+    // The version keeps its own copy, so replacing the working file later
+    // cannot change what reviewers of this version saw.
+    let stored_file = match source.4.as_deref() {
+        Some(path) => match store_version_file(path).await {
+            Ok(stored) => Some(stored),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!(
+                    "Attachment {} of post {} is missing; recording the version without it",
+                    path,
+                    post_id
+                );
+                None
+            }
+            Err(error) => return Err(AppError::internal(error)),
+        },
+        None => None,
+    };
 
     let tags: Vec<String> = sqlx::query_as::<_, (String,)>(
         r#"
@@ -2712,7 +4690,7 @@ async fn create_paper_version_snapshot(
     .bind(post_id)
     .fetch_all(&mut *tx)
     .await
-    .map_err(internal_error)?
+    .map_err(AppError::internal)?
     .into_iter()
     .map(|(name,)| name)
     .collect();
@@ -2722,7 +4700,7 @@ async fn create_paper_version_snapshot(
             .bind(post_id)
             .fetch_all(&mut *tx)
             .await
-            .map_err(internal_error)?
+            .map_err(AppError::internal)?
             .into_iter()
             .map(|(id,)| id)
             .collect();
@@ -2731,12 +4709,12 @@ async fn create_paper_version_snapshot(
     let tags_json = if tags.is_empty() {
         None
     } else {
-        Some(serde_json::to_string(&tags).map_err(internal_error)?)
+        Some(serde_json::to_string(&tags).map_err(AppError::internal)?)
     };
     let citations_json = if citations.is_empty() {
         None
     } else {
-        Some(serde_json::to_string(&citations).map_err(internal_error)?)
+        Some(serde_json::to_string(&citations).map_err(AppError::internal)?)
     };
 
     let result = sqlx::query(
@@ -2750,12 +4728,13 @@ async fn create_paper_version_snapshot(
             github_url,
             file_path,
             file_name,
+            file_sha256,
             tags_json,
             citations_json,
             submitted_by,
             submitted_at,
             created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(post_id)
@@ -2764,8 +4743,9 @@ async fn create_paper_version_snapshot(
     .bind(&source.1)
     .bind(&source.2)
     .bind(&source.3)
-    .bind(&source.4)
-    .bind(&source.5)
+    .bind(stored_file.as_ref().map(|stored| &stored.path))
+    .bind(stored_file.as_ref().and(source.5.as_ref()))
+    .bind(stored_file.as_ref().map(|stored| &stored.sha256))
     .bind(&tags_json)
     .bind(&citations_json)
     .bind(submitted_by)
@@ -2773,7 +4753,7 @@ async fn create_paper_version_snapshot(
     .bind(now)
     .execute(&mut *tx)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
     let version_id = result.last_insert_id() as i64;
 
@@ -2786,22 +4766,8 @@ async fn create_paper_version_snapshot(
     .bind(post_id)
     .execute(&mut *tx)
     .await
-    .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
 
-    tx.commit().await.map_err(internal_error)?;
+    tx.commit().await.map_err(AppError::internal)?;
     Ok((version_id, next_version))
 }
-
-fn internal_error<E: ToString>(error: E) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({"detail": error.to_string()})),
-    )
-}
-
-fn multipart_error(error: MultipartError) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        error.status(),
-        Json(serde_json::json!({"detail": error.body_text()})),
-    )
-}
@@ -0,0 +1,294 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use crate::ai_review::{REVIEW_BACKFILL_SELECT, find_backfill_candidates};
+use crate::audit_log::{
+    AUDIT_ACTION_REVIEW_BACKFILL_CANCEL, AUDIT_ACTION_REVIEW_BACKFILL_START,
+    AUDIT_TARGET_REVIEW_BACKFILL, AuditEvent, RequestMetadata, record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    BACKFILL_ITEM_STATUS_CANCELLED, BACKFILL_ITEM_STATUS_QUEUED, BACKFILL_STATUS_CANCELLED,
+    BACKFILL_STATUS_RUNNING, CreateReviewBackfill, ReviewBackfill, ReviewBackfillListResponse,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::posts::validate_paper_status_filter;
+
+const DEFAULT_BACKFILL_BATCH_SIZE: i32 = 5;
+const MAX_BACKFILL_BATCH_SIZE: i32 = 50;
+const MAX_PROMPT_VERSION_CHARS: usize = 32;
+/// Rows per multi-row insert of backfill items.
+const ITEM_INSERT_CHUNK: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewBackfillQuery {
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+/// Admin backfill routes, nested under `/api/admin`.
+pub fn review_backfill_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route(
+            "/reviews/backfills",
+            get(list_review_backfills).post(create_review_backfill),
+        )
+        .route("/reviews/backfills/{backfill_id}", get(get_review_backfill))
+        .route(
+            "/reviews/backfills/{backfill_id}/cancel",
+            post(cancel_review_backfill),
+        )
+}
+
+/// Queues an AI re-review of every matching paper. The backfill job
+/// schedules them in batches; only one backfill runs at a time.
+async fn create_review_backfill(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<CreateReviewBackfill>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let prompt_version = input
+        .prompt_version
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    if prompt_version.is_some_and(|value| value.chars().count() > MAX_PROMPT_VERSION_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "prompt_version must be at most {} characters",
+            MAX_PROMPT_VERSION_CHARS
+        )));
+    }
+    let paper_status = input
+        .paper_status
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(validate_paper_status_filter)
+        .transpose()?;
+    let batch_size = input.batch_size.unwrap_or(DEFAULT_BACKFILL_BATCH_SIZE);
+    if !(1..=MAX_BACKFILL_BATCH_SIZE).contains(&batch_size) {
+        return Err(AppError::bad_request(format!(
+            "batch_size must be between 1 and {}",
+            MAX_BACKFILL_BATCH_SIZE
+        )));
+    }
+
+    let post_ids = find_backfill_candidates(&pool, prompt_version, paper_status.as_deref())
+        .await
+        .map_err(AppError::internal)?;
+    if input.dry_run {
+        return Ok((
+            StatusCode::OK,
+            Json(serde_json::json!({"matched": post_ids.len()})),
+        ));
+    }
+    if post_ids.is_empty() {
+        return Err(AppError::bad_request("No papers match these filters"));
+    }
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let running: Option<i64> = sqlx::query_scalar(
+        "SELECT id FROM ai_review_backfills WHERE status = ? LIMIT 1 FOR UPDATE",
+    )
+    .bind(BACKFILL_STATUS_RUNNING)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    if let Some(running_id) = running {
+        return Err(AppError::conflict("Another backfill is still running")
+            .with_field("backfill_id", running_id));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO ai_review_backfills
+            (status, prompt_version, paper_status, batch_size, total_items, created_by, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(BACKFILL_STATUS_RUNNING)
+    .bind(prompt_version)
+    .bind(paper_status.as_deref())
+    .bind(batch_size)
+    .bind(post_ids.len() as i64)
+    .bind(admin.id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    let backfill_id = result.last_insert_id() as i64;
+
+    for chunk in post_ids.chunks(ITEM_INSERT_CHUNK) {
+        let mut query_builder = QueryBuilder::<MySql>::new(
+            "INSERT INTO ai_review_backfill_items (backfill_id, post_id, status) ",
+        );
+        query_builder.push_values(chunk, |mut row, post_id| {
+            row.push_bind(backfill_id)
+                .push_bind(*post_id)
+                .push_bind(BACKFILL_ITEM_STATUS_QUEUED);
+        });
+        query_builder
+            .build()
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+    }
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_REVIEW_BACKFILL_START,
+            target_type: AUDIT_TARGET_REVIEW_BACKFILL,
+            target_id: backfill_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: None,
+            after: Some(serde_json::json!({
+                "prompt_version": prompt_version,
+                "paper_status": paper_status,
+                "batch_size": batch_size,
+                "total_items": post_ids.len()
+            })),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let backfill = fetch_review_backfill(&pool, backfill_id).await?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!(backfill))))
+}
+
+async fn list_review_backfills(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<ReviewBackfillQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+
+    let backfills = sqlx::query_as::<_, ReviewBackfill>(&format!(
+        "{} ORDER BY b.created_at DESC, b.id DESC LIMIT ? OFFSET ?",
+        REVIEW_BACKFILL_SELECT
+    ))
+    .bind(i64::from(per_page))
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ai_review_backfills")
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(ReviewBackfillListResponse {
+        backfills,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+async fn get_review_backfill(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(backfill_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+    let backfill = fetch_review_backfill(&pool, backfill_id).await?;
+    Ok(Json(backfill))
+}
+
+/// Stops a running backfill. Papers not yet scheduled are dropped; reviews
+/// already scheduled still run to completion.
+async fn cancel_review_backfill(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(backfill_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+    let before = fetch_review_backfill(&pool, backfill_id).await?;
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let updated = sqlx::query(
+        r#"
+        UPDATE ai_review_backfills
+        SET status = ?, cancelled_by = ?, finished_at = ?
+        WHERE id = ? AND status = ?
+        "#,
+    )
+    .bind(BACKFILL_STATUS_CANCELLED)
+    .bind(admin.id)
+    .bind(now)
+    .bind(backfill_id)
+    .bind(BACKFILL_STATUS_RUNNING)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    if updated.rows_affected() == 0 {
+        return Err(
+            AppError::conflict("Only running backfills can be cancelled")
+                .with_field("status", before.status),
+        );
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE ai_review_backfill_items
+        SET status = ?, processed_at = ?
+        WHERE backfill_id = ? AND status = ?
+        "#,
+    )
+    .bind(BACKFILL_ITEM_STATUS_CANCELLED)
+    .bind(now)
+    .bind(backfill_id)
+    .bind(BACKFILL_ITEM_STATUS_QUEUED)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action: AUDIT_ACTION_REVIEW_BACKFILL_CANCEL,
+            target_type: AUDIT_TARGET_REVIEW_BACKFILL,
+            target_id: backfill_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!(before)),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let backfill = fetch_review_backfill(&pool, backfill_id).await?;
+    Ok(Json(backfill))
+}
+
+async fn fetch_review_backfill(
+    pool: &MySqlPool,
+    backfill_id: i64,
+) -> Result<ReviewBackfill, AppError> {
+    sqlx::query_as::<_, ReviewBackfill>(&format!("{} WHERE b.id = ?", REVIEW_BACKFILL_SELECT))
+        .bind(backfill_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Backfill not found"))
+}
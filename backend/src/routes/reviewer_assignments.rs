@@ -0,0 +1,883 @@
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, patch, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use crate::audit_log::{
+    AUDIT_ACTION_CONFLICT_OVERRIDE, AUDIT_TARGET_REVIEWER_ASSIGNMENT, AuditEvent, RequestMetadata,
+    record_audit_event,
+};
+use crate::error::AppError;
+use crate::models::{
+    AssignedPaperItem, AssignedPaperListResponse, CreateReviewerAssignment,
+    DeclareReviewerConflicts, OverrideReviewerConflict, PAPER_STATUS_PUBLISHED,
+    REVIEWER_ASSIGNMENT_ACCEPTED, REVIEWER_ASSIGNMENT_DECLINED, REVIEWER_ASSIGNMENT_INVITED,
+    REVIEWER_ASSIGNMENT_SUBMITTED, ReviewerAssignmentListResponse, ReviewerAssignmentResponse,
+    ReviewerConflictDeclaration, UpdateReviewerAssignment, is_assignment_overdue,
+};
+use crate::notifications::{
+    NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE, NOTIFICATION_REVIEW_INVITATION, NewNotification,
+    dispatch_notifications,
+};
+use crate::routes::admin::extract_admin_user;
+use crate::routes::auth::extract_current_user;
+
+const ASSIGNMENT_SELECT: &str = r#"
+    SELECT
+        ra.id AS id,
+        ra.post_id AS post_id,
+        ra.reviewer_id AS reviewer_id,
+        u.username AS reviewer_username,
+        u.display_name AS reviewer_display_name,
+        ra.assigned_by AS assigned_by,
+        ra.status AS status,
+        ra.due_at AS due_at,
+        ra.responded_at AS responded_at,
+        ra.submitted_at AS submitted_at,
+        ra.created_at AS created_at,
+        ra.updated_at AS updated_at
+    FROM reviewer_assignments ra
+    JOIN users u ON u.id = ra.reviewer_id
+"#;
+
+const CONFLICT_DECLARATION_SELECT: &str = r#"
+    SELECT
+        rcd.assignment_id AS assignment_id,
+        rcd.has_coauthorship AS has_coauthorship,
+        rcd.has_same_institution AS has_same_institution,
+        rcd.other_conflict AS other_conflict,
+        rcd.declared_at AS declared_at,
+        rcd.overridden_by AS overridden_by,
+        rcd.override_reason AS override_reason,
+        rcd.overridden_at AS overridden_at
+    FROM reviewer_conflict_declarations rcd
+"#;
+
+// Repeats `ReviewerConflictDeclaration::blocks_acceptance` inside the accept
+// UPDATE, so a declaration changed mid-request cannot slip through.
+const CONFLICTS_CLEARED_CONDITION: &str = r#"
+    AND EXISTS (
+        SELECT 1
+        FROM reviewer_conflict_declarations rcd
+        WHERE rcd.assignment_id = reviewer_assignments.id
+          AND (
+            rcd.overridden_at IS NOT NULL
+            OR (
+              rcd.has_coauthorship = FALSE
+              AND rcd.has_same_institution = FALSE
+              AND rcd.other_conflict IS NULL
+            )
+          )
+    )
+"#;
+
+const MAX_CONFLICT_TEXT_CHARS: usize = 2_000;
+
+#[derive(Debug, Deserialize)]
+struct AssignedPaperQuery {
+    status: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AssignmentResponse {
+    Accept,
+    Decline,
+    Submit,
+}
+
+impl AssignmentResponse {
+    fn source_statuses(self) -> &'static [&'static str] {
+        match self {
+            Self::Accept => &[REVIEWER_ASSIGNMENT_INVITED],
+            Self::Decline => &[REVIEWER_ASSIGNMENT_INVITED, REVIEWER_ASSIGNMENT_ACCEPTED],
+            Self::Submit => &[REVIEWER_ASSIGNMENT_ACCEPTED],
+        }
+    }
+
+    fn target_status(self) -> &'static str {
+        match self {
+            Self::Accept => REVIEWER_ASSIGNMENT_ACCEPTED,
+            Self::Decline => REVIEWER_ASSIGNMENT_DECLINED,
+            Self::Submit => REVIEWER_ASSIGNMENT_SUBMITTED,
+        }
+    }
+}
+
+/// Editor-facing endpoints, nested under `/api/posts`.
+pub fn reviewer_assignment_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route(
+            "/{post_id}/reviewer-assignments",
+            get(list_post_assignments).post(create_assignment),
+        )
+        .route(
+            "/{post_id}/reviewer-assignments/{assignment_id}",
+            patch(update_assignment).delete(cancel_assignment),
+        )
+        .route(
+            "/{post_id}/reviewer-assignments/{assignment_id}/conflict-override",
+            post(override_conflict),
+        )
+}
+
+/// Reviewer-facing endpoints, nested under `/api/reviews` next to the
+/// review center.
+pub fn assigned_review_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/assigned", get(list_assigned_papers))
+        .route(
+            "/assignments/{assignment_id}/conflicts",
+            post(declare_conflicts),
+        )
+        .route(
+            "/assignments/{assignment_id}/accept",
+            post(accept_assignment),
+        )
+        .route(
+            "/assignments/{assignment_id}/decline",
+            post(decline_assignment),
+        )
+        .route(
+            "/assignments/{assignment_id}/submit",
+            post(submit_assignment),
+        )
+}
+
+/// Status of `user_id`'s assignment on `post_id`, if they were ever invited.
+pub async fn fetch_reviewer_assignment_status(
+    pool: &MySqlPool,
+    post_id: i64,
+    user_id: i64,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT status FROM reviewer_assignments WHERE post_id = ? AND reviewer_id = ?",
+    )
+    .bind(post_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+async fn list_post_assignments(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let _ = extract_admin_user(&pool, &headers).await?;
+    let _ = fetch_paper(&pool, post_id).await?;
+
+    let mut assignments = sqlx::query_as::<_, ReviewerAssignmentResponse>(&format!(
+        "{} WHERE ra.post_id = ? ORDER BY ra.created_at ASC, ra.id ASC",
+        ASSIGNMENT_SELECT
+    ))
+    .bind(post_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let mut declarations: HashMap<i64, ReviewerConflictDeclaration> =
+        sqlx::query_as::<_, ReviewerConflictDeclaration>(&format!(
+            "{} JOIN reviewer_assignments ra ON ra.id = rcd.assignment_id WHERE ra.post_id = ?",
+            CONFLICT_DECLARATION_SELECT
+        ))
+        .bind(post_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?
+        .into_iter()
+        .map(|declaration| (declaration.assignment_id, with_conflict_flag(declaration)))
+        .collect();
+    let now = Utc::now();
+    for assignment in &mut assignments {
+        assignment.is_overdue = is_assignment_overdue(&assignment.status, assignment.due_at, now);
+        assignment.conflict_declaration = declarations.remove(&assignment.id);
+    }
+
+    let total = assignments.len() as i64;
+    Ok(Json(ReviewerAssignmentListResponse { assignments, total }))
+}
+
+async fn create_assignment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(post_id): Path<i64>,
+    Json(input): Json<CreateReviewerAssignment>,
+) -> Result<impl IntoResponse, AppError> {
+    let editor = extract_admin_user(&pool, &headers).await?;
+    let (author_id, title, paper_status) = fetch_paper(&pool, post_id).await?;
+    // Public preprints are still under review, so only the paper status
+    // decides whether reviewers can be added.
+    if paper_status == PAPER_STATUS_PUBLISHED {
+        return Err(AppError::conflict(
+            "Reviewers cannot be assigned to a published paper",
+        ));
+    }
+    if input.reviewer_id == author_id {
+        return Err(AppError::bad_request(
+            "The paper author cannot review their own paper",
+        ));
+    }
+    let now = Utc::now();
+    if let Some(due_at) = input.due_at
+        && due_at <= now
+    {
+        return Err(AppError::bad_request("Due date must be in the future"));
+    }
+
+    let reviewer_exists = sqlx::query("SELECT id FROM users WHERE id = ? AND deleted_at IS NULL")
+        .bind(input.reviewer_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    if reviewer_exists.is_none() {
+        return Err(AppError::not_found("Reviewer not found"));
+    }
+
+    // A reviewer who declined earlier can be invited again; any other
+    // existing assignment is left untouched.
+    let existing_status = fetch_reviewer_assignment_status(&pool, post_id, input.reviewer_id)
+        .await
+        .map_err(AppError::internal)?;
+    match existing_status.as_deref() {
+        None => {
+            sqlx::query(
+                r#"
+                INSERT INTO reviewer_assignments
+                    (post_id, reviewer_id, assigned_by, status, due_at, created_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(post_id)
+            .bind(input.reviewer_id)
+            .bind(editor.id)
+            .bind(REVIEWER_ASSIGNMENT_INVITED)
+            .bind(input.due_at)
+            .bind(now)
+            .execute(&pool)
+            .await
+            .map_err(|error| match error {
+                sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                    AppError::conflict("Reviewer is already assigned to this paper")
+                }
+                other => AppError::internal(other),
+            })?;
+        }
+        Some(REVIEWER_ASSIGNMENT_DECLINED) => {
+            sqlx::query(
+                r#"
+                UPDATE reviewer_assignments
+                SET
+                    assigned_by = ?,
+                    status = ?,
+                    due_at = ?,
+                    responded_at = NULL,
+                    submitted_at = NULL,
+                    reminder_sent_at = NULL,
+                    overdue_notified_at = NULL,
+                    created_at = ?,
+                    updated_at = ?
+                WHERE post_id = ? AND reviewer_id = ? AND status = ?
+                "#,
+            )
+            .bind(editor.id)
+            .bind(REVIEWER_ASSIGNMENT_INVITED)
+            .bind(input.due_at)
+            .bind(now)
+            .bind(now)
+            .bind(post_id)
+            .bind(input.reviewer_id)
+            .bind(REVIEWER_ASSIGNMENT_DECLINED)
+            .execute(&pool)
+            .await
+            .map_err(AppError::internal)?;
+            // A new invitation needs a fresh declaration.
+            sqlx::query(
+                r#"
+                DELETE rcd
+                FROM reviewer_conflict_declarations rcd
+                JOIN reviewer_assignments ra ON ra.id = rcd.assignment_id
+                WHERE ra.post_id = ? AND ra.reviewer_id = ?
+                "#,
+            )
+            .bind(post_id)
+            .bind(input.reviewer_id)
+            .execute(&pool)
+            .await
+            .map_err(AppError::internal)?;
+        }
+        Some(_) => {
+            return Err(AppError::conflict(
+                "Reviewer is already assigned to this paper",
+            ));
+        }
+    }
+
+    let assignment_id: i64 = sqlx::query_scalar(
+        "SELECT id FROM reviewer_assignments WHERE post_id = ? AND reviewer_id = ?",
+    )
+    .bind(post_id)
+    .bind(input.reviewer_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let assignment = fetch_assignment(&pool, assignment_id).await?;
+
+    let message = match assignment.due_at {
+        Some(due_at) => format!(
+            "{} invited you to review \"{}\" (due {})",
+            editor.username,
+            title,
+            due_at.format("%Y-%m-%d")
+        ),
+        None => format!("{} invited you to review \"{}\"", editor.username, title),
+    };
+    dispatch_notifications(
+        &pool,
+        vec![NewNotification {
+            user_id: assignment.reviewer_id,
+            actor_id: Some(editor.id),
+            event_type: NOTIFICATION_REVIEW_INVITATION,
+            post_id: Some(post_id),
+            comment_id: None,
+            message,
+        }],
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(assignment)))
+}
+
+/// Moves or clears the due date. Reminder state is reset so the reviewer is
+/// reminded again about the new deadline.
+async fn update_assignment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, assignment_id)): Path<(i64, i64)>,
+    Json(input): Json<UpdateReviewerAssignment>,
+) -> Result<impl IntoResponse, AppError> {
+    let _ = extract_admin_user(&pool, &headers).await?;
+    let now = Utc::now();
+    if let Some(due_at) = input.due_at
+        && due_at <= now
+    {
+        return Err(AppError::bad_request("Due date must be in the future"));
+    }
+
+    let result = sqlx::query(
+        r#"
+        UPDATE reviewer_assignments
+        SET due_at = ?, reminder_sent_at = NULL, overdue_notified_at = NULL, updated_at = ?
+        WHERE id = ? AND post_id = ?
+        "#,
+    )
+    .bind(input.due_at)
+    .bind(now)
+    .bind(assignment_id)
+    .bind(post_id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("Reviewer assignment not found"));
+    }
+
+    Ok(Json(fetch_assignment(&pool, assignment_id).await?))
+}
+
+async fn cancel_assignment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, assignment_id)): Path<(i64, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    let _ = extract_admin_user(&pool, &headers).await?;
+
+    let result = sqlx::query("DELETE FROM reviewer_assignments WHERE id = ? AND post_id = ?")
+        .bind(assignment_id)
+        .bind(post_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("Reviewer assignment not found"));
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "Reviewer assignment cancelled"
+    })))
+}
+
+async fn list_assigned_papers(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<AssignedPaperQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let status = query
+        .status
+        .as_deref()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty());
+    if let Some(status) = status
+        && ![
+            REVIEWER_ASSIGNMENT_INVITED,
+            REVIEWER_ASSIGNMENT_ACCEPTED,
+            REVIEWER_ASSIGNMENT_DECLINED,
+            REVIEWER_ASSIGNMENT_SUBMITTED,
+        ]
+        .contains(&status)
+    {
+        return Err(AppError::bad_request("Invalid assignment status"));
+    }
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+
+    let mut query_builder = QueryBuilder::<MySql>::new(
+        r#"
+        SELECT
+            ra.id AS assignment_id,
+            p.id AS post_id,
+            p.title AS title,
+            p.paper_status AS paper_status,
+            CAST(p.current_revision AS SIGNED) AS current_revision,
+            p.is_published AS is_published,
+            p.is_double_blind AS is_double_blind,
+            ra.status AS status,
+            ra.due_at AS due_at,
+            ra.created_at AS assigned_at,
+            ra.responded_at AS responded_at,
+            ra.submitted_at AS submitted_at,
+            rcd.declared_at AS conflicts_declared_at
+        FROM reviewer_assignments ra
+        JOIN posts p ON p.id = ra.post_id
+        LEFT JOIN reviewer_conflict_declarations rcd ON rcd.assignment_id = ra.id
+        WHERE p.deleted_at IS NULL AND ra.reviewer_id = "#,
+    );
+    query_builder.push_bind(current_user.id);
+    if let Some(status) = status {
+        query_builder.push(" AND ra.status = ").push_bind(status);
+    }
+    query_builder
+        .push(" ORDER BY ra.due_at IS NULL, ra.due_at ASC, ra.created_at DESC LIMIT ")
+        .push_bind(i64::from(per_page))
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let mut items = query_builder
+        .build_query_as::<AssignedPaperItem>()
+        .fetch_all(&pool)
+        .await
+        .map_err(AppError::internal)?;
+    let now = Utc::now();
+    for item in &mut items {
+        item.is_overdue = is_assignment_overdue(&item.status, item.due_at, now);
+    }
+
+    let mut count_builder = QueryBuilder::<MySql>::new(
+        "SELECT COUNT(*) FROM reviewer_assignments ra JOIN posts p ON p.id = ra.post_id WHERE p.deleted_at IS NULL AND ra.reviewer_id = ",
+    );
+    count_builder.push_bind(current_user.id);
+    if let Some(status) = status {
+        count_builder.push(" AND ra.status = ").push_bind(status);
+    }
+    let (total,): (i64,) = count_builder
+        .build_query_as()
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(AssignedPaperListResponse {
+        items,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+/// Records the reviewer's conflict-of-interest declaration. It can be
+/// replaced until the invitation is accepted; replacing it drops any editor
+/// override, so the editor sees the new declaration before it takes effect.
+async fn declare_conflicts(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(assignment_id): Path<i64>,
+    Json(input): Json<DeclareReviewerConflicts>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let assignment = fetch_assignment(&pool, assignment_id).await?;
+    if assignment.reviewer_id != current_user.id {
+        return Err(AppError::not_found("Reviewer assignment not found"));
+    }
+    let other_conflict =
+        normalize_conflict_text("other_conflict", input.other_conflict.as_deref())?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO reviewer_conflict_declarations
+            (assignment_id, has_coauthorship, has_same_institution, other_conflict, declared_at)
+        SELECT ra.id, ?, ?, ?, ?
+        FROM reviewer_assignments ra
+        WHERE ra.id = ? AND ra.status = ?
+        ON DUPLICATE KEY UPDATE
+            has_coauthorship = VALUES(has_coauthorship),
+            has_same_institution = VALUES(has_same_institution),
+            other_conflict = VALUES(other_conflict),
+            declared_at = VALUES(declared_at),
+            overridden_by = NULL,
+            override_reason = NULL,
+            overridden_at = NULL
+        "#,
+    )
+    .bind(input.has_coauthorship)
+    .bind(input.has_same_institution)
+    .bind(&other_conflict)
+    .bind(Utc::now())
+    .bind(assignment_id)
+    .bind(REVIEWER_ASSIGNMENT_INVITED)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::conflict(format!(
+            "Conflicts can only be declared while the assignment is {}",
+            REVIEWER_ASSIGNMENT_INVITED
+        ))
+        .with_field("status", assignment.status));
+    }
+
+    let updated = fetch_assignment(&pool, assignment_id).await?;
+    if let Some(editor_id) = updated.assigned_by
+        && updated
+            .conflict_declaration
+            .as_ref()
+            .is_some_and(ReviewerConflictDeclaration::declares_conflict)
+    {
+        let title = fetch_paper_title(&pool, updated.post_id).await?;
+        dispatch_notifications(
+            &pool,
+            vec![NewNotification {
+                user_id: editor_id,
+                actor_id: Some(current_user.id),
+                event_type: NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE,
+                post_id: Some(updated.post_id),
+                comment_id: None,
+                message: format!(
+                    "{} declared a conflict of interest for \"{}\"",
+                    current_user.username, title
+                ),
+            }],
+        )
+        .await;
+    }
+
+    Ok(Json(updated))
+}
+
+/// Lets the reviewer accept despite a declared conflict. The override is
+/// tied to the declaration it was made against.
+async fn override_conflict(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path((post_id, assignment_id)): Path<(i64, i64)>,
+    Json(input): Json<OverrideReviewerConflict>,
+) -> Result<impl IntoResponse, AppError> {
+    let editor = extract_admin_user(&pool, &headers).await?;
+    let reason = normalize_conflict_text("reason", Some(&input.reason))?
+        .ok_or_else(|| AppError::bad_request("An override reason is required"))?;
+
+    let assignment = fetch_assignment(&pool, assignment_id).await?;
+    if assignment.post_id != post_id {
+        return Err(AppError::not_found("Reviewer assignment not found"));
+    }
+    let Some(declaration) = assignment
+        .conflict_declaration
+        .as_ref()
+        .filter(|declaration| declaration.blocks_acceptance())
+    else {
+        return Err(AppError::conflict(
+            "No declared conflict is waiting for an override",
+        ));
+    };
+    if assignment.status != REVIEWER_ASSIGNMENT_INVITED {
+        return Err(AppError::conflict(
+            "Conflicts can only be overridden before the review is accepted",
+        )
+        .with_field("status", assignment.status));
+    }
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let result = sqlx::query(
+        r#"
+        UPDATE reviewer_conflict_declarations
+        SET overridden_by = ?, override_reason = ?, overridden_at = ?
+        WHERE assignment_id = ? AND declared_at = ? AND overridden_at IS NULL
+        "#,
+    )
+    .bind(editor.id)
+    .bind(&reason)
+    .bind(Utc::now())
+    .bind(assignment_id)
+    .bind(declaration.declared_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::conflict(
+            "The conflict declaration changed; review it again before overriding",
+        ));
+    }
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &editor,
+            action: AUDIT_ACTION_CONFLICT_OVERRIDE,
+            target_type: AUDIT_TARGET_REVIEWER_ASSIGNMENT,
+            target_id: assignment_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "post_id": post_id,
+                "reviewer_id": assignment.reviewer_id,
+                "declared_at": declaration.declared_at,
+                "overridden": false,
+            })),
+            after: Some(serde_json::json!({"overridden": true, "reason": reason})),
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let title = fetch_paper_title(&pool, post_id).await?;
+    dispatch_notifications(
+        &pool,
+        vec![NewNotification {
+            user_id: assignment.reviewer_id,
+            actor_id: Some(editor.id),
+            event_type: NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE,
+            post_id: Some(post_id),
+            comment_id: None,
+            message: format!(
+                "{} cleared your declared conflict for \"{}\"; you can now accept the review",
+                editor.username, title
+            ),
+        }],
+    )
+    .await;
+
+    Ok(Json(fetch_assignment(&pool, assignment_id).await?))
+}
+
+async fn accept_assignment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(assignment_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    respond_to_assignment(&pool, &headers, assignment_id, AssignmentResponse::Accept).await
+}
+
+async fn decline_assignment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(assignment_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    respond_to_assignment(&pool, &headers, assignment_id, AssignmentResponse::Decline).await
+}
+
+async fn submit_assignment(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(assignment_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    respond_to_assignment(&pool, &headers, assignment_id, AssignmentResponse::Submit).await
+}
+
+/// Moves the caller's own assignment to the next status. The status guard is
+/// part of the UPDATE so two concurrent responses cannot both succeed.
+async fn respond_to_assignment(
+    pool: &MySqlPool,
+    headers: &HeaderMap,
+    assignment_id: i64,
+    response: AssignmentResponse,
+) -> Result<Json<ReviewerAssignmentResponse>, AppError> {
+    let current_user = extract_current_user(pool, headers).await?;
+    let assignment = fetch_assignment(pool, assignment_id).await?;
+    if assignment.reviewer_id != current_user.id {
+        return Err(AppError::not_found("Reviewer assignment not found"));
+    }
+    if let AssignmentResponse::Accept = response {
+        ensure_conflicts_cleared(&assignment)?;
+    }
+
+    let now = Utc::now();
+    let mut query_builder = QueryBuilder::<MySql>::new("UPDATE reviewer_assignments SET status = ");
+    query_builder.push_bind(response.target_status());
+    match response {
+        AssignmentResponse::Submit => query_builder.push(", submitted_at = ").push_bind(now),
+        _ => query_builder.push(", responded_at = ").push_bind(now),
+    };
+    query_builder
+        .push(", updated_at = ")
+        .push_bind(now)
+        .push(" WHERE id = ")
+        .push_bind(assignment_id)
+        .push(" AND status IN (");
+    let mut separated = query_builder.separated(", ");
+    for status in response.source_statuses() {
+        separated.push_bind(*status);
+    }
+    separated.push_unseparated(")");
+    if let AssignmentResponse::Accept = response {
+        query_builder.push(CONFLICTS_CLEARED_CONDITION);
+    }
+    let result = query_builder
+        .build()
+        .execute(pool)
+        .await
+        .map_err(AppError::internal)?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::conflict(format!(
+            "Assignment cannot be marked {} while it is {}",
+            response.target_status(),
+            assignment.status
+        )));
+    }
+
+    let updated = fetch_assignment(pool, assignment_id).await?;
+    if let Some(editor_id) = updated.assigned_by {
+        let title = fetch_paper_title(pool, updated.post_id).await?;
+        let message = match response {
+            AssignmentResponse::Accept => {
+                format!(
+                    "{} accepted the review of \"{}\"",
+                    current_user.username, title
+                )
+            }
+            AssignmentResponse::Decline => {
+                format!(
+                    "{} declined the review of \"{}\"",
+                    current_user.username, title
+                )
+            }
+            AssignmentResponse::Submit => {
+                format!(
+                    "{} submitted a review of \"{}\"",
+                    current_user.username, title
+                )
+            }
+        };
+        dispatch_notifications(
+            pool,
+            vec![NewNotification {
+                user_id: editor_id,
+                actor_id: Some(current_user.id),
+                event_type: NOTIFICATION_REVIEW_ASSIGNMENT_UPDATE,
+                post_id: Some(updated.post_id),
+                comment_id: None,
+                message,
+            }],
+        )
+        .await;
+    }
+
+    Ok(Json(updated))
+}
+
+async fn fetch_assignment(
+    pool: &MySqlPool,
+    assignment_id: i64,
+) -> Result<ReviewerAssignmentResponse, AppError> {
+    let mut assignment = sqlx::query_as::<_, ReviewerAssignmentResponse>(&format!(
+        "{} WHERE ra.id = ?",
+        ASSIGNMENT_SELECT
+    ))
+    .bind(assignment_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Reviewer assignment not found"))?;
+    assignment.is_overdue =
+        is_assignment_overdue(&assignment.status, assignment.due_at, Utc::now());
+    assignment.conflict_declaration = sqlx::query_as::<_, ReviewerConflictDeclaration>(&format!(
+        "{} WHERE rcd.assignment_id = ?",
+        CONFLICT_DECLARATION_SELECT
+    ))
+    .bind(assignment_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(AppError::internal)?
+    .map(with_conflict_flag);
+
+    Ok(assignment)
+}
+
+fn with_conflict_flag(mut declaration: ReviewerConflictDeclaration) -> ReviewerConflictDeclaration {
+    declaration.has_conflict = declaration.declares_conflict();
+    declaration
+}
+
+fn ensure_conflicts_cleared(assignment: &ReviewerAssignmentResponse) -> Result<(), AppError> {
+    match assignment.conflict_declaration.as_ref() {
+        None => Err(AppError::conflict(
+            "Declare any conflicts of interest before accepting this review",
+        )),
+        Some(declaration) if declaration.blocks_acceptance() => Err(AppError::conflict(
+            "A declared conflict of interest blocks this review until an editor overrides it",
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Trims free-text conflict fields; blank text counts as absent.
+fn normalize_conflict_text(field: &str, raw: Option<&str>) -> Result<Option<String>, AppError> {
+    let text = raw.map(str::trim).filter(|text| !text.is_empty());
+    if text.is_some_and(|text| text.chars().count() > MAX_CONFLICT_TEXT_CHARS) {
+        return Err(AppError::bad_request(format!(
+            "{} must be at most {} characters",
+            field, MAX_CONFLICT_TEXT_CHARS
+        )));
+    }
+
+    Ok(text.map(ToOwned::to_owned))
+}
+
+/// Returns the author, title and paper status of a paper post.
+async fn fetch_paper(pool: &MySqlPool, post_id: i64) -> Result<(i64, String, String), AppError> {
+    let (author_id, title, paper_status, category_code) =
+        sqlx::query_as::<_, (i64, String, String, String)>(
+            r#"
+            SELECT p.author_id, p.title, p.paper_status, c.code
+            FROM posts p
+            JOIN post_categories c ON c.id = p.category_id
+            WHERE p.id = ? AND p.deleted_at IS NULL
+            "#,
+        )
+        .bind(post_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Post not found"))?;
+
+    if category_code != "paper" {
+        return Err(AppError::bad_request(
+            "Reviewers can only be assigned to paper posts",
+        ));
+    }
+
+    Ok((author_id, title, paper_status))
+}
+
+async fn fetch_paper_title(pool: &MySqlPool, post_id: i64) -> Result<String, AppError> {
+    sqlx::query_scalar("SELECT title FROM posts WHERE id = ?")
+        .bind(post_id)
+        .fetch_one(pool)
+        .await
+        .map_err(AppError::internal)
+}
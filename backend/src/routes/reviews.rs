@@ -2,7 +2,7 @@ use axum::{
     Json, Router,
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use serde::Deserialize;
@@ -12,14 +12,23 @@ use crate::ai_review::{
     ReviewTrigger, fetch_latest_review, fetch_post_reviews, fetch_user_review_center,
     schedule_review,
 };
-use crate::models::PAPER_STATUS_SUBMITTED;
+use crate::cache::invalidate_cached_post;
+use crate::conditional_get::{CachePolicy, conditional_json};
+use crate::error::AppError;
+use crate::models::{AiReviewResponse, PAPER_STATUS_SUBMITTED, User};
+use crate::paper_status::{STATUS_TRIGGER_AI_REVIEW_RERUN, record_status_transition};
+use crate::rate_limit::{RateLimitedRoute, rate_limited};
 use crate::routes::auth::extract_current_user;
+use crate::sparse_fields::{FieldSelection, REVIEW_LARGE_FIELDS};
 
 pub fn reviews_routes() -> Router<MySqlPool> {
     Router::new()
         .route("/{post_id}/reviews/latest", get(get_latest_post_review))
         .route("/{post_id}/reviews", get(list_post_reviews))
-        .route("/{post_id}/reviews/rerun", post(rerun_post_review))
+        .route(
+            "/{post_id}/reviews/rerun",
+            post(rate_limited(RateLimitedRoute::AiReview, rerun_post_review)),
+        )
 }
 
 pub fn review_center_routes() -> Router<MySqlPool> {
@@ -30,32 +39,36 @@ pub fn review_center_routes() -> Router<MySqlPool> {
 struct ReviewListQuery {
     limit: Option<i32>,
     offset: Option<i32>,
+    /// Comma-separated top-level fields to return for each review.
+    fields: Option<String>,
+    /// Leaves out each review's input snapshot and raw model response.
+    summary_only: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 struct MyReviewCenterQuery {
     page: Option<i32>,
     per_page: Option<i32>,
+    /// Comma-separated top-level fields to return for each paper.
+    fields: Option<String>,
 }
 
 async fn get_latest_post_review(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let _ = ensure_review_access(&pool, &headers, post_id).await?;
+) -> Result<impl IntoResponse, AppError> {
+    let (current_user, _) = ensure_review_access(&pool, &headers, post_id).await?;
 
-    let review = fetch_latest_review(&pool, post_id)
+    let mut review = fetch_latest_review(&pool, post_id)
         .await
-        .map_err(internal_error)?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "No AI review found for this post"})),
-            )
-        })?;
-
-    Ok(Json(review))
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("No AI review found for this post"))?;
+    if !current_user.is_admin {
+        review.strip_raw_payloads();
+    }
+
+    conditional_json(&headers, CachePolicy::Private, &review)
 }
 
 async fn list_post_reviews(
@@ -63,45 +76,51 @@ async fn list_post_reviews(
     headers: HeaderMap,
     Path(post_id): Path<i64>,
     Query(query): Query<ReviewListQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let _ = ensure_review_access(&pool, &headers, post_id).await?;
+) -> Result<Response, AppError> {
+    let (current_user, _) = ensure_review_access(&pool, &headers, post_id).await?;
 
     let limit = query.limit.unwrap_or(20).clamp(1, 100);
     let offset = query.offset.unwrap_or(0).max(0);
-    let response = fetch_post_reviews(&pool, post_id, limit, offset)
+    let mut response = fetch_post_reviews(&pool, post_id, limit, offset)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
+    if !current_user.is_admin {
+        response
+            .reviews
+            .iter_mut()
+            .for_each(AiReviewResponse::strip_raw_payloads);
+    }
 
-    Ok(Json(response))
+    let response = FieldSelection::new(
+        query.fields.as_deref(),
+        query.summary_only,
+        REVIEW_LARGE_FIELDS,
+    )
+    .select(response, "reviews")?;
+    conditional_json(&headers, CachePolicy::Private, &response)
 }
 
 async fn rerun_post_review(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Path(post_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let category_code = ensure_review_access(&pool, &headers, post_id).await?;
+) -> Result<impl IntoResponse, AppError> {
+    let (current_user, category_code) = ensure_review_access(&pool, &headers, post_id).await?;
     if category_code != "paper" {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "detail": "AI review is only available for paper category posts"
-            })),
+        return Err(AppError::bad_request(
+            "AI review is only available for paper category posts",
         ));
     }
 
-    let latest_version_row =
-        sqlx::query_as::<_, (Option<i64>,)>("SELECT latest_paper_version_id FROM posts WHERE id = ?")
-            .bind(post_id)
-            .fetch_one(&pool)
-            .await
-            .map_err(internal_error)?;
-    let latest_paper_version_id = latest_version_row.0.ok_or_else(|| {
-        (
-            StatusCode::CONFLICT,
-            Json(serde_json::json!({"detail": "No submitted revision available for rerun"})),
-        )
-    })?;
+    let (latest_paper_version_id, previous_status) = sqlx::query_as::<_, (Option<i64>, String)>(
+        "SELECT latest_paper_version_id, paper_status FROM posts WHERE id = ?",
+    )
+    .bind(post_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let latest_paper_version_id = latest_paper_version_id
+        .ok_or_else(|| AppError::conflict("No submitted revision available for rerun"))?;
 
     let now = chrono::Utc::now();
     sqlx::query(
@@ -109,8 +128,8 @@ async fn rerun_post_review(
         UPDATE posts
         SET
             paper_status = ?,
-            is_published = FALSE,
-            published_at = NULL,
+            is_published = is_preprint AND published_at IS NOT NULL,
+            published_at = CASE WHEN is_preprint THEN published_at END,
             updated_at = ?
         WHERE id = ?
         "#,
@@ -120,11 +139,27 @@ async fn rerun_post_review(
     .bind(post_id)
     .execute(&pool)
     .await
-    .map_err(internal_error)?;
-
-    let review_id = schedule_review(&pool, post_id, Some(latest_paper_version_id), ReviewTrigger::Manual)
-        .await
-        .map_err(internal_error)?;
+    .map_err(AppError::internal)?;
+    record_status_transition(
+        &pool,
+        post_id,
+        Some(current_user.id),
+        STATUS_TRIGGER_AI_REVIEW_RERUN,
+        Some(&previous_status),
+        PAPER_STATUS_SUBMITTED,
+    )
+    .await
+    .map_err(AppError::internal)?;
+    invalidate_cached_post(post_id).await;
+
+    let review_id = schedule_review(
+        &pool,
+        post_id,
+        Some(latest_paper_version_id),
+        ReviewTrigger::Manual,
+    )
+    .await
+    .map_err(AppError::internal)?;
 
     Ok((
         StatusCode::ACCEPTED,
@@ -139,23 +174,23 @@ async fn list_my_paper_reviews(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
     Query(query): Query<MyReviewCenterQuery>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<Response, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
     let page = query.page.unwrap_or(1).max(1);
     let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
 
     let response = fetch_user_review_center(&pool, current_user.id, page, per_page)
         .await
-        .map_err(internal_error)?;
+        .map_err(AppError::internal)?;
 
-    Ok(Json(response))
+    FieldSelection::new(query.fields.as_deref(), None, &[]).respond(response, "items")
 }
 
 async fn ensure_review_access(
     pool: &MySqlPool,
     headers: &HeaderMap,
     post_id: i64,
-) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<(User, String), AppError> {
     let current_user = extract_current_user(pool, headers).await?;
 
     let row = sqlx::query_as::<_, (i64, String)>(
@@ -163,36 +198,21 @@ async fn ensure_review_access(
         SELECT p.author_id, c.code
         FROM posts p
         JOIN post_categories c ON c.id = p.category_id
-        WHERE p.id = ?
+        WHERE p.id = ? AND p.deleted_at IS NULL
         "#,
     )
     .bind(post_id)
     .fetch_optional(pool)
     .await
-    .map_err(internal_error)?
-    .ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "Post not found"})),
-        )
-    })?;
+    .map_err(AppError::internal)?
+    .ok_or_else(|| AppError::not_found("Post not found"))?;
 
     let (author_id, category_code) = row;
     if current_user.id != author_id && !current_user.is_admin {
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(
-                serde_json::json!({"detail": "Not authorized to access AI reviews for this post"}),
-            ),
+        return Err(AppError::forbidden(
+            "Not authorized to access AI reviews for this post",
         ));
     }
 
-    Ok(category_code)
-}
-
-fn internal_error<E: ToString>(error: E) -> (StatusCode, Json<serde_json::Value>) {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(serde_json::json!({"detail": error.to_string()})),
-    )
+    Ok((current_user, category_code))
 }
@@ -0,0 +1,323 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use quick_xml::escape::escape;
+use sqlx::{FromRow, MySqlPool};
+
+use crate::error::AppError;
+use crate::models::{
+    PAPER_STATUS_PUBLISHED, ScholarMetaResponse, ScholarMetaTag, is_blind_review_active,
+};
+use crate::routes::posts::frontend_base_url_for_links;
+
+const JOURNAL_NAME: &str = "Thought Manifold";
+
+// Only papers visible to anonymous readers are described; crawlers must not
+// learn about drafts or submissions under review.
+const SCHOLAR_META_SELECT: &str = r#"
+    SELECT
+        p.id AS id,
+        p.title AS title,
+        p.summary AS summary,
+        p.paper_status AS paper_status,
+        p.is_double_blind AS is_double_blind,
+        COALESCE(NULLIF(TRIM(u.display_name), ''), u.username) AS author_name,
+        COALESCE(p.published_at, p.created_at) AS published_on,
+        p.updated_at AS updated_at,
+        pf.file_path AS file_path,
+        (SELECT r.registered_doi FROM post_doi_registrations r WHERE r.post_id = p.id) AS registered_doi,
+        CAST(i.volume AS SIGNED) AS issue_volume,
+        CAST(i.number AS SIGNED) AS issue_number,
+        (
+            SELECT GROUP_CONCAT(t.name ORDER BY t.name SEPARATOR '\n')
+            FROM post_tags pt
+            JOIN tags t ON t.id = pt.tag_id
+            WHERE pt.post_id = p.id
+        ) AS tags
+    FROM posts p
+    JOIN post_categories c ON c.id = p.category_id
+    JOIN users u ON u.id = p.author_id
+    LEFT JOIN post_files pf ON pf.post_id = p.id
+    LEFT JOIN issues i ON i.id = p.issue_id
+    WHERE p.id = ?
+      AND c.code = 'paper'
+      AND p.is_published = TRUE
+      AND p.deleted_at IS NULL
+"#;
+
+#[derive(Debug, FromRow)]
+struct ScholarPaperRow {
+    id: i64,
+    title: String,
+    summary: Option<String>,
+    paper_status: String,
+    is_double_blind: bool,
+    author_name: String,
+    published_on: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    file_path: Option<String>,
+    registered_doi: Option<String>,
+    issue_volume: Option<i64>,
+    issue_number: Option<i64>,
+    tags: Option<String>,
+}
+
+impl ScholarPaperRow {
+    fn pdf_url(&self) -> Option<String> {
+        let path = self.file_path.as_deref()?;
+        path.to_ascii_lowercase().ends_with(".pdf").then(|| {
+            format!(
+                "{}/{}",
+                frontend_base_url_for_links(),
+                path.trim_start_matches('/')
+            )
+        })
+    }
+
+    fn tag_names(&self) -> Vec<&str> {
+        self.tags.as_deref().unwrap_or_default().lines().collect()
+    }
+}
+
+pub fn scholar_meta_routes() -> Router<MySqlPool> {
+    Router::new().route("/{post_id}/scholar-meta", get(get_scholar_meta))
+}
+
+// ============================
+// GET /api/posts/:id/scholar-meta
+// ============================
+async fn get_scholar_meta(
+    State(pool): State<MySqlPool>,
+    Path(post_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let meta = build_scholar_meta(&pool, post_id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("Paper not found"))?;
+
+    Ok(Json(meta))
+}
+
+/// Citation metadata of a public paper, or `None` for anything else.
+pub async fn build_scholar_meta(
+    pool: &MySqlPool,
+    post_id: i64,
+) -> Result<Option<ScholarMetaResponse>, sqlx::Error> {
+    let Some(row) = sqlx::query_as::<_, ScholarPaperRow>(SCHOLAR_META_SELECT)
+        .bind(post_id)
+        .fetch_optional(pool)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(ScholarMetaResponse {
+        post_id: row.id,
+        meta_tags: build_meta_tags(&row),
+        json_ld: build_json_ld(&row),
+    }))
+}
+
+/// Adds the citation tags of the paper at `path` to the SPA shell so crawlers
+/// that do not run JavaScript can index it. Other paths are left untouched.
+pub async fn inject_scholar_meta(pool: &MySqlPool, path: &str, html: String) -> String {
+    let Some(post_id) = path
+        .trim_end_matches('/')
+        .strip_prefix("/posts/")
+        .and_then(|value| value.parse::<i64>().ok())
+    else {
+        return html;
+    };
+
+    let meta = match build_scholar_meta(pool, post_id).await {
+        Ok(Some(meta)) => meta,
+        Ok(None) => return html,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to build scholar meta for post {}: {}",
+                post_id,
+                error
+            );
+            return html;
+        }
+    };
+    let Some(head_end) = html.find("</head>") else {
+        return html;
+    };
+
+    let mut head = String::new();
+    for tag in &meta.meta_tags {
+        head.push_str(&format!(
+            "    <meta name=\"{}\" content=\"{}\" />\n",
+            escape(tag.name.as_str()),
+            escape(tag.content.as_str())
+        ));
+    }
+    // `</` inside the JSON would otherwise end the script element early.
+    head.push_str(&format!(
+        "    <script type=\"application/ld+json\">{}</script>\n  ",
+        meta.json_ld.to_string().replace("</", "<\\/")
+    ));
+
+    let mut injected = String::with_capacity(html.len() + head.len());
+    injected.push_str(&html[..head_end]);
+    injected.push_str(&head);
+    injected.push_str(&html[head_end..]);
+
+    if let Some(title) = meta
+        .meta_tags
+        .iter()
+        .find(|tag| tag.name == "citation_title")
+        && let (Some(start), Some(end)) = (injected.find("<title>"), injected.find("</title>"))
+        && start < end
+    {
+        injected.replace_range(
+            start + "<title>".len()..end,
+            &format!("{} - {}", escape(title.content.as_str()), JOURNAL_NAME),
+        );
+    }
+
+    injected
+}
+
+fn build_meta_tags(row: &ScholarPaperRow) -> Vec<ScholarMetaTag> {
+    let mut tags: Vec<(&str, String)> = vec![("citation_title", row.title.clone())];
+    if !is_blind_review_active(row.is_double_blind, &row.paper_status) {
+        tags.push(("citation_author", row.author_name.clone()));
+    }
+    tags.push((
+        "citation_publication_date",
+        row.published_on.format("%Y/%m/%d").to_string(),
+    ));
+    // Preprints have not been accepted, so they are not cited as journal
+    // articles.
+    if row.paper_status == PAPER_STATUS_PUBLISHED {
+        tags.push(("citation_journal_title", JOURNAL_NAME.to_string()));
+    }
+    tags.push(("citation_publisher", JOURNAL_NAME.to_string()));
+    if let Some(volume) = row.issue_volume {
+        tags.push(("citation_volume", volume.to_string()));
+    }
+    if let Some(number) = row.issue_number {
+        tags.push(("citation_issue", number.to_string()));
+    }
+    if let Some(doi) = &row.registered_doi {
+        tags.push(("citation_doi", doi.clone()));
+    }
+    let keywords = row.tag_names();
+    if !keywords.is_empty() {
+        tags.push(("citation_keywords", keywords.join("; ")));
+    }
+    if let Some(summary) = row
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        tags.push(("citation_abstract", summary.to_string()));
+    }
+    tags.push(("citation_abstract_html_url", landing_page_url(row.id)));
+    if let Some(pdf_url) = row.pdf_url() {
+        tags.push(("citation_pdf_url", pdf_url));
+    }
+
+    tags.into_iter()
+        .map(|(name, content)| ScholarMetaTag {
+            name: name.to_string(),
+            content,
+        })
+        .collect()
+}
+
+fn build_json_ld(row: &ScholarPaperRow) -> serde_json::Value {
+    let mut article = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "ScholarlyArticle",
+        "headline": row.title,
+        "name": row.title,
+        "url": landing_page_url(row.id),
+        "datePublished": row.published_on.format("%Y-%m-%d").to_string(),
+        "publisher": {
+            "@type": "Organization",
+            "name": JOURNAL_NAME,
+        },
+    });
+    let fields = article
+        .as_object_mut()
+        .expect("json! object literal is an object");
+
+    if !is_blind_review_active(row.is_double_blind, &row.paper_status) {
+        fields.insert(
+            "author".to_string(),
+            serde_json::json!([{ "@type": "Person", "name": row.author_name }]),
+        );
+    }
+    if let Some(updated_at) = row.updated_at {
+        fields.insert(
+            "dateModified".to_string(),
+            serde_json::json!(updated_at.format("%Y-%m-%d").to_string()),
+        );
+    }
+    if let Some(summary) = row
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        fields.insert("abstract".to_string(), serde_json::json!(summary));
+    }
+    let keywords = row.tag_names();
+    if !keywords.is_empty() {
+        fields.insert("keywords".to_string(), serde_json::json!(keywords));
+    }
+    if let Some(doi) = &row.registered_doi {
+        fields.insert(
+            "identifier".to_string(),
+            serde_json::json!({
+                "@type": "PropertyValue",
+                "propertyID": "DOI",
+                "value": doi,
+            }),
+        );
+        fields.insert(
+            "sameAs".to_string(),
+            serde_json::json!(format!("https://doi.org/{}", doi)),
+        );
+    }
+    if row.paper_status == PAPER_STATUS_PUBLISHED {
+        let periodical = serde_json::json!({ "@type": "Periodical", "name": JOURNAL_NAME });
+        let part_of = match (row.issue_volume, row.issue_number) {
+            (Some(volume), Some(number)) => serde_json::json!({
+                "@type": "PublicationIssue",
+                "issueNumber": number.to_string(),
+                "isPartOf": {
+                    "@type": "PublicationVolume",
+                    "volumeNumber": volume.to_string(),
+                    "isPartOf": periodical,
+                },
+            }),
+            _ => periodical,
+        };
+        fields.insert("isPartOf".to_string(), part_of);
+    }
+    if let Some(pdf_url) = row.pdf_url() {
+        fields.insert(
+            "encoding".to_string(),
+            serde_json::json!({
+                "@type": "MediaObject",
+                "contentUrl": pdf_url,
+                "encodingFormat": "application/pdf",
+            }),
+        );
+    }
+
+    article
+}
+
+fn landing_page_url(post_id: i64) -> String {
+    format!("{}/posts/{}", frontend_base_url_for_links(), post_id)
+}
@@ -0,0 +1,197 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::{delete, get, post},
+};
+use serde::Deserialize;
+use sqlx::MySqlPool;
+
+use crate::audit_log::{
+    AUDIT_ACTION_POST_PURGE, AUDIT_ACTION_POST_RESTORE, AUDIT_ACTION_USER_PURGE,
+    AUDIT_ACTION_USER_RESTORE, AUDIT_TARGET_POST, AUDIT_TARGET_USER, AuditEvent, RequestMetadata,
+    record_audit_event,
+};
+use crate::cache::invalidate_cached_post_lists;
+use crate::error::AppError;
+use crate::models::{TRASH_ITEM_POST, TRASH_STATUS_TRASHED, TrashItem, TrashItemListResponse};
+use crate::routes::admin::extract_admin_user;
+use crate::trash::{
+    TRASH_ITEM_SELECT, TrashError, fetch_trash_item, purge_trash_item, restore_trash_item,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TrashListQuery {
+    pub item_type: Option<String>,
+    /// Defaults to `trashed`; `all` lists restored and purged items too.
+    pub status: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+/// Admin trash routes, nested under `/api/admin`.
+pub fn trash_routes() -> Router<MySqlPool> {
+    Router::new()
+        .route("/trash", get(list_trash_items))
+        .route("/trash/{trash_item_id}", delete(purge_item))
+        .route("/trash/{trash_item_id}/restore", post(restore_item))
+}
+
+/// Deleted posts and users, most recently deleted first.
+async fn list_trash_items(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(query): Query<TrashListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    extract_admin_user(&pool, &headers).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = i64::from(page - 1) * i64::from(per_page);
+    let item_type = query
+        .item_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let status = match query.status.as_deref().map(str::trim) {
+        None | Some("") => Some(TRASH_STATUS_TRASHED),
+        Some("all") => None,
+        Some(value) => Some(value),
+    };
+
+    let items = sqlx::query_as::<_, TrashItem>(&format!(
+        r#"
+        {}
+        WHERE (? IS NULL OR t.item_type = ?) AND (? IS NULL OR t.status = ?)
+        ORDER BY t.deleted_at DESC, t.id DESC
+        LIMIT ? OFFSET ?
+        "#,
+        TRASH_ITEM_SELECT
+    ))
+    .bind(item_type)
+    .bind(item_type)
+    .bind(status)
+    .bind(status)
+    .bind(i64::from(per_page))
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM trash_items WHERE (? IS NULL OR item_type = ?) AND (? IS NULL OR status = ?)",
+    )
+    .bind(item_type)
+    .bind(item_type)
+    .bind(status)
+    .bind(status)
+    .fetch_one(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(TrashItemListResponse {
+        items,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+/// Restores a trashed post, or a user together with the posts trashed
+/// with them. A post whose author is still in the trash cannot be restored
+/// on its own.
+async fn restore_item(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(trash_item_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    restore_trash_item(&mut tx, trash_item_id, admin.id)
+        .await
+        .map_err(trash_error)?;
+    let item = fetch_trash_item(&mut *tx, trash_item_id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::internal("Trash item disappeared"))?;
+    let (action, target_type) = if item.item_type == TRASH_ITEM_POST {
+        (AUDIT_ACTION_POST_RESTORE, AUDIT_TARGET_POST)
+    } else {
+        (AUDIT_ACTION_USER_RESTORE, AUDIT_TARGET_USER)
+    };
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action,
+            target_type,
+            target_id: item.item_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({"trash_item_id": trash_item_id})),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    // Trashed posts are never cached, so only the listings are stale.
+    invalidate_cached_post_lists().await;
+
+    Ok(Json(item))
+}
+
+/// Permanently deletes a trashed item without waiting for the purge job.
+async fn purge_item(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(trash_item_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let admin = extract_admin_user(&pool, &headers).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    let item = fetch_trash_item(&mut *tx, trash_item_id)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| trash_error(TrashError::NotFound))?;
+    purge_trash_item(&mut tx, trash_item_id)
+        .await
+        .map_err(trash_error)?;
+    let (action, target_type) = if item.item_type == TRASH_ITEM_POST {
+        (AUDIT_ACTION_POST_PURGE, AUDIT_TARGET_POST)
+    } else {
+        (AUDIT_ACTION_USER_PURGE, AUDIT_TARGET_USER)
+    };
+    record_audit_event(
+        &mut *tx,
+        AuditEvent {
+            actor: &admin,
+            action,
+            target_type,
+            target_id: item.item_id,
+            request: &RequestMetadata::from_headers(&headers),
+            before: Some(serde_json::json!({
+                "trash_item_id": trash_item_id,
+                "label": item.label,
+                "post_count": item.post_count,
+            })),
+            after: None,
+        },
+    )
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(Json(serde_json::json!({"detail": "Trash item purged"})))
+}
+
+fn trash_error(error: TrashError) -> AppError {
+    match error {
+        TrashError::NotFound => AppError::not_found("Trash item not found"),
+        TrashError::NotTrashed(status) => {
+            AppError::conflict(format!("Trash item is already {}", status))
+        }
+        TrashError::AuthorTrashed => AppError::conflict("Restore the post's author first"),
+        TrashError::Database(error) => AppError::internal(error),
+    }
+}
@@ -1,114 +1,875 @@
 use axum::{
     Router,
-    extract::{Json, Path, State},
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
-    routing::get,
+    extract::{Json, Path, Query, State},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
 };
-use serde::Deserialize;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Url;
 use sqlx::MySqlPool;
+use utoipa::OpenApi;
 
-use crate::metrics::compute_author_metrics;
-use crate::models::{User, UserResponse};
-use crate::routes::auth::extract_current_user;
-
-#[derive(Debug, Deserialize)]
-pub struct UpdateProfile {
-    pub display_name: Option<String>,
-    pub bio: Option<String>,
-    pub introduction: Option<String>,
-    pub hobbies: Option<String>,
-    pub interests: Option<String>,
-    pub research_areas: Option<String>,
-}
+use crate::account_activity::record_account_event;
+use crate::audit_log::RequestMetadata;
+use crate::error::{AppError, ErrorBody};
+use crate::metrics::fetch_author_metrics;
+use crate::models::{
+    ACCOUNT_EVENT_USERNAME_CHANGE, AuthorMetrics, BlockStatusResponse, BlockedUser, ChangeUsername,
+    CitationRelation, ExpertiseTagsResponse, FollowListQuery, FollowListResponse,
+    FollowStatusResponse, FollowUser, NetworkQuery, PROFILE_ACTIVITY_COMMENT,
+    PROFILE_ACTIVITY_POST, PostListResponse, PostQuery, PostResponse, ProfileActivity,
+    ProfilePublication, PublicProfileResponse, UpdateExpertiseTags, UpdateProfile,
+    UpdateResearchProfile, User, UserNetworkResponse, UserResponse,
+};
+use crate::notifications::notify_new_follower;
+use crate::orcid::is_orcid_id;
+use crate::routes::auth::{extract_current_user, find_user_by_username, is_username_taken};
+use crate::routes::comments::is_blocked_by;
+use crate::routes::posts::{
+    BIBTEX_CONTENT_TYPE, export_posts_bibtex, list_author_posts, list_posts,
+};
+use crate::settings::setting;
+use crate::validation::ValidatedJson;
+
+const DEFAULT_USERNAME_CHANGE_COOLDOWN_DAYS: i64 = 30;
+
+const MAX_EXPERTISE_TAGS: usize = 20;
+const MAX_EXPERTISE_TAG_CHARS: usize = 64;
+
+const ORCID_URL_PREFIXES: &[&str] = &["https://orcid.org/", "http://orcid.org/", "orcid.org/"];
+
+const DEFAULT_NETWORK_LIMIT: i64 = 20;
+const MAX_NETWORK_LIMIT: i64 = 100;
+const PROFILE_ACTIVITY_LIMIT: i64 = 20;
+
+// Posts still under double-blind review would give their authors away.
+const NOT_BLIND_REVIEW_CONDITION: &str =
+    "NOT (p.is_double_blind = TRUE AND p.paper_status IN ('draft', 'submitted', 'revision'))";
 
 pub fn users_routes() -> Router<MySqlPool> {
     Router::new()
         .route("/", get(list_users))
         .route("/me", axum::routing::put(update_profile))
+        .route("/me/blocks", get(list_blocked_users))
+        .route("/me/expertise", axum::routing::put(update_expertise_tags))
+        .route("/me/feed", get(get_following_feed))
+        .route("/me/profile", axum::routing::put(update_research_profile))
+        .route("/me/username", axum::routing::put(change_username))
         .route("/{user_id}", get(get_user))
+        .route("/{user_id}/block", post(block_user).delete(unblock_user))
+        .route("/{user_id}/expertise", get(get_expertise_tags))
+        .route("/{user_id}/follow", post(follow_user).delete(unfollow_user))
+        .route("/{user_id}/followers", get(list_followers))
+        .route("/{user_id}/following", get(list_following))
         .route("/{user_id}/metrics", get(get_user_metrics))
+        .route("/{user_id}/network", get(get_user_network))
         .route("/{user_id}/posts", get(get_user_posts))
+        .route("/{user_id}/profile", get(get_public_profile))
+        .route(
+            "/{user_id}/publications.bib",
+            get(export_user_publications_bibtex),
+        )
 }
 
-async fn list_users(
-    State(pool): State<MySqlPool>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let users = sqlx::query_as::<_, User>("SELECT * FROM users LIMIT 20")
+/// OpenAPI paths for `users_routes`, relative to `/api/users`.
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_users,
+    update_profile,
+    list_blocked_users,
+    update_expertise_tags,
+    get_following_feed,
+    update_research_profile,
+    change_username,
+    get_user,
+    block_user,
+    unblock_user,
+    get_expertise_tags,
+    follow_user,
+    unfollow_user,
+    list_followers,
+    list_following,
+    get_user_metrics,
+    get_user_network,
+    get_user_posts,
+    get_public_profile,
+    export_user_publications_bibtex
+))]
+pub struct UsersApi;
+
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "users",
+    responses(
+        (status = 200, description = "Up to 20 users", body = Vec<UserResponse>),
+    )
+)]
+async fn list_users(State(pool): State<MySqlPool>) -> Result<impl IntoResponse, AppError> {
+    let users = sqlx::query_as::<_, User>("SELECT * FROM users WHERE deleted_at IS NULL LIMIT 20")
         .fetch_all(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     let responses: Vec<UserResponse> = users.into_iter().map(UserResponse::from).collect();
     Ok(Json(responses))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{user_id}",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user with follow counts", body = UserResponse),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
 async fn get_user(
     State(pool): State<MySqlPool>,
     Path(user_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+) -> Result<impl IntoResponse, AppError> {
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ? AND deleted_at IS NULL")
         .bind(user_id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "User not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
+
+    let (follower_count, following_count) = fetch_follow_counts(&pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
+    let mut response = UserResponse::from(user);
+    response.follower_count = Some(follower_count);
+    response.following_count = Some(following_count);
+
+    Ok(Json(response))
+}
+
+async fn fetch_follow_counts(pool: &MySqlPool, user_id: i64) -> Result<(i64, i64), sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM user_follows f JOIN users u ON u.id = f.follower_id
+             WHERE f.followed_id = ? AND u.deleted_at IS NULL),
+            (SELECT COUNT(*) FROM user_follows f JOIN users u ON u.id = f.followed_id
+             WHERE f.follower_id = ? AND u.deleted_at IS NULL)
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
+
+async fn ensure_user_exists(pool: &MySqlPool, user_id: i64) -> Result<(), AppError> {
+    sqlx::query("SELECT id FROM users WHERE id = ? AND deleted_at IS NULL")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(AppError::internal)?
+        .map(|_| ())
+        .ok_or_else(|| AppError::not_found("User not found"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{user_id}/follow",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Following", body = FollowStatusResponse),
+        (status = 403, description = "The user has blocked the caller", body = ErrorBody),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn follow_user(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    if current_user.id == user_id {
+        return Err(AppError::bad_request("You cannot follow yourself"));
+    }
+    ensure_user_exists(&pool, user_id).await?;
+    if is_blocked_by(&pool, user_id, current_user.id)
+        .await
+        .map_err(AppError::internal)?
+    {
+        return Err(AppError::forbidden("You cannot follow this user"));
+    }
+
+    let result = sqlx::query(
+        "INSERT IGNORE INTO user_follows (follower_id, followed_id, created_at) VALUES (?, ?, ?)",
+    )
+    .bind(current_user.id)
+    .bind(user_id)
+    .bind(Utc::now())
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+    if result.rows_affected() > 0 {
+        notify_new_follower(&pool, (current_user.id, &current_user.username), user_id).await;
+    }
+
+    let (follower_count, _) = fetch_follow_counts(&pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(FollowStatusResponse {
+        user_id,
+        following: true,
+        follower_count,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{user_id}/follow",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Not following", body = FollowStatusResponse),
+    )
+)]
+async fn unfollow_user(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    sqlx::query("DELETE FROM user_follows WHERE follower_id = ? AND followed_id = ?")
+        .bind(current_user.id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    let (follower_count, _) = fetch_follow_counts(&pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(FollowStatusResponse {
+        user_id,
+        following: false,
+        follower_count,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{user_id}/followers",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id"), FollowListQuery),
+    responses(
+        (status = 200, description = "One page of followers", body = FollowListResponse),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn list_followers(
+    State(pool): State<MySqlPool>,
+    Path(user_id): Path<i64>,
+    Query(query): Query<FollowListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    list_follow_edges(&pool, user_id, query, "followed_id", "follower_id").await
+}
+
+#[utoipa::path(
+    get,
+    path = "/{user_id}/following",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id"), FollowListQuery),
+    responses(
+        (status = 200, description = "One page of followed users", body = FollowListResponse),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn list_following(
+    State(pool): State<MySqlPool>,
+    Path(user_id): Path<i64>,
+    Query(query): Query<FollowListQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    list_follow_edges(&pool, user_id, query, "follower_id", "followed_id").await
+}
+
+/// Users on the other end of `user_id`'s follow edges, most recent first.
+/// `own_column` is the side matching `user_id`; `other_column` the side
+/// listed.
+async fn list_follow_edges(
+    pool: &MySqlPool,
+    user_id: i64,
+    query: FollowListQuery,
+    own_column: &str,
+    other_column: &str,
+) -> Result<Json<FollowListResponse>, AppError> {
+    ensure_user_exists(pool, user_id).await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+
+    let from_clause = format!(
+        "FROM user_follows f JOIN users u ON u.id = f.{} WHERE f.{} = ? AND u.deleted_at IS NULL",
+        other_column, own_column
+    );
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", from_clause))
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(AppError::internal)?;
+    let users = sqlx::query_as::<_, FollowUser>(&format!(
+        r#"
+        SELECT u.id AS user_id, u.username, u.display_name, u.avatar_url, f.created_at AS followed_at
+        {}
+        ORDER BY f.created_at DESC, u.id DESC
+        LIMIT ? OFFSET ?
+        "#,
+        from_clause
+    ))
+    .bind(user_id)
+    .bind(i64::from(per_page))
+    .bind(i64::from(page - 1) * i64::from(per_page))
+    .fetch_all(pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(FollowListResponse {
+        users,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{user_id}/expertise",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user's expertise tags", body = ExpertiseTagsResponse),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn get_expertise_tags(
+    State(pool): State<MySqlPool>,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    ensure_user_exists(&pool, user_id).await?;
+    let tags = fetch_expertise_tags(&pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(ExpertiseTagsResponse { user_id, tags }))
+}
+
+/// Replaces the caller's expertise tags. Tags share the vocabulary of post
+/// tags so reviewer suggestions can match them against submissions.
+#[utoipa::path(
+    put,
+    path = "/me/expertise",
+    tag = "users",
+    security(("bearer" = [])),
+    request_body = UpdateExpertiseTags,
+    responses(
+        (status = 200, description = "The caller's expertise tags", body = ExpertiseTagsResponse),
+        (status = 401, description = "Not signed in", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn update_expertise_tags(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Json(input): Json<UpdateExpertiseTags>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    let mut tags: Vec<String> = Vec::new();
+    for raw in &input.tags {
+        let tag = raw.trim();
+        if tag.is_empty() || tags.iter().any(|seen| seen.eq_ignore_ascii_case(tag)) {
+            continue;
+        }
+        if tag.chars().count() > MAX_EXPERTISE_TAG_CHARS || tag.contains(',') {
+            return Err(AppError::validation(
+                "tags",
+                format!(
+                    "expertise tags must be at most {} characters and contain no commas",
+                    MAX_EXPERTISE_TAG_CHARS
+                ),
+            ));
+        }
+        tags.push(tag.to_string());
+    }
+    if tags.len() > MAX_EXPERTISE_TAGS {
+        return Err(AppError::validation(
+            "tags",
+            format!("at most {} expertise tags are allowed", MAX_EXPERTISE_TAGS),
+        ));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query("DELETE FROM user_expertise_tags WHERE user_id = ?")
+        .bind(current_user.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    for tag in &tags {
+        sqlx::query("INSERT IGNORE INTO tags (name) VALUES (?)")
+            .bind(tag)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::internal)?;
+        sqlx::query(
+            r#"
+            INSERT IGNORE INTO user_expertise_tags (user_id, tag_id, created_at)
+            SELECT ?, id, ? FROM tags WHERE name = ?
+            "#,
+        )
+        .bind(current_user.id)
+        .bind(now)
+        .bind(tag)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    }
+    tx.commit().await.map_err(AppError::internal)?;
+
+    let tags = fetch_expertise_tags(&pool, current_user.id)
+        .await
+        .map_err(AppError::internal)?;
+    Ok(Json(ExpertiseTagsResponse {
+        user_id: current_user.id,
+        tags,
+    }))
+}
+
+async fn fetch_expertise_tags(pool: &MySqlPool, user_id: i64) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT t.name
+        FROM user_expertise_tags ue
+        JOIN tags t ON t.id = ue.tag_id
+        WHERE ue.user_id = ?
+        ORDER BY t.name ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Blocking also drops any follow between the two users.
+#[utoipa::path(
+    post,
+    path = "/{user_id}/block",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Blocked; any follows between the two are removed", body = BlockStatusResponse),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn block_user(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    if current_user.id == user_id {
+        return Err(AppError::bad_request("You cannot block yourself"));
+    }
+    ensure_user_exists(&pool, user_id).await?;
+
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    sqlx::query(
+        "INSERT IGNORE INTO user_blocks (blocker_id, blocked_id, created_at) VALUES (?, ?, ?)",
+    )
+    .bind(current_user.id)
+    .bind(user_id)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    sqlx::query(
+        r#"
+        DELETE FROM user_follows
+        WHERE (follower_id = ? AND followed_id = ?) OR (follower_id = ? AND followed_id = ?)
+        "#,
+    )
+    .bind(current_user.id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(current_user.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+
+    Ok(Json(BlockStatusResponse {
+        user_id,
+        blocked: true,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{user_id}/block",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Unblocked", body = BlockStatusResponse),
+    )
+)]
+async fn unblock_user(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    sqlx::query("DELETE FROM user_blocks WHERE blocker_id = ? AND blocked_id = ?")
+        .bind(current_user.id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(BlockStatusResponse {
+        user_id,
+        blocked: false,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/blocks",
+    tag = "users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Users the caller has blocked", body = Vec<BlockedUser>),
+        (status = 401, description = "Not signed in", body = ErrorBody),
+    )
+)]
+async fn list_blocked_users(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let users = sqlx::query_as::<_, BlockedUser>(
+        r#"
+        SELECT u.id AS user_id, u.username, u.display_name, u.avatar_url, b.created_at AS blocked_at
+        FROM user_blocks b
+        JOIN users u ON u.id = b.blocked_id
+        WHERE b.blocker_id = ? AND u.deleted_at IS NULL
+        ORDER BY b.created_at DESC, u.id DESC
+        "#,
+    )
+    .bind(current_user.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(users))
+}
+
+/// Posts by the users the caller follows, with the usual list filters.
+#[utoipa::path(
+    get,
+    path = "/me/feed",
+    tag = "users",
+    security(("bearer" = [])),
+    params(PostQuery),
+    responses(
+        (status = 200, description = "Published posts by authors the caller follows", body = PostListResponse),
+        (status = 401, description = "Not signed in", body = ErrorBody),
+    )
+)]
+async fn get_following_feed(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    Query(mut query): Query<PostQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    query.followed_by = Some(current_user.id);
+    list_posts(State(pool), Query(query)).await
+}
+
+/// Public profile looked up by username. The path segment shares its name
+/// with the id routes because sibling route parameters must match.
+#[utoipa::path(
+    get,
+    path = "/{user_id}/profile",
+    tag = "users",
+    params(("user_id" = String, Path, description = "Username")),
+    responses(
+        (status = 200, description = "Public profile with publications and recent activity", body = PublicProfileResponse),
+        (status = 308, description = "A former username; redirects to the current profile"),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn get_public_profile(
+    State(pool): State<MySqlPool>,
+    Path(username): Path<String>,
+) -> Result<Response, AppError> {
+    let username = username.trim();
+    let user = find_user_by_username(&pool, username)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
+    // Links to a former username redirect to the current one.
+    if user.username != username {
+        return Ok(Redirect::permanent(&format!(
+            "/api/v1/users/{}/profile",
+            urlencoding::encode(&user.username)
+        ))
+        .into_response());
+    }
+
+    let (follower_count, following_count) = fetch_follow_counts(&pool, user.id)
+        .await
+        .map_err(AppError::internal)?;
+    let metrics = fetch_author_metrics(&pool, user.id)
+        .await
+        .map_err(AppError::internal)?;
+
+    let publications = sqlx::query_as::<_, ProfilePublication>(&format!(
+        r#"
+        SELECT
+            p.id,
+            p.title,
+            p.summary,
+            p.paper_status,
+            p.is_preprint,
+            p.published_at,
+            COALESCE(ps.citation_count, 0) AS citation_count,
+            COALESCE(ps.external_citation_count, 0) AS external_citation_count
+        FROM posts p
+        JOIN post_categories c ON c.id = p.category_id
+        LEFT JOIN post_stats ps ON ps.post_id = p.id
+        WHERE p.author_id = ?
+          AND c.code = 'paper'
+          AND p.is_published = TRUE
+          AND p.deleted_at IS NULL
+          AND {}
+        ORDER BY COALESCE(p.published_at, p.created_at) DESC, p.id DESC
+        "#,
+        NOT_BLIND_REVIEW_CONDITION
+    ))
+    .bind(user.id)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
 
-    Ok(Json(UserResponse::from(user)))
+    let recent_activity = sqlx::query_as::<_, ProfileActivity>(&format!(
+        r#"
+        SELECT activity_type, post_id, post_title, comment_id, occurred_at
+        FROM (
+            SELECT
+                ? AS activity_type,
+                p.id AS post_id,
+                p.title AS post_title,
+                CAST(NULL AS SIGNED) AS comment_id,
+                COALESCE(p.published_at, p.created_at) AS occurred_at
+            FROM posts p
+            WHERE p.author_id = ? AND p.is_published = TRUE AND p.deleted_at IS NULL AND {blind}
+            UNION ALL
+            SELECT
+                ? AS activity_type,
+                p.id AS post_id,
+                p.title AS post_title,
+                cm.id AS comment_id,
+                cm.created_at AS occurred_at
+            FROM comments cm
+            JOIN posts p ON p.id = cm.post_id
+            WHERE cm.author_id = ?
+              AND cm.is_deleted = FALSE
+              AND p.is_published = TRUE
+              AND p.deleted_at IS NULL
+              AND {blind}
+        ) activity
+        ORDER BY occurred_at DESC
+        LIMIT ?
+        "#,
+        blind = NOT_BLIND_REVIEW_CONDITION
+    ))
+    .bind(PROFILE_ACTIVITY_POST)
+    .bind(user.id)
+    .bind(PROFILE_ACTIVITY_COMMENT)
+    .bind(user.id)
+    .bind(PROFILE_ACTIVITY_LIMIT)
+    .fetch_all(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    Ok(Json(PublicProfileResponse {
+        id: user.id,
+        username: user.username,
+        display_name: user.display_name,
+        bio: user.bio,
+        introduction: user.introduction,
+        hobbies: user.hobbies,
+        interests: user.interests,
+        research_areas: user.research_areas,
+        affiliation: user.affiliation,
+        website: user.website,
+        orcid_id: user.orcid_id,
+        avatar_url: user.avatar_url,
+        created_at: user.created_at,
+        follower_count,
+        following_count,
+        metrics,
+        publications,
+        recent_activity,
+    })
+    .into_response())
 }
 
+#[utoipa::path(
+    get,
+    path = "/{user_id}/metrics",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Citation metrics for the author", body = AuthorMetrics),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
 async fn get_user_metrics(
     State(pool): State<MySqlPool>,
     Path(user_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let user_exists = sqlx::query("SELECT id FROM users WHERE id = ?")
+) -> Result<impl IntoResponse, AppError> {
+    let user_exists = sqlx::query("SELECT id FROM users WHERE id = ? AND deleted_at IS NULL")
         .bind(user_id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     if user_exists.is_none() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"detail": "User not found"})),
-        ));
+        return Err(AppError::not_found("User not found"));
     }
 
-    let metrics = compute_author_metrics(&pool, user_id).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
+    let metrics = fetch_author_metrics(&pool, user_id)
+        .await
+        .map_err(AppError::internal)?;
 
     Ok(Json(metrics))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{user_id}/network",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id"), NetworkQuery),
+    responses(
+        (status = 200, description = "Co-authors and citation links", body = UserNetworkResponse),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn get_user_network(
+    State(pool): State<MySqlPool>,
+    Path(user_id): Path<i64>,
+    Query(query): Query<NetworkQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_exists = sqlx::query("SELECT id FROM users WHERE id = ? AND deleted_at IS NULL")
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    if user_exists.is_none() {
+        return Err(AppError::not_found("User not found"));
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_NETWORK_LIMIT)
+        .clamp(1, MAX_NETWORK_LIMIT);
+
+    let cited_by = fetch_citation_relations(&pool, user_id, true, limit)
+        .await
+        .map_err(AppError::internal)?;
+    let citing = fetch_citation_relations(&pool, user_id, false, limit)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(UserNetworkResponse {
+        user_id,
+        cited_by,
+        citing,
+    }))
+}
+
+/// Counts distinct post-to-post citations between `user_id` and other
+/// authors, in either direction. Only published posts and edges not flagged
+/// by a retraction count; self-citations are left out.
+async fn fetch_citation_relations(
+    pool: &MySqlPool,
+    user_id: i64,
+    incoming: bool,
+    limit: i64,
+) -> Result<Vec<CitationRelation>, sqlx::Error> {
+    let (own_side, other_side) = if incoming {
+        ("cited", "citing")
+    } else {
+        ("citing", "cited")
+    };
+    let sql = format!(
+        r#"
+        SELECT
+            u.id AS user_id,
+            u.username,
+            u.display_name,
+            u.avatar_url,
+            COUNT(*) AS citation_count
+        FROM (
+            SELECT DISTINCT citing_post_id, cited_post_id
+            FROM post_citations
+            WHERE retraction_flagged_at IS NULL
+        ) pc
+        JOIN posts citing ON citing.id = pc.citing_post_id
+        JOIN posts cited ON cited.id = pc.cited_post_id
+        JOIN users u ON u.id = {other}.author_id
+        WHERE {own}.author_id = ?
+          AND {other}.author_id <> ?
+          AND citing.is_published = TRUE
+          AND cited.is_published = TRUE
+          AND citing.deleted_at IS NULL
+          AND cited.deleted_at IS NULL
+        GROUP BY u.id, u.username, u.display_name, u.avatar_url
+        ORDER BY citation_count DESC, u.id ASC
+        LIMIT ?
+        "#,
+        own = own_side,
+        other = other_side
+    );
+
+    sqlx::query_as::<_, CitationRelation>(&sql)
+        .bind(user_id)
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+#[utoipa::path(
+    put,
+    path = "/me",
+    tag = "users",
+    security(("bearer" = [])),
+    request_body = UpdateProfile,
+    responses(
+        (status = 200, description = "The updated user", body = UserResponse),
+        (status = 401, description = "Not signed in", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
 async fn update_profile(
     State(pool): State<MySqlPool>,
     headers: HeaderMap,
-    Json(input): Json<UpdateProfile>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ValidatedJson(input): ValidatedJson<UpdateProfile>,
+) -> Result<impl IntoResponse, AppError> {
     let current_user = extract_current_user(&pool, &headers).await?;
 
     let display_name = input
@@ -144,23 +905,13 @@ async fn update_profile(
         .bind(current_user.id)
         .execute(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     let updated_user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
         .bind(current_user.id)
         .fetch_one(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
+        .map_err(AppError::internal)?;
 
     Ok(Json(UserResponse::from(updated_user)))
 }
@@ -179,105 +930,308 @@ fn normalize_optional_text(input: Option<&str>, fallback: Option<&str>) -> Optio
     }
 }
 
+/// Updates the structured research profile. Omitted fields are kept and
+/// empty strings clear them.
+#[utoipa::path(
+    put,
+    path = "/me/profile",
+    tag = "users",
+    security(("bearer" = [])),
+    request_body = UpdateResearchProfile,
+    responses(
+        (status = 200, description = "The updated user", body = UserResponse),
+        (status = 401, description = "Not signed in", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+    )
+)]
+async fn update_research_profile(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    ValidatedJson(input): ValidatedJson<UpdateResearchProfile>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+
+    let introduction = normalize_optional_text(
+        input.introduction.as_deref(),
+        current_user.introduction.as_deref(),
+    );
+    let hobbies =
+        normalize_optional_text(input.hobbies.as_deref(), current_user.hobbies.as_deref());
+    let interests = normalize_optional_text(
+        input.interests.as_deref(),
+        current_user.interests.as_deref(),
+    );
+    let research_areas = normalize_optional_text(
+        input.research_areas.as_deref(),
+        current_user.research_areas.as_deref(),
+    );
+    let affiliation = normalize_optional_text(
+        input.affiliation.as_deref(),
+        current_user.affiliation.as_deref(),
+    );
+    let website = match input.website.as_deref() {
+        Some(raw) => validate_website(raw)?,
+        None => current_user.website.clone(),
+    };
+    let orcid_id = match input.orcid_id.as_deref() {
+        Some(raw) => validate_orcid_id(raw)?,
+        None => current_user.orcid_id.clone(),
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET introduction = ?, hobbies = ?, interests = ?, research_areas = ?,
+            affiliation = ?, website = ?, orcid_id = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&introduction)
+    .bind(&hobbies)
+    .bind(&interests)
+    .bind(&research_areas)
+    .bind(&affiliation)
+    .bind(&website)
+    .bind(&orcid_id)
+    .bind(Utc::now())
+    .bind(current_user.id)
+    .execute(&pool)
+    .await
+    .map_err(AppError::internal)?;
+
+    let updated_user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+        .bind(current_user.id)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(UserResponse::from(updated_user)))
+}
+
+fn validate_website(raw: &str) -> Result<Option<String>, AppError> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    let parsed = Url::parse(trimmed)
+        .map_err(|_| AppError::validation("website", "website must be a valid URL"))?;
+    if (parsed.scheme() != "http" && parsed.scheme() != "https") || parsed.host_str().is_none() {
+        return Err(AppError::validation(
+            "website",
+            "website must be an http or https URL",
+        ));
+    }
+    Ok(Some(trimmed.to_string()))
+}
+
+/// Accepts a bare iD or an orcid.org link and stores the bare iD.
+fn validate_orcid_id(raw: &str) -> Result<Option<String>, AppError> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let orcid_id = ORCID_URL_PREFIXES
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))
+        .unwrap_or(trimmed)
+        .trim_end_matches('/')
+        .to_ascii_uppercase();
+    if !is_orcid_id(&orcid_id) {
+        return Err(AppError::validation(
+            "orcid_id",
+            "orcid_id must look like 0000-0002-1825-0097",
+        ));
+    }
+    Ok(Some(orcid_id))
+}
+
+/// Renames the caller. The old username stays reserved for them and
+/// redirects to the new one.
+#[utoipa::path(
+    put,
+    path = "/me/username",
+    tag = "users",
+    security(("bearer" = [])),
+    request_body = ChangeUsername,
+    responses(
+        (status = 200, description = "The renamed user", body = UserResponse),
+        (status = 401, description = "Not signed in", body = ErrorBody),
+        (status = 409, description = "Username is taken", body = ErrorBody),
+        (status = 422, description = "Invalid fields, listed under `errors`", body = ErrorBody),
+        (status = 429, description = "Changed too recently; see `next_change_at`", body = ErrorBody),
+    )
+)]
+async fn change_username(
+    State(pool): State<MySqlPool>,
+    headers: HeaderMap,
+    ValidatedJson(input): ValidatedJson<ChangeUsername>,
+) -> Result<impl IntoResponse, AppError> {
+    let current_user = extract_current_user(&pool, &headers).await?;
+    let username = input.username.trim().to_string();
+    if username == current_user.username {
+        return Err(AppError::validation(
+            "username",
+            "username is the same as the current one",
+        ));
+    }
+
+    let cooldown_days = username_change_cooldown_days();
+    if cooldown_days > 0 {
+        let last_changed_at: Option<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT MAX(changed_at) FROM username_history WHERE user_id = ?")
+                .bind(current_user.id)
+                .fetch_one(&pool)
+                .await
+                .map_err(AppError::internal)?;
+        if let Some(last_changed_at) = last_changed_at {
+            let next_change_at = last_changed_at + Duration::days(cooldown_days);
+            if next_change_at > Utc::now() {
+                return Err(
+                    AppError::too_many_requests("Username was changed too recently")
+                        .with_field("next_change_at", next_change_at),
+                );
+            }
+        }
+    }
+
+    if is_username_taken(&pool, &username, Some(current_user.id))
+        .await
+        .map_err(AppError::internal)?
+    {
+        return Err(AppError::conflict("Username is already taken"));
+    }
+
+    let now = Utc::now();
+    let mut tx = pool.begin().await.map_err(AppError::internal)?;
+    // Taking back one of their own former usernames releases its redirect.
+    sqlx::query("DELETE FROM username_history WHERE user_id = ? AND old_username = ?")
+        .bind(current_user.id)
+        .bind(&username)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    sqlx::query(
+        "INSERT INTO username_history (user_id, old_username, changed_at) VALUES (?, ?, ?)",
+    )
+    .bind(current_user.id)
+    .bind(&current_user.username)
+    .bind(now)
+    .execute(&mut *tx)
+    .await
+    .map_err(AppError::internal)?;
+    sqlx::query("UPDATE users SET username = ?, updated_at = ? WHERE id = ?")
+        .bind(&username)
+        .bind(now)
+        .bind(current_user.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::internal)?;
+    tx.commit().await.map_err(AppError::internal)?;
+    record_account_event(
+        &pool,
+        current_user.id,
+        ACCOUNT_EVENT_USERNAME_CHANGE,
+        None,
+        RequestMetadata::from_headers(&headers),
+    );
+
+    let updated_user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+        .bind(current_user.id)
+        .fetch_one(&pool)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(Json(UserResponse::from(updated_user)))
+}
+
+fn username_change_cooldown_days() -> i64 {
+    setting::<i64>("USERNAME_CHANGE_COOLDOWN_DAYS")
+        .filter(|days| *days >= 0)
+        .unwrap_or(DEFAULT_USERNAME_CHANGE_COOLDOWN_DAYS)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{user_id}/posts",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user's published posts, newest first", body = [PostResponse]),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
 async fn get_user_posts(
     State(pool): State<MySqlPool>,
     Path(user_id): Path<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, AppError> {
     // Verify user exists
-    let _user = sqlx::query("SELECT id FROM users WHERE id = ?")
+    let _user = sqlx::query("SELECT id FROM users WHERE id = ? AND deleted_at IS NULL")
         .bind(user_id)
         .fetch_optional(&pool)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"detail": "User not found"})),
-            )
-        })?;
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
+
+    let posts = list_author_posts(&pool, user_id).await?;
+
+    Ok(Json(posts))
+}
+
+/// BibTeX of the user's public papers, newest first.
+#[utoipa::path(
+    get,
+    path = "/{user_id}/publications.bib",
+    tag = "users",
+    params(("user_id" = i64, Path, description = "User id")),
+    responses(
+        (status = 200, description = "BibTeX for the user's published papers"),
+        (status = 404, description = "User not found", body = ErrorBody),
+    )
+)]
+async fn export_user_publications_bibtex(
+    State(pool): State<MySqlPool>,
+    Path(user_id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let username: String = sqlx::query_scalar("SELECT username FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(AppError::internal)?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
 
-    let posts = sqlx::query_as::<_, crate::models::post::Post>(
+    let post_ids: Vec<i64> = sqlx::query_scalar(
         r#"
-        SELECT
-            p.id,
-            p.title,
-            p.content,
-            p.summary,
-            p.github_url,
-            c.code AS category,
-            pf.file_path,
-            pf.file_name,
-            p.author_id,
-            p.is_published,
-            p.published_at,
-            p.paper_status,
-            CAST(p.current_revision AS SIGNED) AS current_revision,
-            COALESCE(ps.view_count, 0) AS view_count,
-            COALESCE(ps.like_count, 0) AS like_count,
-            p.created_at,
-            p.updated_at
+        SELECT p.id
         FROM posts p
         JOIN post_categories c ON c.id = p.category_id
-        LEFT JOIN post_files pf ON pf.post_id = p.id
-        LEFT JOIN post_stats ps ON ps.post_id = p.id
-        WHERE p.author_id = ? AND p.is_published = TRUE
-        ORDER BY p.created_at DESC
+        WHERE p.author_id = ? AND c.code = 'paper' AND p.is_published = TRUE AND p.deleted_at IS NULL
+        ORDER BY COALESCE(p.published_at, p.created_at) DESC, p.id DESC
         "#,
     )
     .bind(user_id)
     .fetch_all(&pool)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"detail": e.to_string()})),
-        )
-    })?;
-
-    // Build responses with author info
-    let author = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
-        .bind(user_id)
-        .fetch_one(&pool)
+    .map_err(AppError::internal)?;
+    let bibtex = export_posts_bibtex(&pool, &post_ids)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"detail": e.to_string()})),
-            )
-        })?;
-
-    let author_resp = UserResponse::from(author);
-
-    let responses: Vec<serde_json::Value> = posts
-        .into_iter()
-        .map(|p| {
-            serde_json::json!({
-                "id": p.id,
-                "title": p.title,
-                "content": p.content,
-                "summary": p.summary,
-                "github_url": p.github_url,
-                "category": p.category,
-                "file_path": p.file_path,
-                "file_name": p.file_name,
-                "author_id": p.author_id,
-                "author": author_resp,
-                "is_published": p.is_published,
-                "published_at": p.published_at,
-                "paper_status": p.paper_status,
-                "current_revision": p.current_revision,
-                "view_count": p.view_count,
-                "like_count": p.like_count,
-                "created_at": p.created_at,
-                "updated_at": p.updated_at,
-            })
-        })
-        .collect();
+        .map_err(AppError::internal)?;
 
-    Ok(Json(responses))
+    let mut file_stem: String = username
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-' || *ch == '_')
+        .collect();
+    if file_stem.is_empty() {
+        file_stem = format!("user-{}", user_id);
+    }
+    Ok((
+        [
+            (header::CONTENT_TYPE, BIBTEX_CONTENT_TYPE.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}-publications.bib\"", file_stem),
+            ),
+        ],
+        bibtex,
+    ))
 }
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, MySqlPool};
+
+use crate::feature_flags::reload_feature_flags;
+use crate::models::SystemSetting;
+use crate::tasks;
+
+pub const DEFAULT_SETTINGS_RELOAD_INTERVAL_SECS: u64 = 30;
+/// Upper bound of `UPLOAD_MAX_SIZE_MB`. The multipart body limit is fixed
+/// when the router is built, so it is sized from this rather than the
+/// current setting.
+pub const MAX_UPLOAD_SIZE_MB_CEILING: i64 = 50;
+
+pub const SETTING_SOURCE_DATABASE: &str = "database";
+pub const SETTING_SOURCE_ENVIRONMENT: &str = "environment";
+pub const SETTING_SOURCE_DEFAULT: &str = "default";
+
+#[derive(Debug, Clone, Copy)]
+pub enum SettingKind {
+    Text,
+    Integer { min: i64, max: i64 },
+    Boolean,
+}
+
+/// A setting admins may override at runtime. The key doubles as the name of
+/// the environment variable it falls back to.
+#[derive(Debug)]
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub description: &'static str,
+}
+
+impl SettingDefinition {
+    /// Returns the value to store, or a message explaining why `raw` is not
+    /// valid for this setting.
+    pub fn validate(&self, raw: &str) -> Result<String, String> {
+        let value = raw.trim();
+        match self.kind {
+            SettingKind::Text if value.is_empty() => Err("value must not be empty".to_string()),
+            SettingKind::Text => Ok(value.to_string()),
+            SettingKind::Integer { min, max } => match value.parse::<i64>() {
+                Ok(parsed) if (min..=max).contains(&parsed) => Ok(parsed.to_string()),
+                _ => Err(format!(
+                    "value must be an integer between {} and {}",
+                    min, max
+                )),
+            },
+            SettingKind::Boolean => match value.to_ascii_lowercase().parse::<bool>() {
+                Ok(parsed) => Ok(parsed.to_string()),
+                Err(_) => Err("value must be true or false".to_string()),
+            },
+        }
+    }
+}
+
+pub const SETTING_DEFINITIONS: &[SettingDefinition] = &[
+    SettingDefinition {
+        key: "GEMINI_MODEL",
+        kind: SettingKind::Text,
+        description: "Gemini model used for new AI reviews",
+    },
+    SettingDefinition {
+        key: "GEMINI_TIMEOUT_SECS",
+        kind: SettingKind::Integer { min: 1, max: 600 },
+        description: "Timeout of one Gemini request in seconds",
+    },
+    SettingDefinition {
+        key: "AI_REVIEW_MAX_INPUT_CHARS",
+        kind: SettingKind::Integer {
+            min: 2001,
+            max: 1_000_000,
+        },
+        description: "Characters of paper text sent to the AI reviewer before truncation",
+    },
+    SettingDefinition {
+        key: "UPLOAD_MAX_SIZE_MB",
+        kind: SettingKind::Integer {
+            min: 1,
+            max: MAX_UPLOAD_SIZE_MB_CEILING,
+        },
+        description: "Largest accepted post attachment in megabytes",
+    },
+    SettingDefinition {
+        key: "COMMENT_MAX_DEPTH",
+        kind: SettingKind::Integer { min: 0, max: 100 },
+        description: "Maximum reply nesting level; 0 removes the limit",
+    },
+    SettingDefinition {
+        key: "COMMENT_EDIT_WINDOW_SECS",
+        kind: SettingKind::Integer {
+            min: 0,
+            max: 31_536_000,
+        },
+        description: "Seconds after creation during which comments can be edited; 0 removes the limit",
+    },
+    SettingDefinition {
+        key: "USERNAME_CHANGE_COOLDOWN_DAYS",
+        kind: SettingKind::Integer { min: 0, max: 3650 },
+        description: "Days a user must wait between username changes; 0 removes the limit",
+    },
+    SettingDefinition {
+        key: "CROSSREF_MAX_DOIS",
+        kind: SettingKind::Integer { min: 1, max: 100 },
+        description: "DOIs extracted from a post and looked up in the Crossref cache",
+    },
+    SettingDefinition {
+        key: "POST_CATEGORIES_RESTRICTED",
+        kind: SettingKind::Boolean,
+        description: "Only allow posts in existing categories instead of creating new ones",
+    },
+];
+
+pub fn find_setting(key: &str) -> Option<&'static SettingDefinition> {
+    SETTING_DEFINITIONS
+        .iter()
+        .find(|definition| definition.key == key)
+}
+
+fn overrides() -> &'static RwLock<HashMap<String, String>> {
+    static OVERRIDES: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+    OVERRIDES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Current raw value of a setting: the admin override when one is loaded,
+/// otherwise the environment variable of the same name.
+pub fn setting_value(key: &str) -> Option<String> {
+    let stored = overrides()
+        .read()
+        .ok()
+        .and_then(|overrides| overrides.get(key).cloned());
+    stored.or_else(|| std::env::var(key).ok())
+}
+
+/// Typed form of [`setting_value`]. Values that do not parse count as unset,
+/// so callers fall back to their defaults.
+pub fn setting<T: FromStr>(key: &str) -> Option<T> {
+    setting_value(key).and_then(|raw| raw.trim().parse::<T>().ok())
+}
+
+/// Replaces the in-memory overrides with the contents of `system_settings`.
+/// Returns whether anything changed.
+pub async fn reload_settings(pool: &MySqlPool) -> Result<bool, sqlx::Error> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT setting_key, setting_value FROM system_settings")
+            .fetch_all(pool)
+            .await?;
+    let loaded: HashMap<String, String> = rows.into_iter().collect();
+
+    let Ok(mut overrides) = overrides().write() else {
+        return Ok(false);
+    };
+    if *overrides == loaded {
+        return Ok(false);
+    }
+    *overrides = loaded;
+    Ok(true)
+}
+
+/// Picks up settings and feature flags written by other server instances.
+/// Changes made through this instance's admin API are applied immediately.
+pub fn spawn_settings_reload(pool: MySqlPool) {
+    let interval_secs = reload_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("System settings reload is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "settings_reload",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                match reload_settings(&pool).await {
+                    Ok(false) => {}
+                    Ok(true) => tracing::info!("Reloaded system settings"),
+                    Err(error) => tracing::error!("System settings reload failed: {}", error),
+                }
+                match reload_feature_flags(&pool).await {
+                    Ok(false) => {}
+                    Ok(true) => tracing::info!("Reloaded feature flags"),
+                    Err(error) => tracing::error!("Feature flag reload failed: {}", error),
+                }
+            }
+        },
+    );
+}
+
+#[derive(Debug, FromRow)]
+struct SystemSettingRow {
+    id: i64,
+    setting_key: String,
+    setting_value: String,
+    updated_by: Option<i64>,
+    updated_at: DateTime<Utc>,
+}
+
+/// Every registered setting with its effective value and where it came
+/// from, in registration order.
+pub async fn list_system_settings(pool: &MySqlPool) -> Result<Vec<SystemSetting>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, SystemSettingRow>(
+        "SELECT id, setting_key, setting_value, updated_by, updated_at FROM system_settings",
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut rows: HashMap<String, SystemSettingRow> = rows
+        .into_iter()
+        .map(|row| (row.setting_key.clone(), row))
+        .collect();
+
+    Ok(SETTING_DEFINITIONS
+        .iter()
+        .map(|definition| {
+            let row = rows.remove(definition.key);
+            let environment = std::env::var(definition.key).ok();
+            let (value, source) = match (&row, environment) {
+                (Some(row), _) => (Some(row.setting_value.clone()), SETTING_SOURCE_DATABASE),
+                (None, Some(value)) => (Some(value), SETTING_SOURCE_ENVIRONMENT),
+                (None, None) => (None, SETTING_SOURCE_DEFAULT),
+            };
+            let (kind, min, max) = match definition.kind {
+                SettingKind::Text => ("text", None, None),
+                SettingKind::Integer { min, max } => ("integer", Some(min), Some(max)),
+                SettingKind::Boolean => ("boolean", None, None),
+            };
+            SystemSetting {
+                id: row.as_ref().map(|row| row.id),
+                key: definition.key.to_string(),
+                kind: kind.to_string(),
+                description: definition.description.to_string(),
+                min,
+                max,
+                value,
+                source: source.to_string(),
+                updated_by: row.as_ref().and_then(|row| row.updated_by),
+                updated_at: row.as_ref().map(|row| row.updated_at),
+            }
+        })
+        .collect())
+}
+
+fn reload_interval_secs() -> u64 {
+    std::env::var("SETTINGS_RELOAD_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SETTINGS_RELOAD_INTERVAL_SECS)
+}
@@ -0,0 +1,90 @@
+//! Sparse list responses. List endpoints accept `fields=`, a comma-separated
+//! list of the top-level item fields to return, and `summary_only=true`,
+//! which returns whole items minus their large fields (post `content`, AI
+//! review snapshots). Pruning happens on the serialized body, so the
+//! response types stay as they are and unknown field names are ignored.
+
+use std::collections::HashSet;
+
+use axum::{
+    Json,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::AppError;
+
+/// Post fields left out by `summary_only`.
+pub const POST_LARGE_FIELDS: &[&str] = &["content"];
+/// AI review fields left out by `summary_only`.
+pub const REVIEW_LARGE_FIELDS: &[&str] = &["input_snapshot", "raw_response"];
+
+/// Which fields of each listed item to send.
+#[derive(Debug)]
+pub struct FieldSelection {
+    keep: Option<HashSet<String>>,
+    drop: &'static [&'static str],
+}
+
+impl FieldSelection {
+    /// `large_fields` are the ones `summary_only` removes; an explicit
+    /// `fields` list can still ask for them.
+    pub fn new(
+        fields: Option<&str>,
+        summary_only: Option<bool>,
+        large_fields: &'static [&'static str],
+    ) -> Self {
+        let keep = fields
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string)
+                    .collect::<HashSet<_>>()
+            })
+            .filter(|keep| !keep.is_empty());
+        let drop = if keep.is_none() && summary_only.unwrap_or(false) {
+            large_fields
+        } else {
+            &[]
+        };
+        Self { keep, drop }
+    }
+
+    fn is_full(&self) -> bool {
+        self.keep.is_none() && self.drop.is_empty()
+    }
+
+    fn prune(&self, item: &mut Map<String, Value>) {
+        if let Some(keep) = &self.keep {
+            item.retain(|field, _| keep.contains(field));
+        }
+        for field in self.drop {
+            item.remove(*field);
+        }
+    }
+
+    /// Responds with `body` as JSON, pruning each object in its `items_key`
+    /// array. Full selections are serialized directly.
+    pub fn respond<T: Serialize>(&self, body: T, items_key: &str) -> Result<Response, AppError> {
+        if self.is_full() {
+            return Ok(Json(body).into_response());
+        }
+        Ok(Json(self.select(body, items_key)?).into_response())
+    }
+
+    /// `body` as a JSON value with each object in its `items_key` array
+    /// pruned, for handlers that build the response themselves.
+    pub fn select<T: Serialize>(&self, body: T, items_key: &str) -> Result<Value, AppError> {
+        let mut body = serde_json::to_value(body).map_err(AppError::internal)?;
+        if let Some(Value::Array(items)) = body.get_mut(items_key) {
+            for item in items {
+                if let Value::Object(item) = item {
+                    self.prune(item);
+                }
+            }
+        }
+        Ok(body)
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use chrono::Utc;
+use sqlx::MySqlPool;
+
+use crate::models::{
+    DatabaseUsage, DirectoryUsage, LargeAttachment, LargePost, SystemUsageReport, TableUsage,
+};
+use crate::version_files::VERSION_FILES_DIR;
+
+/// Local directories that hold user files.
+const STORAGE_DIRS: &[&str] = &["uploads", VERSION_FILES_DIR];
+
+/// Builds the capacity report behind `GET /admin/system/usage`, listing the
+/// `limit` largest posts and attachments.
+pub async fn collect_system_usage(
+    pool: &MySqlPool,
+    limit: i64,
+) -> Result<SystemUsageReport, anyhow::Error> {
+    let storage = tokio::task::spawn_blocking(|| {
+        STORAGE_DIRS
+            .iter()
+            .map(|name| directory_usage(name))
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    let tables = sqlx::query_as::<_, TableUsage>(
+        r#"
+        SELECT
+            CAST(TABLE_NAME AS CHAR) AS table_name,
+            CAST(COALESCE(TABLE_ROWS, 0) AS SIGNED) AS approximate_rows,
+            CAST(COALESCE(DATA_LENGTH, 0) AS SIGNED) AS data_bytes,
+            CAST(COALESCE(INDEX_LENGTH, 0) AS SIGNED) AS index_bytes,
+            CAST(COALESCE(DATA_LENGTH, 0) + COALESCE(INDEX_LENGTH, 0) AS SIGNED) AS total_bytes
+        FROM information_schema.TABLES
+        WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'
+        ORDER BY total_bytes DESC, table_name ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    let database = DatabaseUsage {
+        total_bytes: tables.iter().map(|table| table.total_bytes).sum(),
+        tables,
+    };
+
+    let largest_posts = sqlx::query_as::<_, LargePost>(
+        r#"
+        SELECT
+            id AS post_id,
+            title,
+            author_id,
+            CAST(OCTET_LENGTH(content) AS SIGNED) AS content_bytes
+        FROM posts
+        ORDER BY content_bytes DESC, id ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let attachment_rows: Vec<(i64, String, String, String)> = sqlx::query_as(
+        r#"
+        SELECT pf.post_id, p.title, pf.file_name, pf.file_path
+        FROM post_files pf
+        JOIN posts p ON p.id = pf.post_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut largest_attachments = Vec::with_capacity(attachment_rows.len());
+    for (post_id, title, file_name, file_path) in attachment_rows {
+        // Rows whose file is missing on disk are left out.
+        let Ok(metadata) = tokio::fs::metadata(&file_path).await else {
+            continue;
+        };
+        largest_attachments.push(LargeAttachment {
+            post_id,
+            title,
+            file_name,
+            file_path,
+            size_bytes: metadata.len() as i64,
+        });
+    }
+    largest_attachments.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.post_id.cmp(&b.post_id))
+    });
+    largest_attachments.truncate(limit as usize);
+
+    Ok(SystemUsageReport {
+        generated_at: Utc::now(),
+        storage,
+        database,
+        largest_posts,
+        largest_attachments,
+    })
+}
+
+fn directory_usage(name: &str) -> DirectoryUsage {
+    let root = Path::new(name);
+    let mut usage = DirectoryUsage {
+        name: name.to_string(),
+        exists: root.is_dir(),
+        file_count: 0,
+        total_bytes: 0,
+    };
+
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else if metadata.is_file() {
+                usage.file_count += 1;
+                usage.total_bytes += metadata.len() as i64;
+            }
+        }
+    }
+    usage
+}
@@ -0,0 +1,225 @@
+//! Supervised background work. Scheduled jobs go through `spawn_periodic`
+//! and one-off work (AI review runs, backups, webhook deliveries) through
+//! `spawn_task` instead of bare `tokio::spawn`, so that each run is named
+//! and counted for `GET /admin/tasks`, a panic is caught and recorded
+//! instead of silently ending a job, and shutdown can wait for them.
+//!
+//! On SIGTERM or Ctrl-C, schedulers stop starting new runs, the server
+//! stops accepting connections and finishes the requests in flight, and
+//! then `drain` waits up to `SHUTDOWN_GRACE_SECS` for running tasks.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{Instrument, Span};
+
+use crate::models::{BackgroundTaskListResponse, BackgroundTaskStatus};
+use crate::request_id;
+
+const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 30;
+
+static SHUTDOWN: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+static TRACKER: LazyLock<TaskTracker> = LazyLock::new(TaskTracker::new);
+static REGISTRY: LazyLock<Mutex<BTreeMap<&'static str, TaskRecord>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+#[derive(Default)]
+struct TaskRecord {
+    interval: Option<Duration>,
+    /// Runs in progress; one-off tasks of the same name can overlap.
+    running: u32,
+    runs: u64,
+    panics: u64,
+    last_started_at: Option<DateTime<Utc>>,
+    last_finished_at: Option<DateTime<Utc>>,
+    last_panic: Option<String>,
+    /// Set once a periodic task's scheduler has exited for shutdown.
+    stopped: bool,
+}
+
+/// Runs `job` every `interval`, starting now, until shutdown. A run that is
+/// still going when the next is due delays it rather than overlapping, and
+/// a run that panics is recorded and the next one starts on schedule.
+pub fn spawn_periodic<F, Fut>(name: &'static str, interval: Duration, job: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    update(name, |record| record.interval = Some(interval));
+    TRACKER.spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = SHUTDOWN.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            supervise(
+                name,
+                job().instrument(tracing::info_span!("job", task = name)),
+            )
+            .await;
+        }
+        update(name, |record| record.stopped = true);
+    });
+}
+
+/// Runs `work` once in the background. Shutdown waits for it like any
+/// other task. Work spawned while serving a request keeps its request id
+/// and tracing span, so its logs can be traced back to the request.
+pub fn spawn_task<Fut>(name: &'static str, work: Fut)
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let work = request_id::propagate(work).instrument(Span::current());
+    TRACKER.spawn(supervise(name, work));
+}
+
+/// Resolves once shutdown has begun, for long-lived work such as event
+/// streams that should end rather than be waited for.
+pub async fn shutdown_requested() {
+    SHUTDOWN.cancelled().await;
+}
+
+/// Waits for SIGTERM or Ctrl-C, then stops the schedulers. Passed to the
+/// server as its graceful shutdown signal.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", error);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", error);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("Shutdown requested; finishing requests and background tasks");
+    SHUTDOWN.cancel();
+}
+
+/// Waits for running tasks after the server has stopped, up to the grace
+/// period; tasks still running then are abandoned and logged.
+pub async fn drain() {
+    SHUTDOWN.cancel();
+    TRACKER.close();
+    let grace = Duration::from_secs(shutdown_grace_secs());
+    if tokio::time::timeout(grace, TRACKER.wait()).await.is_ok() {
+        tracing::info!("Background tasks finished");
+        return;
+    }
+    let unfinished: Vec<&str> = registry()
+        .iter()
+        .filter(|(_, record)| record.running > 0)
+        .map(|(name, _)| *name)
+        .collect();
+    tracing::warn!(
+        "Shutting down with background tasks still running after {}s: {}",
+        grace.as_secs(),
+        unfinished.join(", ")
+    );
+}
+
+/// Every task started since startup, for `GET /admin/tasks`.
+pub fn task_statuses() -> BackgroundTaskListResponse {
+    let tasks = registry()
+        .iter()
+        .map(|(name, record)| BackgroundTaskStatus {
+            name: name.to_string(),
+            periodic: record.interval.is_some(),
+            interval_secs: record.interval.map(|interval| interval.as_secs()),
+            state: if record.running > 0 {
+                "running"
+            } else if record.stopped {
+                "stopped"
+            } else {
+                "idle"
+            }
+            .to_string(),
+            running: record.running,
+            runs: record.runs,
+            panics: record.panics,
+            last_started_at: record.last_started_at,
+            last_finished_at: record.last_finished_at,
+            last_panic: record.last_panic.clone(),
+        })
+        .collect();
+    BackgroundTaskListResponse {
+        shutting_down: SHUTDOWN.is_cancelled(),
+        tasks,
+    }
+}
+
+/// Runs one unit of work on its own task so a panic ends only that run.
+async fn supervise<Fut>(name: &'static str, work: Fut)
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    update(name, |record| {
+        record.running += 1;
+        record.runs += 1;
+        record.last_started_at = Some(Utc::now());
+    });
+    let panic = match tokio::spawn(work).await {
+        Err(error) if error.is_panic() => {
+            let payload = error.into_panic();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic with a non-string payload".to_string());
+            tracing::error!("Background task {} panicked: {}", name, message);
+            Some(message)
+        }
+        _ => None,
+    };
+    update(name, |record| {
+        record.running -= 1;
+        record.last_finished_at = Some(Utc::now());
+        if panic.is_some() {
+            record.panics += 1;
+            record.last_panic = panic;
+        }
+    });
+}
+
+fn update(name: &'static str, change: impl FnOnce(&mut TaskRecord)) {
+    change(registry().entry(name).or_default());
+}
+
+fn registry() -> std::sync::MutexGuard<'static, BTreeMap<&'static str, TaskRecord>> {
+    // A panic never happens while the lock is held, but recover regardless.
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn shutdown_grace_secs() -> u64 {
+    std::env::var("SHUTDOWN_GRACE_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_SHUTDOWN_GRACE_SECS)
+}
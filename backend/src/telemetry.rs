@@ -0,0 +1,357 @@
+//! Tracing setup. Logs always go to stdout, filtered by `RUST_LOG` and
+//! formatted as `LOG_FORMAT` asks (see `log_format`). The
+//! OpenTelemetry exporter is compiled in with the `otel` feature and used
+//! when `OTEL_EXPORTER_OTLP_ENDPOINT` is set: request spans, a span per
+//! periodic job run, a client span per outbound call made through `send`,
+//! and a span per SQL statement are then exported over OTLP/HTTP.
+//!
+//! The standard `OTEL_*` variables (`OTEL_SERVICE_NAME`,
+//! `OTEL_TRACES_SAMPLER`, `OTEL_EXPORTER_OTLP_HEADERS`, ...) configure the
+//! exporter; `OTEL_SPAN_FILTER` picks the spans exported, in `RUST_LOG`
+//! syntax.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{Instrument, Span, field};
+use tracing_subscriber::{Layer, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::api_version::unversioned_path;
+use crate::log_format::{self, CorrelationLayer, JsonFormat, RedactingStdout};
+
+const DEFAULT_LOG_FILTER: &str = "backend_rust=debug,tower_http=debug,sqlx=warn";
+
+/// Flushes exported spans when dropped at the end of `main`.
+pub struct TelemetryGuard(Option<exporter::Provider>);
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.0.take() {
+            exporter::shutdown(provider);
+        }
+    }
+}
+
+/// Installs the global subscriber: the stdout log and, when configured,
+/// the OpenTelemetry exporter.
+pub fn init() -> TelemetryGuard {
+    let log_filter = || {
+        tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| DEFAULT_LOG_FILTER.into())
+    };
+    let json = log_format::json_enabled();
+    let (otel_layer, provider, otel_error) = match exporter::layer() {
+        Ok(Some((layer, provider))) => (Some(layer), Some(provider), None),
+        Ok(None) => (None, None, None),
+        Err(error) => (None, None, Some(error)),
+    };
+
+    tracing_subscriber::registry()
+        .with(otel_layer)
+        .with(json.then_some(CorrelationLayer))
+        .with(json.then(|| {
+            tracing_subscriber::fmt::layer()
+                .event_format(JsonFormat)
+                .with_writer(RedactingStdout)
+                .with_filter(log_filter())
+        }))
+        .with((!json).then(|| {
+            tracing_subscriber::fmt::layer()
+                .with_writer(RedactingStdout)
+                .with_filter(log_filter())
+        }))
+        .init();
+
+    match (&provider, otel_error) {
+        (_, Some(error)) => tracing::warn!("OpenTelemetry export disabled: {}", error),
+        (Some(_), None) => tracing::info!("Exporting traces over OTLP"),
+        (None, None) => {}
+    }
+    TelemetryGuard(provider)
+}
+
+/// Span for a request, continuing the caller's trace when it sent a
+/// `traceparent` header.
+pub fn request_span(request: &Request, request_id: &str) -> Span {
+    let span = tracing::info_span!(
+        "request",
+        otel.kind = "server",
+        otel.name = %request.method(),
+        otel.status_code = field::Empty,
+        method = %request.method(),
+        // Masked here too, since span fields are exported as recorded.
+        uri = %log_format::redact(&request.uri().to_string()),
+        request_id = %request_id,
+        user_id = field::Empty,
+        http.route = field::Empty,
+        http.response.status_code = field::Empty,
+    );
+    exporter::set_remote_parent(&span, request.headers());
+    span
+}
+
+/// Names the request span after the matched route, so traces group by
+/// endpoint rather than by every distinct post id, and records the status.
+pub async fn record_route(request: Request, next: Next) -> Response {
+    let span = Span::current();
+    if let Some(route) = request.extensions().get::<MatchedPath>() {
+        let route = unversioned_path(route.as_str()).into_owned();
+        span.record("otel.name", format!("{} {}", request.method(), route));
+        span.record("http.route", route);
+    }
+    let response = next.run(request).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    response
+}
+
+/// Sends an outbound request inside a client span named after `service`.
+/// Only the host and path are recorded; query strings can carry API keys.
+pub async fn send(
+    service: &'static str,
+    request: reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    let span = tracing::info_span!(
+        "outbound",
+        otel.kind = "client",
+        otel.name = %format!("{} {}", request.method(), service),
+        otel.status_code = field::Empty,
+        peer.service = service,
+        http.request.method = %request.method(),
+        server.address = request.url().host_str().unwrap_or_default(),
+        url.path = request.url().path(),
+        http.response.status_code = field::Empty,
+    );
+    let result = client.execute(request).instrument(span.clone()).await;
+    match &result {
+        Ok(response) => {
+            span.record("http.response.status_code", response.status().as_u16());
+            if response.status().is_server_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+        }
+        Err(_) => {
+            span.record("otel.status_code", "ERROR");
+        }
+    }
+    result
+}
+
+#[cfg(feature = "otel")]
+mod exporter {
+    use std::time::{Duration, SystemTime};
+
+    use axum::http::HeaderMap;
+    use opentelemetry::propagation::Extractor;
+    use opentelemetry::trace::{Span as _, SpanKind, TraceContextExt, Tracer, TracerProvider as _};
+    use opentelemetry::{Context, KeyValue, global};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+    use tracing::field::{Field, Visit};
+    use tracing::{Event, Level, Span, Subscriber};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::filter::{EnvFilter, Targets};
+    use tracing_subscriber::layer::{Context as LayerContext, Layer};
+    use tracing_subscriber::registry::Registry;
+
+    pub type Provider = SdkTracerProvider;
+
+    const DEFAULT_SERVICE_NAME: &str = "thought-manifold";
+    const DEFAULT_SPAN_FILTER: &str = "backend_rust=info";
+    const TRACER_NAME: &str = "backend_rust";
+
+    type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+    pub fn layer() -> anyhow::Result<Option<(BoxedLayer, Provider)>> {
+        if !is_configured() {
+            return Ok(None);
+        }
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .build()?;
+        let service_name = std::env::var("OTEL_SERVICE_NAME")
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(service_name).build())
+            .build();
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider.clone());
+
+        let span_filter = std::env::var("OTEL_SPAN_FILTER")
+            .ok()
+            .filter(|raw| !raw.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_SPAN_FILTER.to_string());
+        let spans = tracing_opentelemetry::layer()
+            .with_tracer(provider.tracer(TRACER_NAME))
+            .with_filter(EnvFilter::try_new(span_filter)?);
+        let queries = QuerySpans {
+            tracer: provider.tracer(TRACER_NAME),
+        }
+        .with_filter(Targets::new().with_target("sqlx::query", Level::DEBUG));
+        Ok(Some((Box::new(spans.and_then(queries)), provider)))
+    }
+
+    /// Flushes on a blocking thread; the exporter's HTTP client is a
+    /// blocking one that must not be dropped on the runtime.
+    pub fn shutdown(provider: Provider) {
+        let flushed = std::thread::spawn(move || provider.shutdown()).join();
+        if let Ok(Err(error)) = flushed {
+            eprintln!("Failed to flush OpenTelemetry spans: {}", error);
+        }
+    }
+
+    pub fn set_remote_parent(span: &Span, headers: &HeaderMap) {
+        let parent = global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(headers))
+        });
+        if parent.span().span_context().is_remote()
+            && let Err(error) = span.set_parent(parent)
+        {
+            tracing::debug!("Could not continue the caller's trace: {}", error);
+        }
+    }
+
+    fn is_configured() -> bool {
+        let disabled = std::env::var("OTEL_SDK_DISABLED")
+            .is_ok_and(|raw| raw.trim().eq_ignore_ascii_case("true"));
+        let endpoint = [
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+        ]
+        .iter()
+        .any(|name| std::env::var(name).is_ok_and(|raw| !raw.trim().is_empty()));
+        endpoint && !disabled
+    }
+
+    struct HeaderExtractor<'a>(&'a HeaderMap);
+
+    impl Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|name| name.as_str()).collect()
+        }
+    }
+
+    /// sqlx logs each statement once it finishes, with its duration, rather
+    /// than wrapping it in a span; this turns those events into client spans
+    /// under the span that ran the statement. Statements outside any span,
+    /// such as startup migrations, are not exported.
+    struct QuerySpans {
+        tracer: SdkTracer,
+    }
+
+    impl<S: Subscriber> Layer<S> for QuerySpans {
+        fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+            let parent = Context::current();
+            if !parent.has_active_span() {
+                return;
+            }
+            let mut query = QueryEvent::default();
+            event.record(&mut query);
+
+            let end = SystemTime::now();
+            let start = end
+                .checked_sub(Duration::from_secs_f64(query.elapsed_secs.max(0.0)))
+                .unwrap_or(end);
+            let statement = if query.statement.trim().is_empty() {
+                query.summary.clone()
+            } else {
+                query.statement.trim().to_string()
+            };
+            let mut attributes = vec![
+                KeyValue::new("db.system.name", "mysql"),
+                KeyValue::new("db.query.text", statement),
+                KeyValue::new("db.response.returned_rows", query.rows_returned as i64),
+                KeyValue::new("db.response.affected_rows", query.rows_affected as i64),
+            ];
+            if *event.metadata().level() <= Level::WARN {
+                attributes.push(KeyValue::new("db.slow_statement", true));
+            }
+            let mut span = self
+                .tracer
+                .span_builder(query.summary)
+                .with_kind(SpanKind::Client)
+                .with_start_time(start)
+                .with_attributes(attributes)
+                .start_with_context(&self.tracer, &parent);
+            span.end_with_timestamp(end);
+        }
+    }
+
+    #[derive(Default)]
+    struct QueryEvent {
+        summary: String,
+        statement: String,
+        rows_affected: u64,
+        rows_returned: u64,
+        elapsed_secs: f64,
+    }
+
+    impl Visit for QueryEvent {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            match field.name() {
+                "summary" => self.summary = value.to_string(),
+                "db.statement" => self.statement = value.to_string(),
+                _ => {}
+            }
+        }
+
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            match field.name() {
+                "rows_affected" => self.rows_affected = value,
+                "rows_returned" => self.rows_returned = value,
+                _ => {}
+            }
+        }
+
+        fn record_f64(&mut self, field: &Field, value: f64) {
+            if field.name() == "elapsed_secs" {
+                self.elapsed_secs = value;
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+    }
+}
+
+/// Stand-in when the exporter is not compiled in; spans still reach the
+/// stdout log.
+#[cfg(not(feature = "otel"))]
+mod exporter {
+    use axum::http::HeaderMap;
+    use tracing::Span;
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::registry::Registry;
+
+    pub enum Provider {}
+
+    type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+    pub fn layer() -> anyhow::Result<Option<(BoxedLayer, Provider)>> {
+        if std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").is_ok_and(|raw| !raw.trim().is_empty()) {
+            anyhow::bail!(
+                "OTEL_EXPORTER_OTLP_ENDPOINT is set but the otel feature is not compiled in"
+            );
+        }
+        Ok(None)
+    }
+
+    pub fn shutdown(provider: Provider) {
+        match provider {}
+    }
+
+    pub fn set_remote_parent(_span: &Span, _headers: &HeaderMap) {}
+}
@@ -0,0 +1,440 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, MySql, MySqlConnection, MySqlPool};
+
+use crate::metrics::{
+    fetch_cited_post_ids, refresh_author_metrics_cache, refresh_post_citation_counts,
+};
+use crate::models::{
+    TRASH_ITEM_POST, TRASH_ITEM_USER, TRASH_STATUS_PURGED, TRASH_STATUS_RESTORED,
+    TRASH_STATUS_TRASHED, TrashItem,
+};
+use crate::tasks;
+
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+pub const DEFAULT_TRASH_PURGE_INTERVAL_SECS: u64 = 3_600;
+
+const MAX_TRASH_LABEL_CHARS: usize = 255;
+
+pub const TRASH_ITEM_SELECT: &str = r#"
+    SELECT
+        t.id,
+        t.item_type,
+        t.item_id,
+        t.label,
+        t.status,
+        (SELECT COUNT(*) FROM posts p WHERE p.trash_item_id = t.id) AS post_count,
+        t.deleted_by,
+        d.username AS deleted_by_username,
+        t.deleted_at,
+        t.purge_after,
+        t.restored_by,
+        t.restored_at,
+        t.purged_at
+    FROM trash_items t
+    LEFT JOIN users d ON d.id = t.deleted_by
+"#;
+
+#[derive(Debug)]
+pub enum TrashError {
+    NotFound,
+    /// The item was already restored or purged.
+    NotTrashed(String),
+    /// A post cannot come back while its author is in the trash.
+    AuthorTrashed,
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for TrashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "trash item not found"),
+            Self::NotTrashed(status) => write!(f, "trash item is already {}", status),
+            Self::AuthorTrashed => write!(f, "the post's author is in the trash"),
+            Self::Database(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<sqlx::Error> for TrashError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+pub async fn fetch_trash_item<'e, E>(
+    executor: E,
+    trash_item_id: i64,
+) -> Result<Option<TrashItem>, sqlx::Error>
+where
+    E: Executor<'e, Database = MySql>,
+{
+    sqlx::query_as::<_, TrashItem>(&format!("{} WHERE t.id = ?", TRASH_ITEM_SELECT))
+        .bind(trash_item_id)
+        .fetch_optional(executor)
+        .await
+}
+
+/// Hides a post until it is restored or purged. Its citation edges stay in
+/// place, but the posts it cites stop counting it. Returns `None` when the
+/// post does not exist or is already in the trash.
+pub async fn trash_post(
+    conn: &mut MySqlConnection,
+    post_id: i64,
+    deleted_by: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    let post: Option<(String, i64)> = sqlx::query_as(
+        "SELECT title, author_id FROM posts WHERE id = ? AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(post_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some((title, author_id)) = post else {
+        return Ok(None);
+    };
+
+    let now = Utc::now();
+    let trash_item_id = insert_trash_item(
+        &mut *conn,
+        TRASH_ITEM_POST,
+        post_id,
+        &title,
+        deleted_by,
+        now,
+    )
+    .await?;
+    sqlx::query("UPDATE posts SET deleted_at = ?, trash_item_id = ? WHERE id = ?")
+        .bind(now)
+        .bind(trash_item_id)
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let cited_post_ids = fetch_cited_post_ids(&mut *conn, &[post_id]).await?;
+    refresh_post_citation_counts(&mut *conn, &cited_post_ids).await?;
+    refresh_author_metrics_cache(&mut *conn, &[author_id]).await?;
+    Ok(Some(trash_item_id))
+}
+
+/// Hides a user and every post of theirs that is not already in the trash,
+/// all under one trash item. Posts trashed on their own keep their item.
+pub async fn trash_user(
+    conn: &mut MySqlConnection,
+    user_id: i64,
+    deleted_by: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    let username: Option<String> = sqlx::query_scalar(
+        "SELECT username FROM users WHERE id = ? AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(user_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(username) = username else {
+        return Ok(None);
+    };
+
+    let now = Utc::now();
+    let trash_item_id = insert_trash_item(
+        &mut *conn,
+        TRASH_ITEM_USER,
+        user_id,
+        &username,
+        deleted_by,
+        now,
+    )
+    .await?;
+    sqlx::query("UPDATE users SET deleted_at = ?, trash_item_id = ? WHERE id = ?")
+        .bind(now)
+        .bind(trash_item_id)
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "UPDATE posts SET deleted_at = ?, trash_item_id = ? WHERE author_id = ? AND deleted_at IS NULL",
+    )
+    .bind(now)
+    .bind(trash_item_id)
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let post_ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM posts WHERE trash_item_id = ?")
+        .bind(trash_item_id)
+        .fetch_all(&mut *conn)
+        .await?;
+    let cited_post_ids = fetch_cited_post_ids(&mut *conn, &post_ids).await?;
+    refresh_post_citation_counts(&mut *conn, &cited_post_ids).await?;
+    refresh_author_metrics_cache(&mut *conn, &[user_id]).await?;
+    Ok(Some(trash_item_id))
+}
+
+/// Brings back everything held by a trash item and recounts the citations
+/// its posts make.
+pub async fn restore_trash_item(
+    conn: &mut MySqlConnection,
+    trash_item_id: i64,
+    restored_by: i64,
+) -> Result<(), TrashError> {
+    let (item_type, item_id) = lock_trashed_item(&mut *conn, trash_item_id).await?;
+
+    if item_type == TRASH_ITEM_POST {
+        let author_trashed: Option<bool> = sqlx::query_scalar(
+            r#"
+            SELECT u.deleted_at IS NOT NULL
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.id = ?
+            "#,
+        )
+        .bind(item_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        if author_trashed.unwrap_or(false) {
+            return Err(TrashError::AuthorTrashed);
+        }
+    } else {
+        sqlx::query("UPDATE users SET deleted_at = NULL, trash_item_id = NULL WHERE id = ?")
+            .bind(item_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    let restored: Vec<(i64, i64)> =
+        sqlx::query_as("SELECT id, author_id FROM posts WHERE trash_item_id = ?")
+            .bind(trash_item_id)
+            .fetch_all(&mut *conn)
+            .await?;
+    sqlx::query("UPDATE posts SET deleted_at = NULL, trash_item_id = NULL WHERE trash_item_id = ?")
+        .bind(trash_item_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("UPDATE trash_items SET status = ?, restored_by = ?, restored_at = ? WHERE id = ?")
+        .bind(TRASH_STATUS_RESTORED)
+        .bind(restored_by)
+        .bind(Utc::now())
+        .bind(trash_item_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let post_ids: Vec<i64> = restored.iter().map(|(post_id, _)| *post_id).collect();
+    let mut author_ids: Vec<i64> = restored.iter().map(|(_, author_id)| *author_id).collect();
+    if item_type == TRASH_ITEM_USER {
+        author_ids.push(item_id);
+    }
+    author_ids.sort_unstable();
+    author_ids.dedup();
+    let cited_post_ids = fetch_cited_post_ids(&mut *conn, &post_ids).await?;
+    refresh_post_citation_counts(&mut *conn, &cited_post_ids).await?;
+    refresh_author_metrics_cache(&mut *conn, &author_ids).await?;
+    Ok(())
+}
+
+/// Permanently deletes what a trash item holds. Counters were already
+/// adjusted when it was trashed, so the removed edges change no counts.
+pub async fn purge_trash_item(
+    conn: &mut MySqlConnection,
+    trash_item_id: i64,
+) -> Result<(), TrashError> {
+    let (item_type, item_id) = lock_trashed_item(&mut *conn, trash_item_id).await?;
+    let now = Utc::now();
+
+    if item_type == TRASH_ITEM_POST {
+        purge_post(&mut *conn, item_id).await?;
+    } else {
+        // The user's posts go with them, including ones trashed on their own.
+        sqlx::query(
+            r#"
+            UPDATE trash_items
+            SET status = ?, purged_at = ?
+            WHERE item_type = ? AND status = ?
+              AND item_id IN (SELECT id FROM posts WHERE author_id = ?)
+            "#,
+        )
+        .bind(TRASH_STATUS_PURGED)
+        .bind(now)
+        .bind(TRASH_ITEM_POST)
+        .bind(TRASH_STATUS_TRASHED)
+        .bind(item_id)
+        .execute(&mut *conn)
+        .await?;
+        purge_user(&mut *conn, item_id).await?;
+    }
+
+    sqlx::query("UPDATE trash_items SET status = ?, purged_at = ? WHERE id = ?")
+        .bind(TRASH_STATUS_PURGED)
+        .bind(now)
+        .bind(trash_item_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn purge_post(conn: &mut MySqlConnection, post_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM comments WHERE post_id = ?")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM post_likes WHERE post_id = ?")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM post_citations WHERE citing_post_id = ? OR cited_post_id = ?")
+        .bind(post_id)
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM posts WHERE id = ?")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn purge_user(conn: &mut MySqlConnection, user_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM comments WHERE author_id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM post_likes WHERE user_id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        r#"
+        DELETE FROM post_citations
+        WHERE citing_post_id IN (SELECT id FROM posts WHERE author_id = ?)
+           OR cited_post_id IN (SELECT id FROM posts WHERE author_id = ?)
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query("DELETE FROM posts WHERE author_id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn insert_trash_item(
+    conn: &mut MySqlConnection,
+    item_type: &str,
+    item_id: i64,
+    label: &str,
+    deleted_by: i64,
+    deleted_at: DateTime<Utc>,
+) -> Result<i64, sqlx::Error> {
+    let label: String = label.chars().take(MAX_TRASH_LABEL_CHARS).collect();
+    let purge_after = deleted_at + chrono::Duration::days(trash_retention_days());
+    let result = sqlx::query(
+        r#"
+        INSERT INTO trash_items
+            (item_type, item_id, label, status, deleted_by, deleted_at, purge_after)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(item_type)
+    .bind(item_id)
+    .bind(label)
+    .bind(TRASH_STATUS_TRASHED)
+    .bind(deleted_by)
+    .bind(deleted_at)
+    .bind(purge_after)
+    .execute(conn)
+    .await?;
+    Ok(result.last_insert_id() as i64)
+}
+
+async fn lock_trashed_item(
+    conn: &mut MySqlConnection,
+    trash_item_id: i64,
+) -> Result<(String, i64), TrashError> {
+    let item: Option<(String, i64, String)> = sqlx::query_as(
+        "SELECT item_type, item_id, status FROM trash_items WHERE id = ? FOR UPDATE",
+    )
+    .bind(trash_item_id)
+    .fetch_optional(conn)
+    .await?;
+    match item {
+        None => Err(TrashError::NotFound),
+        Some((_, _, status)) if status != TRASH_STATUS_TRASHED => {
+            Err(TrashError::NotTrashed(status))
+        }
+        Some((item_type, item_id, _)) => Ok((item_type, item_id)),
+    }
+}
+
+/// Starts the job that purges trash items once `TRASH_RETENTION_DAYS` have
+/// passed since they were trashed.
+pub fn spawn_trash_purge(pool: MySqlPool) {
+    let interval_secs = purge_interval_secs();
+    if interval_secs == 0 {
+        tracing::info!("Trash purge job is disabled");
+        return;
+    }
+
+    tasks::spawn_periodic(
+        "trash_purge",
+        Duration::from_secs(interval_secs),
+        move || {
+            let pool = pool.clone();
+            async move {
+                if let Err(error) = purge_expired_items(&pool).await {
+                    tracing::error!("Trash purge failed: {}", error);
+                }
+            }
+        },
+    );
+}
+
+async fn purge_expired_items(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    let due: Vec<i64> = sqlx::query_scalar(
+        "SELECT id FROM trash_items WHERE status = ? AND purge_after <= ? ORDER BY purge_after ASC",
+    )
+    .bind(TRASH_STATUS_TRASHED)
+    .bind(Utc::now())
+    .fetch_all(pool)
+    .await?;
+
+    let mut purged = 0;
+    for trash_item_id in due {
+        let mut tx = pool.begin().await?;
+        match purge_trash_item(&mut tx, trash_item_id).await {
+            Ok(()) => {
+                tx.commit().await?;
+                purged += 1;
+            }
+            // Restored or purged by an admin since it was selected.
+            Err(TrashError::NotFound | TrashError::NotTrashed(_)) => {}
+            Err(error) => {
+                tracing::error!("Failed to purge trash item {}: {}", trash_item_id, error);
+            }
+        }
+    }
+    if purged > 0 {
+        tracing::info!("Purged {} expired trash item(s)", purged);
+    }
+    Ok(())
+}
+
+fn trash_retention_days() -> i64 {
+    std::env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|raw| raw.parse::<i64>().ok())
+        .filter(|value| *value >= 1)
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+
+fn purge_interval_secs() -> u64 {
+    std::env::var("TRASH_PURGE_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_TRASH_PURGE_INTERVAL_SECS)
+}
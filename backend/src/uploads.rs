@@ -0,0 +1,123 @@
+//! Post attachments arriving as multipart file fields. Each field is written
+//! to disk chunk by chunk through a small buffer rather than collected in
+//! memory, and the size limit is checked as the chunks arrive, so a large or
+//! oversized upload costs no more memory than a small one.
+//!
+//! The file is first written to a hidden `.partial` file in `uploads/` and
+//! only renamed to its public name once the handler keeps it. Staging in the
+//! same directory keeps that rename on one filesystem.
+
+use std::path::{Path, PathBuf};
+
+use axum::{
+    extract::multipart::{Field, MultipartError},
+    http::StatusCode,
+};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use uuid::Uuid;
+
+use crate::error::AppError;
+
+pub const UPLOADS_DIR: &str = "uploads";
+const PARTIAL_SUFFIX: &str = ".partial";
+const WRITE_BUFFER_BYTES: usize = 64 * 1024;
+
+/// A file field written to a temporary file. `persist` moves it to its final
+/// name; if it is dropped first, e.g. because a later field failed
+/// validation, the temporary file is removed.
+#[derive(Debug)]
+pub struct StagedUpload {
+    pub original_name: String,
+    /// Lower-cased extension of `original_name`, used for the stored name.
+    extension: String,
+    temp_path: PathBuf,
+    /// Set once `persist` has moved the file, so drop leaves it alone.
+    kept: bool,
+}
+
+impl StagedUpload {
+    /// Moves the file to `uploads/<uuid>.<ext>` and returns that path.
+    pub async fn persist(mut self) -> Result<String, AppError> {
+        let upload_path =
+            PathBuf::from(UPLOADS_DIR).join(format!("{}.{}", Uuid::new_v4(), self.extension));
+        tokio::fs::rename(&self.temp_path, &upload_path)
+            .await
+            .map_err(AppError::internal)?;
+        self.kept = true;
+        Ok(upload_path.to_string_lossy().to_string())
+    }
+}
+
+impl Drop for StagedUpload {
+    fn drop(&mut self) {
+        if !self.kept {
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Streams `field` to a temporary file, failing with 413 as soon as it
+/// grows past `max_bytes`. `extension` should already have been checked
+/// against the allowed types, so a rejected file is never written.
+pub async fn stage_upload(
+    mut field: Field<'_>,
+    original_name: String,
+    extension: String,
+    max_bytes: usize,
+) -> Result<StagedUpload, AppError> {
+    let temp_path =
+        PathBuf::from(UPLOADS_DIR).join(format!(".{}{}", Uuid::new_v4(), PARTIAL_SUFFIX));
+    let file = tokio::fs::File::create(&temp_path)
+        .await
+        .map_err(AppError::internal)?;
+    // Owns the temporary file from here on, so every early return removes it.
+    let staged = StagedUpload {
+        original_name,
+        extension,
+        temp_path,
+        kept: false,
+    };
+
+    let mut writer = BufWriter::with_capacity(WRITE_BUFFER_BYTES, file);
+    let mut written = 0;
+    while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+        written += chunk.len();
+        if written > max_bytes {
+            return Err(AppError::payload_too_large(format!(
+                "File too large. Max size is {}MB",
+                max_bytes / 1024 / 1024
+            )));
+        }
+        writer.write_all(&chunk).await.map_err(AppError::internal)?;
+    }
+    writer.flush().await.map_err(AppError::internal)?;
+
+    Ok(staged)
+}
+
+/// Removes `.partial` files left behind by uploads that were cut off by a
+/// crash or restart. Returns the number removed.
+pub async fn remove_partial_uploads() -> std::io::Result<usize> {
+    let mut entries = tokio::fs::read_dir(UPLOADS_DIR).await?;
+    let mut removed = 0;
+    while let Some(entry) = entries.next_entry().await? {
+        if is_partial_upload(&entry.path()) && tokio::fs::remove_file(entry.path()).await.is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn is_partial_upload(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') && name.ends_with(PARTIAL_SUFFIX))
+}
+
+pub fn multipart_error(error: MultipartError) -> AppError {
+    if error.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::payload_too_large(error.body_text())
+    } else {
+        AppError::bad_request(error.body_text())
+    }
+}
@@ -0,0 +1,164 @@
+//! Request validation. Input types derive `validator::Validate` with their
+//! per-field limits; JSON handlers take them through `ValidatedJson`, and
+//! form and multipart handlers call `validate` on what they parsed. Either
+//! way every violation is reported at once in a 422 listing the fields.
+
+use std::borrow::Cow;
+
+use axum::{
+    Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
+};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::error::{AppError, FieldError};
+
+const MIN_USERNAME_CHARS: usize = 3;
+const MAX_USERNAME_CHARS: usize = 32;
+/// Usernames that would shadow `/api/users/me/...` or pass for erased
+/// accounts.
+const RESERVED_USERNAMES: &[&str] = &["me", "admin", "deleted"];
+const ERASED_USERNAME_PREFIX: &str = "deleted-user-";
+
+const MAX_POST_TAGS: usize = 20;
+const MAX_POST_TAG_CHARS: usize = 64;
+
+/// A JSON body that has been deserialized and validated.
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state)
+            .await
+            .map_err(|rejection| match rejection {
+                // Well-formed JSON of the wrong shape, e.g. a missing field.
+                JsonRejection::JsonDataError(error) => {
+                    AppError::validation("body", error.body_text())
+                }
+                rejection => AppError::bad_request(rejection.body_text()),
+            })?;
+        validate(&value)?;
+        Ok(Self(value))
+    }
+}
+
+pub fn validate<T: Validate>(input: &T) -> Result<(), AppError> {
+    input.validate().map_err(AppError::from)
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut field_errors: Vec<FieldError> = errors
+            .field_errors()
+            .into_iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |error| {
+                    let message = error
+                        .message
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| format!("{} is invalid", field));
+                    FieldError::new(field.as_ref(), message)
+                })
+            })
+            .collect();
+        field_errors.sort_by(|a, b| a.field.cmp(&b.field));
+        AppError::Validation(field_errors)
+    }
+}
+
+fn invalid(code: &'static str, message: impl Into<Cow<'static, str>>) -> ValidationError {
+    ValidationError::new(code).with_message(message.into())
+}
+
+/// Rejects values that are empty once surrounding whitespace is trimmed.
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(invalid("required", "must not be blank"));
+    }
+    Ok(())
+}
+
+/// Rejects an explicit `null` for a field that may be left out but cannot
+/// be cleared. The field is read with `deserialize_present`, so `null`
+/// arrives as `Some(None)`.
+pub fn not_null<T>(value: &Option<Option<T>>) -> Result<(), ValidationError> {
+    if matches!(value, Some(None)) {
+        return Err(invalid("null", "must not be null"));
+    }
+    Ok(())
+}
+
+/// Letters, digits and `_`, `.`, `-`, in the shape `@mentions` recognise.
+pub fn username(username: &str) -> Result<(), ValidationError> {
+    let length = username.chars().count();
+    if !(MIN_USERNAME_CHARS..=MAX_USERNAME_CHARS).contains(&length) {
+        return Err(invalid(
+            "length",
+            format!(
+                "username must be between {} and {} characters",
+                MIN_USERNAME_CHARS, MAX_USERNAME_CHARS
+            ),
+        ));
+    }
+    let valid_chars = username
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-'));
+    let valid_edges = !username.starts_with(['.', '-']) && !username.ends_with(['.', '-']);
+    if !valid_chars || !valid_edges {
+        return Err(invalid(
+            "format",
+            "username may only contain letters, digits, '_', '.' and '-', and must start and end with a letter, digit or '_'",
+        ));
+    }
+    let lowered = username.to_ascii_lowercase();
+    if RESERVED_USERNAMES.contains(&lowered.as_str()) || lowered.starts_with(ERASED_USERNAME_PREFIX)
+    {
+        return Err(invalid("reserved", "username is reserved"));
+    }
+    Ok(())
+}
+
+/// Tags sent as a JSON array. They are stored through the same path as the
+/// form's comma-separated list, so a name may not contain a comma.
+pub fn tag_names(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.iter().any(|tag| tag.contains(',')) {
+        return Err(invalid("format", "tags may not contain commas"));
+    }
+    tag_list(&tags.join(","))
+}
+
+/// A comma-separated tag list as sent by the post forms.
+pub fn tag_list(value: &str) -> Result<(), ValidationError> {
+    let tags: Vec<&str> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    if tags.len() > MAX_POST_TAGS {
+        return Err(invalid(
+            "length",
+            format!("at most {} tags are allowed", MAX_POST_TAGS),
+        ));
+    }
+    if tags
+        .iter()
+        .any(|tag| tag.chars().count() > MAX_POST_TAG_CHARS)
+    {
+        return Err(invalid(
+            "length",
+            format!(
+                "tags must be at most {} characters each",
+                MAX_POST_TAG_CHARS
+            ),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+/// Paper version attachments live outside `uploads/`, which is served
+/// publicly, so they are only reachable through the paper versions API.
+pub const VERSION_FILES_DIR: &str = "version_files";
+
+#[derive(Debug, Clone)]
+pub struct StoredVersionFile {
+    pub path: String,
+    pub sha256: String,
+}
+
+/// Copies an attachment into the content-addressed version store. Identical
+/// files share one blob, and a blob is never rewritten once it exists, so
+/// replacing the working copy cannot change what a version points at.
+pub async fn store_version_file(source_path: &str) -> std::io::Result<StoredVersionFile> {
+    let data = tokio::fs::read(source_path).await?;
+    let sha256 = format!("{:x}", Sha256::digest(&data));
+    let blob_name = match Path::new(source_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some(ext) => format!("{}.{}", sha256, ext.to_ascii_lowercase()),
+        None => sha256.clone(),
+    };
+    let blob_path = PathBuf::from(VERSION_FILES_DIR).join(blob_name);
+
+    if !tokio::fs::try_exists(&blob_path).await? {
+        tokio::fs::create_dir_all(VERSION_FILES_DIR).await?;
+        // A crash mid-write must not leave a truncated blob under its final
+        // name, since existing blobs are trusted as-is.
+        let temp_path = PathBuf::from(VERSION_FILES_DIR).join(format!(".{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&temp_path, &data).await?;
+        tokio::fs::rename(&temp_path, &blob_path).await?;
+    }
+
+    Ok(StoredVersionFile {
+        path: blob_path.to_string_lossy().to_string(),
+        sha256,
+    })
+}
+
+/// Moves attachments of versions recorded before the version store existed
+/// into it. Versions whose file is already gone are left as they are.
+/// Returns the number of versions moved.
+pub async fn backfill_version_files(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let rows: Vec<(i64, String)> = sqlx::query_as(
+        "SELECT id, file_path FROM paper_versions WHERE file_path IS NOT NULL AND file_sha256 IS NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut moved = 0;
+    for (version_id, file_path) in rows {
+        let stored = match store_version_file(&file_path).await {
+            Ok(stored) => stored,
+            Err(error) if error.kind() == ErrorKind::NotFound => continue,
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to store attachment {} of paper version {}: {}",
+                    file_path,
+                    version_id,
+                    error
+                );
+                continue;
+            }
+        };
+        sqlx::query("UPDATE paper_versions SET file_path = ?, file_sha256 = ? WHERE id = ?")
+            .bind(&stored.path)
+            .bind(&stored.sha256)
+            .bind(version_id)
+            .execute(pool)
+            .await?;
+        moved += 1;
+    }
+
+    Ok(moved)
+}
+
+/// Blob paths referenced by the versions of `post_id`.
+pub async fn fetch_version_file_paths(
+    pool: &MySqlPool,
+    post_id: i64,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT DISTINCT file_path FROM paper_versions WHERE post_id = ? AND file_sha256 IS NOT NULL",
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Deletes the given blobs unless another version still references them.
+pub async fn remove_unreferenced_version_files(
+    pool: &MySqlPool,
+    paths: &[String],
+) -> Result<(), sqlx::Error> {
+    for path in paths {
+        let referenced = sqlx::query("SELECT id FROM paper_versions WHERE file_path = ? LIMIT 1")
+            .bind(path)
+            .fetch_optional(pool)
+            .await?;
+        if referenced.is_none() {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,363 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::Utc;
+use reqwest::{Client, StatusCode, Url, header};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair};
+use ring::{aead, agreement, hkdf};
+use serde::Serialize;
+use sqlx::{FromRow, MySqlPool};
+
+use crate::public_url::{parse_public_https_url, public_client};
+use crate::tasks;
+
+pub const DEFAULT_PUSH_TIMEOUT_SECS: u64 = 10;
+pub const DEFAULT_PUSH_TTL_SECS: u64 = 86_400;
+pub const DEFAULT_PUSH_MAX_FAILURES: i32 = 5;
+
+/// RFC 8188 record size. Notification messages are capped at 512
+/// characters, so one record always holds the whole payload.
+const RECORD_SIZE: u32 = 4_096;
+const VAPID_TOKEN_LIFETIME_SECS: i64 = 12 * 3_600;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PushMessage {
+    pub event_type: String,
+    pub message: String,
+    pub post_id: Option<i64>,
+}
+
+struct VapidConfig {
+    key_pair: EcdsaKeyPair,
+    /// The uncompressed P-256 point, base64url encoded, as browsers expect
+    /// it for `applicationServerKey`.
+    public_key: String,
+    subject: String,
+}
+
+impl VapidConfig {
+    /// `None` unless both keys and the contact subject are set and valid.
+    fn from_env() -> Option<Self> {
+        let public_key = env_value("VAPID_PUBLIC_KEY")?;
+        let private_key = env_value("VAPID_PRIVATE_KEY")?;
+        let subject = env_value("VAPID_SUBJECT")?;
+        if !subject.starts_with("mailto:") && !subject.starts_with("https://") {
+            tracing::warn!("VAPID_SUBJECT must be a mailto: or https: URL, Web Push is disabled");
+            return None;
+        }
+
+        let key_pair = URL_SAFE_NO_PAD
+            .decode(&public_key)
+            .ok()
+            .zip(URL_SAFE_NO_PAD.decode(&private_key).ok())
+            .and_then(|(public_bytes, private_bytes)| {
+                EcdsaKeyPair::from_private_key_and_public_key(
+                    &ECDSA_P256_SHA256_FIXED_SIGNING,
+                    &private_bytes,
+                    &public_bytes,
+                    &SystemRandom::new(),
+                )
+                .ok()
+            });
+        let Some(key_pair) = key_pair else {
+            tracing::warn!(
+                "VAPID keys are not a valid base64url P-256 key pair, Web Push is disabled"
+            );
+            return None;
+        };
+
+        Some(Self {
+            key_pair,
+            public_key,
+            subject,
+        })
+    }
+
+    /// The RFC 8292 `Authorization` header value for one push service.
+    fn authorization(&self, endpoint: &Url) -> anyhow::Result<String> {
+        let audience = endpoint.origin().ascii_serialization();
+        let header = URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&serde_json::json!({
+            "aud": audience,
+            "exp": Utc::now().timestamp() + VAPID_TOKEN_LIFETIME_SECS,
+            "sub": self.subject,
+        }))?);
+        let signing_input = format!("{}.{}", header, claims);
+        let signature = self
+            .key_pair
+            .sign(&SystemRandom::new(), signing_input.as_bytes())
+            .map_err(|_| anyhow!("VAPID signing failed"))?;
+
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_ref()),
+            self.public_key
+        ))
+    }
+}
+
+fn vapid_config() -> Option<&'static VapidConfig> {
+    static CONFIG: OnceLock<Option<VapidConfig>> = OnceLock::new();
+    CONFIG.get_or_init(VapidConfig::from_env).as_ref()
+}
+
+/// The key browsers subscribe with, or `None` when Web Push is not
+/// configured.
+pub fn vapid_public_key() -> Option<&'static str> {
+    vapid_config().map(|config| config.public_key.as_str())
+}
+
+/// Checks that a subscription's keys can be used to encrypt messages: a
+/// P-256 public key and a 16-byte auth secret, both base64url encoded.
+pub fn validate_subscription_keys(p256dh: &str, auth_secret: &str) -> bool {
+    let public_key_ok = URL_SAFE_NO_PAD
+        .decode(p256dh.trim_end_matches('='))
+        .is_ok_and(|bytes| bytes.len() == 65 && bytes[0] == 0x04);
+    let auth_ok = URL_SAFE_NO_PAD
+        .decode(auth_secret.trim_end_matches('='))
+        .is_ok_and(|bytes| bytes.len() == 16);
+    public_key_ok && auth_ok
+}
+
+#[derive(Debug, FromRow)]
+struct PushTarget {
+    id: i64,
+    endpoint: String,
+    p256dh: String,
+    auth_secret: String,
+}
+
+/// Sends `message` to every browser the user subscribed, in the background.
+/// Does nothing when Web Push is not configured.
+pub fn queue_push(pool: &MySqlPool, user_id: i64, message: PushMessage) {
+    let Some(config) = vapid_config() else {
+        return;
+    };
+    let pool = pool.clone();
+    tasks::spawn_task("web_push", async move {
+        if let Err(error) = send_push(&pool, config, user_id, &message).await {
+            tracing::warn!(
+                "Web Push for {} to user {} failed: {}",
+                message.event_type,
+                user_id,
+                error
+            );
+        }
+    });
+}
+
+async fn send_push(
+    pool: &MySqlPool,
+    config: &VapidConfig,
+    user_id: i64,
+    message: &PushMessage,
+) -> anyhow::Result<()> {
+    let targets = sqlx::query_as::<_, PushTarget>(
+        "SELECT id, endpoint, p256dh, auth_secret FROM push_subscriptions WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let payload = serde_json::to_vec(message)?;
+    // Endpoints come from browsers, so connections are limited to public hosts.
+    let client = public_client()
+        .timeout(Duration::from_secs(push_timeout_secs()))
+        .build()?;
+
+    for target in targets {
+        match deliver(&client, config, &target, &payload).await {
+            Ok(()) => {
+                sqlx::query(
+                    "UPDATE push_subscriptions SET last_success_at = ?, failure_count = 0 WHERE id = ?",
+                )
+                .bind(Utc::now())
+                .bind(target.id)
+                .execute(pool)
+                .await?;
+            }
+            Err(DeliveryError::Gone) => {
+                sqlx::query("DELETE FROM push_subscriptions WHERE id = ?")
+                    .bind(target.id)
+                    .execute(pool)
+                    .await?;
+            }
+            Err(DeliveryError::Failed(error)) => {
+                tracing::warn!("Web Push to subscription {} failed: {}", target.id, error);
+                sqlx::query(
+                    r#"
+                    UPDATE push_subscriptions
+                    SET last_failure_at = ?, failure_count = failure_count + 1
+                    WHERE id = ?
+                    "#,
+                )
+                .bind(Utc::now())
+                .bind(target.id)
+                .execute(pool)
+                .await?;
+                sqlx::query("DELETE FROM push_subscriptions WHERE id = ? AND failure_count >= ?")
+                    .bind(target.id)
+                    .bind(push_max_failures())
+                    .execute(pool)
+                    .await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+enum DeliveryError {
+    /// The push service no longer knows the endpoint.
+    Gone,
+    Failed(anyhow::Error),
+}
+
+impl From<anyhow::Error> for DeliveryError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Failed(error)
+    }
+}
+
+async fn deliver(
+    client: &Client,
+    config: &VapidConfig,
+    target: &PushTarget,
+    payload: &[u8],
+) -> Result<(), DeliveryError> {
+    let endpoint = parse_public_https_url(&target.endpoint)
+        .map_err(|error| anyhow!("push endpoint {}", error))?;
+    let body = encrypt_payload(&target.p256dh, &target.auth_secret, payload)?;
+    let response = client
+        .post(endpoint.clone())
+        .header(header::AUTHORIZATION, config.authorization(&endpoint)?)
+        .header(header::CONTENT_ENCODING, "aes128gcm")
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header("TTL", push_ttl_secs().to_string())
+        .header("Urgency", "high")
+        .body(body)
+        .send()
+        .await
+        .map_err(|error| DeliveryError::Failed(error.into()))?;
+
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        StatusCode::NOT_FOUND | StatusCode::GONE => Err(DeliveryError::Gone),
+        status => Err(DeliveryError::Failed(anyhow!(
+            "push service returned {}",
+            status
+        ))),
+    }
+}
+
+/// Encrypts a message for one subscription as RFC 8291 prescribes: an
+/// ephemeral ECDH key agreed with the browser's key, mixed with its auth
+/// secret, keys a single aes128gcm record (RFC 8188).
+fn encrypt_payload(p256dh: &str, auth_secret: &str, payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let ua_public = URL_SAFE_NO_PAD
+        .decode(p256dh.trim_end_matches('='))
+        .context("invalid p256dh key")?;
+    let auth_secret = URL_SAFE_NO_PAD
+        .decode(auth_secret.trim_end_matches('='))
+        .context("invalid auth secret")?;
+
+    let rng = SystemRandom::new();
+    let ephemeral = agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng)
+        .map_err(|_| anyhow!("key generation failed"))?;
+    let as_public = ephemeral
+        .compute_public_key()
+        .map_err(|_| anyhow!("key generation failed"))?;
+    let as_public = as_public.as_ref().to_vec();
+
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(&ua_public);
+    key_info.extend_from_slice(&as_public);
+    let ikm = agreement::agree_ephemeral(
+        ephemeral,
+        &agreement::UnparsedPublicKey::new(&agreement::ECDH_P256, &ua_public),
+        |shared_secret| hkdf_derive(&auth_secret, shared_secret, &key_info, 32),
+    )
+    .map_err(|_| anyhow!("key agreement failed"))??;
+
+    let mut salt = [0u8; 16];
+    rng.fill(&mut salt)
+        .map_err(|_| anyhow!("random salt failed"))?;
+    let cek = hkdf_derive(&salt, &ikm, b"Content-Encoding: aes128gcm\0", 16)?;
+    let nonce = hkdf_derive(&salt, &ikm, b"Content-Encoding: nonce\0", 12)?;
+
+    let key = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_128_GCM, &cek).map_err(|_| anyhow!("invalid key"))?,
+    );
+    let nonce =
+        aead::Nonce::try_assume_unique_for_key(&nonce).map_err(|_| anyhow!("invalid nonce"))?;
+    // The 0x02 delimiter marks the last (and only) record.
+    let mut record = payload.to_vec();
+    record.push(0x02);
+    key.seal_in_place_append_tag(nonce, aead::Aad::empty(), &mut record)
+        .map_err(|_| anyhow!("encryption failed"))?;
+
+    let mut body = Vec::with_capacity(16 + 4 + 1 + as_public.len() + record.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(&as_public);
+    body.extend_from_slice(&record);
+    Ok(body)
+}
+
+struct OutputLength(usize);
+
+impl hkdf::KeyType for OutputLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+fn hkdf_derive(salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> anyhow::Result<Vec<u8>> {
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(ikm);
+    let info = [info];
+    let okm = prk
+        .expand(&info, OutputLength(len))
+        .map_err(|_| anyhow!("HKDF expand failed"))?;
+    let mut output = vec![0u8; len];
+    okm.fill(&mut output)
+        .map_err(|_| anyhow!("HKDF expand failed"))?;
+    Ok(output)
+}
+
+fn push_timeout_secs() -> u64 {
+    std::env::var("PUSH_TIMEOUT_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_PUSH_TIMEOUT_SECS)
+}
+
+fn push_ttl_secs() -> u64 {
+    std::env::var("PUSH_TTL_SECS")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_PUSH_TTL_SECS)
+}
+
+fn push_max_failures() -> i32 {
+    std::env::var("PUSH_MAX_FAILURES")
+        .ok()
+        .and_then(|raw| raw.parse::<i32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_PUSH_MAX_FAILURES)
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
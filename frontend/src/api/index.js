@@ -1,6 +1,6 @@
 import axios from 'axios';
 
-const API_BASE_URL = '/api';
+const API_BASE_URL = '/api/v1';
 
 const api = axios.create({
     baseURL: API_BASE_URL,
@@ -165,8 +165,18 @@ export const usersAPI = {
 // Comments API
 export const commentsAPI = {
     list: async (postId) => {
-        const response = await api.get(`/posts/${postId}/comments`);
-        return response.data;
+        const comments = [];
+        let offset = 0;
+        for (;;) {
+            const response = await api.get(`/posts/${postId}/comments`, {
+                params: { limit: 200, offset },
+            });
+            const page = Array.isArray(response.data?.comments) ? response.data.comments : [];
+            comments.push(...page);
+            offset += page.length;
+            if (page.length === 0 || offset >= (response.data?.total ?? 0)) break;
+        }
+        return comments;
     },
     create: async (postId, content, parentCommentId = null) => {
         const payload = { content };
@@ -188,8 +198,10 @@ export const adminAPI = {
         const response = await api.get('/admin/stats');
         return response.data;
     },
-    getUsers: async () => {
-        const response = await api.get('/admin/users');
+    getUsers: async (params = {}) => {
+        const response = await api.get('/admin/users', {
+            params: { per_page: 100, ...params },
+        });
         return response.data;
     },
     updateUserRole: async (userId, isAdmin) => {
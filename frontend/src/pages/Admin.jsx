@@ -39,7 +39,7 @@ function Admin() {
                 adminAPI.getUsers(),
             ]);
             setStats(statsData);
-            setUsers(usersData);
+            setUsers(usersData.users || []);
             setError(null);
         } catch (err) {
             console.error('Failed to fetch admin data:', err);
@@ -395,7 +395,7 @@ function Admin() {
                                             <td>{item.scores?.overall_score ?? '-'}</td>
                                             <td>{item.trigger}</td>
                                             <td>{new Date(item.created_at).toLocaleString()}</td>
-                                            <td style={{ maxWidth: '240px' }}>{item.error_message || '-'}</td>
+                                            <td style={{ maxWidth: '240px' }} title={item.request_id ? `Request ${item.request_id}` : undefined}>{item.error_message || '-'}</td>
                                         </tr>
                                     ))}
                                 </tbody>
@@ -62,7 +62,7 @@ function Login() {
     };
 
     const handleGoogleLogin = () => {
-        window.location.href = '/api/auth/google';
+        window.location.href = '/api/v1/auth/google';
     };
 
     const toggleMode = () => {
@@ -8,6 +8,7 @@ export default defineConfig({
     proxy: {
       '/api': 'http://localhost:8000',
       '/uploads': 'http://localhost:8000',
+      '/oai': 'http://localhost:8000',
     },
   },
 })